@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+use super::rule_system::AdvancementModeDto;
+use crate::application::services::advancement_service::{AdvancementStatus, GainedLevel};
+use crate::application::services::LevelUpOutcome;
+use crate::domain::value_objects::AdvancementAward;
+
+/// Request to award experience or milestones to characters.
+#[derive(Debug, Deserialize)]
+pub struct AwardExperienceRequestDto {
+    pub kind: AdvancementModeDto,
+    pub amount: u32,
+    /// Why it was awarded, shown to the players
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl AwardExperienceRequestDto {
+    pub fn award(&self) -> AdvancementAward {
+        AdvancementAward::new(self.kind.into(), self.amount)
+    }
+}
+
+/// Where a character stands on its world's progression.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvancementDto {
+    pub pc_id: String,
+    pub name: String,
+    pub mode: AdvancementModeDto,
+    pub experience: u32,
+    pub milestones: u32,
+    pub level: u32,
+    /// The level the character's progress has earned
+    pub earned_level: u32,
+    pub can_level_up: bool,
+    /// Progress needed for the next level, if there is one
+    pub next_threshold: Option<u32>,
+}
+
+impl From<AdvancementStatus> for AdvancementDto {
+    fn from(value: AdvancementStatus) -> Self {
+        Self {
+            can_level_up: value.can_level_up(),
+            pc_id: value.pc.id.to_string(),
+            name: value.pc.name,
+            mode: value.mode.into(),
+            experience: value.pc.advancement.experience,
+            milestones: value.pc.advancement.milestones,
+            level: value.level,
+            earned_level: value.earned_level,
+            next_threshold: value.next_threshold,
+        }
+    }
+}
+
+/// One level a character gained.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GainedLevelDto {
+    pub level: u32,
+    pub unlocked_fields: Vec<String>,
+    pub unlocked_skills: Vec<String>,
+}
+
+impl From<GainedLevel> for GainedLevelDto {
+    fn from(value: GainedLevel) -> Self {
+        Self {
+            level: value.level,
+            unlocked_fields: value.unlocked_fields,
+            unlocked_skills: value.unlocked_skills,
+        }
+    }
+}
+
+/// The levels an approved level-up gave a character.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelUpDto {
+    pub pc_id: String,
+    pub name: String,
+    pub level: u32,
+    pub levels: Vec<GainedLevelDto>,
+}
+
+impl From<LevelUpOutcome> for LevelUpDto {
+    fn from(value: LevelUpOutcome) -> Self {
+        Self {
+            pc_id: value.pc.id.to_string(),
+            name: value.pc.name,
+            level: value.pc.advancement.level,
+            levels: value.levels.into_iter().map(GainedLevelDto::from).collect(),
+        }
+    }
+}
+
+// ============================================================================
+// Advancement Notification DTOs (for application layer → infrastructure)
+// ============================================================================
+
+/// Notification that a character was awarded experience or milestones
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperienceAwardedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub pc_id: String,
+    pub character_name: String,
+    /// The award as shown to players, e.g. "250 XP"
+    pub award: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub experience: u32,
+    pub milestones: u32,
+}
+
+impl ExperienceAwardedNotification {
+    pub fn new(status: &AdvancementStatus, award: AdvancementAward, reason: Option<String>) -> Self {
+        Self {
+            message_type: "ExperienceAwarded",
+            pc_id: status.pc.id.to_string(),
+            character_name: status.pc.name.clone(),
+            award: award.to_string(),
+            reason,
+            experience: status.pc.advancement.experience,
+            milestones: status.pc.advancement.milestones,
+        }
+    }
+}
+
+/// Notification to the DM that a character has earned a level to approve
+#[derive(Debug, Clone, Serialize)]
+pub struct LevelUpAvailableNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub pc_id: String,
+    pub character_name: String,
+    pub level: u32,
+    pub earned_level: u32,
+}
+
+impl LevelUpAvailableNotification {
+    pub fn new(status: &AdvancementStatus) -> Self {
+        Self {
+            message_type: "LevelUpAvailable",
+            pc_id: status.pc.id.to_string(),
+            character_name: status.pc.name.clone(),
+            level: status.level,
+            earned_level: status.earned_level,
+        }
+    }
+}
+
+/// Notification that a character gained one or more levels
+#[derive(Debug, Clone, Serialize)]
+pub struct LevelGainedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub pc_id: String,
+    pub character_name: String,
+    pub level: u32,
+    pub unlocked_fields: Vec<String>,
+    pub unlocked_skills: Vec<String>,
+}
+
+impl LevelGainedNotification {
+    pub fn new(outcome: &LevelUpOutcome) -> Self {
+        Self {
+            message_type: "LevelGained",
+            pc_id: outcome.pc.id.to_string(),
+            character_name: outcome.pc.name.clone(),
+            level: outcome.pc.advancement.level,
+            unlocked_fields: outcome.levels.iter().flat_map(|l| l.unlocked_fields.clone()).collect(),
+            unlocked_skills: outcome.levels.iter().flat_map(|l| l.unlocked_skills.clone()).collect(),
+        }
+    }
+}
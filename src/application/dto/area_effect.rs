@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::AreaOfEffect;
+use crate::domain::entities::AreaTemplate;
+
+use super::{GridPositionDto, GridTokenDto};
+
+fn default_line_width() -> u32 {
+    1
+}
+
+/// The shape of an area of effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+pub enum AreaTemplateDto {
+    Sphere {
+        center: GridPositionDto,
+        radius: u32,
+    },
+    Cube {
+        origin: GridPositionDto,
+        size: u32,
+    },
+    Cone {
+        origin: GridPositionDto,
+        toward: GridPositionDto,
+        length: u32,
+    },
+    Line {
+        origin: GridPositionDto,
+        toward: GridPositionDto,
+        length: u32,
+        #[serde(default = "default_line_width")]
+        width: u32,
+    },
+}
+
+impl From<AreaTemplateDto> for AreaTemplate {
+    fn from(value: AreaTemplateDto) -> Self {
+        match value {
+            AreaTemplateDto::Sphere { center, radius } => Self::Sphere {
+                center: center.into(),
+                radius,
+            },
+            AreaTemplateDto::Cube { origin, size } => Self::Cube {
+                origin: origin.into(),
+                size,
+            },
+            AreaTemplateDto::Cone {
+                origin,
+                toward,
+                length,
+            } => Self::Cone {
+                origin: origin.into(),
+                toward: toward.into(),
+                length,
+            },
+            AreaTemplateDto::Line {
+                origin,
+                toward,
+                length,
+                width,
+            } => Self::Line {
+                origin: origin.into(),
+                toward: toward.into(),
+                length,
+                width,
+            },
+        }
+    }
+}
+
+/// Request to set off an area of effect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AreaEffectRequestDto {
+    pub template: AreaTemplateDto,
+    /// What the effect is, e.g. "Fireball"
+    #[serde(default)]
+    pub description: String,
+    /// Saving throw challenge each player character caught must roll
+    #[serde(default)]
+    pub saving_throw_challenge_id: Option<String>,
+}
+
+/// What an area of effect caught.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AreaEffectDto {
+    pub grid_map_id: String,
+    /// Tiles caught, row by row
+    pub tiles: Vec<GridPositionDto>,
+    pub tokens: Vec<GridTokenDto>,
+    /// IDs of the player characters called on to save
+    pub saving_throws: Vec<String>,
+}
+
+impl AreaEffectDto {
+    /// The effect as a client may see it; hidden tokens only for the DM
+    pub fn new(area: &AreaOfEffect, include_hidden: bool) -> Self {
+        Self {
+            grid_map_id: area.grid_map_id.to_string(),
+            tiles: area.tiles.iter().copied().map(GridPositionDto::from).collect(),
+            tokens: area
+                .tokens
+                .iter()
+                .filter(|token| include_hidden || !token.hidden)
+                .map(GridTokenDto::from)
+                .collect(),
+            saving_throws: area.saving_throws.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+}
+
+/// Notification that an area of effect went off
+#[derive(Debug, Clone, Serialize)]
+pub struct AreaEffectNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub description: String,
+    #[serde(flatten)]
+    pub effect: AreaEffectDto,
+}
+
+impl AreaEffectNotification {
+    pub fn new(description: impl Into<String>, effect: AreaEffectDto) -> Self {
+        Self {
+            message_type: "AreaEffect",
+            description: description.into(),
+            effect,
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::domain::entities::{
-    Challenge, ChallengeOutcomes, ChallengeType, Difficulty, DifficultyDescriptor, Outcome,
-    OutcomeTrigger, TriggerCondition, TriggerType,
+    Challenge, ChallengeOutcomes, ChallengeType, ComplexChallengeProgress, ComplexChallengeRoll,
+    ComplexChallengeSettings, ComplexChallengeStatus, Difficulty, DifficultyDescriptor, Outcome,
+    OutcomeTrigger, OutcomeType, TriggerCondition, TriggerType,
 };
-use crate::domain::value_objects::{ChallengeId, SceneId};
+use crate::domain::services::ChallengeOdds;
+use crate::domain::value_objects::{ChallengeId, DiceFormula, RollMode, SceneId, SkillId};
 
 // ============================================================================
 // DTO enums + mapping
@@ -75,6 +78,9 @@ pub struct CreateChallengeRequestDto {
     pub prerequisite_challenges: Vec<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Success and failure limits for complex challenges
+    #[serde(default)]
+    pub complex_settings: Option<ComplexChallengeSettingsDto>,
 }
 
 /// Request to update a challenge
@@ -106,6 +112,8 @@ pub struct UpdateChallengeRequestDto {
     pub is_favorite: Option<bool>,
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub complex_settings: Option<ComplexChallengeSettingsDto>,
 }
 
 /// Difficulty request variants
@@ -161,6 +169,44 @@ impl From<Difficulty> for DifficultyRequestDto {
     }
 }
 
+/// Complex challenge settings
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ComplexChallengeSettingsDto {
+    pub required_successes: u32,
+    /// Failures before the challenge is failed (0 = unlimited)
+    pub max_failures: u32,
+    #[serde(default)]
+    pub flexible_skills: bool,
+    #[serde(default)]
+    pub allowed_skills: Vec<String>,
+}
+
+impl From<ComplexChallengeSettingsDto> for ComplexChallengeSettings {
+    fn from(dto: ComplexChallengeSettingsDto) -> Self {
+        Self {
+            required_successes: dto.required_successes,
+            max_failures: dto.max_failures,
+            flexible_skills: dto.flexible_skills,
+            allowed_skills: dto
+                .allowed_skills
+                .iter()
+                .filter_map(|s| Uuid::parse_str(s).ok().map(SkillId::from_uuid))
+                .collect(),
+        }
+    }
+}
+
+impl From<ComplexChallengeSettings> for ComplexChallengeSettingsDto {
+    fn from(settings: ComplexChallengeSettings) -> Self {
+        Self {
+            required_successes: settings.required_successes,
+            max_failures: settings.max_failures,
+            flexible_skills: settings.flexible_skills,
+            allowed_skills: settings.allowed_skills.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+}
+
 /// Outcomes request
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct OutcomesRequestDto {
@@ -235,6 +281,7 @@ pub enum OutcomeTriggerRequestDto {
     ModifyCharacterStat { stat: String, modifier: i32 },
     TriggerScene { scene_id: String },
     GiveItem { item_name: String, item_description: Option<String> },
+    AddReward { reward_type: String, amount: i32 },
     Custom { description: String },
 }
 
@@ -272,6 +319,9 @@ impl From<OutcomeTriggerRequestDto> for OutcomeTrigger {
                 item_name,
                 item_description,
             },
+            OutcomeTriggerRequestDto::AddReward { reward_type, amount } => {
+                OutcomeTrigger::AddReward { reward_type, amount }
+            }
             OutcomeTriggerRequestDto::Custom { description } => {
                 OutcomeTrigger::Custom { description }
             }
@@ -308,6 +358,9 @@ impl From<OutcomeTrigger> for OutcomeTriggerRequestDto {
                 item_name,
                 item_description,
             },
+            OutcomeTrigger::AddReward { reward_type, amount } => {
+                OutcomeTriggerRequestDto::AddReward { reward_type, amount }
+            }
             OutcomeTrigger::Custom { description } => OutcomeTriggerRequestDto::Custom { description },
         }
     }
@@ -440,6 +493,7 @@ pub struct ChallengeResponseDto {
     pub order: u32,
     pub is_favorite: bool,
     pub tags: Vec<String>,
+    pub complex_settings: Option<ComplexChallengeSettingsDto>,
 }
 
 impl ChallengeResponseDto {
@@ -466,6 +520,7 @@ impl ChallengeResponseDto {
             order: challenge.order,
             is_favorite: challenge.is_favorite,
             tags: challenge.tags,
+            complex_settings: challenge.complex_settings.map(Into::into),
         }
     }
 
@@ -487,6 +542,53 @@ impl ChallengeResponseDto {
             order: challenge.order,
             is_favorite: challenge.is_favorite,
             tags: challenge.tags,
+            complex_settings: challenge.complex_settings.map(Into::into),
+        }
+    }
+}
+
+// ============================================================================
+// Challenge Odds DTOs
+// ============================================================================
+
+/// Query parameters for challenge odds.
+#[derive(Debug, Deserialize)]
+pub struct ChallengeOddsQueryDto {
+    /// Player character whose skill modifier applies (unmodified roll if omitted)
+    #[serde(default)]
+    pub pc_id: Option<String>,
+}
+
+/// Chance of each outcome tier for a roll against a challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeOddsDto {
+    /// Dice formula the odds were calculated for (before the skill modifier)
+    pub formula: String,
+    /// Skill modifier applied to the roll
+    pub skill_modifier: i32,
+    pub critical_success: f64,
+    pub success: f64,
+    pub partial: f64,
+    pub failure: f64,
+    pub critical_failure: f64,
+    /// Chance of a success or critical success
+    pub success_chance: f64,
+    /// False when the odds were estimated by simulation
+    pub exact: bool,
+}
+
+impl ChallengeOddsDto {
+    pub fn new(formula: &DiceFormula, skill_modifier: i32, odds: &ChallengeOdds) -> Self {
+        Self {
+            formula: formula.display(),
+            skill_modifier,
+            critical_success: odds.critical_success,
+            success: odds.success,
+            partial: odds.partial,
+            failure: odds.failure,
+            critical_failure: odds.critical_failure,
+            success_chance: odds.success_chance(),
+            exact: odds.exact,
         }
     }
 }
@@ -510,6 +612,130 @@ pub struct AdHocOutcomesDto {
     pub critical_failure: Option<String>,
 }
 
+// ============================================================================
+// Opposed Check DTOs
+// ============================================================================
+
+/// Who a character is opposed by when the DM triggers an opposed check
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OpponentDto {
+    /// An NPC, rolled by the engine from its stat block
+    Npc {
+        character_id: String,
+        /// Modifier to use instead of the one read from the stat block
+        #[serde(default)]
+        modifier: Option<i32>,
+    },
+    /// A second player character, who rolls for themselves
+    Pc { pc_id: String },
+}
+
+/// The opposing side's roll in an opposed check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpposedRollDto {
+    /// NPC character ID or player character ID
+    pub opponent_id: String,
+    pub opponent_name: String,
+    /// Whether the opponent is an NPC (the DM may override its modifier)
+    pub is_npc: bool,
+    /// Raw die roll (before modifier)
+    pub roll: i32,
+    pub modifier: i32,
+    pub total: i32,
+    #[serde(default)]
+    pub roll_breakdown: Option<String>,
+    /// Roll log entry for the roll (None for manual results)
+    #[serde(default)]
+    pub roll_id: Option<String>,
+}
+
+// ============================================================================
+// Complex Challenge DTOs
+// ============================================================================
+
+/// One round's roll towards a complex challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexChallengeRollDto {
+    pub character_id: String,
+    pub character_name: String,
+    #[serde(default)]
+    pub skill_id: Option<String>,
+    pub total: i32,
+    pub outcome_type: OutcomeType,
+    pub rolled_at: DateTime<Utc>,
+}
+
+impl From<ComplexChallengeRollDto> for ComplexChallengeRoll {
+    fn from(dto: ComplexChallengeRollDto) -> Self {
+        Self {
+            character_id: dto.character_id,
+            character_name: dto.character_name,
+            skill_id: dto
+                .skill_id
+                .and_then(|s| Uuid::parse_str(&s).ok().map(SkillId::from_uuid)),
+            total: dto.total,
+            outcome_type: dto.outcome_type,
+            rolled_at: dto.rolled_at,
+        }
+    }
+}
+
+impl From<ComplexChallengeRoll> for ComplexChallengeRollDto {
+    fn from(roll: ComplexChallengeRoll) -> Self {
+        Self {
+            character_id: roll.character_id,
+            character_name: roll.character_name,
+            skill_id: roll.skill_id.map(|id| id.to_string()),
+            total: roll.total,
+            outcome_type: roll.outcome_type,
+            rolled_at: roll.rolled_at,
+        }
+    }
+}
+
+/// Progress on a complex challenge, alongside the limits it is working towards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplexChallengeProgressDto {
+    pub challenge_id: String,
+    pub challenge_name: String,
+    pub successes: u32,
+    pub required_successes: u32,
+    pub failures: u32,
+    /// Failures before the challenge is failed (0 = unlimited)
+    pub max_failures: u32,
+    pub status: ComplexChallengeStatus,
+    /// Skills players may choose from each round (empty = any skill when flexible)
+    pub flexible_skills: bool,
+    pub allowed_skills: Vec<String>,
+    pub rolls: Vec<ComplexChallengeRollDto>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ComplexChallengeProgressDto {
+    pub fn new(
+        challenge: &Challenge,
+        settings: &ComplexChallengeSettings,
+        progress: ComplexChallengeProgress,
+    ) -> Self {
+        Self {
+            challenge_id: challenge.id.to_string(),
+            challenge_name: challenge.name.clone(),
+            successes: progress.successes,
+            required_successes: settings.required_successes,
+            failures: progress.failures,
+            max_failures: settings.max_failures,
+            status: progress.status,
+            flexible_skills: settings.flexible_skills,
+            allowed_skills: settings.allowed_skills.iter().map(|id| id.to_string()).collect(),
+            rolls: progress.rolls.into_iter().map(Into::into).collect(),
+            started_at: progress.started_at,
+            updated_at: progress.updated_at,
+        }
+    }
+}
+
 // ============================================================================
 // Challenge Outcome Approval DTOs (P3.3)
 // ============================================================================
@@ -544,6 +770,12 @@ pub struct PendingChallengeResolutionDto {
     pub total: i32,
     /// Determined outcome type (e.g., "Success", "Critical Failure")
     pub outcome_type: String,
+    /// Outcome tier the rule system's bands graded the roll into
+    #[serde(default)]
+    pub outcome_tier: Option<OutcomeType>,
+    /// How far the roll beat (positive) or missed (negative) the success line
+    #[serde(default)]
+    pub margin: Option<i32>,
     /// The pre-defined outcome description from the challenge
     pub outcome_description: String,
     /// Triggers that will execute when this outcome is applied
@@ -554,6 +786,12 @@ pub struct PendingChallengeResolutionDto {
     /// Individual die rolls
     #[serde(default)]
     pub individual_rolls: Option<Vec<i32>>,
+    /// Roll log entry for the roll (None for manual results)
+    #[serde(default)]
+    pub roll_id: Option<String>,
+    /// The opponent's roll for opposed checks
+    #[serde(default)]
+    pub opposed: Option<OpposedRollDto>,
     /// When the roll was submitted
     pub timestamp: String,
 }
@@ -575,6 +813,15 @@ pub enum ChallengeOutcomeDecision {
         #[serde(default)]
         guidance: Option<String>,
     },
+    /// Grant a roll mode (e.g. advantage) after the roll; the extra dice are
+    /// rolled and the outcome re-evaluated for approval
+    GrantRollMode {
+        roll_mode: RollMode,
+    },
+    /// Replace the NPC opponent's modifier in an opposed check and re-evaluate
+    SetOpponentModifier {
+        modifier: i32,
+    },
 }
 
 /// Request to approve/modify a challenge outcome
@@ -639,14 +886,23 @@ pub struct ChallengeResolvedNotification {
     pub modifier: i32,
     pub total: i32,
     pub outcome: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome_type: Option<OutcomeType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin: Option<i32>,
     pub outcome_description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roll_breakdown: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub individual_rolls: Option<Vec<i32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roll_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opposed: Option<OpposedRollDto>,
 }
 
 impl ChallengeResolvedNotification {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         challenge_id: String,
         challenge_name: String,
@@ -655,9 +911,13 @@ impl ChallengeResolvedNotification {
         modifier: i32,
         total: i32,
         outcome: String,
+        outcome_type: Option<OutcomeType>,
+        margin: Option<i32>,
         outcome_description: String,
         roll_breakdown: Option<String>,
         individual_rolls: Option<Vec<i32>>,
+        roll_id: Option<String>,
+        opposed: Option<OpposedRollDto>,
     ) -> Self {
         Self {
             message_type: "ChallengeResolved",
@@ -668,9 +928,13 @@ impl ChallengeResolvedNotification {
             modifier,
             total,
             outcome,
+            outcome_type,
+            margin,
             outcome_description,
             roll_breakdown,
             individual_rolls,
+            roll_id,
+            opposed,
         }
     }
 }
@@ -687,6 +951,9 @@ pub struct ChallengeRollSubmittedNotification {
     pub total: i32,
     pub outcome_type: String,
     pub status: String,
+    /// Roll log entry the DM can use to verify the roll
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roll_id: Option<String>,
 }
 
 impl ChallengeRollSubmittedNotification {
@@ -697,6 +964,7 @@ impl ChallengeRollSubmittedNotification {
         modifier: i32,
         total: i32,
         outcome_type: String,
+        roll_id: Option<String>,
     ) -> Self {
         Self {
             message_type: "ChallengeRollSubmitted",
@@ -707,6 +975,7 @@ impl ChallengeRollSubmittedNotification {
             total,
             outcome_type,
             status: "awaiting_dm_approval".to_string(),
+            roll_id,
         }
     }
 }
@@ -729,6 +998,10 @@ pub struct ChallengeOutcomePendingNotification {
     pub outcome_triggers: Vec<crate::domain::value_objects::ProposedToolInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roll_breakdown: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roll_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opposed: Option<OpposedRollDto>,
 }
 
 impl ChallengeOutcomePendingNotification {
@@ -746,6 +1019,8 @@ impl ChallengeOutcomePendingNotification {
         outcome_description: String,
         outcome_triggers: Vec<crate::domain::value_objects::ProposedToolInfo>,
         roll_breakdown: Option<String>,
+        roll_id: Option<String>,
+        opposed: Option<OpposedRollDto>,
     ) -> Self {
         Self {
             message_type: "ChallengeOutcomePending",
@@ -761,6 +1036,8 @@ impl ChallengeOutcomePendingNotification {
             outcome_description,
             outcome_triggers,
             roll_breakdown,
+            roll_id,
+            opposed,
         }
     }
 }
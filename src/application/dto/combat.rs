@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::application::services::{CombatNpc, StartCombat};
+use crate::domain::entities::{
+    CombatOutcome, Combatant, CombatantRef, CombatantStatus, CombatSide, Encounter,
+};
+use crate::domain::value_objects::{CharacterId, LocationId, PlayerCharacterId};
+
+/// Which side a combatant fights on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CombatSideDto {
+    Party,
+    #[default]
+    Enemy,
+}
+
+impl From<CombatSide> for CombatSideDto {
+    fn from(value: CombatSide) -> Self {
+        match value {
+            CombatSide::Party => Self::Party,
+            CombatSide::Enemy => Self::Enemy,
+        }
+    }
+}
+
+impl From<CombatSideDto> for CombatSide {
+    fn from(value: CombatSideDto) -> Self {
+        match value {
+            CombatSideDto::Party => Self::Party,
+            CombatSideDto::Enemy => Self::Enemy,
+        }
+    }
+}
+
+/// How a fight ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CombatOutcomeDto {
+    Victory,
+    Defeat,
+    Fled,
+    Negotiated,
+    Draw,
+    Interrupted,
+}
+
+impl From<CombatOutcome> for CombatOutcomeDto {
+    fn from(value: CombatOutcome) -> Self {
+        match value {
+            CombatOutcome::Victory => Self::Victory,
+            CombatOutcome::Defeat => Self::Defeat,
+            CombatOutcome::Fled => Self::Fled,
+            CombatOutcome::Negotiated => Self::Negotiated,
+            CombatOutcome::Draw => Self::Draw,
+            CombatOutcome::Interrupted => Self::Interrupted,
+        }
+    }
+}
+
+impl From<CombatOutcomeDto> for CombatOutcome {
+    fn from(value: CombatOutcomeDto) -> Self {
+        match value {
+            CombatOutcomeDto::Victory => Self::Victory,
+            CombatOutcomeDto::Defeat => Self::Defeat,
+            CombatOutcomeDto::Fled => Self::Fled,
+            CombatOutcomeDto::Negotiated => Self::Negotiated,
+            CombatOutcomeDto::Draw => Self::Draw,
+            CombatOutcomeDto::Interrupted => Self::Interrupted,
+        }
+    }
+}
+
+/// An NPC brought into a fight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombatNpcRequestDto {
+    pub character_id: String,
+    #[serde(default)]
+    pub side: CombatSideDto,
+    /// Whether the LLM proposes the NPC's turns for the DM to approve
+    #[serde(default = "default_true")]
+    pub llm_controlled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Request to start a fight in a session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartCombatRequestDto {
+    #[serde(default)]
+    pub description: String,
+    /// Where the fight happens; the first player character's location if
+    /// not given
+    #[serde(default)]
+    pub location_id: Option<String>,
+    /// Player characters who fight; everyone in the session if empty
+    #[serde(default)]
+    pub pc_ids: Vec<String>,
+    #[serde(default)]
+    pub npcs: Vec<CombatNpcRequestDto>,
+}
+
+impl StartCombatRequestDto {
+    /// The fight to start; fails on a malformed ID
+    pub fn into_start(self) -> Result<StartCombat, uuid::Error> {
+        Ok(StartCombat {
+            description: self.description,
+            location_id: self
+                .location_id
+                .map(|id| Uuid::parse_str(&id).map(LocationId::from_uuid))
+                .transpose()?,
+            pc_ids: self
+                .pc_ids
+                .iter()
+                .map(|id| Uuid::parse_str(id).map(PlayerCharacterId::from_uuid))
+                .collect::<Result<_, _>>()?,
+            npcs: self
+                .npcs
+                .into_iter()
+                .map(|npc| {
+                    Ok(CombatNpc {
+                        character_id: CharacterId::from_uuid(Uuid::parse_str(&npc.character_id)?),
+                        side: npc.side.into(),
+                        llm_controlled: npc.llm_controlled,
+                    })
+                })
+                .collect::<Result<_, uuid::Error>>()?,
+        })
+    }
+}
+
+/// Request to roll, or record a rolled, initiative
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct InitiativeRequestDto {
+    /// The combatant to roll for; everyone still waiting if not given
+    #[serde(default)]
+    pub combatant_id: Option<String>,
+    /// A total rolled at the table, modifier included
+    #[serde(default)]
+    pub total: Option<i32>,
+}
+
+/// Request to damage (or, with a negative amount, heal) a combatant
+#[derive(Debug, Clone, Deserialize)]
+pub struct DamageCombatantRequestDto {
+    pub amount: i32,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Request to end a fight
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EndCombatRequestDto {
+    /// How it ended; worked out from who is left standing if not given
+    #[serde(default)]
+    pub outcome: Option<CombatOutcomeDto>,
+}
+
+/// One character in a fight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombatantDto {
+    /// Player character or NPC ID
+    pub id: String,
+    /// "pc" or "npc"
+    pub kind: String,
+    pub name: String,
+    pub side: CombatSideDto,
+    pub initiative: Option<i32>,
+    pub initiative_modifier: i32,
+    pub current_hp: Option<i32>,
+    pub max_hp: Option<i32>,
+    /// "active", "delaying", "readied", "defeated" or "fled"
+    pub status: String,
+    /// What a readied action waits for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readied_trigger: Option<String>,
+    pub llm_controlled: bool,
+}
+
+impl From<&Combatant> for CombatantDto {
+    fn from(value: &Combatant) -> Self {
+        let (status, readied_trigger) = match &value.status {
+            CombatantStatus::Active => ("active", None),
+            CombatantStatus::Delaying => ("delaying", None),
+            CombatantStatus::Readied(trigger) => ("readied", Some(trigger.clone())),
+            CombatantStatus::Defeated => ("defeated", None),
+            CombatantStatus::Fled => ("fled", None),
+        };
+        Self {
+            id: value.who.to_string(),
+            kind: match value.who {
+                CombatantRef::Pc(_) => "pc",
+                CombatantRef::Npc(_) => "npc",
+            }
+            .to_string(),
+            name: value.name.clone(),
+            side: value.side.into(),
+            initiative: value.initiative,
+            initiative_modifier: value.initiative_modifier,
+            current_hp: value.current_hp,
+            max_hp: value.max_hp,
+            status: status.to_string(),
+            readied_trigger,
+            llm_controlled: value.llm_controlled,
+        }
+    }
+}
+
+/// A fight in a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterDto {
+    pub session_id: String,
+    pub location_id: String,
+    pub description: String,
+    /// 0 while initiative is being rolled
+    pub round: u32,
+    /// ID of the combatant whose turn it is
+    pub current_turn: Option<String>,
+    /// In turn order once rounds begin
+    pub combatants: Vec<CombatantDto>,
+    pub started_at: String,
+}
+
+impl From<&Encounter> for EncounterDto {
+    fn from(value: &Encounter) -> Self {
+        Self {
+            session_id: value.session_id.to_string(),
+            location_id: value.location_id.to_string(),
+            description: value.description.clone(),
+            round: value.round,
+            current_turn: value.current().map(|c| c.who.to_string()),
+            combatants: value.combatants.iter().map(CombatantDto::from).collect(),
+            started_at: value.started_at.to_rfc3339(),
+        }
+    }
+}
+
+// ============================================================================
+// Combat Notification DTOs (for application layer → infrastructure)
+// ============================================================================
+
+/// Notification that a fight started, or its roster changed
+#[derive(Debug, Clone, Serialize)]
+pub struct CombatUpdatedNotification {
+    /// "CombatStarted" or "CombatUpdated"
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub encounter: EncounterDto,
+}
+
+impl CombatUpdatedNotification {
+    pub fn started(encounter: &Encounter) -> Self {
+        Self {
+            message_type: "CombatStarted",
+            encounter: encounter.into(),
+        }
+    }
+
+    pub fn updated(encounter: &Encounter) -> Self {
+        Self {
+            message_type: "CombatUpdated",
+            encounter: encounter.into(),
+        }
+    }
+}
+
+/// Notification that the turn passed to another combatant
+#[derive(Debug, Clone, Serialize)]
+pub struct CombatTurnChangedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub round: u32,
+    pub combatant_id: String,
+    pub combatant_name: String,
+    /// Whether this turn began a new round
+    pub new_round: bool,
+    pub encounter: EncounterDto,
+}
+
+impl CombatTurnChangedNotification {
+    pub fn new(encounter: &Encounter, current: &Combatant, new_round: bool) -> Self {
+        Self {
+            message_type: "CombatTurnChanged",
+            round: encounter.round,
+            combatant_id: current.who.to_string(),
+            combatant_name: current.name.clone(),
+            new_round,
+            encounter: encounter.into(),
+        }
+    }
+}
+
+/// Notification that a combatant fell or fled
+#[derive(Debug, Clone, Serialize)]
+pub struct CombatantDefeatedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub combatant_id: String,
+    pub combatant_name: String,
+    pub fled: bool,
+}
+
+impl CombatantDefeatedNotification {
+    pub fn new(combatant: &Combatant) -> Self {
+        Self {
+            message_type: "CombatantDefeated",
+            combatant_id: combatant.who.to_string(),
+            combatant_name: combatant.name.clone(),
+            fled: combatant.status == CombatantStatus::Fled,
+        }
+    }
+}
+
+/// Notification that a fight ended
+#[derive(Debug, Clone, Serialize)]
+pub struct CombatEndedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<CombatOutcomeDto>,
+    pub rounds: u32,
+}
+
+impl CombatEndedNotification {
+    pub fn new(outcome: Option<CombatOutcome>, rounds: u32) -> Self {
+        Self {
+            message_type: "CombatEnded",
+            outcome: outcome.map(Into::into),
+            rounds,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::EndedCondition;
+use crate::domain::value_objects::{ActiveCondition, ConditionDuration};
+
+/// How long a condition lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConditionDurationDto {
+    Rounds { rounds: u32 },
+    GameTime { minutes: u32 },
+    UntilRest,
+    Permanent,
+}
+
+impl From<ConditionDuration> for ConditionDurationDto {
+    fn from(value: ConditionDuration) -> Self {
+        match value {
+            ConditionDuration::Rounds(rounds) => Self::Rounds { rounds },
+            ConditionDuration::Minutes(minutes) => Self::GameTime { minutes },
+            ConditionDuration::UntilRest => Self::UntilRest,
+            ConditionDuration::Permanent => Self::Permanent,
+        }
+    }
+}
+
+impl From<ConditionDurationDto> for ConditionDuration {
+    fn from(value: ConditionDurationDto) -> Self {
+        match value {
+            ConditionDurationDto::Rounds { rounds } => Self::Rounds(rounds),
+            ConditionDurationDto::GameTime { minutes } => Self::Minutes(minutes),
+            ConditionDurationDto::UntilRest => Self::UntilRest,
+            ConditionDurationDto::Permanent => Self::Permanent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveConditionDto {
+    pub name: String,
+    pub description: String,
+    pub duration: ConditionDurationDto,
+    pub roll_modifier: i32,
+    /// Game time the condition was applied (RFC 3339)
+    pub applied_at: String,
+    /// Game time a timed condition runs out (RFC 3339)
+    pub expires_at: Option<String>,
+}
+
+impl From<ActiveCondition> for ActiveConditionDto {
+    fn from(value: ActiveCondition) -> Self {
+        Self {
+            expires_at: value.expires_at().map(|t| t.to_rfc3339()),
+            name: value.name,
+            description: value.description,
+            duration: value.duration.into(),
+            roll_modifier: value.roll_modifier,
+            applied_at: value.applied_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Request to apply a condition to an NPC or player character.
+#[derive(Debug, Deserialize)]
+pub struct ApplyConditionRequestDto {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub duration: ConditionDurationDto,
+    /// Added to the character's challenge rolls while the condition lasts
+    #[serde(default)]
+    pub roll_modifier: i32,
+}
+
+impl ApplyConditionRequestDto {
+    /// Build the condition, starting it at the given game time
+    pub fn into_condition(self, applied_at: DateTime<Utc>) -> ActiveCondition {
+        ActiveCondition::new(self.name, self.duration.into(), applied_at)
+            .with_description(self.description)
+            .with_roll_modifier(self.roll_modifier)
+    }
+}
+
+/// A condition that ended on a character.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndedConditionDto {
+    pub character_id: String,
+    pub character_name: String,
+    pub condition: ActiveConditionDto,
+}
+
+impl From<EndedCondition> for EndedConditionDto {
+    fn from(value: EndedCondition) -> Self {
+        Self {
+            character_id: value.character_id,
+            character_name: value.character_name,
+            condition: value.condition.into(),
+        }
+    }
+}
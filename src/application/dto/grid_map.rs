@@ -0,0 +1,659 @@
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::{GeneratedGridMap, ImportedDoor, ImportedGridMap};
+use crate::domain::entities::{
+    CaveSettings, CharacterSight, Cover, DungeonSettings, GridMap, GridPath, GridPosition,
+    MapLight, MapStyle, OutdoorSettings, ReachableTile, TerrainType, Tile, TilePalette,
+};
+
+use super::{GalleryAssetResponseDto, RegionResponseDto};
+use crate::domain::value_objects::{DiagonalRule, PlayerCharacterId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerrainTypeDto {
+    #[default]
+    Ground,
+    Water,
+    Wall,
+    Difficult,
+    Hazard,
+    Pit,
+}
+
+impl From<TerrainTypeDto> for TerrainType {
+    fn from(value: TerrainTypeDto) -> Self {
+        match value {
+            TerrainTypeDto::Ground => TerrainType::Ground,
+            TerrainTypeDto::Water => TerrainType::Water,
+            TerrainTypeDto::Wall => TerrainType::Wall,
+            TerrainTypeDto::Difficult => TerrainType::Difficult,
+            TerrainTypeDto::Hazard => TerrainType::Hazard,
+            TerrainTypeDto::Pit => TerrainType::Pit,
+        }
+    }
+}
+
+impl From<TerrainType> for TerrainTypeDto {
+    fn from(value: TerrainType) -> Self {
+        match value {
+            TerrainType::Ground => TerrainTypeDto::Ground,
+            TerrainType::Water => TerrainTypeDto::Water,
+            TerrainType::Wall => TerrainTypeDto::Wall,
+            TerrainType::Difficult => TerrainTypeDto::Difficult,
+            TerrainType::Hazard => TerrainTypeDto::Hazard,
+            TerrainType::Pit => TerrainTypeDto::Pit,
+        }
+    }
+}
+
+/// One tile of a grid map.
+///
+/// Passability and cover default to the terrain's when not given.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TileDto {
+    #[serde(default)]
+    pub terrain_type: TerrainTypeDto,
+    #[serde(default)]
+    pub elevation: i32,
+    #[serde(default)]
+    pub tile_index: u32,
+    #[serde(default)]
+    pub passable: Option<bool>,
+    #[serde(default)]
+    pub cover_value: Option<u8>,
+}
+
+impl From<&Tile> for TileDto {
+    fn from(value: &Tile) -> Self {
+        Self {
+            terrain_type: value.terrain_type.into(),
+            elevation: value.elevation,
+            tile_index: value.tile_index,
+            passable: Some(value.passable),
+            cover_value: Some(value.cover_value),
+        }
+    }
+}
+
+impl From<TileDto> for Tile {
+    fn from(value: TileDto) -> Self {
+        let tile = Tile::new(value.terrain_type.into(), value.tile_index)
+            .with_elevation(value.elevation);
+        Tile {
+            passable: value.passable.unwrap_or(tile.passable),
+            cover_value: value.cover_value.unwrap_or(tile.cover_value),
+            ..tile
+        }
+    }
+}
+
+/// A tactical grid map, tiles given row by row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridMapDto {
+    pub id: String,
+    pub world_id: String,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub tilesheet_asset: String,
+    pub tile_size: u32,
+    pub tiles: Vec<Vec<TileDto>>,
+}
+
+impl From<&GridMap> for GridMapDto {
+    fn from(value: &GridMap) -> Self {
+        Self {
+            id: value.id.to_string(),
+            world_id: value.world_id.to_string(),
+            name: value.name.clone(),
+            width: value.width,
+            height: value.height,
+            tilesheet_asset: value.tilesheet_asset.clone(),
+            tile_size: value.tile_size,
+            tiles: value
+                .tiles
+                .iter()
+                .map(|row| row.iter().map(TileDto::from).collect())
+                .collect(),
+        }
+    }
+}
+
+impl From<GridMap> for GridMapDto {
+    fn from(value: GridMap) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// A grid map's size and name, without its tiles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridMapSummaryDto {
+    pub id: String,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<GridMap> for GridMapSummaryDto {
+    fn from(value: GridMap) -> Self {
+        Self {
+            id: value.id.to_string(),
+            name: value.name,
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
+
+/// Request to create a grid map.
+#[derive(Debug, Deserialize)]
+pub struct CreateGridMapRequestDto {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub tilesheet_asset: String,
+    #[serde(default)]
+    pub tile_size: Option<u32>,
+    /// Every tile, row by row; all ground if not given
+    #[serde(default)]
+    pub tiles: Option<Vec<Vec<TileDto>>>,
+}
+
+/// Request to update a grid map.
+///
+/// Resizing keeps the tiles that still fit; `tiles`, if given, must match
+/// the new size.
+#[derive(Debug, Deserialize)]
+pub struct UpdateGridMapRequestDto {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub tilesheet_asset: Option<String>,
+    #[serde(default)]
+    pub tile_size: Option<u32>,
+    #[serde(default)]
+    pub tiles: Option<Vec<Vec<TileDto>>>,
+}
+
+/// How to lay out a generated map; settings not given take the style's
+/// defaults.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MapStyleDto {
+    Dungeon {
+        #[serde(default)]
+        max_rooms: Option<u32>,
+        #[serde(default)]
+        min_room_size: Option<u32>,
+        #[serde(default)]
+        max_room_size: Option<u32>,
+    },
+    Cave {
+        #[serde(default)]
+        fill_percent: Option<u32>,
+        #[serde(default)]
+        smoothing_passes: Option<u32>,
+    },
+    Outdoor {
+        #[serde(default)]
+        hills: Option<u32>,
+        #[serde(default)]
+        ponds: Option<u32>,
+        #[serde(default)]
+        tree_percent: Option<u32>,
+        #[serde(default)]
+        rock_percent: Option<u32>,
+    },
+}
+
+impl From<MapStyleDto> for MapStyle {
+    fn from(value: MapStyleDto) -> Self {
+        match value {
+            MapStyleDto::Dungeon {
+                max_rooms,
+                min_room_size,
+                max_room_size,
+            } => {
+                let defaults = DungeonSettings::default();
+                MapStyle::Dungeon(DungeonSettings {
+                    max_rooms: max_rooms.unwrap_or(defaults.max_rooms),
+                    min_room_size: min_room_size.unwrap_or(defaults.min_room_size),
+                    max_room_size: max_room_size.unwrap_or(defaults.max_room_size),
+                })
+            }
+            MapStyleDto::Cave {
+                fill_percent,
+                smoothing_passes,
+            } => {
+                let defaults = CaveSettings::default();
+                MapStyle::Cave(CaveSettings {
+                    fill_percent: fill_percent.unwrap_or(defaults.fill_percent),
+                    smoothing_passes: smoothing_passes.unwrap_or(defaults.smoothing_passes),
+                })
+            }
+            MapStyleDto::Outdoor {
+                hills,
+                ponds,
+                tree_percent,
+                rock_percent,
+            } => {
+                let defaults = OutdoorSettings::default();
+                MapStyle::Outdoor(OutdoorSettings {
+                    hills: hills.unwrap_or(defaults.hills),
+                    ponds: ponds.unwrap_or(defaults.ponds),
+                    tree_percent: tree_percent.unwrap_or(defaults.tree_percent),
+                    rock_percent: rock_percent.unwrap_or(defaults.rock_percent),
+                })
+            }
+        }
+    }
+}
+
+/// Tilesheet index for each kind of terrain in a generated map; any not
+/// given keep the default palette's.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TilePaletteDto {
+    #[serde(default)]
+    pub ground: Option<u32>,
+    #[serde(default)]
+    pub wall: Option<u32>,
+    #[serde(default)]
+    pub water: Option<u32>,
+    #[serde(default)]
+    pub difficult: Option<u32>,
+    #[serde(default)]
+    pub hazard: Option<u32>,
+    #[serde(default)]
+    pub pit: Option<u32>,
+}
+
+impl From<TilePaletteDto> for TilePalette {
+    fn from(value: TilePaletteDto) -> Self {
+        let palette = TilePalette::default();
+        Self {
+            ground: value.ground.unwrap_or(palette.ground),
+            wall: value.wall.unwrap_or(palette.wall),
+            water: value.water.unwrap_or(palette.water),
+            difficult: value.difficult.unwrap_or(palette.difficult),
+            hazard: value.hazard.unwrap_or(palette.hazard),
+            pit: value.pit.unwrap_or(palette.pit),
+        }
+    }
+}
+
+/// Request to generate a grid map.
+#[derive(Debug, Deserialize)]
+pub struct GenerateGridMapRequestDto {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub tilesheet_asset: String,
+    #[serde(default)]
+    pub tile_size: Option<u32>,
+    pub style: MapStyleDto,
+    #[serde(default)]
+    pub palette: TilePaletteDto,
+    /// Seed, as a string since it may not fit in a JSON number; a random
+    /// one if not given
+    #[serde(default)]
+    pub seed: Option<String>,
+    /// Location the map becomes the tactical map of
+    #[serde(default)]
+    pub location_id: Option<String>,
+    /// Whether each room becomes a region of the location
+    #[serde(default)]
+    pub rooms_as_regions: bool,
+}
+
+/// A generated grid map, the seed that rebuilds it and any regions made
+/// from its rooms.
+#[derive(Debug, Serialize)]
+pub struct GeneratedGridMapDto {
+    pub seed: String,
+    pub grid_map: GridMapDto,
+    pub regions: Vec<RegionResponseDto>,
+}
+
+impl GeneratedGridMapDto {
+    pub fn new(seed: u64, generated: GeneratedGridMap) -> Self {
+        Self {
+            seed: seed.to_string(),
+            grid_map: GridMapDto::from(generated.grid_map),
+            regions: generated.regions.into_iter().map(RegionResponseDto::from).collect(),
+        }
+    }
+}
+
+/// Where an imported map goes, given alongside the map file.
+#[derive(Debug, Deserialize)]
+pub struct ImportGridMapQueryDto {
+    pub name: String,
+    #[serde(default)]
+    pub tilesheet_asset: Option<String>,
+    /// Location the map becomes the tactical map of, its areas becoming
+    /// regions and its image a backdrop
+    #[serde(default)]
+    pub location_id: Option<String>,
+}
+
+/// A door of an imported map and the connection made from it.
+#[derive(Debug, Serialize)]
+pub struct ImportedDoorDto {
+    pub x: u32,
+    pub y: u32,
+    pub locked: bool,
+    /// Regions either side, if the door joins two
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_region_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_region_id: Option<String>,
+}
+
+impl From<ImportedDoor> for ImportedDoorDto {
+    fn from(imported: ImportedDoor) -> Self {
+        let (x, y) = imported.door.position;
+        Self {
+            x,
+            y,
+            locked: imported.door.locked,
+            from_region_id: imported.connection.as_ref().map(|c| c.from_region.to_string()),
+            to_region_id: imported.connection.as_ref().map(|c| c.to_region.to_string()),
+        }
+    }
+}
+
+/// A light of an imported map, in tiles.
+#[derive(Debug, Serialize)]
+pub struct MapLightDto {
+    pub x: f64,
+    pub y: f64,
+    pub range: f64,
+    pub color: String,
+}
+
+impl From<MapLight> for MapLightDto {
+    fn from(light: MapLight) -> Self {
+        Self {
+            x: light.x,
+            y: light.y,
+            range: light.range,
+            color: light.color,
+        }
+    }
+}
+
+/// An imported grid map and what was made from it.
+#[derive(Debug, Serialize)]
+pub struct ImportedGridMapDto {
+    pub grid_map: GridMapDto,
+    pub regions: Vec<RegionResponseDto>,
+    pub doors: Vec<ImportedDoorDto>,
+    pub lights: Vec<MapLightDto>,
+    pub backdrop: Option<GalleryAssetResponseDto>,
+}
+
+impl From<ImportedGridMap> for ImportedGridMapDto {
+    fn from(imported: ImportedGridMap) -> Self {
+        Self {
+            grid_map: GridMapDto::from(imported.grid_map),
+            regions: imported.regions.into_iter().map(RegionResponseDto::from).collect(),
+            doors: imported.doors.into_iter().map(ImportedDoorDto::from).collect(),
+            lights: imported.lights.into_iter().map(MapLightDto::from).collect(),
+            backdrop: imported.backdrop.map(GalleryAssetResponseDto::from),
+        }
+    }
+}
+
+/// A single tile to replace.
+#[derive(Debug, Deserialize)]
+pub struct TilePatchDto {
+    pub x: u32,
+    pub y: u32,
+    #[serde(flatten)]
+    pub tile: TileDto,
+}
+
+/// Request to replace some of a grid map's tiles.
+#[derive(Debug, Deserialize)]
+pub struct PatchTilesRequestDto {
+    pub tiles: Vec<TilePatchDto>,
+}
+
+/// Request to give a location a tactical map.
+#[derive(Debug, Deserialize)]
+pub struct SetGridMapRequestDto {
+    pub grid_map_id: String,
+}
+
+/// How diagonal steps are counted when moving across a grid map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagonalRuleDto {
+    /// No diagonal steps
+    Forbidden,
+    /// 5-5-5
+    Uniform,
+    /// 5-10-5
+    Alternating,
+}
+
+impl From<DiagonalRuleDto> for DiagonalRule {
+    fn from(value: DiagonalRuleDto) -> Self {
+        match value {
+            DiagonalRuleDto::Forbidden => DiagonalRule::Forbidden,
+            DiagonalRuleDto::Uniform => DiagonalRule::Uniform,
+            DiagonalRuleDto::Alternating => DiagonalRule::Alternating,
+        }
+    }
+}
+
+/// A tile on a grid map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GridPositionDto {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl From<GridPosition> for GridPositionDto {
+    fn from((x, y): GridPosition) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<GridPositionDto> for GridPosition {
+    fn from(value: GridPositionDto) -> Self {
+        (value.x, value.y)
+    }
+}
+
+/// Request for the cheapest route between two tiles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathRequestDto {
+    pub from: GridPositionDto,
+    pub to: GridPositionDto,
+    /// The world's rule system's if not given
+    #[serde(default)]
+    pub diagonal_rule: Option<DiagonalRuleDto>,
+    /// Tiles that cannot be entered, such as other combatants'
+    #[serde(default)]
+    pub occupied: Vec<GridPositionDto>,
+}
+
+/// Request for the tiles reachable from a tile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovementRangeRequestDto {
+    pub from: GridPositionDto,
+    /// Movement to spend, in tiles of open ground
+    pub movement: u32,
+    #[serde(default)]
+    pub diagonal_rule: Option<DiagonalRuleDto>,
+    #[serde(default)]
+    pub occupied: Vec<GridPositionDto>,
+}
+
+/// A route across a grid map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridPathDto {
+    /// Every tile on the route, start and end included
+    pub steps: Vec<GridPositionDto>,
+    pub cost: u32,
+}
+
+impl From<GridPath> for GridPathDto {
+    fn from(value: GridPath) -> Self {
+        Self {
+            steps: value.steps.into_iter().map(GridPositionDto::from).collect(),
+            cost: value.cost,
+        }
+    }
+}
+
+/// A tile within movement range, and the least it costs to reach.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReachableTileDto {
+    pub x: u32,
+    pub y: u32,
+    pub cost: u32,
+}
+
+impl From<ReachableTile> for ReachableTileDto {
+    fn from(value: ReachableTile) -> Self {
+        Self {
+            x: value.position.0,
+            y: value.position.1,
+            cost: value.cost,
+        }
+    }
+}
+
+/// How well a target is shielded from an attacker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverDto {
+    None,
+    Light,
+    Heavy,
+    /// No line of sight at all
+    Total,
+}
+
+impl From<Cover> for CoverDto {
+    fn from(value: Cover) -> Self {
+        match value {
+            Cover::None => CoverDto::None,
+            Cover::Light => CoverDto::Light,
+            Cover::Heavy => CoverDto::Heavy,
+            Cover::Total => CoverDto::Total,
+        }
+    }
+}
+
+/// Request for the cover a target has from an attacker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverRequestDto {
+    pub attacker: GridPositionDto,
+    pub target: GridPositionDto,
+}
+
+/// Whether an attacker can see a target, and the target's cover.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverResponseDto {
+    pub line_of_sight: bool,
+    pub cover: CoverDto,
+}
+
+impl From<Cover> for CoverResponseDto {
+    fn from(value: Cover) -> Self {
+        Self {
+            line_of_sight: value != Cover::Total,
+            cover: value.into(),
+        }
+    }
+}
+
+/// A tile a character has seen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeenTileDto {
+    pub x: u32,
+    pub y: u32,
+    #[serde(flatten)]
+    pub tile: TileDto,
+}
+
+/// What a player's character knows of a grid map: the tiles it has seen
+/// and which of them it sees now. Tiles it has never seen are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapViewDto {
+    pub grid_map_id: String,
+    pub pc_id: String,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub tilesheet_asset: String,
+    pub tile_size: u32,
+    /// Tiles in sight now
+    pub visible: Vec<GridPositionDto>,
+    /// Every tile seen so far, row by row
+    pub tiles: Vec<SeenTileDto>,
+}
+
+impl MapViewDto {
+    pub fn new(grid_map: &GridMap, pc_id: PlayerCharacterId, sight: &CharacterSight) -> Self {
+        let mut visible: Vec<GridPosition> = sight.visible.iter().copied().collect();
+        visible.sort_by_key(|&(x, y)| (y, x));
+        let mut explored: Vec<GridPosition> = sight.explored.iter().copied().collect();
+        explored.sort_by_key(|&(x, y)| (y, x));
+
+        Self {
+            grid_map_id: grid_map.id.to_string(),
+            pc_id: pc_id.to_string(),
+            name: grid_map.name.clone(),
+            width: grid_map.width,
+            height: grid_map.height,
+            tilesheet_asset: grid_map.tilesheet_asset.clone(),
+            tile_size: grid_map.tile_size,
+            visible: visible.into_iter().map(GridPositionDto::from).collect(),
+            tiles: explored
+                .into_iter()
+                .filter_map(|(x, y)| {
+                    grid_map.get_tile(x, y).map(|tile| SeenTileDto {
+                        x,
+                        y,
+                        tile: tile.into(),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Notification of what a player's character now sees of a grid map
+#[derive(Debug, Clone, Serialize)]
+pub struct MapViewNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub view: MapViewDto,
+}
+
+impl MapViewNotification {
+    pub fn new(view: MapViewDto) -> Self {
+        Self {
+            message_type: "MapView",
+            view,
+        }
+    }
+}
+
+/// Convert rows of tile DTOs to tiles
+pub fn tiles_from_dto(rows: Vec<Vec<TileDto>>) -> Vec<Vec<Tile>> {
+    rows.into_iter()
+        .map(|row| row.into_iter().map(Tile::from).collect())
+        .collect()
+}
@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::application::services::{PlaceToken, UpdateToken};
+use crate::domain::entities::{Facing, GridPath, GridToken, TokenSubject};
+use crate::domain::value_objects::{CharacterId, GridMapId, GridTokenId, PlayerCharacterId};
+
+use super::GridPositionDto;
+
+/// What a token stands for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TokenSubjectDto {
+    Pc { pc_id: String },
+    Npc { character_id: String },
+    Prop,
+}
+
+impl TokenSubjectDto {
+    /// The subject; fails on a malformed ID
+    pub fn into_subject(self) -> Result<TokenSubject, uuid::Error> {
+        Ok(match self {
+            Self::Pc { pc_id } => TokenSubject::Pc(PlayerCharacterId::from_uuid(Uuid::parse_str(&pc_id)?)),
+            Self::Npc { character_id } => {
+                TokenSubject::Npc(CharacterId::from_uuid(Uuid::parse_str(&character_id)?))
+            }
+            Self::Prop => TokenSubject::Prop,
+        })
+    }
+}
+
+impl From<TokenSubject> for TokenSubjectDto {
+    fn from(value: TokenSubject) -> Self {
+        match value {
+            TokenSubject::Pc(id) => Self::Pc { pc_id: id.to_string() },
+            TokenSubject::Npc(id) => Self::Npc {
+                character_id: id.to_string(),
+            },
+            TokenSubject::Prop => Self::Prop,
+        }
+    }
+}
+
+/// The way a token faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FacingDto {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl From<FacingDto> for Facing {
+    fn from(value: FacingDto) -> Self {
+        match value {
+            FacingDto::North => Self::North,
+            FacingDto::NorthEast => Self::NorthEast,
+            FacingDto::East => Self::East,
+            FacingDto::SouthEast => Self::SouthEast,
+            FacingDto::South => Self::South,
+            FacingDto::SouthWest => Self::SouthWest,
+            FacingDto::West => Self::West,
+            FacingDto::NorthWest => Self::NorthWest,
+        }
+    }
+}
+
+impl From<Facing> for FacingDto {
+    fn from(value: Facing) -> Self {
+        match value {
+            Facing::North => Self::North,
+            Facing::NorthEast => Self::NorthEast,
+            Facing::East => Self::East,
+            Facing::SouthEast => Self::SouthEast,
+            Facing::South => Self::South,
+            Facing::SouthWest => Self::SouthWest,
+            Facing::West => Self::West,
+            Facing::NorthWest => Self::NorthWest,
+        }
+    }
+}
+
+/// A token on a grid map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridTokenDto {
+    pub id: String,
+    pub grid_map_id: String,
+    pub subject: TokenSubjectDto,
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    pub size: u32,
+    pub facing: FacingDto,
+    pub hidden: bool,
+}
+
+impl From<&GridToken> for GridTokenDto {
+    fn from(value: &GridToken) -> Self {
+        Self {
+            id: value.id.to_string(),
+            grid_map_id: value.grid_map_id.to_string(),
+            subject: value.subject.into(),
+            name: value.name.clone(),
+            x: value.x,
+            y: value.y,
+            size: value.size,
+            facing: value.facing.into(),
+            hidden: value.hidden,
+        }
+    }
+}
+
+impl From<GridToken> for GridTokenDto {
+    fn from(value: GridToken) -> Self {
+        Self::from(&value)
+    }
+}
+
+/// Request to put a token on a grid map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaceTokenRequestDto {
+    pub subject: TokenSubjectDto,
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    /// Tiles across; 1 if not given
+    #[serde(default)]
+    pub size: Option<u32>,
+    #[serde(default)]
+    pub facing: Option<FacingDto>,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl PlaceTokenRequestDto {
+    /// The token to place; fails on a malformed ID
+    pub fn into_place(self) -> Result<PlaceToken, uuid::Error> {
+        Ok(PlaceToken {
+            subject: self.subject.into_subject()?,
+            name: self.name,
+            position: (self.x, self.y),
+            size: self.size.unwrap_or(1),
+            facing: self.facing.map(Facing::from).unwrap_or_default(),
+            hidden: self.hidden,
+        })
+    }
+}
+
+/// Request to change a token; fields not given are left as they are.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateTokenRequestDto {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub size: Option<u32>,
+    #[serde(default)]
+    pub facing: Option<FacingDto>,
+    #[serde(default)]
+    pub hidden: Option<bool>,
+}
+
+impl From<UpdateTokenRequestDto> for UpdateToken {
+    fn from(value: UpdateTokenRequestDto) -> Self {
+        Self {
+            name: value.name,
+            size: value.size,
+            facing: value.facing.map(Facing::from),
+            hidden: value.hidden,
+        }
+    }
+}
+
+/// Request to move a token along the cheapest route to a tile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveTokenRequestDto {
+    pub x: u32,
+    pub y: u32,
+    /// The way the last step faces if not given
+    #[serde(default)]
+    pub facing: Option<FacingDto>,
+}
+
+/// A token's move and the route it took.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenMovedDto {
+    pub grid_map_id: String,
+    pub token_id: String,
+    pub x: u32,
+    pub y: u32,
+    pub facing: FacingDto,
+    /// Every tile on the route, start and end included
+    pub path: Vec<GridPositionDto>,
+    pub cost: u32,
+}
+
+impl TokenMovedDto {
+    pub fn new(token: &GridToken, path: GridPath) -> Self {
+        Self {
+            grid_map_id: token.grid_map_id.to_string(),
+            token_id: token.id.to_string(),
+            x: token.x,
+            y: token.y,
+            facing: token.facing.into(),
+            path: path.steps.into_iter().map(GridPositionDto::from).collect(),
+            cost: path.cost,
+        }
+    }
+}
+
+/// Notification that a token was placed or changed
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenUpdatedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub token: GridTokenDto,
+}
+
+impl TokenUpdatedNotification {
+    pub fn new(token: &GridToken) -> Self {
+        Self {
+            message_type: "TokenUpdated",
+            token: token.into(),
+        }
+    }
+}
+
+/// Notification that a token moved
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenMovedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    #[serde(flatten)]
+    pub moved: TokenMovedDto,
+}
+
+impl TokenMovedNotification {
+    pub fn new(moved: TokenMovedDto) -> Self {
+        Self {
+            message_type: "TokenMoved",
+            moved,
+        }
+    }
+}
+
+/// Notification that a token left the map, or went out of the players' sight
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenRemovedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub grid_map_id: String,
+    pub token_id: String,
+}
+
+impl TokenRemovedNotification {
+    pub fn new(grid_map_id: GridMapId, token_id: GridTokenId) -> Self {
+        Self {
+            message_type: "TokenRemoved",
+            grid_map_id: grid_map_id.to_string(),
+            token_id: token_id.to_string(),
+        }
+    }
+}
+
+/// Notification that the DM locked or unlocked movement on a grid map
+#[derive(Debug, Clone, Serialize)]
+pub struct MovementLockedNotification {
+    #[serde(rename = "type")]
+    pub message_type: &'static str,
+    pub grid_map_id: String,
+    pub locked: bool,
+}
+
+impl MovementLockedNotification {
+    pub fn new(grid_map_id: GridMapId, locked: bool) -> Self {
+        Self {
+            message_type: "MovementLocked",
+            grid_map_id: grid_map_id.to_string(),
+            locked,
+        }
+    }
+}
@@ -88,6 +88,29 @@ pub struct CreateItemRequestDto {
     pub properties: Option<String>,
 }
 
+/// Request DTO for updating an item; an empty string clears a field
+#[derive(Debug, Deserialize)]
+pub struct UpdateItemRequestDto {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub item_type: Option<String>,
+    #[serde(default)]
+    pub is_unique: Option<bool>,
+    #[serde(default)]
+    pub properties: Option<String>,
+}
+
+/// Query DTO for listing a world's items
+#[derive(Debug, Deserialize)]
+pub struct ListItemsQueryDto {
+    /// Only list items of this type (e.g., "Weapon"), ignoring case
+    #[serde(default)]
+    pub item_type: Option<String>,
+}
+
 /// Request DTO for adding an item to inventory
 #[derive(Debug, Deserialize)]
 pub struct AddInventoryItemRequestDto {
@@ -3,13 +3,19 @@
 //! DTOs are used for data transfer between layers (HTTP routes, services, etc.)
 //! They provide a stable API that is decoupled from domain entities.
 
+mod advancement;
 mod app_events;
+mod area_effect;
 mod asset;
 mod challenge;
 mod character;
+mod combat;
 mod comfyui_config;
+mod condition;
 mod event_chain;
 mod export;
+mod grid_map;
+mod grid_token;
 mod interaction;
 mod item;
 mod location;
@@ -17,6 +23,7 @@ mod narrative_event;
 mod queue_items;
 mod rule_system;
 mod scene;
+mod sheet_resource;
 mod sheet_template;
 mod session_info;
 mod skill;
@@ -36,6 +43,12 @@ pub use queue_items::{
     LLMRequestType, NarrativeEventSuggestionInfo, PlayerActionItem,
 };
 
+// Advancement DTOs
+pub use advancement::{
+    AdvancementDto, AwardExperienceRequestDto, ExperienceAwardedNotification,
+    LevelGainedNotification, LevelUpAvailableNotification, LevelUpDto,
+};
+
 // Asset DTOs
 pub use asset::{
     parse_asset_type, parse_entity_type, GalleryAssetResponseDto, GenerateAssetRequestDto,
@@ -45,19 +58,30 @@ pub use asset::{
 
 // Challenge DTOs
 pub use challenge::{
-    AdHocOutcomesDto, ChallengeOutcomeApprovalRequest, ChallengeOutcomeDecision,
-    ChallengeOutcomePendingNotification, ChallengeResolvedNotification,
-    ChallengeResponseDto, ChallengeRollSubmittedNotification, CreateChallengeRequestDto,
+    AdHocOutcomesDto, ChallengeOddsDto, ChallengeOddsQueryDto, ChallengeOutcomeApprovalRequest,
+    ChallengeOutcomeDecision, ChallengeOutcomePendingNotification, ChallengeResolvedNotification,
+    ChallengeResponseDto, ChallengeRollSubmittedNotification, ComplexChallengeProgressDto,
+    ComplexChallengeRollDto, ComplexChallengeSettingsDto, CreateChallengeRequestDto,
     DifficultyRequestDto, OutcomeBranchDto, OutcomeBranchResponse, OutcomeBranchSelectionRequest,
-    OutcomeBranchesReadyNotification, OutcomeRequestDto, OutcomeSuggestionReadyNotification,
-    OutcomeSuggestionRequest, OutcomeSuggestionResponse, OutcomesRequestDto,
-    OutcomeTriggerRequestDto, PendingChallengeResolutionDto, TriggerConditionRequestDto,
+    OpponentDto, OpposedRollDto, OutcomeBranchesReadyNotification, OutcomeRequestDto,
+    OutcomeSuggestionReadyNotification, OutcomeSuggestionRequest, OutcomeSuggestionResponse,
+    OutcomesRequestDto, OutcomeTriggerRequestDto, PendingChallengeResolutionDto, TriggerConditionRequestDto,
     UpdateChallengeRequestDto,
 };
 
+// Combat DTOs
+pub use combat::{
+    CombatEndedNotification, CombatNpcRequestDto, CombatOutcomeDto,
+    CombatTurnChangedNotification, CombatUpdatedNotification, CombatantDefeatedNotification,
+    DamageCombatantRequestDto, EncounterDto, EndCombatRequestDto,
+    InitiativeRequestDto, StartCombatRequestDto,
+};
+
 // Rule system DTOs
 pub use rule_system::{
-    parse_system_type, parse_variant, RuleSystemConfigDto,
+    parse_system_type, parse_variant, AdvancementModeDto, DescriptorDifficultiesDto, ProgressionTableDto, RestTypeDto,
+    RuleSystemConfigDto,
+    RuleSystemDefinitionDto, RuleSystemQueryDto,
     RuleSystemPresetDetailsDto, RuleSystemPresetSummaryDto, RuleSystemSummaryDto,
     RuleSystemTypeDetailsDto, RuleSystemVariantDto,
 };
@@ -94,6 +118,25 @@ pub use event_chain::{
     EventChainResponseDto, UpdateEventChainRequestDto,
 };
 
+// Grid map DTOs
+pub use grid_map::{
+    tiles_from_dto, CoverRequestDto, CoverResponseDto, CreateGridMapRequestDto,
+    GenerateGridMapRequestDto, GeneratedGridMapDto, GridMapDto, GridMapSummaryDto, GridPathDto, GridPositionDto,
+    ImportGridMapQueryDto, ImportedGridMapDto, MapViewDto, MapViewNotification, MovementRangeRequestDto,
+    PatchTilesRequestDto, PathRequestDto, ReachableTileDto, SetGridMapRequestDto,
+    UpdateGridMapRequestDto,
+};
+
+// Area effect DTOs
+pub use area_effect::{AreaEffectDto, AreaEffectNotification, AreaEffectRequestDto, AreaTemplateDto};
+
+// Grid token DTOs
+pub use grid_token::{
+    GridTokenDto, MoveTokenRequestDto, MovementLockedNotification, PlaceTokenRequestDto,
+    TokenMovedDto, TokenMovedNotification, TokenRemovedNotification, TokenUpdatedNotification,
+    UpdateTokenRequestDto,
+};
+
 // Location DTOs
 pub use location::{
     parse_location_type, ConnectionResponseDto, CreateConnectionRequestDto,
@@ -110,12 +153,16 @@ pub use character::{
 // Item DTOs
 pub use item::{
     parse_acquisition_method, AddInventoryItemRequestDto, CreateItemRequestDto,
-    InventoryItemResponseDto, ItemResponseDto, UpdateInventoryItemRequestDto,
+    InventoryItemResponseDto, ItemResponseDto, ListItemsQueryDto, UpdateInventoryItemRequestDto,
+    UpdateItemRequestDto,
 };
 
 // ComfyUI config DTO
 pub use comfyui_config::ComfyUIConfigDto;
 
+// Condition DTOs
+pub use condition::{ActiveConditionDto, ApplyConditionRequestDto, EndedConditionDto};
+
 // Skill DTOs
 pub use skill::{
     CreateSkillRequestDto, SkillResponseDto, UpdateSkillRequestDto,
@@ -127,10 +174,17 @@ pub use interaction::{
     SetAvailabilityRequestDto,
 };
 
+// Sheet resource DTOs
+pub use sheet_resource::{
+    ModifyResourceRequestDto, ResourceChangeDto, ResourceOperationDto, RestOutcomeDto,
+    TakeRestRequestDto,
+};
+
 // Sheet template DTOs
 pub use sheet_template::{
-    CreateFieldRequestDto, CreateSectionRequestDto, SheetTemplateResponseDto,
-    SheetTemplateStorageDto, SheetTemplateSummaryDto,
+    CharacterMigrationDto, CreateFieldRequestDto, CreateSectionRequestDto, InvalidSheetReportDto,
+    MigrationPreviewDto, SheetTemplateResponseDto, SheetTemplateStorageDto, SheetTemplateSummaryDto,
+    SheetValidationErrorDto, UpdateSheetTemplateRequestDto,
 };
 
 // Session DTOs
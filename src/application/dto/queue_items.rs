@@ -6,6 +6,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::application::dto::{OpposedRollDto, OutcomeTriggerRequestDto};
+use crate::domain::entities::OutcomeType;
 use crate::domain::value_objects::{
     ApprovalDecision, GamePromptRequest, ProposedToolInfo, QueueItemId, SceneId, SessionId,
 };
@@ -235,13 +237,28 @@ pub struct ChallengeOutcomeApprovalItem {
     pub total: i32,
     /// Determined outcome type (e.g., "Success", "Critical Failure")
     pub outcome_type: String,
+    /// Outcome tier the rule system's bands graded the roll into
+    #[serde(default)]
+    pub outcome_tier: Option<OutcomeType>,
+    /// How far the roll beat (positive) or missed (negative) the success line
+    #[serde(default)]
+    pub margin: Option<i32>,
     /// The pre-defined outcome description
     pub outcome_description: String,
     /// Triggers that will execute when approved
     pub outcome_triggers: Vec<ProposedToolInfo>,
+    /// The outcome's triggers as defined on the challenge, run on approval
+    #[serde(default)]
+    pub triggers: Vec<OutcomeTriggerRequestDto>,
     /// Roll breakdown string
     #[serde(default)]
     pub roll_breakdown: Option<String>,
+    /// Roll log entry for the roll (None for manual results)
+    #[serde(default)]
+    pub roll_id: Option<String>,
+    /// The opponent's roll for opposed checks
+    #[serde(default)]
+    pub opposed: Option<OpposedRollDto>,
     /// When the roll was submitted
     pub timestamp: DateTime<Utc>,
     /// LLM-generated suggestions (if requested)
@@ -1,7 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use super::sheet_template::SheetSectionDto;
+use super::skill::SkillCategoryDto;
+use crate::domain::entities::{RuleSystemDefinition, SkillDefinition};
 use crate::domain::value_objects::{
-    DiceSystem, RuleSystemConfig, RuleSystemType, RuleSystemVariant, StatDefinition, SuccessComparison,
+    ActionEffect, ActionPosition, AdvancementMode, DescriptorDifficulties, DescriptorTarget, DiceSystem,
+    OpposedTieBreak, ProgressionLevel, ProgressionTable, RecoveryAmount, ResolutionBands, ResourceRecovery,
+    RestType, RuleSystemConfig, RuleSystemType, RuleSystemVariant, StatDefinition, SuccessComparison,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -146,7 +151,14 @@ impl From<StatDefinitionDto> for StatDefinition {
 pub enum DiceSystemDto {
     D20,
     D100,
-    DicePool { die_type: u8, success_threshold: u8 },
+    DicePool {
+        die_type: u8,
+        success_threshold: u8,
+        #[serde(default)]
+        ones_cancel: bool,
+        #[serde(default)]
+        again_threshold: Option<u8>,
+    },
     Fate,
     Custom(String),
 }
@@ -159,9 +171,13 @@ impl From<DiceSystem> for DiceSystemDto {
             DiceSystem::DicePool {
                 die_type,
                 success_threshold,
+                ones_cancel,
+                again_threshold,
             } => Self::DicePool {
                 die_type,
                 success_threshold,
+                ones_cancel,
+                again_threshold,
             },
             DiceSystem::Fate => Self::Fate,
             DiceSystem::Custom(s) => Self::Custom(s),
@@ -177,9 +193,13 @@ impl From<DiceSystemDto> for DiceSystem {
             DiceSystemDto::DicePool {
                 die_type,
                 success_threshold,
+                ones_cancel,
+                again_threshold,
             } => Self::DicePool {
                 die_type,
                 success_threshold,
+                ones_cancel,
+                again_threshold,
             },
             DiceSystemDto::Fate => Self::Fate,
             DiceSystemDto::Custom(s) => Self::Custom(s),
@@ -187,6 +207,216 @@ impl From<DiceSystemDto> for DiceSystem {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResolutionBandsDto {
+    Standard,
+    Degrees { critical_margin: i32 },
+    RollUnder,
+    Bands { partial_at: i32, success_at: i32 },
+    Shifts,
+}
+
+impl From<ResolutionBands> for ResolutionBandsDto {
+    fn from(value: ResolutionBands) -> Self {
+        match value {
+            ResolutionBands::Standard => Self::Standard,
+            ResolutionBands::Degrees { critical_margin } => Self::Degrees { critical_margin },
+            ResolutionBands::RollUnder => Self::RollUnder,
+            ResolutionBands::Bands { partial_at, success_at } => Self::Bands { partial_at, success_at },
+            ResolutionBands::Shifts => Self::Shifts,
+        }
+    }
+}
+
+impl From<ResolutionBandsDto> for ResolutionBands {
+    fn from(value: ResolutionBandsDto) -> Self {
+        match value {
+            ResolutionBandsDto::Standard => Self::Standard,
+            ResolutionBandsDto::Degrees { critical_margin } => Self::Degrees { critical_margin },
+            ResolutionBandsDto::RollUnder => Self::RollUnder,
+            ResolutionBandsDto::Bands { partial_at, success_at } => Self::Bands { partial_at, success_at },
+            ResolutionBandsDto::Shifts => Self::Shifts,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpposedTieBreakDto {
+    Defender,
+    Initiator,
+    HigherSkill,
+    Partial,
+}
+
+impl From<OpposedTieBreak> for OpposedTieBreakDto {
+    fn from(value: OpposedTieBreak) -> Self {
+        match value {
+            OpposedTieBreak::Defender => Self::Defender,
+            OpposedTieBreak::Initiator => Self::Initiator,
+            OpposedTieBreak::HigherSkill => Self::HigherSkill,
+            OpposedTieBreak::Partial => Self::Partial,
+        }
+    }
+}
+
+impl From<OpposedTieBreakDto> for OpposedTieBreak {
+    fn from(value: OpposedTieBreakDto) -> Self {
+        match value {
+            OpposedTieBreakDto::Defender => Self::Defender,
+            OpposedTieBreakDto::Initiator => Self::Initiator,
+            OpposedTieBreakDto::HigherSkill => Self::HigherSkill,
+            OpposedTieBreakDto::Partial => Self::Partial,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionPositionDto {
+    Controlled,
+    Risky,
+    Desperate,
+}
+
+impl From<ActionPosition> for ActionPositionDto {
+    fn from(value: ActionPosition) -> Self {
+        match value {
+            ActionPosition::Controlled => Self::Controlled,
+            ActionPosition::Risky => Self::Risky,
+            ActionPosition::Desperate => Self::Desperate,
+        }
+    }
+}
+
+impl From<ActionPositionDto> for ActionPosition {
+    fn from(value: ActionPositionDto) -> Self {
+        match value {
+            ActionPositionDto::Controlled => Self::Controlled,
+            ActionPositionDto::Risky => Self::Risky,
+            ActionPositionDto::Desperate => Self::Desperate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionEffectDto {
+    Limited,
+    Standard,
+    Great,
+}
+
+impl From<ActionEffect> for ActionEffectDto {
+    fn from(value: ActionEffect) -> Self {
+        match value {
+            ActionEffect::Limited => Self::Limited,
+            ActionEffect::Standard => Self::Standard,
+            ActionEffect::Great => Self::Great,
+        }
+    }
+}
+
+impl From<ActionEffectDto> for ActionEffect {
+    fn from(value: ActionEffectDto) -> Self {
+        match value {
+            ActionEffectDto::Limited => Self::Limited,
+            ActionEffectDto::Standard => Self::Standard,
+            ActionEffectDto::Great => Self::Great,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DescriptorTargetDto {
+    Dc { value: u32 },
+    Percentage { value: u32 },
+    Ladder { value: i32 },
+    PositionEffect { position: ActionPositionDto, effect: ActionEffectDto },
+}
+
+impl From<DescriptorTarget> for DescriptorTargetDto {
+    fn from(value: DescriptorTarget) -> Self {
+        match value {
+            DescriptorTarget::Dc(value) => Self::Dc { value },
+            DescriptorTarget::Percentage(value) => Self::Percentage { value },
+            DescriptorTarget::Ladder(value) => Self::Ladder { value },
+            DescriptorTarget::PositionEffect { position, effect } => Self::PositionEffect {
+                position: position.into(),
+                effect: effect.into(),
+            },
+        }
+    }
+}
+
+impl From<DescriptorTargetDto> for DescriptorTarget {
+    fn from(value: DescriptorTargetDto) -> Self {
+        match value {
+            DescriptorTargetDto::Dc { value } => Self::Dc(value),
+            DescriptorTargetDto::Percentage { value } => Self::Percentage(value),
+            DescriptorTargetDto::Ladder { value } => Self::Ladder(value),
+            DescriptorTargetDto::PositionEffect { position, effect } => Self::PositionEffect {
+                position: position.into(),
+                effect: effect.into(),
+            },
+        }
+    }
+}
+
+/// What each difficulty descriptor maps to, keyed by descriptor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DescriptorDifficultiesDto {
+    pub trivial: DescriptorTargetDto,
+    pub easy: DescriptorTargetDto,
+    pub routine: DescriptorTargetDto,
+    pub moderate: DescriptorTargetDto,
+    pub challenging: DescriptorTargetDto,
+    pub hard: DescriptorTargetDto,
+    pub very_hard: DescriptorTargetDto,
+    pub extreme: DescriptorTargetDto,
+    pub impossible: DescriptorTargetDto,
+    pub risky: DescriptorTargetDto,
+    pub desperate: DescriptorTargetDto,
+}
+
+impl From<DescriptorDifficulties> for DescriptorDifficultiesDto {
+    fn from(value: DescriptorDifficulties) -> Self {
+        Self {
+            trivial: value.trivial.into(),
+            easy: value.easy.into(),
+            routine: value.routine.into(),
+            moderate: value.moderate.into(),
+            challenging: value.challenging.into(),
+            hard: value.hard.into(),
+            very_hard: value.very_hard.into(),
+            extreme: value.extreme.into(),
+            impossible: value.impossible.into(),
+            risky: value.risky.into(),
+            desperate: value.desperate.into(),
+        }
+    }
+}
+
+impl From<DescriptorDifficultiesDto> for DescriptorDifficulties {
+    fn from(value: DescriptorDifficultiesDto) -> Self {
+        Self {
+            trivial: value.trivial.into(),
+            easy: value.easy.into(),
+            routine: value.routine.into(),
+            moderate: value.moderate.into(),
+            challenging: value.challenging.into(),
+            hard: value.hard.into(),
+            very_hard: value.very_hard.into(),
+            extreme: value.extreme.into(),
+            impossible: value.impossible.into(),
+            risky: value.risky.into(),
+            desperate: value.desperate.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RuleSystemConfigDto {
     pub name: String,
@@ -197,6 +427,186 @@ pub struct RuleSystemConfigDto {
     pub dice_system: DiceSystemDto,
     pub success_comparison: SuccessComparisonDto,
     pub skill_check_formula: String,
+    /// Missing from configs saved before descriptors were mapped; the
+    /// variant's preset mapping is used instead
+    #[serde(default)]
+    pub descriptor_difficulties: Option<DescriptorDifficultiesDto>,
+    #[serde(default)]
+    pub custom_resolution: Option<ResolutionBandsDto>,
+    #[serde(default)]
+    pub custom_tie_break: Option<OpposedTieBreakDto>,
+    #[serde(default)]
+    pub custom_rest_types: Option<Vec<RestTypeDto>>,
+    #[serde(default)]
+    pub custom_progression: Option<ProgressionTableDto>,
+}
+
+/// A rest and the resources it recovers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestTypeDto {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub duration_minutes: u32,
+    #[serde(default)]
+    pub recoveries: Vec<ResourceRecoveryDto>,
+}
+
+impl From<RestType> for RestTypeDto {
+    fn from(value: RestType) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            duration_minutes: value.duration_minutes,
+            recoveries: value.recoveries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<RestTypeDto> for RestType {
+    fn from(value: RestTypeDto) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            duration_minutes: value.duration_minutes,
+            recoveries: value.recoveries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecoveryAmountDto {
+    Full,
+    Restore { amount: i32 },
+    HalfOfMax,
+    Reduce { amount: i32 },
+    Clear,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceRecoveryDto {
+    pub field_id: String,
+    pub amount: RecoveryAmountDto,
+}
+
+impl From<ResourceRecovery> for ResourceRecoveryDto {
+    fn from(value: ResourceRecovery) -> Self {
+        let amount = match value.amount {
+            RecoveryAmount::Full => RecoveryAmountDto::Full,
+            RecoveryAmount::Restore(amount) => RecoveryAmountDto::Restore { amount },
+            RecoveryAmount::HalfOfMax => RecoveryAmountDto::HalfOfMax,
+            RecoveryAmount::Reduce(amount) => RecoveryAmountDto::Reduce { amount },
+            RecoveryAmount::Clear => RecoveryAmountDto::Clear,
+        };
+        Self {
+            field_id: value.field_id,
+            amount,
+        }
+    }
+}
+
+impl From<ResourceRecoveryDto> for ResourceRecovery {
+    fn from(value: ResourceRecoveryDto) -> Self {
+        let amount = match value.amount {
+            RecoveryAmountDto::Full => RecoveryAmount::Full,
+            RecoveryAmountDto::Restore { amount } => RecoveryAmount::Restore(amount),
+            RecoveryAmountDto::HalfOfMax => RecoveryAmount::HalfOfMax,
+            RecoveryAmountDto::Reduce { amount } => RecoveryAmount::Reduce(amount),
+            RecoveryAmountDto::Clear => RecoveryAmount::Clear,
+        };
+        Self {
+            field_id: value.field_id,
+            amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvancementModeDto {
+    Experience,
+    Milestone,
+}
+
+impl From<AdvancementMode> for AdvancementModeDto {
+    fn from(value: AdvancementMode) -> Self {
+        match value {
+            AdvancementMode::Experience => Self::Experience,
+            AdvancementMode::Milestone => Self::Milestone,
+        }
+    }
+}
+
+impl From<AdvancementModeDto> for AdvancementMode {
+    fn from(value: AdvancementModeDto) -> Self {
+        match value {
+            AdvancementModeDto::Experience => Self::Experience,
+            AdvancementModeDto::Milestone => Self::Milestone,
+        }
+    }
+}
+
+/// The levels characters advance through
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressionTableDto {
+    pub mode: AdvancementModeDto,
+    /// Sheet field that mirrors the character's level (e.g. "LEVEL")
+    #[serde(default)]
+    pub level_field: Option<String>,
+    pub levels: Vec<ProgressionLevelDto>,
+}
+
+/// One level and the total progress it needs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressionLevelDto {
+    pub level: u32,
+    pub threshold: u32,
+    #[serde(default)]
+    pub unlocks_fields: Vec<String>,
+    #[serde(default)]
+    pub unlocks_skills: Vec<String>,
+}
+
+impl From<ProgressionTable> for ProgressionTableDto {
+    fn from(value: ProgressionTable) -> Self {
+        Self {
+            mode: value.mode.into(),
+            level_field: value.level_field,
+            levels: value
+                .levels
+                .into_iter()
+                .map(|level| ProgressionLevelDto {
+                    level: level.level,
+                    threshold: level.threshold,
+                    unlocks_fields: level.unlocks_fields,
+                    unlocks_skills: level.unlocks_skills,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ProgressionTableDto> for ProgressionTable {
+    fn from(value: ProgressionTableDto) -> Self {
+        let table = ProgressionTable::new(value.mode.into());
+        let table = match value.level_field {
+            Some(field_id) => table.with_level_field(field_id),
+            None => table,
+        };
+        value.levels.into_iter().fold(table, |table, level| {
+            table.with_level(ProgressionLevel {
+                level: level.level,
+                threshold: level.threshold,
+                unlocks_fields: level.unlocks_fields,
+                unlocks_skills: level.unlocks_skills,
+            })
+        })
+    }
 }
 
 impl From<RuleSystemConfig> for RuleSystemConfigDto {
@@ -214,6 +624,13 @@ impl From<RuleSystemConfig> for RuleSystemConfigDto {
             dice_system: value.dice_system.into(),
             success_comparison: value.success_comparison.into(),
             skill_check_formula: value.skill_check_formula,
+            descriptor_difficulties: Some(value.descriptor_difficulties.into()),
+            custom_resolution: value.custom_resolution.map(Into::into),
+            custom_tie_break: value.custom_tie_break.map(Into::into),
+            custom_rest_types: value
+                .custom_rest_types
+                .map(|rests| rests.into_iter().map(Into::into).collect()),
+            custom_progression: value.custom_progression.map(Into::into),
         }
     }
 }
@@ -247,6 +664,14 @@ pub struct RuleSystemPresetDetailsDto {
     pub config: RuleSystemConfigDto,
 }
 
+/// Query for rule system listings; a world's homebrew system is included
+/// when `world_id` is given.
+#[derive(Debug, Default, Deserialize)]
+pub struct RuleSystemQueryDto {
+    #[serde(default)]
+    pub world_id: Option<String>,
+}
+
 /// Details about a rule system type.
 #[derive(Debug, Serialize)]
 pub struct RuleSystemTypeDetailsDto {
@@ -294,11 +719,16 @@ pub fn parse_variant(s: &str) -> Result<RuleSystemVariant, String> {
 
 impl From<RuleSystemConfigDto> for RuleSystemConfig {
     fn from(value: RuleSystemConfigDto) -> Self {
+        let variant: RuleSystemVariant = value.variant.into();
+        let descriptor_difficulties = value
+            .descriptor_difficulties
+            .map(DescriptorDifficulties::from)
+            .unwrap_or_else(|| DescriptorDifficulties::for_variant(&variant));
         Self {
             name: value.name,
             description: value.description,
             system_type: value.system_type.into(),
-            variant: value.variant.into(),
+            variant,
             stat_definitions: value
                 .stat_definitions
                 .into_iter()
@@ -307,7 +737,254 @@ impl From<RuleSystemConfigDto> for RuleSystemConfig {
             dice_system: value.dice_system.into(),
             success_comparison: value.success_comparison.into(),
             skill_check_formula: value.skill_check_formula,
+            descriptor_difficulties,
+            custom_resolution: value.custom_resolution.map(Into::into),
+            custom_tie_break: value.custom_tie_break.map(Into::into),
+            custom_rest_types: value
+                .custom_rest_types
+                .map(|rests| rests.into_iter().map(Into::into).collect()),
+            custom_progression: value.custom_progression.map(Into::into),
+        }
+    }
+}
+
+
+// ============================================================================
+// User-defined rule systems
+// ============================================================================
+
+/// Success-counting pool settings for a user-defined system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DicePoolDefinitionDto {
+    pub die_type: u8,
+    pub success_threshold: u8,
+    #[serde(default)]
+    pub ones_cancel: bool,
+    #[serde(default)]
+    pub again_threshold: Option<u8>,
+}
+
+/// A skill declared by a user-defined system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillDefinitionDto {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_skill_category")]
+    pub category: SkillCategoryDto,
+    #[serde(default)]
+    pub base_attribute: Option<String>,
+}
+
+fn default_skill_category() -> SkillCategoryDto {
+    SkillCategoryDto::Other
+}
+
+impl From<SkillDefinition> for SkillDefinitionDto {
+    fn from(value: SkillDefinition) -> Self {
+        Self {
+            name: value.name,
+            description: value.description,
+            category: value.category.into(),
+            base_attribute: value.base_attribute,
+        }
+    }
+}
+
+impl From<SkillDefinitionDto> for SkillDefinition {
+    fn from(value: SkillDefinitionDto) -> Self {
+        Self {
+            name: value.name,
+            description: value.description,
+            category: value.category.into(),
+            base_attribute: value.base_attribute,
+        }
+    }
+}
+
+/// Declarative definition of a homebrew rule system, uploaded as JSON or YAML
+///
+/// Only `name`, `success_comparison` and either `dice` or `dice_pool` are
+/// required; everything else falls back to what the comparison implies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleSystemDefinitionDto {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Dice expression rolled for a check (e.g. "1d20", "2d6", "4dF")
+    #[serde(default)]
+    pub dice: String,
+    /// Count successes on a pool of dice instead of totalling `dice`
+    #[serde(default)]
+    pub dice_pool: Option<DicePoolDefinitionDto>,
+    pub success_comparison: SuccessComparisonDto,
+    #[serde(default)]
+    pub degrees_of_success: Option<ResolutionBandsDto>,
+    #[serde(default)]
+    pub opposed_tie_break: Option<OpposedTieBreakDto>,
+    #[serde(default)]
+    pub skill_check_formula: Option<String>,
+    #[serde(default)]
+    pub stats: Vec<StatDefinitionDto>,
+    #[serde(default)]
+    pub skills: Vec<SkillDefinitionDto>,
+    /// Sections of the default character sheet
+    #[serde(default)]
+    pub sheet_template: Vec<SheetSectionDto>,
+    #[serde(default)]
+    pub descriptor_difficulties: Option<DescriptorDifficultiesDto>,
+    /// Rests characters can take (short rest, long rest, therapy...)
+    #[serde(default)]
+    pub rests: Vec<RestTypeDto>,
+    /// How characters level up; none for a system without levels
+    #[serde(default)]
+    pub progression: Option<ProgressionTableDto>,
+}
+
+impl RuleSystemDefinitionDto {
+    fn dice_system(&self) -> DiceSystem {
+        if let Some(pool) = self.dice_pool {
+            return DiceSystem::DicePool {
+                die_type: pool.die_type,
+                success_threshold: pool.success_threshold,
+                ones_cancel: pool.ones_cancel,
+                again_threshold: pool.again_threshold,
+            };
+        }
+        match self.dice.trim().to_lowercase().as_str() {
+            "d20" | "1d20" => DiceSystem::D20,
+            "d100" | "1d100" | "d%" => DiceSystem::D100,
+            "4df" => DiceSystem::Fate,
+            _ => DiceSystem::Custom(self.dice.trim().to_string()),
+        }
+    }
+}
+
+impl From<RuleSystemDefinitionDto> for RuleSystemDefinition {
+    fn from(value: RuleSystemDefinitionDto) -> Self {
+        let dice_system = value.dice_system();
+        let success_comparison: SuccessComparison = value.success_comparison.into();
+        let descriptor_difficulties = match value.descriptor_difficulties {
+            Some(descriptors) => descriptors.into(),
+            None => match (&dice_system, success_comparison) {
+                (_, SuccessComparison::LessOrEqual) => DescriptorDifficulties::percentile(),
+                (DiceSystem::Fate, _) => DescriptorDifficulties::fate_ladder(),
+                (_, SuccessComparison::Narrative) => DescriptorDifficulties::position_effect(),
+                _ => DescriptorDifficulties::d20(),
+            },
+        };
+        let skill_check_formula = value
+            .skill_check_formula
+            .unwrap_or_else(|| format!("{} + skill vs difficulty", value.dice.trim()));
+        Self {
+            name: value.name,
+            description: value.description,
+            dice_system,
+            success_comparison,
+            resolution: value.degrees_of_success.map(Into::into).unwrap_or_default(),
+            tie_break: value.opposed_tie_break.map(Into::into).unwrap_or_default(),
+            skill_check_formula,
+            stat_definitions: value.stats.into_iter().map(StatDefinition::from).collect(),
+            skills: value.skills.into_iter().map(SkillDefinition::from).collect(),
+            sheet_sections: value.sheet_template.into_iter().map(Into::into).collect(),
+            descriptor_difficulties,
+            rest_types: value.rests.into_iter().map(Into::into).collect(),
+            progression: value.progression.map(Into::into),
+        }
+    }
+}
+
+impl From<RuleSystemDefinition> for RuleSystemDefinitionDto {
+    fn from(value: RuleSystemDefinition) -> Self {
+        let (dice, dice_pool) = match value.dice_system {
+            DiceSystem::D20 => ("1d20".to_string(), None),
+            DiceSystem::D100 => ("1d100".to_string(), None),
+            DiceSystem::Fate => ("4dF".to_string(), None),
+            DiceSystem::Custom(expression) => (expression, None),
+            DiceSystem::DicePool {
+                die_type,
+                success_threshold,
+                ones_cancel,
+                again_threshold,
+            } => (
+                String::new(),
+                Some(DicePoolDefinitionDto {
+                    die_type,
+                    success_threshold,
+                    ones_cancel,
+                    again_threshold,
+                }),
+            ),
+        };
+        Self {
+            name: value.name,
+            description: value.description,
+            dice,
+            dice_pool,
+            success_comparison: value.success_comparison.into(),
+            degrees_of_success: Some(value.resolution.into()),
+            opposed_tie_break: Some(value.tie_break.into()),
+            skill_check_formula: Some(value.skill_check_formula),
+            stats: value.stat_definitions.into_iter().map(Into::into).collect(),
+            skills: value.skills.into_iter().map(Into::into).collect(),
+            sheet_template: value.sheet_sections.into_iter().map(Into::into).collect(),
+            descriptor_difficulties: Some(value.descriptor_difficulties.into()),
+            rests: value.rest_types.into_iter().map(Into::into).collect(),
+            progression: value.progression.map(Into::into),
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{OpposedTieBreak, ResolutionBands};
+
+    const YAML: &str = r#"
+name: Lasers & Feelings
+description: One stat, roll under it for lasers and over it for feelings
+dice: 2d6
+success_comparison: GreaterOrEqual
+degrees_of_success:
+  type: bands
+  partial_at: 7
+  success_at: 10
+stats:
+  - { name: Lasers, abbreviation: LAS, min_value: 2, max_value: 5, default_value: 3 }
+skills:
+  - name: Piloting
+    category: Physical
+    base_attribute: LAS
+sheet_template:
+  - id: main
+    name: Main
+    layout: Vertical
+    fields:
+      - id: NUMBER
+        name: Number
+        field_type: { type: number, min: 2, max: 5, default: 3 }
+"#;
+
+    #[test]
+    fn test_yaml_definition_round_trips() {
+        let dto: RuleSystemDefinitionDto = serde_yaml_ng::from_str(YAML).unwrap();
+        let definition = RuleSystemDefinition::from(dto);
+        assert!(definition.validate().is_ok());
+        assert!(matches!(definition.dice_system, DiceSystem::Custom(ref dice) if dice == "2d6"));
+        assert_eq!(
+            definition.resolution,
+            ResolutionBands::Bands {
+                partial_at: 7,
+                success_at: 10
+            }
+        );
+        assert_eq!(definition.tie_break, OpposedTieBreak::Defender);
+        assert_eq!(definition.descriptor_difficulties, DescriptorDifficulties::d20());
+        assert_eq!(definition.sheet_sections[0].fields.len(), 1);
+
+        let stored = serde_json::to_string(&RuleSystemDefinitionDto::from(definition)).unwrap();
+        let reloaded = RuleSystemDefinition::from(serde_json::from_str::<RuleSystemDefinitionDto>(&stored).unwrap());
+        assert_eq!(reloaded.skills[0].name, "Piloting");
+        assert_eq!(reloaded.skill_check_formula, "2d6 + skill vs difficulty");
+    }
+}
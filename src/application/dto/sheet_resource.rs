@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use super::rule_system::RestTypeDto;
+use crate::application::services::sheet_resource_service::RestedCharacter;
+use crate::application::services::RestOutcome;
+use crate::domain::entities::ResourceChange;
+use crate::domain::value_objects::ResourceOperation;
+
+/// An operation on a sheet resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum ResourceOperationDto {
+    Spend { amount: u32 },
+    Restore { amount: u32 },
+    Reset,
+}
+
+impl From<ResourceOperationDto> for ResourceOperation {
+    fn from(value: ResourceOperationDto) -> Self {
+        match value {
+            ResourceOperationDto::Spend { amount } => Self::Spend(amount),
+            ResourceOperationDto::Restore { amount } => Self::Restore(amount),
+            ResourceOperationDto::Reset => Self::Reset,
+        }
+    }
+}
+
+/// Request to spend, restore or reset a resource on a character's sheet.
+#[derive(Debug, Deserialize)]
+pub struct ModifyResourceRequestDto {
+    #[serde(flatten)]
+    pub operation: ResourceOperationDto,
+    /// Why it changed, for the story timeline
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Request for characters to take a rest.
+#[derive(Debug, Deserialize)]
+pub struct TakeRestRequestDto {
+    pub rest_type: String,
+    /// Characters who rest; everyone in the world if empty
+    #[serde(default)]
+    pub pc_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceChangeDto {
+    pub field_id: String,
+    pub field_name: String,
+    pub previous: i32,
+    pub current: i32,
+}
+
+impl From<ResourceChange> for ResourceChangeDto {
+    fn from(value: ResourceChange) -> Self {
+        Self {
+            field_id: value.field_id,
+            field_name: value.field_name,
+            previous: value.previous,
+            current: value.current,
+        }
+    }
+}
+
+/// The resources a rest changed on one character.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterRestDto {
+    pub pc_id: String,
+    pub name: String,
+    pub changes: Vec<ResourceChangeDto>,
+    /// Names of the conditions the rest ended
+    #[serde(default)]
+    pub ended_conditions: Vec<String>,
+}
+
+impl From<RestedCharacter> for CharacterRestDto {
+    fn from(value: RestedCharacter) -> Self {
+        Self {
+            pc_id: value.pc.id.to_string(),
+            name: value.pc.name,
+            changes: value.changes.into_iter().map(Into::into).collect(),
+            ended_conditions: value.ended_conditions.into_iter().map(|c| c.name).collect(),
+        }
+    }
+}
+
+/// Result of a rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestOutcomeDto {
+    pub rest: RestTypeDto,
+    pub characters: Vec<CharacterRestDto>,
+    /// Game time after the rest, if the world has a running session
+    pub game_time: Option<String>,
+}
+
+impl From<RestOutcome> for RestOutcomeDto {
+    fn from(value: RestOutcome) -> Self {
+        Self {
+            rest: value.rest.into(),
+            characters: value.characters.into_iter().map(Into::into).collect(),
+            game_time: None,
+        }
+    }
+}
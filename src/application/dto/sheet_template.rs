@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::domain::entities::{
-    CharacterSheetTemplate, FieldType, ItemListType, SectionLayout, SelectOption, SheetField,
-    SheetSection,
+    CharacterSheetTemplate, ConversionRule, FieldChange, FieldType, FieldValue, InvalidSheetData,
+    ItemListType, MigrationStep, PlayerCharacter, SectionLayout, SelectOption, SheetField,
+    SheetMigration, SheetSection,
 };
+use super::rule_system::ProgressionTableDto;
 use crate::domain::value_objects::{RuleSystemVariant, WorldId};
 
 /// Response for a sheet template.
@@ -17,6 +20,13 @@ pub struct SheetTemplateResponseDto {
     pub variant: String,
     pub sections: Vec<SheetSectionDto>,
     pub is_default: bool,
+    pub version: u32,
+    /// Migrations not yet applied to the world's characters
+    pub pending_migrations: Vec<SheetMigrationDto>,
+    /// Whether the last applied migrations can still be rolled back
+    pub can_rollback: bool,
+    /// Progression overriding the rule system's
+    pub progression: Option<ProgressionTableDto>,
 }
 
 impl From<CharacterSheetTemplate> for SheetTemplateResponseDto {
@@ -29,6 +39,10 @@ impl From<CharacterSheetTemplate> for SheetTemplateResponseDto {
             variant: format!("{:?}", template.variant),
             sections: template.sections.into_iter().map(Into::into).collect(),
             is_default: template.is_default,
+            version: template.version,
+            pending_migrations: template.pending_migrations.into_iter().map(Into::into).collect(),
+            can_rollback: !template.applied_migrations.is_empty(),
+            progression: template.progression.map(Into::into),
         }
     }
 }
@@ -46,6 +60,19 @@ pub struct SheetTemplateStorageDto {
     pub variant: serde_json::Value,
     pub sections: Vec<SheetSectionDto>,
     pub is_default: bool,
+    #[serde(default = "first_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub pending_migrations: Vec<SheetMigrationDto>,
+    #[serde(default)]
+    pub applied_migrations: Vec<SheetMigrationDto>,
+    #[serde(default)]
+    pub progression: Option<ProgressionTableDto>,
+}
+
+/// Templates saved before versioning are at version 1
+fn first_version() -> u32 {
+    1
 }
 
 impl SheetTemplateStorageDto {
@@ -104,6 +131,10 @@ impl From<&CharacterSheetTemplate> for SheetTemplateStorageDto {
             variant: Self::variant_to_json(&value.variant),
             sections: value.sections.clone().into_iter().map(Into::into).collect(),
             is_default: value.is_default,
+            version: value.version,
+            pending_migrations: value.pending_migrations.iter().cloned().map(Into::into).collect(),
+            applied_migrations: value.applied_migrations.iter().cloned().map(Into::into).collect(),
+            progression: value.progression.clone().map(Into::into),
         }
     }
 }
@@ -123,6 +154,10 @@ impl TryFrom<SheetTemplateStorageDto> for CharacterSheetTemplate {
             variant: SheetTemplateStorageDto::variant_from_json(value.variant),
             sections: value.sections.into_iter().map(Into::into).collect(),
             is_default: value.is_default,
+            version: value.version,
+            pending_migrations: value.pending_migrations.into_iter().map(Into::into).collect(),
+            applied_migrations: value.applied_migrations.into_iter().map(Into::into).collect(),
+            progression: value.progression.map(Into::into),
         })
     }
 }
@@ -179,6 +214,117 @@ pub struct CreateFieldRequestDto {
     pub read_only: bool,
 }
 
+/// A character sheet value that breaks its template.
+#[derive(Debug, Serialize)]
+pub struct SheetFieldErrorDto {
+    pub field_id: String,
+    pub message: String,
+}
+
+/// Error body when character sheet data fails validation.
+#[derive(Debug, Serialize)]
+pub struct SheetValidationErrorDto {
+    pub message: String,
+    pub errors: Vec<SheetFieldErrorDto>,
+}
+
+impl From<&InvalidSheetData> for SheetValidationErrorDto {
+    fn from(invalid: &InvalidSheetData) -> Self {
+        Self {
+            message: "Character sheet does not match its template".to_string(),
+            errors: invalid
+                .0
+                .iter()
+                .map(|e| SheetFieldErrorDto {
+                    field_id: e.field_id.clone(),
+                    message: e.message.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A player character whose sheet no longer fits its template.
+#[derive(Debug, Serialize)]
+pub struct InvalidSheetReportDto {
+    pub pc_id: String,
+    pub name: String,
+    pub user_id: String,
+    pub errors: Vec<SheetFieldErrorDto>,
+}
+
+impl From<(PlayerCharacter, InvalidSheetData)> for InvalidSheetReportDto {
+    fn from((pc, invalid): (PlayerCharacter, InvalidSheetData)) -> Self {
+        Self {
+            pc_id: pc.id.to_string(),
+            name: pc.name,
+            user_id: pc.user_id,
+            errors: SheetValidationErrorDto::from(&invalid).errors,
+        }
+    }
+}
+
+/// Request to replace a template's sections with a new version.
+#[derive(Debug, Deserialize)]
+pub struct UpdateSheetTemplateRequestDto {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub sections: Vec<SheetSectionDto>,
+    /// Old field ID to new field ID, for fields whose values should move
+    #[serde(default)]
+    pub renamed_fields: HashMap<String, String>,
+    /// Replaces the template's progression when given
+    #[serde(default)]
+    pub progression: Option<ProgressionTableDto>,
+}
+
+/// Preview of a template's pending migrations.
+#[derive(Debug, Serialize)]
+pub struct MigrationPreviewDto {
+    pub template_id: String,
+    pub version: u32,
+    pub migrations: Vec<SheetMigrationDto>,
+    /// Characters whose sheets would change
+    pub characters: Vec<CharacterMigrationDto>,
+}
+
+/// How one character's sheet would change in a migration.
+#[derive(Debug, Serialize)]
+pub struct CharacterMigrationDto {
+    pub pc_id: String,
+    pub name: String,
+    pub changes: Vec<FieldChangeDto>,
+}
+
+impl From<(PlayerCharacter, Vec<FieldChange>)> for CharacterMigrationDto {
+    fn from((pc, changes): (PlayerCharacter, Vec<FieldChange>)) -> Self {
+        Self {
+            pc_id: pc.id.to_string(),
+            name: pc.name,
+            changes: changes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldChangeDto {
+    pub field_id: String,
+    pub before: Option<FieldValue>,
+    pub after: Option<FieldValue>,
+}
+
+impl From<FieldChange> for FieldChangeDto {
+    fn from(value: FieldChange) -> Self {
+        Self {
+            field_id: value.field_id,
+            before: value.before,
+            after: value.after,
+        }
+    }
+}
+
 // ============================================================================
 // Nested DTOs (so we can remove serde from domain)
 // ============================================================================
@@ -187,11 +333,15 @@ pub struct CreateFieldRequestDto {
 pub struct SheetSectionDto {
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub description: Option<String>,
     pub fields: Vec<SheetFieldDto>,
     pub layout: SectionLayoutDto,
+    #[serde(default)]
     pub collapsible: bool,
+    #[serde(default)]
     pub collapsed_by_default: bool,
+    #[serde(default)]
     pub order: u32,
 }
 
@@ -229,10 +379,14 @@ impl From<SheetSectionDto> for SheetSection {
 pub struct SheetFieldDto {
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub description: Option<String>,
     pub field_type: FieldTypeDto,
+    #[serde(default)]
     pub required: bool,
+    #[serde(default)]
     pub read_only: bool,
+    #[serde(default)]
     pub order: u32,
 }
 
@@ -375,7 +529,6 @@ pub enum FieldTypeDto {
     },
     Derived {
         formula: String,
-        depends_on: Vec<String>,
     },
     Resource {
         max_field: Option<String>,
@@ -413,7 +566,7 @@ impl From<FieldType> for FieldTypeDto {
                 categories,
                 show_attribute,
             },
-            FieldType::Derived { formula, depends_on } => Self::Derived { formula, depends_on },
+            FieldType::Derived { formula } => Self::Derived { formula },
             FieldType::Resource {
                 max_field,
                 default_max,
@@ -461,7 +614,7 @@ impl From<FieldTypeDto> for FieldType {
                 categories,
                 show_attribute,
             },
-            FieldTypeDto::Derived { formula, depends_on } => Self::Derived { formula, depends_on },
+            FieldTypeDto::Derived { formula } => Self::Derived { formula },
             FieldTypeDto::Resource {
                 max_field,
                 default_max,
@@ -487,3 +640,124 @@ impl From<FieldTypeDto> for FieldType {
     }
 }
 
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheetMigrationDto {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub steps: Vec<MigrationStepDto>,
+}
+
+impl From<SheetMigration> for SheetMigrationDto {
+    fn from(value: SheetMigration) -> Self {
+        Self {
+            from_version: value.from_version,
+            to_version: value.to_version,
+            steps: value.steps.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<SheetMigrationDto> for SheetMigration {
+    fn from(value: SheetMigrationDto) -> Self {
+        Self {
+            from_version: value.from_version,
+            to_version: value.to_version,
+            steps: value.steps.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum MigrationStepDto {
+    Rename {
+        from: String,
+        to: String,
+    },
+    Remove {
+        field_id: String,
+    },
+    Convert {
+        field_id: String,
+        rule: ConversionRuleDto,
+    },
+    Add {
+        field_id: String,
+        default: Option<FieldValue>,
+    },
+}
+
+impl From<MigrationStep> for MigrationStepDto {
+    fn from(value: MigrationStep) -> Self {
+        match value {
+            MigrationStep::Rename { from, to } => Self::Rename { from, to },
+            MigrationStep::Remove { field_id } => Self::Remove { field_id },
+            MigrationStep::Convert { field_id, rule } => Self::Convert {
+                field_id,
+                rule: rule.into(),
+            },
+            MigrationStep::Add { field_id, default } => Self::Add { field_id, default },
+        }
+    }
+}
+
+impl From<MigrationStepDto> for MigrationStep {
+    fn from(value: MigrationStepDto) -> Self {
+        match value {
+            MigrationStepDto::Rename { from, to } => Self::Rename { from, to },
+            MigrationStepDto::Remove { field_id } => Self::Remove { field_id },
+            MigrationStepDto::Convert { field_id, rule } => Self::Convert {
+                field_id,
+                rule: rule.into(),
+            },
+            MigrationStepDto::Add { field_id, default } => Self::Add { field_id, default },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConversionRuleDto {
+    NumberToText,
+    TextToNumber,
+    NumberToResource,
+    ResourceToNumber,
+    CheckboxToNumber,
+    NumberToCheckbox,
+    ToSelectOption { options: Vec<String> },
+    TextToList,
+    Discard,
+}
+
+impl From<ConversionRule> for ConversionRuleDto {
+    fn from(value: ConversionRule) -> Self {
+        match value {
+            ConversionRule::NumberToText => Self::NumberToText,
+            ConversionRule::TextToNumber => Self::TextToNumber,
+            ConversionRule::NumberToResource => Self::NumberToResource,
+            ConversionRule::ResourceToNumber => Self::ResourceToNumber,
+            ConversionRule::CheckboxToNumber => Self::CheckboxToNumber,
+            ConversionRule::NumberToCheckbox => Self::NumberToCheckbox,
+            ConversionRule::ToSelectOption { options } => Self::ToSelectOption { options },
+            ConversionRule::TextToList => Self::TextToList,
+            ConversionRule::Discard => Self::Discard,
+        }
+    }
+}
+
+impl From<ConversionRuleDto> for ConversionRule {
+    fn from(value: ConversionRuleDto) -> Self {
+        match value {
+            ConversionRuleDto::NumberToText => Self::NumberToText,
+            ConversionRuleDto::TextToNumber => Self::TextToNumber,
+            ConversionRuleDto::NumberToResource => Self::NumberToResource,
+            ConversionRuleDto::ResourceToNumber => Self::ResourceToNumber,
+            ConversionRuleDto::CheckboxToNumber => Self::CheckboxToNumber,
+            ConversionRuleDto::NumberToCheckbox => Self::NumberToCheckbox,
+            ConversionRuleDto::ToSelectOption { options } => Self::ToSelectOption { options },
+            ConversionRuleDto::TextToList => Self::TextToList,
+            ConversionRuleDto::Discard => Self::Discard,
+        }
+    }
+}
@@ -335,6 +335,12 @@ pub enum StoryEventTypeResponseDto {
         new_value: i32,
         reason: String,
     },
+    LevelGained {
+        character_id: String,
+        character_name: String,
+        level: u32,
+        unlocked: Vec<String>,
+    },
     FlagChanged {
         flag_name: String,
         new_value: bool,
@@ -552,6 +558,17 @@ impl From<StoryEventType> for StoryEventTypeResponseDto {
                 new_value,
                 reason,
             },
+            StoryEventType::LevelGained {
+                character_id,
+                character_name,
+                level,
+                unlocked,
+            } => StoryEventTypeResponseDto::LevelGained {
+                character_id: character_id.to_string(),
+                character_name,
+                level,
+                unlocked,
+            },
             StoryEventType::FlagChanged {
                 flag_name,
                 new_value,
@@ -9,7 +9,7 @@ use crate::domain::value_objects::RuleSystemConfig;
 #[serde(untagged)]
 pub enum RuleSystemInputDto {
     VariantOnly { variant: RuleSystemVariantDto },
-    Full(RuleSystemConfigDto),
+    Full(Box<RuleSystemConfigDto>),
 }
 
 impl RuleSystemInputDto {
@@ -18,7 +18,7 @@ impl RuleSystemInputDto {
             RuleSystemInputDto::VariantOnly { variant } => {
                 RuleSystemConfig::from_variant(variant.into())
             }
-            RuleSystemInputDto::Full(config) => config.into(),
+            RuleSystemInputDto::Full(config) => (*config).into(),
         }
     }
 }
@@ -8,7 +8,7 @@
 use async_trait::async_trait;
 use tokio::sync::mpsc;
 
-use crate::domain::value_objects::{SessionId, WorldId};
+use crate::domain::value_objects::{SeededRollSource, SessionId, WorldId};
 
 /// Participant role in a session
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -202,4 +202,12 @@ pub trait AsyncSessionPort: Send + Sync {
         internal_reasoning: Option<String>,
         proposed_tools: Vec<crate::domain::value_objects::ProposedToolInfo>,
     ) -> Result<bool, AsyncSessionError>;
+
+    // === Dice rolling ===
+
+    /// Take the seeded roll source for a session's next engine roll
+    ///
+    /// Each call advances the session's roll sequence, so every roll gets a
+    /// distinct, replayable source. Returns None if the session does not exist.
+    async fn next_roll_source(&self, session_id: SessionId) -> Option<SeededRollSource>;
 }
@@ -8,6 +8,7 @@ mod llm_port;
 mod queue_notification_port;
 mod queue_port;
 mod repository_port;
+mod roll_log_port;
 mod session_management_port;
 mod settings_port;
 mod world_exporter_port;
@@ -29,7 +30,8 @@ pub use llm_port::{
 
 pub use repository_port::{
     AssetRepositoryPort, ChallengeRepositoryPort, CharacterNode, CharacterRepositoryPort,
-    EventChainRepositoryPort, InteractionRepositoryPort, LocationRepositoryPort,
+    EventChainRepositoryPort, GridMapRepositoryPort, GridTokenRepositoryPort,
+    InteractionRepositoryPort, ItemRepositoryPort, LocationRepositoryPort,
     NarrativeEventRepositoryPort, PlayerCharacterRepositoryPort, RegionRepositoryPort,
     RelationshipEdge, RelationshipRepositoryPort, SceneRepositoryPort, SheetTemplateRepositoryPort,
     SkillRepositoryPort, SocialNetwork, StoryEventRepositoryPort, WantRepositoryPort,
@@ -42,6 +44,8 @@ pub use session_management_port::{
 };
 // Note: ProposedToolInfo is now in domain::value_objects
 
+pub use roll_log_port::{RollLogError, RollLogPort};
+
 pub use settings_port::{SettingsError, SettingsRepositoryPort};
 
 pub use queue_notification_port::{QueueNotificationPort, WaitResult};
@@ -52,7 +56,7 @@ pub use queue_port::{
 pub use crate::domain::value_objects::QueueItemId;
 
 pub use world_exporter_port::{
-    CharacterData, ExportOptions, LocationData, PlayerWorldSnapshot, SceneData,
+    CharacterData, ExportOptions, ItemData, LocationData, PlayerWorldSnapshot, SceneData,
     WorldData, WorldExporterPort,
 };
 
@@ -10,14 +10,15 @@ use serde::{Deserialize, Serialize};
 use crate::domain::entities::{
     Act, ActantialRole, ActantialView, AcquisitionMethod, ChainStatus, Challenge,
     ChallengeLocationAvailability, ChallengePrerequisite, Character, CharacterSheetTemplate,
-    CharacterWant, EventChain, EventChainMembership, FeaturedNpc, FrequencyLevel, GalleryAsset,
-    GenerationBatch, Goal, GridMap, InteractionRequirement, InteractionTargetType,
+    CharacterWant, ComplexChallengeProgress, EventChain, EventChainMembership, FeaturedNpc,
+    FrequencyLevel, GalleryAsset,
+    GenerationBatch, Goal, GridMap, GridToken, InteractionRequirement, InteractionTargetType,
     InteractionTemplate, InventoryItem, InvolvedCharacter, Item, Location, LocationConnection,
-    NarrativeEvent, PlayerCharacter, Region, Scene, SceneCharacter, SceneCharacterRole, SheetTemplateId,
+    NarrativeEvent, PlayerCharacter, Region, RegionConnection, Scene, SceneCharacter, SceneCharacterRole, SheetTemplateId,
     Skill, StoryEvent, Want, World, WorkflowConfiguration,
 };
 use crate::domain::value_objects::{
-    ActId, AssetId, BatchId, ChallengeId, CharacterId, EventChainId, GoalId, GridMapId,
+    ActId, AssetId, BatchId, ChallengeId, CharacterId, EventChainId, GoalId, GridMapId, GridTokenId,
     InteractionId, ItemId, LocationId, NarrativeEventId, PlayerCharacterId, RegionId,
     RegionRelationshipType, Relationship, RelationshipId, SceneId, SessionId, SkillId,
     StoryEventId, WantId, WorldId,
@@ -171,7 +172,8 @@ pub trait CharacterRepositoryPort: Send + Sync {
     // Inventory (POSSESSES edges to Item nodes)
     // -------------------------------------------------------------------------
 
-    /// Add an item to character's inventory
+    /// Add an item from the catalogue of the character's world to their
+    /// inventory, adding to the quantity if they already hold some
     async fn add_inventory_item(
         &self,
         character_id: CharacterId,
@@ -301,6 +303,9 @@ pub trait PlayerCharacterRepositoryPort: Send + Sync {
         world_id: WorldId,
     ) -> Result<Vec<PlayerCharacter>>;
 
+    /// Get all player characters in a world
+    async fn get_by_world(&self, world_id: WorldId) -> Result<Vec<PlayerCharacter>>;
+
     /// Get all unbound player characters for a user (no session)
     async fn get_unbound_by_user(&self, user_id: &str) -> Result<Vec<PlayerCharacter>>;
 
@@ -640,10 +645,33 @@ pub trait GridMapRepositoryPort: Send + Sync {
     /// Get a grid map by ID
     async fn get(&self, id: GridMapId) -> Result<Option<GridMap>>;
 
+    /// List all grid maps in a world
+    async fn list(&self, world_id: WorldId) -> Result<Vec<GridMap>>;
+
     /// Delete a grid map
     async fn delete(&self, id: GridMapId) -> Result<()>;
 }
 
+// =============================================================================
+// Grid Token Repository Port
+// =============================================================================
+
+/// Repository port for GridToken operations
+#[async_trait]
+pub trait GridTokenRepositoryPort: Send + Sync {
+    /// Save a grid token
+    async fn save(&self, token: &GridToken) -> Result<()>;
+
+    /// Get a grid token by ID
+    async fn get(&self, id: GridTokenId) -> Result<Option<GridToken>>;
+
+    /// List all tokens on a grid map
+    async fn list(&self, grid_map_id: GridMapId) -> Result<Vec<GridToken>>;
+
+    /// Delete a grid token
+    async fn delete(&self, id: GridTokenId) -> Result<()>;
+}
+
 // =============================================================================
 // Skill Repository Port
 // =============================================================================
@@ -691,6 +719,9 @@ pub trait ItemRepositoryPort: Send + Sync {
 
     /// Get items by type
     async fn get_by_type(&self, world_id: WorldId, item_type: &str) -> Result<Vec<Item>>;
+
+    /// How many of an item are held across every inventory
+    async fn held_count(&self, id: ItemId) -> Result<u32>;
 }
 
 // =============================================================================
@@ -929,6 +960,22 @@ pub trait ChallengeRepositoryPort: Send + Sync {
         challenge_id: ChallengeId,
         location_id: LocationId,
     ) -> Result<()>;
+
+    // -------------------------------------------------------------------------
+    // Complex Challenge Progress (HAS_PROGRESS)
+    // -------------------------------------------------------------------------
+
+    /// Get the progress made on a complex challenge
+    async fn get_complex_progress(
+        &self,
+        challenge_id: ChallengeId,
+    ) -> Result<Option<ComplexChallengeProgress>>;
+
+    /// Save the progress made on a complex challenge (creates or replaces it)
+    async fn save_complex_progress(&self, progress: &ComplexChallengeProgress) -> Result<()>;
+
+    /// Clear a complex challenge's progress so it can be attempted again
+    async fn clear_complex_progress(&self, challenge_id: ChallengeId) -> Result<()>;
 }
 
 // =============================================================================
@@ -1365,6 +1412,9 @@ pub trait RegionRepositoryPort: Send + Sync {
     /// List all spawn point regions in a world
     async fn list_spawn_points(&self, world_id: WorldId) -> Result<Vec<Region>>;
 
+    /// Create a connection between two regions
+    async fn create_connection(&self, connection: &RegionConnection) -> Result<()>;
+
     /// Get all NPCs with relationships to a region (for presence determination)
     async fn get_npcs_related_to_region(
         &self,
@@ -0,0 +1,49 @@
+//! Roll Log Port - Interface for persisting audited dice rolls
+//!
+//! Every engine roll made during a session is stored so that the DM can
+//! review and replay it later.
+
+use async_trait::async_trait;
+
+use crate::domain::entities::RollLogEntry;
+use crate::domain::value_objects::{RollId, SessionId};
+
+/// Port for storing and querying the roll log
+#[async_trait]
+pub trait RollLogPort: Send + Sync {
+    /// Append a roll to the log
+    async fn record(&self, entry: &RollLogEntry) -> Result<(), RollLogError>;
+
+    /// Fetch a single roll by ID
+    async fn get(&self, id: RollId) -> Result<Option<RollLogEntry>, RollLogError>;
+
+    /// List a session's rolls, most recent first
+    async fn list_for_session(
+        &self,
+        session_id: SessionId,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<RollLogEntry>, RollLogError>;
+}
+
+/// Errors that can occur when accessing the roll log
+#[derive(Debug)]
+pub enum RollLogError {
+    /// Database or storage-level error
+    StorageError(String),
+    /// Serialization/deserialization error
+    SerializationError(String),
+}
+
+impl std::fmt::Display for RollLogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RollLogError::StorageError(msg) => write!(f, "Roll log storage error: {}", msg),
+            RollLogError::SerializationError(msg) => {
+                write!(f, "Roll log serialization error: {}", msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RollLogError {}
@@ -5,6 +5,7 @@
 //! maintaining hexagonal architecture boundaries.
 
 use crate::domain::value_objects::{ProposedToolInfo, SessionId, WorldId};
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
 use super::SessionParticipantRole;
@@ -174,6 +175,9 @@ pub trait SessionManagementPort: Send + Sync {
         &self,
         session_id: SessionId,
     ) -> Option<SessionWorldContext>;
+
+    /// Get the session's current in-game time
+    fn get_game_time(&self, session_id: SessionId) -> Option<DateTime<Utc>>;
 }
 
 /// World context data for building LLM prompts
@@ -8,7 +8,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::application::dto::RuleSystemConfigDto;
+use crate::application::dto::{GridMapDto, RuleSystemConfigDto};
 use crate::domain::value_objects::{SceneId, WorldId};
 
 /// Simplified world snapshot for Player clients
@@ -23,6 +23,9 @@ pub struct PlayerWorldSnapshot {
     pub locations: Vec<LocationData>,
     /// All characters in the world
     pub characters: Vec<CharacterData>,
+    /// The world's item catalogue
+    #[serde(default)]
+    pub items: Vec<ItemData>,
     /// All scenes in the world
     pub scenes: Vec<SceneData>,
     /// The current active scene (if any)
@@ -49,6 +52,9 @@ pub struct LocationData {
     pub location_type: String,
     pub backdrop_asset: Option<String>,
     pub atmosphere: Option<String>,
+    /// The location's tactical map, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_map: Option<GridMapDto>,
     // Note: parent_id is now derived from CONTAINS_LOCATION edges, not stored here
 }
 
@@ -65,6 +71,17 @@ pub struct CharacterData {
     pub is_active: bool,
 }
 
+/// Item data for Player clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemData {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub item_type: Option<String>,
+    pub is_unique: bool,
+    pub properties: Option<String>,
+}
+
 /// Scene data for Player clients
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SceneData {
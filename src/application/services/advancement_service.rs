@@ -0,0 +1,338 @@
+//! Advancement Service
+//!
+//! Awards experience and milestones to player characters and levels them up
+//! along the progression their sheet template or rule system defines. Awards
+//! are banked straight away; the levels they earn wait for the DM, who is
+//! told when a character becomes eligible. Gaining a level updates the
+//! sheet, opens the fields and skills it unlocks and is recorded on the story
+//! timeline as a `LevelGained` event.
+
+use anyhow::{Context, Result};
+use std::sync::Arc;
+
+use crate::application::dto::{
+    ExperienceAwardedNotification, LevelGainedNotification, LevelUpAvailableNotification,
+};
+use crate::application::ports::outbound::{
+    AsyncSessionPort, PlayerCharacterRepositoryPort, SkillRepositoryPort, WorldRepositoryPort,
+};
+use crate::application::services::{PlayerCharacterService, StoryEventService};
+use crate::domain::entities::{CharacterSheetTemplate, PlayerCharacter};
+use crate::domain::value_objects::{
+    AdvancementAward, AdvancementMode, CharacterId, PlayerCharacterId, ProgressionTable, SessionId,
+    WorldId,
+};
+
+/// Error type for advancement operations
+#[derive(Debug, thiserror::Error)]
+pub enum AdvancementError {
+    #[error("World {0} has no progression to advance along")]
+    NoProgression(WorldId),
+    #[error("{0} has not earned a new level")]
+    NotEligible(String),
+}
+
+/// Where a character stands on its world's progression
+#[derive(Debug, Clone)]
+pub struct AdvancementStatus {
+    pub pc: PlayerCharacter,
+    pub mode: AdvancementMode,
+    /// The level the character is at
+    pub level: u32,
+    /// The level its progress has earned; above `level` while a level-up
+    /// waits for approval
+    pub earned_level: u32,
+    /// Progress needed for the next level, if there is one
+    pub next_threshold: Option<u32>,
+}
+
+impl AdvancementStatus {
+    fn new(mut pc: PlayerCharacter, template: &CharacterSheetTemplate, table: &ProgressionTable) -> Self {
+        pc.advancement.level = template.character_level(&pc, table);
+        let level = pc.advancement.level;
+        Self {
+            mode: table.mode,
+            level,
+            earned_level: pc.advancement.earned_level(table),
+            next_threshold: table.next_level(level).map(|l| l.threshold),
+            pc,
+        }
+    }
+
+    pub fn can_level_up(&self) -> bool {
+        self.earned_level > self.level
+    }
+}
+
+/// One level a character gained
+#[derive(Debug, Clone)]
+pub struct GainedLevel {
+    pub level: u32,
+    /// Names of the sheet fields the level unlocked
+    pub unlocked_fields: Vec<String>,
+    /// Names of the skills the character became proficient in
+    pub unlocked_skills: Vec<String>,
+}
+
+/// What an approved level-up did for a character
+#[derive(Debug, Clone)]
+pub struct LevelUpOutcome {
+    pub pc: PlayerCharacter,
+    pub levels: Vec<GainedLevel>,
+}
+
+/// Service for awarding progress and levelling characters up
+#[derive(Clone)]
+pub struct AdvancementService {
+    player_character_service: Arc<dyn PlayerCharacterService>,
+    pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
+    world_repository: Arc<dyn WorldRepositoryPort>,
+    skill_repository: Arc<dyn SkillRepositoryPort>,
+    story_event_service: StoryEventService,
+    sessions: Arc<dyn AsyncSessionPort>,
+}
+
+impl AdvancementService {
+    /// Create a new advancement service
+    pub fn new(
+        player_character_service: Arc<dyn PlayerCharacterService>,
+        pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
+        world_repository: Arc<dyn WorldRepositoryPort>,
+        skill_repository: Arc<dyn SkillRepositoryPort>,
+        story_event_service: StoryEventService,
+        sessions: Arc<dyn AsyncSessionPort>,
+    ) -> Self {
+        Self {
+            player_character_service,
+            pc_repository,
+            world_repository,
+            skill_repository,
+            story_event_service,
+            sessions,
+        }
+    }
+
+    /// The progression a world's characters follow, if it has one
+    pub async fn progression(&self, world_id: WorldId) -> Result<Option<ProgressionTable>> {
+        match self.progression_for(world_id).await {
+            Ok((_, table)) => Ok(Some(table)),
+            Err(e) if e.downcast_ref::<AdvancementError>().is_some() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Where a character stands; None if the character is unknown
+    ///
+    /// Fails with `AdvancementError::NoProgression` if its world has none.
+    pub async fn status(&self, pc_id: PlayerCharacterId) -> Result<Option<AdvancementStatus>> {
+        let Some(pc) = self.pc_repository.get(pc_id).await? else {
+            return Ok(None);
+        };
+        let (template, table) = self.progression_for(pc.world_id).await?;
+        Ok(Some(AdvancementStatus::new(pc, &template, &table)))
+    }
+
+    /// Award experience or milestones to a character
+    ///
+    /// Everyone in the character's session hears of the award, and the DM is
+    /// told if it earned a level. Returns None if the character is unknown.
+    pub async fn award(
+        &self,
+        pc_id: PlayerCharacterId,
+        award: AdvancementAward,
+        reason: Option<String>,
+    ) -> Result<Option<AdvancementStatus>> {
+        let Some(pc) = self.pc_repository.get(pc_id).await? else {
+            return Ok(None);
+        };
+        let (template, table) = self.progression_for(pc.world_id).await?;
+        self.award_to(pc, &template, &table, award, reason).await.map(Some)
+    }
+
+    /// Award experience or milestones to every character in a session
+    pub async fn award_session(
+        &self,
+        session_id: SessionId,
+        award: AdvancementAward,
+        reason: Option<String>,
+    ) -> Result<Vec<AdvancementStatus>> {
+        let Some(world_id) = self.sessions.get_session_world_id(session_id).await else {
+            return Ok(Vec::new());
+        };
+        let (template, table) = self.progression_for(world_id).await?;
+        let pcs = self.pc_repository.get_by_session(session_id).await?;
+
+        let mut awarded = Vec::with_capacity(pcs.len());
+        for pc in pcs {
+            awarded.push(
+                self.award_to(pc, &template, &table, award, reason.clone())
+                    .await?,
+            );
+        }
+        Ok(awarded)
+    }
+
+    /// Characters in a world with a level-up waiting for approval
+    pub async fn pending_level_ups(&self, world_id: WorldId) -> Result<Vec<AdvancementStatus>> {
+        let (template, table) = self.progression_for(world_id).await?;
+        Ok(self
+            .pc_repository
+            .get_by_world(world_id)
+            .await?
+            .into_iter()
+            .map(|pc| AdvancementStatus::new(pc, &template, &table))
+            .filter(AdvancementStatus::can_level_up)
+            .collect())
+    }
+
+    /// Approve the levels a character has earned
+    ///
+    /// Each level updates the sheet, opens what it unlocks and is recorded on
+    /// the timeline. Fails with `AdvancementError::NotEligible` if nothing is
+    /// waiting. Returns None if the character is unknown.
+    pub async fn level_up(
+        &self,
+        pc_id: PlayerCharacterId,
+        game_time: Option<String>,
+    ) -> Result<Option<LevelUpOutcome>> {
+        let Some(pc) = self.pc_repository.get(pc_id).await? else {
+            return Ok(None);
+        };
+        let (template, table) = self.progression_for(pc.world_id).await?;
+        let status = AdvancementStatus::new(pc, &template, &table);
+        if !status.can_level_up() {
+            return Err(AdvancementError::NotEligible(status.pc.name).into());
+        }
+
+        let skills = self.skill_repository.list(status.pc.world_id).await?;
+        let mut pc = status.pc;
+        let mut levels = Vec::new();
+        for level in table.levels_gained(status.level, status.earned_level) {
+            let sheet = pc.sheet_data.get_or_insert_with(Default::default);
+            let unlocked_fields = template.gain_level(sheet, &table, level);
+
+            // Skills are named in the table; grant the world's skill of that
+            // name, or the name itself if the world has none
+            let mut unlocked_skills = Vec::new();
+            for name in &level.unlocks_skills {
+                let skill = skills.iter().find(|skill| {
+                    skill.name.eq_ignore_ascii_case(name) || skill.id.to_string() == *name
+                });
+                let (skill_id, skill_name) = match skill {
+                    Some(skill) => (skill.id.to_string(), skill.name.clone()),
+                    None => (name.clone(), name.clone()),
+                };
+                if sheet.grant_proficiency(&skill_id) {
+                    unlocked_skills.push(skill_name);
+                }
+            }
+
+            levels.push(GainedLevel {
+                level: level.level,
+                unlocked_fields,
+                unlocked_skills,
+            });
+        }
+        pc.advancement.level = status.earned_level;
+
+        self.player_character_service
+            .compute_derived_fields(std::slice::from_mut(&mut pc))
+            .await?;
+        self.pc_repository.update(&pc).await?;
+
+        for gained in &levels {
+            let mut unlocked = gained.unlocked_fields.clone();
+            unlocked.extend(gained.unlocked_skills.iter().cloned());
+            if let Err(e) = self
+                .story_event_service
+                .record_level_gained(
+                    pc.world_id,
+                    pc.session_id,
+                    CharacterId::from_uuid(*pc.id.as_uuid()),
+                    pc.name.clone(),
+                    gained.level,
+                    unlocked,
+                    game_time.clone(),
+                )
+                .await
+            {
+                tracing::warn!("Failed to record level {} for {}: {}", gained.level, pc.id, e);
+            }
+        }
+
+        tracing::info!("{} reached level {}", pc.name, pc.advancement.level);
+        let outcome = LevelUpOutcome { pc, levels };
+        if let Some(session_id) = self.sessions.find_session_for_world(outcome.pc.world_id).await {
+            self.notify(session_id, LevelGainedNotification::new(&outcome), false)
+                .await;
+        }
+        Ok(Some(outcome))
+    }
+
+    /// The template and progression a world's characters use
+    async fn progression_for(&self, world_id: WorldId) -> Result<(CharacterSheetTemplate, ProgressionTable)> {
+        let world = self
+            .world_repository
+            .get(world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
+        let template = self
+            .player_character_service
+            .sheet_template(world_id)
+            .await?
+            .context("World not found")?;
+        let table = template
+            .progression_for(&world.rule_system)
+            .ok_or(AdvancementError::NoProgression(world_id))?;
+        Ok((template, table))
+    }
+
+    async fn award_to(
+        &self,
+        mut pc: PlayerCharacter,
+        template: &CharacterSheetTemplate,
+        table: &ProgressionTable,
+        award: AdvancementAward,
+        reason: Option<String>,
+    ) -> Result<AdvancementStatus> {
+        let earned_before = AdvancementStatus::new(pc.clone(), template, table).earned_level;
+        pc.advancement.award(award);
+        self.pc_repository.update(&pc).await?;
+        let status = AdvancementStatus::new(pc, template, table);
+        tracing::info!("{} was awarded {}", status.pc.name, award);
+
+        if let Some(session_id) = self.sessions.find_session_for_world(status.pc.world_id).await {
+            self.notify(
+                session_id,
+                ExperienceAwardedNotification::new(&status, award, reason),
+                false,
+            )
+            .await;
+            if status.earned_level > earned_before {
+                self.notify(session_id, LevelUpAvailableNotification::new(&status), true)
+                    .await;
+            }
+        }
+        Ok(status)
+    }
+
+    /// Send a notification to the session, or only to its DM; delivery
+    /// failures are only reported
+    async fn notify(&self, session_id: SessionId, notification: impl serde::Serialize, dm_only: bool) {
+        let message = match serde_json::to_value(&notification) {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::error!("Failed to serialize advancement notification: {}", e);
+                return;
+            }
+        };
+        let sent = if dm_only {
+            self.sessions.send_to_dm(session_id, message).await
+        } else {
+            self.sessions.broadcast_to_session(session_id, message).await
+        };
+        if let Err(e) = sent {
+            tracing::warn!("Failed to send advancement notification: {}", e);
+        }
+    }
+}
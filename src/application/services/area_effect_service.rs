@@ -0,0 +1,152 @@
+//! Area Effect Service
+//!
+//! Resolves spells and hazards that fill an area of a grid map: which tiles
+//! the template catches, walls permitting, and which tokens stand on them.
+//! Applying an effect shows it to the session (hidden tokens it catches are
+//! only named to the DM) and, given a saving throw, prompts the player of
+//! every player character caught in it to roll.
+
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::application::dto::{AreaEffectDto, AreaEffectNotification};
+use crate::application::ports::outbound::AsyncSessionPort;
+use crate::application::services::{GridMapService, GridTokenService, SavingThrowTrigger};
+use crate::domain::entities::{AreaTemplate, GridMapError, GridPosition, GridToken, TokenSubject};
+use crate::domain::value_objects::{ChallengeId, GridMapId, PlayerCharacterId, SessionId};
+
+/// What an area of effect caught
+#[derive(Debug, Clone)]
+pub struct AreaOfEffect {
+    pub grid_map_id: GridMapId,
+    /// Tiles caught, row by row
+    pub tiles: Vec<GridPosition>,
+    /// Tokens standing on any of them
+    pub tokens: Vec<GridToken>,
+    /// Player characters called on to save
+    pub saving_throws: Vec<PlayerCharacterId>,
+}
+
+/// Service for areas of effect on grid maps
+#[derive(Clone)]
+pub struct AreaEffectService {
+    grid_map_service: Arc<dyn GridMapService>,
+    grid_token_service: GridTokenService,
+    sessions: Arc<dyn AsyncSessionPort>,
+    saving_throws: Option<Arc<dyn SavingThrowTrigger>>,
+}
+
+impl AreaEffectService {
+    /// Create a new area effect service
+    pub fn new(
+        grid_map_service: Arc<dyn GridMapService>,
+        grid_token_service: GridTokenService,
+        sessions: Arc<dyn AsyncSessionPort>,
+    ) -> Self {
+        Self {
+            grid_map_service,
+            grid_token_service,
+            sessions,
+            saving_throws: None,
+        }
+    }
+
+    /// Set the saving throw trigger, so effects can call for saves
+    pub fn with_saving_throws(mut self, saving_throws: Arc<dyn SavingThrowTrigger>) -> Self {
+        self.saving_throws = Some(saving_throws);
+        self
+    }
+
+    /// The tiles and tokens an area of effect would catch
+    pub async fn resolve(&self, grid_map_id: GridMapId, template: &AreaTemplate) -> Result<AreaOfEffect> {
+        let grid_map = self
+            .grid_map_service
+            .get_grid_map(grid_map_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Grid map not found: {}", grid_map_id))?;
+        let (x, y) = template.origin();
+        if !grid_map.contains(x, y) {
+            return Err(GridMapError::OutOfBounds { x, y }.into());
+        }
+
+        let caught = grid_map.area_tiles(template);
+        let mut tiles: Vec<GridPosition> = caught.iter().copied().collect();
+        tiles.sort_by_key(|&(x, y)| (y, x));
+        let tokens = self
+            .grid_token_service
+            .list(grid_map_id, true)
+            .await?
+            .into_iter()
+            .filter(|token| token.footprint().iter().any(|tile| caught.contains(tile)))
+            .collect();
+
+        Ok(AreaOfEffect {
+            grid_map_id,
+            tiles,
+            tokens,
+            saving_throws: Vec::new(),
+        })
+    }
+
+    /// Set off an area of effect in a session
+    ///
+    /// Given a saving throw challenge, each player character caught is
+    /// prompted to roll it.
+    pub async fn apply(
+        &self,
+        session_id: SessionId,
+        grid_map_id: GridMapId,
+        template: &AreaTemplate,
+        description: &str,
+        saving_throw: Option<ChallengeId>,
+    ) -> Result<AreaOfEffect> {
+        let mut area = self.resolve(grid_map_id, template).await?;
+
+        if let Some(challenge_id) = saving_throw {
+            let pc_ids: Vec<PlayerCharacterId> = area
+                .tokens
+                .iter()
+                .filter_map(|token| match token.subject {
+                    TokenSubject::Pc(pc_id) => Some(pc_id),
+                    _ => None,
+                })
+                .collect();
+            match self.saving_throws.as_ref() {
+                Some(saving_throws) if !pc_ids.is_empty() => {
+                    area.saving_throws = saving_throws
+                        .trigger_saving_throws(session_id, challenge_id, &pc_ids)
+                        .await?;
+                }
+                Some(_) => {}
+                None => tracing::warn!("No saving throw trigger set; {} calls for no saves", description),
+            }
+        }
+        tracing::info!(
+            "{}: {} tiles and {} tokens caught on grid map {}",
+            description,
+            area.tiles.len(),
+            area.tokens.len(),
+            grid_map_id
+        );
+
+        self.notify(session_id, description, &area).await;
+        Ok(area)
+    }
+
+    /// Show the effect to the session; players only hear of visible tokens
+    async fn notify(&self, session_id: SessionId, description: &str, area: &AreaOfEffect) {
+        let dm_view = AreaEffectNotification::new(description, AreaEffectDto::new(area, true));
+        let player_view = AreaEffectNotification::new(description, AreaEffectDto::new(area, false));
+        match (serde_json::to_value(dm_view), serde_json::to_value(player_view)) {
+            (Ok(dm_message), Ok(player_message)) => {
+                if let Err(e) = self.sessions.send_to_dm(session_id, dm_message).await {
+                    tracing::warn!("Failed to send area effect to DM: {}", e);
+                }
+                if let Err(e) = self.sessions.broadcast_to_players(session_id, player_message).await {
+                    tracing::warn!("Failed to send area effect to players: {}", e);
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => tracing::error!("Failed to serialize area effect: {}", e),
+        }
+    }
+}
@@ -18,6 +18,7 @@ use crate::application::dto::{
 };
 use crate::application::ports::outbound::{AsyncSessionPort, LlmPort};
 use crate::application::services::{OutcomeSuggestionService, OutcomeTriggerService, SettingsService};
+use crate::domain::entities::OutcomeTrigger;
 use crate::domain::value_objects::SessionId;
 
 /// Error type for challenge outcome approval operations
@@ -29,6 +30,8 @@ pub enum ChallengeOutcomeError {
     SessionError(String),
     #[error("Invalid state: {0}")]
     InvalidState(String),
+    #[error("Roll error: {0}")]
+    RollError(String),
 }
 
 /// Service for managing challenge outcome approvals
@@ -101,7 +104,10 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
             modifier: resolution.modifier,
             total: resolution.total,
             outcome_type: resolution.outcome_type.clone(),
+            outcome_tier: resolution.outcome_tier,
+            margin: resolution.margin,
             outcome_description: resolution.outcome_description.clone(),
+            triggers: resolution.outcome_triggers.clone(),
             outcome_triggers: resolution
                 .outcome_triggers
                 .into_iter()
@@ -113,6 +119,8 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
                 })
                 .collect(),
             roll_breakdown: resolution.roll_breakdown,
+            roll_id: resolution.roll_id,
+            opposed: resolution.opposed,
             timestamp: Utc::now(),
             suggestions: None,
             is_generating_suggestions: false,
@@ -177,6 +185,13 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
                 // Remove from pending
                 self.remove_pending(resolution_id).await;
             }
+            ChallengeOutcomeDecision::GrantRollMode { .. }
+            | ChallengeOutcomeDecision::SetOpponentModifier { .. } => {
+                // Re-evaluating needs the challenge and the roll log
+                return Err(ChallengeOutcomeError::InvalidState(
+                    "Rolls are re-evaluated through challenge resolution".to_string(),
+                ));
+            }
             ChallengeOutcomeDecision::Suggest { guidance } => {
                 // Mark as generating suggestions
                 self.set_generating_suggestions(resolution_id, true).await;
@@ -300,6 +315,12 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
         }
     }
 
+    /// Get a single pending resolution
+    pub async fn get_pending(&self, resolution_id: &str) -> Option<ChallengeOutcomeApprovalItem> {
+        let pending = self.pending.read().await;
+        pending.get(resolution_id).cloned()
+    }
+
     /// Get all pending resolutions for a session
     pub async fn get_pending_for_session(
         &self,
@@ -331,9 +352,13 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
             item.modifier,
             item.total,
             item.outcome_type.clone(),
+            item.outcome_tier,
+            item.margin,
             description.clone(),
             item.roll_breakdown.clone(),
             None,
+            item.roll_id.clone(),
+            item.opposed.clone(),
         );
 
         // Broadcast to all session participants
@@ -345,8 +370,21 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
             .map_err(|e| ChallengeOutcomeError::SessionError(e.to_string()))?;
 
         // Execute outcome triggers
-        // TODO: Parse outcome_triggers from ProposedToolInfo back to OutcomeTrigger
-        // For now, log that we would execute triggers
+        let triggers: Vec<OutcomeTrigger> = item.triggers.iter().cloned().map(OutcomeTrigger::from).collect();
+        if !triggers.is_empty() {
+            let trigger_result = self
+                .outcome_trigger_service
+                .execute_triggers(&triggers, self.sessions.as_ref(), session_id, &item.character_id)
+                .await;
+            if !trigger_result.warnings.is_empty() {
+                tracing::info!(
+                    "Outcome triggers for challenge {} executed with warnings: {:?}",
+                    item.challenge_id,
+                    trigger_result.warnings
+                );
+            }
+        }
+
         tracing::info!(
             "Challenge {} resolved with outcome: {}",
             item.challenge_id,
@@ -375,6 +413,8 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
             item.outcome_description.clone(),
             item.outcome_triggers.clone(),
             item.roll_breakdown.clone(),
+            item.roll_id.clone(),
+            item.opposed.clone(),
         );
 
         let value = serde_json::to_value(&msg)
@@ -400,6 +440,7 @@ impl<L: LlmPort + 'static> ChallengeOutcomeApprovalService<L> {
             item.modifier,
             item.total,
             item.outcome_type.clone(),
+            item.roll_id.clone(),
         );
 
         // Broadcast to all session participants (they'll see the roll is pending)
@@ -6,19 +6,36 @@
 //!
 //! Uses `AsyncSessionPort` for session operations, maintaining hexagonal architecture.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::application::dto::{AdHocOutcomesDto, AppEvent};
+use async_trait::async_trait;
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
+
+use crate::application::dto::{
+    AdHocOutcomesDto, AppEvent, ChallengeOddsDto, ChallengeOutcomeApprovalItem,
+    ComplexChallengeProgressDto, OpponentDto, OpposedRollDto,
+};
 use crate::application::ports::outbound::AsyncSessionPort;
 use crate::application::ports::outbound::EventBusPort;
 use crate::application::ports::outbound::ApprovalQueuePort;
+use crate::application::ports::outbound::{CharacterRepositoryPort, WorldRepositoryPort};
 use crate::application::dto::{OutcomeTriggerRequestDto, PendingChallengeResolutionDto};
 use crate::application::services::{
-    ChallengeOutcomeApprovalService, ChallengeService, DMApprovalQueueService, OutcomeTriggerService,
-    PlayerCharacterService, SkillService,
+    ChallengeOutcomeApprovalService, ChallengeOutcomeError, ChallengeService, DMApprovalQueueService, DiceRollService,
+    GrantRollError, OutcomeTriggerService, PlayerCharacterService, SkillService,
+};
+use crate::domain::entities::{
+    Challenge, ChallengeType, ComplexChallengeProgress, ComplexChallengeRoll, ComplexChallengeSettings,
+    ComplexChallengeStatus, OutcomeType, RollPurpose,
+};
+use crate::domain::services::{
+    evaluate_opposed, evaluate_roll, ChallengeOdds, RollEvaluation, RollOutcome,
+};
+use crate::domain::value_objects::{
+    CharacterId, ChallengeId, DescriptorTarget, DiceFormula, DiceParseError, DiceRollInput, DiceRollResult, DiceSystem, FateLadder,
+    RollId, RollMode, RuleSystemConfig, RuleSystemType, SessionId, PlayerCharacterId, SkillId, WorldId,
 };
-use crate::domain::entities::OutcomeType;
-use crate::domain::value_objects::{ChallengeId, DiceRollInput, SessionId, PlayerCharacterId, SkillId};
 use tracing::{debug, info};
 
 /// Dice input type for challenge rolls
@@ -42,9 +59,14 @@ struct ChallengeResolvedMessage {
     modifier: i32,
     total: i32,
     outcome: String,
+    outcome_type: OutcomeType,
+    margin: Option<i32>,
     outcome_description: String,
     roll_breakdown: Option<String>,
     individual_rolls: Option<Vec<i32>>,
+    roll_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opposed: Option<OpposedRollDto>,
 }
 
 /// Challenge prompt message DTO
@@ -59,6 +81,16 @@ struct ChallengePromptMessage {
     character_modifier: i32,
     suggested_dice: Option<String>,
     rule_system_hint: Option<String>,
+    /// Outcome odds, only included in the DM's copy of the prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    odds: Option<ChallengeOddsDto>,
+}
+
+/// Complex challenge progress message DTO
+#[derive(Debug, Clone, serde::Serialize)]
+struct ComplexChallengeProgressMessage {
+    r#type: &'static str,
+    progress: ComplexChallengeProgressDto,
 }
 
 /// Error message DTO
@@ -93,7 +125,10 @@ fn error_message(code: &str, message: &str) -> Option<serde_json::Value> {
 /// This struct holds the common data needed by both `handle_roll` and `handle_roll_input`.
 struct ChallengePreamble {
     challenge: crate::domain::entities::Challenge,
-    /// Skill ID fetched from REQUIRES_SKILL edge (may be None if no skill is set)
+    /// The world's rule system, for degree-of-success bands and opposed ties
+    rule_system: RuleSystemConfig,
+    /// Skill ID fetched from REQUIRES_SKILL edge (may be None if no skill is set),
+    /// or the skill the player chose for a round of a complex challenge
     skill_id: Option<crate::domain::value_objects::SkillId>,
     session_id: Option<SessionId>,
     player_name: String,
@@ -101,6 +136,79 @@ struct ChallengePreamble {
     character_id: String,
 }
 
+/// A roll made towards a challenge, by a player or by the engine for an NPC opponent
+#[derive(Debug, Clone)]
+struct SubmittedRoll {
+    character_id: String,
+    name: String,
+    outcome: RollOutcome,
+    /// Modifier shown with the roll
+    modifier: i32,
+    total: i32,
+    roll_breakdown: Option<String>,
+    individual_rolls: Option<Vec<i32>>,
+    roll_id: Option<RollId>,
+}
+
+/// The opposing side of a pending opposed check
+#[derive(Debug, Clone)]
+enum PendingOpponent {
+    /// Rolled by the engine as soon as the initiator rolls
+    Npc {
+        character_id: CharacterId,
+        name: String,
+        modifier: i32,
+    },
+    /// Rolls for themselves; held until both sides are in
+    Pc {
+        pc_id: String,
+        roll: Option<SubmittedRoll>,
+    },
+}
+
+/// An opposed check triggered by the DM, waiting for its rolls
+#[derive(Debug, Clone)]
+struct PendingOpposedCheck {
+    /// Player character making the check
+    initiator_id: String,
+    initiator_roll: Option<SubmittedRoll>,
+    opponent: PendingOpponent,
+}
+
+impl PendingOpposedCheck {
+    /// Record a side's roll, failing with an error code and message for a
+    /// character outside the check or a side that has already rolled
+    ///
+    /// Each side rolls once; a second roll would let them keep rolling until
+    /// the other side commits.
+    fn record(&mut self, roll: SubmittedRoll) -> Result<(), (&'static str, &'static str)> {
+        let slot = if roll.character_id == self.initiator_id {
+            &mut self.initiator_roll
+        } else {
+            match &mut self.opponent {
+                PendingOpponent::Pc { pc_id, roll: opponent_roll } if *pc_id == roll.character_id => {
+                    opponent_roll
+                }
+                _ => return Err(("NOT_IN_OPPOSED_CHECK", "You are not taking part in this opposed check")),
+            }
+        };
+        if slot.is_some() {
+            return Err(("ALREADY_ROLLED", "You have already rolled for this opposed check"));
+        }
+        *slot = Some(roll);
+        Ok(())
+    }
+
+    /// Whether both sides are in; an NPC opponent is rolled on demand
+    fn is_ready(&self) -> bool {
+        let opponent_ready = match &self.opponent {
+            PendingOpponent::Npc { .. } => true,
+            PendingOpponent::Pc { roll, .. } => roll.is_some(),
+        };
+        self.initiator_roll.is_some() && opponent_ready
+    }
+}
+
 use crate::application::ports::outbound::LlmPort;
 
 /// Service responsible for challenge-related flows.
@@ -115,10 +223,18 @@ pub struct ChallengeResolutionService<S: ChallengeService, K: SkillService, Q: A
     challenge_service: Arc<S>,
     skill_service: Arc<K>,
     player_character_service: Arc<P>,
+    world_repository: Arc<dyn WorldRepositoryPort>,
+    character_repository: Arc<dyn CharacterRepositoryPort>,
     event_bus: Arc<dyn EventBusPort<AppEvent>>,
     dm_approval_queue_service: Arc<DMApprovalQueueService<Q>>,
     outcome_trigger_service: Arc<OutcomeTriggerService>,
+    dice_roll_service: Arc<DiceRollService>,
     challenge_outcome_approval_service: Option<Arc<ChallengeOutcomeApprovalService<L>>>,
+    /// Opposed checks waiting for rolls, keyed by session and challenge ID
+    opposed_checks: Arc<RwLock<HashMap<(SessionId, String), PendingOpposedCheck>>>,
+    /// One lock per complex challenge, held while its progress is read,
+    /// updated and saved so concurrent rolls cannot overwrite each other
+    complex_progress_locks: Arc<Mutex<HashMap<ChallengeId, Arc<Mutex<()>>>>>,
 }
 
 impl<S, K, Q, P, L> ChallengeResolutionService<S, K, Q, P, L>
@@ -129,24 +245,33 @@ where
     P: PlayerCharacterService,
     L: LlmPort + 'static,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         sessions: Arc<dyn AsyncSessionPort>,
         challenge_service: Arc<S>,
         skill_service: Arc<K>,
         player_character_service: Arc<P>,
+        world_repository: Arc<dyn WorldRepositoryPort>,
+        character_repository: Arc<dyn CharacterRepositoryPort>,
         event_bus: Arc<dyn EventBusPort<AppEvent>>,
         dm_approval_queue_service: Arc<DMApprovalQueueService<Q>>,
         outcome_trigger_service: Arc<OutcomeTriggerService>,
+        dice_roll_service: Arc<DiceRollService>,
     ) -> Self {
         Self {
             sessions,
             challenge_service,
             skill_service,
             player_character_service,
+            world_repository,
+            character_repository,
             event_bus,
             dm_approval_queue_service,
             outcome_trigger_service,
+            dice_roll_service,
             challenge_outcome_approval_service: None,
+            opposed_checks: Arc::new(RwLock::new(HashMap::new())),
+            complex_progress_locks: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -159,6 +284,74 @@ where
         self
     }
 
+    /// Drop the opposed checks still waiting for rolls in a session that has ended
+    pub async fn end_session(&self, session_id: SessionId) {
+        self.opposed_checks
+            .write()
+            .await
+            .retain(|(check_session_id, _), _| *check_session_id != session_id);
+    }
+
+    /// Take the lock on a complex challenge's progress
+    async fn lock_complex_progress(&self, challenge_id: ChallengeId) -> OwnedMutexGuard<()> {
+        let lock = self
+            .complex_progress_locks
+            .lock()
+            .await
+            .entry(challenge_id)
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    /// Look up the world's rule system, falling back to the default system
+    /// when the world cannot be loaded.
+    async fn rule_system(&self, world_id: WorldId) -> RuleSystemConfig {
+        match self.world_repository.get(world_id).await {
+            Ok(Some(world)) => world.rule_system,
+            Ok(None) => {
+                tracing::warn!("World {} not found, using the default rule system", world_id);
+                RuleSystemConfig::default()
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load world {}: {}, using the default rule system", world_id, e);
+                RuleSystemConfig::default()
+            }
+        }
+    }
+
+    /// The skill a challenge tests: its ID, its name, and the attribute it
+    /// falls back to
+    async fn challenge_skill(&self, challenge_id: ChallengeId) -> (Option<SkillId>, String, Option<String>) {
+        // Fetch skill_id from REQUIRES_SKILL edge
+        let skill_id = match self.challenge_service.get_required_skill(challenge_id).await {
+            Ok(skill_id) => skill_id,
+            Err(e) => {
+                tracing::warn!("Failed to get required skill for challenge {}: {}", challenge_id, e);
+                None
+            }
+        };
+
+        // Look up skill name (and the attribute it falls back to) from skill service
+        let (skill_name, base_attribute) = if let Some(ref sid) = skill_id {
+            match self.skill_service.get_skill(sid.clone()).await {
+                Ok(Some(skill)) => (skill.name, skill.base_attribute),
+                Ok(None) => {
+                    tracing::warn!("Skill {} not found for challenge", sid);
+                    (sid.to_string(), None)
+                }
+                Err(e) => {
+                    tracing::error!("Failed to look up skill {}: {}", sid, e);
+                    (sid.to_string(), None)
+                }
+            }
+        } else {
+            ("Unknown Skill".to_string(), None)
+        };
+
+        (skill_id, skill_name, base_attribute)
+    }
+
     /// Get a player character ID for a client in a session.
     ///
     /// This looks up the client's PC by matching their user_id in the session
@@ -218,11 +411,15 @@ where
     /// - Character modifier lookup
     /// - Character ID resolution
     ///
+    /// For complex challenges the player may choose which of the allowed skills
+    /// to roll; `chosen_skill` is ignored for other challenges.
+    ///
     /// Returns `Ok(preamble)` on success, or `Err(error_message)` on failure.
     async fn gather_challenge_preamble(
         &self,
         client_id: &str,
         challenge_id_str: &str,
+        chosen_skill: Option<&str>,
         log_prefix: &str,
     ) -> Result<ChallengePreamble, Option<serde_json::Value>> {
         // Parse challenge_id
@@ -257,6 +454,26 @@ where
             }
         };
 
+        // Complex challenges let the player pick the skill for each round
+        let skill_id = match (challenge.complex(), chosen_skill) {
+            (Some(settings), Some(chosen)) => {
+                let chosen = match uuid::Uuid::parse_str(chosen) {
+                    Ok(uuid) => SkillId::from_uuid(uuid),
+                    Err(_) => {
+                        return Err(error_message("INVALID_SKILL_ID", "Invalid skill ID format"));
+                    }
+                };
+                if !settings.allows_skill(&chosen, skill_id.as_ref()) {
+                    return Err(error_message(
+                        "SKILL_NOT_ALLOWED",
+                        "That skill cannot be used for this challenge",
+                    ));
+                }
+                Some(chosen)
+            }
+            _ => skill_id,
+        };
+
         // Get session and player info via async session port
         let session_id = match self.sessions.get_client_session(client_id).await {
             Some(sid) => Some(sid),
@@ -274,65 +491,85 @@ where
             .await
             .unwrap_or_else(|| "Unknown Player".to_string());
 
+        let pc_id = match session_id {
+            Some(session_id_val) => self.get_client_player_character(client_id, session_id_val).await,
+            None => None,
+        };
+
         // Look up character's skill modifier from PlayerCharacterService
-        let character_modifier = if let Some(session_id_val) = session_id {
-            if let Some(pc_id) = self.get_client_player_character(client_id, session_id_val).await {
-                if let Some(ref sid) = skill_id {
-                    match self
-                        .player_character_service
-                        .get_skill_modifier(pc_id, sid.clone())
-                        .await
-                    {
-                        Ok(modifier) => {
-                            debug!(
-                                pc_id = %pc_id,
-                                skill_id = %sid,
-                                modifier = modifier,
-                                "Found skill modifier for player character ({})", log_prefix
-                            );
-                            modifier
-                        }
-                        Err(e) => {
-                            debug!(
-                                pc_id = %pc_id,
-                                skill_id = %sid,
-                                error = %e,
-                                "Failed to get skill modifier, defaulting to 0 ({})", log_prefix
-                            );
-                            0
-                        }
+        let skill_modifier = if let Some(pc_id) = pc_id {
+            if let Some(ref sid) = skill_id {
+                match self
+                    .player_character_service
+                    .get_skill_modifier(pc_id, sid.clone())
+                    .await
+                {
+                    Ok(modifier) => {
+                        debug!(
+                            pc_id = %pc_id,
+                            skill_id = %sid,
+                            modifier = modifier,
+                            "Found skill modifier for player character ({})", log_prefix
+                        );
+                        modifier
+                    }
+                    Err(e) => {
+                        debug!(
+                            pc_id = %pc_id,
+                            skill_id = %sid,
+                            error = %e,
+                            "Failed to get skill modifier, defaulting to 0 ({})", log_prefix
+                        );
+                        0
                     }
-                } else {
-                    debug!(
-                        pc_id = %pc_id,
-                        "No skill assigned to challenge, defaulting modifier to 0 ({})", log_prefix
-                    );
-                    0
                 }
             } else {
                 debug!(
-                    session_id = %session_id_val,
-                    client_id = %client_id,
-                    "Could not find player character for client ({})", log_prefix
+                    pc_id = %pc_id,
+                    "No skill assigned to challenge, defaulting modifier to 0 ({})", log_prefix
                 );
                 0
             }
         } else {
+            debug!(
+                session_id = ?session_id,
+                client_id = %client_id,
+                "Could not find player character for client ({})", log_prefix
+            );
             0
         };
 
-        // Get character ID from player character lookup
-        let character_id = if let Some(session_id_val) = session_id {
-            self.get_client_player_character(client_id, session_id_val)
-                .await
-                .map(|id| id.to_string())
-                .unwrap_or_else(|| player_name.clone())
-        } else {
-            player_name.clone()
+        // Conditions on the character (Poisoned, Blessed) shift every roll they make
+        let condition_modifier = match pc_id {
+            Some(pc_id) => match self.player_character_service.get_pc(pc_id).await {
+                Ok(Some(pc)) => pc.conditions.roll_modifier(),
+                Ok(None) => 0,
+                Err(e) => {
+                    debug!(
+                        pc_id = %pc_id,
+                        error = %e,
+                        "Failed to load conditions, ignoring them ({})", log_prefix
+                    );
+                    0
+                }
+            },
+            None => 0,
         };
+        let character_modifier = skill_modifier + condition_modifier;
+
+        // Get character ID from player character lookup
+        let character_id = pc_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| player_name.clone());
+
+        let rule_system = self.rule_system(challenge.world_id).await;
+        // Rolls are judged against what a descriptor means in this world
+        let mut challenge = challenge;
+        challenge.difficulty = challenge.difficulty.resolve(&rule_system.descriptor_difficulties);
 
         Ok(ChallengePreamble {
             challenge,
+            rule_system,
             skill_id,
             session_id,
             player_name,
@@ -351,8 +588,7 @@ where
         challenge_id_str: &str,
         challenge: &crate::domain::entities::Challenge,
         skill_id: Option<SkillId>,
-        outcome_type: OutcomeType,
-        outcome: &crate::domain::entities::Outcome,
+        evaluation: RollEvaluation<'_>,
         session_id: Option<SessionId>,
         character_id: String,
         player_name: String,
@@ -361,7 +597,17 @@ where
         total: i32,
         roll_breakdown: Option<String>,
         individual_rolls: Option<Vec<i32>>,
+        roll_id: Option<RollId>,
+        opposed: Option<OpposedRollDto>,
     ) {
+        let outcome_name = evaluation.display_name();
+        let RollEvaluation {
+            outcome_type,
+            outcome,
+            margin,
+            ..
+        } = evaluation;
+
         // P3.3: If session has DM and approval service is configured, queue for approval
         if let Some(sid) = session_id {
             if self.sessions.session_has_dm(sid).await {
@@ -395,7 +641,9 @@ where
                         roll,
                         modifier,
                         total,
-                        outcome_type: outcome_type.display_name().to_string(),
+                        outcome_type: outcome_name.to_string(),
+                        outcome_tier: Some(outcome_type),
+                        margin,
                         outcome_description: outcome.description.clone(),
                         outcome_triggers: outcome
                             .triggers
@@ -405,6 +653,8 @@ where
                             .collect(),
                         roll_breakdown: roll_breakdown.clone(),
                         individual_rolls: individual_rolls.clone(),
+                        roll_id: roll_id.map(|id| id.to_string()),
+                        opposed: opposed.clone(),
                         timestamp: chrono::Utc::now().to_rfc3339(),
                     };
 
@@ -440,7 +690,7 @@ where
             challenge_id: Some(challenge_id_str.to_string()),
             challenge_name: challenge.name.clone(),
             world_id: world_id.to_string(),
-            character_id,
+            character_id: character_id.clone(),
             success,
             roll: Some(roll),
             total: Some(total),
@@ -454,7 +704,7 @@ where
         if let Some(sid) = session_id {
             let trigger_result = self
                 .outcome_trigger_service
-                .execute_triggers(&outcome.triggers, self.sessions.as_ref(), sid)
+                .execute_triggers(&outcome.triggers, self.sessions.as_ref(), sid, &character_id)
                 .await;
 
             if !trigger_result.warnings.is_empty() {
@@ -476,10 +726,14 @@ where
                 roll,
                 modifier,
                 total,
-                outcome: outcome_type.display_name().to_string(),
+                outcome: outcome_name.to_string(),
+                outcome_type,
+                margin,
                 outcome_description: outcome.description.clone(),
                 roll_breakdown,
                 individual_rolls,
+                roll_id: roll_id.map(|id| id.to_string()),
+                opposed,
             };
             if let Ok(json) = serde_json::to_value(&result_msg) {
                 if let Err(e) = self
@@ -499,57 +753,72 @@ where
     }
 
     /// Handle a player submitting a challenge roll (legacy method with simple integer roll).
+    ///
+    /// The player rolled physically, so a roll mode is only noted in the breakdown.
     pub async fn handle_roll(
         &self,
         client_id: String,
         challenge_id_str: String,
         roll: i32,
+        roll_mode: RollMode,
+        skill_id: Option<String>,
     ) -> Option<serde_json::Value> {
         // Gather common preamble data
         let preamble = match self
-            .gather_challenge_preamble(&client_id, &challenge_id_str, "legacy roll")
+            .gather_challenge_preamble(&client_id, &challenge_id_str, skill_id.as_deref(), "legacy roll")
             .await
         {
             Ok(p) => p,
             Err(err_msg) => return err_msg,
         };
 
-        // Evaluate challenge result
-        let (outcome_type, outcome) =
-            evaluate_challenge_result(&preamble.challenge, roll, preamble.character_modifier);
+        let submitted = SubmittedRoll {
+            character_id: preamble.character_id.clone(),
+            name: preamble.player_name.clone(),
+            outcome: if preamble.rule_system.system_type == RuleSystemType::D20 {
+                RollOutcome::d20(roll, preamble.character_modifier)
+            } else {
+                RollOutcome::flat(roll, preamble.character_modifier)
+            },
+            modifier: preamble.character_modifier,
+            total: roll + preamble.character_modifier,
+            // Legacy method doesn't have formula info
+            roll_breakdown: (roll_mode != RollMode::Normal)
+                .then(|| format!("Manual ({}): {}", roll_mode, roll)),
+            individual_rolls: None,
+            roll_id: None,
+        };
 
-        // Use common helper to publish events, execute triggers, and broadcast
-        self.resolve_challenge_internal(
-            &challenge_id_str,
-            &preamble.challenge,
-            preamble.skill_id,
-            outcome_type,
-            outcome,
-            preamble.session_id,
-            preamble.character_id,
-            preamble.player_name,
-            roll,
-            preamble.character_modifier,
-            roll + preamble.character_modifier,
-            None, // Legacy method doesn't have formula info
-            None,
-        )
-        .await;
+        if preamble.challenge.is_opposed() {
+            return self.resolve_opposed(&challenge_id_str, preamble, submitted).await;
+        }
 
-        None
+        // Evaluate challenge result with the world's degree-of-success bands
+        let evaluation = evaluate_roll(
+            &preamble.challenge,
+            preamble.rule_system.resolution_bands(),
+            &submitted.outcome,
+        );
+        self.conclude_roll(&challenge_id_str, &preamble, submitted, evaluation, None)
+            .await
     }
 
     /// Handle a player submitting a challenge roll with dice input (formula or manual).
     /// This is the enhanced version that supports dice formulas like "1d20+5".
+    ///
+    /// A roll mode (advantage, bonus dice, ...) is applied to the formula's primary
+    /// die; manual results are taken as already rolled with it.
     pub async fn handle_roll_input(
         &self,
         client_id: String,
         challenge_id_str: String,
         dice_input: DiceInputType,
+        roll_mode: RollMode,
+        skill_id: Option<String>,
     ) -> Option<serde_json::Value> {
         // Gather common preamble data
         let preamble = match self
-            .gather_challenge_preamble(&client_id, &challenge_id_str, "dice input roll")
+            .gather_challenge_preamble(&client_id, &challenge_id_str, skill_id.as_deref(), "dice input roll")
             .await
         {
             Ok(p) => p,
@@ -561,10 +830,33 @@ where
             DiceInputType::Formula(formula) => DiceRollInput::Formula(formula),
             DiceInputType::Manual(value) => DiceRollInput::ManualResult(value),
         };
+        let roll_input = match roll_input.with_roll_mode(roll_mode) {
+            Ok(input) => input,
+            Err(e) => {
+                return error_message("INVALID_ROLL_MODE", &format!("Cannot roll with {}: {}", roll_mode, e));
+            }
+        };
 
-        // Resolve the dice roll with character modifier
-        let roll_result = match roll_input.resolve_with_modifier(preamble.character_modifier) {
-            Ok(result) => result,
+        // Resolve the dice roll with character modifier, recording it in the session roll log
+        let resolved = match preamble.session_id {
+            Some(session_id) => self
+                .dice_roll_service
+                .roll(
+                    session_id,
+                    &roll_input,
+                    preamble.character_modifier,
+                    RollPurpose::Challenge,
+                    Some(preamble.challenge.name.clone()),
+                    preamble.player_name.clone(),
+                )
+                .await
+                .map(|logged| (logged.result, logged.roll_id)),
+            None => roll_input
+                .resolve_with_modifier(preamble.character_modifier)
+                .map(|result| (result, None)),
+        };
+        let (roll_result, roll_id) = match resolved {
+            Ok(resolved) => resolved,
             Err(e) => {
                 return error_message("INVALID_DICE_FORMULA", &format!("Invalid dice formula: {}", e));
             }
@@ -577,41 +869,550 @@ where
             roll_result.dice_total // For formula, use just the dice total
         };
 
-        // Evaluate challenge result
-        let (outcome_type, outcome) =
-            evaluate_challenge_result(&preamble.challenge, raw_roll, preamble.character_modifier);
+        let submitted = SubmittedRoll {
+            character_id: preamble.character_id.clone(),
+            name: preamble.player_name.clone(),
+            outcome: RollOutcome::from_result(&roll_result, raw_roll, preamble.character_modifier),
+            modifier: roll_result.modifier_applied,
+            total: roll_result.total,
+            roll_breakdown: Some(if roll_result.is_manual() && roll_mode != RollMode::Normal {
+                format!("Manual ({}): {}", roll_mode, roll_result.total)
+            } else {
+                roll_result.breakdown()
+            }),
+            individual_rolls: if roll_result.is_manual() {
+                None
+            } else {
+                Some(roll_result.individual_rolls.clone())
+            },
+            roll_id,
+        };
+
+        if preamble.challenge.is_opposed() {
+            return self.resolve_opposed(&challenge_id_str, preamble, submitted).await;
+        }
+
+        // Evaluate challenge result, counting pool successes or Fate shifts natively
+        let evaluation = evaluate_roll(
+            &preamble.challenge,
+            preamble.rule_system.resolution_bands(),
+            &submitted.outcome,
+        );
+        self.conclude_roll(&challenge_id_str, &preamble, submitted, evaluation, None)
+            .await
+    }
+
+    /// Finish an evaluated roll: count it towards a complex challenge, or resolve
+    /// the challenge through the usual outcome pipeline.
+    async fn conclude_roll(
+        &self,
+        challenge_id_str: &str,
+        preamble: &ChallengePreamble,
+        roll: SubmittedRoll,
+        evaluation: RollEvaluation<'_>,
+        opposed: Option<OpposedRollDto>,
+    ) -> Option<serde_json::Value> {
+        if let Some(settings) = preamble.challenge.complex() {
+            return self
+                .record_complex_roll(challenge_id_str, preamble, &settings, roll, evaluation.outcome_type)
+                .await;
+        }
 
         // Use common helper to publish events, execute triggers, and broadcast
         self.resolve_challenge_internal(
-            &challenge_id_str,
+            challenge_id_str,
             &preamble.challenge,
             preamble.skill_id,
-            outcome_type,
-            outcome,
+            evaluation,
             preamble.session_id,
-            preamble.character_id,
-            preamble.player_name,
-            raw_roll,
-            roll_result.modifier_applied,
-            roll_result.total,
-            Some(roll_result.breakdown()),
-            if roll_result.is_manual() {
-                None
-            } else {
-                Some(roll_result.individual_rolls.clone())
-            },
+            roll.character_id,
+            roll.name,
+            roll.outcome.raw_roll,
+            roll.modifier,
+            roll.total,
+            roll.roll_breakdown,
+            roll.individual_rolls,
+            roll.roll_id,
+            opposed,
         )
         .await;
 
         None
     }
 
+    /// Count a roll towards a complex challenge and broadcast the new progress.
+    ///
+    /// Once the challenge succeeds or fails, its success or failure outcome is
+    /// resolved like a single roll (DM approval, triggers, story events), using
+    /// the roll that finished it.
+    async fn record_complex_roll(
+        &self,
+        challenge_id_str: &str,
+        preamble: &ChallengePreamble,
+        settings: &ComplexChallengeSettings,
+        roll: SubmittedRoll,
+        outcome_type: OutcomeType,
+    ) -> Option<serde_json::Value> {
+        let challenge = &preamble.challenge;
+        let progress_lock = self.lock_complex_progress(challenge.id).await;
+        let mut progress = match self.challenge_service.get_complex_progress(challenge.id).await {
+            Ok(progress) => progress.unwrap_or_else(|| ComplexChallengeProgress::new(challenge.id)),
+            Err(e) => {
+                tracing::error!("Failed to load progress for complex challenge {}: {}", challenge_id_str, e);
+                return error_message("PROGRESS_LOAD_ERROR", "Failed to load challenge progress");
+            }
+        };
+        if progress.status.is_complete() {
+            return error_message(
+                "CHALLENGE_COMPLETE",
+                "This challenge is already complete; the DM must trigger it again to retry",
+            );
+        }
+
+        let status = progress.record(
+            settings,
+            ComplexChallengeRoll {
+                character_id: roll.character_id.clone(),
+                character_name: roll.name.clone(),
+                skill_id: preamble.skill_id,
+                total: roll.total,
+                outcome_type,
+                rolled_at: chrono::Utc::now(),
+            },
+        );
+        if let Err(e) = self.challenge_service.save_complex_progress(&progress).await {
+            tracing::error!("Failed to save progress for complex challenge {}: {}", challenge_id_str, e);
+            return error_message("PROGRESS_SAVE_ERROR", "Failed to save challenge progress");
+        }
+        drop(progress_lock);
+        info!(
+            challenge_id = %challenge_id_str,
+            successes = progress.successes,
+            failures = progress.failures,
+            status = status.display_name(),
+            "Recorded complex challenge roll"
+        );
+
+        if let Some(session_id) = preamble.session_id {
+            self.broadcast_complex_progress(session_id, challenge, settings, progress)
+                .await;
+        }
+
+        if status.is_complete() {
+            let evaluation = if status == ComplexChallengeStatus::Succeeded {
+                RollEvaluation {
+                    outcome_type: OutcomeType::Success,
+                    outcome: &challenge.outcomes.success,
+                    margin: None,
+                    success_level: None,
+                }
+            } else {
+                RollEvaluation {
+                    outcome_type: OutcomeType::Failure,
+                    outcome: &challenge.outcomes.failure,
+                    margin: None,
+                    success_level: None,
+                }
+            };
+            self.resolve_challenge_internal(
+                challenge_id_str,
+                challenge,
+                preamble.skill_id,
+                evaluation,
+                preamble.session_id,
+                roll.character_id,
+                roll.name,
+                roll.outcome.raw_roll,
+                roll.modifier,
+                roll.total,
+                roll.roll_breakdown,
+                roll.individual_rolls,
+                roll.roll_id,
+                None,
+            )
+            .await;
+        }
+
+        None
+    }
+
+    /// Broadcast a complex challenge's progress to everyone in the session
+    async fn broadcast_complex_progress(
+        &self,
+        session_id: SessionId,
+        challenge: &Challenge,
+        settings: &ComplexChallengeSettings,
+        progress: ComplexChallengeProgress,
+    ) {
+        let message = ComplexChallengeProgressMessage {
+            r#type: "ComplexChallengeProgress",
+            progress: ComplexChallengeProgressDto::new(challenge, settings, progress),
+        };
+        match serde_json::to_value(&message) {
+            Ok(json) => {
+                if let Err(e) = self.sessions.broadcast_to_session(session_id, json).await {
+                    tracing::error!("Failed to broadcast complex challenge progress: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize complex challenge progress: {}", e),
+        }
+    }
+
+    /// Record one side of an opposed check and resolve it once both sides have rolled.
+    ///
+    /// An NPC opponent is rolled by the engine as soon as the initiator rolls; a
+    /// second PC's roll is held until the other side is in.
+    async fn resolve_opposed(
+        &self,
+        challenge_id_str: &str,
+        preamble: ChallengePreamble,
+        roll: SubmittedRoll,
+    ) -> Option<serde_json::Value> {
+        let Some(session_id) = preamble.session_id else {
+            return error_message("NOT_IN_SESSION", "Opposed checks can only be rolled in a session");
+        };
+        let key = (session_id, challenge_id_str.to_string());
+
+        let ready = {
+            let mut checks = self.opposed_checks.write().await;
+            let Some(check) = checks.get_mut(&key) else {
+                return error_message(
+                    "NO_OPPONENT",
+                    "This opposed check has no opponent; the DM must trigger it with one",
+                );
+            };
+            if let Err((code, message)) = check.record(roll) {
+                return error_message(code, message);
+            }
+            if check.is_ready() {
+                checks.remove(&key)
+            } else {
+                None
+            }
+        };
+        let Some(PendingOpposedCheck {
+            initiator_roll: Some(initiator),
+            opponent,
+            ..
+        }) = ready
+        else {
+            debug!(challenge_id = %challenge_id_str, "Opposed check roll recorded, waiting for the other side");
+            return None;
+        };
+
+        let (opponent, is_npc) = match opponent {
+            PendingOpponent::Npc {
+                character_id,
+                name,
+                modifier,
+            } => match self
+                .roll_npc_opponent(session_id, &preamble, character_id, name, modifier)
+                .await
+            {
+                Ok(roll) => (roll, true),
+                Err(e) => {
+                    tracing::error!("Failed to roll for NPC opponent {}: {}", character_id, e);
+                    return error_message("OPPONENT_ROLL_FAILED", &format!("Failed to roll for the opponent: {}", e));
+                }
+            },
+            PendingOpponent::Pc { roll: Some(roll), .. } => (roll, false),
+            PendingOpponent::Pc { roll: None, .. } => return None,
+        };
+
+        let evaluation = evaluate_opposed(
+            &preamble.challenge,
+            &preamble.rule_system,
+            &initiator.outcome,
+            &opponent.outcome,
+        );
+        let opposed = OpposedRollDto {
+            opponent_id: opponent.character_id,
+            opponent_name: opponent.name,
+            is_npc,
+            roll: opponent.outcome.raw_roll,
+            modifier: opponent.modifier,
+            total: opponent.total,
+            roll_breakdown: opponent.roll_breakdown,
+            roll_id: opponent.roll_id.map(|id| id.to_string()),
+        };
+
+        self.conclude_roll(challenge_id_str, &preamble, initiator, evaluation, Some(opposed))
+            .await
+    }
+
+    /// Roll the rule system's standard dice for an NPC opponent, recording the
+    /// roll in the session roll log.
+    async fn roll_npc_opponent(
+        &self,
+        session_id: SessionId,
+        preamble: &ChallengePreamble,
+        character_id: CharacterId,
+        name: String,
+        modifier: i32,
+    ) -> Result<SubmittedRoll, DiceParseError> {
+        let formula = DiceFormula::default_for_system(&preamble.rule_system.dice_system);
+        let logged = self
+            .dice_roll_service
+            .roll(
+                session_id,
+                &DiceRollInput::Formula(formula.display()),
+                modifier,
+                RollPurpose::Challenge,
+                Some(format!("{} (opposed)", preamble.challenge.name)),
+                name.clone(),
+            )
+            .await?;
+        let result = logged.result;
+        Ok(SubmittedRoll {
+            character_id: character_id.to_string(),
+            name,
+            outcome: RollOutcome::from_result(&result, result.dice_total, modifier),
+            modifier,
+            total: result.total,
+            roll_breakdown: Some(result.breakdown()),
+            individual_rolls: Some(result.individual_rolls.clone()),
+            roll_id: logged.roll_id,
+        })
+    }
+
+    /// Grant a roll mode (e.g. advantage) on a roll awaiting DM approval.
+    ///
+    /// Only the extra dice are rolled; they join the dice already rolled, the
+    /// combined roll is logged in place of the original, the outcome is
+    /// re-evaluated and the resolution goes back to the DM. A roll can only be
+    /// given a mode once.
+    pub async fn grant_roll_mode(
+        &self,
+        session_id: SessionId,
+        resolution_id: &str,
+        roll_mode: RollMode,
+    ) -> Result<(), ChallengeOutcomeError> {
+        let (approval_service, mut item) = self.pending_resolution(session_id, resolution_id).await?;
+
+        let roll_id = item
+            .roll_id
+            .as_deref()
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())
+            .map(RollId::from_uuid)
+            .ok_or_else(|| {
+                ChallengeOutcomeError::InvalidState(
+                    "Only dice rolled by the engine can be given a roll mode".to_string(),
+                )
+            })?;
+        let granted = self
+            .dice_roll_service
+            .grant_roll_mode(session_id, roll_id, roll_mode)
+            .await
+            .map_err(|e| match e {
+                GrantRollError::AlreadyGranted(_) => ChallengeOutcomeError::InvalidState(e.to_string()),
+                e => ChallengeOutcomeError::RollError(e.to_string()),
+            })?;
+        let roll_result = granted.result;
+
+        let mut challenge = self.pending_challenge(&item).await?;
+        let rule_system = self.rule_system(challenge.world_id).await;
+        challenge.difficulty = challenge.difficulty.resolve(&rule_system.descriptor_difficulties);
+        let initiator = RollOutcome::from_result(&roll_result, roll_result.dice_total, item.modifier);
+        let evaluation = match &item.opposed {
+            Some(opposed) => {
+                let opponent = self.opponent_outcome(opposed).await?;
+                evaluate_opposed(&challenge, &rule_system, &initiator, &opponent)
+            }
+            None => evaluate_roll(&challenge, rule_system.resolution_bands(), &initiator),
+        };
+
+        info!(
+            resolution_id = %resolution_id,
+            roll_mode = %roll_mode,
+            outcome = evaluation.display_name(),
+            "DM granted roll mode on pending challenge roll"
+        );
+
+        item.roll = roll_result.dice_total;
+        item.total = roll_result.total;
+        item.roll_breakdown = Some(roll_result.breakdown());
+        item.roll_id = granted.roll_id.map(|id| id.to_string());
+        self.requeue_resolution(
+            &approval_service,
+            item,
+            evaluation,
+            Some(roll_result.individual_rolls.clone()),
+        )
+        .await
+    }
+
+    /// Override an NPC opponent's modifier on a pending opposed check and re-evaluate it.
+    ///
+    /// The opponent's dice are kept; only the modifier added to them changes.
+    pub async fn set_opponent_modifier(
+        &self,
+        session_id: SessionId,
+        resolution_id: &str,
+        modifier: i32,
+    ) -> Result<(), ChallengeOutcomeError> {
+        let (approval_service, mut item) = self.pending_resolution(session_id, resolution_id).await?;
+        let mut opposed = item
+            .opposed
+            .clone()
+            .filter(|opposed| opposed.is_npc)
+            .ok_or_else(|| {
+                ChallengeOutcomeError::InvalidState(
+                    "Only an NPC opponent's modifier can be changed".to_string(),
+                )
+            })?;
+
+        let opponent = match self.replay_roll(opposed.roll_id.as_deref()).await? {
+            Some(original) => {
+                let result = original.with_modifier_applied(modifier).ok_or_else(|| {
+                    ChallengeOutcomeError::InvalidState(
+                        "A dice pool's modifier is rolled as dice and cannot be changed".to_string(),
+                    )
+                })?;
+                opposed.roll_breakdown = Some(result.breakdown());
+                RollOutcome::from_result(&result, result.dice_total, modifier)
+            }
+            None => RollOutcome::flat(opposed.roll, modifier),
+        };
+        opposed.modifier = modifier;
+        opposed.total = opposed.roll + modifier;
+
+        // The initiator's roll stands as queued, with any roll mode granted since;
+        // the logged dice only add what a pool or Fate roll counted
+        let initiator_result = self.replay_roll(item.roll_id.as_deref()).await?;
+        let initiator = match &initiator_result {
+            Some(result) => RollOutcome::from_result(result, item.roll, item.modifier),
+            None => RollOutcome::flat(item.roll, item.modifier),
+        };
+        let challenge = self.pending_challenge(&item).await?;
+        let rule_system = self.rule_system(challenge.world_id).await;
+        let evaluation = evaluate_opposed(&challenge, &rule_system, &initiator, &opponent);
+
+        info!(
+            resolution_id = %resolution_id,
+            modifier,
+            outcome = evaluation.display_name(),
+            "DM changed opponent modifier on pending opposed check"
+        );
+
+        item.opposed = Some(opposed);
+        let individual_rolls = initiator_result.map(|result| result.individual_rolls);
+        self.requeue_resolution(&approval_service, item, evaluation, individual_rolls)
+            .await
+    }
+
+    /// Look up a pending resolution the DM is adjusting, checking it belongs to the session
+    async fn pending_resolution(
+        &self,
+        session_id: SessionId,
+        resolution_id: &str,
+    ) -> Result<(Arc<ChallengeOutcomeApprovalService<L>>, ChallengeOutcomeApprovalItem), ChallengeOutcomeError> {
+        let approval_service = self.challenge_outcome_approval_service.clone().ok_or_else(|| {
+            ChallengeOutcomeError::InvalidState("Outcome approval is not enabled".to_string())
+        })?;
+        let item = approval_service
+            .get_pending(resolution_id)
+            .await
+            .ok_or_else(|| ChallengeOutcomeError::NotFound(resolution_id.to_string()))?;
+        if item.session_id != session_id {
+            return Err(ChallengeOutcomeError::InvalidState("Session mismatch".to_string()));
+        }
+        Ok((approval_service, item))
+    }
+
+    /// Load the challenge a pending resolution was rolled against
+    async fn pending_challenge(
+        &self,
+        item: &ChallengeOutcomeApprovalItem,
+    ) -> Result<Challenge, ChallengeOutcomeError> {
+        let challenge_id = uuid::Uuid::parse_str(&item.challenge_id)
+            .map(ChallengeId::from_uuid)
+            .map_err(|_| ChallengeOutcomeError::InvalidState("Invalid challenge ID".to_string()))?;
+        self.challenge_service
+            .get_challenge(challenge_id)
+            .await
+            .map_err(|e| ChallengeOutcomeError::InvalidState(e.to_string()))?
+            .ok_or_else(|| ChallengeOutcomeError::NotFound(item.challenge_id.clone()))
+    }
+
+    /// Replay a logged roll; None when the roll was entered manually
+    async fn replay_roll(&self, roll_id: Option<&str>) -> Result<Option<DiceRollResult>, ChallengeOutcomeError> {
+        let Some(roll_id) = roll_id
+            .and_then(|id| uuid::Uuid::parse_str(id).ok())
+            .map(RollId::from_uuid)
+        else {
+            return Ok(None);
+        };
+        self.dice_roll_service
+            .get_roll(roll_id)
+            .await
+            .map_err(|e| ChallengeOutcomeError::RollError(e.to_string()))?
+            .and_then(|entry| entry.replay())
+            .map(Some)
+            .ok_or_else(|| ChallengeOutcomeError::RollError(format!("Roll {} could not be replayed", roll_id)))
+    }
+
+    /// Rebuild the opponent's side of an opposed check from its logged roll
+    async fn opponent_outcome(&self, opposed: &OpposedRollDto) -> Result<RollOutcome, ChallengeOutcomeError> {
+        Ok(match self.replay_roll(opposed.roll_id.as_deref()).await? {
+            Some(result) => RollOutcome::from_result(&result, result.dice_total, opposed.modifier),
+            None => RollOutcome::flat(opposed.roll, opposed.modifier),
+        })
+    }
+
+    /// Re-queue an adjusted resolution under its original ID so the DM approves the new outcome
+    async fn requeue_resolution(
+        &self,
+        approval_service: &ChallengeOutcomeApprovalService<L>,
+        item: ChallengeOutcomeApprovalItem,
+        evaluation: RollEvaluation<'_>,
+        individual_rolls: Option<Vec<i32>>,
+    ) -> Result<(), ChallengeOutcomeError> {
+        let outcome_name = evaluation.display_name();
+        let RollEvaluation {
+            outcome_type,
+            outcome,
+            margin,
+            ..
+        } = evaluation;
+        let resolution = PendingChallengeResolutionDto {
+            resolution_id: item.resolution_id,
+            challenge_id: item.challenge_id,
+            challenge_name: item.challenge_name,
+            challenge_description: item.challenge_description,
+            skill_name: item.skill_name,
+            character_id: item.character_id,
+            character_name: item.character_name,
+            roll: item.roll,
+            modifier: item.modifier,
+            total: item.total,
+            outcome_type: outcome_name.to_string(),
+            outcome_tier: Some(outcome_type),
+            margin,
+            outcome_description: outcome.description.clone(),
+            outcome_triggers: outcome
+                .triggers
+                .iter()
+                .cloned()
+                .map(OutcomeTriggerRequestDto::from)
+                .collect(),
+            roll_breakdown: item.roll_breakdown,
+            individual_rolls,
+            roll_id: item.roll_id,
+            opposed: item.opposed,
+            timestamp: item.timestamp.to_rfc3339(),
+        };
+        approval_service.queue_for_approval(item.session_id, resolution).await?;
+
+        Ok(())
+    }
+
     /// Handle DM-triggered challenges.
     pub async fn handle_trigger(
         &self,
         client_id: String,
         challenge_id_str: String,
         target_character_id: String,
+        opponent: Option<OpponentDto>,
     ) -> Option<serde_json::Value> {
         // Check if client is DM
         if !self.sessions.is_client_dm(&client_id).await {
@@ -640,31 +1441,69 @@ where
             }
         };
 
-        // Fetch skill_id from REQUIRES_SKILL edge
-        let skill_id = match self.challenge_service.get_required_skill(challenge_uuid).await {
-            Ok(skill_id) => skill_id,
-            Err(e) => {
-                tracing::warn!("Failed to get required skill for challenge {}: {}", challenge_uuid, e);
-                None
-            }
-        };
+        let (skill_id, skill_name, base_attribute) = self.challenge_skill(challenge_uuid).await;
 
-        // Look up skill name from skill service
-        let skill_name = if let Some(ref sid) = skill_id {
-            match self.skill_service.get_skill(sid.clone()).await {
-                Ok(Some(skill)) => skill.name,
-                Ok(None) => {
-                    tracing::warn!("Skill {} not found for challenge", sid);
-                    sid.to_string()
+        let rule_system = self.rule_system(challenge.world_id).await;
+
+        // Opposed checks need someone to roll against before anyone can roll
+        if challenge.is_opposed() {
+            let Some(opponent) = opponent else {
+                return error_message(
+                    "OPPONENT_REQUIRED",
+                    "An opposed challenge must be triggered with an opponent",
+                );
+            };
+            let Some(session_id) = session_id else {
+                return error_message("NOT_IN_SESSION", "Opposed checks can only be triggered in a session");
+            };
+            let opponent = match opponent {
+                OpponentDto::Npc { character_id, modifier } => {
+                    let npc_id = match uuid::Uuid::parse_str(&character_id) {
+                        Ok(uuid) => CharacterId::from_uuid(uuid),
+                        Err(_) => {
+                            return error_message("INVALID_OPPONENT", "Invalid opponent character ID format");
+                        }
+                    };
+                    let npc = match self.character_repository.get(npc_id).await {
+                        Ok(Some(npc)) => npc,
+                        Ok(None) => {
+                            return error_message("OPPONENT_NOT_FOUND", &format!("Character {} not found", character_id));
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to load opponent {}: {}", character_id, e);
+                            return error_message("OPPONENT_LOAD_ERROR", "Failed to load opponent");
+                        }
+                    };
+                    // The DM's override wins over the NPC's stat block and conditions
+                    let modifier = modifier.unwrap_or_else(|| {
+                        npc.stats.check_modifier(
+                            &skill_name,
+                            base_attribute.as_deref(),
+                            rule_system.system_type == RuleSystemType::D20,
+                        ) + npc.conditions.roll_modifier()
+                    });
+                    PendingOpponent::Npc {
+                        character_id: npc_id,
+                        name: npc.name,
+                        modifier,
+                    }
                 }
-                Err(e) => {
-                    tracing::error!("Failed to look up skill {}: {}", sid, e);
-                    sid.to_string()
+                OpponentDto::Pc { pc_id } => {
+                    if pc_id == target_character_id {
+                        return error_message("INVALID_OPPONENT", "A character cannot oppose themselves");
+                    }
+                    PendingOpponent::Pc { pc_id, roll: None }
                 }
-            }
-        } else {
-            "Unknown Skill".to_string()
-        };
+            };
+            self.opposed_checks.write().await.insert(
+                (session_id, challenge_id_str.clone()),
+                PendingOpposedCheck {
+                    initiator_id: target_character_id.clone(),
+                    initiator_roll: None,
+                    opponent,
+                },
+            );
+        }
 
         // Look up skill modifier for target character
         let character_modifier = if let Some(ref sid) = skill_id {
@@ -695,19 +1534,46 @@ where
             0
         };
 
+        // Triggering a finished complex challenge starts a new attempt
+        let complex = match challenge.complex() {
+            Some(settings) => {
+                let _progress_lock = self.lock_complex_progress(challenge.id).await;
+                let progress = match self.challenge_service.get_complex_progress(challenge.id).await {
+                    Ok(Some(progress)) if !progress.status.is_complete() => progress,
+                    Ok(_) => {
+                        let progress = ComplexChallengeProgress::new(challenge.id);
+                        if let Err(e) = self.challenge_service.save_complex_progress(&progress).await {
+                            tracing::error!("Failed to start complex challenge {}: {}", challenge_id_str, e);
+                            return error_message("PROGRESS_SAVE_ERROR", "Failed to start challenge progress");
+                        }
+                        progress
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to load progress for complex challenge {}: {}", challenge_id_str, e);
+                        return error_message("PROGRESS_LOAD_ERROR", "Failed to load challenge progress");
+                    }
+                };
+                Some((settings, progress))
+            }
+            None => None,
+        };
+
         // Get suggested dice based on difficulty type
-        let (suggested_dice, rule_system_hint) = get_dice_suggestion_for_challenge(&challenge);
+        let (suggested_dice, rule_system_hint) = get_dice_suggestion_for_challenge(&challenge, &rule_system);
+
+        let odds = challenge_odds(&challenge, &rule_system, &suggested_dice, character_modifier);
 
         let prompt = ChallengePromptMessage {
             r#type: "ChallengePrompt",
             challenge_id: challenge_id_str.clone(),
             challenge_name: challenge.name.clone(),
             skill_name: skill_name.clone(),
-            difficulty_display: challenge.difficulty.display(),
+            difficulty_display: challenge.difficulty.describe(&rule_system.descriptor_difficulties),
             description: challenge.description.clone(),
             character_modifier,
             suggested_dice: Some(suggested_dice),
             rule_system_hint: Some(rule_system_hint),
+            odds: None,
         };
 
         if let Some(session_id) = session_id {
@@ -718,6 +1584,21 @@ where
             } else {
                 tracing::error!("Failed to serialize challenge prompt");
             }
+
+            // The DM's copy carries the odds so they can judge the challenge before it is rolled
+            let dm_prompt = ChallengePromptMessage { odds, ..prompt };
+            if let Ok(msg_json) = serde_json::to_value(&dm_prompt) {
+                if let Err(e) = self.sessions.send_to_dm(session_id, msg_json).await {
+                    tracing::error!("Failed to send challenge prompt to DM: {}", e);
+                }
+            } else {
+                tracing::error!("Failed to serialize DM challenge prompt");
+            }
+
+            if let Some((settings, progress)) = complex {
+                self.broadcast_complex_progress(session_id, &challenge, &settings, progress)
+                    .await;
+            }
         }
 
         tracing::info!(
@@ -730,6 +1611,38 @@ where
         None
     }
 
+    /// Calculate the odds of each outcome for a challenge, rolled with its suggested dice.
+    ///
+    /// When a player character is given, their modifier for the required skill is
+    /// applied. Returns None if the challenge does not exist.
+    pub async fn get_challenge_odds(
+        &self,
+        challenge_id: ChallengeId,
+        pc_id: Option<PlayerCharacterId>,
+    ) -> anyhow::Result<Option<ChallengeOddsDto>> {
+        let Some(challenge) = self.challenge_service.get_challenge(challenge_id).await? else {
+            return Ok(None);
+        };
+
+        let skill_modifier = match pc_id {
+            Some(pc_id) => match self.challenge_service.get_required_skill(challenge_id).await? {
+                Some(skill_id) => {
+                    self.player_character_service
+                        .get_skill_modifier(pc_id, skill_id)
+                        .await?
+                }
+                None => 0,
+            },
+            None => 0,
+        };
+
+        let rule_system = self.rule_system(challenge.world_id).await;
+        let (suggested_dice, _) = get_dice_suggestion_for_challenge(&challenge, &rule_system);
+        challenge_odds(&challenge, &rule_system, &suggested_dice, skill_modifier)
+            .map(Some)
+            .ok_or_else(|| anyhow::anyhow!("Invalid suggested dice: {}", suggested_dice))
+    }
+
     /// Handle DM approval/rejection of a challenge suggestion.
     pub async fn handle_suggestion_decision(
         &self,
@@ -788,8 +1701,10 @@ where
                             }
                         };
 
-                        let difficulty_display = modified_difficulty
-                            .unwrap_or_else(|| challenge.difficulty.display());
+                        let rule_system = self.rule_system(challenge.world_id).await;
+                        let difficulty_display = modified_difficulty.unwrap_or_else(|| {
+                            challenge.difficulty.describe(&rule_system.descriptor_difficulties)
+                        });
 
                         // Look up skill modifier for target character if available
                         let character_modifier = if let Some(ref sid) = skill_id {
@@ -827,7 +1742,7 @@ where
 
                         // Get suggested dice based on difficulty type
                         let (suggested_dice, rule_system_hint) =
-                            get_dice_suggestion_for_challenge(&challenge);
+                            get_dice_suggestion_for_challenge(&challenge, &rule_system);
 
                         let prompt = ChallengePromptMessage {
             r#type: "ChallengePrompt",
@@ -839,6 +1754,7 @@ where
                             character_modifier,
                             suggested_dice: Some(suggested_dice),
                             rule_system_hint: Some(rule_system_hint),
+                            odds: None,
                         };
 
                         if let Some(sid) = session_id {
@@ -927,6 +1843,7 @@ where
             character_modifier: 0, // DM would need to specify this
             suggested_dice: Some(suggested_dice),
             rule_system_hint: Some(rule_system_hint),
+            odds: None,
         };
 
         // Broadcast to session (the target player will see it)
@@ -952,108 +1869,248 @@ where
     }
 }
 
+/// Error type for calling on characters to make saving throws
+#[derive(Debug, thiserror::Error)]
+pub enum SavingThrowError {
+    #[error("Challenge not found: {0}")]
+    ChallengeNotFound(ChallengeId),
+    #[error("{0} is not a saving throw")]
+    NotASavingThrow(String),
+}
+
+/// Calls on several player characters at once to roll the same saving throw,
+/// as when a spell or hazard catches them all
+#[async_trait]
+pub trait SavingThrowTrigger: Send + Sync {
+    /// Prompt each character's player to roll the challenge with their own
+    /// modifier; returns the characters who were prompted
+    async fn trigger_saving_throws(
+        &self,
+        session_id: SessionId,
+        challenge_id: ChallengeId,
+        pc_ids: &[PlayerCharacterId],
+    ) -> anyhow::Result<Vec<PlayerCharacterId>>;
+}
+
+#[async_trait]
+impl<S, K, Q, P, L> SavingThrowTrigger for ChallengeResolutionService<S, K, Q, P, L>
+where
+    S: ChallengeService,
+    K: SkillService,
+    Q: ApprovalQueuePort<crate::application::dto::ApprovalItem>,
+    P: PlayerCharacterService,
+    L: LlmPort + 'static,
+{
+    async fn trigger_saving_throws(
+        &self,
+        session_id: SessionId,
+        challenge_id: ChallengeId,
+        pc_ids: &[PlayerCharacterId],
+    ) -> anyhow::Result<Vec<PlayerCharacterId>> {
+        let challenge = self
+            .challenge_service
+            .get_challenge(challenge_id)
+            .await?
+            .ok_or(SavingThrowError::ChallengeNotFound(challenge_id))?;
+        if challenge.challenge_type != ChallengeType::SavingThrow || challenge.is_opposed() {
+            return Err(SavingThrowError::NotASavingThrow(challenge.name).into());
+        }
+
+        let (skill_id, skill_name, _) = self.challenge_skill(challenge_id).await;
+        let rule_system = self.rule_system(challenge.world_id).await;
+        let (suggested_dice, rule_system_hint) = get_dice_suggestion_for_challenge(&challenge, &rule_system);
+        let difficulty_display = challenge.difficulty.describe(&rule_system.descriptor_difficulties);
+
+        let mut prompted = Vec::new();
+        for &pc_id in pc_ids {
+            let pc = match self.player_character_service.get_pc(pc_id).await {
+                Ok(Some(pc)) => pc,
+                Ok(None) => {
+                    tracing::warn!("PC {} not found for saving throw {}", pc_id, challenge.name);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load PC {} for saving throw: {}", pc_id, e);
+                    continue;
+                }
+            };
+            let character_modifier = match skill_id {
+                Some(sid) => self
+                    .player_character_service
+                    .get_skill_modifier(pc_id, sid)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to get skill modifier for PC {}: {}, using 0", pc_id, e);
+                        0
+                    }),
+                None => 0,
+            };
+
+            let prompt = ChallengePromptMessage {
+                r#type: "ChallengePrompt",
+                challenge_id: challenge_id.to_string(),
+                challenge_name: challenge.name.clone(),
+                skill_name: skill_name.clone(),
+                difficulty_display: difficulty_display.clone(),
+                description: challenge.description.clone(),
+                character_modifier,
+                suggested_dice: Some(suggested_dice.clone()),
+                rule_system_hint: Some(rule_system_hint.clone()),
+                odds: None,
+            };
+            match serde_json::to_value(&prompt) {
+                Ok(msg_json) => {
+                    if let Err(e) = self.sessions.send_to_participant(session_id, &pc.user_id, msg_json).await {
+                        tracing::error!("Failed to send saving throw prompt to {}: {}", pc.name, e);
+                        continue;
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to serialize saving throw prompt: {}", e);
+                    continue;
+                }
+            }
+            prompted.push(pc_id);
+        }
+
+        info!(
+            "Called for {} saving throws ({} characters) in session {}",
+            challenge.name,
+            prompted.len(),
+            session_id
+        );
+        Ok(prompted)
+    }
+}
+
 /// Get suggested dice and rule system hint based on challenge difficulty type.
+///
+/// The dice are the world's rule system dice, so pools, Fate dice and custom
+/// expressions are suggested (and their odds calculated) as they are rolled.
 fn get_dice_suggestion_for_challenge(
     challenge: &crate::domain::entities::Challenge,
+    rule_system: &RuleSystemConfig,
 ) -> (String, String) {
-    match &challenge.difficulty {
+    let dice = DiceFormula::default_for_system(&rule_system.dice_system).display();
+    let add_skill = match &rule_system.dice_system {
+        DiceSystem::DicePool { .. } => "add a die for each point of your skill",
+        DiceSystem::Fate => "add your approach",
+        _ => "add your skill modifier",
+    };
+    let hint = match &challenge.difficulty {
         crate::domain::entities::Difficulty::DC(_) => {
-            // D20 systems (D&D, Pathfinder, etc.)
-            (
-                "1d20".to_string(),
-                "Roll 1d20 and add your skill modifier".to_string(),
-            )
+            format!("Roll {} and {}", dice, add_skill)
         }
         crate::domain::entities::Difficulty::Percentage(_) => {
-            // Percentile systems (Call of Cthulhu, etc.)
-            (
-                "1d100".to_string(),
-                "Roll percentile dice (1d100), lower is better".to_string(),
-            )
+            format!("Roll {}, lower is better", dice)
         }
         crate::domain::entities::Difficulty::Descriptor(desc) => {
-            // Narrative systems - suggest 2d6 for PbtA-style games
-            (
-                "2d6".to_string(),
-                format!("Roll 2d6 for {} difficulty", desc.display_name()),
-            )
+            // Follow what the descriptor means in the world's rule system
+            match desc.target(&rule_system.descriptor_difficulties) {
+                DescriptorTarget::Dc(dc) => format!("Roll {} and {} against DC {}", dice, add_skill, dc),
+                DescriptorTarget::Percentage(target) => {
+                    format!("Roll {}, {} or lower succeeds", dice, target)
+                }
+                DescriptorTarget::Ladder(rung) => {
+                    format!("Roll {} and {} against {}", dice, add_skill, FateLadder(rung).display())
+                }
+                target @ DescriptorTarget::PositionEffect { .. } => {
+                    format!("Roll {} for {} difficulty ({})", dice, desc.display_name(), target.display())
+                }
+            }
         }
         crate::domain::entities::Difficulty::Opposed => {
             // Opposed rolls - both parties roll
-            (
-                "1d20".to_string(),
-                "Opposed roll - both parties roll and compare".to_string(),
-            )
+            "Opposed roll - both parties roll and compare".to_string()
         }
         crate::domain::entities::Difficulty::Custom(desc) => {
             // Custom difficulty - let the hint explain
-            (
-                "1d20".to_string(),
-                format!("Custom difficulty: {}", desc),
-            )
+            format!("Custom difficulty: {}", desc)
         }
-    }
+    };
+    (dice, hint)
 }
 
-/// Evaluate a challenge roll result (moved from websocket.rs)
-fn evaluate_challenge_result(
+/// Calculate the odds of rolling the suggested dice against a challenge.
+///
+/// Returns None if the suggested dice are not a valid formula.
+fn challenge_odds(
     challenge: &crate::domain::entities::Challenge,
-    roll: i32,
-    modifier: i32,
-) -> (OutcomeType, &crate::domain::entities::Outcome) {
-    let total = roll + modifier;
-
-    match &challenge.difficulty {
-        crate::domain::entities::Difficulty::DC(dc) => {
-            if roll == 20 {
-                if let Some(ref critical_success) = challenge.outcomes.critical_success {
-                    return (OutcomeType::CriticalSuccess, critical_success);
-                }
-            }
-            if roll == 1 {
-                if let Some(ref critical_failure) = challenge.outcomes.critical_failure {
-                    return (OutcomeType::CriticalFailure, critical_failure);
-                }
-            }
+    rule_system: &RuleSystemConfig,
+    suggested_dice: &str,
+    skill_modifier: i32,
+) -> Option<ChallengeOddsDto> {
+    let formula = DiceFormula::parse(suggested_dice).ok()?;
+    let mut challenge = challenge.clone();
+    challenge.difficulty = challenge.difficulty.resolve(&rule_system.descriptor_difficulties);
+    let odds = ChallengeOdds::calculate(&challenge, rule_system.resolution_bands(), &formula, skill_modifier);
+    Some(ChallengeOddsDto::new(&formula, skill_modifier, &odds))
+}
 
-            if total >= *dc as i32 {
-                (OutcomeType::Success, &challenge.outcomes.success)
-            } else {
-                (OutcomeType::Failure, &challenge.outcomes.failure)
-            }
-        }
-        crate::domain::entities::Difficulty::Percentage(target) => {
-            if roll == 1 {
-                if let Some(ref critical_success) = challenge.outcomes.critical_success {
-                    return (OutcomeType::CriticalSuccess, critical_success);
-                }
-            }
-            if roll == 100 {
-                if let Some(ref critical_failure) = challenge.outcomes.critical_failure {
-                    return (OutcomeType::CriticalFailure, critical_failure);
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Challenge, Difficulty};
+
+    #[test]
+    fn test_suggested_dice_follow_the_rule_system() {
+        let challenge = Challenge::new(WorldId::new(), "Climb", Difficulty::DC(2));
+        let rule_system = RuleSystemConfig {
+            dice_system: DiceSystem::DicePool {
+                die_type: 10,
+                success_threshold: 8,
+                ones_cancel: false,
+                again_threshold: None,
+            },
+            ..RuleSystemConfig::default()
+        };
 
-            if roll <= *target as i32 {
-                (OutcomeType::Success, &challenge.outcomes.success)
-            } else {
-                (OutcomeType::Failure, &challenge.outcomes.failure)
-            }
-        }
-        crate::domain::entities::Difficulty::Descriptor(_) => {
-            if roll >= 11 {
-                (OutcomeType::Success, &challenge.outcomes.success)
-            } else {
-                (OutcomeType::Failure, &challenge.outcomes.failure)
-            }
-        }
-        crate::domain::entities::Difficulty::Opposed => {
-            (OutcomeType::Success, &challenge.outcomes.success)
-        }
-        crate::domain::entities::Difficulty::Custom(_) => {
-            (OutcomeType::Success, &challenge.outcomes.success)
+        let (dice, hint) = get_dice_suggestion_for_challenge(&challenge, &rule_system);
+        assert_eq!(dice, "1d10>8");
+        assert!(hint.contains("a die for each point"));
+
+        // Two successes are out of reach of one die, but not of five
+        let one_die = challenge_odds(&challenge, &rule_system, &dice, 0).unwrap();
+        let five_dice = challenge_odds(&challenge, &rule_system, &dice, 4).unwrap();
+        assert_eq!(one_die.success_chance, 0.0);
+        assert!(five_dice.success_chance > 0.0);
+
+        let (dice, _) = get_dice_suggestion_for_challenge(&challenge, &RuleSystemConfig::default());
+        assert_eq!(dice, "1d20");
+    }
+
+    fn submitted(character_id: &str, roll: i32) -> SubmittedRoll {
+        SubmittedRoll {
+            character_id: character_id.to_string(),
+            name: character_id.to_string(),
+            outcome: RollOutcome::flat(roll, 0),
+            modifier: 0,
+            total: roll,
+            roll_breakdown: None,
+            individual_rolls: None,
+            roll_id: None,
         }
     }
-}
 
+    #[test]
+    fn test_opposed_sides_roll_once() {
+        let mut check = PendingOpposedCheck {
+            initiator_id: "alice".to_string(),
+            initiator_roll: None,
+            opponent: PendingOpponent::Pc {
+                pc_id: "bob".to_string(),
+                roll: None,
+            },
+        };
+
+        assert!(check.record(submitted("alice", 3)).is_ok());
+        assert_eq!(check.record(submitted("alice", 19)).unwrap_err().0, "ALREADY_ROLLED");
+        assert_eq!(check.record(submitted("carol", 10)).unwrap_err().0, "NOT_IN_OPPOSED_CHECK");
+        assert!(!check.is_ready());
 
+        assert!(check.record(submitted("bob", 12)).is_ok());
+        assert_eq!(check.record(submitted("bob", 20)).unwrap_err().0, "ALREADY_ROLLED");
+        assert!(check.is_ready());
+        assert_eq!(check.initiator_roll.as_ref().map(|roll| roll.total), Some(3));
+    }
+}
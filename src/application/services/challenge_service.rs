@@ -11,6 +11,7 @@
 //! - `REQUIRES_COMPLETION_OF` -> Prerequisite challenges
 //! - `AVAILABLE_AT` -> Locations where challenge is available
 //! - `ON_SUCCESS_UNLOCKS` -> Locations unlocked on success
+//! - `HAS_PROGRESS` -> Progress on a complex challenge
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -18,7 +19,9 @@ use std::sync::Arc;
 use tracing::{debug, info, instrument};
 
 use crate::application::ports::outbound::ChallengeRepositoryPort;
-use crate::domain::entities::{Challenge, ChallengeLocationAvailability, ChallengePrerequisite};
+use crate::domain::entities::{
+    Challenge, ChallengeLocationAvailability, ChallengePrerequisite, ComplexChallengeProgress,
+};
 use crate::domain::value_objects::{ChallengeId, LocationId, SceneId, SkillId, WorldId};
 
 /// Challenge service trait defining the application use cases
@@ -144,6 +147,19 @@ pub trait ChallengeService: Send + Sync {
 
     /// Remove an unlock from a challenge
     async fn remove_unlock_location(&self, challenge_id: ChallengeId, location_id: LocationId) -> Result<()>;
+
+    // -------------------------------------------------------------------------
+    // Complex Challenge Progress (HAS_PROGRESS)
+    // -------------------------------------------------------------------------
+
+    /// Get the progress made on a complex challenge
+    async fn get_complex_progress(&self, challenge_id: ChallengeId) -> Result<Option<ComplexChallengeProgress>>;
+
+    /// Save the progress made on a complex challenge
+    async fn save_complex_progress(&self, progress: &ComplexChallengeProgress) -> Result<()>;
+
+    /// Clear a complex challenge's progress so it can be attempted again
+    async fn clear_complex_progress(&self, challenge_id: ChallengeId) -> Result<()>;
 }
 
 /// Default implementation of ChallengeService using port abstractions
@@ -458,6 +474,44 @@ impl ChallengeService for ChallengeServiceImpl {
             .await
             .context("Failed to remove unlock location")
     }
+
+    // -------------------------------------------------------------------------
+    // Complex Challenge Progress (HAS_PROGRESS)
+    // -------------------------------------------------------------------------
+
+    #[instrument(skip(self))]
+    async fn get_complex_progress(&self, challenge_id: ChallengeId) -> Result<Option<ComplexChallengeProgress>> {
+        debug!(challenge_id = %challenge_id, "Getting complex challenge progress");
+        self.repository
+            .get_complex_progress(challenge_id)
+            .await
+            .context("Failed to get complex challenge progress")
+    }
+
+    #[instrument(skip(self, progress), fields(challenge_id = %progress.challenge_id))]
+    async fn save_complex_progress(&self, progress: &ComplexChallengeProgress) -> Result<()> {
+        self.repository
+            .save_complex_progress(progress)
+            .await
+            .context("Failed to save complex challenge progress")?;
+        debug!(
+            successes = progress.successes,
+            failures = progress.failures,
+            status = progress.status.display_name(),
+            "Saved complex challenge progress"
+        );
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn clear_complex_progress(&self, challenge_id: ChallengeId) -> Result<()> {
+        self.repository
+            .clear_complex_progress(challenge_id)
+            .await
+            .context("Failed to clear complex challenge progress")?;
+        info!(challenge_id = %challenge_id, "Cleared complex challenge progress");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
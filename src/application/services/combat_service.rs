@@ -0,0 +1,761 @@
+//! Combat Service
+//!
+//! Runs the fights in each session. The roster is the session's player
+//! characters plus the NPCs the DM brings in; initiative is rolled with the
+//! world's dice (or taken from a stat, in systems that act in stat order)
+//! and turns pass down the order from there. Hit points are kept where they
+//! live: on the player character's sheet as the `HP` resource, on the NPC's
+//! stat block otherwise.
+//!
+//! Everyone in the session hears of each change. The fight's start, every
+//! completed round, each combatant who falls or flees and its end are
+//! recorded on the story timeline. When an LLM-controlled NPC's turn comes
+//! up, the update carries a prompt for the caller to propose its action
+//! through the DM approval queue. Encounters last as long as the server;
+//! they are not persisted.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::application::dto::{
+    CombatEndedNotification, CombatTurnChangedNotification, CombatUpdatedNotification,
+    CombatantDefeatedNotification,
+};
+use crate::application::ports::outbound::{
+    AsyncSessionPort, CharacterRepositoryPort, WorldRepositoryPort,
+};
+use crate::application::services::{
+    DiceRollService, PlayerCharacterService, SheetResourceService, StoryEventService,
+};
+use crate::domain::entities::{
+    CharacterSheetTemplate, CombatEventType, CombatOutcome, Combatant, CombatantRef,
+    CombatantStatus, CombatSide, Encounter, EncounterError, PlayerCharacter, RollPurpose,
+    TurnChange,
+};
+use crate::domain::value_objects::{
+    CharacterId, DiceFormula, DiceRollInput, InitiativeRule, LocationId, PlayerCharacterId,
+    ResourceOperation, SessionId,
+};
+
+/// Sheet resource that holds a player character's hit points
+const HP_FIELD: &str = "HP";
+
+/// Error type for combat operations
+#[derive(Debug, thiserror::Error)]
+pub enum CombatError {
+    #[error("Session {0} is already in combat")]
+    AlreadyInCombat(SessionId),
+    #[error("Session {0} is not in combat")]
+    NotInCombat(SessionId),
+    #[error("Session {0} not found")]
+    SessionNotFound(SessionId),
+    #[error("No location given and no player character to fight where they stand")]
+    NoLocation,
+    #[error("{0} has no hit points to track")]
+    NoHitPoints(String),
+    #[error(transparent)]
+    Encounter(#[from] EncounterError),
+}
+
+/// An NPC brought into a fight
+#[derive(Debug, Clone)]
+pub struct CombatNpc {
+    pub character_id: CharacterId,
+    pub side: CombatSide,
+    pub llm_controlled: bool,
+}
+
+/// What a fight starts with
+#[derive(Debug, Clone, Default)]
+pub struct StartCombat {
+    pub description: String,
+    /// Where the fight happens; the first player character's location if
+    /// not given
+    pub location_id: Option<LocationId>,
+    /// Player characters who fight; everyone in the session if empty
+    pub pc_ids: Vec<PlayerCharacterId>,
+    pub npcs: Vec<CombatNpc>,
+}
+
+/// An LLM-controlled NPC whose turn it is
+#[derive(Debug, Clone)]
+pub struct NpcTurn {
+    pub character_id: CharacterId,
+    pub name: String,
+    /// Describes the fight and asks for the NPC's action
+    pub prompt: String,
+}
+
+/// What a change to a fight led to
+#[derive(Debug, Clone)]
+pub struct CombatUpdate {
+    pub encounter: Encounter,
+    /// The round that just finished; the caller advances the game clock
+    pub completed_round: Option<u32>,
+    /// Set when the turn passed to an NPC the LLM should act for
+    pub npc_turn: Option<NpcTurn>,
+    /// Set when the change ended the fight
+    pub ended: Option<CombatOutcome>,
+}
+
+impl CombatUpdate {
+    fn new(encounter: Encounter) -> Self {
+        Self {
+            encounter,
+            completed_round: None,
+            npc_turn: None,
+            ended: None,
+        }
+    }
+}
+
+/// Service for running combat encounters
+#[derive(Clone)]
+pub struct CombatService {
+    player_character_service: Arc<dyn PlayerCharacterService>,
+    character_repository: Arc<dyn CharacterRepositoryPort>,
+    world_repository: Arc<dyn WorldRepositoryPort>,
+    dice_roll_service: Arc<DiceRollService>,
+    sheet_resource_service: SheetResourceService,
+    story_event_service: StoryEventService,
+    sessions: Arc<dyn AsyncSessionPort>,
+    /// The fight running in each session
+    encounters: Arc<RwLock<HashMap<SessionId, Encounter>>>,
+}
+
+impl CombatService {
+    /// Create a new combat service
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        player_character_service: Arc<dyn PlayerCharacterService>,
+        character_repository: Arc<dyn CharacterRepositoryPort>,
+        world_repository: Arc<dyn WorldRepositoryPort>,
+        dice_roll_service: Arc<DiceRollService>,
+        sheet_resource_service: SheetResourceService,
+        story_event_service: StoryEventService,
+        sessions: Arc<dyn AsyncSessionPort>,
+    ) -> Self {
+        Self {
+            player_character_service,
+            character_repository,
+            world_repository,
+            dice_roll_service,
+            sheet_resource_service,
+            story_event_service,
+            sessions,
+            encounters: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// The fight running in a session, if any
+    pub async fn encounter(&self, session_id: SessionId) -> Option<Encounter> {
+        self.encounters.read().await.get(&session_id).cloned()
+    }
+
+    /// Start a fight in a session
+    ///
+    /// In systems that do not roll initiative the first round begins at
+    /// once; otherwise it waits for everyone's initiative.
+    pub async fn start(
+        &self,
+        session_id: SessionId,
+        start: StartCombat,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        if self.encounters.read().await.contains_key(&session_id) {
+            return Err(CombatError::AlreadyInCombat(session_id).into());
+        }
+        let world_id = self
+            .sessions
+            .get_session_world_id(session_id)
+            .await
+            .ok_or(CombatError::SessionNotFound(session_id))?;
+        let world = self
+            .world_repository
+            .get(world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
+        let rule = world.rule_system.initiative();
+        let template = self.player_character_service.sheet_template(world_id).await?;
+
+        let pcs = if start.pc_ids.is_empty() {
+            self.player_character_service.get_pcs_by_session(session_id).await?
+        } else {
+            let mut pcs = Vec::with_capacity(start.pc_ids.len());
+            for pc_id in &start.pc_ids {
+                let pc = self
+                    .player_character_service
+                    .get_pc(*pc_id)
+                    .await?
+                    .with_context(|| format!("Player character not found: {}", pc_id))?;
+                pcs.push(pc);
+            }
+            pcs
+        };
+        let location_id = start
+            .location_id
+            .or_else(|| pcs.first().map(|pc| pc.current_location_id))
+            .ok_or(CombatError::NoLocation)?;
+
+        let mut encounter = Encounter::new(session_id, world_id, location_id, start.description);
+        for pc in &pcs {
+            encounter
+                .add(pc_combatant(pc, template.as_ref(), &rule))
+                .map_err(CombatError::from)?;
+        }
+        for npc in &start.npcs {
+            let character = self
+                .character_repository
+                .get(npc.character_id)
+                .await?
+                .with_context(|| format!("Character not found: {}", npc.character_id))?;
+            let stats = &character.stats;
+            let modifier = rule.modifier(|name| {
+                stats
+                    .stats
+                    .iter()
+                    .find(|(stat, _)| stat.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| *value)
+            });
+            let mut combatant = Combatant::new(CombatantRef::Npc(character.id), character.name.clone(), npc.side)
+                .with_initiative_modifier(modifier)
+                .with_hp(stats.current_hp.or(stats.max_hp), stats.max_hp);
+            if npc.llm_controlled {
+                combatant = combatant.llm_controlled();
+            }
+            encounter.add(combatant).map_err(CombatError::from)?;
+        }
+
+        // Systems that act in stat order need no rolls
+        let change = if rule.rolled {
+            None
+        } else {
+            for combatant in &mut encounter.combatants {
+                combatant.initiative = Some(combatant.initiative_modifier);
+            }
+            Some(encounter.begin().map_err(CombatError::from)?)
+        };
+
+        {
+            let mut encounters = self.encounters.write().await;
+            if encounters.contains_key(&session_id) {
+                return Err(CombatError::AlreadyInCombat(session_id).into());
+            }
+            encounters.insert(session_id, encounter.clone());
+        }
+        tracing::info!(
+            "Combat started in session {} with {} combatants",
+            session_id,
+            encounter.combatants.len()
+        );
+
+        self.record(&encounter, CombatEventType::Started, None, None, None, game_time.clone())
+            .await;
+        self.notify(session_id, CombatUpdatedNotification::started(&encounter))
+            .await;
+        match change {
+            Some(change) => Ok(self.turn_changed(encounter, Some(change), game_time).await),
+            None => Ok(CombatUpdate::new(encounter)),
+        }
+    }
+
+    /// Roll initiative for one combatant, or everyone still waiting
+    ///
+    /// Rolls are seeded and logged like every other engine roll. The first
+    /// round begins once everyone has initiative.
+    pub async fn roll_initiative(
+        &self,
+        session_id: SessionId,
+        combatant_id: Option<&str>,
+        roller: String,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        let world_id = self
+            .sessions
+            .get_session_world_id(session_id)
+            .await
+            .ok_or(CombatError::SessionNotFound(session_id))?;
+        let world = self
+            .world_repository
+            .get(world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
+        let input = DiceRollInput::Formula(
+            DiceFormula::default_for_system(&world.rule_system.dice_system).to_string(),
+        );
+
+        let mut encounters = self.encounters.write().await;
+        let encounter = encounters
+            .get_mut(&session_id)
+            .ok_or(CombatError::NotInCombat(session_id))?;
+        if encounter.has_begun() {
+            return Err(CombatError::Encounter(EncounterError::AlreadyStarted).into());
+        }
+        let rolling: Vec<(CombatantRef, String, i32)> = match combatant_id {
+            Some(id) => {
+                let combatant = encounter.find(id).map_err(CombatError::from)?;
+                vec![(combatant.who, combatant.name.clone(), combatant.initiative_modifier)]
+            }
+            None => encounter
+                .awaiting_initiative()
+                .map(|c| (c.who, c.name.clone(), c.initiative_modifier))
+                .collect(),
+        };
+
+        for (who, name, modifier) in rolling {
+            let roll = self
+                .dice_roll_service
+                .roll(
+                    session_id,
+                    &input,
+                    modifier,
+                    RollPurpose::Initiative,
+                    Some(format!("{} initiative", name)),
+                    roller.clone(),
+                )
+                .await?;
+            tracing::debug!("{} rolled {} for initiative", name, roll.result.total);
+            encounter
+                .set_initiative(who, roll.result.total)
+                .map_err(CombatError::from)?;
+        }
+
+        let encounter = encounter.clone();
+        drop(encounters);
+        self.initiative_recorded(encounter, game_time).await
+    }
+
+    /// Record an initiative total rolled at the table
+    pub async fn set_initiative(
+        &self,
+        session_id: SessionId,
+        combatant_id: &str,
+        total: i32,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        let encounter = self
+            .update(session_id, |encounter| {
+                if encounter.has_begun() {
+                    return Err(EncounterError::AlreadyStarted);
+                }
+                let who = encounter.find(combatant_id)?.who;
+                encounter.set_initiative(who, total)
+            })
+            .await?
+            .0;
+        self.initiative_recorded(encounter, game_time).await
+    }
+
+    /// End the current turn and pass to the next combatant able to act
+    pub async fn next_turn(&self, session_id: SessionId, game_time: Option<String>) -> Result<CombatUpdate> {
+        let (encounter, change) = self.update(session_id, Encounter::next_turn).await?;
+        Ok(self.turn_changed(encounter, change, game_time).await)
+    }
+
+    /// The current combatant delays, leaving the order until it acts
+    pub async fn delay(&self, session_id: SessionId, game_time: Option<String>) -> Result<CombatUpdate> {
+        let (encounter, change) = self.update(session_id, Encounter::delay).await?;
+        Ok(self.turn_changed(encounter, change, game_time).await)
+    }
+
+    /// The current combatant readies an action for a trigger and ends its
+    /// turn
+    pub async fn ready(
+        &self,
+        session_id: SessionId,
+        trigger: String,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        let (encounter, change) = self
+            .update(session_id, |encounter| encounter.ready(trigger))
+            .await?;
+        Ok(self.turn_changed(encounter, change, game_time).await)
+    }
+
+    /// A delaying combatant steps back into the order and takes its turn,
+    /// or a readied combatant's trigger happens and it spends its action
+    pub async fn act(
+        &self,
+        session_id: SessionId,
+        combatant_id: &str,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        let (encounter, change) = self
+            .update(session_id, |encounter| {
+                let combatant = encounter.find(combatant_id)?;
+                let who = combatant.who;
+                if matches!(combatant.status, CombatantStatus::Readied(_)) {
+                    encounter.trigger_readied(who).map(|_| None)
+                } else {
+                    encounter.act_now(who).map(Some)
+                }
+            })
+            .await?;
+        match change {
+            Some(change) => Ok(self.turn_changed(encounter, Some(change), game_time).await),
+            None => {
+                self.notify(session_id, CombatUpdatedNotification::updated(&encounter))
+                    .await;
+                Ok(CombatUpdate::new(encounter))
+            }
+        }
+    }
+
+    /// Damage a combatant (a negative amount heals)
+    ///
+    /// Player characters lose hit points from their sheet, NPCs from their
+    /// stat block; neither drops below 0 nor heals past its maximum. A
+    /// combatant brought to 0 is defeated, and the fight ends if that
+    /// leaves one side with nobody standing.
+    pub async fn damage(
+        &self,
+        session_id: SessionId,
+        combatant_id: &str,
+        amount: i32,
+        reason: Option<String>,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        let combatant = self
+            .encounter(session_id)
+            .await
+            .ok_or(CombatError::NotInCombat(session_id))?
+            .find(combatant_id)
+            .map_err(CombatError::from)?
+            .clone();
+
+        let (current, max) = match combatant.who {
+            CombatantRef::Pc(pc_id) => {
+                self.damage_pc(pc_id, &combatant.name, amount, reason, game_time.clone())
+                    .await?
+            }
+            CombatantRef::Npc(character_id) => self.damage_npc(character_id, &combatant.name, amount).await?,
+        };
+
+        let (encounter, defeated) = self
+            .update(session_id, |encounter| encounter.record_hp(combatant.who, current, max))
+            .await?;
+        if defeated {
+            self.fallen(encounter, combatant.who, game_time).await
+        } else {
+            self.notify(session_id, CombatUpdatedNotification::updated(&encounter))
+                .await;
+            Ok(CombatUpdate::new(encounter))
+        }
+    }
+
+    /// Take a combatant out of the fight, defeated or fled
+    ///
+    /// The fight ends if that leaves one side with nobody standing.
+    pub async fn remove(
+        &self,
+        session_id: SessionId,
+        combatant_id: &str,
+        fled: bool,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        let (encounter, who) = self
+            .update(session_id, |encounter| {
+                let who = encounter.find(combatant_id)?.who;
+                encounter.remove_from_fight(who, fled).map(|_| who)
+            })
+            .await?;
+        self.fallen(encounter, who, game_time).await
+    }
+
+    /// End the fight in a session
+    ///
+    /// Without an outcome, it is worked out from who is left standing.
+    pub async fn end(
+        &self,
+        session_id: SessionId,
+        outcome: Option<CombatOutcome>,
+        game_time: Option<String>,
+    ) -> Result<CombatUpdate> {
+        let encounter = self
+            .encounters
+            .write()
+            .await
+            .remove(&session_id)
+            .ok_or(CombatError::NotInCombat(session_id))?;
+        let outcome = outcome.or_else(|| encounter.outcome());
+        tracing::info!(
+            "Combat in session {} ended after {} rounds: {:?}",
+            session_id,
+            encounter.round,
+            outcome
+        );
+
+        self.record(
+            &encounter,
+            CombatEventType::Ended,
+            outcome,
+            Some(encounter.round),
+            None,
+            game_time,
+        )
+        .await;
+        self.notify(session_id, CombatEndedNotification::new(outcome, encounter.round))
+            .await;
+
+        let mut update = CombatUpdate::new(encounter);
+        update.ended = outcome;
+        Ok(update)
+    }
+
+    /// Apply a change to a session's fight and return the result
+    async fn update<T>(
+        &self,
+        session_id: SessionId,
+        change: impl FnOnce(&mut Encounter) -> Result<T, EncounterError>,
+    ) -> Result<(Encounter, T)> {
+        let mut encounters = self.encounters.write().await;
+        let encounter = encounters
+            .get_mut(&session_id)
+            .ok_or(CombatError::NotInCombat(session_id))?;
+        let result = change(encounter).map_err(CombatError::from)?;
+        Ok((encounter.clone(), result))
+    }
+
+    /// Begin the first round if everyone has initiative
+    async fn initiative_recorded(&self, encounter: Encounter, game_time: Option<String>) -> Result<CombatUpdate> {
+        if encounter.awaiting_initiative().next().is_some() {
+            self.notify(encounter.session_id, CombatUpdatedNotification::updated(&encounter))
+                .await;
+            return Ok(CombatUpdate::new(encounter));
+        }
+        let (encounter, change) = self.update(encounter.session_id, Encounter::begin).await?;
+        Ok(self.turn_changed(encounter, Some(change), game_time).await)
+    }
+
+    /// Record a completed round, announce whose turn it is and prepare the
+    /// prompt for an LLM-controlled NPC
+    async fn turn_changed(
+        &self,
+        encounter: Encounter,
+        change: Option<TurnChange>,
+        game_time: Option<String>,
+    ) -> CombatUpdate {
+        let mut update = CombatUpdate::new(encounter);
+        let Some(change) = change else {
+            // Nobody is left able to act
+            self.notify(update.encounter.session_id, CombatUpdatedNotification::updated(&update.encounter))
+                .await;
+            return update;
+        };
+
+        if let Some(round) = change.completed_round {
+            self.record(
+                &update.encounter,
+                CombatEventType::RoundCompleted,
+                None,
+                Some(round),
+                Some(format!("Combat round {} completed", round)),
+                game_time,
+            )
+            .await;
+            update.completed_round = Some(round);
+        }
+
+        let encounter = &update.encounter;
+        let Some(current) = encounter.current() else {
+            return update;
+        };
+        self.notify(
+            encounter.session_id,
+            CombatTurnChangedNotification::new(encounter, current, change.completed_round.is_some()),
+        )
+        .await;
+
+        if let (true, CombatantRef::Npc(character_id)) = (current.llm_controlled, current.who) {
+            update.npc_turn = Some(NpcTurn {
+                character_id,
+                name: current.name.clone(),
+                prompt: turn_prompt(encounter, current),
+            });
+        }
+        update
+    }
+
+    /// Record and announce a combatant leaving the fight, ending it if
+    /// one side has nobody left standing
+    async fn fallen(&self, encounter: Encounter, who: CombatantRef, game_time: Option<String>) -> Result<CombatUpdate> {
+        let Some(combatant) = encounter.get(who) else {
+            return Ok(CombatUpdate::new(encounter));
+        };
+        let fled = combatant.status == CombatantStatus::Fled;
+        let (combat_type, summary) = if fled {
+            (CombatEventType::CharacterFled, format!("{} fled from combat", combatant.name))
+        } else {
+            (CombatEventType::CharacterDefeated, format!("{} was defeated", combatant.name))
+        };
+        self.record(&encounter, combat_type, None, Some(encounter.round), Some(summary), game_time.clone())
+            .await;
+        self.notify(encounter.session_id, CombatantDefeatedNotification::new(combatant))
+            .await;
+
+        match encounter.outcome() {
+            Some(outcome) => self.end(encounter.session_id, Some(outcome), game_time).await,
+            None => {
+                self.notify(encounter.session_id, CombatUpdatedNotification::updated(&encounter))
+                    .await;
+                Ok(CombatUpdate::new(encounter))
+            }
+        }
+    }
+
+    /// Change a player character's `HP` resource; returns its hit points
+    async fn damage_pc(
+        &self,
+        pc_id: PlayerCharacterId,
+        name: &str,
+        amount: i32,
+        reason: Option<String>,
+        game_time: Option<String>,
+    ) -> Result<(i32, Option<i32>)> {
+        let pc = self
+            .player_character_service
+            .get_pc(pc_id)
+            .await?
+            .with_context(|| format!("Player character not found: {}", pc_id))?;
+        let template = self
+            .player_character_service
+            .sheet_template(pc.world_id)
+            .await?
+            .context("World of player character not found")?;
+        let level = pc
+            .sheet_data
+            .as_ref()
+            .and_then(|sheet| template.resource_level(sheet, HP_FIELD).ok())
+            .ok_or_else(|| CombatError::NoHitPoints(name.to_string()))?;
+
+        // Damage beyond what is left takes the character to 0
+        let operation = if amount >= 0 {
+            ResourceOperation::Spend(amount.min(level.current - level.floor).max(0).unsigned_abs())
+        } else {
+            ResourceOperation::Restore(amount.unsigned_abs())
+        };
+        let (_, change) = self
+            .sheet_resource_service
+            .modify_resource(pc_id, HP_FIELD, operation, reason, game_time)
+            .await?
+            .with_context(|| format!("Player character not found: {}", pc_id))?;
+        Ok((change.current, level.ceiling))
+    }
+
+    /// Change an NPC's hit points on its stat block; returns them
+    async fn damage_npc(&self, character_id: CharacterId, name: &str, amount: i32) -> Result<(i32, Option<i32>)> {
+        let mut character = self
+            .character_repository
+            .get(character_id)
+            .await?
+            .with_context(|| format!("Character not found: {}", character_id))?;
+        let stats = &mut character.stats;
+        let current = stats
+            .current_hp
+            .or(stats.max_hp)
+            .ok_or_else(|| CombatError::NoHitPoints(name.to_string()))?;
+
+        let mut hp = (current - amount).max(0);
+        if let Some(max) = stats.max_hp {
+            hp = hp.min(max.max(current));
+        }
+        stats.current_hp = Some(hp);
+        let max = stats.max_hp;
+        self.character_repository.update(&character).await?;
+        Ok((hp, max))
+    }
+
+    /// Record a moment of the fight on the story timeline; failures are
+    /// only reported
+    async fn record(
+        &self,
+        encounter: &Encounter,
+        combat_type: CombatEventType,
+        outcome: Option<CombatOutcome>,
+        rounds: Option<u32>,
+        summary: Option<String>,
+        game_time: Option<String>,
+    ) {
+        if let Err(e) = self
+            .story_event_service
+            .record_combat_event(
+                encounter.world_id,
+                encounter.session_id,
+                encounter.location_id,
+                combat_type,
+                encounter.participants(),
+                encounter.enemies(),
+                outcome,
+                rounds,
+                summary,
+                game_time,
+            )
+            .await
+        {
+            tracing::warn!("Failed to record {:?} combat event: {}", combat_type, e);
+        }
+    }
+
+    /// Send a notification to everyone in the session; delivery failures
+    /// are only reported
+    async fn notify(&self, session_id: SessionId, notification: impl serde::Serialize) {
+        let message = match serde_json::to_value(&notification) {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::error!("Failed to serialize combat notification: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self.sessions.broadcast_to_session(session_id, message).await {
+            tracing::warn!("Failed to send combat notification: {}", e);
+        }
+    }
+}
+
+/// A player character as a combatant, with initiative and hit points taken
+/// from its sheet
+fn pc_combatant(pc: &PlayerCharacter, template: Option<&CharacterSheetTemplate>, rule: &InitiativeRule) -> Combatant {
+    let sheet = pc.sheet_data.as_ref();
+    let modifier = rule.modifier(|name| sheet.and_then(|sheet| sheet.get_number(name)));
+    let hp = sheet
+        .zip(template)
+        .and_then(|(sheet, template)| template.resource_level(sheet, HP_FIELD).ok());
+    Combatant::new(CombatantRef::Pc(pc.id), pc.name.clone(), CombatSide::Party)
+        .with_initiative_modifier(modifier)
+        .with_hp(hp.map(|hp| hp.current), hp.and_then(|hp| hp.ceiling))
+}
+
+/// Describe the fight for the LLM and ask for an NPC's action
+fn turn_prompt(encounter: &Encounter, current: &Combatant) -> String {
+    let roster: Vec<String> = encounter
+        .combatants
+        .iter()
+        .map(|c| {
+            let side = match (c.side == current.side, c.who == current.who) {
+                (_, true) => "you",
+                (true, false) => "ally",
+                (false, false) => "foe",
+            };
+            let state = match (&c.status, c.current_hp, c.max_hp) {
+                (CombatantStatus::Defeated, ..) => "defeated".to_string(),
+                (CombatantStatus::Fled, ..) => "fled".to_string(),
+                (_, Some(hp), Some(max)) => format!("{}/{} HP", hp, max),
+                _ => "standing".to_string(),
+            };
+            format!("{} ({}, {})", c.name, side, state)
+        })
+        .collect();
+
+    let mut prompt = format!("Round {} of combat", encounter.round);
+    if !encounter.description.is_empty() {
+        prompt.push_str(&format!(": {}", encounter.description));
+    }
+    prompt.push_str(&format!(
+        ". Combatants in turn order: {}. Decide what {} does on this turn and describe it.",
+        roster.join("; "),
+        current.name
+    ));
+    prompt
+}
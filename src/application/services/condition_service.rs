@@ -0,0 +1,181 @@
+//! Condition Service
+//!
+//! Applies, removes and expires conditions on NPCs and player characters.
+//! Conditions are stored on the character they affect; timed conditions are
+//! expired against the session's game time whenever it advances.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::application::ports::outbound::{CharacterRepositoryPort, PlayerCharacterRepositoryPort};
+use crate::domain::entities::{Character, PlayerCharacter};
+use crate::domain::value_objects::{
+    ActiveCondition, ActiveConditions, CharacterId, PlayerCharacterId, WorldId,
+};
+
+/// A condition that ended, and who it ended on
+#[derive(Debug, Clone)]
+pub struct EndedCondition {
+    /// ID of the NPC or player character
+    pub character_id: String,
+    pub character_name: String,
+    pub condition: ActiveCondition,
+}
+
+/// A character conditions can be applied to
+enum Afflicted {
+    Npc(Character),
+    Pc(PlayerCharacter),
+}
+
+impl Afflicted {
+    fn conditions(&self) -> &ActiveConditions {
+        match self {
+            Self::Npc(character) => &character.conditions,
+            Self::Pc(pc) => &pc.conditions,
+        }
+    }
+
+    fn conditions_mut(&mut self) -> &mut ActiveConditions {
+        match self {
+            Self::Npc(character) => &mut character.conditions,
+            Self::Pc(pc) => &mut pc.conditions,
+        }
+    }
+
+    fn id(&self) -> String {
+        match self {
+            Self::Npc(character) => character.id.to_string(),
+            Self::Pc(pc) => pc.id.to_string(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Npc(character) => &character.name,
+            Self::Pc(pc) => &pc.name,
+        }
+    }
+}
+
+/// Service for character conditions
+#[derive(Clone)]
+pub struct ConditionService {
+    character_repository: Arc<dyn CharacterRepositoryPort>,
+    pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
+}
+
+impl ConditionService {
+    /// Create a new condition service
+    pub fn new(
+        character_repository: Arc<dyn CharacterRepositoryPort>,
+        pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
+    ) -> Self {
+        Self {
+            character_repository,
+            pc_repository,
+        }
+    }
+
+    /// The conditions on an NPC or player character
+    ///
+    /// Returns None if no character has this ID.
+    pub async fn conditions(&self, character_id: &str) -> Result<Option<Vec<ActiveCondition>>> {
+        Ok(self
+            .find(character_id)
+            .await?
+            .map(|afflicted| afflicted.conditions().iter().cloned().collect()))
+    }
+
+    /// The world an NPC or player character belongs to
+    pub async fn world_of(&self, character_id: &str) -> Result<Option<WorldId>> {
+        Ok(self.find(character_id).await?.map(|afflicted| match afflicted {
+            Afflicted::Npc(character) => character.world_id,
+            Afflicted::Pc(pc) => pc.world_id,
+        }))
+    }
+
+    /// Apply a condition to an NPC or player character, replacing any
+    /// condition of the same name
+    ///
+    /// Returns false if no character has this ID.
+    pub async fn apply_condition(&self, character_id: &str, condition: ActiveCondition) -> Result<bool> {
+        let Some(mut afflicted) = self.find(character_id).await? else {
+            return Ok(false);
+        };
+        tracing::info!("{} gained condition '{}' ({})", afflicted.name(), condition.name, condition.duration);
+        afflicted.conditions_mut().apply(condition);
+        self.save(&afflicted).await?;
+        Ok(true)
+    }
+
+    /// Remove a condition by name
+    ///
+    /// Returns the removed condition, or None if the character is unknown or
+    /// did not have it.
+    pub async fn remove_condition(&self, character_id: &str, name: &str) -> Result<Option<ActiveCondition>> {
+        let Some(mut afflicted) = self.find(character_id).await? else {
+            return Ok(None);
+        };
+        let removed = afflicted.conditions_mut().remove(name);
+        if removed.is_some() {
+            self.save(&afflicted).await?;
+        }
+        Ok(removed)
+    }
+
+    /// Expire every timed condition in a world that has run out by `now`
+    pub async fn expire_conditions(&self, world_id: WorldId, now: DateTime<Utc>) -> Result<Vec<EndedCondition>> {
+        let npcs = self.character_repository.list(world_id).await?;
+        let pcs = self.pc_repository.get_by_world(world_id).await?;
+        let afflicted = npcs
+            .into_iter()
+            .map(Afflicted::Npc)
+            .chain(pcs.into_iter().map(Afflicted::Pc));
+
+        let mut ended = Vec::new();
+        for mut afflicted in afflicted {
+            if afflicted.conditions().is_empty() {
+                continue;
+            }
+            let expired = afflicted.conditions_mut().expire(now);
+            if expired.is_empty() {
+                continue;
+            }
+            self.save(&afflicted).await?;
+            ended.extend(expired.into_iter().map(|condition| EndedCondition {
+                character_id: afflicted.id(),
+                character_name: afflicted.name().to_string(),
+                condition,
+            }));
+        }
+
+        if !ended.is_empty() {
+            tracing::info!("{} conditions expired in world {}", ended.len(), world_id);
+        }
+        Ok(ended)
+    }
+
+    /// Look up an NPC or player character by ID
+    async fn find(&self, character_id: &str) -> Result<Option<Afflicted>> {
+        let Ok(uuid) = uuid::Uuid::parse_str(character_id) else {
+            return Ok(None);
+        };
+        if let Some(character) = self.character_repository.get(CharacterId::from_uuid(uuid)).await? {
+            return Ok(Some(Afflicted::Npc(character)));
+        }
+        Ok(self
+            .pc_repository
+            .get(PlayerCharacterId::from_uuid(uuid))
+            .await?
+            .map(Afflicted::Pc))
+    }
+
+    async fn save(&self, afflicted: &Afflicted) -> Result<()> {
+        match afflicted {
+            Afflicted::Npc(character) => self.character_repository.update(character).await,
+            Afflicted::Pc(pc) => self.pc_repository.update(pc).await,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Dice roll service - seeded engine rolls and the per-session roll log.
+//!
+//! Every roll the engine makes on behalf of a session draws its dice from the
+//! session's seeded `RollSource` and is written to the roll log, so the DM can
+//! replay and verify any roll after the fact. A session's seed predicts all
+//! of its future rolls, so it is only revealed once the session has ended.
+//!
+//! Uses `AsyncSessionPort` for session operations, maintaining hexagonal architecture.
+
+use std::sync::Arc;
+
+use crate::application::ports::outbound::{AsyncSessionPort, RollLogError, RollLogPort};
+use crate::domain::entities::{RollLogEntry, RollPurpose};
+use crate::domain::value_objects::{
+    DiceParseError, DiceRollInput, DiceRollResult, RollId, RollMode, SessionId,
+};
+
+/// Dice rolled message DTO (ad-hoc and table rolls)
+#[derive(Debug, Clone, serde::Serialize)]
+struct DiceRolledMessage {
+    r#type: &'static str,
+    roll_id: Option<String>,
+    roller: String,
+    purpose: String,
+    label: Option<String>,
+    formula: String,
+    total: i32,
+    roll_breakdown: String,
+    individual_rolls: Vec<i32>,
+}
+
+/// Error message DTO
+#[derive(Debug, Clone, serde::Serialize)]
+struct ErrorMessage {
+    r#type: &'static str,
+    code: String,
+    message: String,
+}
+
+/// Helper function to create error messages
+fn error_message(code: &str, message: &str) -> Option<serde_json::Value> {
+    let error_msg = ErrorMessage {
+        r#type: "Error",
+        code: code.to_string(),
+        message: message.to_string(),
+    };
+    serde_json::to_value(&error_msg).ok()
+}
+
+/// Why a roll mode could not be granted on a logged roll
+#[derive(Debug, thiserror::Error)]
+pub enum GrantRollError {
+    #[error("Roll {0} is not in the roll log")]
+    NotFound(RollId),
+    #[error("A roll mode has already been granted on roll {0}")]
+    AlreadyGranted(RollId),
+    #[error("Roll {0} could not be replayed")]
+    Unreplayable(RollId),
+    #[error("No roll source for session {0}")]
+    NoSession(SessionId),
+    #[error(transparent)]
+    Dice(#[from] DiceParseError),
+    #[error(transparent)]
+    Log(#[from] RollLogError),
+}
+
+/// A resolved roll and the log entry it was recorded under
+#[derive(Debug, Clone)]
+pub struct LoggedRoll {
+    pub result: DiceRollResult,
+    /// None for manual results, or if the roll could not be recorded
+    pub roll_id: Option<RollId>,
+}
+
+/// Service responsible for seeded, audited dice rolls.
+pub struct DiceRollService {
+    sessions: Arc<dyn AsyncSessionPort>,
+    roll_log: Arc<dyn RollLogPort>,
+}
+
+impl DiceRollService {
+    pub fn new(sessions: Arc<dyn AsyncSessionPort>, roll_log: Arc<dyn RollLogPort>) -> Self {
+        Self { sessions, roll_log }
+    }
+
+    /// Resolve dice input for a session and record the roll.
+    ///
+    /// Formula input is rolled from the session's seeded source. Manual results
+    /// are passed through unlogged since no engine dice were involved.
+    pub async fn roll(
+        &self,
+        session_id: SessionId,
+        input: &DiceRollInput,
+        skill_modifier: i32,
+        purpose: RollPurpose,
+        label: Option<String>,
+        roller: String,
+    ) -> Result<LoggedRoll, DiceParseError> {
+        if matches!(input, DiceRollInput::ManualResult(_)) {
+            return Ok(LoggedRoll {
+                result: input.resolve_with_modifier(skill_modifier)?,
+                roll_id: None,
+            });
+        }
+
+        let Some(mut source) = self.sessions.next_roll_source(session_id).await else {
+            tracing::warn!(
+                session_id = %session_id,
+                "No roll source for session, rolling unseeded"
+            );
+            return Ok(LoggedRoll {
+                result: input.resolve_with_modifier(skill_modifier)?,
+                roll_id: None,
+            });
+        };
+
+        let result = input.resolve_with_modifier_from(skill_modifier, &mut source)?;
+        let Some(entry) =
+            RollLogEntry::record(session_id, purpose, label, roller, &source, &result)
+        else {
+            return Ok(LoggedRoll { result, roll_id: None });
+        };
+
+        let roll_id = match self.roll_log.record(&entry).await {
+            Ok(()) => Some(entry.id),
+            Err(e) => {
+                tracing::error!("Failed to record roll {} in roll log: {}", entry.id, e);
+                None
+            }
+        };
+
+        Ok(LoggedRoll { result, roll_id })
+    }
+
+    /// Grant a roll mode on a logged roll after the fact, recording the
+    /// combined roll.
+    ///
+    /// Only the extra dice are drawn, from the session's next sequence index;
+    /// the dice already rolled are kept. A roll can be given a mode once.
+    pub async fn grant_roll_mode(
+        &self,
+        session_id: SessionId,
+        roll_id: RollId,
+        mode: RollMode,
+    ) -> Result<LoggedRoll, GrantRollError> {
+        let base = self
+            .roll_log
+            .get(roll_id)
+            .await?
+            .filter(|entry| entry.session_id == session_id)
+            .ok_or(GrantRollError::NotFound(roll_id))?;
+        if base.granted.is_some() {
+            return Err(GrantRollError::AlreadyGranted(roll_id));
+        }
+        let original = base.replay().ok_or(GrantRollError::Unreplayable(roll_id))?;
+        let extra_dice = original.granted_dice(mode)?;
+
+        let mut source = self
+            .sessions
+            .next_roll_source(session_id)
+            .await
+            .ok_or(GrantRollError::NoSession(session_id))?;
+        let extra = extra_dice.roll_from(&mut source);
+        let result = original.with_granted_mode(mode, &extra.individual_rolls)?;
+        let entry = RollLogEntry::record_granted(&base, mode, &source, &result)
+            .ok_or(GrantRollError::Unreplayable(roll_id))?;
+        self.roll_log.record(&entry).await?;
+
+        Ok(LoggedRoll {
+            result,
+            roll_id: Some(entry.id),
+        })
+    }
+
+    /// Handle a free-standing roll (ad-hoc or random table) from a client.
+    ///
+    /// The result is broadcast to everyone in the session.
+    pub async fn handle_free_roll(
+        &self,
+        client_id: String,
+        formula: String,
+        label: Option<String>,
+        is_table_roll: bool,
+    ) -> Option<serde_json::Value> {
+        let Some(session_id) = self.sessions.get_client_session(&client_id).await else {
+            return error_message("NOT_IN_SESSION", "You must join a session before rolling dice");
+        };
+        let roller = self
+            .sessions
+            .get_client_user_id(&client_id)
+            .await
+            .unwrap_or_else(|| "Unknown Player".to_string());
+        let purpose = if is_table_roll {
+            RollPurpose::Table
+        } else {
+            RollPurpose::AdHoc
+        };
+
+        let logged = match self
+            .roll(
+                session_id,
+                &DiceRollInput::Formula(formula.clone()),
+                0,
+                purpose,
+                label.clone(),
+                roller.clone(),
+            )
+            .await
+        {
+            Ok(logged) => logged,
+            Err(e) => {
+                return error_message("INVALID_DICE_FORMULA", &format!("Invalid dice formula: {}", e));
+            }
+        };
+
+        let msg = DiceRolledMessage {
+            r#type: "DiceRolled",
+            roll_id: logged.roll_id.map(|id| id.to_string()),
+            roller,
+            purpose: purpose.as_str().to_string(),
+            label,
+            formula,
+            total: logged.result.total,
+            roll_breakdown: logged.result.breakdown(),
+            individual_rolls: logged.result.individual_rolls.clone(),
+        };
+        match serde_json::to_value(&msg) {
+            Ok(json) => {
+                if let Err(e) = self.sessions.broadcast_to_session(session_id, json).await {
+                    tracing::error!("Failed to broadcast DiceRolled: {}", e);
+                }
+            }
+            Err(e) => tracing::error!("Failed to serialize DiceRolled message: {}", e),
+        }
+
+        None
+    }
+
+    /// List a session's logged rolls, most recent first
+    pub async fn list_session_rolls(
+        &self,
+        session_id: SessionId,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<RollLogEntry>, RollLogError> {
+        self.roll_log.list_for_session(session_id, limit, offset).await
+    }
+
+    /// Fetch a single logged roll
+    pub async fn get_roll(&self, roll_id: RollId) -> Result<Option<RollLogEntry>, RollLogError> {
+        self.roll_log.get(roll_id).await
+    }
+
+    /// Whether a session's seed may be revealed, which is once it has ended
+    pub async fn is_seed_revealed(&self, session_id: SessionId) -> bool {
+        self.sessions.get_session_world_id(session_id).await.is_none()
+    }
+}
@@ -13,6 +13,7 @@ use crate::application::ports::outbound::{
     SessionManagementPort,
 };
 use crate::application::services::tool_execution_service::ToolExecutionService;
+use crate::application::services::ConditionService;
 use crate::application::dto::ApprovalItem;
 use crate::domain::value_objects::{ApprovalDecision, GameTool, SessionId};
 
@@ -34,6 +35,12 @@ impl<Q: ApprovalQueuePort<ApprovalItem>> DMApprovalQueueService<Q> {
         }
     }
 
+    /// Persist conditions from approved condition tools on the affected characters
+    pub fn with_condition_service(mut self, condition_service: ConditionService) -> Self {
+        self.tool_execution_service = self.tool_execution_service.with_condition_service(condition_service);
+        self
+    }
+
     /// Get all pending approvals for a session (for DM UI)
     pub async fn get_pending(&self, session_id: SessionId) -> Result<Vec<QueueItem<ApprovalItem>>, QueueError> {
         // The underlying ApprovalQueuePort implementation may not filter by
@@ -442,6 +449,49 @@ impl<Q: ApprovalQueuePort<ApprovalItem>> DMApprovalQueueService<Q> {
                     description,
                 })
             }
+            "add_condition" => {
+                let character_id = args
+                    .get("character_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| QueueError::Backend("Missing character_id".to_string()))?
+                    .to_string();
+                let condition_name = args
+                    .get("condition_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| QueueError::Backend("Missing condition_name".to_string()))?
+                    .to_string();
+                let description = args
+                    .get("description")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let duration = args
+                    .get("duration")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                Ok(GameTool::AddCondition {
+                    character_id,
+                    condition_name,
+                    description,
+                    duration,
+                })
+            }
+            "remove_condition" => {
+                let character_id = args
+                    .get("character_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| QueueError::Backend("Missing character_id".to_string()))?
+                    .to_string();
+                let condition_name = args
+                    .get("condition_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| QueueError::Backend("Missing condition_name".to_string()))?
+                    .to_string();
+                Ok(GameTool::RemoveCondition {
+                    character_id,
+                    condition_name,
+                })
+            }
             _ => Err(QueueError::Backend(format!("Unknown tool: {}", tool_info.name))),
         }
     }
@@ -14,8 +14,11 @@
 //! - `ModifyRelationship` - Changes NPC relationship sentiment
 //! - `ModifyStat` - Changes character stat value
 //! - `TriggerScene` - Initiates scene transition
-//! - `StartCombat` - Initiates combat encounter
-//! - `AddReward` - Grants experience or rewards
+//! - `StartCombat` - Starts a combat encounter against the named NPCs
+//! - `AreaEffect` - Sets off a spell or hazard over an area of a grid map,
+//!   calling for saving throws from the characters caught
+//! - `AddReward` - Grants experience and milestones to the session's characters
+//!   (other rewards are logged for the DM)
 //! - `Custom` - Logs for DM action
 //!
 //! # Architecture
@@ -31,8 +34,11 @@ use crate::application::ports::outbound::{
     AsyncSessionPort, ChallengeRepositoryPort, NarrativeEventRepositoryPort,
     RelationshipRepositoryPort,
 };
-use crate::domain::entities::EventEffect;
-use crate::domain::value_objects::SessionId;
+use crate::application::services::{
+    AdvancementService, AreaEffectService, CombatNpc, CombatService, StartCombat,
+};
+use crate::domain::entities::{AreaTemplate, CombatSide, EventEffect};
+use crate::domain::value_objects::{AdvancementAward, ChallengeId, CharacterId, GridMapId, SessionId};
 
 // =============================================================================
 // Error Types
@@ -103,6 +109,9 @@ pub struct EventEffectExecutor {
     challenge_repo: Arc<dyn ChallengeRepositoryPort>,
     narrative_event_repo: Arc<dyn NarrativeEventRepositoryPort>,
     relationship_repo: Arc<dyn RelationshipRepositoryPort>,
+    advancement_service: Option<AdvancementService>,
+    combat_service: Option<CombatService>,
+    area_effect_service: Option<AreaEffectService>,
 }
 
 impl EventEffectExecutor {
@@ -118,9 +127,30 @@ impl EventEffectExecutor {
             challenge_repo,
             narrative_event_repo,
             relationship_repo,
+            advancement_service: None,
+            combat_service: None,
+            area_effect_service: None,
         }
     }
 
+    /// Set the advancement service, so rewards grant experience and milestones
+    pub fn with_advancement_service(mut self, advancement_service: AdvancementService) -> Self {
+        self.advancement_service = Some(advancement_service);
+        self
+    }
+
+    /// Set the combat service, so starting combat opens an encounter
+    pub fn with_combat_service(mut self, combat_service: CombatService) -> Self {
+        self.combat_service = Some(combat_service);
+        self
+    }
+
+    /// Set the area effect service, so area effects resolve on their grid map
+    pub fn with_area_effect_service(mut self, area_effect_service: AreaEffectService) -> Self {
+        self.area_effect_service = Some(area_effect_service);
+        self
+    }
+
     /// Execute all effects from an outcome
     ///
     /// # Arguments
@@ -222,8 +252,12 @@ impl EventEffectExecutor {
                 self.execute_trigger_scene(*scene_id, scene_name, session_id).await
             }
 
-            EventEffect::StartCombat { participants: _, participant_names, combat_description } => {
-                self.execute_start_combat(participant_names, combat_description, session_id).await
+            EventEffect::StartCombat { participants, participant_names, combat_description } => {
+                self.execute_start_combat(participants, participant_names, combat_description, session_id).await
+            }
+
+            EventEffect::AreaEffect { grid_map_id, template, description, saving_throw } => {
+                self.execute_area_effect(*grid_map_id, template, description, *saving_throw, session_id).await
             }
 
             EventEffect::AddReward { reward_type, amount, description } => {
@@ -541,6 +575,7 @@ impl EventEffectExecutor {
 
     async fn execute_start_combat(
         &self,
+        participants: &[CharacterId],
         participant_names: &[String],
         combat_description: &str,
         session_id: SessionId,
@@ -554,6 +589,37 @@ impl EventEffectExecutor {
         );
         let _ = self.sessions.add_to_conversation_history(session_id, "System", &msg).await;
 
+        // The named NPCs face the session's characters
+        if let Some(combat) = self.combat_service.as_ref() {
+            let start = StartCombat {
+                description: combat_description.to_string(),
+                npcs: participants
+                    .iter()
+                    .map(|&character_id| CombatNpc {
+                        character_id,
+                        side: CombatSide::Enemy,
+                        llm_controlled: true,
+                    })
+                    .collect(),
+                ..Default::default()
+            };
+            match combat.start(session_id, start, None).await {
+                Ok(update) => {
+                    return EffectExecutionResult {
+                        description: format!(
+                            "Combat started: {} ({} combatants)",
+                            combat_description,
+                            update.encounter.combatants.len()
+                        ),
+                        was_executed: true,
+                        note: (!update.encounter.has_begun())
+                            .then(|| "DM should roll initiative".to_string()),
+                    };
+                }
+                Err(e) => warn!(error = %e, "Failed to start combat"),
+            }
+        }
+
         EffectExecutionResult {
             description: format!("Combat initiated: {}", combat_description),
             was_executed: false, // DM should run combat
@@ -561,6 +627,47 @@ impl EventEffectExecutor {
         }
     }
 
+    async fn execute_area_effect(
+        &self,
+        grid_map_id: GridMapId,
+        template: &AreaTemplate,
+        description: &str,
+        saving_throw: Option<ChallengeId>,
+        session_id: SessionId,
+    ) -> EffectExecutionResult {
+        debug!(grid_map_id = %grid_map_id, description = description, "Setting off area effect");
+
+        let msg = format!("[AREA EFFECT] {}", description);
+        let _ = self.sessions.add_to_conversation_history(session_id, "System", &msg).await;
+
+        if let Some(area_effects) = self.area_effect_service.as_ref() {
+            match area_effects
+                .apply(session_id, grid_map_id, template, description, saving_throw)
+                .await
+            {
+                Ok(area) => {
+                    return EffectExecutionResult {
+                        description: format!(
+                            "{} caught {} tokens ({} saving throws called)",
+                            description,
+                            area.tokens.len(),
+                            area.saving_throws.len()
+                        ),
+                        was_executed: true,
+                        note: None,
+                    };
+                }
+                Err(e) => warn!(error = %e, "Failed to set off area effect"),
+            }
+        }
+
+        EffectExecutionResult {
+            description: format!("Area effect: {}", description),
+            was_executed: false, // DM should resolve the area
+            note: Some("DM should resolve the area and any saving throws".to_string()),
+        }
+    }
+
     async fn execute_add_reward(
         &self,
         reward_type: &str,
@@ -573,6 +680,24 @@ impl EventEffectExecutor {
         let msg = format!("[REWARD] {} {} - {}", amount, reward_type, description);
         let _ = self.sessions.add_to_conversation_history(session_id, "System", &msg).await;
 
+        // Experience and milestones go to every character in the session
+        if let (Some(award), Some(advancement)) = (
+            AdvancementAward::from_reward(reward_type, amount),
+            self.advancement_service.as_ref(),
+        ) {
+            let reason = (!description.is_empty()).then(|| description.to_string());
+            match advancement.award_session(session_id, award, reason).await {
+                Ok(awarded) => {
+                    return EffectExecutionResult {
+                        description: format!("Awarded {} to {} characters ({})", award, awarded.len(), description),
+                        was_executed: true,
+                        note: None,
+                    };
+                }
+                Err(e) => warn!(error = %e, "Failed to award {}", award),
+            }
+        }
+
         EffectExecutionResult {
             description: format!("Awarded {} {} ({})", amount, reward_type, description),
             was_executed: false, // DM should apply reward
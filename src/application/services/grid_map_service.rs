@@ -0,0 +1,513 @@
+//! Grid Map Service - Application service for tactical grid maps
+//!
+//! This service provides use case implementations for creating and editing
+//! the tactical maps a location's fights are played out on, and for finding
+//! paths, movement ranges and cover across them. Maps can also be generated
+//! from a seed, their rooms becoming regions of a location. Changes and
+//! queries that do not fit a map fail with a `GridMapError`.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, info, instrument};
+
+use crate::application::ports::outbound::{
+    GridMapRepositoryPort, LocationRepositoryPort, WorldRepositoryPort,
+};
+use crate::domain::entities::{
+    Cover, GridMap, GridMapError, GridPath, GridPosition, MapGenerator, MapStyle, ReachableTile, Region,
+    Tile,
+};
+use crate::domain::value_objects::{DiagonalRule, GridMapId, LocationId, WorldId};
+
+/// Largest tile size, in pixels, a map may be rendered at
+const MAX_TILE_SIZE: u32 = 512;
+
+/// Most rooms a generated dungeon may have
+const MAX_GENERATED_ROOMS: u32 = 100;
+
+/// Most times a generated cave may be smoothed
+const MAX_SMOOTHING_PASSES: u32 = 20;
+
+/// Most hills, and most ponds, a generated outdoor map may have
+const MAX_OUTDOOR_FEATURES: u32 = 50;
+
+/// Request to create a new grid map
+#[derive(Debug, Clone)]
+pub struct CreateGridMapRequest {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub tilesheet_asset: String,
+    pub tile_size: Option<u32>,
+    /// Every tile, row by row; all ground if not given
+    pub tiles: Option<Vec<Vec<Tile>>>,
+}
+
+/// Request to update an existing grid map
+#[derive(Debug, Clone, Default)]
+pub struct UpdateGridMapRequest {
+    pub name: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub tilesheet_asset: Option<String>,
+    pub tile_size: Option<u32>,
+    pub tiles: Option<Vec<Vec<Tile>>>,
+}
+
+/// Request to generate a grid map from a seed
+#[derive(Debug, Clone)]
+pub struct GenerateGridMapRequest {
+    pub name: String,
+    pub tilesheet_asset: String,
+    pub tile_size: Option<u32>,
+    pub generator: MapGenerator,
+    /// Location the map becomes the tactical map of
+    pub location_id: Option<LocationId>,
+    /// Whether each generated room becomes a region of the location
+    pub rooms_as_regions: bool,
+}
+
+/// A generated grid map and the regions made from its rooms
+#[derive(Debug, Clone)]
+pub struct GeneratedGridMap {
+    pub grid_map: GridMap,
+    pub regions: Vec<Region>,
+}
+
+/// Grid map service trait defining the application use cases
+#[async_trait]
+pub trait GridMapService: Send + Sync {
+    /// List all grid maps in a world
+    async fn list_grid_maps(&self, world_id: WorldId) -> Result<Vec<GridMap>>;
+
+    /// Get a grid map by ID
+    async fn get_grid_map(&self, id: GridMapId) -> Result<Option<GridMap>>;
+
+    /// Get the tactical map of a location, if it has one
+    async fn get_location_grid_map(&self, location_id: LocationId) -> Result<Option<GridMap>>;
+
+    /// Create a grid map in a world
+    async fn create_grid_map(&self, world_id: WorldId, request: CreateGridMapRequest) -> Result<GridMap>;
+
+    /// Generate a grid map in a world from a seed
+    ///
+    /// Given a location, the map becomes its tactical map and, if asked,
+    /// each room of a dungeon one of its regions.
+    async fn generate_grid_map(
+        &self,
+        world_id: WorldId,
+        request: GenerateGridMapRequest,
+    ) -> Result<GeneratedGridMap>;
+
+    /// Update a grid map
+    async fn update_grid_map(&self, id: GridMapId, request: UpdateGridMapRequest) -> Result<GridMap>;
+
+    /// Replace some of a grid map's tiles
+    async fn patch_tiles(&self, id: GridMapId, patches: Vec<(u32, u32, Tile)>) -> Result<GridMap>;
+
+    /// Delete a grid map, unlinking it from its location
+    async fn delete_grid_map(&self, id: GridMapId) -> Result<()>;
+
+    /// The cheapest route between two tiles, avoiding occupied ones
+    ///
+    /// Diagonals follow the world's rule system unless a rule is given.
+    /// Given the tiles a character has explored, every other tile counts as
+    /// a wall, here and in the queries below, so nothing leaks through the
+    /// fog of war.
+    async fn find_path(
+        &self,
+        id: GridMapId,
+        from: GridPosition,
+        to: GridPosition,
+        rule: Option<DiagonalRule>,
+        occupied: HashSet<GridPosition>,
+        explored: Option<HashSet<GridPosition>>,
+    ) -> Result<Option<GridPath>>;
+
+    /// Every tile reachable from a tile for a movement budget
+    async fn movement_range(
+        &self,
+        id: GridMapId,
+        from: GridPosition,
+        movement: u32,
+        rule: Option<DiagonalRule>,
+        occupied: HashSet<GridPosition>,
+        explored: Option<HashSet<GridPosition>>,
+    ) -> Result<Vec<ReachableTile>>;
+
+    /// The cover a target has from an attacker; `Cover::Total` if the
+    /// attacker cannot see it at all
+    async fn cover(
+        &self,
+        id: GridMapId,
+        attacker: GridPosition,
+        target: GridPosition,
+        explored: Option<HashSet<GridPosition>>,
+    ) -> Result<Cover>;
+}
+
+/// Default implementation of GridMapService using port abstractions
+#[derive(Clone)]
+pub struct GridMapServiceImpl {
+    grid_map_repository: Arc<dyn GridMapRepositoryPort>,
+    world_repository: Arc<dyn WorldRepositoryPort>,
+    location_repository: Arc<dyn LocationRepositoryPort>,
+}
+
+impl GridMapServiceImpl {
+    /// Create a new GridMapServiceImpl with the given repositories
+    pub fn new(
+        grid_map_repository: Arc<dyn GridMapRepositoryPort>,
+        world_repository: Arc<dyn WorldRepositoryPort>,
+        location_repository: Arc<dyn LocationRepositoryPort>,
+    ) -> Self {
+        Self {
+            grid_map_repository,
+            world_repository,
+            location_repository,
+        }
+    }
+
+    fn validate_name(name: &str) -> Result<()> {
+        if name.trim().is_empty() {
+            anyhow::bail!("Grid map name cannot be empty");
+        }
+        if name.len() > 255 {
+            anyhow::bail!("Grid map name cannot exceed 255 characters");
+        }
+        Ok(())
+    }
+
+    fn validate_tile_size(tile_size: u32) -> Result<()> {
+        if tile_size == 0 || tile_size > MAX_TILE_SIZE {
+            anyhow::bail!("Tile size must be between 1 and {} pixels", MAX_TILE_SIZE);
+        }
+        Ok(())
+    }
+
+    fn validate_style(style: &MapStyle) -> Result<()> {
+        match style {
+            MapStyle::Dungeon(settings) if settings.max_rooms > MAX_GENERATED_ROOMS => {
+                anyhow::bail!("A dungeon can have at most {} rooms", MAX_GENERATED_ROOMS);
+            }
+            MapStyle::Cave(settings) if settings.smoothing_passes > MAX_SMOOTHING_PASSES => {
+                anyhow::bail!("A cave can be smoothed at most {} times", MAX_SMOOTHING_PASSES);
+            }
+            MapStyle::Outdoor(settings)
+                if settings.hills > MAX_OUTDOOR_FEATURES || settings.ponds > MAX_OUTDOOR_FEATURES =>
+            {
+                anyhow::bail!("An outdoor map can have at most {0} hills and {0} ponds", MAX_OUTDOOR_FEATURES);
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn load(&self, id: GridMapId) -> Result<GridMap> {
+        self.grid_map_repository
+            .get(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Grid map not found: {}", id))
+    }
+
+    /// A grid map as known to whoever has explored the given tiles, or the
+    /// whole of it
+    async fn load_explored(&self, id: GridMapId, explored: Option<HashSet<GridPosition>>) -> Result<GridMap> {
+        let grid_map = self.load(id).await?;
+        Ok(match explored {
+            Some(explored) => grid_map.as_explored(&explored),
+            None => grid_map,
+        })
+    }
+
+    /// The diagonal rule to move by: the one asked for, else the world's
+    async fn diagonal_rule(&self, grid_map: &GridMap, rule: Option<DiagonalRule>) -> Result<DiagonalRule> {
+        if let Some(rule) = rule {
+            return Ok(rule);
+        }
+        let world = self
+            .world_repository
+            .get(grid_map.world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", grid_map.world_id))?;
+        Ok(world.rule_system.diagonal_rule())
+    }
+
+    fn check_on_map(grid_map: &GridMap, (x, y): GridPosition) -> Result<(), GridMapError> {
+        if grid_map.contains(x, y) {
+            Ok(())
+        } else {
+            Err(GridMapError::OutOfBounds { x, y })
+        }
+    }
+}
+
+#[async_trait]
+impl GridMapService for GridMapServiceImpl {
+    #[instrument(skip(self))]
+    async fn list_grid_maps(&self, world_id: WorldId) -> Result<Vec<GridMap>> {
+        debug!(world_id = %world_id, "Listing grid maps for world");
+        self.grid_map_repository
+            .list(world_id)
+            .await
+            .context("Failed to list grid maps from repository")
+    }
+
+    #[instrument(skip(self))]
+    async fn get_grid_map(&self, id: GridMapId) -> Result<Option<GridMap>> {
+        debug!(grid_map_id = %id, "Fetching grid map");
+        self.grid_map_repository
+            .get(id)
+            .await
+            .context("Failed to get grid map from repository")
+    }
+
+    #[instrument(skip(self))]
+    async fn get_location_grid_map(&self, location_id: LocationId) -> Result<Option<GridMap>> {
+        match self.location_repository.get_grid_map_id(location_id).await? {
+            Some(id) => self.get_grid_map(id).await,
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip(self, request), fields(world_id = %world_id, name = %request.name))]
+    async fn create_grid_map(&self, world_id: WorldId, request: CreateGridMapRequest) -> Result<GridMap> {
+        Self::validate_name(&request.name)?;
+        GridMap::check_size(request.width, request.height)?;
+
+        let _ = self
+            .world_repository
+            .get(world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
+
+        let mut grid_map = GridMap::new(
+            world_id,
+            request.name,
+            request.width,
+            request.height,
+            request.tilesheet_asset,
+        );
+        if let Some(tile_size) = request.tile_size {
+            Self::validate_tile_size(tile_size)?;
+            grid_map.tile_size = tile_size;
+        }
+        if let Some(tiles) = request.tiles {
+            grid_map.set_tiles(tiles)?;
+        }
+
+        self.grid_map_repository
+            .save(&grid_map)
+            .await
+            .context("Failed to save grid map in repository")?;
+
+        info!(grid_map_id = %grid_map.id, "Created grid map: {}", grid_map.name);
+        Ok(grid_map)
+    }
+
+    #[instrument(skip(self, request), fields(world_id = %world_id, name = %request.name))]
+    async fn generate_grid_map(
+        &self,
+        world_id: WorldId,
+        request: GenerateGridMapRequest,
+    ) -> Result<GeneratedGridMap> {
+        if request.rooms_as_regions && request.location_id.is_none() {
+            anyhow::bail!("Rooms can only become regions of a location");
+        }
+        if let Some(location_id) = request.location_id {
+            let location = self
+                .location_repository
+                .get(location_id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Location not found: {}", location_id))?;
+            if location.world_id != world_id {
+                anyhow::bail!("Location {} is not in world {}", location_id, world_id);
+            }
+        }
+
+        Self::validate_style(&request.generator.style)?;
+        let layout = request.generator.generate()?;
+        let grid_map = self
+            .create_grid_map(
+                world_id,
+                CreateGridMapRequest {
+                    name: request.name,
+                    width: request.generator.width,
+                    height: request.generator.height,
+                    tilesheet_asset: request.tilesheet_asset,
+                    tile_size: request.tile_size,
+                    tiles: Some(layout.tiles),
+                },
+            )
+            .await?;
+
+        let mut regions = Vec::new();
+        if let Some(location_id) = request.location_id {
+            self.location_repository
+                .set_grid_map(location_id, grid_map.id)
+                .await
+                .context("Failed to set location grid map in repository")?;
+
+            if request.rooms_as_regions {
+                for (order, room) in layout.rooms.iter().enumerate() {
+                    let region = Region::new(location_id, format!("{} - Room {}", grid_map.name, order + 1))
+                        .with_description(format!("A {} by {} room", room.width, room.height))
+                        .with_map_bounds(room.map_bounds(grid_map.tile_size))
+                        .with_order(order as u32);
+                    self.location_repository
+                        .create_region(location_id, &region)
+                        .await
+                        .context("Failed to save generated region in repository")?;
+                    regions.push(region);
+                }
+            }
+        }
+
+        info!(
+            grid_map_id = %grid_map.id,
+            seed = request.generator.seed,
+            regions = regions.len(),
+            "Generated grid map: {}",
+            grid_map.name
+        );
+        Ok(GeneratedGridMap { grid_map, regions })
+    }
+
+    #[instrument(skip(self, request), fields(grid_map_id = %id))]
+    async fn update_grid_map(&self, id: GridMapId, request: UpdateGridMapRequest) -> Result<GridMap> {
+        let mut grid_map = self.load(id).await?;
+
+        if let Some(name) = request.name {
+            Self::validate_name(&name)?;
+            grid_map.name = name;
+        }
+        if request.width.is_some() || request.height.is_some() {
+            grid_map.resize(
+                request.width.unwrap_or(grid_map.width),
+                request.height.unwrap_or(grid_map.height),
+            )?;
+        }
+        if let Some(tilesheet_asset) = request.tilesheet_asset {
+            grid_map.tilesheet_asset = tilesheet_asset;
+        }
+        if let Some(tile_size) = request.tile_size {
+            Self::validate_tile_size(tile_size)?;
+            grid_map.tile_size = tile_size;
+        }
+        if let Some(tiles) = request.tiles {
+            grid_map.set_tiles(tiles)?;
+        }
+
+        self.grid_map_repository
+            .save(&grid_map)
+            .await
+            .context("Failed to update grid map in repository")?;
+
+        info!(grid_map_id = %id, "Updated grid map: {}", grid_map.name);
+        Ok(grid_map)
+    }
+
+    #[instrument(skip(self, patches), fields(grid_map_id = %id, count = patches.len()))]
+    async fn patch_tiles(&self, id: GridMapId, patches: Vec<(u32, u32, Tile)>) -> Result<GridMap> {
+        let mut grid_map = self.load(id).await?;
+        grid_map.patch_tiles(patches)?;
+
+        self.grid_map_repository
+            .save(&grid_map)
+            .await
+            .context("Failed to save grid map tiles in repository")?;
+
+        debug!(grid_map_id = %id, "Patched tiles of grid map: {}", grid_map.name);
+        Ok(grid_map)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_grid_map(&self, id: GridMapId) -> Result<()> {
+        let grid_map = self.load(id).await?;
+
+        self.grid_map_repository
+            .delete(id)
+            .await
+            .context("Failed to delete grid map from repository")?;
+
+        info!(grid_map_id = %id, "Deleted grid map: {}", grid_map.name);
+        Ok(())
+    }
+
+    #[instrument(skip(self, occupied, explored))]
+    async fn find_path(
+        &self,
+        id: GridMapId,
+        from: GridPosition,
+        to: GridPosition,
+        rule: Option<DiagonalRule>,
+        occupied: HashSet<GridPosition>,
+        explored: Option<HashSet<GridPosition>>,
+    ) -> Result<Option<GridPath>> {
+        let grid_map = self.load_explored(id, explored).await?;
+        Self::check_on_map(&grid_map, from)?;
+        Self::check_on_map(&grid_map, to)?;
+        let rule = self.diagonal_rule(&grid_map, rule).await?;
+
+        Ok(grid_map.find_path(from, to, rule, &occupied))
+    }
+
+    #[instrument(skip(self, occupied, explored))]
+    async fn movement_range(
+        &self,
+        id: GridMapId,
+        from: GridPosition,
+        movement: u32,
+        rule: Option<DiagonalRule>,
+        occupied: HashSet<GridPosition>,
+        explored: Option<HashSet<GridPosition>>,
+    ) -> Result<Vec<ReachableTile>> {
+        let grid_map = self.load_explored(id, explored).await?;
+        Self::check_on_map(&grid_map, from)?;
+        let rule = self.diagonal_rule(&grid_map, rule).await?;
+
+        Ok(grid_map.reachable(from, movement, rule, &occupied))
+    }
+
+    #[instrument(skip(self, explored))]
+    async fn cover(
+        &self,
+        id: GridMapId,
+        attacker: GridPosition,
+        target: GridPosition,
+        explored: Option<HashSet<GridPosition>>,
+    ) -> Result<Cover> {
+        let grid_map = self.load_explored(id, explored).await?;
+        Self::check_on_map(&grid_map, attacker)?;
+        Self::check_on_map(&grid_map, target)?;
+
+        Ok(grid_map.cover_between(attacker, target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{CaveSettings, DungeonSettings, OutdoorSettings};
+
+    #[test]
+    fn test_grid_map_request_validation() {
+        assert!(GridMapServiceImpl::validate_name("").is_err());
+        assert!(GridMapServiceImpl::validate_name(&"x".repeat(256)).is_err());
+        assert!(GridMapServiceImpl::validate_name("Goblin Cave").is_ok());
+
+        assert!(GridMapServiceImpl::validate_tile_size(0).is_err());
+        assert!(GridMapServiceImpl::validate_tile_size(MAX_TILE_SIZE + 1).is_err());
+        assert!(GridMapServiceImpl::validate_tile_size(64).is_ok());
+
+        let dungeon = |max_rooms| MapStyle::Dungeon(DungeonSettings { max_rooms, ..DungeonSettings::default() });
+        assert!(GridMapServiceImpl::validate_style(&dungeon(MAX_GENERATED_ROOMS)).is_ok());
+        assert!(GridMapServiceImpl::validate_style(&dungeon(u32::MAX)).is_err());
+        let cave = MapStyle::Cave(CaveSettings { smoothing_passes: 1_000_000, ..CaveSettings::default() });
+        assert!(GridMapServiceImpl::validate_style(&cave).is_err());
+        let outdoor = MapStyle::Outdoor(OutdoorSettings { ponds: MAX_OUTDOOR_FEATURES + 1, ..OutdoorSettings::default() });
+        assert!(GridMapServiceImpl::validate_style(&outdoor).is_err());
+        assert!(GridMapServiceImpl::validate_style(&MapStyle::Outdoor(OutdoorSettings::default())).is_ok());
+    }
+}
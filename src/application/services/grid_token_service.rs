@@ -0,0 +1,389 @@
+//! Grid Token Service
+//!
+//! Places the tokens on grid maps and moves them. A move follows the
+//! cheapest route the map allows, around impassable tiles and other tokens,
+//! and is refused if there is none. Players may only move their own
+//! character's token; once the DM locks movement on a map, only on that
+//! character's turn in the session's fight.
+//!
+//! Everyone in the session hears of each token that is placed, changed,
+//! moved or removed, except that hidden tokens are only ever shown to the
+//! DM. When a player character's token moves, its player is sent what it
+//! now sees. Movement locks last as long as the server; they are not
+//! persisted.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::application::dto::{
+    MovementLockedNotification, TokenMovedDto, TokenMovedNotification, TokenRemovedNotification,
+    TokenUpdatedNotification,
+};
+use crate::application::ports::outbound::{AsyncSessionPort, GridTokenRepositoryPort};
+use crate::application::services::{CombatService, GridMapService, VisionService};
+use crate::domain::entities::{
+    CombatantRef, Facing, GridMap, GridPath, GridPosition, GridToken, TokenPlacementError,
+    TokenSubject,
+};
+use crate::domain::value_objects::{GridMapId, GridTokenId, PlayerCharacterId, SessionId};
+
+/// Error type for grid token operations
+#[derive(Debug, thiserror::Error)]
+pub enum TokenError {
+    #[error("Grid token not found: {0}")]
+    NotFound(GridTokenId),
+    #[error("{0} is not your character's token")]
+    NotYours(String),
+    #[error("Movement on this map is locked until your turn")]
+    NotYourTurn,
+    #[error(transparent)]
+    Placement(#[from] TokenPlacementError),
+}
+
+/// Who is moving a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenMover {
+    /// The DM, who may move any token at any time
+    Dm,
+    /// A player, who may only move their own character's token
+    Player(PlayerCharacterId),
+}
+
+/// A token to put on a grid map
+#[derive(Debug, Clone)]
+pub struct PlaceToken {
+    pub subject: TokenSubject,
+    pub name: String,
+    pub position: GridPosition,
+    pub size: u32,
+    pub facing: Facing,
+    pub hidden: bool,
+}
+
+/// Changes to a token; fields not given are left as they are
+#[derive(Debug, Clone, Default)]
+pub struct UpdateToken {
+    pub name: Option<String>,
+    pub size: Option<u32>,
+    pub facing: Option<Facing>,
+    pub hidden: Option<bool>,
+}
+
+/// Service for the tokens on grid maps
+#[derive(Clone)]
+pub struct GridTokenService {
+    token_repository: Arc<dyn GridTokenRepositoryPort>,
+    grid_map_service: Arc<dyn GridMapService>,
+    combat_service: CombatService,
+    vision_service: VisionService,
+    sessions: Arc<dyn AsyncSessionPort>,
+    /// Maps on which players may only move on their turn
+    locked: Arc<RwLock<HashSet<GridMapId>>>,
+}
+
+impl GridTokenService {
+    /// Create a new grid token service
+    pub fn new(
+        token_repository: Arc<dyn GridTokenRepositoryPort>,
+        grid_map_service: Arc<dyn GridMapService>,
+        combat_service: CombatService,
+        vision_service: VisionService,
+        sessions: Arc<dyn AsyncSessionPort>,
+    ) -> Self {
+        Self {
+            token_repository,
+            grid_map_service,
+            combat_service,
+            vision_service,
+            sessions,
+            locked: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Get a token by ID
+    pub async fn get(&self, token_id: GridTokenId) -> Result<GridToken> {
+        self.token_repository
+            .get(token_id)
+            .await
+            .context("Failed to load grid token")?
+            .ok_or_else(|| TokenError::NotFound(token_id).into())
+    }
+
+    /// The tokens on a grid map; hidden ones only if asked for
+    pub async fn list(&self, grid_map_id: GridMapId, include_hidden: bool) -> Result<Vec<GridToken>> {
+        let mut tokens = self
+            .token_repository
+            .list(grid_map_id)
+            .await
+            .context("Failed to list grid tokens")?;
+        if !include_hidden {
+            tokens.retain(|token| !token.hidden);
+        }
+        Ok(tokens)
+    }
+
+    /// A player character's token on a grid map, if it has one
+    pub async fn pc_token(&self, grid_map_id: GridMapId, pc_id: PlayerCharacterId) -> Result<Option<GridToken>> {
+        Ok(self
+            .list(grid_map_id, true)
+            .await?
+            .into_iter()
+            .find(|token| token.subject == TokenSubject::Pc(pc_id)))
+    }
+
+    /// Put a token on a grid map
+    pub async fn place(&self, grid_map_id: GridMapId, place: PlaceToken) -> Result<GridToken> {
+        let grid_map = self.load_grid_map(grid_map_id).await?;
+        let (x, y) = place.position;
+        let mut token = GridToken::new(grid_map_id, place.subject, place.name, x, y)
+            .with_size(place.size)
+            .with_facing(place.facing);
+        if place.hidden {
+            token = token.hidden();
+        }
+
+        let others = self.list(grid_map_id, true).await?;
+        grid_map
+            .check_token_placement(&token, token.position(), &others)
+            .map_err(TokenError::from)?;
+        self.token_repository
+            .save(&token)
+            .await
+            .context("Failed to save grid token")?;
+        tracing::info!("Placed {} at ({}, {}) on {}", token.name, x, y, grid_map.name);
+
+        if let Some(session_id) = self.session_for(&grid_map).await {
+            self.notify(session_id, token.hidden, TokenUpdatedNotification::new(&token))
+                .await;
+            self.look(session_id, &token).await;
+        }
+        Ok(token)
+    }
+
+    /// Change a token's name, size, facing or visibility
+    pub async fn update(&self, token_id: GridTokenId, update: UpdateToken) -> Result<GridToken> {
+        let mut token = self.get(token_id).await?;
+        let grid_map = self.load_grid_map(token.grid_map_id).await?;
+        let was_hidden = token.hidden;
+
+        if let Some(name) = update.name {
+            token.name = name;
+        }
+        if let Some(facing) = update.facing {
+            token.facing = facing;
+        }
+        if let Some(hidden) = update.hidden {
+            token.hidden = hidden;
+        }
+        if let Some(size) = update.size.filter(|&size| size != token.size) {
+            token.size = size;
+            let others = self.list(token.grid_map_id, true).await?;
+            grid_map
+                .check_token_placement(&token, token.position(), &others)
+                .map_err(TokenError::from)?;
+        }
+        self.token_repository
+            .save(&token)
+            .await
+            .context("Failed to save grid token")?;
+
+        if let Some(session_id) = self.session_for(&grid_map).await {
+            self.notify(session_id, token.hidden, TokenUpdatedNotification::new(&token))
+                .await;
+            if token.hidden && !was_hidden {
+                self.notify_players(session_id, TokenRemovedNotification::new(token.grid_map_id, token.id))
+                    .await;
+            }
+        }
+        Ok(token)
+    }
+
+    /// Move a token along the cheapest route to a tile
+    ///
+    /// Without a facing the token turns the way its last step went.
+    pub async fn move_token(
+        &self,
+        token_id: GridTokenId,
+        mover: TokenMover,
+        to: GridPosition,
+        facing: Option<Facing>,
+    ) -> Result<(GridToken, GridPath)> {
+        let mut token = self.get(token_id).await?;
+        let grid_map = self.load_grid_map(token.grid_map_id).await?;
+        let session_id = self.session_for(&grid_map).await;
+
+        if let TokenMover::Player(pc_id) = mover {
+            if token.subject != TokenSubject::Pc(pc_id) {
+                return Err(TokenError::NotYours(token.name).into());
+            }
+            if self.is_locked(grid_map.id).await && !self.is_turn_of(session_id, pc_id).await {
+                return Err(TokenError::NotYourTurn.into());
+            }
+        }
+
+        let others = self.list(token.grid_map_id, true).await?;
+        grid_map
+            .check_token_placement(&token, to, &others)
+            .map_err(TokenError::from)?;
+        let path = self
+            .grid_map_service
+            .find_path(
+                grid_map.id,
+                token.position(),
+                to,
+                None,
+                grid_map.blocked_anchors(&token, &others),
+                None,
+            )
+            .await?
+            .ok_or(TokenError::Placement(TokenPlacementError::Unreachable { x: to.0, y: to.1 }))?;
+
+        token.facing = facing
+            .or_else(|| {
+                path.steps
+                    .windows(2)
+                    .last()
+                    .and_then(|step| Facing::of_step(step[0], step[1]))
+            })
+            .unwrap_or(token.facing);
+        (token.x, token.y) = to;
+        self.token_repository
+            .save(&token)
+            .await
+            .context("Failed to save grid token")?;
+        tracing::debug!(
+            "Moved {} to ({}, {}) on {} for {}",
+            token.name,
+            to.0,
+            to.1,
+            grid_map.name,
+            path.cost
+        );
+
+        if let Some(session_id) = session_id {
+            let moved = TokenMovedDto::new(&token, path.clone());
+            self.notify(session_id, token.hidden, TokenMovedNotification::new(moved))
+                .await;
+            self.look(session_id, &token).await;
+        }
+        Ok((token, path))
+    }
+
+    /// Take a token off its map
+    pub async fn remove(&self, token_id: GridTokenId) -> Result<GridToken> {
+        let token = self.get(token_id).await?;
+        self.token_repository
+            .delete(token_id)
+            .await
+            .context("Failed to delete grid token")?;
+
+        let grid_map = self.load_grid_map(token.grid_map_id).await?;
+        if let Some(session_id) = self.session_for(&grid_map).await {
+            self.notify(session_id, token.hidden, TokenRemovedNotification::new(token.grid_map_id, token.id))
+                .await;
+        }
+        Ok(token)
+    }
+
+    /// Lock or unlock player movement on a grid map
+    pub async fn set_locked(&self, grid_map_id: GridMapId, locked: bool) -> Result<()> {
+        let grid_map = self.load_grid_map(grid_map_id).await?;
+        {
+            let mut maps = self.locked.write().await;
+            if locked {
+                maps.insert(grid_map_id);
+            } else {
+                maps.remove(&grid_map_id);
+            }
+        }
+        tracing::info!(
+            "Movement on {} {}",
+            grid_map.name,
+            if locked { "locked" } else { "unlocked" }
+        );
+
+        if let Some(session_id) = self.session_for(&grid_map).await {
+            self.notify(session_id, false, MovementLockedNotification::new(grid_map_id, locked))
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Whether players may only move on their turn on a grid map
+    pub async fn is_locked(&self, grid_map_id: GridMapId) -> bool {
+        self.locked.read().await.contains(&grid_map_id)
+    }
+
+    /// Whether it is a player character's turn in its session's fight
+    async fn is_turn_of(&self, session_id: Option<SessionId>, pc_id: PlayerCharacterId) -> bool {
+        let Some(session_id) = session_id else {
+            return false;
+        };
+        self.combat_service
+            .encounter(session_id)
+            .await
+            .is_some_and(|encounter| {
+                encounter
+                    .current()
+                    .is_some_and(|combatant| combatant.who == CombatantRef::Pc(pc_id))
+            })
+    }
+
+    async fn load_grid_map(&self, grid_map_id: GridMapId) -> Result<GridMap> {
+        self.grid_map_service
+            .get_grid_map(grid_map_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Grid map not found: {}", grid_map_id))
+    }
+
+    /// The session playing a grid map's world, if one is running
+    async fn session_for(&self, grid_map: &GridMap) -> Option<SessionId> {
+        self.sessions.find_session_for_world(grid_map.world_id).await
+    }
+
+    /// Show a player character's player what it sees from its token
+    async fn look(&self, session_id: SessionId, token: &GridToken) {
+        let TokenSubject::Pc(pc_id) = token.subject else {
+            return;
+        };
+        if let Err(e) = self
+            .vision_service
+            .look(session_id, token.grid_map_id, pc_id, token.position())
+            .await
+        {
+            tracing::warn!("Failed to update what {} sees: {}", token.name, e);
+        }
+    }
+
+    /// Tell the session of a change; only the DM hears of hidden tokens
+    async fn notify(&self, session_id: SessionId, hidden: bool, notification: impl serde::Serialize) {
+        let Some(message) = to_message(notification) else {
+            return;
+        };
+        let sent = if hidden {
+            self.sessions.send_to_dm(session_id, message).await
+        } else {
+            self.sessions.broadcast_to_session(session_id, message).await
+        };
+        if let Err(e) = sent {
+            tracing::warn!("Failed to send grid token notification: {}", e);
+        }
+    }
+
+    async fn notify_players(&self, session_id: SessionId, notification: impl serde::Serialize) {
+        let Some(message) = to_message(notification) else {
+            return;
+        };
+        if let Err(e) = self.sessions.broadcast_to_players(session_id, message).await {
+            tracing::warn!("Failed to send grid token notification: {}", e);
+        }
+    }
+}
+
+fn to_message(notification: impl serde::Serialize) -> Option<serde_json::Value> {
+    serde_json::to_value(&notification)
+        .map_err(|e| tracing::error!("Failed to serialize grid token notification: {}", e))
+        .ok()
+}
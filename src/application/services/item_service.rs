@@ -0,0 +1,714 @@
+//! Item Service - Application service for a world's item catalogue
+//!
+//! This service provides use case implementations for creating, listing,
+//! updating and deleting the items of a world, and for putting them in
+//! characters' inventories. Inventories only ever hold items from the
+//! catalogue of the character's own world, and a unique item can only be
+//! held one at a time.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{debug, info, instrument};
+
+use crate::application::ports::outbound::{
+    CharacterRepositoryPort, ItemRepositoryPort, WorldRepositoryPort,
+};
+use crate::domain::entities::{AcquisitionMethod, Item};
+use crate::domain::value_objects::{CharacterId, ItemId, WorldId};
+
+/// Error type for item and inventory operations
+#[derive(Debug, thiserror::Error)]
+pub enum ItemError {
+    #[error("Item not found: {0}")]
+    NotFound(ItemId),
+    #[error("Character not found: {0}")]
+    CharacterNotFound(CharacterId),
+    #[error("{0} is not in the item catalogue of the character's world")]
+    OtherWorld(String),
+    #[error("{0} is not in the character's inventory")]
+    NotHeld(String),
+    #[error("Quantity must be at least 1")]
+    NoQuantity,
+    #[error("{0} is unique; only one can be held")]
+    Unique(String),
+    #[error("{0} is held more than once, so it cannot be made unique")]
+    HeldMoreThanOnce(String),
+    #[error("Item properties must be JSON: {0}")]
+    Properties(String),
+}
+
+/// Request to create a new item
+#[derive(Debug, Clone)]
+pub struct CreateItemRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub item_type: Option<String>,
+    pub is_unique: bool,
+    /// Item-specific properties, as JSON
+    pub properties: Option<String>,
+}
+
+/// Request to update an existing item
+#[derive(Debug, Clone, Default)]
+pub struct UpdateItemRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub item_type: Option<String>,
+    pub is_unique: Option<bool>,
+    pub properties: Option<String>,
+}
+
+/// Request to put a catalogue item in a character's inventory
+#[derive(Debug, Clone)]
+pub struct AddInventoryItemRequest {
+    pub item_id: ItemId,
+    pub quantity: u32,
+    pub equipped: bool,
+    pub acquisition_method: Option<AcquisitionMethod>,
+}
+
+/// Item service trait defining the application use cases
+#[async_trait]
+pub trait ItemService: Send + Sync {
+    /// List the items in a world, only those of a type if one is given
+    async fn list_items(&self, world_id: WorldId, item_type: Option<&str>) -> Result<Vec<Item>>;
+
+    /// Get an item by ID
+    async fn get_item(&self, item_id: ItemId) -> Result<Option<Item>>;
+
+    /// Add an item to a world's catalogue
+    async fn create_item(&self, world_id: WorldId, request: CreateItemRequest) -> Result<Item>;
+
+    /// Update an item
+    async fn update_item(&self, item_id: ItemId, request: UpdateItemRequest) -> Result<Item>;
+
+    /// Delete an item, taking it out of every inventory
+    async fn delete_item(&self, item_id: ItemId) -> Result<()>;
+
+    /// Put a catalogue item in a character's inventory, adding to the
+    /// quantity if they already hold some
+    async fn add_inventory_item(
+        &self,
+        character_id: CharacterId,
+        request: AddInventoryItemRequest,
+    ) -> Result<()>;
+
+    /// Change how many of an item a character holds, or whether it is
+    /// equipped
+    async fn update_inventory_item(
+        &self,
+        character_id: CharacterId,
+        item_id: ItemId,
+        quantity: Option<u32>,
+        equipped: Option<bool>,
+    ) -> Result<()>;
+
+    /// Take an item out of a character's inventory
+    async fn remove_inventory_item(&self, character_id: CharacterId, item_id: ItemId) -> Result<()>;
+}
+
+/// Default implementation of ItemService using port abstractions
+#[derive(Clone)]
+pub struct ItemServiceImpl {
+    item_repository: Arc<dyn ItemRepositoryPort>,
+    world_repository: Arc<dyn WorldRepositoryPort>,
+    character_repository: Arc<dyn CharacterRepositoryPort>,
+}
+
+impl ItemServiceImpl {
+    /// Create a new ItemServiceImpl with the given repositories
+    pub fn new(
+        item_repository: Arc<dyn ItemRepositoryPort>,
+        world_repository: Arc<dyn WorldRepositoryPort>,
+        character_repository: Arc<dyn CharacterRepositoryPort>,
+    ) -> Self {
+        Self {
+            item_repository,
+            world_repository,
+            character_repository,
+        }
+    }
+
+    fn validate_name(name: &str) -> Result<()> {
+        if name.trim().is_empty() {
+            anyhow::bail!("Item name cannot be empty");
+        }
+        if name.len() > 255 {
+            anyhow::bail!("Item name cannot exceed 255 characters");
+        }
+        Ok(())
+    }
+
+    fn validate_description(description: &str) -> Result<()> {
+        if description.len() > 10000 {
+            anyhow::bail!("Item description cannot exceed 10000 characters");
+        }
+        Ok(())
+    }
+
+    fn validate_properties(properties: &str) -> Result<(), ItemError> {
+        serde_json::from_str::<serde_json::Value>(properties)
+            .map(|_| ())
+            .map_err(|e| ItemError::Properties(e.to_string()))
+    }
+
+    async fn load(&self, item_id: ItemId) -> Result<Item> {
+        Ok(self
+            .item_repository
+            .get(item_id)
+            .await?
+            .ok_or(ItemError::NotFound(item_id))?)
+    }
+
+    /// How many of an item a character holds and whether it is equipped
+    async fn held(&self, character_id: CharacterId, item_id: ItemId) -> Result<Option<(u32, bool)>> {
+        Ok(self
+            .character_repository
+            .get_inventory(character_id)
+            .await?
+            .into_iter()
+            .find(|held| held.item.id == item_id)
+            .map(|held| (held.quantity, held.equipped)))
+    }
+}
+
+#[async_trait]
+impl ItemService for ItemServiceImpl {
+    #[instrument(skip(self))]
+    async fn list_items(&self, world_id: WorldId, item_type: Option<&str>) -> Result<Vec<Item>> {
+        debug!(world_id = %world_id, "Listing items for world");
+        match item_type {
+            Some(item_type) => self.item_repository.get_by_type(world_id, item_type).await,
+            None => self.item_repository.list(world_id).await,
+        }
+        .context("Failed to list items from repository")
+    }
+
+    #[instrument(skip(self))]
+    async fn get_item(&self, item_id: ItemId) -> Result<Option<Item>> {
+        debug!(item_id = %item_id, "Fetching item");
+        self.item_repository
+            .get(item_id)
+            .await
+            .context("Failed to get item from repository")
+    }
+
+    #[instrument(skip(self, request), fields(world_id = %world_id, name = %request.name))]
+    async fn create_item(&self, world_id: WorldId, request: CreateItemRequest) -> Result<Item> {
+        Self::validate_name(&request.name)?;
+
+        let _ = self
+            .world_repository
+            .get(world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
+
+        let mut item = Item::new(world_id, request.name);
+        if let Some(description) = request.description {
+            Self::validate_description(&description)?;
+            item = item.with_description(description);
+        }
+        if let Some(item_type) = request.item_type.filter(|t| !t.trim().is_empty()) {
+            item = item.with_type(item_type);
+        }
+        if request.is_unique {
+            item = item.unique();
+        }
+        if let Some(properties) = request.properties {
+            Self::validate_properties(&properties)?;
+            item = item.with_properties(properties);
+        }
+
+        self.item_repository
+            .create(&item)
+            .await
+            .context("Failed to create item in repository")?;
+
+        info!(item_id = %item.id, "Created item: {}", item.name);
+        Ok(item)
+    }
+
+    #[instrument(skip(self, request), fields(item_id = %item_id))]
+    async fn update_item(&self, item_id: ItemId, request: UpdateItemRequest) -> Result<Item> {
+        let mut item = self.load(item_id).await?;
+
+        if let Some(name) = request.name {
+            Self::validate_name(&name)?;
+            item.name = name;
+        }
+        if let Some(description) = request.description {
+            Self::validate_description(&description)?;
+            item.description = Some(description).filter(|d| !d.is_empty());
+        }
+        if let Some(item_type) = request.item_type {
+            item.item_type = Some(item_type).filter(|t| !t.trim().is_empty());
+        }
+        if let Some(is_unique) = request.is_unique {
+            if is_unique && !item.is_unique && self.item_repository.held_count(item_id).await? > 1 {
+                return Err(ItemError::HeldMoreThanOnce(item.name).into());
+            }
+            item.is_unique = is_unique;
+        }
+        if let Some(properties) = request.properties {
+            if !properties.is_empty() {
+                Self::validate_properties(&properties)?;
+            }
+            item.properties = Some(properties).filter(|p| !p.is_empty());
+        }
+
+        self.item_repository
+            .update(&item)
+            .await
+            .context("Failed to update item in repository")?;
+
+        info!(item_id = %item_id, "Updated item: {}", item.name);
+        Ok(item)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_item(&self, item_id: ItemId) -> Result<()> {
+        let item = self.load(item_id).await?;
+
+        self.item_repository
+            .delete(item_id)
+            .await
+            .context("Failed to delete item from repository")?;
+
+        info!(item_id = %item_id, "Deleted item: {}", item.name);
+        Ok(())
+    }
+
+    #[instrument(skip(self, request), fields(character_id = %character_id, item_id = %request.item_id))]
+    async fn add_inventory_item(
+        &self,
+        character_id: CharacterId,
+        request: AddInventoryItemRequest,
+    ) -> Result<()> {
+        let character = self
+            .character_repository
+            .get(character_id)
+            .await?
+            .ok_or(ItemError::CharacterNotFound(character_id))?;
+        let item = self.load(request.item_id).await?;
+        if item.world_id != character.world_id {
+            return Err(ItemError::OtherWorld(item.name).into());
+        }
+        if request.quantity == 0 {
+            return Err(ItemError::NoQuantity.into());
+        }
+        if item.is_unique {
+            let held = self.item_repository.held_count(item.id).await?;
+            if held.saturating_add(request.quantity) > 1 {
+                return Err(ItemError::Unique(item.name).into());
+            }
+        }
+
+        self.character_repository
+            .add_inventory_item(
+                character_id,
+                item.id,
+                request.quantity,
+                request.equipped,
+                request.acquisition_method,
+            )
+            .await
+            .context("Failed to add inventory item in repository")?;
+
+        info!(quantity = request.quantity, "Gave {} to {}", item.name, character.name);
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(character_id = %character_id, item_id = %item_id))]
+    async fn update_inventory_item(
+        &self,
+        character_id: CharacterId,
+        item_id: ItemId,
+        quantity: Option<u32>,
+        equipped: Option<bool>,
+    ) -> Result<()> {
+        let item = self.load(item_id).await?;
+        let (held_quantity, held_equipped) = self
+            .held(character_id, item_id)
+            .await?
+            .ok_or_else(|| ItemError::NotHeld(item.name.clone()))?;
+        let quantity = quantity.unwrap_or(held_quantity);
+        if quantity == 0 {
+            return Err(ItemError::NoQuantity.into());
+        }
+        if item.is_unique {
+            let held_elsewhere = self.item_repository.held_count(item_id).await?.saturating_sub(held_quantity);
+            if held_elsewhere.saturating_add(quantity) > 1 {
+                return Err(ItemError::Unique(item.name).into());
+            }
+        }
+
+        self.character_repository
+            .update_inventory_item(character_id, item_id, quantity, equipped.unwrap_or(held_equipped))
+            .await
+            .context("Failed to update inventory item in repository")
+    }
+
+    #[instrument(skip(self), fields(character_id = %character_id, item_id = %item_id))]
+    async fn remove_inventory_item(&self, character_id: CharacterId, item_id: ItemId) -> Result<()> {
+        self.character_repository
+            .remove_inventory_item(character_id, item_id)
+            .await
+            .context("Failed to remove inventory item in repository")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{
+        Act, ActantialRole, ActantialView, Character, CharacterWant, FrequencyLevel, InventoryItem, Want,
+        World,
+    };
+    use crate::domain::value_objects::{CampbellArchetype, LocationId, SceneId, WantId};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Fake item repository for testing; `held` stands in for every
+    /// inventory's count of each item
+    struct FakeItemRepository {
+        items: Mutex<HashMap<ItemId, Item>>,
+        held: HashMap<ItemId, u32>,
+    }
+
+    impl FakeItemRepository {
+        fn new(items: Vec<Item>, held: HashMap<ItemId, u32>) -> Self {
+            Self {
+                items: Mutex::new(items.into_iter().map(|item| (item.id, item)).collect()),
+                held,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ItemRepositoryPort for FakeItemRepository {
+        async fn create(&self, item: &Item) -> Result<()> {
+            self.items.lock().unwrap().insert(item.id, item.clone());
+            Ok(())
+        }
+
+        async fn get(&self, id: ItemId) -> Result<Option<Item>> {
+            Ok(self.items.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn list(&self, _world_id: WorldId) -> Result<Vec<Item>> {
+            Ok(vec![])
+        }
+
+        async fn update(&self, item: &Item) -> Result<()> {
+            self.items.lock().unwrap().insert(item.id, item.clone());
+            Ok(())
+        }
+
+        async fn delete(&self, _id: ItemId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_by_type(&self, _world_id: WorldId, _item_type: &str) -> Result<Vec<Item>> {
+            Ok(vec![])
+        }
+
+        async fn held_count(&self, id: ItemId) -> Result<u32> {
+            Ok(self.held.get(&id).copied().unwrap_or(0))
+        }
+    }
+
+    /// Fake world repository for testing
+    struct FakeWorldRepository;
+
+    #[async_trait]
+    impl WorldRepositoryPort for FakeWorldRepository {
+        async fn create(&self, _world: &World) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get(&self, _id: WorldId) -> Result<Option<World>> {
+            Ok(None)
+        }
+
+        async fn list(&self) -> Result<Vec<World>> {
+            Ok(vec![])
+        }
+
+        async fn update(&self, _world: &World) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: WorldId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn create_act(&self, _act: &Act) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_acts(&self, _world_id: WorldId) -> Result<Vec<Act>> {
+            Ok(vec![])
+        }
+    }
+
+    /// Fake character repository for testing; records the items given out
+    struct FakeCharacterRepository {
+        characters: HashMap<CharacterId, Character>,
+        given: Mutex<Vec<(CharacterId, ItemId, u32)>>,
+    }
+
+    impl FakeCharacterRepository {
+        fn new(characters: Vec<Character>) -> Self {
+            Self {
+                characters: characters.into_iter().map(|character| (character.id, character)).collect(),
+                given: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CharacterRepositoryPort for FakeCharacterRepository {
+        async fn create(&self, _character: &Character) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get(&self, id: CharacterId) -> Result<Option<Character>> {
+            Ok(self.characters.get(&id).cloned())
+        }
+
+        async fn list(&self, _world_id: WorldId) -> Result<Vec<Character>> {
+            Ok(vec![])
+        }
+
+        async fn update(&self, _character: &Character) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: CharacterId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_by_scene(&self, _scene_id: SceneId) -> Result<Vec<Character>> {
+            Ok(vec![])
+        }
+
+        async fn create_want(&self, _character_id: CharacterId, _want: &Want, _priority: u32) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_wants(&self, _character_id: CharacterId) -> Result<Vec<CharacterWant>> {
+            Ok(vec![])
+        }
+
+        async fn update_want(&self, _want: &Want) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete_want(&self, _want_id: WantId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn set_want_target(&self, _want_id: WantId, _target_id: &str, _target_type: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_want_target(&self, _want_id: WantId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn add_actantial_view(
+            &self,
+            _subject_id: CharacterId,
+            _role: ActantialRole,
+            _target_id: CharacterId,
+            _view: &ActantialView,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_actantial_views(
+            &self,
+            _character_id: CharacterId,
+        ) -> Result<Vec<(ActantialRole, CharacterId, ActantialView)>> {
+            Ok(vec![])
+        }
+
+        async fn remove_actantial_view(
+            &self,
+            _subject_id: CharacterId,
+            _role: ActantialRole,
+            _target_id: CharacterId,
+            _want_id: WantId,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn add_inventory_item(
+            &self,
+            character_id: CharacterId,
+            item_id: ItemId,
+            quantity: u32,
+            _equipped: bool,
+            _acquisition_method: Option<AcquisitionMethod>,
+        ) -> Result<()> {
+            self.given.lock().unwrap().push((character_id, item_id, quantity));
+            Ok(())
+        }
+
+        async fn get_inventory(&self, _character_id: CharacterId) -> Result<Vec<InventoryItem>> {
+            Ok(vec![])
+        }
+
+        async fn update_inventory_item(
+            &self,
+            _character_id: CharacterId,
+            _item_id: ItemId,
+            _quantity: u32,
+            _equipped: bool,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_inventory_item(&self, _character_id: CharacterId, _item_id: ItemId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn set_home_location(
+            &self,
+            _character_id: CharacterId,
+            _location_id: LocationId,
+            _description: Option<String>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_home_location(&self, _character_id: CharacterId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn set_work_location(
+            &self,
+            _character_id: CharacterId,
+            _location_id: LocationId,
+            _role: String,
+            _schedule: Option<String>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_work_location(&self, _character_id: CharacterId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn add_frequented_location(
+            &self,
+            _character_id: CharacterId,
+            _location_id: LocationId,
+            _frequency: FrequencyLevel,
+            _time_of_day: String,
+            _day_of_week: Option<String>,
+            _reason: Option<String>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_frequented_location(
+            &self,
+            _character_id: CharacterId,
+            _location_id: LocationId,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn add_avoided_location(
+            &self,
+            _character_id: CharacterId,
+            _location_id: LocationId,
+            _reason: String,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_avoided_location(
+            &self,
+            _character_id: CharacterId,
+            _location_id: LocationId,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_npcs_at_location(
+            &self,
+            _location_id: LocationId,
+            _time_of_day: Option<&str>,
+        ) -> Result<Vec<Character>> {
+            Ok(vec![])
+        }
+    }
+
+    fn service(
+        items: Vec<Item>,
+        held: HashMap<ItemId, u32>,
+        characters: Vec<Character>,
+    ) -> (ItemServiceImpl, Arc<FakeItemRepository>, Arc<FakeCharacterRepository>) {
+        let item_repository = Arc::new(FakeItemRepository::new(items, held));
+        let character_repository = Arc::new(FakeCharacterRepository::new(characters));
+        let service = ItemServiceImpl::new(
+            item_repository.clone(),
+            Arc::new(FakeWorldRepository),
+            character_repository.clone(),
+        );
+        (service, item_repository, character_repository)
+    }
+
+    #[tokio::test]
+    async fn test_items_held_more_than_once_cannot_be_made_unique() {
+        let world_id = WorldId::new();
+        let rope = Item::new(world_id, "Rope");
+        let lantern = Item::new(world_id, "Lantern");
+        let (service, items, _) = service(
+            vec![rope.clone(), lantern.clone()],
+            HashMap::from([(rope.id, 2), (lantern.id, 1)]),
+            vec![],
+        );
+        let make_unique = UpdateItemRequest {
+            is_unique: Some(true),
+            ..UpdateItemRequest::default()
+        };
+
+        let err = service.update_item(rope.id, make_unique.clone()).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ItemError>(), Some(ItemError::HeldMoreThanOnce(_))));
+        assert!(!items.get(rope.id).await.unwrap().unwrap().is_unique);
+
+        assert!(service.update_item(lantern.id, make_unique).await.unwrap().is_unique);
+    }
+
+    #[tokio::test]
+    async fn test_unique_items_held_elsewhere_cannot_be_given() {
+        let world_id = WorldId::new();
+        let crown = Item::new(world_id, "Crown").unique();
+        let sceptre = Item::new(world_id, "Sceptre").unique();
+        let queen = Character::new(world_id, "Queen", CampbellArchetype::Mentor);
+        let (service, _, characters) = service(
+            vec![crown.clone(), sceptre.clone()],
+            HashMap::from([(crown.id, 1)]),
+            vec![queen.clone()],
+        );
+        let give = |item: &Item, quantity| AddInventoryItemRequest {
+            item_id: item.id,
+            quantity,
+            equipped: false,
+            acquisition_method: None,
+        };
+
+        let err = service.add_inventory_item(queen.id, give(&crown, 1)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ItemError>(), Some(ItemError::Unique(_))));
+        let err = service.add_inventory_item(queen.id, give(&sceptre, 2)).await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<ItemError>(), Some(ItemError::Unique(_))));
+        assert!(characters.given.lock().unwrap().is_empty());
+
+        service.add_inventory_item(queen.id, give(&sceptre, 1)).await.unwrap();
+        assert_eq!(*characters.given.lock().unwrap(), vec![(queen.id, sceptre.id, 1)]);
+    }
+}
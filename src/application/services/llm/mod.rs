@@ -54,6 +54,7 @@ use tool_parser::parse_tool_calls_from_response;
 ///         current_mood: Some("Cautious".to_string()),
 ///         wants: vec!["Protect his establishment".to_string()],
 ///         relationship_to_player: Some("Acquaintance".to_string()),
+///         conditions: vec![],
 ///     },
 /// };
 ///
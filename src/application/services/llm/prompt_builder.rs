@@ -98,6 +98,13 @@ pub fn build_system_prompt_with_notes(
         ));
     }
 
+    if !character.conditions.is_empty() {
+        prompt.push_str("\nCONDITIONS AFFECTING YOU (play these out):\n");
+        for condition in &character.conditions {
+            prompt.push_str(&format!("- {}\n", condition));
+        }
+    }
+
     // Active challenges - potential things that might be triggered
     if !active_challenges.is_empty() {
         prompt.push_str("## Active Challenges\n");
@@ -240,6 +247,12 @@ pub fn build_user_message(request: &GamePromptRequest) -> String {
                 message.push_str(&format!("The player uses an item on {}.\n", target));
             }
         }
+        "combat_turn" => {
+            // The engine asks for an NPC's action when its turn comes up
+            if let Some(dialogue) = &request.player_action.dialogue {
+                message.push_str(&format!("[COMBAT] {}\n", dialogue));
+            }
+        }
         other => {
             message.push_str(&format!("The player performs action: {}\n", other));
             if let Some(target) = &request.player_action.target {
@@ -352,6 +365,13 @@ pub fn build_system_prompt_from_assembled(
         ));
     }
 
+    if !character.conditions.is_empty() {
+        prompt.push_str("\nCONDITIONS AFFECTING YOU (play these out):\n");
+        for condition in &character.conditions {
+            prompt.push_str(&format!("- {}\n", condition));
+        }
+    }
+
     // If challenges context was included, add suggestion format
     if assembled.get(ContextCategory::Challenges).is_some() {
         prompt.push_str("\nIf a player's action matches a challenge trigger, include:\n");
@@ -488,6 +508,7 @@ mod tests {
             current_mood: Some("Suspicious".to_string()),
             wants: vec!["Protect his tavern".to_string()],
             relationship_to_player: Some("Acquaintance".to_string()),
+            conditions: vec!["Poisoned: queasy and pale".to_string()],
         };
 
         let prompt = build_system_prompt(&context, &character);
@@ -497,6 +518,7 @@ mod tests {
         assert!(prompt.contains("The Rusty Anchor"));
         assert!(prompt.contains("Suspicious"));
         assert!(prompt.contains("Protect his tavern"));
+        assert!(prompt.contains("- Poisoned: queasy and pale"));
         assert!(prompt.contains("<reasoning>"));
         assert!(prompt.contains("<dialogue>"));
     }
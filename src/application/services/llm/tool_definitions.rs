@@ -204,7 +204,7 @@ pub fn get_game_tool_definitions() -> Vec<ToolDefinition> {
                     },
                     "duration": {
                         "type": "string",
-                        "description": "Duration of the condition (e.g., '1 hour', 'until rest', 'permanent')"
+                        "description": "Duration of the condition (e.g., '3 rounds', '1 hour', 'until rest', 'permanent')"
                     }
                 },
                 "required": ["character_id", "condition_name", "description"]
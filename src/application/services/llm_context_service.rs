@@ -213,6 +213,13 @@ impl LLMContextService {
             }
         }
 
+        if !character.conditions.is_empty() {
+            content.push_str("CONDITIONS:\n");
+            for condition in character.conditions.iter() {
+                content.push_str(&format!("- {}\n", condition.summary()));
+            }
+        }
+
         let token_count = self.token_counter.count(&content);
         
         let (final_content, was_truncated) = if token_count > budget {
@@ -471,6 +478,7 @@ impl LLMContextService {
             current_mood: None, // Character entity doesn't have mood - would need session state
             wants: want_descriptions,
             relationship_to_player: None, // Would need player context to determine
+            conditions: character.conditions.iter().map(|c| c.summary()).collect(),
         })
     }
 
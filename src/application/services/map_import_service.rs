@@ -0,0 +1,198 @@
+//! Map Import Service
+//!
+//! Turns maps drawn in other tools into grid maps. The map's tiles become a
+//! new grid map; given a location, the grid map becomes its tactical map,
+//! the rendered image is kept as a backdrop in the location's gallery, and
+//! each area the map's doors close off becomes a region, joined to its
+//! neighbours by a connection through the door that locks as the door does.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{debug, info, instrument};
+
+use crate::application::ports::outbound::{LocationRepositoryPort, RegionRepositoryPort};
+use crate::application::services::{AssetService, CreateAssetRequest, CreateGridMapRequest, GridMapService};
+use crate::domain::entities::{
+    AssetType, EntityType, GalleryAsset, GridMap, ImportedMap, MapDoor, MapLight, Region, RegionConnection,
+};
+use crate::domain::value_objects::{LocationId, WorldId};
+
+/// Request to import a map drawn in another tool
+#[derive(Debug, Clone)]
+pub struct ImportGridMapRequest {
+    pub name: String,
+    pub map: ImportedMap,
+    pub tilesheet_asset: Option<String>,
+    /// Location the map becomes the tactical map of
+    pub location_id: Option<LocationId>,
+}
+
+/// An imported grid map and what was made from it
+#[derive(Debug, Clone)]
+pub struct ImportedGridMap {
+    pub grid_map: GridMap,
+    /// Regions made from the map's areas
+    pub regions: Vec<Region>,
+    pub doors: Vec<ImportedDoor>,
+    pub lights: Vec<MapLight>,
+    /// The map's image, kept in the location's gallery
+    pub backdrop: Option<GalleryAsset>,
+}
+
+/// A door of an imported map
+#[derive(Debug, Clone)]
+pub struct ImportedDoor {
+    pub door: MapDoor,
+    /// The connection made from it, if it joins two regions
+    pub connection: Option<RegionConnection>,
+}
+
+/// Service for importing maps from other tools
+#[derive(Clone)]
+pub struct MapImportService {
+    grid_map_service: Arc<dyn GridMapService>,
+    location_repository: Arc<dyn LocationRepositoryPort>,
+    region_repository: Arc<dyn RegionRepositoryPort>,
+    asset_service: Arc<dyn AssetService>,
+    output_dir: PathBuf,
+}
+
+impl MapImportService {
+    /// Create a new map import service, saving map images under `output_dir`
+    pub fn new(
+        grid_map_service: Arc<dyn GridMapService>,
+        location_repository: Arc<dyn LocationRepositoryPort>,
+        region_repository: Arc<dyn RegionRepositoryPort>,
+        asset_service: Arc<dyn AssetService>,
+        output_dir: PathBuf,
+    ) -> Self {
+        Self {
+            grid_map_service,
+            location_repository,
+            region_repository,
+            asset_service,
+            output_dir,
+        }
+    }
+
+    /// Import a map into a world
+    #[instrument(skip(self, request), fields(world_id = %world_id, name = %request.name))]
+    pub async fn import(&self, world_id: WorldId, request: ImportGridMapRequest) -> Result<ImportedGridMap> {
+        if let Some(location_id) = request.location_id {
+            let location = self
+                .location_repository
+                .get(location_id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Location not found: {}", location_id))?;
+            if location.world_id != world_id {
+                anyhow::bail!("Location {} is not in world {}", location_id, world_id);
+            }
+        }
+
+        let map = request.map;
+        let grid_map = self
+            .grid_map_service
+            .create_grid_map(
+                world_id,
+                CreateGridMapRequest {
+                    name: request.name,
+                    width: map.width,
+                    height: map.height,
+                    tilesheet_asset: request.tilesheet_asset.unwrap_or_default(),
+                    tile_size: Some(map.tile_size),
+                    tiles: Some(map.tiles.clone()),
+                },
+            )
+            .await?;
+
+        let mut imported = ImportedGridMap {
+            grid_map,
+            regions: Vec::new(),
+            doors: map
+                .doors
+                .iter()
+                .map(|&door| ImportedDoor { door, connection: None })
+                .collect(),
+            lights: map.lights.clone(),
+            backdrop: None,
+        };
+        let Some(location_id) = request.location_id else {
+            if map.image.is_some() {
+                debug!("Map image left out, as the map is not imported into a location");
+            }
+            info!(grid_map_id = %imported.grid_map.id, "Imported grid map: {}", imported.grid_map.name);
+            return Ok(imported);
+        };
+
+        self.location_repository
+            .set_grid_map(location_id, imported.grid_map.id)
+            .await
+            .context("Failed to set location grid map in repository")?;
+
+        if let Some(image) = &map.image {
+            let dir = self
+                .output_dir
+                .join(EntityType::Location.as_str())
+                .join(location_id.to_string())
+                .join(AssetType::Backdrop.as_str());
+            tokio::fs::create_dir_all(&dir).await?;
+            let path = dir.join(format!("{}.{}", imported.grid_map.id, image.extension));
+            tokio::fs::write(&path, &image.data).await?;
+
+            let asset = self
+                .asset_service
+                .create_asset(CreateAssetRequest {
+                    entity_type: EntityType::Location,
+                    entity_id: location_id.to_string(),
+                    asset_type: AssetType::Backdrop,
+                    file_path: path.to_string_lossy().into_owned(),
+                    label: Some(imported.grid_map.name.clone()),
+                })
+                .await?;
+            imported.backdrop = Some(asset);
+        }
+
+        let (areas, doorways) = map.divide();
+        let tile_size = imported.grid_map.tile_size;
+        for (order, area) in areas.iter().enumerate() {
+            let region = Region::new(location_id, format!("{} - Area {}", imported.grid_map.name, order + 1))
+                .with_description(format!("An area of {} tiles", area.tiles.len()))
+                .with_map_bounds(area.bounds().map_bounds(tile_size))
+                .with_order(order as u32);
+            self.location_repository
+                .create_region(location_id, &region)
+                .await
+                .context("Failed to save imported region in repository")?;
+            imported.regions.push(region);
+        }
+        for doorway in doorways {
+            let (from, to) = doorway.between;
+            let mut connection = RegionConnection::new(imported.regions[from].id, imported.regions[to].id)
+                .with_description("A door");
+            if doorway.door.locked {
+                connection = connection.locked("The door is locked");
+            }
+            self.region_repository
+                .create_connection(&connection)
+                .await
+                .context("Failed to save door connection in repository")?;
+            if let Some(imported_door) = imported
+                .doors
+                .iter_mut()
+                .find(|imported_door| imported_door.door == doorway.door && imported_door.connection.is_none())
+            {
+                imported_door.connection = Some(connection);
+            }
+        }
+
+        info!(
+            grid_map_id = %imported.grid_map.id,
+            regions = imported.regions.len(),
+            doors = imported.doors.len(),
+            "Imported grid map: {}",
+            imported.grid_map.name
+        );
+        Ok(imported)
+    }
+}
@@ -4,6 +4,7 @@
 //! for the WrldBldr Engine. Each service follows hexagonal architecture principles,
 //! accepting repository dependencies and returning domain entities or DTOs.
 
+pub mod advancement_service;
 pub mod asset_generation_queue_service;
 pub mod asset_service;
 pub mod challenge_outcome_approval_service;
@@ -11,17 +12,24 @@ pub mod challenge_resolution_service;
 pub mod session_join_service;
 pub mod challenge_service;
 pub mod dm_approval_queue_service;
+pub mod dice_roll_service;
 pub mod character_service;
+pub mod combat_service;
+pub mod condition_service;
 pub mod dm_action_queue_service;
 pub mod event_chain_service;
 pub mod generation_event_publisher;
 pub mod generation_service;
 pub mod generation_queue_projection_service;
 pub mod interaction_service;
+pub mod item_service;
 pub mod llm_queue_service;
 pub mod llm;
 pub mod llm_context_service;
+pub mod grid_map_service;
+pub mod grid_token_service;
 pub mod location_service;
+pub mod map_import_service;
 
 // Re-export LLM service types for backward compatibility
 pub mod narrative_event_service;
@@ -34,12 +42,15 @@ pub mod relationship_service;
 pub mod scene_resolution_service;
 pub mod scene_service;
 pub mod settings_service;
+pub mod sheet_resource_service;
 pub mod sheet_template_service;
 pub mod skill_service;
 pub mod story_event_service;
 pub mod suggestion_service;
 pub mod tool_execution_service;
 pub mod trigger_evaluation_service;
+pub mod vision_service;
+pub mod area_effect_service;
 pub mod event_effect_executor;
 pub mod presence_service;
 pub mod workflow_config_service;
@@ -50,7 +61,7 @@ pub mod world_service;
 
 // Re-export world service types (used in HTTP routes and websocket)
 pub use world_service::{
-    CreateActRequest, CreateWorldRequest, UpdateWorldRequest, WorldService, WorldServiceImpl,
+    CreateActRequest, CreateWorldRequest, UpdateWorldRequest, WorldError, WorldService, WorldServiceImpl,
 };
 
 // Re-export session join service types
@@ -78,6 +89,19 @@ pub use player_character_service::{
     UpdatePlayerCharacterRequest,
 };
 
+// Re-export grid map service types
+pub use grid_map_service::{
+    CreateGridMapRequest, GenerateGridMapRequest, GeneratedGridMap, GridMapService,
+    GridMapServiceImpl, UpdateGridMapRequest,
+};
+pub use map_import_service::{ImportGridMapRequest, ImportedDoor, ImportedGridMap, MapImportService};
+
+// Re-export item service types
+pub use item_service::{
+    AddInventoryItemRequest, CreateItemRequest, ItemError, ItemService, ItemServiceImpl,
+    UpdateItemRequest,
+};
+
 // Re-export location service types
 pub use location_service::{
     CreateConnectionRequest, CreateLocationRequest, LocationService, LocationServiceImpl,
@@ -121,7 +145,19 @@ pub use relationship_service::{RelationshipService, RelationshipServiceImpl};
 pub use asset_service::{AssetService, AssetServiceImpl, CreateAssetRequest};
 
 // Re-export sheet template service types
-pub use sheet_template_service::SheetTemplateService;
+pub use sheet_template_service::{SheetMigrationError, SheetTemplateService};
+
+// Re-export condition service types (used in HTTP routes and websocket)
+pub use condition_service::{ConditionService, EndedCondition};
+
+// Re-export sheet resource service types (used in HTTP routes and websocket)
+pub use sheet_resource_service::{RestOutcome, SheetResourceService};
+pub use advancement_service::{AdvancementError, AdvancementService, LevelUpOutcome};
+pub use combat_service::{CombatError, CombatNpc, CombatService, CombatUpdate, StartCombat};
+pub use vision_service::{VisionError, VisionService};
+pub use grid_token_service::{GridTokenService, PlaceToken, TokenError, TokenMover, UpdateToken};
+pub use area_effect_service::{AreaEffectService, AreaOfEffect};
+pub use challenge_resolution_service::{SavingThrowError, SavingThrowTrigger};
 
 // Re-export settings service types
 pub use settings_service::SettingsService;
@@ -149,6 +185,9 @@ pub use generation_queue_projection_service::{
 // Re-export outcome trigger service
 pub use outcome_trigger_service::OutcomeTriggerService;
 
+// Re-export dice roll service
+pub use dice_roll_service::{DiceRollService, GrantRollError};
+
 // Re-export challenge outcome approval service (P3.3)
 pub use challenge_outcome_approval_service::{
     ChallengeOutcomeApprovalService, ChallengeOutcomeError,
@@ -375,7 +375,7 @@ mod tests {
 
     #[async_trait::async_trait]
     impl LlmPort for MockLlm {
-        type Error = String;
+        type Error = std::io::Error;
 
         async fn generate(
             &self,
@@ -11,8 +11,9 @@ use crate::application::ports::outbound::{
     AsyncSessionPort, ChallengeRepositoryPort,
 };
 use crate::application::services::tool_execution_service::StateChange;
+use crate::application::services::AdvancementService;
 use crate::domain::entities::OutcomeTrigger;
-use crate::domain::value_objects::SessionId;
+use crate::domain::value_objects::{AdvancementAward, PlayerCharacterId, SessionId};
 
 /// Result of executing outcome triggers
 #[derive(Debug, Clone)]
@@ -28,6 +29,8 @@ pub struct TriggerExecutionResult {
 /// Service for executing outcome triggers
 pub struct OutcomeTriggerService {
     challenge_repository: Arc<dyn ChallengeRepositoryPort>,
+    /// Advancement service for experience and milestone rewards
+    advancement_service: Option<AdvancementService>,
 }
 
 impl OutcomeTriggerService {
@@ -35,9 +38,16 @@ impl OutcomeTriggerService {
     pub fn new(challenge_repository: Arc<dyn ChallengeRepositoryPort>) -> Self {
         Self {
             challenge_repository,
+            advancement_service: None,
         }
     }
 
+    /// Set the advancement service, so rewards grant experience and milestones
+    pub fn with_advancement_service(mut self, advancement_service: AdvancementService) -> Self {
+        self.advancement_service = Some(advancement_service);
+        self
+    }
+
     /// Execute a list of outcome triggers
     ///
     /// This method processes each trigger and generates appropriate state changes.
@@ -45,19 +55,21 @@ impl OutcomeTriggerService {
     ///
     /// It uses the async session port to record conversation history and any
     /// session-scoped side effects, preserving the application/infra boundary.
+    /// `character_id` is the player character who rolled, which rewards go to.
     #[instrument(skip(self, session_port))]
     pub async fn execute_triggers(
         &self,
         triggers: &[OutcomeTrigger],
         session_port: &dyn AsyncSessionPort,
         session_id: SessionId,
+        character_id: &str,
     ) -> TriggerExecutionResult {
         let mut state_changes = Vec::new();
         let mut warnings = Vec::new();
 
         for trigger in triggers {
             match self
-                .execute_single_trigger(trigger, session_port, session_id)
+                .execute_single_trigger(trigger, session_port, session_id, character_id)
                 .await
             {
                 Ok(changes) => state_changes.extend(changes),
@@ -87,6 +99,7 @@ impl OutcomeTriggerService {
         trigger: &OutcomeTrigger,
         session_port: &dyn AsyncSessionPort,
         session_id: SessionId,
+        character_id: &str,
     ) -> Result<Vec<StateChange>, String> {
         match trigger {
             OutcomeTrigger::RevealInformation { info, persist } => {
@@ -229,6 +242,38 @@ impl OutcomeTriggerService {
                 }])
             }
 
+            OutcomeTrigger::AddReward { reward_type, amount } => {
+                debug!(reward_type = %reward_type, amount = %amount, "Adding reward");
+
+                // Experience and milestones are banked on the character who
+                // rolled; other rewards are only narrated
+                let award = AdvancementAward::from_reward(reward_type, *amount);
+                let pc_id = uuid::Uuid::parse_str(character_id)
+                    .ok()
+                    .map(PlayerCharacterId::from_uuid);
+                if let (Some(award), Some(pc_id), Some(advancement)) =
+                    (award, pc_id, self.advancement_service.as_ref())
+                {
+                    advancement
+                        .award(pc_id, award, Some("Challenge outcome".to_string()))
+                        .await
+                        .map_err(|e| format!("Failed to award {}: {}", award, e))?;
+                }
+
+                session_port
+                    .add_to_conversation_history(
+                        session_id,
+                        "System",
+                        &format!("[REWARD] {} {}", amount, reward_type),
+                    )
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+                Ok(vec![StateChange::EventTriggered {
+                    name: format!("Reward: {} {}", amount, reward_type),
+                }])
+            }
+
             OutcomeTrigger::Custom { description } => {
                 debug!(description = %description, "Custom trigger");
 
@@ -253,11 +298,15 @@ impl OutcomeTriggerService {
 mod tests {
     use super::*;
     use crate::application::ports::outbound::{
-        BroadcastMessage, PendingApprovalInfo, SessionManagementError, SessionManagementPort,
-        SessionWorldContext,
+        AsyncSessionError, SessionJoinInfo, SessionParticipantInfo, SessionParticipantRole,
+        SessionWorldData,
+    };
+    use crate::domain::entities::{
+        Challenge, ChallengeLocationAvailability, ChallengePrerequisite, ComplexChallengeProgress,
+    };
+    use crate::domain::value_objects::{
+        ChallengeId, LocationId, ProposedToolInfo, SceneId, SeededRollSource, SkillId, WorldId,
     };
-    use crate::domain::entities::Challenge;
-    use crate::domain::value_objects::{ChallengeId, WorldId};
     use async_trait::async_trait;
     use std::sync::Mutex;
 
@@ -274,102 +323,162 @@ mod tests {
         }
     }
 
-    impl SessionManagementPort for FakeSessionManager {
-        fn get_client_session(&self, _client_id: &str) -> Option<SessionId> {
+    #[async_trait]
+    impl AsyncSessionPort for FakeSessionManager {
+        async fn get_client_session(&self, _client_id: &str) -> Option<SessionId> {
             Some(SessionId::new())
         }
 
-        fn is_client_dm(&self, _client_id: &str) -> bool {
+        async fn is_client_dm(&self, _client_id: &str) -> bool {
             false
         }
 
-        fn get_client_user_id(&self, _client_id: &str) -> Option<String> {
+        async fn get_client_user_id(&self, _client_id: &str) -> Option<String> {
             None
         }
 
-        fn get_pending_approval(
-            &self,
-            _session_id: SessionId,
-            _request_id: &str,
-        ) -> Option<PendingApprovalInfo> {
+        async fn get_participant_info(&self, _client_id: &str) -> Option<SessionParticipantInfo> {
             None
         }
 
-        fn add_pending_approval(
-            &mut self,
-            _session_id: SessionId,
-            _approval: PendingApprovalInfo,
-        ) -> Result<(), SessionManagementError> {
-            Ok(())
+        async fn get_session_world_id(&self, _session_id: SessionId) -> Option<WorldId> {
+            None
         }
 
-        fn remove_pending_approval(
-            &mut self,
-            _session_id: SessionId,
-            _request_id: &str,
-        ) -> Result<(), SessionManagementError> {
-            Ok(())
+        async fn find_session_for_world(&self, _world_id: WorldId) -> Option<SessionId> {
+            None
         }
 
-        fn increment_retry_count(
-            &mut self,
+        async fn create_session(
+            &self,
+            _world_id: WorldId,
+            _world_snapshot: SessionWorldData,
+        ) -> SessionId {
+            SessionId::new()
+        }
+
+        async fn create_session_with_id(
+            &self,
+            session_id: SessionId,
+            _world_id: WorldId,
+            _world_snapshot: SessionWorldData,
+        ) -> SessionId {
+            session_id
+        }
+
+        async fn join_session(
+            &self,
+            session_id: SessionId,
+            _client_id: &str,
+            _user_id: String,
+            _role: SessionParticipantRole,
+        ) -> Result<SessionJoinInfo, AsyncSessionError> {
+            Err(AsyncSessionError::SessionNotFound(session_id.to_string()))
+        }
+
+        async fn broadcast_to_session(
+            &self,
             _session_id: SessionId,
-            _request_id: &str,
-        ) -> Result<u32, SessionManagementError> {
-            Ok(0)
+            _message: serde_json::Value,
+        ) -> Result<(), AsyncSessionError> {
+            Ok(())
         }
 
-        fn broadcast_to_players(
+        async fn broadcast_to_players(
             &self,
             _session_id: SessionId,
-            _message: &BroadcastMessage,
-        ) -> Result<(), SessionManagementError> {
+            _message: serde_json::Value,
+        ) -> Result<(), AsyncSessionError> {
             Ok(())
         }
 
-        fn send_to_dm(
+        async fn send_to_dm(
             &self,
             _session_id: SessionId,
-            _message: &BroadcastMessage,
-        ) -> Result<(), SessionManagementError> {
+            _message: serde_json::Value,
+        ) -> Result<(), AsyncSessionError> {
             Ok(())
         }
 
-        fn broadcast_except(
+        async fn broadcast_except(
             &self,
             _session_id: SessionId,
-            _message: &BroadcastMessage,
+            _message: serde_json::Value,
             _exclude_client: &str,
-        ) -> Result<(), SessionManagementError> {
+        ) -> Result<(), AsyncSessionError> {
             Ok(())
         }
 
-        fn broadcast_to_session(
+        async fn get_session_participants(
             &self,
             _session_id: SessionId,
-            _message: &BroadcastMessage,
-        ) -> Result<(), SessionManagementError> {
-            Ok(())
+        ) -> Vec<SessionParticipantInfo> {
+            vec![]
         }
 
-        fn add_to_conversation_history(
-            &mut self,
+        async fn add_to_conversation_history(
+            &self,
             _session_id: SessionId,
             _speaker: &str,
             text: &str,
-        ) -> Result<(), SessionManagementError> {
+        ) -> Result<(), AsyncSessionError> {
             self.history.lock().unwrap().push(text.to_string());
             Ok(())
         }
 
-        fn session_has_dm(&self, _session_id: SessionId) -> bool {
+        async fn session_has_dm(&self, _session_id: SessionId) -> bool {
             false
         }
 
-        fn get_session_world_context(
+        async fn get_session_snapshot(&self, _session_id: SessionId) -> Option<serde_json::Value> {
+            None
+        }
+
+        async fn list_session_ids(&self) -> Vec<SessionId> {
+            vec![]
+        }
+
+        async fn client_leave_session(
+            &self,
+            _client_id: &str,
+        ) -> Option<(SessionId, SessionParticipantInfo)> {
+            None
+        }
+
+        async fn update_session_scene(
+            &self,
+            _session_id: SessionId,
+            _scene_id: String,
+        ) -> Result<(), AsyncSessionError> {
+            Ok(())
+        }
+
+        async fn send_to_participant(
+            &self,
+            _session_id: SessionId,
+            _user_id: &str,
+            _message: serde_json::Value,
+        ) -> Result<(), AsyncSessionError> {
+            Ok(())
+        }
+
+        async fn get_session_dm(&self, _session_id: SessionId) -> Option<SessionParticipantInfo> {
+            None
+        }
+
+        async fn register_pending_approval(
             &self,
             _session_id: SessionId,
-        ) -> Option<SessionWorldContext> {
+            _approval_id: String,
+            _npc_name: String,
+            _proposed_dialogue: String,
+            _internal_reasoning: Option<String>,
+            _proposed_tools: Vec<ProposedToolInfo>,
+        ) -> Result<bool, AsyncSessionError> {
+            Ok(true)
+        }
+
+        async fn next_roll_source(&self, _session_id: SessionId) -> Option<SeededRollSource> {
             None
         }
     }
@@ -461,19 +570,139 @@ mod tests {
         ) -> anyhow::Result<bool> {
             Ok(false)
         }
+
+        async fn list_by_location(&self, _location_id: LocationId) -> anyhow::Result<Vec<Challenge>> {
+            Ok(vec![])
+        }
+
+        async fn set_required_skill(
+            &self,
+            _challenge_id: ChallengeId,
+            _skill_id: SkillId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_required_skill(&self, _challenge_id: ChallengeId) -> anyhow::Result<Option<SkillId>> {
+            Ok(None)
+        }
+
+        async fn remove_required_skill(&self, _challenge_id: ChallengeId) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn tie_to_scene(&self, _challenge_id: ChallengeId, _scene_id: SceneId) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_tied_scene(&self, _challenge_id: ChallengeId) -> anyhow::Result<Option<SceneId>> {
+            Ok(None)
+        }
+
+        async fn untie_from_scene(&self, _challenge_id: ChallengeId) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn add_prerequisite(
+            &self,
+            _challenge_id: ChallengeId,
+            _prerequisite: ChallengePrerequisite,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_prerequisites(
+            &self,
+            _challenge_id: ChallengeId,
+        ) -> anyhow::Result<Vec<ChallengePrerequisite>> {
+            Ok(vec![])
+        }
+
+        async fn remove_prerequisite(
+            &self,
+            _challenge_id: ChallengeId,
+            _prerequisite_id: ChallengeId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_dependent_challenges(
+            &self,
+            _challenge_id: ChallengeId,
+        ) -> anyhow::Result<Vec<ChallengeId>> {
+            Ok(vec![])
+        }
+
+        async fn add_location_availability(
+            &self,
+            _challenge_id: ChallengeId,
+            _availability: ChallengeLocationAvailability,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_location_availabilities(
+            &self,
+            _challenge_id: ChallengeId,
+        ) -> anyhow::Result<Vec<ChallengeLocationAvailability>> {
+            Ok(vec![])
+        }
+
+        async fn remove_location_availability(
+            &self,
+            _challenge_id: ChallengeId,
+            _location_id: LocationId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn add_unlock_location(
+            &self,
+            _challenge_id: ChallengeId,
+            _location_id: LocationId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_unlock_locations(&self, _challenge_id: ChallengeId) -> anyhow::Result<Vec<LocationId>> {
+            Ok(vec![])
+        }
+
+        async fn remove_unlock_location(
+            &self,
+            _challenge_id: ChallengeId,
+            _location_id: LocationId,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn get_complex_progress(
+            &self,
+            _challenge_id: ChallengeId,
+        ) -> anyhow::Result<Option<ComplexChallengeProgress>> {
+            Ok(None)
+        }
+
+        async fn save_complex_progress(&self, _progress: &ComplexChallengeProgress) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn clear_complex_progress(&self, _challenge_id: ChallengeId) -> anyhow::Result<()> {
+            Ok(())
+        }
     }
 
     #[tokio::test]
     async fn test_reveal_information() {
         let repo = Arc::new(FakeChallengeRepository::new());
         let service = OutcomeTriggerService::new(repo);
-        let mut session = FakeSessionManager::new();
+        let session = FakeSessionManager::new();
         let session_id = SessionId::new();
 
         let triggers = vec![OutcomeTrigger::reveal("A secret passage is revealed!")];
 
         let result = service
-            .execute_triggers(&triggers, &mut session, session_id)
+            .execute_triggers(&triggers, &session, session_id, "active_pc")
             .await;
 
         assert_eq!(result.trigger_count, 1);
@@ -489,7 +718,7 @@ mod tests {
     async fn test_give_item() {
         let repo = Arc::new(FakeChallengeRepository::new());
         let service = OutcomeTriggerService::new(repo);
-        let mut session = FakeSessionManager::new();
+        let session = FakeSessionManager::new();
         let session_id = SessionId::new();
 
         let triggers = vec![OutcomeTrigger::GiveItem {
@@ -498,7 +727,7 @@ mod tests {
         }];
 
         let result = service
-            .execute_triggers(&triggers, &mut session, session_id)
+            .execute_triggers(&triggers, &session, session_id, "active_pc")
             .await;
 
         assert_eq!(result.trigger_count, 1);
@@ -512,7 +741,7 @@ mod tests {
     async fn test_multiple_triggers() {
         let repo = Arc::new(FakeChallengeRepository::new());
         let service = OutcomeTriggerService::new(repo);
-        let mut session = FakeSessionManager::new();
+        let session = FakeSessionManager::new();
         let session_id = SessionId::new();
 
         let triggers = vec![
@@ -525,7 +754,7 @@ mod tests {
         ];
 
         let result = service
-            .execute_triggers(&triggers, &mut session, session_id)
+            .execute_triggers(&triggers, &session, session_id, "active_pc")
             .await;
 
         assert_eq!(result.trigger_count, 3);
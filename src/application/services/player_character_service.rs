@@ -5,14 +5,16 @@
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use std::collections::hash_map::{Entry, HashMap};
 use std::sync::Arc;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use crate::application::ports::outbound::{
-    LocationRepositoryPort, PlayerCharacterRepositoryPort, WorldRepositoryPort,
+    LocationRepositoryPort, PlayerCharacterRepositoryPort, SessionParticipantRole,
+    SheetTemplateRepositoryPort, WorldRepositoryPort,
 };
 use crate::domain::entities::PlayerCharacter;
-use crate::domain::entities::CharacterSheetData;
+use crate::domain::entities::{CharacterSheetData, CharacterSheetTemplate, InvalidSheetData};
 use crate::domain::value_objects::{
     LocationId, PlayerCharacterId, SessionId, SkillId, WorldId,
 };
@@ -30,6 +32,9 @@ pub struct CreatePlayerCharacterRequest {
     pub sheet_data: Option<CharacterSheetData>,
     pub sprite_asset: Option<String>,
     pub portrait_asset: Option<String>,
+    /// Role of whoever is creating the character; only the DM may set
+    /// read-only sheet fields
+    pub caller_role: SessionParticipantRole,
 }
 
 /// Request to update an existing player character
@@ -40,6 +45,11 @@ pub struct UpdatePlayerCharacterRequest {
     pub sheet_data: Option<CharacterSheetData>,
     pub sprite_asset: Option<String>,
     pub portrait_asset: Option<String>,
+    /// How many tiles far the character sees on a grid map
+    pub vision_radius: Option<u32>,
+    /// Role of whoever is editing the character; only the DM may change
+    /// read-only sheet fields
+    pub caller_role: SessionParticipantRole,
 }
 
 /// Player character service trait defining the application use cases
@@ -81,6 +91,29 @@ pub trait PlayerCharacterService: Send + Sync {
     /// Get a player character's modifier for a specific skill.
     /// Returns 0 if the PC doesn't have the skill or doesn't have sheet data.
     async fn get_skill_modifier(&self, id: PlayerCharacterId, skill_id: SkillId) -> Result<i32>;
+
+    /// Recalculate the derived fields of characters' sheets from their
+    /// world's sheet template. Every read through this service already does so.
+    async fn compute_derived_fields(&self, pcs: &mut [PlayerCharacter]) -> Result<()>;
+    /// Check sheet data against its world's sheet template; `previous` is the
+    /// sheet being replaced, whose read-only values anyone but the DM must keep.
+    /// Fails with `InvalidSheetData` listing every bad field.
+    async fn validate_sheet(
+        &self,
+        world_id: WorldId,
+        sheet: &CharacterSheetData,
+        previous: Option<&CharacterSheetData>,
+        caller_role: SessionParticipantRole,
+    ) -> Result<()>;
+
+    /// Player characters in a template's world whose sheets no longer fit it
+    async fn invalid_sheets(
+        &self,
+        template: &CharacterSheetTemplate,
+    ) -> Result<Vec<(PlayerCharacter, InvalidSheetData)>>;
+
+    /// The sheet template a world's characters use, if the world exists
+    async fn sheet_template(&self, world_id: WorldId) -> Result<Option<CharacterSheetTemplate>>;
 }
 
 /// Default implementation of PlayerCharacterService using port abstractions
@@ -89,6 +122,7 @@ pub struct PlayerCharacterServiceImpl {
     pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
     location_repository: Arc<dyn LocationRepositoryPort>,
     world_repository: Arc<dyn WorldRepositoryPort>,
+    sheet_template_repository: Arc<dyn SheetTemplateRepositoryPort>,
 }
 
 impl PlayerCharacterServiceImpl {
@@ -97,14 +131,64 @@ impl PlayerCharacterServiceImpl {
         pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
         location_repository: Arc<dyn LocationRepositoryPort>,
         world_repository: Arc<dyn WorldRepositoryPort>,
+        sheet_template_repository: Arc<dyn SheetTemplateRepositoryPort>,
     ) -> Self {
         Self {
             pc_repository,
             location_repository,
             world_repository,
+            sheet_template_repository,
         }
     }
 
+    /// The sheet template a world's characters use: its saved default, or the
+    /// one its rule system generates
+    async fn sheet_template_for(&self, world_id: WorldId) -> Result<Option<CharacterSheetTemplate>> {
+        if let Some(template) = self.sheet_template_repository.get_default_for_world(&world_id).await? {
+            return Ok(Some(template));
+        }
+        Ok(self
+            .world_repository
+            .get(world_id)
+            .await?
+            .map(|world| CharacterSheetTemplate::default_for_world(&world)))
+    }
+
+    /// Refuse sheet edits to fields the character's level has not unlocked
+    async fn check_locked_fields(
+        &self,
+        pc: &PlayerCharacter,
+        sheet: &CharacterSheetData,
+        previous: Option<&CharacterSheetData>,
+    ) -> Result<()> {
+        let Some(world) = self.world_repository.get(pc.world_id).await? else {
+            return Ok(());
+        };
+        let Some(template) = self.sheet_template_for(pc.world_id).await? else {
+            return Ok(());
+        };
+        if let Some(table) = template.progression_for(&world.rule_system) {
+            let level = template.character_level(pc, &table);
+            template.check_locked_fields(&table, level, sheet, previous)?;
+        }
+        Ok(())
+    }
+
+    /// Record the version of the world's sheet template a sheet is saved
+    /// under, so migrations queued before it are not run on it; see
+    /// `CharacterSheetTemplate::version_for_saved_sheet`
+    async fn stamp_template_version(
+        &self,
+        world_id: WorldId,
+        sheet: &mut CharacterSheetData,
+        previous: Option<&CharacterSheetData>,
+    ) -> Result<()> {
+        if let Some(template) = self.sheet_template_for(world_id).await? {
+            sheet.template_version = template.version_for_saved_sheet(previous);
+        }
+        Ok(())
+    }
+
     /// Validate create request
     fn validate_create_request(request: &CreatePlayerCharacterRequest) -> Result<()> {
         if request.name.trim().is_empty() {
@@ -169,7 +253,8 @@ impl PlayerCharacterService for PlayerCharacterServiceImpl {
         if let Some(description) = request.description {
             pc = pc.with_description(description);
         }
-        if let Some(sheet_data) = request.sheet_data {
+        if let Some(mut sheet_data) = request.sheet_data {
+            self.stamp_template_version(request.world_id, &mut sheet_data, None).await?;
             pc = pc.with_sheet_data(sheet_data);
         }
         if let Some(sprite) = request.sprite_asset {
@@ -181,6 +266,11 @@ impl PlayerCharacterService for PlayerCharacterServiceImpl {
 
         // Validate the PC
         pc.validate().map_err(|e| anyhow::anyhow!(e))?;
+        if let Some(sheet) = &pc.sheet_data {
+            self.validate_sheet(pc.world_id, sheet, None, request.caller_role).await?;
+            self.check_locked_fields(&pc, sheet, None).await?;
+        }
+        self.compute_derived_fields(std::slice::from_mut(&mut pc)).await?;
 
         self.pc_repository
             .create(&pc)
@@ -209,10 +299,13 @@ impl PlayerCharacterService for PlayerCharacterServiceImpl {
     #[instrument(skip(self), fields(pc_id = %id))]
     async fn get_pc(&self, id: PlayerCharacterId) -> Result<Option<PlayerCharacter>> {
         debug!(pc_id = %id, "Fetching player character");
-        self.pc_repository
+        let mut pc = self
+            .pc_repository
             .get(id)
             .await
-            .context("Failed to get player character from repository")
+            .context("Failed to get player character from repository")?;
+        self.compute_derived_fields(pc.as_mut_slice()).await?;
+        Ok(pc)
     }
 
     #[instrument(skip(self), fields(user_id = %user_id, session_id = %session_id))]
@@ -222,19 +315,25 @@ impl PlayerCharacterService for PlayerCharacterServiceImpl {
         session_id: SessionId,
     ) -> Result<Option<PlayerCharacter>> {
         debug!(user_id = %user_id, session_id = %session_id, "Fetching player character by user and session");
-        self.pc_repository
+        let mut pc = self
+            .pc_repository
             .get_by_user_and_session(user_id, session_id)
             .await
-            .context("Failed to get player character from repository")
+            .context("Failed to get player character from repository")?;
+        self.compute_derived_fields(pc.as_mut_slice()).await?;
+        Ok(pc)
     }
 
     #[instrument(skip(self), fields(session_id = %session_id))]
     async fn get_pcs_by_session(&self, session_id: SessionId) -> Result<Vec<PlayerCharacter>> {
         debug!(session_id = %session_id, "Fetching player characters for session");
-        self.pc_repository
+        let mut pcs = self
+            .pc_repository
             .get_by_session(session_id)
             .await
-            .context("Failed to get player characters from repository")
+            .context("Failed to get player characters from repository")?;
+        self.compute_derived_fields(&mut pcs).await?;
+        Ok(pcs)
     }
 
     #[instrument(skip(self), fields(pc_id = %id))]
@@ -262,7 +361,14 @@ impl PlayerCharacterService for PlayerCharacterServiceImpl {
                 Some(description)
             };
         }
-        if let Some(sheet_data) = request.sheet_data {
+        let sheet_changed = request.sheet_data.is_some();
+        if let Some(mut sheet_data) = request.sheet_data {
+            self.validate_sheet(pc.world_id, &sheet_data, pc.sheet_data.as_ref(), request.caller_role)
+                .await?;
+            self.check_locked_fields(&pc, &sheet_data, pc.sheet_data.as_ref())
+                .await?;
+            self.stamp_template_version(pc.world_id, &mut sheet_data, pc.sheet_data.as_ref())
+                .await?;
             pc.sheet_data = Some(sheet_data);
         }
         if let Some(sprite) = request.sprite_asset {
@@ -279,9 +385,15 @@ impl PlayerCharacterService for PlayerCharacterServiceImpl {
                 Some(portrait)
             };
         }
+        if let Some(vision_radius) = request.vision_radius {
+            pc.vision_radius = vision_radius;
+        }
 
         pc.touch(); // Update last_active_at
         pc.validate().map_err(|e| anyhow::anyhow!(e))?;
+        if sheet_changed {
+            self.compute_derived_fields(std::slice::from_mut(&mut pc)).await?;
+        }
 
         self.pc_repository
             .update(&pc)
@@ -359,5 +471,66 @@ impl PlayerCharacterService for PlayerCharacterServiceImpl {
         debug!(pc_id = %id, skill_id = %skill_id, "No skill modifier found, defaulting to 0");
         Ok(0)
     }
+
+    /// A broken formula is logged rather than failing the request, so a bad
+    /// template edit never locks players out of their characters.
+    async fn compute_derived_fields(&self, pcs: &mut [PlayerCharacter]) -> Result<()> {
+        let mut templates: HashMap<WorldId, Option<CharacterSheetTemplate>> = HashMap::new();
+        for pc in pcs {
+            let Some(sheet) = pc.sheet_data.as_mut() else {
+                continue;
+            };
+            let template = match templates.entry(pc.world_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.sheet_template_for(pc.world_id).await?),
+            };
+            if let Some(template) = template {
+                if let Err(e) = template.compute_derived(sheet) {
+                    warn!(pc_id = %pc.id, "Could not compute derived sheet fields: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn validate_sheet(
+        &self,
+        world_id: WorldId,
+        sheet: &CharacterSheetData,
+        previous: Option<&CharacterSheetData>,
+        caller_role: SessionParticipantRole,
+    ) -> Result<()> {
+        if let Some(template) = self.sheet_template_for(world_id).await? {
+            let by_player = caller_role != SessionParticipantRole::DungeonMaster;
+            template.validate_sheet(sheet, previous, by_player)?;
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self, template), fields(world_id = %template.world_id))]
+    async fn invalid_sheets(
+        &self,
+        template: &CharacterSheetTemplate,
+    ) -> Result<Vec<(PlayerCharacter, InvalidSheetData)>> {
+        let pcs = self
+            .pc_repository
+            .get_by_world(template.world_id)
+            .await
+            .context("Failed to get player characters from repository")?;
+
+        // Each sheet is its own previous version, so read-only values pass
+        Ok(pcs
+            .into_iter()
+            .filter_map(|pc| {
+                let sheet = pc.sheet_data.as_ref()?;
+                let invalid = template.validate_sheet(sheet, Some(sheet), true).err()?;
+                Some((pc, invalid))
+            })
+            .collect())
+    }
+
+    async fn sheet_template(&self, world_id: WorldId) -> Result<Option<CharacterSheetTemplate>> {
+        self.sheet_template_for(world_id).await
+    }
 }
 
@@ -238,6 +238,7 @@ fn create_demo_world() -> crate::application::dto::WorldSnapshot {
         name: "Demo World".to_string(),
         description: "A demonstration world for testing".to_string(),
         rule_system: RuleSystemConfig::default(),
+        rule_system_definition: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     };
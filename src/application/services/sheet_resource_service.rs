@@ -0,0 +1,198 @@
+//! Sheet Resource Service
+//!
+//! Spends and recovers the resources on player character sheets (hit points,
+//! spell slots, hit dice, luck, stress) and runs the rests a world's rule
+//! system defines. Every change is recorded on the story timeline as a
+//! `StatModified` event. Resting also ends conditions that last until rest.
+
+use anyhow::{Context, Result};
+use std::sync::Arc;
+
+use crate::application::ports::outbound::{PlayerCharacterRepositoryPort, WorldRepositoryPort};
+use crate::application::services::{PlayerCharacterService, StoryEventService};
+use crate::domain::entities::{PlayerCharacter, ResourceChange};
+use crate::domain::value_objects::{
+    ActiveCondition, CharacterId, PlayerCharacterId, ResourceOperation, RestType, WorldId,
+};
+
+/// What a rest did for one character
+#[derive(Debug, Clone)]
+pub struct RestedCharacter {
+    pub pc: PlayerCharacter,
+    pub changes: Vec<ResourceChange>,
+    /// Conditions the rest ended
+    pub ended_conditions: Vec<ActiveCondition>,
+}
+
+/// What a rest recovered for each character who took it
+#[derive(Debug, Clone)]
+pub struct RestOutcome {
+    pub rest: RestType,
+    /// Characters the rest changed anything for
+    pub characters: Vec<RestedCharacter>,
+}
+
+/// Service for operating on character sheet resources
+#[derive(Clone)]
+pub struct SheetResourceService {
+    player_character_service: Arc<dyn PlayerCharacterService>,
+    pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
+    world_repository: Arc<dyn WorldRepositoryPort>,
+    story_event_service: StoryEventService,
+}
+
+impl SheetResourceService {
+    /// Create a new sheet resource service
+    pub fn new(
+        player_character_service: Arc<dyn PlayerCharacterService>,
+        pc_repository: Arc<dyn PlayerCharacterRepositoryPort>,
+        world_repository: Arc<dyn WorldRepositoryPort>,
+        story_event_service: StoryEventService,
+    ) -> Self {
+        Self {
+            player_character_service,
+            pc_repository,
+            world_repository,
+            story_event_service,
+        }
+    }
+
+    /// The rests a world's rule system offers
+    pub async fn rest_types(&self, world_id: WorldId) -> Result<Vec<RestType>> {
+        let world = self
+            .world_repository
+            .get(world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
+        Ok(world.rule_system.rest_types())
+    }
+
+    /// Spend, restore or reset one resource on a character's sheet
+    ///
+    /// Fails with `ResourceError` if the field is not a resource or a spend
+    /// asks for more than is left. Returns None if the character is unknown.
+    pub async fn modify_resource(
+        &self,
+        pc_id: PlayerCharacterId,
+        field_id: &str,
+        operation: ResourceOperation,
+        reason: Option<String>,
+        game_time: Option<String>,
+    ) -> Result<Option<(PlayerCharacter, ResourceChange)>> {
+        let Some(mut pc) = self.player_character_service.get_pc(pc_id).await? else {
+            return Ok(None);
+        };
+        let template = self
+            .player_character_service
+            .sheet_template(pc.world_id)
+            .await?
+            .context("World of player character not found")?;
+
+        let sheet = pc.sheet_data.get_or_insert_with(Default::default);
+        let change = template.apply_resource(sheet, field_id, operation)?;
+        self.pc_repository.update(&pc).await?;
+
+        let reason = reason.unwrap_or_else(|| {
+            match operation {
+                ResourceOperation::Spend(_) => "spent",
+                ResourceOperation::Restore(_) => "restored",
+                ResourceOperation::Reset => "reset",
+            }
+            .to_string()
+        });
+        self.record_change(&pc, &change, reason, game_time).await;
+        Ok(Some((pc, change)))
+    }
+
+    /// Have characters take one of their world's rests
+    ///
+    /// With no characters given, everyone in the world rests. Returns None if
+    /// the rule system has no rest with this ID. Advancing game time is left
+    /// to the caller, which owns the session clock.
+    pub async fn take_rest(
+        &self,
+        world_id: WorldId,
+        rest_id: &str,
+        pc_ids: &[PlayerCharacterId],
+        game_time: Option<String>,
+    ) -> Result<Option<RestOutcome>> {
+        let world = self
+            .world_repository
+            .get(world_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
+        let Some(rest) = world.rule_system.rest_type(rest_id) else {
+            return Ok(None);
+        };
+        let template = self
+            .player_character_service
+            .sheet_template(world_id)
+            .await?
+            .context("World not found")?;
+
+        let mut pcs = self.pc_repository.get_by_world(world_id).await?;
+        if !pc_ids.is_empty() {
+            pcs.retain(|pc| pc_ids.contains(&pc.id));
+        }
+        self.player_character_service
+            .compute_derived_fields(&mut pcs)
+            .await?;
+
+        let mut characters = Vec::new();
+        for mut pc in pcs {
+            let changes = match pc.sheet_data.as_mut() {
+                Some(sheet) => template.apply_rest(sheet, &rest),
+                None => Vec::new(),
+            };
+            let ended_conditions = pc.conditions.end_rest();
+            if changes.is_empty() && ended_conditions.is_empty() {
+                continue;
+            }
+            self.pc_repository.update(&pc).await?;
+            for change in &changes {
+                self.record_change(&pc, change, rest.name.clone(), game_time.clone())
+                    .await;
+            }
+            characters.push(RestedCharacter {
+                pc,
+                changes,
+                ended_conditions,
+            });
+        }
+
+        tracing::info!(
+            "{} characters in world {} took a {}",
+            characters.len(),
+            world_id,
+            rest.name
+        );
+        Ok(Some(RestOutcome { rest, characters }))
+    }
+
+    /// Log a resource change to the timeline; the change itself is already
+    /// saved, so a failure here is only reported
+    async fn record_change(
+        &self,
+        pc: &PlayerCharacter,
+        change: &ResourceChange,
+        reason: String,
+        game_time: Option<String>,
+    ) {
+        if let Err(e) = self
+            .story_event_service
+            .record_stat_modified(
+                pc.world_id,
+                pc.session_id,
+                CharacterId::from_uuid(*pc.id.as_uuid()),
+                change.field_name.clone(),
+                change.previous,
+                change.current,
+                reason,
+                game_time,
+            )
+            .await
+        {
+            tracing::warn!("Failed to record resource change for {}: {}", pc.id, e);
+        }
+    }
+}
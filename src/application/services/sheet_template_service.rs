@@ -5,25 +5,49 @@
 //! the business logic layer for sheet template operations.
 
 use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::application::ports::outbound::SheetTemplateRepositoryPort;
-use crate::domain::entities::{CharacterSheetTemplate, SheetTemplateId};
+use crate::application::ports::outbound::{PlayerCharacterRepositoryPort, SheetTemplateRepositoryPort};
+use crate::domain::entities::{
+    CharacterSheetTemplate, FieldChange, PlayerCharacter, SheetMigration, SheetSection,
+    SheetTemplateId,
+};
 use crate::domain::value_objects::WorldId;
 
+/// Why a template's migrations cannot be applied or rolled back
+#[derive(Debug, thiserror::Error)]
+pub enum SheetMigrationError {
+    #[error("Only the world's default sheet template can be migrated")]
+    NotDefault,
+    #[error("Character sheets have been edited since the migration was applied: {}", .0.join(", "))]
+    EditedSince(Vec<String>),
+}
+
 /// Service for managing character sheet templates
 pub struct SheetTemplateService {
     repository: Arc<dyn SheetTemplateRepositoryPort>,
+    player_character_repository: Arc<dyn PlayerCharacterRepositoryPort>,
 }
 
 impl SheetTemplateService {
     /// Create a new sheet template service
-    pub fn new(repository: Arc<dyn SheetTemplateRepositoryPort>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn SheetTemplateRepositoryPort>,
+        player_character_repository: Arc<dyn PlayerCharacterRepositoryPort>,
+    ) -> Self {
+        Self {
+            repository,
+            player_character_repository,
+        }
     }
 
     /// Create a new sheet template
+    ///
+    /// Fails with `InvalidDerivedFields` if a derived formula is broken or
+    /// derived fields depend on each other in a cycle.
     pub async fn create(&self, template: &CharacterSheetTemplate) -> Result<()> {
+        template.derived_fields()?;
         self.repository.create(template).await
     }
 
@@ -45,11 +69,140 @@ impl SheetTemplateService {
         self.repository.list_by_world(world_id).await
     }
 
-    /// Update a sheet template
+    /// Update a sheet template, checking its derived fields as `create` does
     pub async fn update(&self, template: &CharacterSheetTemplate) -> Result<()> {
+        template.derived_fields()?;
         self.repository.update(template).await
     }
 
+    /// Replace a template's sections, saving it as the next version
+    ///
+    /// The migration from the current version is queued on the template until
+    /// `apply_migration` carries the world's character sheets across.
+    pub async fn revise(
+        &self,
+        current: &CharacterSheetTemplate,
+        sections: Vec<SheetSection>,
+        renames: &HashMap<String, String>,
+    ) -> Result<CharacterSheetTemplate> {
+        let revised = current.revise(sections, renames);
+        self.update(&revised).await?;
+        Ok(revised)
+    }
+
+    /// Refuse to migrate sheets for any template but the world's default,
+    /// the only one character sheets are checked against
+    async fn check_default(&self, template: &CharacterSheetTemplate) -> Result<()> {
+        let default = self.repository.get_default_for_world(&template.world_id).await?;
+        if default.is_none_or(|default| default.id != template.id) {
+            return Err(SheetMigrationError::NotDefault.into());
+        }
+        Ok(())
+    }
+
+    /// What applying a template's pending migrations would change on each
+    /// character in its world
+    ///
+    /// Characters the migrations leave untouched are not listed.
+    pub async fn preview_migration(
+        &self,
+        template: &CharacterSheetTemplate,
+    ) -> Result<Vec<(PlayerCharacter, Vec<FieldChange>)>> {
+        self.check_default(template).await?;
+        let pcs = self
+            .player_character_repository
+            .get_by_world(template.world_id)
+            .await?;
+        Ok(pcs
+            .into_iter()
+            .filter_map(|pc| {
+                let sheet = pc.sheet_data.as_ref()?;
+                let migrated = SheetMigration::apply_all(&template.pending_migrations, sheet);
+                let changes = SheetMigration::changes(sheet, &migrated);
+                (!changes.is_empty()).then_some((pc, changes))
+            })
+            .collect())
+    }
+
+    /// Apply a template's pending migrations to every character in its world
+    ///
+    /// Only the world's default template can be migrated. Each sheet is
+    /// backed up first, and the migrations move to the template's applied
+    /// list so `rollback_migration` can undo them.
+    pub async fn apply_migration(
+        &self,
+        template: &CharacterSheetTemplate,
+    ) -> Result<CharacterSheetTemplate> {
+        self.check_default(template).await?;
+        let pcs = self
+            .player_character_repository
+            .get_by_world(template.world_id)
+            .await?;
+        for mut pc in pcs {
+            let Some(sheet) = pc.sheet_data.take() else {
+                continue;
+            };
+            pc.sheet_data = Some(SheetMigration::apply_all(&template.pending_migrations, &sheet));
+            pc.sheet_backup = Some(sheet);
+            self.player_character_repository.update(&pc).await?;
+        }
+
+        let mut migrated = template.clone();
+        migrated.applied_migrations = std::mem::take(&mut migrated.pending_migrations);
+        self.repository.update(&migrated).await?;
+        tracing::info!(
+            "Migrated character sheets of world {} to template {} version {}",
+            template.world_id,
+            template.id.0,
+            template.version
+        );
+        Ok(migrated)
+    }
+
+    /// Restore the sheets backed up by the last `apply_migration`
+    ///
+    /// Refused if any sheet has been edited since, as restoring it would lose
+    /// the edit. The rolled-back migrations are queued again ahead of any
+    /// pending ones.
+    pub async fn rollback_migration(
+        &self,
+        template: &CharacterSheetTemplate,
+    ) -> Result<CharacterSheetTemplate> {
+        self.check_default(template).await?;
+        let pcs = self
+            .player_character_repository
+            .get_by_world(template.world_id)
+            .await?;
+        let edited: Vec<String> = pcs
+            .iter()
+            .filter(|pc| {
+                pc.sheet_backup.as_ref().is_some_and(|backup| {
+                    pc.sheet_data.as_ref()
+                        != Some(&SheetMigration::apply_all(&template.applied_migrations, backup))
+                })
+            })
+            .map(|pc| pc.name.clone())
+            .collect();
+        if !edited.is_empty() {
+            return Err(SheetMigrationError::EditedSince(edited).into());
+        }
+
+        for mut pc in pcs {
+            if let Some(backup) = pc.sheet_backup.take() {
+                pc.sheet_data = Some(backup);
+                self.player_character_repository.update(&pc).await?;
+            }
+        }
+
+        let mut restored = template.clone();
+        restored.pending_migrations = std::mem::take(&mut restored.applied_migrations);
+        restored
+            .pending_migrations
+            .extend(template.pending_migrations.iter().cloned());
+        self.repository.update(&restored).await?;
+        Ok(restored)
+    }
+
     /// Delete a sheet template
     pub async fn delete(&self, id: &SheetTemplateId) -> Result<()> {
         self.repository.delete(id).await
@@ -10,7 +10,7 @@ use std::sync::Arc;
 use tracing::{debug, info, instrument};
 
 use crate::application::ports::outbound::{SkillRepositoryPort, WorldRepositoryPort};
-use crate::domain::entities::{default_skills_for_variant, Skill, SkillCategory};
+use crate::domain::entities::{default_skills_for_world, Skill, SkillCategory};
 use crate::domain::value_objects::{SkillId, WorldId};
 
 /// Request to create a new skill
@@ -121,7 +121,7 @@ impl SkillService for SkillServiceImpl {
                 variant = ?world.rule_system.variant,
                 "No skills found, generating defaults"
             );
-            default_skills_for_variant(world_id, &world.rule_system.variant)
+            default_skills_for_world(&world)
         } else {
             skills
         };
@@ -270,7 +270,7 @@ impl SkillService for SkillServiceImpl {
             .ok_or_else(|| anyhow::anyhow!("World not found: {}", world_id))?;
 
         // Generate default skills
-        let skills = default_skills_for_variant(world_id, &world.rule_system.variant);
+        let skills = default_skills_for_world(&world);
 
         // Save all skills
         for skill in &skills {
@@ -16,7 +16,7 @@ use std::sync::Arc;
 use crate::application::dto::AppEvent;
 use crate::application::ports::outbound::{EventBusPort, StoryEventRepositoryPort};
 use crate::domain::entities::{
-    ChallengeEventOutcome, DmMarkerType, InfoImportance, InfoType, InvolvedCharacter,
+    ChallengeEventOutcome, CombatEventType, CombatOutcome, DmMarkerType, InfoImportance, InfoType, InvolvedCharacter,
     ItemSource, MarkerImportance, StoryEvent, StoryEventType,
 };
 use crate::domain::value_objects::{
@@ -463,6 +463,140 @@ impl StoryEventService {
         Ok(event_id)
     }
 
+    /// Record a change to a character's stat or resource
+    ///
+    /// Sheet edits outside a session are recorded against the world only.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_stat_modified(
+        &self,
+        world_id: WorldId,
+        session_id: Option<SessionId>,
+        character_id: CharacterId,
+        stat_name: String,
+        previous_value: i32,
+        new_value: i32,
+        reason: String,
+        game_time: Option<String>,
+    ) -> Result<StoryEventId> {
+        let event_type = StoryEventType::StatModified {
+            character_id,
+            stat_name: stat_name.clone(),
+            previous_value,
+            new_value,
+            reason: reason.clone(),
+        };
+
+        let mut event = StoryEvent::new(world_id, event_type)
+            .with_summary(format!("{}: {} -> {} ({})", stat_name, previous_value, new_value, reason));
+
+        if let Some(gt) = game_time {
+            event = event.with_game_time(gt);
+        }
+
+        let event_id = event.id;
+        self.repository.create(&event).await?;
+
+        // Player characters are not Character nodes, so the event type
+        // carries the character rather than an INVOLVES edge
+        if let Some(sid) = session_id {
+            self.repository.set_session(event_id, sid).await?;
+        }
+
+        self.publish_event_created(&event).await;
+
+        tracing::debug!("Recorded stat modified event: {}", event_id);
+        Ok(event_id)
+    }
+
+    /// Record a player character reaching a new level
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_level_gained(
+        &self,
+        world_id: WorldId,
+        session_id: Option<SessionId>,
+        character_id: CharacterId,
+        character_name: String,
+        level: u32,
+        unlocked: Vec<String>,
+        game_time: Option<String>,
+    ) -> Result<StoryEventId> {
+        let mut summary = format!("{} reached level {}", character_name, level);
+        if !unlocked.is_empty() {
+            summary.push_str(&format!(", unlocking {}", unlocked.join(", ")));
+        }
+        let event_type = StoryEventType::LevelGained {
+            character_id,
+            character_name,
+            level,
+            unlocked,
+        };
+
+        let mut event = StoryEvent::new(world_id, event_type).with_summary(summary);
+
+        if let Some(gt) = game_time {
+            event = event.with_game_time(gt);
+        }
+
+        let event_id = event.id;
+        self.repository.create(&event).await?;
+
+        if let Some(sid) = session_id {
+            self.repository.set_session(event_id, sid).await?;
+        }
+
+        self.publish_event_created(&event).await;
+
+        tracing::debug!("Recorded level gained event: {}", event_id);
+        Ok(event_id)
+    }
+
+    /// Record a moment in a combat encounter: its start, a completed round,
+    /// a combatant falling or fleeing, or its end
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_combat_event(
+        &self,
+        world_id: WorldId,
+        session_id: SessionId,
+        location_id: LocationId,
+        combat_type: CombatEventType,
+        participants: Vec<CharacterId>,
+        enemies: Vec<String>,
+        outcome: Option<CombatOutcome>,
+        rounds: Option<u32>,
+        summary: Option<String>,
+        game_time: Option<String>,
+    ) -> Result<StoryEventId> {
+        let event_type = StoryEventType::CombatEvent {
+            combat_type,
+            participants,
+            enemies,
+            outcome,
+            location_id,
+            rounds,
+        };
+
+        let mut event = StoryEvent::new(world_id, event_type);
+        match summary {
+            Some(summary) => event = event.with_summary(summary),
+            None => event.auto_summarize(),
+        }
+
+        if let Some(gt) = game_time {
+            event = event.with_game_time(gt);
+        }
+
+        let event_id = event.id;
+        self.repository.create(&event).await?;
+
+        self.repository.set_session(event_id, session_id).await?;
+        self.repository.set_location(event_id, location_id).await?;
+
+        self.publish_event_created(&event).await;
+
+        tracing::debug!("Recorded combat event: {}", event_id);
+        Ok(event_id)
+    }
+
     /// Record session start
     pub async fn record_session_started(
         &self,
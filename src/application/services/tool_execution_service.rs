@@ -1,15 +1,17 @@
 //! Tool Execution Service - Executes approved tool calls to modify game state
 //!
 //! This service handles the execution of game tools that have been approved by the DM.
-//! It modifies in-memory session state without persisting to the database, allowing
-//! for future expansion with more complex effects.
+//! It modifies in-memory session state; conditions are also persisted on the
+//! affected character when a `ConditionService` is configured.
 
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use crate::application::ports::outbound::SessionManagementPort;
+use crate::application::services::ConditionService;
 use crate::domain::value_objects::{
-    ChangeAmount, GameTool, InfoImportance, RelationshipChange, SessionId,
+    ActiveCondition, ChangeAmount, ConditionDuration, GameTool, InfoImportance, RelationshipChange,
+    SessionId,
 };
 
 /// Result of executing a tool
@@ -109,12 +111,22 @@ pub enum ToolExecutionError {
 }
 
 /// Service for executing approved game tools
-pub struct ToolExecutionService;
+pub struct ToolExecutionService {
+    condition_service: Option<ConditionService>,
+}
 
 impl ToolExecutionService {
     /// Create a new tool execution service
     pub fn new() -> Self {
-        Self
+        Self {
+            condition_service: None,
+        }
+    }
+
+    /// Persist conditions added and removed by tools
+    pub fn with_condition_service(mut self, condition_service: ConditionService) -> Self {
+        self.condition_service = Some(condition_service);
+        self
     }
 
     /// Execute an approved tool call and modify session state
@@ -581,6 +593,24 @@ impl ToolExecutionService {
         session_id: SessionId,
     ) -> Result<ToolExecutionResult, ToolExecutionError> {
         let dur_str = duration.unwrap_or("permanent");
+
+        if let Some(condition_service) = &self.condition_service {
+            let parsed = ConditionDuration::parse(dur_str).unwrap_or_else(|| {
+                warn!("Unrecognised condition duration '{}', treating as permanent", dur_str);
+                ConditionDuration::Permanent
+            });
+            let applied_at = session.get_game_time(session_id).unwrap_or_else(chrono::Utc::now);
+            let condition = ActiveCondition::new(condition_name, parsed, applied_at)
+                .with_description(description);
+            let applied = condition_service
+                .apply_condition(character_id, condition)
+                .await
+                .map_err(|e| ToolExecutionError::ExecutionError(e.to_string()))?;
+            if !applied {
+                return Err(ToolExecutionError::CharacterNotFound(character_id.to_string()));
+            }
+        }
+
         let description_msg = format!(
             "Condition '{}' added to {} ({}): {}",
             condition_name, character_id, dur_str, description
@@ -619,6 +649,16 @@ impl ToolExecutionService {
         session: &mut S,
         session_id: SessionId,
     ) -> Result<ToolExecutionResult, ToolExecutionError> {
+        if let Some(condition_service) = &self.condition_service {
+            let removed = condition_service
+                .remove_condition(character_id, condition_name)
+                .await
+                .map_err(|e| ToolExecutionError::ExecutionError(e.to_string()))?;
+            if removed.is_none() {
+                debug!("{} has no condition '{}' to remove", character_id, condition_name);
+            }
+        }
+
         let description_msg = format!(
             "Condition '{}' removed from {}",
             condition_name, character_id
@@ -815,6 +855,10 @@ mod tests {
         ) -> Option<crate::application::ports::outbound::SessionWorldContext> {
             None
         }
+
+        fn get_game_time(&self, _session_id: SessionId) -> Option<chrono::DateTime<chrono::Utc>> {
+            None
+        }
     }
 
     fn create_test_session() -> FakeSessionManager {
@@ -0,0 +1,162 @@
+//! Vision Service
+//!
+//! Keeps the fog of war on each session's grid maps. When a player
+//! character looks around from a tile, it sees every tile within its vision
+//! radius that a sight line reaches; those tiles stay explored after it
+//! moves on. Each player is sent only what their own character has seen,
+//! so the layout of unexplored rooms never leaves the server; the DM reads
+//! the full map through the grid map service instead, and a player's path,
+//! range and cover queries run over only the tiles their character has
+//! explored. Fog lasts as long as the server; it is not persisted.
+
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::application::dto::{MapViewDto, MapViewNotification};
+use crate::application::ports::outbound::AsyncSessionPort;
+use crate::application::services::{GridMapService, PlayerCharacterService};
+use crate::domain::entities::{CharacterSight, FogOfWar, GridMap, GridMapError, GridPosition, PlayerCharacter};
+use crate::domain::value_objects::{GridMapId, PlayerCharacterId, SessionId};
+
+/// Error type for vision operations
+#[derive(Debug, thiserror::Error)]
+pub enum VisionError {
+    #[error("{0} is not playing in session {1}")]
+    NotInSession(String, SessionId),
+}
+
+/// Service for line of sight and fog of war
+#[derive(Clone)]
+pub struct VisionService {
+    grid_map_service: Arc<dyn GridMapService>,
+    player_character_service: Arc<dyn PlayerCharacterService>,
+    sessions: Arc<dyn AsyncSessionPort>,
+    /// The fog on each map played in each session
+    fog: Arc<RwLock<HashMap<(SessionId, GridMapId), FogOfWar>>>,
+}
+
+impl VisionService {
+    /// Create a new vision service
+    pub fn new(
+        grid_map_service: Arc<dyn GridMapService>,
+        player_character_service: Arc<dyn PlayerCharacterService>,
+        sessions: Arc<dyn AsyncSessionPort>,
+    ) -> Self {
+        Self {
+            grid_map_service,
+            player_character_service,
+            sessions,
+            fog: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// A player character looks around from a tile of a grid map
+    ///
+    /// Its player is sent what it now sees and has seen.
+    pub async fn look(
+        &self,
+        session_id: SessionId,
+        grid_map_id: GridMapId,
+        pc_id: PlayerCharacterId,
+        (x, y): GridPosition,
+    ) -> Result<MapViewDto> {
+        let grid_map = self.load_grid_map(grid_map_id).await?;
+        if !grid_map.contains(x, y) {
+            return Err(GridMapError::OutOfBounds { x, y }.into());
+        }
+        let pc = self.load_pc(session_id, pc_id).await?;
+
+        let visible = grid_map.visible_from((x, y), pc.vision_radius);
+        let view = {
+            let mut fog = self.fog.write().await;
+            let sight = fog.entry((session_id, grid_map_id)).or_default().look(pc_id, visible);
+            MapViewDto::new(&grid_map, pc_id, sight)
+        };
+        tracing::debug!(
+            "{} looked around from ({}, {}) on {}: {} tiles in sight",
+            pc.name,
+            x,
+            y,
+            grid_map.name,
+            view.visible.len()
+        );
+
+        self.notify(session_id, &pc, view.clone()).await;
+        Ok(view)
+    }
+
+    /// What a player character knows of a grid map
+    pub async fn view(
+        &self,
+        session_id: SessionId,
+        grid_map_id: GridMapId,
+        pc_id: PlayerCharacterId,
+    ) -> Result<MapViewDto> {
+        let grid_map = self.load_grid_map(grid_map_id).await?;
+        self.load_pc(session_id, pc_id).await?;
+
+        let sight = self.sight(session_id, grid_map_id, pc_id).await;
+        Ok(MapViewDto::new(&grid_map, pc_id, &sight))
+    }
+
+    /// Every tile of a grid map a player character has seen
+    pub async fn explored(
+        &self,
+        session_id: SessionId,
+        grid_map_id: GridMapId,
+        pc_id: PlayerCharacterId,
+    ) -> HashSet<GridPosition> {
+        self.sight(session_id, grid_map_id, pc_id).await.explored
+    }
+
+    async fn sight(&self, session_id: SessionId, grid_map_id: GridMapId, pc_id: PlayerCharacterId) -> CharacterSight {
+        self.fog
+            .read()
+            .await
+            .get(&(session_id, grid_map_id))
+            .map(|fog| fog.sight(pc_id))
+            .unwrap_or_default()
+    }
+
+    async fn load_grid_map(&self, grid_map_id: GridMapId) -> Result<GridMap> {
+        self.grid_map_service
+            .get_grid_map(grid_map_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Grid map not found: {}", grid_map_id))
+    }
+
+    /// Load a player character, checking it plays in the session
+    async fn load_pc(&self, session_id: SessionId, pc_id: PlayerCharacterId) -> Result<PlayerCharacter> {
+        let pc = self
+            .player_character_service
+            .get_pc(pc_id)
+            .await
+            .context("Failed to load player character")?
+            .ok_or_else(|| anyhow::anyhow!("Player character not found: {}", pc_id))?;
+        if pc.session_id != Some(session_id) {
+            return Err(VisionError::NotInSession(pc.name, session_id).into());
+        }
+        Ok(pc)
+    }
+
+    /// Send a player their character's view; delivery failures are only
+    /// reported
+    async fn notify(&self, session_id: SessionId, pc: &PlayerCharacter, view: MapViewDto) {
+        let message = match serde_json::to_value(MapViewNotification::new(view)) {
+            Ok(message) => message,
+            Err(e) => {
+                tracing::error!("Failed to serialize map view: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self
+            .sessions
+            .send_to_participant(session_id, &pc.user_id, message)
+            .await
+        {
+            tracing::warn!("Failed to send map view to {}: {}", pc.name, e);
+        }
+    }
+}
@@ -12,9 +12,16 @@ use crate::application::ports::outbound::{
     ExportOptions, PlayerWorldSnapshot, WorldExporterPort, WorldRepositoryPort,
 };
 use crate::application::services::SettingsService;
-use crate::domain::entities::{Act, MonomythStage, World};
+use crate::domain::entities::{Act, MonomythStage, RuleSystemDefinition, World};
 use crate::domain::value_objects::{AppSettings, RuleSystemConfig, WorldId};
 
+/// Error type for world operations
+#[derive(Debug, thiserror::Error)]
+pub enum WorldError {
+    #[error("World not found: {0}")]
+    NotFound(WorldId),
+}
+
 /// Request to create a new world
 #[derive(Debug, Clone)]
 pub struct CreateWorldRequest {
@@ -65,6 +72,9 @@ pub trait WorldService: Send + Sync {
     /// Update a world
     async fn update_world(&self, id: WorldId, request: UpdateWorldRequest) -> Result<World>;
 
+    /// Switch a world to a validated user-defined rule system
+    async fn set_rule_system_definition(&self, id: WorldId, definition: RuleSystemDefinition) -> Result<World>;
+
     /// Delete a world with cascading cleanup of all related entities
     async fn delete_world(&self, id: WorldId) -> Result<()>;
 
@@ -231,6 +241,23 @@ impl WorldService for WorldServiceImpl {
         Ok(world)
     }
 
+    #[instrument(skip(self, definition), fields(world_id = %id, rule_system = %definition.name))]
+    async fn set_rule_system_definition(&self, id: WorldId, definition: RuleSystemDefinition) -> Result<World> {
+        definition.validate()?;
+
+        let mut world = self.repository.get(id).await?.ok_or(WorldError::NotFound(id))?;
+
+        world.use_rule_system_definition(definition);
+
+        self.repository
+            .update(&world)
+            .await
+            .context("Failed to update world in repository")?;
+
+        info!(world_id = %id, "World now uses rule system: {}", world.rule_system.name);
+        Ok(world)
+    }
+
     #[instrument(skip(self))]
     async fn delete_world(&self, id: WorldId) -> Result<()> {
         // Verify the world exists before deletion
@@ -0,0 +1,157 @@
+//! Advancement on character sheets - Locked fields and gaining levels
+//!
+//! A progression table can hold sheet fields back until a level is reached.
+//! Locked fields keep whatever value they had; gaining the level fills them
+//! in with the template's defaults and updates the sheet's level field.
+
+use crate::domain::value_objects::{ProgressionLevel, ProgressionTable, RuleSystemConfig};
+
+use super::sheet_template::SheetFieldError;
+use super::{CharacterSheetData, CharacterSheetTemplate, FieldValue, InvalidSheetData, PlayerCharacter};
+
+impl CharacterSheetTemplate {
+    /// The progression characters on this template follow: the template's
+    /// own, else the rule system's
+    pub fn progression_for(&self, rule_system: &RuleSystemConfig) -> Option<ProgressionTable> {
+        self.progression.clone().or_else(|| rule_system.progression())
+    }
+
+    /// The level a sheet's level field records, if the template has one
+    pub fn sheet_level(&self, data: &CharacterSheetData, table: &ProgressionTable) -> Option<u32> {
+        let level_field = table.level_field.as_deref()?;
+        self.get_field(level_field)?;
+        data.get_number(level_field).and_then(|level| u32::try_from(level).ok())
+    }
+
+    /// The level a character is at: the approved level, or the sheet's level
+    /// field for characters levelled before advancement was tracked
+    pub fn character_level(&self, pc: &PlayerCharacter, table: &ProgressionTable) -> u32 {
+        pc.sheet_data
+            .as_ref()
+            .and_then(|data| self.sheet_level(data, table))
+            .map_or(pc.advancement.level, |level| level.max(pc.advancement.level))
+    }
+
+    /// Check that a sheet edit leaves the fields still locked at `level`
+    /// unchanged
+    ///
+    /// `previous` is the sheet before the edit (None for a new character).
+    /// Fields the template does not have are ignored.
+    pub fn check_locked_fields(
+        &self,
+        table: &ProgressionTable,
+        level: u32,
+        data: &CharacterSheetData,
+        previous: Option<&CharacterSheetData>,
+    ) -> Result<(), InvalidSheetData> {
+        let errors: Vec<SheetFieldError> = table
+            .locked_fields(level)
+            .into_iter()
+            .filter(|(field_id, _)| self.get_field(field_id).is_some())
+            .filter(|(field_id, _)| data.get(field_id) != previous.and_then(|p| p.get(field_id)))
+            .map(|(field_id, unlocked_at)| SheetFieldError {
+                field_id: field_id.to_string(),
+                message: format!("unlocks at level {}", unlocked_at),
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidSheetData(errors))
+        }
+    }
+
+    /// Raise a sheet to a level: record it in the level field and fill in
+    /// the fields it unlocks
+    ///
+    /// Unlocked fields that already hold a value keep it. Returns the names
+    /// of the fields the level unlocked.
+    pub fn gain_level(
+        &self,
+        data: &mut CharacterSheetData,
+        table: &ProgressionTable,
+        level: &ProgressionLevel,
+    ) -> Vec<String> {
+        if let Some(level_field) = &table.level_field {
+            if self.get_field(level_field).is_some() {
+                data.set(level_field.clone(), FieldValue::Number(level.level as i32));
+            }
+        }
+
+        level
+            .unlocks_fields
+            .iter()
+            .filter_map(|field_id| {
+                let field = self.get_field(field_id)?;
+                if data.get(field_id).is_none() {
+                    if let Some(value) = field.field_type.default_value() {
+                        data.set(field_id.clone(), value);
+                    }
+                }
+                Some(field.name.clone())
+            })
+            .collect()
+    }
+}
+
+impl CharacterSheetData {
+    /// Make a character proficient in a skill, keeping any bonus it already
+    /// has; returns false if it already was
+    pub fn grant_proficiency(&mut self, skill_id: &str) -> bool {
+        let bonus = match self.get(skill_id) {
+            Some(FieldValue::SkillEntry { proficient: true, .. }) => return false,
+            Some(FieldValue::SkillEntry { bonus, .. }) => *bonus,
+            _ => 0,
+        };
+        self.set(
+            skill_id,
+            FieldValue::SkillEntry {
+                skill_id: skill_id.to_string(),
+                proficient: true,
+                bonus,
+            },
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{FieldType, SheetField, SheetSection};
+    use crate::domain::value_objects::{AdvancementMode, RuleSystemVariant, WorldId};
+
+    #[test]
+    fn test_locked_fields_open_with_levels() {
+        let template = CharacterSheetTemplate::new(WorldId::new(), "Sheet", RuleSystemVariant::GenericD20).with_section(
+            SheetSection::new("core", "Core")
+                .with_field(SheetField::new("LEVEL", "Level", FieldType::Number { min: Some(1), max: None, default: Some(1) }))
+                .with_field(SheetField::new("KI", "Ki Points", FieldType::Resource { max_field: None, default_max: Some(2) })),
+        );
+        let table = ProgressionTable::from_thresholds(AdvancementMode::Experience, &[0, 300])
+            .with_level_field("LEVEL")
+            .with_level(ProgressionLevel {
+                unlocks_fields: vec!["KI".to_string(), "MISSING".to_string()],
+                ..ProgressionLevel::new(2, 300)
+            });
+
+        let mut data = CharacterSheetData::new();
+        data.set("LEVEL", FieldValue::Number(1));
+        let mut edited = data.clone();
+        edited.set("KI", FieldValue::Resource { current: 5, max: 5 });
+        let errors = template.check_locked_fields(&table, 1, &edited, Some(&data)).unwrap_err();
+        assert_eq!(errors.0[0].field_id, "KI");
+        assert_eq!(errors.0[0].message, "unlocks at level 2");
+        assert!(template.check_locked_fields(&table, 1, &data, None).is_ok());
+
+        let unlocked = template.gain_level(&mut data, &table, &table.levels[1]);
+        assert_eq!(unlocked, vec!["Ki Points".to_string()]);
+        assert_eq!(data.get("LEVEL"), Some(&FieldValue::Number(2)));
+        assert_eq!(data.get("KI"), Some(&FieldValue::Resource { current: 2, max: 2 }));
+        assert!(template.check_locked_fields(&table, 2, &edited, Some(&data)).is_ok());
+
+        assert!(data.grant_proficiency("arcana"));
+        assert!(!data.grant_proficiency("arcana"));
+    }
+}
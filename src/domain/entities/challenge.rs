@@ -15,7 +15,11 @@
 //! The embedded fields `scene_id`, `skill_id`, and `prerequisite_challenges` are
 //! DEPRECATED and kept only for backward compatibility during migration.
 
-use crate::domain::value_objects::{ChallengeId, LocationId, SceneId, SkillId, WorldId};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::{
+    ChallengeId, DescriptorDifficulties, DescriptorTarget, LocationId, SceneId, SkillId, WorldId,
+};
 
 /// A challenge that can be triggered during gameplay
 ///
@@ -44,6 +48,8 @@ pub struct Challenge {
     pub is_favorite: bool,
     /// Tags for filtering
     pub tags: Vec<String>,
+    /// Success and failure limits for complex (multi-roll) challenges
+    pub complex_settings: Option<ComplexChallengeSettings>,
 }
 
 impl Challenge {
@@ -69,6 +75,7 @@ impl Challenge {
             order: 0,
             is_favorite: false,
             tags: Vec::new(),
+            complex_settings: None,
         }
     }
 
@@ -97,10 +104,28 @@ impl Challenge {
         self
     }
 
+    pub fn with_complex_settings(mut self, settings: ComplexChallengeSettings) -> Self {
+        self.complex_settings = Some(settings);
+        self
+    }
+
     /// Check if a trigger condition matches some player action/context
     pub fn matches_trigger(&self, action: &str, context: &str) -> bool {
         self.trigger_conditions.iter().any(|tc| tc.matches(action, context))
     }
+
+    /// Whether the roll is compared against an opponent's roll
+    pub fn is_opposed(&self) -> bool {
+        self.challenge_type == ChallengeType::OpposedCheck || self.difficulty == Difficulty::Opposed
+    }
+
+    /// Settings for a complex challenge, defaulted when none were saved
+    ///
+    /// Returns None for single-roll challenges.
+    pub fn complex(&self) -> Option<ComplexChallengeSettings> {
+        (self.challenge_type == ChallengeType::ComplexChallenge)
+            .then(|| self.complex_settings.clone().unwrap_or_default())
+    }
 }
 
 /// Types of challenges
@@ -169,6 +194,31 @@ impl Difficulty {
         }
     }
 
+    /// Describe the difficulty with what a descriptor means in the world's
+    /// rule system (e.g. "Hard (DC 20)")
+    pub fn describe(&self, descriptors: &DescriptorDifficulties) -> String {
+        match self {
+            Self::Descriptor(d) => format!("{} ({})", d.display_name(), d.target(descriptors).display()),
+            _ => self.display(),
+        }
+    }
+
+    /// Replace a descriptor with the DC or percentage the rule system maps it to
+    ///
+    /// Descriptors mapped to position and effect stay as they are: the roll is
+    /// graded by the system's bands rather than against a number.
+    pub fn resolve(&self, descriptors: &DescriptorDifficulties) -> Self {
+        match self {
+            Self::Descriptor(d) => match d.target(descriptors) {
+                DescriptorTarget::Dc(dc) => Self::DC(dc),
+                DescriptorTarget::Percentage(p) => Self::Percentage(p),
+                DescriptorTarget::Ladder(rung) => Self::DC(rung.max(0) as u32),
+                DescriptorTarget::PositionEffect { .. } => self.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+
     /// Standard D20 difficulty presets
     pub fn d20_easy() -> Self { Self::DC(10) }
     pub fn d20_medium() -> Self { Self::DC(15) }
@@ -214,6 +264,23 @@ impl DifficultyDescriptor {
             Self::Desperate => "Desperate",
         }
     }
+
+    /// What this descriptor means under a rule system's mapping
+    pub fn target(&self, descriptors: &DescriptorDifficulties) -> DescriptorTarget {
+        match self {
+            Self::Trivial => descriptors.trivial,
+            Self::Easy => descriptors.easy,
+            Self::Routine => descriptors.routine,
+            Self::Moderate => descriptors.moderate,
+            Self::Challenging => descriptors.challenging,
+            Self::Hard => descriptors.hard,
+            Self::VeryHard => descriptors.very_hard,
+            Self::Extreme => descriptors.extreme,
+            Self::Impossible => descriptors.impossible,
+            Self::Risky => descriptors.risky,
+            Self::Desperate => descriptors.desperate,
+        }
+    }
 }
 
 /// Outcomes for a challenge
@@ -310,6 +377,11 @@ pub enum OutcomeTrigger {
         item_name: String,
         item_description: Option<String>,
     },
+    /// Grant the character who rolled experience, milestones or another reward
+    AddReward {
+        reward_type: String,
+        amount: i32,
+    },
     /// Custom trigger with free-text description
     Custom {
         description: String,
@@ -512,7 +584,8 @@ pub struct ChallengeResult {
 }
 
 /// Type of outcome achieved
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum OutcomeType {
     CriticalSuccess,
     Success,
@@ -561,6 +634,24 @@ impl Default for ComplexChallengeSettings {
     }
 }
 
+impl ComplexChallengeSettings {
+    /// Whether a skill may be rolled for a round of the challenge
+    ///
+    /// The challenge's required skill is always allowed. With flexible skills,
+    /// any of the allowed skills may be chosen instead (any skill at all when
+    /// none are listed).
+    pub fn allows_skill(&self, skill_id: &SkillId, required_skill: Option<&SkillId>) -> bool {
+        match required_skill {
+            Some(required) if required == skill_id => true,
+            None if !self.flexible_skills => true,
+            _ => {
+                self.flexible_skills
+                    && (self.allowed_skills.is_empty() || self.allowed_skills.contains(skill_id))
+            }
+        }
+    }
+}
+
 // =============================================================================
 // Edge Support Structs (Graph-First Design)
 // =============================================================================
@@ -669,6 +760,23 @@ mod tests {
         assert_eq!(Difficulty::Descriptor(DifficultyDescriptor::Hard).display(), "Hard");
     }
 
+    #[test]
+    fn test_descriptor_resolves_per_rule_system() {
+        let hard = Difficulty::Descriptor(DifficultyDescriptor::Hard);
+        assert_eq!(hard.resolve(&DescriptorDifficulties::d20()), Difficulty::DC(20));
+        assert_eq!(hard.resolve(&DescriptorDifficulties::percentile()), Difficulty::Percentage(25));
+        assert_eq!(hard.resolve(&DescriptorDifficulties::fate_ladder()), Difficulty::DC(4));
+        assert_eq!(hard.resolve(&DescriptorDifficulties::position_effect()), hard);
+        assert_eq!(Difficulty::DC(12).resolve(&DescriptorDifficulties::percentile()), Difficulty::DC(12));
+
+        assert_eq!(hard.describe(&DescriptorDifficulties::pathfinder()), "Hard (DC 25)");
+        assert_eq!(hard.describe(&DescriptorDifficulties::fate_ladder()), "Hard (Great (+4))");
+        assert_eq!(
+            Difficulty::Descriptor(DifficultyDescriptor::Risky).describe(&DescriptorDifficulties::position_effect()),
+            "Risky (risky position, standard effect)"
+        );
+    }
+
     #[test]
     fn test_outcome_triggers() {
         let outcome = Outcome::new("You discover a secret passage!")
@@ -677,4 +785,29 @@ mod tests {
 
         assert_eq!(outcome.triggers.len(), 2);
     }
+
+    #[test]
+    fn test_complex_settings_allowed_skills() {
+        let required = SkillId::new();
+        let listed = SkillId::new();
+        let other = SkillId::new();
+
+        let fixed = ComplexChallengeSettings::default();
+        assert!(fixed.allows_skill(&required, Some(&required)));
+        assert!(!fixed.allows_skill(&listed, Some(&required)));
+
+        let flexible = ComplexChallengeSettings {
+            flexible_skills: true,
+            allowed_skills: vec![listed],
+            ..Default::default()
+        };
+        assert!(flexible.allows_skill(&required, Some(&required)));
+        assert!(flexible.allows_skill(&listed, Some(&required)));
+        assert!(!flexible.allows_skill(&other, Some(&required)));
+
+        let challenge = Challenge::new(WorldId::new(), "Chase", Difficulty::DC(12));
+        assert!(challenge.complex().is_none());
+        let challenge = challenge.with_challenge_type(ChallengeType::ComplexChallenge);
+        assert_eq!(challenge.complex().unwrap().required_successes, 3);
+    }
 }
@@ -10,7 +10,9 @@
 //!
 //! Archetype history remains as JSON (acceptable per ADR - complex nested non-relational)
 
-use crate::domain::value_objects::{ArchetypeChange, CampbellArchetype, CharacterId, WorldId};
+use crate::domain::value_objects::{
+    ActiveConditions, ArchetypeChange, CampbellArchetype, CharacterId, WorldId,
+};
 
 /// A character (NPC) in the world
 #[derive(Debug, Clone)]
@@ -34,6 +36,8 @@ pub struct Character {
 
     // Game Stats (stored as JSON - acceptable per ADR)
     pub stats: StatBlock,
+    /// Conditions currently affecting the character (stored as JSON)
+    pub conditions: ActiveConditions,
 
     // Character state
     pub is_alive: bool,
@@ -53,6 +57,7 @@ impl Character {
             current_archetype: archetype,
             archetype_history: Vec::new(),
             stats: StatBlock::default(),
+            conditions: ActiveConditions::new(),
             is_alive: true,
             is_active: true,
         }
@@ -135,4 +140,26 @@ impl StatBlock {
     pub fn set_stat(&mut self, name: impl Into<String>, value: i32) {
         self.stats.insert(name.into(), value);
     }
+
+    /// Modifier the character adds to a check with a skill
+    ///
+    /// A stat named after the skill is used as the modifier directly. Failing
+    /// that the skill's base attribute is used, converted to a d20 ability
+    /// modifier when `ability_scores` is set. Names match case-insensitively.
+    pub fn check_modifier(&self, skill_name: &str, base_attribute: Option<&str>, ability_scores: bool) -> i32 {
+        let find = |name: &str| {
+            self.stats
+                .iter()
+                .find(|(stat, _)| stat.eq_ignore_ascii_case(name))
+                .map(|(_, value)| *value)
+        };
+        if let Some(value) = find(skill_name) {
+            return value;
+        }
+        match base_attribute.and_then(find) {
+            Some(score) if ability_scores => (score - 10).div_euclid(2),
+            Some(value) => value,
+            None => 0,
+        }
+    }
 }
@@ -0,0 +1,162 @@
+//! Complex challenge progress
+//!
+//! A complex challenge ("6 successes before 3 failures") is worked through one
+//! roll at a time, possibly by several player characters and across game
+//! sessions. Its progress is kept until the challenge succeeds or fails, and
+//! can be cleared to attempt it again.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ComplexChallengeSettings, OutcomeType};
+use crate::domain::value_objects::{ChallengeId, SkillId};
+
+/// Where a complex challenge stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplexChallengeStatus {
+    InProgress,
+    /// Reached the required number of successes
+    Succeeded,
+    /// Ran out of allowed failures
+    Failed,
+}
+
+impl ComplexChallengeStatus {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::InProgress => "In Progress",
+            Self::Succeeded => "Succeeded",
+            Self::Failed => "Failed",
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !matches!(self, Self::InProgress)
+    }
+}
+
+/// One round's roll towards a complex challenge
+#[derive(Debug, Clone)]
+pub struct ComplexChallengeRoll {
+    pub character_id: String,
+    pub character_name: String,
+    /// Skill the character chose to roll this round
+    pub skill_id: Option<SkillId>,
+    pub total: i32,
+    pub outcome_type: OutcomeType,
+    pub rolled_at: DateTime<Utc>,
+}
+
+/// Accumulated successes and failures on a complex challenge
+#[derive(Debug, Clone)]
+pub struct ComplexChallengeProgress {
+    pub challenge_id: ChallengeId,
+    pub successes: u32,
+    pub failures: u32,
+    pub status: ComplexChallengeStatus,
+    /// Every roll made towards the challenge, oldest first
+    pub rolls: Vec<ComplexChallengeRoll>,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ComplexChallengeProgress {
+    pub fn new(challenge_id: ChallengeId) -> Self {
+        let now = Utc::now();
+        Self {
+            challenge_id,
+            successes: 0,
+            failures: 0,
+            status: ComplexChallengeStatus::InProgress,
+            rolls: Vec::new(),
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Count a roll towards the challenge and return the new status
+    ///
+    /// Partial successes count as successes; critical results count once like
+    /// any other. Rolls made after the challenge is complete are ignored.
+    pub fn record(
+        &mut self,
+        settings: &ComplexChallengeSettings,
+        roll: ComplexChallengeRoll,
+    ) -> ComplexChallengeStatus {
+        if self.status.is_complete() {
+            return self.status;
+        }
+
+        match roll.outcome_type {
+            OutcomeType::CriticalSuccess | OutcomeType::Success | OutcomeType::Partial => {
+                self.successes += 1
+            }
+            OutcomeType::Failure | OutcomeType::CriticalFailure => self.failures += 1,
+        }
+        self.updated_at = roll.rolled_at;
+        self.rolls.push(roll);
+
+        self.status = if self.successes >= settings.required_successes.max(1) {
+            ComplexChallengeStatus::Succeeded
+        } else if settings.max_failures > 0 && self.failures >= settings.max_failures {
+            ComplexChallengeStatus::Failed
+        } else {
+            ComplexChallengeStatus::InProgress
+        };
+        self.status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roll(outcome_type: OutcomeType) -> ComplexChallengeRoll {
+        ComplexChallengeRoll {
+            character_id: "pc".to_string(),
+            character_name: "Ayla".to_string(),
+            skill_id: None,
+            total: 12,
+            outcome_type,
+            rolled_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_successes_before_failures() {
+        let settings = ComplexChallengeSettings {
+            required_successes: 2,
+            max_failures: 2,
+            ..Default::default()
+        };
+        let mut progress = ComplexChallengeProgress::new(ChallengeId::new());
+
+        assert_eq!(progress.record(&settings, roll(OutcomeType::Failure)), ComplexChallengeStatus::InProgress);
+        assert_eq!(progress.record(&settings, roll(OutcomeType::Partial)), ComplexChallengeStatus::InProgress);
+        assert_eq!(
+            progress.record(&settings, roll(OutcomeType::CriticalSuccess)),
+            ComplexChallengeStatus::Succeeded
+        );
+
+        // Further rolls don't change a finished challenge
+        assert_eq!(progress.record(&settings, roll(OutcomeType::Failure)), ComplexChallengeStatus::Succeeded);
+        assert_eq!((progress.successes, progress.failures), (2, 1));
+        assert_eq!(progress.rolls.len(), 3);
+    }
+
+    #[test]
+    fn test_unlimited_failures_never_fail() {
+        let settings = ComplexChallengeSettings {
+            required_successes: 1,
+            max_failures: 0,
+            ..Default::default()
+        };
+        let mut progress = ComplexChallengeProgress::new(ChallengeId::new());
+        for _ in 0..10 {
+            progress.record(&settings, roll(OutcomeType::CriticalFailure));
+        }
+        assert_eq!(progress.status, ComplexChallengeStatus::InProgress);
+        assert_eq!(progress.failures, 10);
+    }
+}
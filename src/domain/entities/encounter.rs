@@ -0,0 +1,573 @@
+//! Combat encounters - Roster, initiative and turn order
+//!
+//! An encounter runs in rounds. Every combatant rolls initiative (or takes
+//! its place by stat, in systems that do not roll) before the first round;
+//! turns then pass down the order, skipping anyone defeated, fled or
+//! delaying. A combatant can delay, re-entering the order after any other
+//! turn, or ready an action that lapses when its next turn comes round.
+//! Hit points are mirrored from the sheet or stat block they are kept on.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use super::CombatOutcome;
+use crate::domain::value_objects::{CharacterId, LocationId, PlayerCharacterId, SessionId, WorldId};
+
+/// Which character a combatant is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CombatantRef {
+    Pc(PlayerCharacterId),
+    Npc(CharacterId),
+}
+
+impl CombatantRef {
+    /// The combatant as a character on the story timeline
+    pub fn character_id(&self) -> CharacterId {
+        match self {
+            Self::Pc(id) => CharacterId::from_uuid(*id.as_uuid()),
+            Self::Npc(id) => *id,
+        }
+    }
+}
+
+impl fmt::Display for CombatantRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pc(id) => write!(f, "{}", id),
+            Self::Npc(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// The side a combatant fights on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatSide {
+    /// The player characters and their allies
+    Party,
+    Enemy,
+}
+
+/// Whether a combatant is still taking turns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombatantStatus {
+    Active,
+    /// Out of the turn order until it chooses to act
+    Delaying,
+    /// Holding an action for a trigger until its next turn
+    Readied(String),
+    Defeated,
+    Fled,
+}
+
+impl CombatantStatus {
+    /// Whether the combatant is still in the fight
+    pub fn is_standing(&self) -> bool {
+        !matches!(self, Self::Defeated | Self::Fled)
+    }
+}
+
+/// One character in an encounter
+#[derive(Debug, Clone)]
+pub struct Combatant {
+    pub who: CombatantRef,
+    pub name: String,
+    pub side: CombatSide,
+    /// Added to the initiative roll; breaks ties
+    pub initiative_modifier: i32,
+    /// None until initiative is rolled
+    pub initiative: Option<i32>,
+    pub current_hp: Option<i32>,
+    pub max_hp: Option<i32>,
+    pub status: CombatantStatus,
+    /// Whether the LLM proposes this combatant's turns for the DM to approve
+    pub llm_controlled: bool,
+}
+
+impl Combatant {
+    pub fn new(who: CombatantRef, name: impl Into<String>, side: CombatSide) -> Self {
+        Self {
+            who,
+            name: name.into(),
+            side,
+            initiative_modifier: 0,
+            initiative: None,
+            current_hp: None,
+            max_hp: None,
+            status: CombatantStatus::Active,
+            llm_controlled: false,
+        }
+    }
+
+    pub fn with_initiative_modifier(mut self, modifier: i32) -> Self {
+        self.initiative_modifier = modifier;
+        self
+    }
+
+    pub fn with_hp(mut self, current: Option<i32>, max: Option<i32>) -> Self {
+        self.current_hp = current;
+        self.max_hp = max;
+        self
+    }
+
+    pub fn llm_controlled(mut self) -> Self {
+        self.llm_controlled = true;
+        self
+    }
+
+    fn can_act(&self) -> bool {
+        matches!(self.status, CombatantStatus::Active | CombatantStatus::Readied(_))
+    }
+}
+
+/// Why an encounter refused a change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncounterError {
+    /// Nobody in the encounter is this character
+    UnknownCombatant(String),
+    /// The character is already in the encounter
+    AlreadyInEncounter(String),
+    /// Combatants still waiting on their initiative
+    InitiativeMissing(Vec<String>),
+    NoCombatants,
+    /// Rounds have already begun
+    AlreadyStarted,
+    /// Rounds have not begun yet
+    NotStarted,
+    /// The character is not delaying
+    NotDelaying(String),
+    /// The character has no readied action
+    NotReadied(String),
+}
+
+impl fmt::Display for EncounterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCombatant(who) => write!(f, "{} is not in the encounter", who),
+            Self::AlreadyInEncounter(name) => write!(f, "{} is already in the encounter", name),
+            Self::InitiativeMissing(names) => {
+                write!(f, "Waiting on initiative for {}", names.join(", "))
+            }
+            Self::NoCombatants => write!(f, "The encounter has no combatants"),
+            Self::AlreadyStarted => write!(f, "Combat has already begun"),
+            Self::NotStarted => write!(f, "Combat has not begun"),
+            Self::NotDelaying(name) => write!(f, "{} is not delaying", name),
+            Self::NotReadied(name) => write!(f, "{} has no readied action", name),
+        }
+    }
+}
+
+impl std::error::Error for EncounterError {}
+
+/// Where the turn moved to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TurnChange {
+    pub round: u32,
+    /// Index in the turn order of the combatant whose turn it is
+    pub turn: usize,
+    /// The round that just finished, if the turn wrapped into a new one
+    pub completed_round: Option<u32>,
+}
+
+/// A fight in a session
+#[derive(Debug, Clone)]
+pub struct Encounter {
+    pub session_id: SessionId,
+    pub world_id: WorldId,
+    pub location_id: LocationId,
+    pub description: String,
+    /// In turn order once rounds begin
+    pub combatants: Vec<Combatant>,
+    /// 0 until rounds begin
+    pub round: u32,
+    /// Index of the combatant whose turn it is
+    pub turn: usize,
+    pub started_at: DateTime<Utc>,
+}
+
+impl Encounter {
+    pub fn new(
+        session_id: SessionId,
+        world_id: WorldId,
+        location_id: LocationId,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            session_id,
+            world_id,
+            location_id,
+            description: description.into(),
+            combatants: Vec::new(),
+            round: 0,
+            turn: 0,
+            started_at: Utc::now(),
+        }
+    }
+
+    /// Whether rounds have begun
+    pub fn has_begun(&self) -> bool {
+        self.round > 0
+    }
+
+    /// Add a combatant; once rounds have begun it joins in its initiative
+    /// place, or at the end of the order if it has none
+    pub fn add(&mut self, combatant: Combatant) -> Result<(), EncounterError> {
+        if self.get(combatant.who).is_some() {
+            return Err(EncounterError::AlreadyInEncounter(combatant.name));
+        }
+        if !self.has_begun() {
+            self.combatants.push(combatant);
+            return Ok(());
+        }
+
+        let position = combatant.initiative.and_then(|initiative| {
+            self.combatants
+                .iter()
+                .position(|c| c.initiative.is_some_and(|other| other < initiative))
+        });
+        let position = position.unwrap_or(self.combatants.len());
+        if position <= self.turn {
+            self.turn += 1;
+        }
+        self.combatants.insert(position, combatant);
+        Ok(())
+    }
+
+    pub fn get(&self, who: CombatantRef) -> Option<&Combatant> {
+        self.combatants.iter().find(|c| c.who == who)
+    }
+
+    /// The combatant with this character ID, PC or NPC
+    pub fn find(&self, id: &str) -> Result<&Combatant, EncounterError> {
+        self.combatants
+            .iter()
+            .find(|c| c.who.to_string() == id)
+            .ok_or_else(|| EncounterError::UnknownCombatant(id.to_string()))
+    }
+
+    fn get_mut(&mut self, who: CombatantRef) -> Result<&mut Combatant, EncounterError> {
+        self.combatants
+            .iter_mut()
+            .find(|c| c.who == who)
+            .ok_or_else(|| EncounterError::UnknownCombatant(who.to_string()))
+    }
+
+    /// The combatant whose turn it is
+    pub fn current(&self) -> Option<&Combatant> {
+        if self.has_begun() {
+            self.combatants.get(self.turn)
+        } else {
+            None
+        }
+    }
+
+    /// Record a combatant's initiative
+    pub fn set_initiative(&mut self, who: CombatantRef, initiative: i32) -> Result<(), EncounterError> {
+        self.get_mut(who)?.initiative = Some(initiative);
+        Ok(())
+    }
+
+    /// Combatants still to roll initiative
+    pub fn awaiting_initiative(&self) -> impl Iterator<Item = &Combatant> {
+        self.combatants.iter().filter(|c| c.initiative.is_none())
+    }
+
+    /// Sort the roster into turn order and start the first round
+    ///
+    /// Higher initiative goes first; ties go to the higher modifier, then
+    /// to the party.
+    pub fn begin(&mut self) -> Result<TurnChange, EncounterError> {
+        if self.has_begun() {
+            return Err(EncounterError::AlreadyStarted);
+        }
+        if self.combatants.is_empty() {
+            return Err(EncounterError::NoCombatants);
+        }
+        let missing: Vec<String> = self.awaiting_initiative().map(|c| c.name.clone()).collect();
+        if !missing.is_empty() {
+            return Err(EncounterError::InitiativeMissing(missing));
+        }
+
+        self.combatants.sort_by(|a, b| {
+            b.initiative
+                .cmp(&a.initiative)
+                .then(b.initiative_modifier.cmp(&a.initiative_modifier))
+                .then((a.side == CombatSide::Enemy).cmp(&(b.side == CombatSide::Enemy)))
+        });
+        self.round = 1;
+        self.turn = self.combatants.iter().position(Combatant::can_act).unwrap_or(0);
+        Ok(TurnChange {
+            round: self.round,
+            turn: self.turn,
+            completed_round: None,
+        })
+    }
+
+    /// Pass the turn to the next combatant able to act
+    ///
+    /// A readied action lapses when its holder's turn comes round. Returns
+    /// None when nobody is left to act.
+    pub fn next_turn(&mut self) -> Result<Option<TurnChange>, EncounterError> {
+        if !self.has_begun() {
+            return Err(EncounterError::NotStarted);
+        }
+        let count = self.combatants.len();
+        let mut completed_round = None;
+        for step in 1..=count {
+            let index = (self.turn + step) % count;
+            if index <= self.turn && completed_round.is_none() {
+                completed_round = Some(self.round);
+            }
+            if self.combatants[index].can_act() {
+                if let Some(round) = completed_round {
+                    self.round = round + 1;
+                }
+                self.turn = index;
+                self.combatants[index].status = CombatantStatus::Active;
+                return Ok(Some(TurnChange {
+                    round: self.round,
+                    turn: self.turn,
+                    completed_round,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The current combatant delays, leaving the order until it acts
+    pub fn delay(&mut self) -> Result<Option<TurnChange>, EncounterError> {
+        if !self.has_begun() {
+            return Err(EncounterError::NotStarted);
+        }
+        self.combatants[self.turn].status = CombatantStatus::Delaying;
+        self.next_turn()
+    }
+
+    /// The current combatant readies an action for a trigger and ends its
+    /// turn
+    pub fn ready(&mut self, trigger: impl Into<String>) -> Result<Option<TurnChange>, EncounterError> {
+        if !self.has_begun() {
+            return Err(EncounterError::NotStarted);
+        }
+        self.combatants[self.turn].status = CombatantStatus::Readied(trigger.into());
+        self.next_turn()
+    }
+
+    /// A delaying combatant re-enters the order straight after the current
+    /// turn and takes its turn; that place in the order is kept from then on
+    pub fn act_now(&mut self, who: CombatantRef) -> Result<TurnChange, EncounterError> {
+        if !self.has_begun() {
+            return Err(EncounterError::NotStarted);
+        }
+        let index = self
+            .combatants
+            .iter()
+            .position(|c| c.who == who)
+            .ok_or_else(|| EncounterError::UnknownCombatant(who.to_string()))?;
+        if self.combatants[index].status != CombatantStatus::Delaying {
+            return Err(EncounterError::NotDelaying(self.combatants[index].name.clone()));
+        }
+
+        // When everyone left delayed, the turn stayed with the last of them,
+        // who then simply acts in their own place
+        if index != self.turn {
+            let mut combatant = self.combatants.remove(index);
+            if index < self.turn {
+                self.turn -= 1;
+            }
+            combatant.status = CombatantStatus::Active;
+            self.turn += 1;
+            self.combatants.insert(self.turn, combatant);
+        } else {
+            self.combatants[index].status = CombatantStatus::Active;
+        }
+        Ok(TurnChange {
+            round: self.round,
+            turn: self.turn,
+            completed_round: None,
+        })
+    }
+
+    /// A combatant's readied action is triggered, spending it
+    ///
+    /// Returns the trigger it was waiting for.
+    pub fn trigger_readied(&mut self, who: CombatantRef) -> Result<String, EncounterError> {
+        let combatant = self.get_mut(who)?;
+        match std::mem::replace(&mut combatant.status, CombatantStatus::Active) {
+            CombatantStatus::Readied(trigger) => Ok(trigger),
+            other => {
+                combatant.status = other;
+                Err(EncounterError::NotReadied(combatant.name.clone()))
+            }
+        }
+    }
+
+    /// Record a combatant's hit points; dropping to 0 defeats it
+    ///
+    /// Returns true if this defeated the combatant.
+    pub fn record_hp(&mut self, who: CombatantRef, current: i32, max: Option<i32>) -> Result<bool, EncounterError> {
+        let combatant = self.get_mut(who)?;
+        combatant.current_hp = Some(current);
+        if max.is_some() {
+            combatant.max_hp = max;
+        }
+        if current <= 0 && combatant.status.is_standing() {
+            combatant.status = CombatantStatus::Defeated;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Take a combatant out of the fight, defeated or fled
+    pub fn remove_from_fight(&mut self, who: CombatantRef, fled: bool) -> Result<(), EncounterError> {
+        self.get_mut(who)?.status = if fled {
+            CombatantStatus::Fled
+        } else {
+            CombatantStatus::Defeated
+        };
+        Ok(())
+    }
+
+    /// How the fight has gone, once one side has nobody left standing
+    pub fn outcome(&self) -> Option<CombatOutcome> {
+        let standing = |side: CombatSide| {
+            self.combatants
+                .iter()
+                .any(|c| c.side == side && c.status.is_standing())
+        };
+        if !standing(CombatSide::Party) {
+            let fled = self
+                .combatants
+                .iter()
+                .any(|c| c.side == CombatSide::Party && c.status == CombatantStatus::Fled);
+            Some(if fled { CombatOutcome::Fled } else { CombatOutcome::Defeat })
+        } else if !standing(CombatSide::Enemy) {
+            Some(CombatOutcome::Victory)
+        } else {
+            None
+        }
+    }
+
+    /// The party's characters, for the story timeline
+    pub fn participants(&self) -> Vec<CharacterId> {
+        self.combatants
+            .iter()
+            .filter(|c| c.side == CombatSide::Party)
+            .map(|c| c.who.character_id())
+            .collect()
+    }
+
+    /// The names of the party's foes
+    pub fn enemies(&self) -> Vec<String> {
+        self.combatants
+            .iter()
+            .filter(|c| c.side == CombatSide::Enemy)
+            .map(|c| c.name.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npc(name: &str, side: CombatSide, initiative: i32) -> Combatant {
+        let mut combatant = Combatant::new(CombatantRef::Npc(CharacterId::new()), name, side);
+        combatant.initiative = Some(initiative);
+        combatant
+    }
+
+    fn encounter(combatants: Vec<Combatant>) -> Encounter {
+        let mut encounter = Encounter::new(SessionId::new(), WorldId::new(), LocationId::new(), "Ambush");
+        for combatant in combatants {
+            encounter.add(combatant).unwrap();
+        }
+        encounter
+    }
+
+    fn names(encounter: &Encounter) -> Vec<&str> {
+        encounter.combatants.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_turns_follow_initiative_and_wrap_rounds() {
+        let mut fight = encounter(vec![
+            npc("Goblin", CombatSide::Enemy, 12),
+            npc("Ranger", CombatSide::Party, 18),
+            npc("Wolf", CombatSide::Enemy, 12).with_initiative_modifier(3),
+        ]);
+        fight.combatants.push(Combatant::new(CombatantRef::Npc(CharacterId::new()), "Late", CombatSide::Party));
+        assert_eq!(fight.begin(), Err(EncounterError::InitiativeMissing(vec!["Late".to_string()])));
+        fight.combatants.pop();
+
+        let first = fight.begin().unwrap();
+        assert_eq!(names(&fight), vec!["Ranger", "Wolf", "Goblin"]);
+        assert_eq!((first.round, first.turn), (1, 0));
+
+        let wolf = fight.combatants[1].who;
+        assert!(fight.record_hp(wolf, 0, Some(11)).unwrap());
+        let change = fight.next_turn().unwrap().unwrap();
+        assert_eq!(fight.current().unwrap().name, "Goblin");
+        assert_eq!(change.completed_round, None);
+
+        let change = fight.next_turn().unwrap().unwrap();
+        assert_eq!(change.completed_round, Some(1));
+        assert_eq!((change.round, fight.current().unwrap().name.as_str()), (2, "Ranger"));
+        assert_eq!(fight.outcome(), None);
+
+        let goblin = fight.combatants[2].who;
+        fight.remove_from_fight(goblin, true).unwrap();
+        assert_eq!(fight.outcome(), Some(CombatOutcome::Victory));
+    }
+
+    #[test]
+    fn test_delay_and_ready() {
+        let mut fight = encounter(vec![
+            npc("Rogue", CombatSide::Party, 20),
+            npc("Orc", CombatSide::Enemy, 15),
+            npc("Cleric", CombatSide::Party, 10),
+        ]);
+        fight.begin().unwrap();
+        let rogue = fight.combatants[0].who;
+
+        fight.delay().unwrap();
+        assert_eq!(fight.current().unwrap().name, "Orc");
+        fight.next_turn().unwrap();
+        assert_eq!(fight.current().unwrap().name, "Cleric");
+
+        // The rogue steps back in after the cleric, keeping that place
+        fight.act_now(rogue).unwrap();
+        assert_eq!(names(&fight), vec!["Orc", "Cleric", "Rogue"]);
+        assert_eq!(fight.current().unwrap().name, "Rogue");
+        assert_eq!(fight.act_now(rogue), Err(EncounterError::NotDelaying("Rogue".to_string())));
+
+        let change = fight.ready("the orc moves").unwrap().unwrap();
+        assert_eq!((change.round, fight.current().unwrap().name.as_str()), (2, "Orc"));
+        assert_eq!(fight.trigger_readied(rogue).unwrap(), "the orc moves");
+        assert!(fight.trigger_readied(rogue).is_err());
+
+        let cleric = fight.combatants[1].who;
+        fight.remove_from_fight(rogue, false).unwrap();
+        fight.remove_from_fight(cleric, true).unwrap();
+        assert_eq!(fight.outcome(), Some(CombatOutcome::Fled));
+    }
+
+    #[test]
+    fn test_act_now_when_everyone_delayed() {
+        let mut fight = encounter(vec![
+            npc("Rogue", CombatSide::Party, 20),
+            npc("Orc", CombatSide::Enemy, 15),
+        ]);
+        fight.begin().unwrap();
+        let orc = fight.combatants[1].who;
+
+        fight.delay().unwrap();
+        assert_eq!(fight.delay().unwrap(), None);
+        assert_eq!(fight.current().unwrap().name, "Orc");
+
+        let change = fight.act_now(orc).unwrap();
+        assert_eq!(change.turn, 1);
+        assert_eq!(names(&fight), vec!["Rogue", "Orc"]);
+        assert_eq!(fight.current().unwrap().status, CombatantStatus::Active);
+    }
+}
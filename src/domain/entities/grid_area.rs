@@ -0,0 +1,195 @@
+//! Areas of effect on grid maps - Spheres, cubes, cones and lines
+//!
+//! A template covers the tiles whose centres fall inside its shape, and
+//! spreads from a point of origin: a tile is only caught if a line of sight
+//! runs to it from there, so walls and high ground shelter what lies behind
+//! them. Walls themselves are never caught. Cones and lines start at their
+//! caster's tile and leave it out; spheres and cubes include their origin.
+
+use std::collections::HashSet;
+
+use super::{GridMap, GridPosition};
+
+/// The shape of an area of effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AreaTemplate {
+    /// Every tile within `radius` tiles of a centre tile
+    Sphere { center: GridPosition, radius: u32 },
+    /// A square `size` tiles across, spreading from its top-left tile
+    Cube { origin: GridPosition, size: u32 },
+    /// A cone `length` tiles long from a tile toward another, as wide at
+    /// any distance as it is far from the origin
+    Cone {
+        origin: GridPosition,
+        toward: GridPosition,
+        length: u32,
+    },
+    /// A line `length` tiles long and `width` tiles wide from a tile toward
+    /// another
+    Line {
+        origin: GridPosition,
+        toward: GridPosition,
+        length: u32,
+        width: u32,
+    },
+}
+
+impl AreaTemplate {
+    /// The tile the effect spreads from
+    pub fn origin(&self) -> GridPosition {
+        match *self {
+            Self::Sphere { center, .. } => center,
+            Self::Cube { origin, .. } | Self::Cone { origin, .. } | Self::Line { origin, .. } => {
+                origin
+            }
+        }
+    }
+
+    /// Whether a tile lies inside the shape, walls aside
+    ///
+    /// Sizes and directions come from clients unchecked, so the products
+    /// are widened and saturate rather than overflow.
+    fn covers(&self, (x, y): GridPosition) -> bool {
+        let (ox, oy) = self.origin();
+        let (vx, vy) = (i128::from(x) - i128::from(ox), i128::from(y) - i128::from(oy));
+        let square = |n: u32| i128::from(n).pow(2);
+        match *self {
+            Self::Sphere { radius, .. } => vx * vx + vy * vy <= square(radius),
+            Self::Cube { size, .. } => (0..i128::from(size)).contains(&vx) && (0..i128::from(size)).contains(&vy),
+            Self::Cone { toward, length, .. } => {
+                let (dx, dy) = direction((ox, oy), toward);
+                let along = vx * dx + vy * dy;
+                let distance = vx * vx + vy * vy;
+                // Half as wide as it is far: within atan(1/2) of the axis
+                along > 0
+                    && distance <= square(length)
+                    && along.saturating_mul(along).saturating_mul(5)
+                        >= (4 * distance).saturating_mul(dx * dx + dy * dy)
+            }
+            Self::Line {
+                toward,
+                length,
+                width,
+                ..
+            } => {
+                let (dx, dy) = direction((ox, oy), toward);
+                let heading = dx * dx + dy * dy;
+                let along = vx * dx + vy * dy;
+                let across = vx * dy - vy * dx;
+                along > 0
+                    && along.saturating_mul(along) <= square(length).saturating_mul(heading)
+                    && across.saturating_mul(across).saturating_mul(4)
+                        <= square(width).saturating_mul(heading)
+            }
+        }
+    }
+}
+
+/// The way from one tile to another; nowhere if they are the same
+fn direction(from: GridPosition, to: GridPosition) -> (i128, i128) {
+    (
+        i128::from(to.0) - i128::from(from.0),
+        i128::from(to.1) - i128::from(from.1),
+    )
+}
+
+impl GridMap {
+    /// The tiles an area of effect catches
+    pub fn area_tiles(&self, template: &AreaTemplate) -> HashSet<GridPosition> {
+        let origin = template.origin();
+        if !self.contains(origin.0, origin.1) {
+            return HashSet::new();
+        }
+
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&position| {
+                template.covers(position)
+                    && self
+                        .get_tile(position.0, position.1)
+                        .is_some_and(|tile| !tile.terrain_type.blocks_sight())
+                    && self.line_of_sight(origin, position)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{TerrainType, Tile};
+    use crate::domain::value_objects::WorldId;
+
+    #[test]
+    fn test_area_shapes() {
+        let map = GridMap::new(WorldId::new(), "Field", 11, 11, "field.png");
+
+        let sphere = map.area_tiles(&AreaTemplate::Sphere { center: (5, 5), radius: 1 });
+        assert_eq!(sphere.len(), 5);
+
+        let cube = map.area_tiles(&AreaTemplate::Cube { origin: (9, 9), size: 3 });
+        assert_eq!(cube.len(), 4);
+
+        let cone = map.area_tiles(&AreaTemplate::Cone {
+            origin: (5, 5),
+            toward: (6, 5),
+            length: 4,
+        });
+        assert!(cone.contains(&(6, 5)) && cone.contains(&(9, 5)) && cone.contains(&(8, 6)));
+        assert!(!cone.contains(&(5, 5)) && !cone.contains(&(6, 6)) && !cone.contains(&(8, 7)));
+        assert!(!cone.contains(&(4, 5)) && !cone.contains(&(9, 7)));
+
+        let line = map.area_tiles(&AreaTemplate::Line {
+            origin: (0, 0),
+            toward: (1, 1),
+            length: 3,
+            width: 1,
+        });
+        let mut line: Vec<_> = line.into_iter().collect();
+        line.sort();
+        assert_eq!(line, vec![(1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_walls_shelter_tiles_behind_them() {
+        let mut map = GridMap::new(WorldId::new(), "Cellar", 9, 5, "cellar.png");
+        for y in 0..5 {
+            map.set_tile(4, y, Tile::new(TerrainType::Wall, 1));
+        }
+
+        let fireball = map.area_tiles(&AreaTemplate::Sphere { center: (2, 2), radius: 4 });
+        assert!(fireball.contains(&(0, 0)) && fireball.contains(&(3, 4)));
+        assert!(!fireball.iter().any(|&(x, _)| x >= 4));
+
+        // Nothing happens where the origin is off the map
+        assert!(map
+            .area_tiles(&AreaTemplate::Sphere { center: (20, 2), radius: 30 })
+            .is_empty());
+    }
+
+    #[test]
+    fn test_huge_templates_cover_the_whole_map() {
+        let map = GridMap::new(WorldId::new(), "Field", 5, 5, "field.png");
+
+        let sphere = map.area_tiles(&AreaTemplate::Sphere { center: (2, 2), radius: u32::MAX });
+        assert_eq!(sphere.len(), 25);
+
+        let cube = map.area_tiles(&AreaTemplate::Cube { origin: (0, 0), size: u32::MAX });
+        assert_eq!(cube.len(), 25);
+
+        let cone = map.area_tiles(&AreaTemplate::Cone {
+            origin: (0, 2),
+            toward: (u32::MAX, 2),
+            length: u32::MAX,
+        });
+        assert!(cone.contains(&(4, 2)) && !cone.contains(&(0, 2)));
+
+        let line = map.area_tiles(&AreaTemplate::Line {
+            origin: (0, 0),
+            toward: (u32::MAX, u32::MAX),
+            length: u32::MAX,
+            width: u32::MAX,
+        });
+        assert!(line.contains(&(4, 4)) && line.contains(&(4, 1)));
+    }
+}
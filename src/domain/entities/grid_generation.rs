@@ -0,0 +1,510 @@
+//! Procedural grid maps - Dungeons, caves and outdoor battlemaps
+//!
+//! A `MapGenerator` lays out a map's tiles from a seed: the same seed, size
+//! and style always give the same tiles, so a generated map can be rebuilt
+//! or shared by its seed alone. Faces are drawn from a `SeededRollSource`
+//! for the same reason dice rolls are.
+//!
+//! - Dungeons carve rooms out of solid rock and join each to the last by a
+//!   corridor, so every room can be reached
+//! - Caves fill the map with rock at random and smooth it with a cellular
+//!   automaton, keeping only the largest open cavern
+//! - Outdoor maps raise hills, sink ponds and scatter trees and boulders
+
+use std::collections::{HashSet, VecDeque};
+
+use super::{GridMap, GridMapError, GridPosition, MapBounds, TerrainType, Tile};
+use crate::domain::value_objects::{RollSource, SeededRollSource};
+
+/// The kind of map to generate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapStyle {
+    /// Rooms joined by corridors
+    Dungeon(DungeonSettings),
+    /// A winding cavern
+    Cave(CaveSettings),
+    /// Open ground with hills, ponds, trees and boulders
+    Outdoor(OutdoorSettings),
+}
+
+/// How a dungeon is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DungeonSettings {
+    pub max_rooms: u32,
+    pub min_room_size: u32,
+    pub max_room_size: u32,
+}
+
+impl Default for DungeonSettings {
+    fn default() -> Self {
+        Self {
+            max_rooms: 8,
+            min_room_size: 4,
+            max_room_size: 8,
+        }
+    }
+}
+
+/// How a cave is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaveSettings {
+    /// Chance, in percent, of each tile starting as rock
+    pub fill_percent: u32,
+    /// Times the cellular automaton smooths the rock
+    pub smoothing_passes: u32,
+}
+
+impl Default for CaveSettings {
+    fn default() -> Self {
+        Self {
+            fill_percent: 45,
+            smoothing_passes: 4,
+        }
+    }
+}
+
+/// How an outdoor map is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutdoorSettings {
+    pub hills: u32,
+    pub ponds: u32,
+    /// Chance, in percent, of a tree on each open tile
+    pub tree_percent: u32,
+    /// Chance, in percent, of a boulder on each open tile
+    pub rock_percent: u32,
+}
+
+impl Default for OutdoorSettings {
+    fn default() -> Self {
+        Self {
+            hills: 2,
+            ponds: 1,
+            tree_percent: 10,
+            rock_percent: 3,
+        }
+    }
+}
+
+/// Tilesheet index to draw each kind of terrain with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TilePalette {
+    pub ground: u32,
+    pub wall: u32,
+    pub water: u32,
+    pub difficult: u32,
+    pub hazard: u32,
+    pub pit: u32,
+}
+
+impl Default for TilePalette {
+    fn default() -> Self {
+        Self {
+            ground: 0,
+            wall: 1,
+            water: 2,
+            difficult: 3,
+            hazard: 4,
+            pit: 5,
+        }
+    }
+}
+
+impl TilePalette {
+    /// A tile of the given terrain, drawn from the palette
+    pub fn tile(&self, terrain_type: TerrainType) -> Tile {
+        let tile_index = match terrain_type {
+            TerrainType::Ground => self.ground,
+            TerrainType::Wall => self.wall,
+            TerrainType::Water => self.water,
+            TerrainType::Difficult => self.difficult,
+            TerrainType::Hazard => self.hazard,
+            TerrainType::Pit => self.pit,
+        };
+        Tile::new(terrain_type, tile_index)
+    }
+}
+
+/// A room carved out of a generated dungeon, in tiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeneratedRoom {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl GeneratedRoom {
+    pub fn center(&self) -> GridPosition {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// Where the room lies on the rendered map, in pixels
+    pub fn map_bounds(&self, tile_size: u32) -> MapBounds {
+        MapBounds::new(
+            self.x * tile_size,
+            self.y * tile_size,
+            self.width * tile_size,
+            self.height * tile_size,
+        )
+    }
+
+    /// Whether two rooms touch or overlap, counting a wall's width around each
+    fn crowds(&self, other: &GeneratedRoom) -> bool {
+        self.x <= other.x + other.width
+            && other.x <= self.x + self.width
+            && self.y <= other.y + other.height
+            && other.y <= self.y + self.height
+    }
+}
+
+/// The tiles of a generated map, row by row, and any rooms in it
+#[derive(Debug, Clone)]
+pub struct GeneratedLayout {
+    pub tiles: Vec<Vec<Tile>>,
+    pub rooms: Vec<GeneratedRoom>,
+}
+
+/// Lays out a grid map's tiles from a seed
+#[derive(Debug, Clone)]
+pub struct MapGenerator {
+    pub width: u32,
+    pub height: u32,
+    pub seed: u64,
+    pub style: MapStyle,
+    pub palette: TilePalette,
+}
+
+impl MapGenerator {
+    pub fn new(width: u32, height: u32, seed: u64, style: MapStyle) -> Self {
+        Self {
+            width,
+            height,
+            seed,
+            style,
+            palette: TilePalette::default(),
+        }
+    }
+
+    pub fn with_palette(mut self, palette: TilePalette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Lay out the map
+    pub fn generate(&self) -> Result<GeneratedLayout, GridMapError> {
+        GridMap::check_size(self.width, self.height)?;
+        let mut dice = Dice(SeededRollSource::new(self.seed, 0));
+
+        Ok(match self.style {
+            MapStyle::Dungeon(settings) => self.dungeon(&mut dice, settings),
+            MapStyle::Cave(settings) => GeneratedLayout {
+                tiles: self.cave(&mut dice, settings),
+                rooms: Vec::new(),
+            },
+            MapStyle::Outdoor(settings) => GeneratedLayout {
+                tiles: self.outdoor(&mut dice, settings),
+                rooms: Vec::new(),
+            },
+        })
+    }
+
+    fn filled(&self, terrain_type: TerrainType) -> Vec<Vec<Tile>> {
+        vec![vec![self.palette.tile(terrain_type); self.width as usize]; self.height as usize]
+    }
+
+    fn dungeon(&self, dice: &mut Dice, settings: DungeonSettings) -> GeneratedLayout {
+        let mut tiles = self.filled(TerrainType::Wall);
+        let floor = self.palette.tile(TerrainType::Ground);
+        let max_rooms = settings.max_rooms;
+        let min_size = settings.min_room_size.max(1);
+        let max_size = settings.max_room_size.max(min_size);
+        let mut rooms: Vec<GeneratedRoom> = Vec::new();
+
+        // Rooms keep a wall all round, so the map must be at least that big
+        if self.width < min_size + 2 || self.height < min_size + 2 {
+            return GeneratedLayout { tiles, rooms };
+        }
+        for _ in 0..max_rooms.saturating_mul(4) {
+            if rooms.len() as u32 >= max_rooms {
+                break;
+            }
+            let width = dice.between(min_size, max_size.min(self.width - 2));
+            let height = dice.between(min_size, max_size.min(self.height - 2));
+            let room = GeneratedRoom {
+                x: dice.between(1, self.width - width - 1),
+                y: dice.between(1, self.height - height - 1),
+                width,
+                height,
+            };
+            if rooms.iter().any(|other| other.crowds(&room)) {
+                continue;
+            }
+
+            for y in room.y..room.y + room.height {
+                for x in room.x..room.x + room.width {
+                    tiles[y as usize][x as usize] = floor.clone();
+                }
+            }
+            if let Some(last) = rooms.last() {
+                let (from, to) = (last.center(), room.center());
+                // Bend the corridor one way or the other at random
+                let corner = if dice.chance(50) { (to.0, from.1) } else { (from.0, to.1) };
+                for (a, b) in [(from, corner), (corner, to)] {
+                    for y in a.1.min(b.1)..=a.1.max(b.1) {
+                        for x in a.0.min(b.0)..=a.0.max(b.0) {
+                            tiles[y as usize][x as usize] = floor.clone();
+                        }
+                    }
+                }
+            }
+            rooms.push(room);
+        }
+
+        GeneratedLayout { tiles, rooms }
+    }
+
+    fn cave(&self, dice: &mut Dice, settings: CaveSettings) -> Vec<Vec<Tile>> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        let border = |x: i64, y: i64| x == 0 || y == 0 || x == width - 1 || y == height - 1;
+        let mut rock: Vec<Vec<bool>> = (0..height)
+            .map(|y| (0..width).map(|x| border(x, y) || dice.chance(settings.fill_percent)).collect())
+            .collect();
+
+        for _ in 0..settings.smoothing_passes {
+            let rock_around = |x: i64, y: i64| {
+                (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+                    .filter(|&(nx, ny)| (nx, ny) != (x, y))
+                    .filter(|&(nx, ny)| {
+                        nx < 0 || ny < 0 || nx >= width || ny >= height || rock[ny as usize][nx as usize]
+                    })
+                    .count()
+            };
+            rock = (0..height)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| match rock_around(x, y) {
+                            _ if border(x, y) => true,
+                            n if n >= 5 => true,
+                            n if n <= 3 => false,
+                            _ => rock[y as usize][x as usize],
+                        })
+                        .collect()
+                })
+                .collect();
+        }
+
+        // Fill in every pocket cut off from the largest cavern
+        let cavern = largest_open_area(&rock);
+        (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| match cavern.contains(&(x, y)) {
+                        true => self.palette.tile(TerrainType::Ground),
+                        false => self.palette.tile(TerrainType::Wall),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn outdoor(&self, dice: &mut Dice, settings: OutdoorSettings) -> Vec<Vec<Tile>> {
+        let mut tiles = self.filled(TerrainType::Ground);
+
+        for _ in 0..settings.hills {
+            let (cx, cy, radius) = self.blob(dice, 2, 5);
+            for (x, y) in self.within(cx, cy, radius) {
+                let tile = &mut tiles[y as usize][x as usize];
+                tile.elevation = (tile.elevation + 1).min(2);
+            }
+        }
+        for _ in 0..settings.ponds {
+            let (cx, cy, radius) = self.blob(dice, 1, 3);
+            for (x, y) in self.within(cx, cy, radius) {
+                tiles[y as usize][x as usize] = self.palette.tile(TerrainType::Water);
+            }
+        }
+        for row in &mut tiles {
+            for tile in row.iter_mut().filter(|tile| tile.terrain_type == TerrainType::Ground) {
+                // Trees give light cover, boulders heavy cover
+                let scatter = if dice.chance(settings.tree_percent) {
+                    self.palette.tile(TerrainType::Difficult).with_cover(1)
+                } else if dice.chance(settings.rock_percent) {
+                    self.palette.tile(TerrainType::Wall)
+                } else {
+                    continue;
+                };
+                *tile = scatter.with_elevation(tile.elevation);
+            }
+        }
+
+        tiles
+    }
+
+    /// A random centre on the map and a radius in the given range
+    fn blob(&self, dice: &mut Dice, min_radius: u32, max_radius: u32) -> (u32, u32, u32) {
+        (
+            dice.between(0, self.width - 1),
+            dice.between(0, self.height - 1),
+            dice.between(min_radius, max_radius),
+        )
+    }
+
+    /// Every tile on the map within `radius` of a centre tile
+    fn within(&self, cx: u32, cy: u32, radius: u32) -> Vec<GridPosition> {
+        let r = i64::from(radius);
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                let (dx, dy) = (i64::from(x) - i64::from(cx), i64::from(y) - i64::from(cy));
+                dx * dx + dy * dy <= r * r
+            })
+            .collect()
+    }
+}
+
+/// The largest set of open tiles joined side by side
+fn largest_open_area(rock: &[Vec<bool>]) -> HashSet<GridPosition> {
+    let mut seen: HashSet<GridPosition> = HashSet::new();
+    let mut largest = HashSet::new();
+
+    for (y, row) in rock.iter().enumerate() {
+        for (x, &is_rock) in row.iter().enumerate() {
+            let start = (x as u32, y as u32);
+            if is_rock || seen.contains(&start) {
+                continue;
+            }
+            let mut area = HashSet::from([start]);
+            let mut queue = VecDeque::from([start]);
+            while let Some((x, y)) = queue.pop_front() {
+                let neighbours = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                for (nx, ny) in neighbours {
+                    let open = rock
+                        .get(ny as usize)
+                        .and_then(|row| row.get(nx as usize))
+                        .is_some_and(|&is_rock| !is_rock);
+                    if open && area.insert((nx, ny)) {
+                        queue.push_back((nx, ny));
+                    }
+                }
+            }
+            seen.extend(area.iter().copied());
+            if area.len() > largest.len() {
+                largest = area;
+            }
+        }
+    }
+
+    largest
+}
+
+/// Draws numbers for the generator from a seeded source
+struct Dice(SeededRollSource);
+
+impl Dice {
+    /// A number from `low` to `high`, both included
+    fn between(&mut self, low: u32, high: u32) -> u32 {
+        if high <= low {
+            return low;
+        }
+        low + self.0.roll_die(high - low + 1) as u32 - 1
+    }
+
+    /// Whether something with the given percent chance happens
+    fn chance(&mut self, percent: u32) -> bool {
+        self.0.roll_die(100) as u32 <= percent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn impassable(layout: &GeneratedLayout) -> Vec<Vec<bool>> {
+        layout
+            .tiles
+            .iter()
+            .map(|row| row.iter().map(|tile| !tile.passable).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_same_seed_gives_the_same_map() {
+        for style in [
+            MapStyle::Dungeon(DungeonSettings::default()),
+            MapStyle::Cave(CaveSettings::default()),
+            MapStyle::Outdoor(OutdoorSettings::default()),
+        ] {
+            let first = MapGenerator::new(40, 30, 7, style).generate().unwrap();
+            let again = MapGenerator::new(40, 30, 7, style).generate().unwrap();
+            let other = MapGenerator::new(40, 30, 8, style).generate().unwrap();
+            assert_eq!(first.tiles, again.tiles);
+            assert_eq!(first.rooms, again.rooms);
+            assert_ne!(first.tiles, other.tiles);
+        }
+
+        assert!(MapGenerator::new(0, 30, 7, MapStyle::Cave(CaveSettings::default())).generate().is_err());
+    }
+
+    #[test]
+    fn test_dungeon_rooms_are_carved_and_joined() {
+        let layout = MapGenerator::new(48, 32, 3, MapStyle::Dungeon(DungeonSettings::default())).generate().unwrap();
+        assert!(layout.rooms.len() >= 2);
+
+        // Every floor tile belongs to one connected dungeon
+        let floors = impassable(&layout).iter().flatten().filter(|&&rock| !rock).count();
+        let reachable = largest_open_area(&impassable(&layout));
+        assert_eq!(reachable.len(), floors);
+        for room in &layout.rooms {
+            assert!(reachable.contains(&room.center()));
+            assert!(room.x >= 1 && room.x + room.width < 48);
+        }
+
+        let bounds = layout.rooms[0].map_bounds(32);
+        assert_eq!(bounds.x, layout.rooms[0].x * 32);
+        assert_eq!(bounds.width, layout.rooms[0].width * 32);
+    }
+
+    #[test]
+    fn test_cave_is_one_cavern_walled_in() {
+        let layout = MapGenerator::new(40, 30, 11, MapStyle::Cave(CaveSettings::default())).generate().unwrap();
+        let rock = impassable(&layout);
+        let open = rock.iter().flatten().filter(|&&rock| !rock).count();
+        assert!(open > 0);
+        assert_eq!(largest_open_area(&rock).len(), open);
+        assert!(rock[0].iter().all(|&rock| rock) && rock[29].iter().all(|&rock| rock));
+    }
+
+    #[test]
+    fn test_outdoor_scatter() {
+        let palette = TilePalette {
+            difficult: 30,
+            ..TilePalette::default()
+        };
+        let style = MapStyle::Outdoor(OutdoorSettings {
+            hills: 3,
+            tree_percent: 20,
+            ..OutdoorSettings::default()
+        });
+        let layout = MapGenerator::new(40, 30, 5, style)
+            .with_palette(palette)
+            .generate()
+            .unwrap();
+        let tiles: Vec<&Tile> = layout.tiles.iter().flatten().collect();
+
+        let trees: Vec<_> = tiles
+            .iter()
+            .filter(|tile| tile.terrain_type == TerrainType::Difficult)
+            .collect();
+        assert!(!trees.is_empty());
+        assert!(trees.iter().all(|tree| tree.cover_value == 1 && tree.tile_index == 30));
+        assert!(tiles.iter().any(|tile| tile.terrain_type == TerrainType::Water));
+        assert!(tiles.iter().any(|tile| tile.elevation > 0));
+        assert!(layout.rooms.is_empty());
+    }
+}
@@ -0,0 +1,328 @@
+//! Imported grid maps - Maps drawn in other tools, read onto the grid
+//!
+//! Map editors such as Dungeondraft and Tiled draw walls as lines and doors
+//! as points rather than tiles. An `ImportedMap` collects what was read from
+//! such a file: walls are traced onto the tiles they run through, doors
+//! open up the tile they stand on, and lights and the rendered image are
+//! kept alongside for the client to draw.
+//!
+//! Doors divide a map into areas; each area can become a region of a
+//! location, and each door a connection between the regions either side.
+
+use std::collections::{HashSet, VecDeque};
+
+use super::grid_generation::GeneratedRoom;
+use super::{GridMap, GridMapError, GridPosition, TerrainType, Tile};
+
+/// Fewest tiles an area needs to count; smaller pockets are left out
+pub const MIN_AREA_TILES: usize = 4;
+
+/// Distance, in tiles, between the points walls are traced at
+const WALL_TRACE_STEP: f64 = 0.1;
+
+/// A door on an imported map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapDoor {
+    pub position: GridPosition,
+    pub locked: bool,
+}
+
+/// A light on an imported map, in tiles
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapLight {
+    pub x: f64,
+    pub y: f64,
+    pub range: f64,
+    /// Colour as hex, e.g. "ffeccd8b"
+    pub color: String,
+}
+
+/// The rendered image of an imported map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapImage {
+    pub data: Vec<u8>,
+    /// File extension matching the image's format, e.g. "png"
+    pub extension: &'static str,
+}
+
+impl MapImage {
+    /// An image, its format told from its first bytes; PNG if unknown
+    pub fn new(data: Vec<u8>) -> Self {
+        let extension = if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            "jpg"
+        } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+            "webp"
+        } else {
+            "png"
+        };
+        Self { data, extension }
+    }
+}
+
+/// A map read from another tool's file
+#[derive(Debug, Clone)]
+pub struct ImportedMap {
+    pub width: u32,
+    pub height: u32,
+    /// Tile size in pixels
+    pub tile_size: u32,
+    /// The tiles, row by row
+    pub tiles: Vec<Vec<Tile>>,
+    pub doors: Vec<MapDoor>,
+    pub lights: Vec<MapLight>,
+    pub image: Option<MapImage>,
+}
+
+/// Part of an imported map closed off by walls and doors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapArea {
+    /// Its tiles, row by row
+    pub tiles: Vec<GridPosition>,
+}
+
+impl MapArea {
+    /// The smallest rectangle holding the whole area
+    pub fn bounds(&self) -> GeneratedRoom {
+        let (mut min_x, mut min_y) = (u32::MAX, u32::MAX);
+        let (mut max_x, mut max_y) = (0, 0);
+        for &(x, y) in &self.tiles {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+        GeneratedRoom {
+            x: min_x,
+            y: min_y,
+            width: max_x + 1 - min_x,
+            height: max_y + 1 - min_y,
+        }
+    }
+}
+
+/// A door and the two areas it joins, by their index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Doorway {
+    pub door: MapDoor,
+    pub between: (usize, usize),
+}
+
+impl ImportedMap {
+    /// An open map of the given size
+    pub fn new(width: u32, height: u32, tile_size: u32) -> Result<Self, GridMapError> {
+        GridMap::check_size(width, height)?;
+        Ok(Self {
+            width,
+            height,
+            tile_size,
+            tiles: vec![vec![Tile::default(); width as usize]; height as usize],
+            doors: Vec::new(),
+            lights: Vec::new(),
+            image: None,
+        })
+    }
+
+    fn tile_at(&mut self, x: f64, y: f64) -> Option<&mut Tile> {
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+        self.tiles.get_mut(y as usize)?.get_mut(x as usize)
+    }
+
+    /// The part of a segment that lies on the map, if any
+    fn clip(&self, from: (f64, f64), to: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        if !dx.is_finite() || !dy.is_finite() {
+            return None;
+        }
+        let (mut enter, mut leave) = (0.0_f64, 1.0_f64);
+        let edges = [
+            (-dx, from.0),
+            (dx, f64::from(self.width) - from.0),
+            (-dy, from.1),
+            (dy, f64::from(self.height) - from.1),
+        ];
+        for (towards, room) in edges {
+            if towards == 0.0 {
+                if room < 0.0 {
+                    return None;
+                }
+            } else if towards < 0.0 {
+                enter = enter.max(room / towards);
+            } else {
+                leave = leave.min(room / towards);
+            }
+        }
+        (enter <= leave).then_some((
+            (from.0 + dx * enter, from.1 + dy * enter),
+            (from.0 + dx * leave, from.1 + dy * leave),
+        ))
+    }
+
+    /// Trace a wall between two points onto every tile it runs through,
+    /// leaving out any part of it off the map
+    pub fn add_wall(&mut self, from: (f64, f64), to: (f64, f64), tile_index: u32) {
+        let Some((from, to)) = self.clip(from, to) else {
+            return;
+        };
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = ((dx * dx + dy * dy).sqrt() / WALL_TRACE_STEP).ceil().max(1.0) as u32;
+        for step in 0..=steps {
+            let along = f64::from(step) / f64::from(steps);
+            if let Some(tile) = self.tile_at(from.0 + dx * along, from.1 + dy * along) {
+                *tile = Tile::new(TerrainType::Wall, tile_index).with_elevation(tile.elevation);
+            }
+        }
+    }
+
+    /// Put a door at a point, opening up the tile it stands on
+    pub fn add_door(&mut self, x: f64, y: f64, locked: bool) {
+        let position = (
+            (x.max(0.0) as u32).min(self.width - 1),
+            (y.max(0.0) as u32).min(self.height - 1),
+        );
+        let tile = &mut self.tiles[position.1 as usize][position.0 as usize];
+        if !tile.passable {
+            *tile = Tile::default().with_elevation(tile.elevation);
+        }
+        self.doors.push(MapDoor { position, locked });
+    }
+
+    /// The areas the map's walls and doors divide it into, largest first,
+    /// and the doors that join two of them
+    pub fn divide(&self) -> (Vec<MapArea>, Vec<Doorway>) {
+        let doors: HashSet<GridPosition> = self.doors.iter().map(|door| door.position).collect();
+        let open = |(x, y): GridPosition| {
+            !doors.contains(&(x, y))
+                && self
+                    .tiles
+                    .get(y as usize)
+                    .and_then(|row| row.get(x as usize))
+                    .is_some_and(|tile| tile.passable)
+        };
+
+        let mut area_of = vec![vec![None; self.width as usize]; self.height as usize];
+        let mut areas: Vec<MapArea> = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !open((x, y)) || area_of[y as usize][x as usize].is_some() {
+                    continue;
+                }
+                let mut tiles = vec![(x, y)];
+                let mut seen = HashSet::from([(x, y)]);
+                let mut queue = VecDeque::from([(x, y)]);
+                while let Some(position) = queue.pop_front() {
+                    for next in side_neighbours(position) {
+                        if open(next) && seen.insert(next) {
+                            tiles.push(next);
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                let index = areas.len();
+                for &(tx, ty) in &tiles {
+                    area_of[ty as usize][tx as usize] = Some(index);
+                }
+                tiles.sort_by_key(|&(x, y)| (y, x));
+                areas.push(MapArea { tiles });
+            }
+        }
+
+        // Drop the pockets too small to count, keeping the rest largest first
+        let mut kept: Vec<usize> = (0..areas.len())
+            .filter(|&index| areas[index].tiles.len() >= MIN_AREA_TILES)
+            .collect();
+        kept.sort_by_key(|&index| std::cmp::Reverse(areas[index].tiles.len()));
+        let renumbered = |index: usize| kept.iter().position(|&k| k == index);
+
+        let doorways = self
+            .doors
+            .iter()
+            .filter_map(|door| {
+                let mut sides: Vec<usize> = side_neighbours(door.position)
+                    .into_iter()
+                    .filter_map(|(x, y)| area_of.get(y as usize)?.get(x as usize).copied().flatten())
+                    .filter_map(renumbered)
+                    .collect();
+                sides.sort_unstable();
+                sides.dedup();
+                match sides[..] {
+                    [a, b, ..] => Some(Doorway {
+                        door: *door,
+                        between: (a, b),
+                    }),
+                    _ => None,
+                }
+            })
+            .collect();
+        let areas = kept.iter().map(|&index| areas[index].clone()).collect();
+
+        (areas, doorways)
+    }
+}
+
+/// The tiles beside a tile, not counting diagonals
+fn side_neighbours((x, y): GridPosition) -> Vec<GridPosition> {
+    let mut neighbours = vec![(x + 1, y), (x, y + 1)];
+    if x > 0 {
+        neighbours.push((x - 1, y));
+    }
+    if y > 0 {
+        neighbours.push((x, y - 1));
+    }
+    neighbours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walls_are_traced_onto_tiles() {
+        let mut map = ImportedMap::new(6, 4, 70).unwrap();
+        map.add_wall((3.0, 0.0), (3.0, 3.9), 1);
+
+        for y in 0..4 {
+            assert_eq!(map.tiles[y][3].terrain_type, TerrainType::Wall);
+            assert!(!map.tiles[y][3].passable);
+            assert!(map.tiles[y][2].passable && map.tiles[y][4].passable);
+        }
+        assert!(ImportedMap::new(0, 4, 70).is_err());
+    }
+
+    #[test]
+    fn test_walls_are_clipped_to_the_map() {
+        let mut map = ImportedMap::new(6, 4, 70).unwrap();
+        map.add_wall((1.5, -1e9), (1.5, 1e9), 1);
+        map.add_wall((-1e9, 2.5), (1e9, 2.5), 1);
+        map.add_wall((-1e308, 0.5), (1e308, 0.5), 1);
+        map.add_wall((10.0, 10.0), (20.0, 0.0), 1);
+
+        for y in 0..4 {
+            assert_eq!(map.tiles[y][1].terrain_type, TerrainType::Wall);
+        }
+        assert!(map.tiles[2].iter().all(|tile| tile.terrain_type == TerrainType::Wall));
+        assert!(map.tiles[0][5].passable && map.tiles[3][5].passable);
+    }
+
+    #[test]
+    fn test_doors_divide_the_map_into_areas() {
+        // Two rooms split by a wall with a door, and a pocket too small to count
+        let mut map = ImportedMap::new(9, 4, 70).unwrap();
+        map.add_wall((3.5, 0.0), (3.5, 3.9), 1);
+        map.add_wall((7.5, 0.0), (7.5, 1.9), 1);
+        map.add_wall((7.5, 2.5), (8.9, 2.5), 1);
+        map.add_door(3.5, 1.5, true);
+
+        assert!(map.tiles[1][3].passable);
+        let (areas, doorways) = map.divide();
+        assert_eq!(areas.len(), 2);
+        assert_eq!(areas[0].tiles.len(), 14);
+        assert_eq!(areas[1].tiles.len(), 12);
+        assert_eq!(areas[1].bounds(), GeneratedRoom { x: 0, y: 0, width: 3, height: 4 });
+
+        assert_eq!(doorways.len(), 1);
+        assert_eq!(doorways[0].between, (0, 1));
+        assert!(doorways[0].door.locked);
+    }
+}
@@ -1,7 +1,15 @@
 //! Grid map for tactical combat
 
+use std::fmt;
+
 use crate::domain::value_objects::{GridMapId, WorldId};
 
+/// Largest width or height a grid map may have
+pub const MAX_GRID_DIMENSION: u32 = 256;
+
+/// Highest a tile may rise, or deepest it may sink, in elevation levels
+pub const MAX_ELEVATION: i32 = 1000;
+
 /// A tactical grid map for combat
 #[derive(Debug, Clone)]
 pub struct GridMap {
@@ -58,25 +66,120 @@ impl GridMap {
         }
     }
 
+    /// Whether a coordinate lies on the map
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Check that a map of the given size may be created
+    pub fn check_size(width: u32, height: u32) -> Result<(), GridMapError> {
+        if width == 0 || height == 0 || width > MAX_GRID_DIMENSION || height > MAX_GRID_DIMENSION {
+            return Err(GridMapError::InvalidSize { width, height });
+        }
+        Ok(())
+    }
+
+    /// Change the map's size, keeping the tiles that still fit and filling
+    /// new ones with the default tile
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), GridMapError> {
+        Self::check_size(width, height)?;
+        self.tiles
+            .resize_with(height as usize, || vec![Tile::default(); width as usize]);
+        for row in &mut self.tiles {
+            row.resize_with(width as usize, Tile::default);
+        }
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
+    /// Replace every tile; the rows must match the map's size
+    pub fn set_tiles(&mut self, tiles: Vec<Vec<Tile>>) -> Result<(), GridMapError> {
+        let matches = tiles.len() == self.height as usize
+            && tiles.iter().all(|row| row.len() == self.width as usize);
+        if !matches {
+            return Err(GridMapError::WrongShape {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        for (y, row) in tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                tile.check_elevation(x as u32, y as u32)?;
+            }
+        }
+        self.tiles = tiles;
+        Ok(())
+    }
+
+    /// Replace some tiles; nothing changes if any lies off the map
+    pub fn patch_tiles(&mut self, patches: Vec<(u32, u32, Tile)>) -> Result<(), GridMapError> {
+        if let Some(&(x, y, _)) = patches.iter().find(|(x, y, _)| !self.contains(*x, *y)) {
+            return Err(GridMapError::OutOfBounds { x, y });
+        }
+        for (x, y, tile) in &patches {
+            tile.check_elevation(*x, *y)?;
+        }
+        for (x, y, tile) in patches {
+            self.set_tile(x, y, tile);
+        }
+        Ok(())
+    }
+
     /// Calculate movement cost between adjacent tiles considering elevation
     pub fn movement_cost(&self, from: (u32, u32), to: (u32, u32)) -> Option<u32> {
         let from_tile = self.get_tile(from.0, from.1)?;
         let to_tile = self.get_tile(to.0, to.1)?;
 
-        if !to_tile.passable {
+        if !to_tile.is_passable() {
             return None;
         }
 
-        let elevation_diff = (to_tile.elevation - from_tile.elevation).abs();
+        let elevation_diff = to_tile.elevation.abs_diff(from_tile.elevation);
         let base_cost = to_tile.terrain_type.movement_cost();
 
         // Climbing costs extra
-        Some(base_cost + elevation_diff as u32)
+        base_cost.checked_add(elevation_diff)
     }
 }
 
+/// A change to a grid map that does not fit it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridMapError {
+    /// Width or height is zero or above `MAX_GRID_DIMENSION`
+    InvalidSize { width: u32, height: u32 },
+    /// Tile rows do not match the map's size
+    WrongShape { width: u32, height: u32 },
+    OutOfBounds { x: u32, y: u32 },
+    /// A tile's elevation is beyond `MAX_ELEVATION` either way
+    InvalidElevation { x: u32, y: u32, elevation: i32 },
+}
+
+impl fmt::Display for GridMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSize { width, height } => write!(
+                f,
+                "Grid maps must be 1 to {} tiles across, not {}x{}",
+                MAX_GRID_DIMENSION, width, height
+            ),
+            Self::WrongShape { width, height } => {
+                write!(f, "Tiles must be {} rows of {} tiles", height, width)
+            }
+            Self::OutOfBounds { x, y } => write!(f, "Tile ({}, {}) is off the map", x, y),
+            Self::InvalidElevation { x, y, elevation } => write!(
+                f,
+                "Tile ({}, {}) has elevation {}; it must be between -{} and {}",
+                x, y, elevation, MAX_ELEVATION, MAX_ELEVATION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridMapError {}
+
 /// A single tile on the grid map
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Tile {
     pub terrain_type: TerrainType,
     /// Elevation level (supports height differences)
@@ -117,6 +220,23 @@ impl Tile {
         self
     }
 
+    /// Whether units can move through the tile; walls and pits never let
+    /// them, whatever the tile says
+    pub fn is_passable(&self) -> bool {
+        self.passable && self.terrain_type.default_passable()
+    }
+
+    fn check_elevation(&self, x: u32, y: u32) -> Result<(), GridMapError> {
+        if self.elevation.unsigned_abs() > MAX_ELEVATION.unsigned_abs() {
+            return Err(GridMapError::InvalidElevation {
+                x,
+                y,
+                elevation: self.elevation,
+            });
+        }
+        Ok(())
+    }
+
     pub fn blocking(mut self) -> Self {
         self.passable = false;
         self
@@ -162,4 +282,42 @@ impl TerrainType {
             _ => 0,
         }
     }
+
+    /// Whether the terrain cuts off sight through it
+    pub fn blocks_sight(&self) -> bool {
+        matches!(self, Self::Wall)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_and_patch_tiles() {
+        let mut map = GridMap::new(WorldId::new(), "Crypt", 3, 2, "crypt.png");
+        map.set_tile(2, 1, Tile::new(TerrainType::Wall, 4));
+
+        map.resize(4, 3).unwrap();
+        assert_eq!(map.tiles.len(), 3);
+        assert!(map.tiles.iter().all(|row| row.len() == 4));
+        assert_eq!(map.get_tile(2, 1).unwrap().terrain_type, TerrainType::Wall);
+        assert_eq!(map.get_tile(3, 2), Some(&Tile::default()));
+        assert_eq!(map.resize(0, 3), Err(GridMapError::InvalidSize { width: 0, height: 3 }));
+
+        let water = Tile::new(TerrainType::Water, 7);
+        assert_eq!(
+            map.patch_tiles(vec![(0, 0, water.clone()), (4, 0, water.clone())]),
+            Err(GridMapError::OutOfBounds { x: 4, y: 0 })
+        );
+        assert_eq!(map.get_tile(0, 0), Some(&Tile::default()));
+        map.patch_tiles(vec![(0, 0, water.clone())]).unwrap();
+        assert_eq!(map.get_tile(0, 0), Some(&water));
+
+        assert!(map.set_tiles(vec![vec![Tile::default(); 4]; 2]).is_err());
+        assert_eq!(
+            map.patch_tiles(vec![(1, 1, Tile::default().with_elevation(-MAX_ELEVATION - 1))]),
+            Err(GridMapError::InvalidElevation { x: 1, y: 1, elevation: -MAX_ELEVATION - 1 })
+        );
+    }
 }
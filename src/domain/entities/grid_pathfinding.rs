@@ -0,0 +1,242 @@
+//! Pathfinding on grid maps - Shortest paths and movement ranges
+//!
+//! A step costs what `GridMap::movement_cost` says: the terrain's cost plus
+//! any climb. How diagonal steps count is up to the rule system's
+//! `DiagonalRule`. Under the alternating rule the cost of a diagonal
+//! depends on how many came before it, so searches track that along with
+//! the tile. Occupied tiles cannot be entered, and a diagonal step cannot
+//! squeeze between two impassable tiles.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::domain::value_objects::DiagonalRule;
+
+use super::GridMap;
+
+/// A tile on a grid map, as (x, y)
+pub type GridPosition = (u32, u32);
+
+/// A route across a grid map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridPath {
+    /// Every tile on the route, start and end included
+    pub steps: Vec<GridPosition>,
+    pub cost: u32,
+}
+
+/// A tile a mover can reach, and the least it costs to get there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReachableTile {
+    pub position: GridPosition,
+    pub cost: u32,
+}
+
+/// A tile, and whether an odd number of diagonal steps led to it
+type SearchState = (GridPosition, bool);
+
+const DIRECTIONS: [(i64, i64); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+impl GridMap {
+    /// The cheapest route between two tiles, if one exists
+    pub fn find_path(
+        &self,
+        from: GridPosition,
+        to: GridPosition,
+        rule: DiagonalRule,
+        occupied: &HashSet<GridPosition>,
+    ) -> Option<GridPath> {
+        if !self.contains(from.0, from.1) || !self.contains(to.0, to.1) {
+            return None;
+        }
+
+        let start = (from, false);
+        let mut best: HashMap<SearchState, u32> = HashMap::from([(start, 0)]);
+        let mut came_from: HashMap<SearchState, SearchState> = HashMap::new();
+        let mut open = BinaryHeap::from([Reverse((distance(from, to), 0, start))]);
+
+        while let Some(Reverse((_, cost, state))) = open.pop() {
+            if best.get(&state).is_some_and(|&known| cost > known) {
+                continue;
+            }
+            if state.0 == to {
+                let mut steps = vec![state.0];
+                let mut current = state;
+                while let Some(&previous) = came_from.get(&current) {
+                    steps.push(previous.0);
+                    current = previous;
+                }
+                steps.reverse();
+                return Some(GridPath { steps, cost });
+            }
+
+            for (next, step_cost) in self.steps(state, rule, occupied) {
+                let next_cost = cost.saturating_add(step_cost);
+                if best.get(&next).is_none_or(|&known| next_cost < known) {
+                    best.insert(next, next_cost);
+                    came_from.insert(next, state);
+                    open.push(Reverse((next_cost.saturating_add(distance(next.0, to)), next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Every tile reachable from `from` for at most `budget` movement,
+    /// the start included, ordered row by row
+    pub fn reachable(
+        &self,
+        from: GridPosition,
+        budget: u32,
+        rule: DiagonalRule,
+        occupied: &HashSet<GridPosition>,
+    ) -> Vec<ReachableTile> {
+        if !self.contains(from.0, from.1) {
+            return Vec::new();
+        }
+
+        let start = (from, false);
+        let mut best: HashMap<SearchState, u32> = HashMap::from([(start, 0)]);
+        let mut open = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((cost, state))) = open.pop() {
+            if best.get(&state).is_some_and(|&known| cost > known) {
+                continue;
+            }
+            for (next, step_cost) in self.steps(state, rule, occupied) {
+                let next_cost = cost.saturating_add(step_cost);
+                if next_cost <= budget && best.get(&next).is_none_or(|&known| next_cost < known) {
+                    best.insert(next, next_cost);
+                    open.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+
+        let mut cheapest: HashMap<GridPosition, u32> = HashMap::new();
+        for ((position, _), cost) in best {
+            cheapest
+                .entry(position)
+                .and_modify(|known| *known = (*known).min(cost))
+                .or_insert(cost);
+        }
+        let mut tiles: Vec<ReachableTile> = cheapest
+            .into_iter()
+            .map(|(position, cost)| ReachableTile { position, cost })
+            .collect();
+        tiles.sort_by_key(|tile| (tile.position.1, tile.position.0));
+        tiles
+    }
+
+    fn passable_at(&self, x: u32, y: u32) -> bool {
+        self.get_tile(x, y).is_some_and(|tile| tile.is_passable())
+    }
+
+    /// The steps that can be taken from a search state, and what each costs
+    fn steps<'a>(
+        &'a self,
+        (position, odd_diagonals): SearchState,
+        rule: DiagonalRule,
+        occupied: &'a HashSet<GridPosition>,
+    ) -> impl Iterator<Item = (SearchState, u32)> + 'a {
+        DIRECTIONS.iter().filter_map(move |&(dx, dy)| {
+            let diagonal = dx != 0 && dy != 0;
+            if diagonal && rule == DiagonalRule::Forbidden {
+                return None;
+            }
+            let x = u32::try_from(i64::from(position.0) + dx).ok()?;
+            let y = u32::try_from(i64::from(position.1) + dy).ok()?;
+            if !self.contains(x, y) || occupied.contains(&(x, y)) {
+                return None;
+            }
+            if diagonal && !self.passable_at(position.0, y) && !self.passable_at(x, position.1) {
+                return None;
+            }
+            let cost = self
+                .movement_cost(position, (x, y))
+                .filter(|&cost| cost != u32::MAX)?;
+
+            Some(match rule {
+                DiagonalRule::Alternating if diagonal && odd_diagonals => (((x, y), false), cost.saturating_mul(2)),
+                DiagonalRule::Alternating if diagonal => (((x, y), true), cost),
+                _ => (((x, y), odd_diagonals), cost),
+            })
+        })
+    }
+}
+
+/// Fewest steps between two tiles, diagonals allowed; no step costs less
+/// than one, so this never overestimates a path's cost
+fn distance(a: GridPosition, b: GridPosition) -> u32 {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{TerrainType, Tile};
+    use crate::domain::value_objects::WorldId;
+
+    #[test]
+    fn test_paths_follow_diagonal_rules_and_terrain() {
+        let mut map = GridMap::new(WorldId::new(), "Field", 6, 6, "field.png");
+        let none = HashSet::new();
+
+        let uniform = map.find_path((0, 0), (3, 3), DiagonalRule::Uniform, &none).unwrap();
+        assert_eq!(uniform.steps, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+        assert_eq!(uniform.cost, 3);
+        assert_eq!(map.find_path((0, 0), (3, 3), DiagonalRule::Alternating, &none).unwrap().cost, 4);
+        assert_eq!(map.find_path((0, 0), (3, 3), DiagonalRule::Forbidden, &none).unwrap().cost, 6);
+
+        // A wall across the middle with a gap at the bottom
+        for y in 0..5 {
+            map.set_tile(2, y, Tile::new(TerrainType::Wall, 1));
+        }
+        map.set_tile(2, 5, Tile::new(TerrainType::Difficult, 2).with_elevation(1));
+        let around = map.find_path((0, 0), (4, 0), DiagonalRule::Uniform, &none).unwrap();
+        assert!(around.steps.contains(&(2, 5)));
+        // Four steps down, the climb into rough ground (2 + 1), the climb
+        // back out (1 + 1) and four steps up
+        assert_eq!(around.cost, 4 + 3 + 2 + 4);
+
+        let blocked = HashSet::from([(2, 5)]);
+        assert_eq!(map.find_path((0, 0), (4, 0), DiagonalRule::Uniform, &blocked), None);
+
+        // A wall marked passable is still a wall, and no climb overflows
+        let mut breach = Tile::new(TerrainType::Wall, 1).with_elevation(i32::MIN);
+        breach.passable = true;
+        map.set_tile(2, 5, breach);
+        assert_eq!(map.find_path((0, 0), (4, 0), DiagonalRule::Uniform, &none), None);
+        map.set_tile(4, 5, Tile::default().with_elevation(i32::MIN));
+        map.set_tile(5, 5, Tile::default().with_elevation(i32::MAX));
+        assert_eq!(map.movement_cost((4, 5), (5, 5)), None);
+        assert!(map.find_path((4, 5), (5, 4), DiagonalRule::Alternating, &none).is_some());
+    }
+
+    #[test]
+    fn test_reachable_tiles_within_budget() {
+        let mut map = GridMap::new(WorldId::new(), "Room", 5, 5, "room.png");
+        map.set_tile(1, 0, Tile::new(TerrainType::Water, 3));
+        let occupied = HashSet::from([(0, 1)]);
+
+        let tiles = map.reachable((0, 0), 1, DiagonalRule::Uniform, &occupied);
+        let positions: Vec<_> = tiles.iter().map(|tile| tile.position).collect();
+        assert_eq!(positions, vec![(0, 0), (1, 1)]);
+
+        let tiles = map.reachable((0, 0), 2, DiagonalRule::Alternating, &HashSet::new());
+        let cost_at = |position| tiles.iter().find(|tile| tile.position == position).map(|tile| tile.cost);
+        assert_eq!(cost_at((1, 0)), Some(2));
+        assert_eq!(cost_at((1, 1)), Some(1));
+        // A second diagonal would cost two more
+        assert_eq!(cost_at((2, 2)), None);
+        assert_eq!(cost_at((0, 2)), Some(2));
+    }
+}
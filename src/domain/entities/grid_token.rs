@@ -0,0 +1,253 @@
+//! Grid tokens - Who and what stands where on a grid map
+//!
+//! A token puts a player character, an NPC or a prop on a grid map. It
+//! covers a square of `size` tiles across, anchored at its top-left tile.
+//! Tokens cannot overlap, and cannot stand on impassable tiles.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::domain::value_objects::{CharacterId, GridMapId, GridTokenId, PlayerCharacterId};
+
+use super::{GridMap, GridPosition};
+
+/// Largest number of tiles across a token may be
+pub const MAX_TOKEN_SIZE: u32 = 4;
+
+/// What a token stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenSubject {
+    Pc(PlayerCharacterId),
+    Npc(CharacterId),
+    /// Furniture, traps, markers and anything else that is not a character
+    Prop,
+}
+
+/// The way a token faces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Facing {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    #[default]
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Facing {
+    /// The way a step from one tile to another faces; None for no step
+    pub fn of_step(from: GridPosition, to: GridPosition) -> Option<Self> {
+        let dx = i64::from(to.0) - i64::from(from.0);
+        let dy = i64::from(to.1) - i64::from(from.1);
+        Some(match (dx.signum(), dy.signum()) {
+            (0, -1) => Self::North,
+            (1, -1) => Self::NorthEast,
+            (1, 0) => Self::East,
+            (1, 1) => Self::SouthEast,
+            (0, 1) => Self::South,
+            (-1, 1) => Self::SouthWest,
+            (-1, 0) => Self::West,
+            (-1, -1) => Self::NorthWest,
+            _ => return None,
+        })
+    }
+}
+
+/// A token on a grid map
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridToken {
+    pub id: GridTokenId,
+    pub grid_map_id: GridMapId,
+    pub subject: TokenSubject,
+    pub name: String,
+    /// Top-left tile the token covers
+    pub x: u32,
+    pub y: u32,
+    /// Tiles across; the token covers a square this size
+    pub size: u32,
+    pub facing: Facing,
+    /// Whether only the DM can see the token
+    pub hidden: bool,
+}
+
+impl GridToken {
+    pub fn new(
+        grid_map_id: GridMapId,
+        subject: TokenSubject,
+        name: impl Into<String>,
+        x: u32,
+        y: u32,
+    ) -> Self {
+        Self {
+            id: GridTokenId::new(),
+            grid_map_id,
+            subject,
+            name: name.into(),
+            x,
+            y,
+            size: 1,
+            facing: Facing::default(),
+            hidden: false,
+        }
+    }
+
+    pub fn with_size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_facing(mut self, facing: Facing) -> Self {
+        self.facing = facing;
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    pub fn position(&self) -> GridPosition {
+        (self.x, self.y)
+    }
+
+    /// The tiles the token would cover anchored at a tile
+    pub fn footprint_at(&self, (x, y): GridPosition) -> Vec<GridPosition> {
+        (0..self.size)
+            .flat_map(|dy| (0..self.size).map(move |dx| (x.saturating_add(dx), y.saturating_add(dy))))
+            .collect()
+    }
+
+    /// The tiles the token covers
+    pub fn footprint(&self) -> Vec<GridPosition> {
+        self.footprint_at(self.position())
+    }
+}
+
+/// Why a token cannot stand or move where it was asked to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenPlacementError {
+    /// Size is zero or above `MAX_TOKEN_SIZE`
+    InvalidSize(u32),
+    OffMap { x: u32, y: u32 },
+    Impassable { x: u32, y: u32 },
+    /// Another token already stands there
+    Occupied { x: u32, y: u32 },
+    /// No path leads there
+    Unreachable { x: u32, y: u32 },
+}
+
+impl fmt::Display for TokenPlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSize(size) => write!(
+                f,
+                "Tokens must be 1 to {} tiles across, not {}",
+                MAX_TOKEN_SIZE, size
+            ),
+            Self::OffMap { x, y } => write!(f, "Tile ({}, {}) is off the map", x, y),
+            Self::Impassable { x, y } => write!(f, "Tile ({}, {}) cannot be stood on", x, y),
+            Self::Occupied { x, y } => write!(f, "Tile ({}, {}) is already taken", x, y),
+            Self::Unreachable { x, y } => write!(f, "No path leads to ({}, {})", x, y),
+        }
+    }
+}
+
+impl std::error::Error for TokenPlacementError {}
+
+impl GridMap {
+    /// Check that a token may stand at a tile, given where the other tokens
+    /// on the map stand
+    pub fn check_token_placement(
+        &self,
+        token: &GridToken,
+        at: GridPosition,
+        others: &[GridToken],
+    ) -> Result<(), TokenPlacementError> {
+        if token.size == 0 || token.size > MAX_TOKEN_SIZE {
+            return Err(TokenPlacementError::InvalidSize(token.size));
+        }
+        let taken = tiles_taken(token, others);
+        for (x, y) in token.footprint_at(at) {
+            let Some(tile) = self.get_tile(x, y) else {
+                return Err(TokenPlacementError::OffMap { x, y });
+            };
+            if !tile.is_passable() {
+                return Err(TokenPlacementError::Impassable { x, y });
+            }
+            if taken.contains(&(x, y)) {
+                return Err(TokenPlacementError::Occupied { x, y });
+            }
+        }
+        Ok(())
+    }
+
+    /// The tiles a token cannot be anchored on, as an occupied set for
+    /// pathfinding
+    ///
+    /// A token bigger than one tile moves by its top-left tile, so any tile
+    /// from which its footprint would overlap another token or hang over
+    /// impassable ground or the map's edge is closed to it.
+    pub fn blocked_anchors(&self, token: &GridToken, others: &[GridToken]) -> HashSet<GridPosition> {
+        let taken = tiles_taken(token, others);
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&anchor| {
+                token.footprint_at(anchor).iter().any(|&(x, y)| {
+                    taken.contains(&(x, y)) || !self.get_tile(x, y).is_some_and(|tile| tile.is_passable())
+                })
+            })
+            .collect()
+    }
+}
+
+/// The tiles covered by every token but one
+fn tiles_taken(token: &GridToken, others: &[GridToken]) -> HashSet<GridPosition> {
+    others
+        .iter()
+        .filter(|other| other.id != token.id)
+        .flat_map(GridToken::footprint)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{TerrainType, Tile};
+    use crate::domain::value_objects::WorldId;
+
+    #[test]
+    fn test_token_placement() {
+        let mut map = GridMap::new(WorldId::new(), "Inn", 5, 5, "inn.png");
+        map.set_tile(4, 4, Tile::new(TerrainType::Wall, 1));
+        let ogre = GridToken::new(map.id, TokenSubject::Npc(CharacterId::new()), "Ogre", 0, 0)
+            .with_size(2);
+        let pc = GridToken::new(map.id, TokenSubject::Pc(PlayerCharacterId::new()), "Mira", 3, 0);
+        let others = vec![ogre.clone(), pc.clone()];
+
+        assert_eq!(ogre.footprint(), vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert!(map.check_token_placement(&pc, (2, 0), &others).is_ok());
+        assert_eq!(
+            map.check_token_placement(&pc, (1, 1), &others),
+            Err(TokenPlacementError::Occupied { x: 1, y: 1 })
+        );
+        assert_eq!(
+            map.check_token_placement(&ogre, (3, 3), &others),
+            Err(TokenPlacementError::Impassable { x: 4, y: 4 })
+        );
+        assert_eq!(
+            map.check_token_placement(&ogre, (4, 0), &others),
+            Err(TokenPlacementError::OffMap { x: 5, y: 0 })
+        );
+        // A token never gets in its own way
+        assert!(map.check_token_placement(&ogre, (1, 1), &others).is_ok());
+        assert_eq!(Facing::of_step((2, 2), (1, 3)), Some(Facing::SouthWest));
+
+        // The ogre cannot squeeze its corner onto the wall or the PC
+        let blocked = map.blocked_anchors(&ogre, &others);
+        assert!(blocked.contains(&(3, 3)) && blocked.contains(&(2, 0)) && blocked.contains(&(4, 1)));
+        assert!(!blocked.contains(&(1, 2)) && !blocked.contains(&(0, 0)));
+    }
+}
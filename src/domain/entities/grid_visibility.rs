@@ -0,0 +1,257 @@
+//! Visibility on grid maps - Line of sight, cover and fog of war
+//!
+//! Sight runs in a straight line between tile centres, at eye height above
+//! each end's elevation. Walls block it, as does ground that rises above
+//! the line, and it cannot slip diagonally between two walls. Lines are
+//! traced both ways and the clearer one counts, so if one tile can see
+//! another, the other can see it too. Anything between the ends that gives
+//! cover gives it to the target.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::value_objects::PlayerCharacterId;
+
+use super::{GridMap, GridPosition, TerrainType, Tile};
+
+/// How far above its tile's elevation a viewer's eyes are
+const EYE_HEIGHT: i64 = 1;
+
+/// How well a target is shielded from an attacker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Cover {
+    None,
+    Light,
+    Heavy,
+    /// No line of sight at all
+    Total,
+}
+
+impl Cover {
+    /// Cover given by a tile's `cover_value`
+    pub fn from_value(value: u8) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Light,
+            _ => Self::Heavy,
+        }
+    }
+}
+
+impl GridMap {
+    /// Whether one tile can be seen from another
+    pub fn line_of_sight(&self, from: GridPosition, to: GridPosition) -> bool {
+        self.cover_between(from, to) != Cover::Total
+    }
+
+    /// The cover a target on one tile has from an attacker on another
+    pub fn cover_between(&self, attacker: GridPosition, target: GridPosition) -> Cover {
+        if !self.contains(attacker.0, attacker.1) || !self.contains(target.0, target.1) {
+            return Cover::Total;
+        }
+        self.trace(attacker, target).min(self.trace(target, attacker))
+    }
+
+    /// Every tile seen from a tile by a viewer who sees `radius` tiles far,
+    /// its own tile included
+    pub fn visible_from(&self, from: GridPosition, radius: u32) -> HashSet<GridPosition> {
+        if !self.contains(from.0, from.1) {
+            return HashSet::new();
+        }
+
+        let reach = u64::from(radius) * u64::from(radius);
+        let min_x = from.0.saturating_sub(radius);
+        let min_y = from.1.saturating_sub(radius);
+        let max_x = from.0.saturating_add(radius).min(self.width - 1);
+        let max_y = from.1.saturating_add(radius).min(self.height - 1);
+
+        (min_y..=max_y)
+            .flat_map(|y| (min_x..=max_x).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                let dx = u64::from(x.abs_diff(from.0));
+                let dy = u64::from(y.abs_diff(from.1));
+                dx * dx + dy * dy <= reach && self.line_of_sight(from, (x, y))
+            })
+            .collect()
+    }
+
+    /// The map as a character who has explored only some tiles knows it:
+    /// every other tile is a wall, so it can be neither crossed nor seen
+    /// through
+    pub fn as_explored(&self, explored: &HashSet<GridPosition>) -> GridMap {
+        let mut known = self.clone();
+        for (y, row) in known.tiles.iter_mut().enumerate() {
+            for (x, tile) in row.iter_mut().enumerate() {
+                if !explored.contains(&(x as u32, y as u32)) {
+                    *tile = Tile::new(TerrainType::Wall, 0);
+                }
+            }
+        }
+        known
+    }
+
+    fn blocks_sight_at(&self, x: i64, y: i64) -> bool {
+        match (u32::try_from(x), u32::try_from(y)) {
+            (Ok(x), Ok(y)) => self
+                .get_tile(x, y)
+                .is_some_and(|tile| tile.terrain_type.blocks_sight()),
+            _ => false,
+        }
+    }
+
+    fn elevation_at(&self, (x, y): GridPosition) -> i64 {
+        self.get_tile(x, y).map_or(0, |tile| i64::from(tile.elevation))
+    }
+
+    /// Follow the line from one tile to another, returning the most cover
+    /// anything along it gives
+    fn trace(&self, from: GridPosition, to: GridPosition) -> Cover {
+        let line = bresenham(from, to);
+        let steps = (line.len() - 1) as i64;
+        let eye_from = self.elevation_at(from) + EYE_HEIGHT;
+        let eye_to = self.elevation_at(to) + EYE_HEIGHT;
+
+        let mut cover = Cover::None;
+        for (i, pair) in line.windows(2).enumerate() {
+            let ((px, py), (qx, qy)) = (pair[0], pair[1]);
+            if px != qx && py != qy && self.blocks_sight_at(px, qy) && self.blocks_sight_at(qx, py) {
+                return Cover::Total;
+            }
+            let i = i as i64 + 1;
+            if i == steps {
+                break;
+            }
+
+            let position = (qx as u32, qy as u32);
+            let Some(tile) = self.get_tile(position.0, position.1) else {
+                return Cover::Total;
+            };
+            // Compare elevation * steps with the line's height * steps to
+            // stay in whole numbers
+            let line_height = eye_from * (steps - i) + eye_to * i;
+            if tile.terrain_type.blocks_sight() || i64::from(tile.elevation) * steps > line_height {
+                return Cover::Total;
+            }
+            cover = cover.max(Cover::from_value(tile.cover_value));
+        }
+        cover
+    }
+}
+
+/// The tiles a straight line between two tiles passes through, both ends
+/// included
+fn bresenham(from: GridPosition, to: GridPosition) -> Vec<(i64, i64)> {
+    let (mut x, mut y) = (i64::from(from.0), i64::from(from.1));
+    let (x1, y1) = (i64::from(to.0), i64::from(to.1));
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let sx = if x < x1 { 1 } else { -1 };
+    let sy = if y < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let mut line = vec![(x, y)];
+    while (x, y) != (x1, y1) {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+        line.push((x, y));
+    }
+    line
+}
+
+/// What each player character can see of a grid map, and has seen of it
+#[derive(Debug, Clone, Default)]
+pub struct FogOfWar {
+    sight: HashMap<PlayerCharacterId, CharacterSight>,
+}
+
+/// One character's view of a map
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharacterSight {
+    /// Tiles in sight from where the character last looked
+    pub visible: HashSet<GridPosition>,
+    /// Every tile the character has seen
+    pub explored: HashSet<GridPosition>,
+}
+
+impl FogOfWar {
+    /// Record what a character sees now; everything it saw before stays
+    /// explored
+    pub fn look(&mut self, pc_id: PlayerCharacterId, visible: HashSet<GridPosition>) -> &CharacterSight {
+        let sight = self.sight.entry(pc_id).or_default();
+        sight.explored.extend(visible.iter().copied());
+        sight.visible = visible;
+        sight
+    }
+
+    /// A character's view; empty if it has not looked yet
+    pub fn sight(&self, pc_id: PlayerCharacterId) -> CharacterSight {
+        self.sight.get(&pc_id).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{DiagonalRule, WorldId};
+
+    #[test]
+    fn test_walls_elevation_and_cover_along_sight_lines() {
+        let mut map = GridMap::new(WorldId::new(), "Hall", 7, 5, "hall.png");
+        map.set_tile(3, 0, Tile::new(TerrainType::Wall, 1));
+        map.set_tile(3, 2, Tile::new(TerrainType::Ground, 2).with_cover(1));
+        map.set_tile(3, 4, Tile::new(TerrainType::Ground, 3).with_elevation(3));
+
+        assert!(!map.line_of_sight((0, 0), (6, 0)));
+        assert_eq!(map.cover_between((0, 2), (6, 2)), Cover::Light);
+        assert!(!map.line_of_sight((0, 4), (6, 4)));
+        assert_eq!(map.cover_between((0, 1), (6, 1)), Cover::None);
+        // Whoever stands on the ridge sees down both sides
+        assert!(map.line_of_sight((3, 4), (6, 4)) && map.line_of_sight((0, 4), (3, 4)));
+
+        // No peeking diagonally between two walls
+        map.set_tile(1, 3, Tile::new(TerrainType::Wall, 1));
+        map.set_tile(0, 4, Tile::new(TerrainType::Wall, 1));
+        assert!(!map.line_of_sight((0, 3), (1, 4)));
+    }
+
+    #[test]
+    fn test_fog_of_war_remembers_explored_tiles() {
+        let mut map = GridMap::new(WorldId::new(), "Corridor", 9, 3, "corridor.png");
+        for y in 0..3 {
+            map.set_tile(4, y, Tile::new(TerrainType::Wall, 1));
+        }
+        let pc_id = PlayerCharacterId::new();
+        let mut fog = FogOfWar::default();
+
+        let seen = map.visible_from((1, 1), 2);
+        assert!(seen.contains(&(3, 1)) && seen.contains(&(1, 1)));
+        assert!(!seen.contains(&(3, 0)) && !seen.contains(&(4, 1)));
+        fog.look(pc_id, seen);
+
+        // Beyond the wall nothing is seen, but the old tiles stay explored
+        let sight = fog.look(pc_id, map.visible_from((7, 1), 8)).clone();
+        assert!(sight.visible.contains(&(8, 2)));
+        assert!(!sight.visible.contains(&(1, 1)));
+        assert!(sight.explored.contains(&(1, 1)) && sight.explored.contains(&(8, 2)));
+        assert_eq!(fog.sight(PlayerCharacterId::new()), CharacterSight::default());
+    }
+
+    #[test]
+    fn test_unexplored_tiles_are_walls() {
+        let map = GridMap::new(WorldId::new(), "Vault", 5, 1, "vault.png");
+        let explored: HashSet<GridPosition> = [(0, 0), (1, 0), (2, 0), (4, 0)].into();
+        let known = map.as_explored(&explored);
+
+        assert_eq!(known.get_tile(1, 0), map.get_tile(1, 0));
+        assert_eq!(known.get_tile(3, 0).map(|tile| tile.terrain_type), Some(TerrainType::Wall));
+        assert!(map.find_path((0, 0), (4, 0), DiagonalRule::Uniform, &HashSet::new()).is_some());
+        assert!(known.find_path((0, 0), (4, 0), DiagonalRule::Uniform, &HashSet::new()).is_none());
+        assert_eq!(known.cover_between((0, 0), (4, 0)), Cover::Total);
+    }
+}
@@ -1,12 +1,21 @@
 //! Domain entities - Core business objects with identity
 
+mod advancement;
 mod challenge;
 mod character;
+mod complex_challenge;
+mod encounter;
 mod event_chain;
 mod gallery_asset;
 mod generation_batch;
 mod goal;
+mod grid_area;
+mod grid_generation;
+mod grid_import;
 mod grid_map;
+mod grid_pathfinding;
+mod grid_token;
+mod grid_visibility;
 mod interaction;
 mod item;
 mod location;
@@ -14,7 +23,11 @@ mod narrative_event;
 mod observation;
 mod region;
 mod player_character;
+mod roll_log;
+mod rule_system_definition;
 mod scene;
+mod sheet_migration;
+mod sheet_resource;
 mod sheet_template;
 mod skill;
 mod story_event;
@@ -24,15 +37,27 @@ mod world;
 
 pub use challenge::{
     Challenge, ChallengeLocationAvailability, ChallengeOutcomes, ChallengePrerequisite,
-    ChallengeType, ChallengeUnlock, Difficulty, DifficultyDescriptor, Outcome, OutcomeType,
-    OutcomeTrigger, TriggerCondition, TriggerType,
+    ChallengeType, ChallengeUnlock, ComplexChallengeSettings, Difficulty, DifficultyDescriptor,
+    Outcome, OutcomeType, OutcomeTrigger, TriggerCondition, TriggerType,
 };
 pub use character::{Character, StatBlock};
+pub use complex_challenge::{ComplexChallengeProgress, ComplexChallengeRoll, ComplexChallengeStatus};
+pub use encounter::{
+    Combatant, CombatantRef, CombatantStatus, CombatSide, Encounter, EncounterError, TurnChange,
+};
 pub use event_chain::{ChainStatus, EventChain};
 pub use gallery_asset::{AssetType, EntityType, GalleryAsset, GenerationMetadata};
 pub use generation_batch::{BatchStatus, GenerationBatch, GenerationRequest};
 pub use goal::Goal;
-pub use grid_map::GridMap;
+pub use grid_area::AreaTemplate;
+pub use grid_import::{ImportedMap, MapDoor, MapImage, MapLight};
+pub use grid_generation::{
+    CaveSettings, DungeonSettings, MapGenerator, MapStyle, OutdoorSettings, TilePalette,
+};
+pub use grid_map::{GridMap, GridMapError, TerrainType, Tile};
+pub use grid_pathfinding::{GridPath, GridPosition, ReachableTile};
+pub use grid_token::{Facing, GridToken, TokenPlacementError, TokenSubject};
+pub use grid_visibility::{CharacterSight, Cover, FogOfWar};
 pub use interaction::{
     InteractionCondition, InteractionRequirement, InteractionTarget, InteractionTargetType,
     InteractionTemplate, InteractionType,
@@ -46,13 +71,17 @@ pub use narrative_event::{
     TriggerLogic,
 };
 pub use observation::{NpcObservation, ObservationSummary, ObservationType};
-pub use player_character::PlayerCharacter;
+pub use player_character::{PlayerCharacter, DEFAULT_VISION_RADIUS};
+pub use roll_log::{RollLogEntry, RollPurpose};
+pub use rule_system_definition::{InvalidRuleSystem, RuleSystemDefinition, SkillDefinition};
 pub use scene::{Scene, SceneCharacter, SceneCharacterRole, SceneCondition, TimeContext, TimeOfDay};
+pub use sheet_migration::{ConversionRule, FieldChange, MigrationStep, SheetMigration};
+pub use sheet_resource::{ResourceChange, ResourceError};
 pub use sheet_template::{
-    CharacterSheetData, CharacterSheetTemplate, FieldType, FieldValue, ItemListType,
-    SectionLayout, SelectOption, SheetField, SheetSection, SheetTemplateId,
+    CharacterSheetData, CharacterSheetTemplate, FieldType, FieldValue, InvalidDerivedFields,
+    InvalidSheetData, ItemListType, SectionLayout, SelectOption, SheetField, SheetSection, SheetTemplateId,
 };
-pub use skill::{default_skills_for_variant, Skill, SkillCategory};
+pub use skill::{default_skills_for_world, Skill, SkillCategory};
 pub use story_event::{
     ChallengeEventOutcome, CombatEventType, CombatOutcome, DmMarkerType, InfoImportance, InfoType,
     InvolvedCharacter, ItemSource, MarkerImportance, StoryEvent, StoryEventType,
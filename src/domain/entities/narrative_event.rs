@@ -18,9 +18,12 @@ use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
 use crate::domain::value_objects::{
-    ActId, ChallengeId, CharacterId, EventChainId, LocationId, NarrativeEventId, SceneId, WorldId,
+    ActId, ChallengeId, CharacterId, EventChainId, GridMapId, LocationId, NarrativeEventId, SceneId,
+    WorldId,
 };
 
+use super::AreaTemplate;
+
 /// A narrative event that can be triggered when conditions are met
 ///
 /// # Graph Relationships
@@ -346,6 +349,15 @@ pub enum EventEffect {
         modifier: i32,
     },
 
+    /// Set off a spell or hazard over an area of a grid map
+    AreaEffect {
+        grid_map_id: GridMapId,
+        template: AreaTemplate,
+        description: String,
+        /// Saving throw each player character caught must roll
+        saving_throw: Option<ChallengeId>,
+    },
+
     /// Add experience/reward
     AddReward {
         reward_type: String,
@@ -2,10 +2,13 @@
 
 use chrono::{DateTime, Utc};
 use crate::domain::value_objects::{
-    LocationId, PlayerCharacterId, RegionId, SessionId, WorldId,
+    ActiveConditions, Advancement, LocationId, PlayerCharacterId, RegionId, SessionId, WorldId,
 };
 use crate::domain::entities::sheet_template::CharacterSheetData;
 
+/// How many tiles far a character sees on a grid map unless told otherwise
+pub const DEFAULT_VISION_RADIUS: u32 = 12;
+
 /// A player character (PC) - distinct from NPCs
 ///
 /// PCs are created by players when joining a session, have character sheets,
@@ -33,6 +36,14 @@ pub struct PlayerCharacter {
     
     // Character sheet data (matches CharacterSheetData from Phase 14)
     pub sheet_data: Option<CharacterSheetData>,
+    /// The sheet as it was before the last template migration, for rollback
+    pub sheet_backup: Option<CharacterSheetData>,
+    /// Conditions currently affecting the character
+    pub conditions: ActiveConditions,
+    /// Experience, milestones and the level the DM has approved
+    pub advancement: Advancement,
+    /// How many tiles far the character sees on a grid map
+    pub vision_radius: u32,
     
     // Location tracking
     pub current_location_id: LocationId,
@@ -66,6 +77,10 @@ impl PlayerCharacter {
             name: name.into(),
             description: None,
             sheet_data: None,
+            sheet_backup: None,
+            conditions: ActiveConditions::new(),
+            advancement: Advancement::new(),
+            vision_radius: DEFAULT_VISION_RADIUS,
             current_location_id: starting_location_id,
             current_region_id: None,
             starting_location_id,
@@ -0,0 +1,289 @@
+//! Roll log entity
+//!
+//! Every dice roll made by the engine during a session is recorded with the
+//! seed and sequence index it was drawn from. Because seeded rolls are
+//! deterministic, a disputed roll can be replayed from its entry and compared
+//! against what was reported at the table. A roll mode the DM grants after
+//! the fact is recorded as its own entry, replayed from the roll it was
+//! granted on and the extra dice drawn for it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::value_objects::{
+    DiceFormula, DiceRollResult, RollId, RollMode, SeededRollSource, SessionId,
+};
+
+/// Why a roll was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollPurpose {
+    /// A player rolling against a challenge
+    Challenge,
+    /// A free-standing roll (damage, a DM's secret check, ...)
+    AdHoc,
+    /// A roll on a random table
+    Table,
+    /// A combatant's initiative roll
+    Initiative,
+}
+
+impl RollPurpose {
+    /// Get a display name for the roll purpose
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RollPurpose::Challenge => "Challenge",
+            RollPurpose::AdHoc => "Ad-hoc",
+            RollPurpose::Table => "Table",
+            RollPurpose::Initiative => "Initiative",
+        }
+    }
+
+    /// Identifier used when storing the purpose
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RollPurpose::Challenge => "challenge",
+            RollPurpose::AdHoc => "ad_hoc",
+            RollPurpose::Table => "table",
+            RollPurpose::Initiative => "initiative",
+        }
+    }
+}
+
+impl std::fmt::Display for RollPurpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+impl std::str::FromStr for RollPurpose {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "challenge" => Ok(RollPurpose::Challenge),
+            "ad_hoc" | "adhoc" => Ok(RollPurpose::AdHoc),
+            "table" => Ok(RollPurpose::Table),
+            "initiative" => Ok(RollPurpose::Initiative),
+            _ => Err(anyhow::anyhow!("Invalid roll purpose: {}", s)),
+        }
+    }
+}
+
+/// An audited record of a single engine roll
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollLogEntry {
+    pub id: RollId,
+    /// Session the roll was made in
+    pub session_id: SessionId,
+    pub purpose: RollPurpose,
+    /// What was rolled for (challenge name, table name, free text)
+    pub label: Option<String>,
+    /// User ID of whoever asked for the roll
+    pub roller: String,
+    /// The formula as rolled, including any skill modifier or pool bonus
+    pub formula: String,
+    /// Session seed the dice were drawn from
+    pub seed: u64,
+    /// Position of this roll in the session's sequence
+    pub sequence: u64,
+    /// Every die rolled in order, including dropped and rerolled dice
+    pub dice: Vec<i32>,
+    pub total: i32,
+    /// Human-readable breakdown (e.g., "4d6kh3[6, 5, 4, (2)] = 15")
+    pub breakdown: String,
+    pub rolled_at: DateTime<Utc>,
+    /// The roll this one granted a roll mode on, if it did
+    ///
+    /// Only the extra dice are drawn from this entry's sequence; the rest
+    /// are replayed from the original roll.
+    pub granted: Option<GrantedRoll>,
+}
+
+/// A roll mode granted on an earlier roll
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrantedRoll {
+    pub mode: RollMode,
+    /// Entry of the roll the mode was granted on
+    pub base_id: RollId,
+    /// Formula of the roll the mode was granted on
+    pub base_formula: String,
+    /// Sequence index of the roll the mode was granted on
+    pub base_sequence: u64,
+}
+
+impl RollLogEntry {
+    /// Record a roll drawn from `source`
+    ///
+    /// Returns None for manual results, which have no dice to audit.
+    pub fn record(
+        session_id: SessionId,
+        purpose: RollPurpose,
+        label: Option<String>,
+        roller: String,
+        source: &SeededRollSource,
+        result: &DiceRollResult,
+    ) -> Option<Self> {
+        let formula = result.formula.as_ref()?;
+        Some(Self {
+            id: RollId::new(),
+            session_id,
+            purpose,
+            label,
+            roller,
+            formula: formula.display(),
+            seed: source.seed(),
+            sequence: source.sequence(),
+            dice: all_dice(result),
+            total: result.total,
+            breakdown: result.breakdown(),
+            rolled_at: Utc::now(),
+            granted: None,
+        })
+    }
+
+    /// Record `mode` granted on the roll `base`, its extra dice drawn from
+    /// `source`
+    ///
+    /// `result` is the original roll re-resolved with the extra dice.
+    pub fn record_granted(
+        base: &RollLogEntry,
+        mode: RollMode,
+        source: &SeededRollSource,
+        result: &DiceRollResult,
+    ) -> Option<Self> {
+        let mut entry = Self::record(
+            base.session_id,
+            base.purpose,
+            base.label.clone(),
+            base.roller.clone(),
+            source,
+            result,
+        )?;
+        entry.granted = Some(GrantedRoll {
+            mode,
+            base_id: base.id,
+            base_formula: base.formula.clone(),
+            base_sequence: base.sequence,
+        });
+        Some(entry)
+    }
+
+    /// Replay the roll from its seed and sequence index
+    pub fn replay(&self) -> Option<DiceRollResult> {
+        let Some(granted) = &self.granted else {
+            let formula = DiceFormula::parse(&self.formula).ok()?;
+            return Some(formula.roll_from(&mut SeededRollSource::new(self.seed, self.sequence)));
+        };
+        let base = DiceFormula::parse(&granted.base_formula)
+            .ok()?
+            .roll_from(&mut SeededRollSource::new(self.seed, granted.base_sequence));
+        let extra = base
+            .granted_dice(granted.mode)
+            .ok()?
+            .roll_from(&mut SeededRollSource::new(self.seed, self.sequence));
+        base.with_granted_mode(granted.mode, &extra.individual_rolls).ok()
+    }
+
+    /// Check that replaying the roll reproduces the recorded dice and total
+    pub fn verify(&self) -> bool {
+        self.replay()
+            .map(|replayed| all_dice(&replayed) == self.dice && replayed.total == self.total)
+            .unwrap_or(false)
+    }
+}
+
+/// Every die in a result, in evaluation order
+fn all_dice(result: &DiceRollResult) -> Vec<i32> {
+    result
+        .terms
+        .iter()
+        .flat_map(|term| term.rolls.iter().map(|r| r.value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::DiceRollInput;
+
+    fn logged(formula: &str, modifier: i32) -> RollLogEntry {
+        let mut source = SeededRollSource::new(0xD1CE, 3);
+        let result = DiceRollInput::Formula(formula.to_string())
+            .resolve_with_modifier_from(modifier, &mut source)
+            .unwrap();
+        RollLogEntry::record(
+            SessionId::new(),
+            RollPurpose::Challenge,
+            None,
+            "player-1".to_string(),
+            &source,
+            &result,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_recorded_rolls_verify() {
+        for (formula, modifier) in [("1d20", 4), ("4d6kh3", 0), ("6d10>8f1!", 2), ("4dF", -1)] {
+            let entry = logged(formula, modifier);
+            assert!(entry.verify(), "{} did not replay", entry.formula);
+        }
+    }
+
+    #[test]
+    fn test_tampered_roll_fails_verification() {
+        let mut entry = logged("2d6", 1);
+        entry.total += 1;
+        assert!(!entry.verify());
+
+        let mut entry = logged("2d6", 1);
+        entry.dice[0] = 7;
+        assert!(!entry.verify());
+    }
+
+    #[test]
+    fn test_granted_rolls_verify() {
+        let base = logged("1d20", 3);
+        let original = base.replay().unwrap();
+        let source = SeededRollSource::new(base.seed, 4);
+        let extra = original
+            .granted_dice(RollMode::Advantage)
+            .unwrap()
+            .roll_from(&mut source.clone());
+        let result = original
+            .with_granted_mode(RollMode::Advantage, &extra.individual_rolls)
+            .unwrap();
+
+        let entry = RollLogEntry::record_granted(&base, RollMode::Advantage, &source, &result).unwrap();
+        assert_eq!(entry.granted.as_ref().unwrap().base_id, base.id);
+        assert_eq!(entry.dice.len(), 2);
+        assert!(entry.verify());
+
+        let elsewhere = SeededRollSource::new(base.seed, 5);
+        let misplaced = RollLogEntry::record_granted(&base, RollMode::Advantage, &elsewhere, &result).unwrap();
+        assert!(!misplaced.verify());
+    }
+
+    #[test]
+    fn test_manual_results_are_not_logged() {
+        let source = SeededRollSource::new(1, 0);
+        let result = DiceRollResult::from_manual(17);
+        let entry = RollLogEntry::record(
+            SessionId::new(),
+            RollPurpose::Challenge,
+            None,
+            "player-1".to_string(),
+            &source,
+            &result,
+        );
+        assert!(entry.is_none());
+    }
+
+    #[test]
+    fn test_purpose_round_trips_through_str() {
+        for purpose in [RollPurpose::Challenge, RollPurpose::AdHoc, RollPurpose::Table] {
+            assert_eq!(purpose.as_str().parse::<RollPurpose>().unwrap(), purpose);
+        }
+    }
+}
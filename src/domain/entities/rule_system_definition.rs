@@ -0,0 +1,333 @@
+//! User-defined rule systems
+//!
+//! Homebrew systems are uploaded as a declarative definition and stored on
+//! their world. A definition supplies everything a preset gets from its
+//! variant: the world's `RuleSystemConfig`, the default skill list and the
+//! default character sheet template.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::domain::value_objects::{
+    DescriptorDifficulties, DiceFormula, DiceSystem, OpposedTieBreak, ProgressionTable, ResolutionBands,
+    RestType, RuleSystemConfig, RuleSystemType, RuleSystemVariant, StatDefinition, SuccessComparison,
+    WorldId,
+};
+
+use super::{CharacterSheetTemplate, SheetSection, Skill, SkillCategory};
+
+/// A complete user-defined rule system
+#[derive(Debug, Clone)]
+pub struct RuleSystemDefinition {
+    pub name: String,
+    pub description: String,
+    /// Dice rolled for a check
+    pub dice_system: DiceSystem,
+    pub success_comparison: SuccessComparison,
+    /// Degrees of success
+    pub resolution: ResolutionBands,
+    /// Who wins a tied opposed check
+    pub tie_break: OpposedTieBreak,
+    /// Formula for skill checks (display only)
+    pub skill_check_formula: String,
+    pub stat_definitions: Vec<StatDefinition>,
+    pub skills: Vec<SkillDefinition>,
+    /// Sections of the default character sheet; empty for a sheet generated
+    /// from the stats and skills
+    pub sheet_sections: Vec<SheetSection>,
+    pub descriptor_difficulties: DescriptorDifficulties,
+    /// Rests characters can take and what they recover
+    pub rest_types: Vec<RestType>,
+    /// How characters advance; None for a system without levels
+    pub progression: Option<ProgressionTable>,
+}
+
+/// A skill declared by a rule system definition
+#[derive(Debug, Clone)]
+pub struct SkillDefinition {
+    pub name: String,
+    pub description: String,
+    pub category: SkillCategory,
+    /// Abbreviation of the stat the skill derives from
+    pub base_attribute: Option<String>,
+}
+
+/// Problems found while validating a rule system definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRuleSystem(pub Vec<String>);
+
+impl fmt::Display for InvalidRuleSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid rule system definition: {}", self.0.join("; "))
+    }
+}
+
+impl std::error::Error for InvalidRuleSystem {}
+
+impl RuleSystemDefinition {
+    /// Check the definition is internally consistent, reporting every problem
+    pub fn validate(&self) -> Result<(), InvalidRuleSystem> {
+        let mut problems = Vec::new();
+
+        if self.name.trim().is_empty() {
+            problems.push("name must not be empty".to_string());
+        }
+
+        match &self.dice_system {
+            DiceSystem::Custom(expression) => {
+                if let Err(e) = DiceFormula::parse(expression) {
+                    problems.push(format!("dice '{}' is not a valid expression: {}", expression, e));
+                }
+            }
+            DiceSystem::DicePool {
+                die_type,
+                success_threshold,
+                again_threshold,
+                ..
+            } => {
+                if *die_type < 2 {
+                    problems.push("dice pool die type must be at least 2".to_string());
+                }
+                if *success_threshold == 0 || success_threshold > die_type {
+                    problems.push(format!(
+                        "dice pool success threshold must be between 1 and {}",
+                        die_type
+                    ));
+                }
+                if again_threshold.is_some_and(|again| again <= 1 || again > *die_type) {
+                    problems.push(format!("dice pool again threshold must be between 2 and {}", die_type));
+                }
+            }
+            DiceSystem::D20 | DiceSystem::D100 | DiceSystem::Fate => {}
+        }
+
+        match self.resolution {
+            ResolutionBands::Degrees { critical_margin } if critical_margin <= 0 => {
+                problems.push("degrees of success need a positive critical margin".to_string());
+            }
+            ResolutionBands::Bands { partial_at, success_at } if partial_at > success_at => {
+                problems.push("partial success band must not start above full success".to_string());
+            }
+            ResolutionBands::RollUnder if self.success_comparison != SuccessComparison::LessOrEqual => {
+                problems.push("roll-under degrees of success need the less_or_equal comparison".to_string());
+            }
+            _ => {}
+        }
+
+        let mut abbreviations = HashSet::new();
+        for stat in &self.stat_definitions {
+            if stat.name.trim().is_empty() || stat.abbreviation.trim().is_empty() {
+                problems.push("stats need a name and an abbreviation".to_string());
+            } else if !abbreviations.insert(stat.abbreviation.to_uppercase()) {
+                problems.push(format!("stat abbreviation '{}' is used twice", stat.abbreviation));
+            }
+            if stat.min_value > stat.max_value
+                || stat.default_value < stat.min_value
+                || stat.default_value > stat.max_value
+            {
+                problems.push(format!(
+                    "stat '{}' needs min <= default <= max",
+                    stat.abbreviation
+                ));
+            }
+        }
+
+        let mut skill_names = HashSet::new();
+        for skill in &self.skills {
+            if skill.name.trim().is_empty() {
+                problems.push("skills need a name".to_string());
+            } else if !skill_names.insert(skill.name.to_lowercase()) {
+                problems.push(format!("skill '{}' is defined twice", skill.name));
+            }
+            if let Some(attribute) = &skill.base_attribute {
+                if !abbreviations.contains(&attribute.to_uppercase()) {
+                    problems.push(format!(
+                        "skill '{}' is based on unknown stat '{}'",
+                        skill.name, attribute
+                    ));
+                }
+            }
+        }
+
+        let mut section_ids = HashSet::new();
+        let mut field_ids = HashSet::new();
+        for section in &self.sheet_sections {
+            if !section_ids.insert(section.id.as_str()) {
+                problems.push(format!("sheet section '{}' is defined twice", section.id));
+            }
+            for field in &section.fields {
+                if !field_ids.insert(field.id.as_str()) {
+                    problems.push(format!("sheet field '{}' is defined twice", field.id));
+                }
+            }
+        }
+
+        let mut rest_ids = HashSet::new();
+        for rest in &self.rest_types {
+            if rest.id.trim().is_empty() || rest.name.trim().is_empty() {
+                problems.push("rests need an id and a name".to_string());
+            } else if !rest_ids.insert(rest.id.as_str()) {
+                problems.push(format!("rest '{}' is defined twice", rest.id));
+            }
+        }
+
+        if let Some(progression) = &self.progression {
+            problems.extend(progression.problems());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidRuleSystem(problems))
+        }
+    }
+
+    /// The variant a world using this definition records
+    pub fn variant(&self) -> RuleSystemVariant {
+        RuleSystemVariant::Custom(self.name.clone())
+    }
+
+    /// Whether a world's rule system configuration came from this definition
+    pub fn defines(&self, config: &RuleSystemConfig) -> bool {
+        config.variant == self.variant()
+    }
+
+    /// The rule system configuration a world using this definition runs on
+    pub fn to_config(&self) -> RuleSystemConfig {
+        let system_type = match (&self.dice_system, self.success_comparison) {
+            (_, SuccessComparison::LessOrEqual) | (DiceSystem::D100, _) => RuleSystemType::D100,
+            (_, SuccessComparison::Narrative) => RuleSystemType::Narrative,
+            (DiceSystem::D20, _) => RuleSystemType::D20,
+            _ => RuleSystemType::Custom,
+        };
+        RuleSystemConfig {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            system_type,
+            variant: self.variant(),
+            stat_definitions: self.stat_definitions.clone(),
+            dice_system: self.dice_system.clone(),
+            success_comparison: self.success_comparison,
+            skill_check_formula: self.skill_check_formula.clone(),
+            descriptor_difficulties: self.descriptor_difficulties,
+            custom_resolution: Some(self.resolution),
+            custom_tie_break: Some(self.tie_break),
+            custom_rest_types: Some(self.rest_types.clone()),
+            custom_progression: self.progression.clone(),
+        }
+    }
+
+    /// The default skills for a world using this definition
+    pub fn default_skills(&self, world_id: WorldId) -> Vec<Skill> {
+        self.skills
+            .iter()
+            .enumerate()
+            .map(|(order, definition)| {
+                let skill = Skill::new(world_id, definition.name.clone(), definition.category)
+                    .with_description(definition.description.clone())
+                    .with_order(order as u32 + 1);
+                match &definition.base_attribute {
+                    Some(attribute) => skill.with_base_attribute(attribute.clone()),
+                    None => skill,
+                }
+            })
+            .collect()
+    }
+
+    /// The default character sheet for a world using this definition
+    ///
+    /// Without declared sections the sheet lists the stats and skills.
+    pub fn default_sheet_template(&self, world_id: WorldId) -> CharacterSheetTemplate {
+        let template = CharacterSheetTemplate::new(
+            world_id,
+            format!("{} Character Sheet", self.name),
+            self.variant(),
+        )
+        .with_description(self.description.clone())
+        .as_default();
+
+        if self.sheet_sections.is_empty() {
+            return template.with_generated_sections(&self.stat_definitions);
+        }
+        self.sheet_sections
+            .iter()
+            .cloned()
+            .fold(template, CharacterSheetTemplate::with_section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{FieldType, SheetField};
+
+    fn definition() -> RuleSystemDefinition {
+        RuleSystemDefinition {
+            name: "Blades".to_string(),
+            description: "Heists in a haunted city".to_string(),
+            dice_system: DiceSystem::Custom("2d6".to_string()),
+            success_comparison: SuccessComparison::Narrative,
+            resolution: ResolutionBands::Bands {
+                partial_at: 4,
+                success_at: 6,
+            },
+            tie_break: OpposedTieBreak::Partial,
+            skill_check_formula: "2d6 + action".to_string(),
+            stat_definitions: vec![StatDefinition::new("Prowess", "PRW", 0, 4, 1)],
+            skills: vec![SkillDefinition {
+                name: "Skirmish".to_string(),
+                description: String::new(),
+                category: SkillCategory::Physical,
+                base_attribute: Some("prw".to_string()),
+            }],
+            sheet_sections: vec![],
+            descriptor_difficulties: DescriptorDifficulties::position_effect(),
+            rest_types: vec![],
+            progression: None,
+        }
+    }
+
+    #[test]
+    fn test_definition_supplies_config_skills_and_sheet() {
+        let definition = definition();
+        assert!(definition.validate().is_ok());
+
+        let config = definition.to_config();
+        assert_eq!(config.variant, RuleSystemVariant::Custom("Blades".to_string()));
+        assert_eq!(config.system_type, RuleSystemType::Narrative);
+        assert!(definition.defines(&config));
+        assert_eq!(
+            config.resolution_bands(),
+            ResolutionBands::Bands {
+                partial_at: 4,
+                success_at: 6
+            }
+        );
+        assert_eq!(config.opposed_tie_break(), OpposedTieBreak::Partial);
+
+        let world_id = WorldId::new();
+        let skills = definition.default_skills(world_id);
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].base_attribute.as_deref(), Some("prw"));
+
+        let template = definition.default_sheet_template(world_id);
+        assert!(template.is_default);
+        assert!(template.get_field("PRW").is_some());
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let mut definition = definition();
+        definition.dice_system = DiceSystem::Custom("2q6".to_string());
+        definition.skills[0].base_attribute = Some("GRT".to_string());
+        definition.sheet_sections = vec![
+            SheetSection::new("main", "Main")
+                .with_field(SheetField::new("HP", "Health", FieldType::Checkbox { default: false })),
+            SheetSection::new("main", "Again")
+                .with_field(SheetField::new("HP", "Health", FieldType::Checkbox { default: false })),
+        ];
+
+        let InvalidRuleSystem(problems) = definition.validate().unwrap_err();
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+    }
+}
@@ -0,0 +1,423 @@
+//! Sheet template migrations - Carrying character sheets across template edits
+//!
+//! Every edit to a sheet template bumps its version and records a
+//! `SheetMigration`: the fields renamed, removed, added or retyped between the
+//! two versions. Migrations stay pending on the template until they are applied
+//! to the characters of its world. Sheets record the template version they
+//! were saved under, so a migration skips sheets that already fit its target.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::{CharacterSheetData, CharacterSheetTemplate, FieldType, FieldValue, SheetField};
+
+/// The changes between two versions of a sheet template
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetMigration {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// Steps in the order they are applied: renames, removals, conversions,
+    /// additions
+    pub steps: Vec<MigrationStep>,
+}
+
+/// One change to the fields of a character sheet
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationStep {
+    /// A field was given a new ID; its value moves with it
+    Rename { from: String, to: String },
+    /// A field was removed; its value is dropped
+    Remove { field_id: String },
+    /// A field changed type; its value is converted
+    Convert {
+        field_id: String,
+        rule: ConversionRule,
+    },
+    /// A field was added; sheets without a value get its default
+    Add {
+        field_id: String,
+        default: Option<FieldValue>,
+    },
+}
+
+/// How a value is converted when its field changes type
+///
+/// Values that do not survive a conversion are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionRule {
+    /// The number written out as text
+    NumberToText,
+    /// Text parsed as a whole number
+    TextToNumber,
+    /// A number becomes a full resource (current = max)
+    NumberToResource,
+    /// A resource keeps its current value
+    ResourceToNumber,
+    /// Checked is 1, unchecked 0
+    CheckboxToNumber,
+    /// Any non-zero number is checked
+    NumberToCheckbox,
+    /// The value is kept if it names one of the options
+    ToSelectOption { options: Vec<String> },
+    /// Text becomes a one-item list
+    TextToList,
+    /// The value cannot be carried over (including fields that became derived)
+    Discard,
+}
+
+impl ConversionRule {
+    /// The rule for a field changing type, or None when the type is unchanged
+    pub fn between(from: &FieldType, to: &FieldType) -> Option<Self> {
+        if std::mem::discriminant(from) == std::mem::discriminant(to) {
+            return None;
+        }
+        Some(match (from, to) {
+            (FieldType::Number { .. }, FieldType::Text { .. }) => Self::NumberToText,
+            (FieldType::Text { .. }, FieldType::Number { .. }) => Self::TextToNumber,
+            (FieldType::Number { .. }, FieldType::Resource { .. }) => Self::NumberToResource,
+            (FieldType::Resource { .. }, FieldType::Number { .. }) => Self::ResourceToNumber,
+            (FieldType::Checkbox { .. }, FieldType::Number { .. }) => Self::CheckboxToNumber,
+            (FieldType::Number { .. }, FieldType::Checkbox { .. }) => Self::NumberToCheckbox,
+            (FieldType::Text { .. } | FieldType::Number { .. }, FieldType::Select { options }) => {
+                Self::ToSelectOption {
+                    options: options.iter().map(|o| o.value.clone()).collect(),
+                }
+            }
+            (FieldType::Text { .. }, FieldType::ItemList { .. }) => Self::TextToList,
+            _ => Self::Discard,
+        })
+    }
+
+    /// Convert a value, or None if it does not survive
+    pub fn convert(&self, value: FieldValue) -> Option<FieldValue> {
+        match (self, value) {
+            (Self::NumberToText, FieldValue::Number(n)) => Some(FieldValue::Text(n.to_string())),
+            (Self::TextToNumber, FieldValue::Text(s)) => s.trim().parse().ok().map(FieldValue::Number),
+            (Self::NumberToResource, FieldValue::Number(n)) => {
+                Some(FieldValue::Resource { current: n, max: n })
+            }
+            (Self::ResourceToNumber, FieldValue::Resource { current, .. }) => {
+                Some(FieldValue::Number(current))
+            }
+            (Self::CheckboxToNumber, FieldValue::Boolean(b)) => Some(FieldValue::Number(b as i32)),
+            (Self::NumberToCheckbox, FieldValue::Number(n)) => Some(FieldValue::Boolean(n != 0)),
+            (Self::ToSelectOption { options }, value) => {
+                let choice = match value {
+                    FieldValue::Text(s) => s,
+                    FieldValue::Number(n) => n.to_string(),
+                    _ => return None,
+                };
+                options.contains(&choice).then_some(FieldValue::Text(choice))
+            }
+            (Self::TextToList, FieldValue::Text(s)) if !s.trim().is_empty() => {
+                Some(FieldValue::List(vec![s]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// How one value of a character sheet changes in a migration
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field_id: String,
+    pub before: Option<FieldValue>,
+    pub after: Option<FieldValue>,
+}
+
+impl SheetMigration {
+    /// Plan the migration from one version of a template to the next
+    ///
+    /// `renames` maps old field IDs to new ones. A removed field and an added
+    /// field with the same display name are also treated as a rename.
+    pub fn plan(
+        from: &CharacterSheetTemplate,
+        to: &CharacterSheetTemplate,
+        renames: &HashMap<String, String>,
+    ) -> Self {
+        let old_fields = fields_by_id(from);
+        let new_fields = fields_by_id(to);
+
+        let mut renamed: Vec<(&str, &str)> = renames
+            .iter()
+            .filter(|(old, new)| {
+                old_fields.contains_key(old.as_str())
+                    && !new_fields.contains_key(old.as_str())
+                    && new_fields.contains_key(new.as_str())
+                    && !old_fields.contains_key(new.as_str())
+            })
+            .map(|(old, new)| (old.as_str(), new.as_str()))
+            .collect();
+        renamed.sort();
+
+        // Infer renames from display names the edit kept
+        let removed: Vec<&SheetField> = from
+            .sections
+            .iter()
+            .flat_map(|s| s.fields.iter())
+            .filter(|f| !new_fields.contains_key(f.id.as_str()))
+            .filter(|f| !renamed.iter().any(|(old, _)| *old == f.id))
+            .collect();
+        let added: Vec<&SheetField> = to
+            .sections
+            .iter()
+            .flat_map(|s| s.fields.iter())
+            .filter(|f| !old_fields.contains_key(f.id.as_str()))
+            .filter(|f| !renamed.iter().any(|(_, new)| *new == f.id))
+            .collect();
+        for old in &removed {
+            let mut same_name = added.iter().filter(|new| new.name.eq_ignore_ascii_case(&old.name));
+            if let (Some(new), None) = (same_name.next(), same_name.next()) {
+                if !renamed.iter().any(|(_, taken)| *taken == new.id) {
+                    renamed.push((old.id.as_str(), new.id.as_str()));
+                }
+            }
+        }
+
+        let mut steps: Vec<MigrationStep> = renamed
+            .iter()
+            .map(|(old, new)| MigrationStep::Rename {
+                from: old.to_string(),
+                to: new.to_string(),
+            })
+            .collect();
+        for old in &removed {
+            if !renamed.iter().any(|(renamed_from, _)| *renamed_from == old.id) {
+                steps.push(MigrationStep::Remove {
+                    field_id: old.id.clone(),
+                });
+            }
+        }
+        for field in to.sections.iter().flat_map(|s| s.fields.iter()) {
+            let previous = old_fields.get(field.id.as_str()).or_else(|| {
+                renamed
+                    .iter()
+                    .find(|(_, new)| *new == field.id)
+                    .and_then(|(old, _)| old_fields.get(old))
+            });
+            if let Some(rule) = previous.and_then(|p| ConversionRule::between(&p.field_type, &field.field_type)) {
+                steps.push(MigrationStep::Convert {
+                    field_id: field.id.clone(),
+                    rule,
+                });
+            }
+        }
+        for new in &added {
+            if !renamed.iter().any(|(_, renamed_to)| *renamed_to == new.id) {
+                steps.push(MigrationStep::Add {
+                    field_id: new.id.clone(),
+                    default: new.field_type.default_value(),
+                });
+            }
+        }
+
+        Self {
+            from_version: from.version,
+            to_version: to.version,
+            steps,
+        }
+    }
+
+    /// Whether the migration changes nothing
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The sheet as it is after this migration
+    ///
+    /// Sheets saved under the target version or a later one are returned
+    /// unchanged.
+    pub fn apply(&self, sheet: &CharacterSheetData) -> CharacterSheetData {
+        if sheet.template_version.is_some_and(|version| version >= self.to_version) {
+            return sheet.clone();
+        }
+        let mut values = sheet.values.clone();
+
+        // Renames move together, so swapped IDs do not clobber each other
+        let moved: Vec<(String, FieldValue)> = self
+            .steps
+            .iter()
+            .filter_map(|step| match step {
+                MigrationStep::Rename { from, to } => values.remove(from).map(|v| (to.clone(), v)),
+                _ => None,
+            })
+            .collect();
+        values.extend(moved);
+
+        for step in &self.steps {
+            match step {
+                MigrationStep::Rename { .. } => {}
+                MigrationStep::Remove { field_id } => {
+                    values.remove(field_id);
+                }
+                MigrationStep::Convert { field_id, rule } => {
+                    if let Some(converted) = values.remove(field_id).and_then(|v| rule.convert(v)) {
+                        values.insert(field_id.clone(), converted);
+                    }
+                }
+                MigrationStep::Add { field_id, default } => {
+                    if let Some(default) = default {
+                        values.entry(field_id.clone()).or_insert_with(|| default.clone());
+                    }
+                }
+            }
+        }
+
+        CharacterSheetData {
+            values,
+            template_version: Some(self.to_version),
+        }
+    }
+
+    /// Apply a chain of migrations in order
+    pub fn apply_all(migrations: &[SheetMigration], sheet: &CharacterSheetData) -> CharacterSheetData {
+        migrations
+            .iter()
+            .fold(sheet.clone(), |sheet, migration| migration.apply(&sheet))
+    }
+
+    /// Every value that differs between two versions of a sheet, by field ID
+    pub fn changes(before: &CharacterSheetData, after: &CharacterSheetData) -> Vec<FieldChange> {
+        let ids: BTreeSet<&String> = before.values.keys().chain(after.values.keys()).collect();
+        ids.into_iter()
+            .filter_map(|id| {
+                let (old, new) = (before.values.get(id), after.values.get(id));
+                (old != new).then(|| FieldChange {
+                    field_id: id.clone(),
+                    before: old.cloned(),
+                    after: new.cloned(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn fields_by_id(template: &CharacterSheetTemplate) -> HashMap<&str, &SheetField> {
+    template
+        .sections
+        .iter()
+        .flat_map(|s| s.fields.iter())
+        .map(|f| (f.id.as_str(), f))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{SelectOption, SheetSection};
+    use crate::domain::value_objects::{RuleSystemVariant, WorldId};
+
+    fn template(fields: Vec<SheetField>) -> CharacterSheetTemplate {
+        let section = fields
+            .into_iter()
+            .fold(SheetSection::new("main", "Main"), SheetSection::with_field);
+        CharacterSheetTemplate::new(WorldId::new(), "Sheet", RuleSystemVariant::GenericD20).with_section(section)
+    }
+
+    fn number(id: &str, name: &str) -> SheetField {
+        SheetField::new(id, name, FieldType::Number { min: None, max: None, default: Some(1) })
+    }
+
+    fn text(id: &str, name: &str) -> SheetField {
+        SheetField::new(id, name, FieldType::Text { multiline: false, max_length: None })
+    }
+
+    #[test]
+    fn test_plan_detects_renames_removals_conversions_and_additions() {
+        let before = template(vec![
+            number("STR", "Strength"),
+            number("GRIT", "Grit"),
+            text("CLASS", "Class"),
+            number("OLD_HP", "Health"),
+            text("NOTES", "Notes"),
+        ]);
+        let mut after = template(vec![
+            number("MIGHT", "Might"),
+            number("HEALTH", "Health"),
+            SheetField::new("CLASS", "Class", FieldType::Select {
+                options: vec![SelectOption::new("fighter", "Fighter")],
+            }),
+            number("LUCK", "Luck"),
+            text("NOTES", "Notes"),
+        ]);
+        after.version = 2;
+
+        let renames = HashMap::from([("STR".to_string(), "MIGHT".to_string())]);
+        let migration = SheetMigration::plan(&before, &after, &renames);
+        assert_eq!((migration.from_version, migration.to_version), (1, 2));
+        assert_eq!(
+            migration.steps,
+            vec![
+                MigrationStep::Rename { from: "STR".into(), to: "MIGHT".into() },
+                MigrationStep::Rename { from: "OLD_HP".into(), to: "HEALTH".into() },
+                MigrationStep::Remove { field_id: "GRIT".into() },
+                MigrationStep::Convert {
+                    field_id: "CLASS".into(),
+                    rule: ConversionRule::ToSelectOption { options: vec!["fighter".into()] },
+                },
+                MigrationStep::Add { field_id: "LUCK".into(), default: Some(FieldValue::Number(1)) },
+            ]
+        );
+
+        let mut sheet = CharacterSheetData::new();
+        sheet.set("STR", FieldValue::Number(14));
+        sheet.set("GRIT", FieldValue::Number(3));
+        sheet.set("CLASS", FieldValue::Text("wizard".into()));
+        sheet.set("OLD_HP", FieldValue::Number(9));
+        sheet.set("NOTES", FieldValue::Text("brave".into()));
+
+        let migrated = migration.apply(&sheet);
+        assert_eq!(migrated.get_number("MIGHT"), Some(14));
+        assert_eq!(migrated.get_number("HEALTH"), Some(9));
+        assert_eq!(migrated.get_number("LUCK"), Some(1));
+        assert!(migrated.get("GRIT").is_none());
+        assert!(migrated.get("CLASS").is_none(), "wizard is not an option");
+
+        assert_eq!(migrated.template_version, Some(2));
+
+        let changes = SheetMigration::changes(&sheet, &migrated);
+        let ids: Vec<&str> = changes.iter().map(|c| c.field_id.as_str()).collect();
+        assert_eq!(ids, vec!["CLASS", "GRIT", "HEALTH", "LUCK", "MIGHT", "OLD_HP", "STR"]);
+    }
+
+    #[test]
+    fn test_sheets_saved_under_the_new_version_are_left_alone() {
+        let before = template(vec![number("HP", "Health")]);
+        let mut after = template(vec![SheetField::new(
+            "HP",
+            "Health",
+            FieldType::Resource { max_field: None, default_max: None },
+        )]);
+        after.version = 2;
+        let migration = SheetMigration::plan(&before, &after, &HashMap::new());
+
+        let mut sheet = CharacterSheetData::new();
+        sheet.set("HP", FieldValue::Resource { current: 4, max: 10 });
+        sheet.template_version = Some(2);
+        assert_eq!(migration.apply(&sheet), sheet);
+
+        sheet.template_version = Some(1);
+        sheet.set("HP", FieldValue::Number(10));
+        assert_eq!(
+            migration.apply(&sheet).get("HP"),
+            Some(&FieldValue::Resource { current: 10, max: 10 })
+        );
+    }
+
+    #[test]
+    fn test_conversion_rules() {
+        let number = FieldType::Number { min: None, max: None, default: None };
+        let resource = FieldType::Resource { max_field: None, default_max: None };
+        assert_eq!(ConversionRule::between(&number, &number), None);
+
+        let rule = ConversionRule::between(&number, &resource).unwrap();
+        assert_eq!(
+            rule.convert(FieldValue::Number(7)),
+            Some(FieldValue::Resource { current: 7, max: 7 })
+        );
+        let rule = ConversionRule::between(&FieldType::Text { multiline: true, max_length: None }, &number).unwrap();
+        assert_eq!(rule.convert(FieldValue::Text(" 12 ".into())), Some(FieldValue::Number(12)));
+        assert_eq!(rule.convert(FieldValue::Text("twelve".into())), None);
+        let derived = FieldType::Derived { formula: "1".into() };
+        assert_eq!(ConversionRule::between(&number, &derived), Some(ConversionRule::Discard));
+    }
+}
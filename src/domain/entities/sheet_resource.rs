@@ -0,0 +1,204 @@
+//! Sheet resources - Spending, restoring and resting on character sheets
+//!
+//! A resource is any `Resource` or `Number` field of a sheet template. Its
+//! bounds come from the template: a resource runs from 0 to its max (read
+//! from `max_field` when the sheet has it), a number between its min and max.
+
+use std::fmt;
+
+use crate::domain::value_objects::{ResourceLevel, ResourceOperation, RestType};
+
+use super::{CharacterSheetData, CharacterSheetTemplate, FieldType, FieldValue};
+
+/// How one resource changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceChange {
+    pub field_id: String,
+    pub field_name: String,
+    pub previous: i32,
+    pub current: i32,
+}
+
+/// Why a resource operation was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceError {
+    /// The template has no field with this ID
+    UnknownField(String),
+    /// The field is not a resource or number
+    NotAResource(String),
+    /// A spend asked for more than is left
+    Insufficient { field_id: String, available: i32, requested: u32 },
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField(id) => write!(f, "Sheet has no field '{}'", id),
+            Self::NotAResource(id) => write!(f, "Field '{}' is not a resource", id),
+            Self::Insufficient {
+                field_id,
+                available,
+                requested,
+            } => write!(
+                f,
+                "Cannot spend {} of '{}': only {} left",
+                requested, field_id, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResourceError {}
+
+impl CharacterSheetTemplate {
+    /// Where a resource stands on a sheet
+    pub fn resource_level(
+        &self,
+        data: &CharacterSheetData,
+        field_id: &str,
+    ) -> Result<ResourceLevel, ResourceError> {
+        let field = self
+            .get_field(field_id)
+            .ok_or_else(|| ResourceError::UnknownField(field_id.to_string()))?;
+
+        match &field.field_type {
+            FieldType::Resource {
+                max_field,
+                default_max,
+            } => {
+                let stored = match data.get(field_id) {
+                    Some(FieldValue::Resource { current, max }) => Some((*current, *max)),
+                    _ => None,
+                };
+                let max = max_field
+                    .as_deref()
+                    .and_then(|id| data.get_number(id))
+                    .or(stored.map(|(_, max)| max))
+                    .or(*default_max)
+                    .unwrap_or(0);
+                Ok(ResourceLevel {
+                    current: stored.map_or(max, |(current, _)| current),
+                    floor: 0,
+                    ceiling: Some(max),
+                    reset_to: max,
+                })
+            }
+            FieldType::Number { min, max, default } => {
+                let floor = min.unwrap_or(0);
+                let reset_to = default.unwrap_or(floor);
+                Ok(ResourceLevel {
+                    current: data.get_number(field_id).unwrap_or(reset_to),
+                    floor,
+                    ceiling: *max,
+                    reset_to,
+                })
+            }
+            _ => Err(ResourceError::NotAResource(field_id.to_string())),
+        }
+    }
+
+    /// Spend, restore or reset one resource on a sheet
+    pub fn apply_resource(
+        &self,
+        data: &mut CharacterSheetData,
+        field_id: &str,
+        operation: ResourceOperation,
+    ) -> Result<ResourceChange, ResourceError> {
+        let level = self.resource_level(data, field_id)?;
+        let current = level.after(operation).ok_or_else(|| match operation {
+            ResourceOperation::Spend(requested) => ResourceError::Insufficient {
+                field_id: field_id.to_string(),
+                available: level.available(),
+                requested,
+            },
+            _ => ResourceError::NotAResource(field_id.to_string()),
+        })?;
+        Ok(self.set_resource(data, field_id, &level, current))
+    }
+
+    /// Recover the resources a rest brings back
+    ///
+    /// Fields the sheet template does not have, or that are not resources,
+    /// are skipped. Resources the rest leaves unchanged are not reported.
+    pub fn apply_rest(&self, data: &mut CharacterSheetData, rest: &RestType) -> Vec<ResourceChange> {
+        rest.recoveries
+            .iter()
+            .filter_map(|recovery| {
+                let level = self.resource_level(data, &recovery.field_id).ok()?;
+                let current = recovery.amount.recover(&level);
+                (current != level.current)
+                    .then(|| self.set_resource(data, &recovery.field_id, &level, current))
+            })
+            .collect()
+    }
+
+    fn set_resource(
+        &self,
+        data: &mut CharacterSheetData,
+        field_id: &str,
+        level: &ResourceLevel,
+        current: i32,
+    ) -> ResourceChange {
+        let field = self.get_field(field_id);
+        let value = match field.map(|f| &f.field_type) {
+            Some(FieldType::Resource { .. }) => FieldValue::Resource {
+                current,
+                max: level.ceiling.unwrap_or(current),
+            },
+            _ => FieldValue::Number(current),
+        };
+        data.set(field_id, value);
+        ResourceChange {
+            field_id: field_id.to_string(),
+            field_name: field.map_or_else(|| field_id.to_string(), |f| f.name.clone()),
+            previous: level.current,
+            current,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{RecoveryAmount, RuleSystemConfig, RuleSystemVariant, WorldId};
+
+    #[test]
+    fn test_spend_restore_and_rest() {
+        let template = CharacterSheetTemplate::default_for_variant(WorldId::new(), &RuleSystemVariant::Dnd5e);
+        let mut data = CharacterSheetData::new();
+        data.set("LEVEL", FieldValue::Number(5));
+        data.set("HP", FieldValue::Resource { current: 30, max: 30 });
+
+        let change = template
+            .apply_resource(&mut data, "HIT_DICE", ResourceOperation::Spend(4))
+            .unwrap();
+        assert_eq!((change.previous, change.current), (5, 1));
+        assert_eq!(
+            template.apply_resource(&mut data, "HIT_DICE", ResourceOperation::Spend(2)),
+            Err(ResourceError::Insufficient {
+                field_id: "HIT_DICE".to_string(),
+                available: 1,
+                requested: 2
+            })
+        );
+        template
+            .apply_resource(&mut data, "HP", ResourceOperation::Spend(25))
+            .unwrap();
+        assert_eq!(
+            template.apply_resource(&mut data, "SKILLS", ResourceOperation::Reset),
+            Err(ResourceError::NotAResource("SKILLS".to_string()))
+        );
+
+        let long_rest = RuleSystemConfig::dnd_5e().rest_type("long_rest").unwrap();
+        let changes = template.apply_rest(&mut data, &long_rest);
+        let summary: Vec<(&str, i32, i32)> = changes
+            .iter()
+            .map(|c| (c.field_id.as_str(), c.previous, c.current))
+            .collect();
+        assert_eq!(summary, vec![("HP", 5, 30), ("HIT_DICE", 1, 3)]);
+        assert_eq!(data.get("HIT_DICE"), Some(&FieldValue::Resource { current: 3, max: 5 }));
+
+        let stress = RestType::new("vent", "Vent", 0).recovers("LEVEL", RecoveryAmount::Reduce(2));
+        assert_eq!(template.apply_rest(&mut data, &stress)[0].current, 3);
+    }
+}
@@ -16,10 +16,14 @@
 //!
 //! This is an accepted exception to the "no serde in domain" rule.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::value_objects::{RuleSystemVariant, WorldId};
+use super::{SheetMigration, World};
+use crate::domain::value_objects::{
+    ProgressionTable, RuleSystemVariant, SheetFormula, StatDefinition, WorldId,
+};
 
 /// Unique identifier for a sheet template
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -54,6 +58,15 @@ pub struct CharacterSheetTemplate {
     pub sections: Vec<SheetSection>,
     /// Whether this is the default template (created from preset)
     pub is_default: bool,
+    /// Bumped by every edit, starting at 1
+    pub version: u32,
+    /// Migrations from edits not yet applied to the world's characters
+    pub pending_migrations: Vec<SheetMigration>,
+    /// The migrations last applied, kept so they can be rolled back
+    pub applied_migrations: Vec<SheetMigration>,
+    /// Progression overriding the rule system's, for templates that level
+    /// differently
+    pub progression: Option<ProgressionTable>,
 }
 
 impl CharacterSheetTemplate {
@@ -66,6 +79,10 @@ impl CharacterSheetTemplate {
             variant,
             sections: Vec::new(),
             is_default: false,
+            version: 1,
+            pending_migrations: Vec::new(),
+            applied_migrations: Vec::new(),
+            progression: None,
         }
     }
 
@@ -99,8 +116,239 @@ impl CharacterSheetTemplate {
             .flat_map(|s| s.fields.iter())
             .find(|f| f.id == field_id)
     }
+
+    /// The next version of this template, with `sections` replacing the
+    /// current ones
+    ///
+    /// The migration from this version is planned (see `SheetMigration::plan`)
+    /// and queued behind any still pending.
+    pub fn revise(&self, sections: Vec<SheetSection>, renames: &HashMap<String, String>) -> Self {
+        let mut revised = Self {
+            sections,
+            version: self.version + 1,
+            ..self.clone()
+        };
+        let migration = SheetMigration::plan(self, &revised, renames);
+        if !migration.is_empty() {
+            revised.pending_migrations.push(migration);
+        }
+        revised
+    }
+
+    /// The template version to record on a sheet being saved, which was
+    /// `previous` before the save
+    ///
+    /// While migrations are pending (including after a rollback) the sheet
+    /// keeps the version it had, so applying them still carries it across.
+    pub fn version_for_saved_sheet(&self, previous: Option<&CharacterSheetData>) -> Option<u32> {
+        if self.pending_migrations.is_empty() {
+            Some(self.version)
+        } else {
+            previous.and_then(|sheet| sheet.template_version)
+        }
+    }
+
+    /// Derived fields with their parsed formulas, ordered so that each comes
+    /// after the derived fields it reads
+    ///
+    /// Fails when a formula does not parse, reads a field the template does
+    /// not have, or derived fields depend on each other in a cycle.
+    pub fn derived_fields(&self) -> Result<Vec<(&SheetField, SheetFormula)>, InvalidDerivedFields> {
+        let mut problems = Vec::new();
+        let mut formulas = HashMap::new();
+        let mut declared = Vec::new();
+        for field in self.sections.iter().flat_map(|s| s.fields.iter()) {
+            let FieldType::Derived { formula, .. } = &field.field_type else {
+                continue;
+            };
+            match SheetFormula::parse(formula) {
+                Ok(formula) => {
+                    for reference in formula.references() {
+                        if self.get_field(reference).is_none() {
+                            problems.push(format!(
+                                "'{}' reads unknown field '{}'",
+                                field.id, reference
+                            ));
+                        }
+                    }
+                    declared.push(field.id.as_str());
+                    formulas.insert(field.id.as_str(), (field, formula));
+                }
+                Err(e) => problems.push(format!("'{}': {}", field.id, e)),
+            }
+        }
+
+        let mut ordered = Vec::new();
+        let mut done = HashSet::new();
+        let mut visiting = Vec::new();
+        for id in declared {
+            visit_derived(id, &formulas, &mut visiting, &mut done, &mut ordered, &mut problems);
+        }
+
+        if !problems.is_empty() {
+            return Err(InvalidDerivedFields(problems));
+        }
+        Ok(ordered
+            .into_iter()
+            .filter_map(|id| formulas.remove(id))
+            .collect())
+    }
+
+    /// Recalculate every derived field of a character sheet
+    ///
+    /// Inputs missing from the sheet count as their field's default, or zero.
+    /// Results are rounded down. A formula that cannot be evaluated leaves its
+    /// field as it was and is reported.
+    pub fn compute_derived(&self, data: &mut CharacterSheetData) -> Result<(), InvalidDerivedFields> {
+        let mut problems = Vec::new();
+        for (field, formula) in self.derived_fields()? {
+            let result = formula.evaluate(|id| {
+                data.get(id)
+                    .and_then(FieldValue::as_number)
+                    .or_else(|| self.get_field(id).and_then(|f| f.field_type.default_number()))
+                    .unwrap_or(0) as f64
+            });
+            match result {
+                Ok(value) if value.is_finite() => {
+                    data.set(field.id.clone(), FieldValue::Number(value.floor() as i32));
+                }
+                Ok(_) => problems.push(format!("'{}' did not produce a number", field.id)),
+                Err(e) => problems.push(format!("'{}': {}", field.id, e)),
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidDerivedFields(problems))
+        }
+    }
+
+    /// Check character sheet data against this template
+    ///
+    /// `previous` is the sheet before the edit (None for a new character).
+    /// When a player edits, read-only fields must keep their previous value,
+    /// or start out unset or at their default; the DM may set them freely.
+    /// Derived fields are recalculated after validation, so they are not
+    /// checked. Values stored under IDs the template does not define are
+    /// left alone.
+    pub fn validate_sheet(
+        &self,
+        data: &CharacterSheetData,
+        previous: Option<&CharacterSheetData>,
+        by_player: bool,
+    ) -> Result<(), InvalidSheetData> {
+        let mut errors = Vec::new();
+        for field in self.sections.iter().flat_map(|s| s.fields.iter()) {
+            if matches!(field.field_type, FieldType::Derived { .. }) {
+                continue;
+            }
+            let mut fail = |message: String| {
+                errors.push(SheetFieldError {
+                    field_id: field.id.clone(),
+                    message,
+                })
+            };
+
+            let value = data.get(&field.id);
+            let kept = match previous {
+                Some(previous) => value == previous.get(&field.id),
+                None => value.is_none() || value.cloned() == field.field_type.default_value(),
+            };
+            if by_player && field.read_only && !kept {
+                fail("is read-only".to_string());
+                continue;
+            }
+            match value {
+                Some(value) => {
+                    if let Some(message) = field.field_type.check(value) {
+                        fail(message);
+                    } else if field.required && value.is_blank() {
+                        fail("is required".to_string());
+                    }
+                }
+                None if field.required => fail("is required".to_string()),
+                None => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidSheetData(errors))
+        }
+    }
+}
+
+/// Depth-first step of the derived field ordering; `visiting` holds the
+/// current path so a cycle can be reported in full
+fn visit_derived<'a>(
+    id: &'a str,
+    formulas: &HashMap<&'a str, (&'a SheetField, SheetFormula)>,
+    visiting: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+    ordered: &mut Vec<&'a str>,
+    problems: &mut Vec<String>,
+) {
+    if done.contains(id) {
+        return;
+    }
+    if let Some(start) = visiting.iter().position(|v| *v == id) {
+        let mut cycle = visiting[start..].to_vec();
+        cycle.push(id);
+        problems.push(format!("derived fields form a cycle: {}", cycle.join(" -> ")));
+        return;
+    }
+    let Some((_, formula)) = formulas.get(id) else {
+        return;
+    };
+    visiting.push(id);
+    for reference in formula.references() {
+        if let Some((&dependency, _)) = formulas.get_key_value(reference) {
+            visit_derived(dependency, formulas, visiting, done, ordered, problems);
+        }
+    }
+    visiting.pop();
+    done.insert(id);
+    ordered.push(id);
+}
+
+/// A character sheet value that breaks its template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetFieldError {
+    pub field_id: String,
+    pub message: String,
+}
+
+/// Every problem found validating a character sheet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSheetData(pub Vec<SheetFieldError>);
+
+impl fmt::Display for InvalidSheetData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self
+            .0
+            .iter()
+            .map(|e| format!("{} {}", e.field_id, e.message))
+            .collect();
+        write!(f, "Invalid character sheet: {}", errors.join("; "))
+    }
 }
 
+impl std::error::Error for InvalidSheetData {}
+
+/// Problems with a template's derived field formulas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDerivedFields(pub Vec<String>);
+
+impl fmt::Display for InvalidDerivedFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid derived fields: {}", self.0.join("; "))
+    }
+}
+
+impl std::error::Error for InvalidDerivedFields {}
+
 /// A section of the character sheet (e.g., "Attributes", "Skills", "Combat")
 #[derive(Debug, Clone)]
 pub struct SheetSection {
@@ -257,8 +505,6 @@ pub enum FieldType {
     Derived {
         /// Formula to calculate the value (e.g., "floor((STR - 10) / 2)")
         formula: String,
-        /// Fields this depends on
-        depends_on: Vec<String>,
     },
     /// A resource with current/max values (e.g., HP, spell slots)
     Resource {
@@ -279,6 +525,72 @@ pub enum FieldType {
     },
 }
 
+impl FieldType {
+    /// The value a new sheet starts with, if the field has a default
+    pub fn default_value(&self) -> Option<FieldValue> {
+        match self {
+            Self::Number { default, .. } => default.map(FieldValue::Number),
+            Self::Checkbox { default } => Some(FieldValue::Boolean(*default)),
+            Self::Resource { default_max, .. } => {
+                default_max.map(|max| FieldValue::Resource { current: max, max })
+            }
+            _ => None,
+        }
+    }
+
+    /// The numeric value a field holds before anything is entered
+    fn default_number(&self) -> Option<i32> {
+        match self {
+            Self::Number { default, .. } => *default,
+            Self::Checkbox { default } => Some(*default as i32),
+            Self::Resource { default_max, .. } => *default_max,
+            _ => None,
+        }
+    }
+
+    /// Why a value does not fit this field, if it does not
+    fn check(&self, value: &FieldValue) -> Option<String> {
+        match (self, value) {
+            (Self::Number { min, max, .. }, FieldValue::Number(n)) => match (min, max) {
+                (Some(min), _) if n < min => Some(format!("must be at least {}", min)),
+                (_, Some(max)) if n > max => Some(format!("must be at most {}", max)),
+                _ => None,
+            },
+            (Self::Number { .. }, _) => Some("must be a number".to_string()),
+            (Self::Text { max_length, .. }, FieldValue::Text(text)) => max_length
+                .filter(|max| text.chars().count() > *max)
+                .map(|max| format!("must be at most {} characters", max)),
+            (Self::Text { .. }, _) => Some("must be text".to_string()),
+            (Self::Checkbox { .. }, FieldValue::Boolean(_)) => None,
+            (Self::Checkbox { .. }, _) => Some("must be true or false".to_string()),
+            (Self::Select { options }, FieldValue::Text(choice)) => {
+                if options.iter().any(|o| &o.value == choice) {
+                    None
+                } else {
+                    Some(format!("'{}' is not one of the options", choice))
+                }
+            }
+            (Self::Select { .. }, _) => Some("must be one of the options".to_string()),
+            (Self::Resource { .. }, FieldValue::Resource { current, max }) => {
+                if *max < 0 {
+                    Some("maximum must not be negative".to_string())
+                } else if *current > *max {
+                    Some(format!("current value must be at most {}", max))
+                } else {
+                    None
+                }
+            }
+            (Self::Resource { .. }, _) => Some("must be a resource".to_string()),
+            (Self::ItemList { max_items, .. }, FieldValue::List(items)) => max_items
+                .filter(|max| items.len() > *max)
+                .map(|max| format!("must have at most {} items", max)),
+            (Self::ItemList { .. }, _) => Some("must be a list".to_string()),
+            // Skill fields hold per-skill entries the sheet UI manages
+            (Self::SkillReference { .. } | Self::SkillList { .. } | Self::Derived { .. }, _) => None,
+        }
+    }
+}
+
 /// Option in a select field
 #[derive(Debug, Clone)]
 pub struct SelectOption {
@@ -316,10 +628,14 @@ pub enum ItemListType {
 }
 
 /// Character data that conforms to a template
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CharacterSheetData {
     /// Map of field_id -> value
     pub values: HashMap<String, FieldValue>,
+    /// Version of the template the sheet was last saved under; None for
+    /// sheets saved before versions were recorded
+    #[serde(default)]
+    pub template_version: Option<u32>,
 }
 
 impl CharacterSheetData {
@@ -398,7 +714,7 @@ impl CharacterSheetData {
 }
 
 /// A value stored for a field
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FieldValue {
     Number(i32),
     Text(String),
@@ -415,11 +731,44 @@ pub enum FieldValue {
     },
 }
 
+impl FieldValue {
+    /// Whether the value counts as not filled in
+    fn is_blank(&self) -> bool {
+        match self {
+            Self::Text(s) => s.trim().is_empty(),
+            Self::List(items) => items.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// The value as a number for formulas: a resource's current value, a
+    /// skill's bonus, 1 or 0 for a checkbox
+    pub fn as_number(&self) -> Option<i32> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::Boolean(b) => Some(*b as i32),
+            Self::Resource { current, .. } => Some(*current),
+            Self::SkillEntry { bonus, .. } => Some(*bonus),
+            Self::Text(s) => s.trim().parse().ok(),
+            Self::List(_) => None,
+        }
+    }
+}
+
 // ============================================================================
 // Default Templates per Rule System
 // ============================================================================
 
 impl CharacterSheetTemplate {
+    /// Create the default template for a world, honouring a user-defined
+    /// rule system when the world runs on one
+    pub fn default_for_world(world: &World) -> Self {
+        match world.custom_rule_system() {
+            Some(definition) => definition.default_sheet_template(world.id),
+            None => Self::default_for_variant(world.id, &world.rule_system.variant),
+        }
+    }
+
     /// Create the default template for a rule system variant
     pub fn default_for_variant(world_id: WorldId, variant: &RuleSystemVariant) -> Self {
         match variant {
@@ -458,12 +807,12 @@ impl CharacterSheetTemplate {
                 SheetSection::new("modifiers", "Ability Modifiers")
                     .with_layout(SectionLayout::Grid { columns: 3 })
                     .with_order(1)
-                    .with_field(SheetField::new("STR_MOD", "STR Mod", FieldType::Derived { formula: "floor((STR - 10) / 2)".into(), depends_on: vec!["STR".into()] }).read_only().with_order(0))
-                    .with_field(SheetField::new("DEX_MOD", "DEX Mod", FieldType::Derived { formula: "floor((DEX - 10) / 2)".into(), depends_on: vec!["DEX".into()] }).read_only().with_order(1))
-                    .with_field(SheetField::new("CON_MOD", "CON Mod", FieldType::Derived { formula: "floor((CON - 10) / 2)".into(), depends_on: vec!["CON".into()] }).read_only().with_order(2))
-                    .with_field(SheetField::new("INT_MOD", "INT Mod", FieldType::Derived { formula: "floor((INT - 10) / 2)".into(), depends_on: vec!["INT".into()] }).read_only().with_order(3))
-                    .with_field(SheetField::new("WIS_MOD", "WIS Mod", FieldType::Derived { formula: "floor((WIS - 10) / 2)".into(), depends_on: vec!["WIS".into()] }).read_only().with_order(4))
-                    .with_field(SheetField::new("CHA_MOD", "CHA Mod", FieldType::Derived { formula: "floor((CHA - 10) / 2)".into(), depends_on: vec!["CHA".into()] }).read_only().with_order(5))
+                    .with_field(SheetField::new("STR_MOD", "STR Mod", FieldType::Derived { formula: "floor((STR - 10) / 2)".into() }).read_only().with_order(0))
+                    .with_field(SheetField::new("DEX_MOD", "DEX Mod", FieldType::Derived { formula: "floor((DEX - 10) / 2)".into() }).read_only().with_order(1))
+                    .with_field(SheetField::new("CON_MOD", "CON Mod", FieldType::Derived { formula: "floor((CON - 10) / 2)".into() }).read_only().with_order(2))
+                    .with_field(SheetField::new("INT_MOD", "INT Mod", FieldType::Derived { formula: "floor((INT - 10) / 2)".into() }).read_only().with_order(3))
+                    .with_field(SheetField::new("WIS_MOD", "WIS Mod", FieldType::Derived { formula: "floor((WIS - 10) / 2)".into() }).read_only().with_order(4))
+                    .with_field(SheetField::new("CHA_MOD", "CHA Mod", FieldType::Derived { formula: "floor((CHA - 10) / 2)".into() }).read_only().with_order(5))
             )
             // Combat section
             .with_section(
@@ -473,9 +822,11 @@ impl CharacterSheetTemplate {
                     .with_field(SheetField::new("HP", "Hit Points", FieldType::Resource { max_field: Some("HP_MAX".into()), default_max: Some(10) }).with_order(0))
                     .with_field(SheetField::new("HP_MAX", "Max HP", FieldType::Number { min: Some(1), max: None, default: Some(10) }).with_order(1))
                     .with_field(SheetField::new("AC", "Armor Class", FieldType::Number { min: Some(0), max: None, default: Some(10) }).with_order(2))
-                    .with_field(SheetField::new("INITIATIVE", "Initiative", FieldType::Derived { formula: "DEX_MOD".into(), depends_on: vec!["DEX_MOD".into()] }).read_only().with_order(3))
+                    .with_field(SheetField::new("INITIATIVE", "Initiative", FieldType::Derived { formula: "DEX_MOD".into() }).read_only().with_order(3))
                     .with_field(SheetField::new("SPEED", "Speed", FieldType::Number { min: Some(0), max: None, default: Some(30) }).with_order(4))
-                    .with_field(SheetField::new("PROF_BONUS", "Proficiency Bonus", FieldType::Number { min: Some(2), max: Some(6), default: Some(2) }).with_order(5))
+                    .with_field(SheetField::new("LEVEL", "Level", FieldType::Number { min: Some(1), max: Some(20), default: Some(1) }).with_order(5))
+                    .with_field(SheetField::new("PROF_BONUS", "Proficiency Bonus", FieldType::Derived { formula: "lookup(LEVEL, 1: 2, 5: 3, 9: 4, 13: 5, 17: 6)".into() }).read_only().with_order(6))
+                    .with_field(SheetField::new("HIT_DICE", "Hit Dice", FieldType::Resource { max_field: Some("LEVEL".into()), default_max: Some(1) }).with_order(7))
             )
             // Skills section
             .with_section(
@@ -592,12 +943,12 @@ impl CharacterSheetTemplate {
                     .with_layout(SectionLayout::Grid { columns: 2 })
                     .with_order(1)
                     .with_field(SheetField::new("HP", "Hit Points", FieldType::Resource { max_field: Some("HP_MAX".into()), default_max: Some(10) }))
-                    .with_field(SheetField::new("HP_MAX", "Max HP", FieldType::Derived { formula: "floor((CON + SIZ) / 10)".into(), depends_on: vec!["CON".into(), "SIZ".into()] }).read_only())
+                    .with_field(SheetField::new("HP_MAX", "Max HP", FieldType::Derived { formula: "floor((CON + SIZ) / 10)".into() }).read_only())
                     .with_field(SheetField::new("SAN", "Sanity", FieldType::Resource { max_field: Some("SAN_MAX".into()), default_max: Some(50) }))
                     .with_field(SheetField::new("SAN_MAX", "Max Sanity", FieldType::Number { min: Some(0), max: Some(99), default: Some(99) }))
                     .with_field(SheetField::new("LUCK", "Luck", FieldType::Number { min: Some(0), max: Some(100), default: Some(50) }))
                     .with_field(SheetField::new("MP", "Magic Points", FieldType::Resource { max_field: Some("MP_MAX".into()), default_max: Some(10) }))
-                    .with_field(SheetField::new("MP_MAX", "Max MP", FieldType::Derived { formula: "floor(POW / 5)".into(), depends_on: vec!["POW".into()] }).read_only())
+                    .with_field(SheetField::new("MP_MAX", "Max MP", FieldType::Derived { formula: "floor(POW / 5)".into() }).read_only())
             )
             .with_section(
                 SheetSection::new("skills", "Skills")
@@ -861,4 +1212,171 @@ impl CharacterSheetTemplate {
                     .with_field(SheetField::new("SKILLS", "Character Skills", FieldType::SkillList { show_modifier: false, show_proficiency: false }))
             )
     }
+
+    /// Add sections for a rule system's stats, notes and skills
+    ///
+    /// Used for user-defined systems that do not declare their own sheet.
+    pub fn with_generated_sections(self, stats: &[StatDefinition]) -> Self {
+        let attributes = stats.iter().enumerate().fold(
+            SheetSection::new("attributes", "Attributes")
+                .with_layout(SectionLayout::Grid { columns: 3 })
+                .with_order(0),
+            |section, (order, stat)| {
+                section.with_field(
+                    SheetField::new(
+                        stat.abbreviation.clone(),
+                        stat.name.clone(),
+                        FieldType::Number {
+                            min: Some(stat.min_value),
+                            max: Some(stat.max_value),
+                            default: Some(stat.default_value),
+                        },
+                    )
+                    .with_order(order as u32),
+                )
+            },
+        );
+        let template = if stats.is_empty() { self } else { self.with_section(attributes) };
+        template
+            .with_section(
+                SheetSection::new("basic", "Basic Info")
+                    .with_layout(SectionLayout::Vertical)
+                    .with_order(1)
+                    .with_field(SheetField::new("NOTES", "Character Notes", FieldType::Text { multiline: true, max_length: None }))
+            )
+            .with_section(
+                SheetSection::new("skills", "Skills")
+                    .with_layout(SectionLayout::Vertical)
+                    .with_order(2)
+                    .with_field(SheetField::new("SKILLS", "Character Skills", FieldType::SkillList { show_modifier: true, show_proficiency: false }))
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::RuleSystemType;
+
+    fn derived(id: &str, formula: &str) -> SheetField {
+        SheetField::new(id, id, FieldType::Derived { formula: formula.into() })
+    }
+
+    #[test]
+    fn test_default_templates_compute_derived_fields() {
+        let world_id = WorldId::new();
+        let variants = [RuleSystemType::D20, RuleSystemType::D100, RuleSystemType::Narrative]
+            .into_iter()
+            .flat_map(RuleSystemVariant::variants_for_type);
+        for variant in variants {
+            let template = CharacterSheetTemplate::default_for_variant(world_id, &variant);
+            assert!(template.derived_fields().is_ok(), "{:?}", variant);
+        }
+
+        let template = CharacterSheetTemplate::default_for_variant(world_id, &RuleSystemVariant::Dnd5e);
+        let mut data = CharacterSheetData::new();
+        data.set("DEX", FieldValue::Number(15));
+        data.set("LEVEL", FieldValue::Number(9));
+        template.compute_derived(&mut data).unwrap();
+        assert_eq!(data.get_number("DEX_MOD"), Some(2));
+        assert_eq!(data.get_number("INITIATIVE"), Some(2));
+        assert_eq!(data.get_number("STR_MOD"), Some(0));
+        assert_eq!(data.get_number("PROF_BONUS"), Some(4));
+    }
+
+    #[test]
+    fn test_sheet_validation_reports_each_field() {
+        let template = CharacterSheetTemplate::new(WorldId::new(), "Checks", RuleSystemVariant::GenericD20)
+            .with_section(
+                SheetSection::new("main", "Main")
+                    .with_field(SheetField::new("NAME", "Name", FieldType::Text { multiline: false, max_length: Some(5) }).required())
+                    .with_field(SheetField::new("STR", "Strength", FieldType::Number { min: Some(1), max: Some(20), default: None }))
+                    .with_field(SheetField::new("CLASS", "Class", FieldType::Select { options: vec![SelectOption::new("fighter", "Fighter")] }))
+                    .with_field(SheetField::new("XP", "XP", FieldType::Number { min: None, max: None, default: Some(0) }).read_only())
+                    .with_field(derived("MOD", "floor((STR - 10) / 2)").read_only()),
+            );
+
+        let mut sheet = CharacterSheetData::new();
+        sheet.set("NAME", FieldValue::Text("Bo".into()));
+        sheet.set("STR", FieldValue::Number(12));
+        sheet.set("CLASS", FieldValue::Text("fighter".into()));
+        sheet.set("MOD", FieldValue::Number(99));
+        assert!(template.validate_sheet(&sheet, None, true).is_ok());
+
+        let mut edited = sheet.clone();
+        edited.set("NAME", FieldValue::Text("  ".into()));
+        edited.set("STR", FieldValue::Number(25));
+        edited.set("CLASS", FieldValue::Text("wizard".into()));
+        edited.set("XP", FieldValue::Number(300));
+        let InvalidSheetData(errors) = template.validate_sheet(&edited, Some(&sheet), true).unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field_id.as_str()).collect();
+        assert_eq!(fields, vec!["NAME", "STR", "CLASS", "XP"]);
+        assert_eq!(errors[1].message, "must be at most 20");
+
+        // A read-only value may stay as it was
+        let mut previous = sheet.clone();
+        previous.set("XP", FieldValue::Number(300));
+        let mut kept = previous.clone();
+        kept.set("STR", FieldValue::Number(14));
+        assert!(template.validate_sheet(&kept, Some(&previous), true).is_ok());
+
+        // Only players are held to read-only values; a new sheet may start at the default
+        let mut awarded = kept.clone();
+        awarded.set("XP", FieldValue::Number(900));
+        assert!(template.validate_sheet(&awarded, Some(&kept), false).is_ok());
+        assert!(template.validate_sheet(&awarded, None, false).is_ok());
+        assert!(template.validate_sheet(&awarded, None, true).is_err());
+        awarded.set("XP", FieldValue::Number(0));
+        assert!(template.validate_sheet(&awarded, None, true).is_ok());
+    }
+
+    #[test]
+    fn test_derived_field_problems_are_reported() {
+        let template = CharacterSheetTemplate::new(WorldId::new(), "Loops", RuleSystemVariant::GenericD20)
+            .with_section(
+                SheetSection::new("main", "Main")
+                    .with_field(derived("A", "B + 1"))
+                    .with_field(derived("B", "floor(A / 2)"))
+                    .with_field(derived("C", "GHOST"))
+                    .with_field(derived("D", "(")),
+            );
+
+        let InvalidDerivedFields(problems) = template.derived_fields().unwrap_err();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("A -> B -> A")));
+    }
+
+    #[test]
+    fn test_revise_queues_migrations_between_versions() {
+        let number = FieldType::Number { min: None, max: None, default: Some(10) };
+        let template = CharacterSheetTemplate::new(WorldId::new(), "Sheet", RuleSystemVariant::GenericD20)
+            .with_section(SheetSection::new("main", "Main").with_field(SheetField::new("HP", "HP", number.clone())));
+
+        let unchanged = template.revise(template.sections.clone(), &HashMap::new());
+        assert_eq!(unchanged.version, 2);
+        assert!(unchanged.pending_migrations.is_empty());
+
+        let sections = vec![SheetSection::new("main", "Main")
+            .with_field(SheetField::new("HP", "HP", number.clone()))
+            .with_field(SheetField::new("AC", "Armor", number))];
+        let revised = unchanged.revise(sections, &HashMap::new());
+        assert_eq!(revised.version, 3);
+        assert_eq!(revised.pending_migrations.len(), 1);
+        assert_eq!((revised.pending_migrations[0].from_version, revised.pending_migrations[0].to_version), (2, 3));
+
+        let migrated = SheetMigration::apply_all(&revised.pending_migrations, &CharacterSheetData::new());
+        assert_eq!(migrated.get_number("AC"), Some(10));
+
+        // A sheet saved while the migration is pending keeps its version, so
+        // the migration still reaches it
+        let mut saved = CharacterSheetData::new();
+        saved.template_version = Some(2);
+        assert_eq!(revised.version_for_saved_sheet(Some(&saved)), Some(2));
+        assert_eq!(revised.version_for_saved_sheet(None), None);
+        assert_eq!(
+            SheetMigration::apply_all(&revised.pending_migrations, &saved).get_number("AC"),
+            Some(10)
+        );
+        assert_eq!(unchanged.version_for_saved_sheet(Some(&saved)), Some(2));
+    }
 }
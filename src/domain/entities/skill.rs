@@ -4,6 +4,7 @@
 //! - Default skills from a rule system preset
 //! - Custom skills created by the DM
 
+use super::World;
 use crate::domain::value_objects::{RuleSystemVariant, SkillId, WorldId};
 
 /// A skill that characters can use for challenges
@@ -105,6 +106,15 @@ impl SkillCategory {
     }
 }
 
+/// Default skills for a world, honouring a user-defined rule system when the
+/// world runs on one
+pub fn default_skills_for_world(world: &World) -> Vec<Skill> {
+    match world.custom_rule_system() {
+        Some(definition) => definition.default_skills(world.id),
+        None => default_skills_for_variant(world.id, &world.rule_system.variant),
+    }
+}
+
 /// Default skills for each rule system variant
 pub fn default_skills_for_variant(world_id: WorldId, variant: &RuleSystemVariant) -> Vec<Skill> {
     match variant {
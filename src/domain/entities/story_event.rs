@@ -191,6 +191,15 @@ pub enum StoryEventType {
         reason: String,
     },
 
+    /// Player character gained a level
+    LevelGained {
+        character_id: CharacterId,
+        character_name: String,
+        level: u32,
+        /// Names of the sheet fields and skills the level unlocked
+        unlocked: Vec<String>,
+    },
+
     /// Flag was set or unset
     FlagChanged {
         flag_name: String,
@@ -425,6 +434,11 @@ impl StoryEvent {
             StoryEventType::StatModified {
                 stat_name, reason, ..
             } => format!("{} changed: {}", stat_name, reason),
+            StoryEventType::LevelGained {
+                character_name,
+                level,
+                ..
+            } => format!("{} reached level {}", character_name, level),
             StoryEventType::FlagChanged {
                 flag_name,
                 new_value,
@@ -459,6 +473,7 @@ impl StoryEvent {
             StoryEventType::DmMarker { .. } => "DM Marker",
             StoryEventType::NarrativeEventTriggered { .. } => "Narrative Event",
             StoryEventType::StatModified { .. } => "Stat Modified",
+            StoryEventType::LevelGained { .. } => "Level Gained",
             StoryEventType::FlagChanged { .. } => "Flag Changed",
             StoryEventType::SessionStarted { .. } => "Session Start",
             StoryEventType::SessionEnded { .. } => "Session End",
@@ -4,6 +4,8 @@ use chrono::{DateTime, Utc};
 
 use crate::domain::value_objects::{RuleSystemConfig, WorldId};
 
+use super::RuleSystemDefinition;
+
 /// A complete campaign world
 #[derive(Debug, Clone)]
 pub struct World {
@@ -11,6 +13,8 @@ pub struct World {
     pub name: String,
     pub description: String,
     pub rule_system: RuleSystemConfig,
+    /// Uploaded definition for a homebrew rule system
+    pub rule_system_definition: Option<RuleSystemDefinition>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -23,6 +27,7 @@ impl World {
             name: name.into(),
             description: description.into(),
             rule_system: RuleSystemConfig::default(),
+            rule_system_definition: None,
             created_at: now,
             updated_at: now,
         }
@@ -33,6 +38,22 @@ impl World {
         self
     }
 
+    /// Switch the world to a user-defined rule system
+    pub fn use_rule_system_definition(&mut self, definition: RuleSystemDefinition) {
+        self.rule_system = definition.to_config();
+        self.rule_system_definition = Some(definition);
+        self.updated_at = Utc::now();
+    }
+
+    /// The user-defined rule system the world currently runs on, if any
+    ///
+    /// A stored definition is ignored once the world switches to another system.
+    pub fn custom_rule_system(&self) -> Option<&RuleSystemDefinition> {
+        self.rule_system_definition
+            .as_ref()
+            .filter(|definition| definition.defines(&self.rule_system))
+    }
+
     pub fn update_name(&mut self, name: impl Into<String>) {
         self.name = name.into();
         self.updated_at = Utc::now();
@@ -6,4 +6,5 @@
 //! - Domain Services: Pure business logic operations
 
 pub mod entities;
+pub mod services;
 pub mod value_objects;
@@ -0,0 +1,580 @@
+//! Challenge outcome evaluation
+//!
+//! Decides which outcome tier a roll earns against a challenge's difficulty.
+//! Success-counting dice pools are read by net successes and Fate rolls by
+//! shifts over the opposition; everything else is graded by the world's
+//! `ResolutionBands`. Optional tiers the challenge does not define fall back
+//! to the nearest defined one.
+
+use std::cmp::Ordering;
+
+use crate::domain::entities::{Challenge, Difficulty, Outcome, OutcomeType};
+use crate::domain::value_objects::{
+    DiceRollResult, FateLadder, OpposedTieBreak, ResolutionBands, RuleSystemConfig, SuccessComparison,
+};
+
+/// Successes above the requirement that make a dice pool roll exceptional
+const EXCEPTIONAL_POOL_MARGIN: i32 = 4;
+
+/// Shifts that make a Fate roll "succeed with style"
+const FATE_STYLE_SHIFTS: i32 = 3;
+
+/// Opposition used for Fate rolls when the challenge has no ladder difficulty
+const FATE_DEFAULT_OPPOSITION: FateLadder = FateLadder(2);
+
+/// Roll-under targets below this fumble on 96-100 rather than only on 100
+const ROLL_UNDER_WIDE_FUMBLE_BELOW: i32 = 50;
+
+/// The parts of a roll that outcome evaluation looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RollOutcome {
+    /// Value of the dice before the skill modifier
+    pub raw_roll: i32,
+    /// Skill modifier added to the raw roll
+    pub modifier: i32,
+    /// Net successes when the roll counted successes
+    pub successes: Option<i32>,
+    /// Whether a dice pool botched
+    pub botched: bool,
+    /// Ladder rung reached when the roll used Fate dice
+    pub fate_ladder: Option<FateLadder>,
+    /// Whether the roll's one kept d20 came up 20
+    pub natural_20: bool,
+    /// Whether the roll's one kept d20 came up 1
+    pub natural_1: bool,
+}
+
+impl RollOutcome {
+    /// A plain roll with no pool or Fate semantics
+    pub fn flat(raw_roll: i32, modifier: i32) -> Self {
+        Self {
+            raw_roll,
+            modifier,
+            successes: None,
+            botched: false,
+            fate_ladder: None,
+            natural_20: false,
+            natural_1: false,
+        }
+    }
+
+    /// A single d20 whose face is the raw roll
+    pub fn d20(face: i32, modifier: i32) -> Self {
+        Self {
+            natural_20: face == 20,
+            natural_1: face == 1,
+            ..Self::flat(face, modifier)
+        }
+    }
+
+    /// Read the outcome-relevant parts of a dice roll result
+    pub fn from_result(result: &DiceRollResult, raw_roll: i32, modifier: i32) -> Self {
+        Self {
+            successes: result.successes,
+            botched: result.is_botch(),
+            fate_ladder: result.fate_ladder,
+            natural_20: result.is_natural_20(),
+            natural_1: result.is_natural_1(),
+            ..Self::flat(raw_roll, modifier)
+        }
+    }
+
+    /// Raw roll plus modifier
+    pub fn total(&self) -> i32 {
+        self.raw_roll.saturating_add(self.modifier)
+    }
+}
+
+/// How well a roll-under check succeeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuccessLevel {
+    /// A roll of 01
+    Critical,
+    /// At most a fifth of the target
+    Extreme,
+    /// At most half the target
+    Hard,
+    /// At most the target
+    Regular,
+}
+
+impl SuccessLevel {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Critical => "Critical Success!",
+            Self::Extreme => "Extreme Success",
+            Self::Hard => "Hard Success",
+            Self::Regular => "Regular Success",
+        }
+    }
+}
+
+/// The outcome a roll earned against a challenge
+#[derive(Debug, Clone, Copy)]
+pub struct RollEvaluation<'a> {
+    pub outcome_type: OutcomeType,
+    pub outcome: &'a Outcome,
+    /// How far the roll beat (positive) or missed (negative) the line for
+    /// success, in the units the system counts: points over the DC, points
+    /// under a percentage, successes or shifts. None when nothing was compared.
+    pub margin: Option<i32>,
+    /// Level a successful roll-under check reached
+    pub success_level: Option<SuccessLevel>,
+}
+
+impl<'a> RollEvaluation<'a> {
+    fn new((outcome_type, outcome): (OutcomeType, &'a Outcome), margin: Option<i32>) -> Self {
+        Self {
+            outcome_type,
+            outcome,
+            margin,
+            success_level: None,
+        }
+    }
+
+    /// Name of the result, giving a roll-under success's level
+    pub fn display_name(&self) -> &'static str {
+        self.success_level
+            .map_or(self.outcome_type.display_name(), |level| level.display_name())
+    }
+}
+
+/// Evaluate a roll against a challenge, grading it by the rule system's bands
+pub fn evaluate_roll<'a>(
+    challenge: &'a Challenge,
+    bands: ResolutionBands,
+    roll: &RollOutcome,
+) -> RollEvaluation<'a> {
+    if let Some(successes) = roll.successes {
+        return evaluate_pool(challenge, successes, roll.botched);
+    }
+    if let Some(ladder) = roll.fate_ladder {
+        return evaluate_fate(challenge, ladder);
+    }
+    match (bands, &challenge.difficulty) {
+        (ResolutionBands::Shifts, _) => evaluate_fate(challenge, FateLadder(roll.total())),
+        (ResolutionBands::Bands { partial_at, success_at }, Difficulty::DC(_) | Difficulty::Descriptor(_)) => {
+            evaluate_bands(challenge, roll.total(), partial_at, success_at)
+        }
+        (ResolutionBands::Degrees { critical_margin }, Difficulty::DC(dc)) => {
+            evaluate_degrees(challenge, roll, *dc as i32, critical_margin)
+        }
+        (ResolutionBands::RollUnder, Difficulty::Percentage(target)) => {
+            evaluate_roll_under(challenge, roll.raw_roll, *target as i32)
+        }
+        _ => evaluate_flat(challenge, roll),
+    }
+}
+
+/// Evaluate an opposed check from the initiator's side
+///
+/// Both rolls are scored the same way: net successes for dice pools, success
+/// level against the roller's own skill (their modifier) for roll-under
+/// systems, and the total otherwise. The margin is the initiator's score less
+/// the opponent's; ties are settled by the rule system.
+pub fn evaluate_opposed<'a>(
+    challenge: &'a Challenge,
+    rule_system: &RuleSystemConfig,
+    initiator: &RollOutcome,
+    opponent: &RollOutcome,
+) -> RollEvaluation<'a> {
+    let roll_under = rule_system.success_comparison == SuccessComparison::LessOrEqual;
+    let margin = opposed_score(initiator, roll_under).saturating_sub(opposed_score(opponent, roll_under));
+    let initiator_wins = match margin.cmp(&0) {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => match rule_system.opposed_tie_break() {
+            OpposedTieBreak::Defender => false,
+            OpposedTieBreak::Initiator => true,
+            OpposedTieBreak::HigherSkill => initiator.modifier > opponent.modifier,
+            OpposedTieBreak::Partial => {
+                let tier = select_outcome(challenge, OutcomeType::Partial, OutcomeType::Failure);
+                return RollEvaluation::new(tier, Some(0));
+            }
+        },
+    };
+    let tier = if !initiator_wins {
+        (OutcomeType::Failure, &challenge.outcomes.failure)
+    } else if rule_system.resolution_bands() == ResolutionBands::Shifts && margin >= FATE_STYLE_SHIFTS {
+        select_outcome(challenge, OutcomeType::CriticalSuccess, OutcomeType::Success)
+    } else {
+        (OutcomeType::Success, &challenge.outcomes.success)
+    };
+    RollEvaluation::new(tier, Some(margin))
+}
+
+/// Score one side of an opposed check; higher is better
+fn opposed_score(roll: &RollOutcome, roll_under: bool) -> i32 {
+    if let Some(successes) = roll.successes {
+        return successes;
+    }
+    if !roll_under {
+        return roll.total();
+    }
+    // Success levels: fumble, failure, regular, hard, extreme, critical
+    let skill = roll.modifier;
+    match roll.raw_roll {
+        1 => 4,
+        raw if roll_under_fumbles(raw, skill) => -1,
+        raw if raw <= skill / 5 => 3,
+        raw if raw <= skill / 2 => 2,
+        raw if raw <= skill => 1,
+        _ => 0,
+    }
+}
+
+/// Whether a percentile roll fumbles against a target
+fn roll_under_fumbles(roll: i32, target: i32) -> bool {
+    roll >= 100 || (target < ROLL_UNDER_WIDE_FUMBLE_BELOW && roll >= 96)
+}
+
+/// Pick the outcome for `outcome_type`, using `fallback` when the challenge
+/// does not define that optional tier.
+fn select_outcome(
+    challenge: &Challenge,
+    outcome_type: OutcomeType,
+    fallback: OutcomeType,
+) -> (OutcomeType, &Outcome) {
+    let outcomes = &challenge.outcomes;
+    let optional = match outcome_type {
+        OutcomeType::CriticalSuccess => outcomes.critical_success.as_ref(),
+        OutcomeType::Partial => outcomes.partial.as_ref(),
+        OutcomeType::CriticalFailure => outcomes.critical_failure.as_ref(),
+        OutcomeType::Success => return (OutcomeType::Success, &outcomes.success),
+        OutcomeType::Failure => return (OutcomeType::Failure, &outcomes.failure),
+    };
+    match optional {
+        Some(outcome) => (outcome_type, outcome),
+        None => select_outcome(challenge, fallback, OutcomeType::Failure),
+    }
+}
+
+/// Evaluate a dice pool by net successes. A DC is read as the number of
+/// successes required (at least one).
+fn evaluate_pool(challenge: &Challenge, successes: i32, botched: bool) -> RollEvaluation<'_> {
+    let required = match &challenge.difficulty {
+        Difficulty::DC(dc) => (*dc as i32).max(1),
+        _ => 1,
+    };
+    let margin = Some(successes.saturating_sub(required));
+    let tier = if botched {
+        select_outcome(challenge, OutcomeType::CriticalFailure, OutcomeType::Failure)
+    } else if successes >= required.saturating_add(EXCEPTIONAL_POOL_MARGIN) {
+        select_outcome(challenge, OutcomeType::CriticalSuccess, OutcomeType::Success)
+    } else if successes >= required {
+        (OutcomeType::Success, &challenge.outcomes.success)
+    } else if successes > 0 {
+        select_outcome(challenge, OutcomeType::Partial, OutcomeType::Failure)
+    } else {
+        (OutcomeType::Failure, &challenge.outcomes.failure)
+    };
+    RollEvaluation::new(tier, margin)
+}
+
+/// Evaluate a Fate roll by shifts over the opposition. A DC is read as the
+/// opposition's ladder rung; ties succeed at a cost, 3+ shifts succeed with style.
+fn evaluate_fate(challenge: &Challenge, ladder: FateLadder) -> RollEvaluation<'_> {
+    let opposition = match &challenge.difficulty {
+        Difficulty::DC(dc) => FateLadder(*dc as i32),
+        _ => FATE_DEFAULT_OPPOSITION,
+    };
+    let shifts = ladder.shifts_over(opposition);
+    let tier = if shifts >= FATE_STYLE_SHIFTS {
+        select_outcome(challenge, OutcomeType::CriticalSuccess, OutcomeType::Success)
+    } else if shifts > 0 {
+        (OutcomeType::Success, &challenge.outcomes.success)
+    } else if shifts == 0 {
+        select_outcome(challenge, OutcomeType::Partial, OutcomeType::Success)
+    } else {
+        (OutcomeType::Failure, &challenge.outcomes.failure)
+    };
+    RollEvaluation::new(tier, Some(shifts))
+}
+
+/// Evaluate a total against fixed bands; the difficulty only sets the fiction
+fn evaluate_bands(challenge: &Challenge, total: i32, partial_at: i32, success_at: i32) -> RollEvaluation<'_> {
+    let tier = if total >= success_at {
+        (OutcomeType::Success, &challenge.outcomes.success)
+    } else if total >= partial_at {
+        select_outcome(challenge, OutcomeType::Partial, OutcomeType::Success)
+    } else {
+        (OutcomeType::Failure, &challenge.outcomes.failure)
+    };
+    RollEvaluation::new(tier, Some(total.saturating_sub(success_at)))
+}
+
+/// Evaluate four degrees of success around a DC, with natural 20s and 1s
+/// moving the result one degree up or down
+fn evaluate_degrees<'a>(
+    challenge: &'a Challenge,
+    roll: &RollOutcome,
+    dc: i32,
+    critical_margin: i32,
+) -> RollEvaluation<'a> {
+    let margin = roll.total().saturating_sub(dc);
+    // 0 = critical failure, 1 = failure, 2 = success, 3 = critical success
+    let mut degree = if margin >= critical_margin {
+        3
+    } else if margin >= 0 {
+        2
+    } else if margin > -critical_margin {
+        1
+    } else {
+        0
+    };
+    if roll.natural_20 {
+        degree = (degree + 1).min(3);
+    } else if roll.natural_1 {
+        degree = (degree - 1).max(0);
+    }
+    let tier = match degree {
+        3 => select_outcome(challenge, OutcomeType::CriticalSuccess, OutcomeType::Success),
+        2 => (OutcomeType::Success, &challenge.outcomes.success),
+        1 => (OutcomeType::Failure, &challenge.outcomes.failure),
+        _ => select_outcome(challenge, OutcomeType::CriticalFailure, OutcomeType::Failure),
+    };
+    RollEvaluation::new(tier, Some(margin))
+}
+
+/// Evaluate a percentile roll under a target. A roll of 01 and an extreme
+/// success reach the critical tier, hard and regular successes the success
+/// tier; each success reports its own level.
+fn evaluate_roll_under(challenge: &Challenge, roll: i32, target: i32) -> RollEvaluation<'_> {
+    let level = match roll {
+        1 => Some(SuccessLevel::Critical),
+        _ if roll_under_fumbles(roll, target) => None,
+        _ if roll <= target / 5 => Some(SuccessLevel::Extreme),
+        _ if roll <= target / 2 => Some(SuccessLevel::Hard),
+        _ if roll <= target => Some(SuccessLevel::Regular),
+        _ => None,
+    };
+    let tier = match level {
+        Some(SuccessLevel::Critical | SuccessLevel::Extreme) => {
+            select_outcome(challenge, OutcomeType::CriticalSuccess, OutcomeType::Success)
+        }
+        Some(SuccessLevel::Hard | SuccessLevel::Regular) => (OutcomeType::Success, &challenge.outcomes.success),
+        None if roll_under_fumbles(roll, target) => {
+            select_outcome(challenge, OutcomeType::CriticalFailure, OutcomeType::Failure)
+        }
+        None => (OutcomeType::Failure, &challenge.outcomes.failure),
+    };
+    RollEvaluation {
+        success_level: level,
+        ..RollEvaluation::new(tier, Some(target.saturating_sub(roll)))
+    }
+}
+
+/// Evaluate a plain roll against the challenge difficulty
+fn evaluate_flat<'a>(challenge: &'a Challenge, outcome: &RollOutcome) -> RollEvaluation<'a> {
+    let (roll, total) = (outcome.raw_roll, outcome.total());
+    match &challenge.difficulty {
+        Difficulty::DC(dc) => {
+            let margin = Some(total.saturating_sub(*dc as i32));
+            if outcome.natural_20 {
+                if let Some(ref critical_success) = challenge.outcomes.critical_success {
+                    return RollEvaluation::new((OutcomeType::CriticalSuccess, critical_success), margin);
+                }
+            }
+            if outcome.natural_1 {
+                if let Some(ref critical_failure) = challenge.outcomes.critical_failure {
+                    return RollEvaluation::new((OutcomeType::CriticalFailure, critical_failure), margin);
+                }
+            }
+
+            if total >= *dc as i32 {
+                RollEvaluation::new((OutcomeType::Success, &challenge.outcomes.success), margin)
+            } else {
+                RollEvaluation::new((OutcomeType::Failure, &challenge.outcomes.failure), margin)
+            }
+        }
+        Difficulty::Percentage(target) => {
+            let margin = Some((*target as i32).saturating_sub(roll));
+            if roll == 1 {
+                if let Some(ref critical_success) = challenge.outcomes.critical_success {
+                    return RollEvaluation::new((OutcomeType::CriticalSuccess, critical_success), margin);
+                }
+            }
+            if roll == 100 {
+                if let Some(ref critical_failure) = challenge.outcomes.critical_failure {
+                    return RollEvaluation::new((OutcomeType::CriticalFailure, critical_failure), margin);
+                }
+            }
+
+            if roll <= *target as i32 {
+                RollEvaluation::new((OutcomeType::Success, &challenge.outcomes.success), margin)
+            } else {
+                RollEvaluation::new((OutcomeType::Failure, &challenge.outcomes.failure), margin)
+            }
+        }
+        Difficulty::Descriptor(_) => {
+            if roll >= 11 {
+                RollEvaluation::new((OutcomeType::Success, &challenge.outcomes.success), None)
+            } else {
+                RollEvaluation::new((OutcomeType::Failure, &challenge.outcomes.failure), None)
+            }
+        }
+        Difficulty::Opposed => RollEvaluation::new((OutcomeType::Success, &challenge.outcomes.success), None),
+        Difficulty::Custom(_) => RollEvaluation::new((OutcomeType::Success, &challenge.outcomes.success), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::ChallengeOutcomes;
+    use crate::domain::value_objects::{DiceFormula, RuleSystemConfig, WorldId};
+
+    fn challenge(difficulty: Difficulty) -> Challenge {
+        let outcomes = ChallengeOutcomes::simple("up", "down")
+            .with_partial("at a cost")
+            .with_critical_success("flawless")
+            .with_critical_failure("disaster");
+        Challenge::new(WorldId::new(), "Climb the wall", difficulty).with_outcomes(outcomes)
+    }
+
+    fn grade(bands: ResolutionBands, difficulty: Difficulty, raw: i32, modifier: i32) -> (OutcomeType, Option<i32>) {
+        let challenge = challenge(difficulty);
+        let result = evaluate_roll(&challenge, bands, &RollOutcome::d20(raw, modifier));
+        (result.outcome_type, result.margin)
+    }
+
+    #[test]
+    fn test_pathfinder_degrees_of_success() {
+        let bands = RuleSystemConfig::pathfinder_2e().resolution_bands();
+        let dc = || Difficulty::DC(20);
+        assert_eq!(grade(bands, dc(), 15, 15), (OutcomeType::CriticalSuccess, Some(10)));
+        assert_eq!(grade(bands, dc(), 12, 8), (OutcomeType::Success, Some(0)));
+        assert_eq!(grade(bands, dc(), 5, 6), (OutcomeType::Failure, Some(-9)));
+        assert_eq!(grade(bands, dc(), 2, 5), (OutcomeType::CriticalFailure, Some(-13)));
+        // Natural 20 and 1 move one degree
+        assert_eq!(grade(bands, dc(), 20, -5), (OutcomeType::Success, Some(-5)));
+        assert_eq!(grade(bands, dc(), 1, 25), (OutcomeType::Failure, Some(6)));
+    }
+
+    #[test]
+    fn test_naturals_come_from_the_kept_d20() {
+        let challenge = challenge(Difficulty::DC(25));
+        let bands = RuleSystemConfig::dnd_5e().resolution_bands();
+        let formula = DiceFormula::parse("1d20+1d4").unwrap();
+        let mut faces = [17, 3].into_iter();
+        let result = formula.roll_with(|_| faces.next().unwrap());
+        let roll = RollOutcome::from_result(&result, result.dice_total, 2);
+        assert_eq!(roll.raw_roll, 20);
+        assert_eq!(evaluate_roll(&challenge, bands, &roll).outcome_type, OutcomeType::Failure);
+
+        let mut faces = [20, 1].into_iter();
+        let result = formula.roll_with(|_| faces.next().unwrap());
+        let roll = RollOutcome::from_result(&result, result.dice_total, 2);
+        assert_eq!(evaluate_roll(&challenge, bands, &roll).outcome_type, OutcomeType::CriticalSuccess);
+    }
+
+    #[test]
+    fn test_call_of_cthulhu_success_levels_and_fumbles() {
+        let bands = RuleSystemConfig::call_of_cthulhu_7e().resolution_bands();
+        let skill = |target| Difficulty::Percentage(target);
+        assert_eq!(grade(bands, skill(60), 1, 0).0, OutcomeType::CriticalSuccess);
+        assert_eq!(grade(bands, skill(60), 12, 0), (OutcomeType::CriticalSuccess, Some(48)));
+        assert_eq!(grade(bands, skill(60), 30, 0), (OutcomeType::Success, Some(30)));
+        assert_eq!(grade(bands, skill(60), 61, 0), (OutcomeType::Failure, Some(-1)));
+        assert_eq!(grade(bands, skill(60), 97, 0).0, OutcomeType::Failure);
+        assert_eq!(grade(bands, skill(60), 100, 0).0, OutcomeType::CriticalFailure);
+        assert_eq!(grade(bands, skill(40), 96, 0).0, OutcomeType::CriticalFailure);
+
+        let level = |raw| {
+            let challenge = challenge(skill(60));
+            evaluate_roll(&challenge, bands, &RollOutcome::flat(raw, 0)).display_name()
+        };
+        assert_eq!(level(1), "Critical Success!");
+        assert_eq!(level(12), "Extreme Success");
+        assert_eq!(level(30), "Hard Success");
+        assert_eq!(level(45), "Regular Success");
+        assert_eq!(level(61), "Failure");
+    }
+
+    #[test]
+    fn test_pbta_bands_ignore_the_dc() {
+        let bands = RuleSystemConfig::powered_by_apocalypse().resolution_bands();
+        let dc = || Difficulty::DC(15);
+        assert_eq!(grade(bands, dc(), 8, 2), (OutcomeType::Success, Some(0)));
+        assert_eq!(grade(bands, dc(), 7, 1), (OutcomeType::Partial, Some(-2)));
+        assert_eq!(grade(bands, dc(), 5, 1), (OutcomeType::Failure, Some(-4)));
+    }
+
+    #[test]
+    fn test_fate_shifts_from_a_plain_total() {
+        let bands = RuleSystemConfig::fate_core().resolution_bands();
+        assert_eq!(grade(bands, Difficulty::DC(2), 2, 3), (OutcomeType::CriticalSuccess, Some(3)));
+        assert_eq!(grade(bands, Difficulty::DC(2), 0, 2), (OutcomeType::Partial, Some(0)));
+        assert_eq!(grade(bands, Difficulty::DC(2), -1, 2), (OutcomeType::Failure, Some(-1)));
+    }
+
+    fn contest(rule_system: &RuleSystemConfig, initiator: RollOutcome, opponent: RollOutcome) -> (OutcomeType, Option<i32>) {
+        let challenge = challenge(Difficulty::Opposed);
+        let result = evaluate_opposed(&challenge, rule_system, &initiator, &opponent);
+        (result.outcome_type, result.margin)
+    }
+
+    #[test]
+    fn test_opposed_ties_follow_the_rule_system() {
+        let tie = (RollOutcome::flat(12, 3), RollOutcome::flat(10, 5));
+        assert_eq!(
+            contest(&RuleSystemConfig::dnd_5e(), tie.0, tie.1),
+            (OutcomeType::Failure, Some(0))
+        );
+        assert_eq!(
+            contest(&RuleSystemConfig::pathfinder_2e(), tie.0, tie.1),
+            (OutcomeType::Success, Some(0))
+        );
+        assert_eq!(
+            contest(&RuleSystemConfig::fate_core(), tie.0, tie.1),
+            (OutcomeType::Partial, Some(0))
+        );
+        assert_eq!(
+            contest(&RuleSystemConfig::dnd_5e(), RollOutcome::flat(15, 3), RollOutcome::flat(10, 5)),
+            (OutcomeType::Success, Some(3))
+        );
+    }
+
+    #[test]
+    fn test_opposed_roll_under_compares_success_levels() {
+        let coc = RuleSystemConfig::call_of_cthulhu_7e();
+        // Hard success (30 against 60) beats a regular one (40 against 50)
+        assert_eq!(
+            contest(&coc, RollOutcome::flat(30, 60), RollOutcome::flat(40, 50)),
+            (OutcomeType::Success, Some(1))
+        );
+        // Same level: the higher skill wins
+        assert_eq!(
+            contest(&coc, RollOutcome::flat(45, 60), RollOutcome::flat(40, 50)).0,
+            OutcomeType::Success
+        );
+        assert_eq!(
+            contest(&coc, RollOutcome::flat(40, 50), RollOutcome::flat(45, 60)).0,
+            OutcomeType::Failure
+        );
+    }
+
+    #[test]
+    fn test_standard_bands_keep_natural_criticals() {
+        assert_eq!(
+            grade(ResolutionBands::Standard, Difficulty::DC(25), 20, 0),
+            (OutcomeType::CriticalSuccess, Some(-5))
+        );
+        assert_eq!(grade(ResolutionBands::Standard, Difficulty::DC(12), 9, 3), (OutcomeType::Success, Some(0)));
+    }
+
+    #[test]
+    fn test_extreme_manual_totals_saturate() {
+        let pf2e = RuleSystemConfig::pathfinder_2e();
+        assert_eq!(
+            grade(pf2e.resolution_bands(), Difficulty::DC(20), i32::MIN, 0),
+            (OutcomeType::CriticalFailure, Some(i32::MIN))
+        );
+        let pbta = RuleSystemConfig::powered_by_apocalypse().resolution_bands();
+        assert_eq!(grade(pbta, Difficulty::DC(15), i32::MIN, 0).1, Some(i32::MIN));
+        assert_eq!(
+            contest(&pf2e, RollOutcome::flat(i32::MAX, 0), RollOutcome::flat(i32::MIN, 0)),
+            (OutcomeType::Success, Some(i32::MAX))
+        );
+    }
+}
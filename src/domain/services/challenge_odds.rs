@@ -0,0 +1,199 @@
+//! Success probability for challenges
+//!
+//! Classifies every state a dice formula can roll with the same evaluation the
+//! live resolution uses, so the odds shown to the DM always agree with how
+//! rolls are actually judged.
+
+use crate::domain::entities::{Challenge, OutcomeType};
+use crate::domain::value_objects::{DiceFormula, DieKind, FateLadder, ResolutionBands};
+
+use super::{evaluate_roll, RollDistribution, RollOutcome};
+
+/// Chance of each outcome tier for a roll against a challenge
+///
+/// Tiers the challenge does not define are folded into the tier a roll would
+/// actually resolve to, so the chances always sum to one.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChallengeOdds {
+    pub critical_success: f64,
+    pub success: f64,
+    pub partial: f64,
+    pub failure: f64,
+    pub critical_failure: f64,
+    /// False when the formula was too complex to enumerate and was simulated
+    pub exact: bool,
+}
+
+impl ChallengeOdds {
+    /// Calculate the odds of rolling `formula` with `skill_modifier` against a challenge
+    ///
+    /// The modifier is applied the way a rolled formula applies it: as extra
+    /// dice for success-counting pools and as a flat bonus otherwise.
+    pub fn calculate(
+        challenge: &Challenge,
+        bands: ResolutionBands,
+        formula: &DiceFormula,
+        skill_modifier: i32,
+    ) -> Self {
+        let counts_successes = formula.counts_successes();
+        let formula = if counts_successes {
+            formula.clone().with_pool_bonus(skill_modifier)
+        } else {
+            formula.clone().with_modifier(skill_modifier)
+        };
+        let rolls_fate = formula
+            .dice_terms()
+            .iter()
+            .any(|term| term.kind == DieKind::Fate);
+
+        // Natural 20s and 1s can only be told from the dice total of a lone d20
+        let single_d20 = formula.is_single_d20();
+
+        let distribution = RollDistribution::of(&formula);
+        let mut odds = Self {
+            exact: distribution.is_exact(),
+            ..Self::default()
+        };
+        for (state, probability) in distribution.states() {
+            let total = state.dice_total.saturating_add(formula.modifier);
+            let roll = RollOutcome {
+                successes: counts_successes.then_some(total),
+                botched: counts_successes && state.any_failure && !state.any_success,
+                fate_ladder: rolls_fate.then_some(FateLadder(total)),
+                ..if single_d20 {
+                    RollOutcome::d20(state.dice_total, skill_modifier)
+                } else {
+                    RollOutcome::flat(state.dice_total, skill_modifier)
+                }
+            };
+            let outcome_type = evaluate_roll(challenge, bands, &roll).outcome_type;
+            *odds.tier_mut(outcome_type) += probability;
+        }
+        odds
+    }
+
+    fn tier_mut(&mut self, outcome_type: OutcomeType) -> &mut f64 {
+        match outcome_type {
+            OutcomeType::CriticalSuccess => &mut self.critical_success,
+            OutcomeType::Success => &mut self.success,
+            OutcomeType::Partial => &mut self.partial,
+            OutcomeType::Failure => &mut self.failure,
+            OutcomeType::CriticalFailure => &mut self.critical_failure,
+        }
+    }
+
+    /// Chance of a success or critical success
+    pub fn success_chance(&self) -> f64 {
+        self.critical_success + self.success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{ChallengeOutcomes, Difficulty};
+    use crate::domain::value_objects::{DiceSystem, RuleSystemConfig, WorldId};
+
+    fn challenge(difficulty: Difficulty, outcomes: ChallengeOutcomes) -> Challenge {
+        Challenge::new(WorldId::new(), "Climb the wall", difficulty).with_outcomes(outcomes)
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_d20_against_dc_with_crits() {
+        let outcomes = ChallengeOutcomes::simple("up", "down")
+            .with_critical_success("flawless")
+            .with_critical_failure("falls");
+        let challenge = challenge(Difficulty::DC(15), outcomes);
+        let odds = ChallengeOdds::calculate(
+            &challenge,
+            ResolutionBands::Standard,
+            &DiceFormula::parse("1d20").unwrap(),
+            3,
+        );
+
+        // Needs 12+ on the die; natural 20 and natural 1 are their own tiers
+        assert!(odds.exact);
+        assert_close(odds.critical_success, 0.05);
+        assert_close(odds.success, 0.40);
+        assert_close(odds.failure, 0.50);
+        assert_close(odds.critical_failure, 0.05);
+        assert_close(odds.success_chance(), 0.45);
+    }
+
+    #[test]
+    fn test_undefined_tiers_fold_into_success_and_failure() {
+        let challenge = challenge(Difficulty::DC(15), ChallengeOutcomes::simple("up", "down"));
+        let odds = ChallengeOdds::calculate(
+            &challenge,
+            ResolutionBands::Standard,
+            &DiceFormula::parse("1d20").unwrap(),
+            3,
+        );
+        assert_close(odds.critical_success + odds.critical_failure, 0.0);
+        assert_close(odds.success, 0.45);
+        assert_close(odds.failure, 0.55);
+    }
+
+    #[test]
+    fn test_dice_pool_odds_count_successes_and_botches() {
+        let system = DiceSystem::DicePool {
+            die_type: 10,
+            success_threshold: 8,
+            ones_cancel: true,
+            again_threshold: None,
+        };
+        let pool = DiceFormula::dice_pool(1, &system).unwrap();
+        let outcomes = ChallengeOutcomes::simple("up", "down").with_critical_failure("falls");
+        let challenge = challenge(Difficulty::DC(1), outcomes);
+
+        // The skill modifier adds a die: two d10s
+        let odds = ChallengeOdds::calculate(&challenge, ResolutionBands::Standard, &pool, 1);
+        // Botch: no successes and at least one 1
+        let no_success = 0.7 * 0.7;
+        let no_success_no_ones = 0.6 * 0.6;
+        assert_close(odds.critical_failure, no_success - no_success_no_ones);
+        // Success needs net successes >= 1
+        assert_close(odds.success, 0.3 * 0.3 + 2.0 * 0.3 * 0.6);
+        assert_close(odds.success + odds.failure + odds.critical_failure, 1.0);
+    }
+
+    #[test]
+    fn test_pbta_odds_follow_the_bands() {
+        let outcomes = ChallengeOutcomes::simple("up", "down").with_partial("at a cost");
+        let challenge = challenge(Difficulty::DC(10), outcomes);
+        let bands = RuleSystemConfig::powered_by_apocalypse().resolution_bands();
+        let odds = ChallengeOdds::calculate(&challenge, bands, &DiceFormula::parse("2d6").unwrap(), 1);
+
+        // 2d6+1: 10+ needs 9+ on the dice, 7-9 needs 6-8
+        assert_close(odds.success, 10.0 / 36.0);
+        assert_close(odds.partial, 16.0 / 36.0);
+        assert_close(odds.failure, 10.0 / 36.0);
+    }
+
+    #[test]
+    fn test_fate_odds_include_ties_as_partial() {
+        let outcomes = ChallengeOutcomes::simple("up", "down").with_partial("at a cost");
+        let challenge = challenge(Difficulty::DC(2), outcomes);
+        let odds = ChallengeOdds::calculate(
+            &challenge,
+            ResolutionBands::Standard,
+            &DiceFormula::parse("4dF").unwrap(),
+            2,
+        );
+
+        // Tie when the dice total 0
+        assert_close(odds.partial, 19.0 / 81.0);
+        let total = odds.critical_success + odds.success + odds.partial + odds.failure
+            + odds.critical_failure;
+        assert_close(total, 1.0);
+    }
+}
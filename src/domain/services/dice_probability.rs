@@ -0,0 +1,490 @@
+//! Exact probability distributions for dice formulas
+//!
+//! Each dice group is reduced to a distribution over per-die scores (the face,
+//! or the success and failure marks it earns), folding rerolls and explosions
+//! into that single die. Groups are then built up by convolution, keep/drop
+//! rules by a pass over the order statistics, and arithmetic by combining the
+//! distributions of both operands.
+//!
+//! Explosions are followed until the remaining chance drops below
+//! `NEGLIGIBLE_PROBABILITY`. Formulas that keep or drop exploding dice, or
+//! whose state space is too large to enumerate, fall back to a seeded
+//! simulation and are flagged as inexact.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::domain::value_objects::{
+    DiceExpr, DiceFormula, DiceRollResult, DiceTerm, KeepRule, SeededRollSource,
+    MAX_EXPLOSIONS_PER_DIE, MAX_REROLLS_PER_DIE,
+};
+
+/// Chance below which an explosion or reroll chain is no longer followed
+const NEGLIGIBLE_PROBABILITY: f64 = 1e-12;
+
+/// Combination steps allowed before giving up on an exact answer
+const MAX_EXACT_WORK: u64 = 20_000_000;
+
+/// Rolls used when a formula is simulated instead of enumerated
+const SIMULATED_ROLLS: u64 = 20_000;
+
+/// Seed for simulated rolls, so repeated calculations agree
+const SIMULATION_SEED: u64 = 0x0DD5;
+
+/// The parts of a rolled formula that decide a challenge outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RollState {
+    /// Value of the dice expression before the formula's flat modifier
+    pub dice_total: i32,
+    /// A success-counting group rolled at least one success
+    pub any_success: bool,
+    /// A success-counting group rolled at least one failure die
+    pub any_failure: bool,
+}
+
+impl RollState {
+    fn constant(value: i32) -> Self {
+        Self {
+            dice_total: value,
+            any_success: false,
+            any_failure: false,
+        }
+    }
+
+    /// Read the state of a rolled result
+    pub fn of_result(result: &DiceRollResult) -> Self {
+        Self {
+            dice_total: result.dice_total,
+            any_success: result.terms.iter().any(|t| t.successes.unwrap_or(0) > 0),
+            any_failure: result.terms.iter().any(|t| t.failures > 0),
+        }
+    }
+}
+
+/// Probability distribution of the states a formula can roll
+#[derive(Debug, Clone)]
+pub struct RollDistribution {
+    states: BTreeMap<RollState, f64>,
+    exact: bool,
+}
+
+impl RollDistribution {
+    /// Compute the distribution of `formula`
+    pub fn of(formula: &DiceFormula) -> Self {
+        let mut work = 0;
+        match expression_distribution(&formula.expression, &mut work) {
+            Some(states) => Self {
+                states: states.into_iter().collect(),
+                exact: true,
+            },
+            None => Self::simulate(formula),
+        }
+    }
+
+    /// Estimate the distribution by rolling the formula many times
+    fn simulate(formula: &DiceFormula) -> Self {
+        let mut source = SeededRollSource::new(SIMULATION_SEED, 0);
+        let mut states = BTreeMap::new();
+        for _ in 0..SIMULATED_ROLLS {
+            let result = formula.roll_from(&mut source);
+            *states.entry(RollState::of_result(&result)).or_insert(0.0) +=
+                1.0 / SIMULATED_ROLLS as f64;
+        }
+        Self {
+            states,
+            exact: false,
+        }
+    }
+
+    /// Every reachable state with its probability
+    pub fn states(&self) -> impl Iterator<Item = (&RollState, f64)> {
+        self.states.iter().map(|(state, p)| (state, *p))
+    }
+
+    /// Whether the distribution was enumerated rather than simulated
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+type Distribution<K> = HashMap<K, f64>;
+
+/// What a single counted die contributes: face sum, successes and failures
+type DieScore = (i32, u32, u32);
+
+/// Add a probability to a distribution entry
+fn add<K: std::hash::Hash + Eq>(dist: &mut Distribution<K>, key: K, p: f64) {
+    *dist.entry(key).or_insert(0.0) += p;
+}
+
+/// Charge `steps` against the work budget, failing once it is exhausted
+fn charge(work: &mut u64, steps: usize) -> Option<()> {
+    *work += steps as u64;
+    (*work <= MAX_EXACT_WORK).then_some(())
+}
+
+fn expression_distribution(expr: &DiceExpr, work: &mut u64) -> Option<Distribution<RollState>> {
+    match expr {
+        DiceExpr::Constant(n) => Some(HashMap::from([(RollState::constant(*n), 1.0)])),
+        DiceExpr::Dice(term) => term_distribution(term, work),
+        DiceExpr::Negate(inner) => {
+            let inner = expression_distribution(inner, work)?;
+            let mut dist = HashMap::with_capacity(inner.len());
+            for (state, p) in inner {
+                let negated = RollState {
+                    dice_total: state.dice_total.saturating_neg(),
+                    ..state
+                };
+                add(&mut dist, negated, p);
+            }
+            Some(dist)
+        }
+        DiceExpr::Binary { op, left, right } => {
+            let left = expression_distribution(left, work)?;
+            let right = expression_distribution(right, work)?;
+            charge(work, left.len() * right.len())?;
+            let mut dist = HashMap::new();
+            for (l, lp) in &left {
+                for (r, rp) in &right {
+                    let state = RollState {
+                        dice_total: op.apply(l.dice_total, r.dice_total),
+                        any_success: l.any_success || r.any_success,
+                        any_failure: l.any_failure || r.any_failure,
+                    };
+                    add(&mut dist, state, lp * rp);
+                }
+            }
+            Some(dist)
+        }
+    }
+}
+
+/// Distribution of a single dice group
+fn term_distribution(term: &DiceTerm, work: &mut u64) -> Option<Distribution<RollState>> {
+    let faces = first_face_distribution(term);
+    let scores = match &term.keep {
+        None => {
+            let per_die = die_distribution(term, &faces, work)?;
+            let mut scores: Distribution<DieScore> = HashMap::from([((0, 0, 0), 1.0)]);
+            for _ in 0..term.count {
+                charge(work, scores.len() * per_die.len())?;
+                let mut next = HashMap::new();
+                for (acc, p) in &scores {
+                    for (die, q) in &per_die {
+                        add(&mut next, combine(*acc, *die), p * q);
+                    }
+                }
+                scores = next;
+            }
+            scores
+        }
+        // Ranking a variable number of exploded dice is left to simulation
+        Some(_) if term.explode.is_some() => return None,
+        Some(keep) => kept_distribution(term, *keep, &faces, work)?,
+    };
+
+    let mut dist = HashMap::new();
+    for ((sum, successes, failures), p) in scores {
+        let state = if term.success.is_some() {
+            RollState {
+                dice_total: successes as i32 - failures as i32,
+                any_success: successes > 0,
+                any_failure: failures > 0,
+            }
+        } else {
+            RollState::constant(sum)
+        };
+        add(&mut dist, state, p);
+    }
+    Some(dist)
+}
+
+fn combine(a: DieScore, b: DieScore) -> DieScore {
+    (a.0.saturating_add(b.0), a.1 + b.1, a.2 + b.2)
+}
+
+/// Score of a single counted face
+fn score(term: &DiceTerm, face: i32) -> DieScore {
+    match &term.success {
+        Some(success) => (
+            0,
+            success.matches(face) as u32,
+            term.failure.is_some_and(|f| f.matches(face)) as u32,
+        ),
+        None => (face, 0, 0),
+    }
+}
+
+/// Faces of a fresh die, lowest first, each equally likely
+fn uniform_faces(term: &DiceTerm) -> Vec<(i32, f64)> {
+    let (min_face, max_face) = term.face_range();
+    let chance = 1.0 / (max_face - min_face + 1) as f64;
+    (min_face..=max_face).map(|face| (face, chance)).collect()
+}
+
+/// Faces a die settles on after any rerolls, lowest first
+fn first_face_distribution(term: &DiceTerm) -> Vec<(i32, f64)> {
+    let uniform = uniform_faces(term);
+    let mut faces = uniform.clone();
+    let Some(reroll) = &term.reroll else {
+        return faces;
+    };
+
+    let limit = if reroll.once { 1 } else { MAX_REROLLS_PER_DIE };
+    for _ in 0..limit {
+        let pending: f64 = faces
+            .iter()
+            .filter(|(face, _)| reroll.target.matches(*face))
+            .map(|(_, p)| p)
+            .sum();
+        if pending < NEGLIGIBLE_PROBABILITY {
+            break;
+        }
+        for ((face, p), (_, fresh)) in faces.iter_mut().zip(&uniform) {
+            if reroll.target.matches(*face) {
+                *p = 0.0;
+            }
+            *p += pending * fresh;
+        }
+    }
+    faces
+}
+
+/// Score distribution of one die, including the extra dice it explodes into
+fn die_distribution(
+    term: &DiceTerm,
+    first_faces: &[(i32, f64)],
+    work: &mut u64,
+) -> Option<Distribution<DieScore>> {
+    let uniform = uniform_faces(term);
+    let mut dist = HashMap::new();
+    let mut chain: Distribution<DieScore> = HashMap::from([((0, 0, 0), 1.0)]);
+    let mut faces = first_faces;
+    let mut explosions = 0;
+
+    while !chain.is_empty() {
+        charge(work, chain.len() * faces.len())?;
+        let mut exploding = HashMap::new();
+        for (acc, p) in &chain {
+            for (face, q) in faces {
+                let scored = combine(*acc, score(term, *face));
+                let explodes = explosions < MAX_EXPLOSIONS_PER_DIE
+                    && term.explode.is_some_and(|e| e.matches(*face));
+                if explodes {
+                    add(&mut exploding, scored, p * q);
+                } else {
+                    add(&mut dist, scored, p * q);
+                }
+            }
+        }
+        if exploding.values().sum::<f64>() < NEGLIGIBLE_PROBABILITY {
+            break;
+        }
+        chain = exploding;
+        faces = uniform.as_slice();
+        explosions += 1;
+    }
+    Some(dist)
+}
+
+/// Score distribution of a group with a keep/drop rule
+///
+/// Faces are visited from the end being kept; at each face we choose how many
+/// of the remaining dice show it, given that none show a face already visited.
+fn kept_distribution(
+    term: &DiceTerm,
+    keep: KeepRule,
+    faces: &[(i32, f64)],
+    work: &mut u64,
+) -> Option<Distribution<DieScore>> {
+    let count = term.count;
+    let (keep_high, kept) = match keep {
+        KeepRule::KeepHighest(n) => (true, n.min(count)),
+        KeepRule::KeepLowest(n) => (false, n.min(count)),
+        KeepRule::DropHighest(n) => (false, count.saturating_sub(n)),
+        KeepRule::DropLowest(n) => (true, count.saturating_sub(n)),
+    };
+    let ordered: Vec<(i32, f64)> = if keep_high {
+        faces.iter().rev().copied().collect()
+    } else {
+        faces.to_vec()
+    };
+    let ln_factorials = ln_factorials(count);
+
+    // (dice placed so far, score of the kept dice) -> probability
+    let mut states: Distribution<(u32, DieScore)> = HashMap::from([((0, (0, 0, 0)), 1.0)]);
+    let mut remaining_mass: f64 = ordered.iter().map(|(_, p)| p).sum();
+    for (face, p) in ordered {
+        let q = if remaining_mass > 0.0 {
+            (p / remaining_mass).min(1.0)
+        } else {
+            1.0
+        };
+        remaining_mass -= p;
+        charge(work, states.len() * (count as usize + 1))?;
+
+        let mut next = HashMap::new();
+        for ((placed, acc), prob) in &states {
+            let left = count - placed;
+            for shown in 0..=left {
+                let chance = binomial(&ln_factorials, left, shown, q);
+                if chance == 0.0 {
+                    continue;
+                }
+                let keeping = shown.min(kept.saturating_sub(*placed));
+                let face_score = score(term, face);
+                let added = (
+                    face_score.0.saturating_mul(keeping as i32),
+                    face_score.1 * keeping,
+                    face_score.2 * keeping,
+                );
+                add(&mut next, (placed + shown, combine(*acc, added)), prob * chance);
+            }
+        }
+        states = next;
+    }
+
+    let mut dist = HashMap::new();
+    for ((_, score), p) in states {
+        add(&mut dist, score, p);
+    }
+    Some(dist)
+}
+
+/// ln(n!) for 0..=n
+fn ln_factorials(n: u32) -> Vec<f64> {
+    let mut table = Vec::with_capacity(n as usize + 1);
+    let mut acc = 0.0;
+    table.push(acc);
+    for i in 1..=n {
+        acc += (i as f64).ln();
+        table.push(acc);
+    }
+    table
+}
+
+/// Chance that exactly `k` of `n` dice land on an outcome of chance `q`
+fn binomial(ln_factorials: &[f64], n: u32, k: u32, q: f64) -> f64 {
+    if q <= 0.0 {
+        return if k == 0 { 1.0 } else { 0.0 };
+    }
+    if q >= 1.0 {
+        return if k == n { 1.0 } else { 0.0 };
+    }
+    let (n_us, k_us) = (n as usize, k as usize);
+    let ln_choose = ln_factorials[n_us] - ln_factorials[k_us] - ln_factorials[n_us - k_us];
+    (ln_choose + k as f64 * q.ln() + (n - k) as f64 * (1.0 - q).ln()).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distribution(formula: &str) -> RollDistribution {
+        RollDistribution::of(&DiceFormula::parse(formula).unwrap())
+    }
+
+    /// Chance that the dice total is exactly `value`
+    fn probability_of_total(dist: &RollDistribution, value: i32) -> f64 {
+        dist.states()
+            .filter(|(state, _)| state.dice_total == value)
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    fn total_mass(dist: &RollDistribution) -> f64 {
+        dist.states().map(|(_, p)| p).sum()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_single_die_is_uniform() {
+        let dist = distribution("1d20");
+        assert!(dist.is_exact());
+        for face in 1..=20 {
+            assert_close(probability_of_total(&dist, face), 0.05);
+        }
+        assert_close(total_mass(&dist), 1.0);
+    }
+
+    #[test]
+    fn test_sums_and_arithmetic() {
+        let dist = distribution("2d6");
+        assert_close(probability_of_total(&dist, 7), 6.0 / 36.0);
+        assert_close(probability_of_total(&dist, 2), 1.0 / 36.0);
+
+        let dist = distribution("(1d4)*2");
+        assert_close(probability_of_total(&dist, 8), 0.25);
+        assert_close(probability_of_total(&dist, 3), 0.0);
+    }
+
+    #[test]
+    fn test_keep_highest_matches_order_statistics() {
+        // 2d20kh1: P(max = k) = (k^2 - (k-1)^2) / 400
+        let dist = distribution("2d20kh1");
+        for k in 1..=20 {
+            assert_close(probability_of_total(&dist, k), (2 * k - 1) as f64 / 400.0);
+        }
+
+        let dist = distribution("4d6kh3");
+        assert_close(total_mass(&dist), 1.0);
+        assert_close(probability_of_total(&dist, 18), 21.0 / 1296.0);
+        assert_close(probability_of_total(&dist, 3), 1.0 / 1296.0);
+    }
+
+    #[test]
+    fn test_rerolls_and_explosions() {
+        // Rerolling 1s once on a d6: P(1) = 1/36
+        let dist = distribution("1d6ro1");
+        assert_close(probability_of_total(&dist, 1), 1.0 / 36.0);
+        assert_close(probability_of_total(&dist, 6), 7.0 / 36.0);
+
+        // An exploding d6 never totals 6
+        let dist = distribution("1d6!");
+        assert_close(probability_of_total(&dist, 6), 0.0);
+        assert_close(probability_of_total(&dist, 7), 1.0 / 36.0);
+        assert!((total_mass(&dist) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_success_pools_track_botches() {
+        // One d10 succeeding on 8+ with 1s cancelling
+        let dist = distribution("1d10>8f1");
+        let botch: f64 = dist
+            .states()
+            .filter(|(s, _)| s.any_failure && !s.any_success)
+            .map(|(_, p)| p)
+            .sum();
+        assert_close(botch, 0.1);
+        assert_close(probability_of_total(&dist, 1), 0.3);
+        assert_close(probability_of_total(&dist, -1), 0.1);
+    }
+
+    #[test]
+    fn test_fate_dice() {
+        let dist = distribution("4dF");
+        assert_close(probability_of_total(&dist, 4), 1.0 / 81.0);
+        assert_close(probability_of_total(&dist, 0), 19.0 / 81.0);
+    }
+
+    #[test]
+    fn test_exploding_keep_is_simulated() {
+        let dist = distribution("4d6!kh3");
+        assert!(!dist.is_exact());
+        assert!((total_mass(&dist) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_long_explosion_chains_are_simulated() {
+        let dist = distribution("1d1000!>2");
+        assert!(!dist.is_exact());
+        assert!((total_mass(&dist) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,9 @@
+//! Domain services - Pure business logic that spans entities and value objects
+
+mod challenge_evaluation;
+mod challenge_odds;
+mod dice_probability;
+
+pub use challenge_evaluation::{evaluate_opposed, evaluate_roll, RollEvaluation, RollOutcome};
+pub use challenge_odds::ChallengeOdds;
+pub use dice_probability::RollDistribution;
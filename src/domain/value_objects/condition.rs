@@ -0,0 +1,245 @@
+//! Conditions - Timed states on characters (Poisoned, Frightened, Blessed)
+//!
+//! A condition lasts for a number of combat rounds, a span of game time, until
+//! the character next rests, or until it is removed. Timed conditions are
+//! measured against the session's `GameTime` and expire as it advances.
+
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Game time one combat round takes
+pub const SECONDS_PER_ROUND: i64 = 6;
+
+/// How long a condition lasts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionDuration {
+    /// A number of combat rounds
+    Rounds(u32),
+    /// A span of game time, in minutes
+    Minutes(u32),
+    /// Until the character next rests
+    UntilRest,
+    /// Until removed
+    Permanent,
+}
+
+impl ConditionDuration {
+    /// Parse a free-text duration such as "3 rounds", "1 hour" or "until rest"
+    ///
+    /// Returns None for text that names no known duration.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        if text.is_empty() || text == "permanent" || text == "indefinite" {
+            return Some(Self::Permanent);
+        }
+        if text.starts_with("until") && text.contains("rest") {
+            return Some(Self::UntilRest);
+        }
+
+        let mut words = text.split_whitespace();
+        let amount: u32 = match words.next()? {
+            "a" | "an" | "one" => 1,
+            number => number.parse().ok()?,
+        };
+        let unit = words.next()?;
+        match unit.trim_end_matches('s') {
+            "round" | "turn" => Some(Self::Rounds(amount)),
+            "minute" | "min" => Some(Self::Minutes(amount)),
+            "hour" | "hr" => Some(Self::Minutes(amount.saturating_mul(60))),
+            "day" => Some(Self::Minutes(amount.saturating_mul(60 * 24))),
+            _ => None,
+        }
+    }
+
+    /// Game time the condition lasts, if it is timed
+    pub fn span(&self) -> Option<Duration> {
+        match *self {
+            Self::Rounds(rounds) => Some(Duration::seconds(rounds as i64 * SECONDS_PER_ROUND)),
+            Self::Minutes(minutes) => Some(Duration::minutes(minutes as i64)),
+            Self::UntilRest | Self::Permanent => None,
+        }
+    }
+}
+
+impl fmt::Display for ConditionDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Rounds(1) => write!(f, "1 round"),
+            Self::Rounds(rounds) => write!(f, "{} rounds", rounds),
+            Self::Minutes(minutes) if minutes > 0 && minutes % (60 * 24) == 0 => {
+                write!(f, "{} day(s)", minutes / (60 * 24))
+            }
+            Self::Minutes(minutes) if minutes > 0 && minutes % 60 == 0 => {
+                write!(f, "{} hour(s)", minutes / 60)
+            }
+            Self::Minutes(minutes) => write!(f, "{} minute(s)", minutes),
+            Self::UntilRest => write!(f, "until rest"),
+            Self::Permanent => write!(f, "permanent"),
+        }
+    }
+}
+
+/// A condition affecting a character
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveCondition {
+    pub name: String,
+    pub description: String,
+    pub duration: ConditionDuration,
+    /// Added to the character's challenge rolls while the condition lasts
+    pub roll_modifier: i32,
+    /// Game time the condition was applied
+    pub applied_at: DateTime<Utc>,
+}
+
+impl ActiveCondition {
+    pub fn new(
+        name: impl Into<String>,
+        duration: ConditionDuration,
+        applied_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            duration,
+            roll_modifier: 0,
+            applied_at,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn with_roll_modifier(mut self, modifier: i32) -> Self {
+        self.roll_modifier = modifier;
+        self
+    }
+
+    /// Game time the condition runs out, if it is timed
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.duration.span().map(|span| self.applied_at + span)
+    }
+
+    /// Whether the condition has run out by `now`
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at().is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// One-line description for prompts and logs
+    pub fn summary(&self) -> String {
+        let mut summary = self.name.clone();
+        if !self.description.is_empty() {
+            summary.push_str(&format!(": {}", self.description));
+        }
+        if self.roll_modifier != 0 {
+            summary.push_str(&format!(" ({:+} to rolls)", self.roll_modifier));
+        }
+        summary
+    }
+}
+
+/// The conditions currently affecting a character
+///
+/// Names are unique (ignoring case); applying a condition the character
+/// already has replaces it, restarting its duration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActiveConditions(Vec<ActiveCondition>);
+
+impl ActiveConditions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveCondition> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Apply a condition, replacing any with the same name
+    pub fn apply(&mut self, condition: ActiveCondition) {
+        self.remove(&condition.name);
+        self.0.push(condition);
+    }
+
+    /// Remove a condition by name
+    pub fn remove(&mut self, name: &str) -> Option<ActiveCondition> {
+        let index = self.0.iter().position(|c| c.name.eq_ignore_ascii_case(name))?;
+        Some(self.0.remove(index))
+    }
+
+    /// Remove and return the conditions that have run out by `now`
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<ActiveCondition> {
+        self.take_where(|c| c.is_expired_at(now))
+    }
+
+    /// Remove and return the conditions a rest ends
+    pub fn end_rest(&mut self) -> Vec<ActiveCondition> {
+        self.take_where(|c| c.duration == ConditionDuration::UntilRest)
+    }
+
+    /// Total modifier the conditions give to challenge rolls
+    pub fn roll_modifier(&self) -> i32 {
+        self.0.iter().map(|c| c.roll_modifier).sum()
+    }
+
+    fn take_where(&mut self, ends: impl Fn(&ActiveCondition) -> bool) -> Vec<ActiveCondition> {
+        let (ended, kept) = std::mem::take(&mut self.0).into_iter().partition(|c| ends(c));
+        self.0 = kept;
+        ended
+    }
+}
+
+impl From<Vec<ActiveCondition>> for ActiveConditions {
+    fn from(conditions: Vec<ActiveCondition>) -> Self {
+        let mut active = Self::new();
+        for condition in conditions {
+            active.apply(condition);
+        }
+        active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_durations() {
+        assert_eq!(ConditionDuration::parse("3 rounds"), Some(ConditionDuration::Rounds(3)));
+        assert_eq!(ConditionDuration::parse("1 Hour"), Some(ConditionDuration::Minutes(60)));
+        assert_eq!(ConditionDuration::parse("a day"), Some(ConditionDuration::Minutes(1440)));
+        assert_eq!(ConditionDuration::parse("until long rest"), Some(ConditionDuration::UntilRest));
+        assert_eq!(ConditionDuration::parse("permanent"), Some(ConditionDuration::Permanent));
+        assert_eq!(ConditionDuration::parse("while the moon is full"), None);
+    }
+
+    #[test]
+    fn test_conditions_expire_with_game_time() {
+        let start = Utc::now();
+        let mut conditions = ActiveConditions::new();
+        conditions.apply(ActiveCondition::new("Stunned", ConditionDuration::Rounds(2), start));
+        conditions.apply(
+            ActiveCondition::new("Poisoned", ConditionDuration::Minutes(60), start).with_roll_modifier(-2),
+        );
+        conditions.apply(ActiveCondition::new("Exhausted", ConditionDuration::UntilRest, start));
+        conditions.apply(ActiveCondition::new("Cursed", ConditionDuration::Permanent, start));
+        assert_eq!(conditions.roll_modifier(), -2);
+
+        let expired = conditions.expire(start + Duration::seconds(12));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].name, "Stunned");
+
+        let expired = conditions.expire(start + Duration::days(30));
+        assert_eq!(expired[0].name, "Poisoned");
+        assert_eq!(conditions.roll_modifier(), 0);
+
+        assert_eq!(conditions.end_rest()[0].name, "Exhausted");
+        let remaining: Vec<&str> = conditions.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(remaining, vec!["Cursed"]);
+    }
+}
@@ -1,19 +1,51 @@
 //! Dice rolling value objects and parsing
 //!
-//! Supports dice formulas like "1d20+5", "2d6-1", "1d100", etc.
-//! Also supports manual result input for physical dice rolls.
+//! Supports dice expressions such as "1d20+5", "4d6kh3", "2d20kl1", "1d6!",
+//! "3d8+1d6+4", "d%" and "(2d6+3)*2", Fate dice ("4dF+2") and success-counting
+//! dice pools ("5d10>8f1!"). Also supports manual result input for physical
+//! dice rolls.
+//!
+//! ## Grammar
+//!
+//! ```text
+//! expression := product (("+" | "-") product)*
+//! product    := unary (("*" | "/") unary)*
+//! unary      := "-" unary | primary
+//! primary    := "(" expression ")" | dice | number
+//! dice       := [count] "d" (sides | "%") modifier* | [count] "dF"
+//! modifier   := "kh" N | "kl" N | "k" N | "dh" N | "dl" N   (keep / drop)
+//!             | "!" [compare]                               (exploding)
+//!             | "r" compare | "ro" compare                  (reroll until / once)
+//!             | (">" | "<" | "=") N                         (count successes)
+//!             | "f" compare                                 (failures cancel successes)
+//! compare    := [">" | "<" | "="] N
+//! ```
+//!
+//! Compare points follow the common VTT convention: `>5` means "5 or higher",
+//! `<2` means "2 or lower" and a bare number means "exactly". Division rounds
+//! down. A dice group with a success target is worth its number of successes
+//! (minus failures) instead of the sum of its faces. The parser is hand-written
+//! to keep the domain layer free of parsing dependencies.
 
-use rand::Rng;
 use std::fmt;
 
-use super::DiceSystem;
+use super::{DiceSystem, RandomRollSource, RollMode, RollSource};
+
+/// Maximum number of dice a single formula may roll (before explosions)
+pub const MAX_DICE_PER_FORMULA: u32 = 1000;
+/// Maximum number of faces on a single die
+pub const MAX_DIE_SIZE: u32 = 10_000;
+/// Maximum number of extra dice a single exploding die may add
+pub const MAX_EXPLOSIONS_PER_DIE: u32 = 100;
+/// Maximum number of rerolls for a single die with "reroll until"
+pub const MAX_REROLLS_PER_DIE: u32 = 100;
 
 /// Error when parsing a dice formula
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DiceParseError {
     /// The formula string is empty
     Empty,
-    /// Invalid format - expected XdY or XdY+Z
+    /// Invalid format - unexpected character, unbalanced parentheses, etc.
     InvalidFormat(String),
     /// Dice count must be at least 1
     InvalidDiceCount,
@@ -21,6 +53,12 @@ pub enum DiceParseError {
     InvalidDieSize,
     /// Modifier overflow
     ModifierOverflow,
+    /// The formula rolls more dice than allowed
+    TooManyDice,
+    /// Die size exceeds the allowed maximum
+    DieSizeTooLarge,
+    /// A divisor in the formula can evaluate to zero
+    DivisionByZero,
 }
 
 impl fmt::Display for DiceParseError {
@@ -31,209 +69,1148 @@ impl fmt::Display for DiceParseError {
             Self::InvalidDiceCount => write!(f, "Dice count must be at least 1"),
             Self::InvalidDieSize => write!(f, "Die size must be at least 2"),
             Self::ModifierOverflow => write!(f, "Modifier value overflow"),
+            Self::TooManyDice => {
+                write!(f, "A formula may roll at most {} dice", MAX_DICE_PER_FORMULA)
+            }
+            Self::DieSizeTooLarge => write!(f, "Die size must be at most {}", MAX_DIE_SIZE),
+            Self::DivisionByZero => write!(f, "Divisor can evaluate to zero"),
         }
     }
 }
 
 impl std::error::Error for DiceParseError {}
 
-/// A parsed dice formula like "2d6+3"
+// =============================================================================
+// Expression AST
+// =============================================================================
+
+/// A comparison target used by exploding and rerolling dice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparePoint {
+    /// Matches exactly this face
+    Exactly(i32),
+    /// Matches this face or higher (written `>N`)
+    AtLeast(i32),
+    /// Matches this face or lower (written `<N`)
+    AtMost(i32),
+}
+
+impl ComparePoint {
+    /// Check whether a die face matches this compare point
+    pub fn matches(&self, value: i32) -> bool {
+        match self {
+            Self::Exactly(n) => value == *n,
+            Self::AtLeast(n) => value >= *n,
+            Self::AtMost(n) => value <= *n,
+        }
+    }
+
+    fn notation(&self) -> String {
+        match self {
+            Self::Exactly(n) => n.to_string(),
+            Self::AtLeast(n) => format!(">{}", n),
+            Self::AtMost(n) => format!("<{}", n),
+        }
+    }
+}
+
+/// Keep/drop rule applied to a group of dice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeepRule {
+    KeepHighest(u32),
+    KeepLowest(u32),
+    DropHighest(u32),
+    DropLowest(u32),
+}
+
+impl KeepRule {
+    fn notation(&self) -> String {
+        match self {
+            Self::KeepHighest(n) => format!("kh{}", n),
+            Self::KeepLowest(n) => format!("kl{}", n),
+            Self::DropHighest(n) => format!("dh{}", n),
+            Self::DropLowest(n) => format!("dl{}", n),
+        }
+    }
+
+    /// Number of dice that count towards the total out of `count` rolled
+    fn kept_count(&self, count: u32) -> u32 {
+        match self {
+            Self::KeepHighest(n) | Self::KeepLowest(n) => (*n).min(count),
+            Self::DropHighest(n) | Self::DropLowest(n) => count.saturating_sub(*n),
+        }
+    }
+}
+
+/// Reroll rule for a group of dice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RerollRule {
+    /// Faces that trigger a reroll
+    pub target: ComparePoint,
+    /// Reroll only once (`ro`) instead of until the face no longer matches (`r`)
+    pub once: bool,
+}
+
+/// The kind of faces on a die
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DieKind {
+    /// Faces numbered 1..=sides
+    #[default]
+    Numbered,
+    /// Fate/Fudge die with faces -1, 0 and +1
+    Fate,
+}
+
+/// A single group of identical dice, e.g. "4d6kh3", "4dF" or "5d10>8"
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct DiceFormula {
-    /// Number of dice to roll (X in XdY)
-    pub dice_count: u8,
-    /// Size of each die (Y in XdY)
-    pub die_size: u8,
-    /// Modifier to add/subtract after rolling (+Z or -Z)
-    pub modifier: i32,
+pub struct DiceTerm {
+    /// Number of dice to roll
+    pub count: u32,
+    /// Number of faces on each die
+    pub sides: u32,
+    /// Numbered or Fate faces
+    pub kind: DieKind,
+    /// Keep/drop highest or lowest dice
+    pub keep: Option<KeepRule>,
+    /// Faces that add another die when rolled
+    pub explode: Option<ComparePoint>,
+    /// Faces that are rerolled
+    pub reroll: Option<RerollRule>,
+    /// Faces that count as a success (the group is then worth its successes)
+    pub success: Option<ComparePoint>,
+    /// Faces that cancel a success (botches)
+    pub failure: Option<ComparePoint>,
 }
 
-impl DiceFormula {
-    /// Create a new dice formula
-    pub fn new(dice_count: u8, die_size: u8, modifier: i32) -> Result<Self, DiceParseError> {
-        if dice_count == 0 {
+impl DiceTerm {
+    /// Create a plain group of dice with no modifiers
+    pub fn new(count: u32, sides: u32) -> Self {
+        Self {
+            count,
+            sides,
+            kind: DieKind::Numbered,
+            keep: None,
+            explode: None,
+            reroll: None,
+            success: None,
+            failure: None,
+        }
+    }
+
+    /// Create a group of Fate dice ("NdF")
+    pub fn fate(count: u32) -> Self {
+        Self {
+            kind: DieKind::Fate,
+            ..Self::new(count, 3)
+        }
+    }
+
+    /// Lowest and highest face on a single die
+    pub fn face_range(&self) -> (i32, i32) {
+        match self.kind {
+            DieKind::Numbered => (1, self.sides as i32),
+            DieKind::Fate => (-1, 1),
+        }
+    }
+
+    /// Format as notation (e.g., "4d6kh3")
+    pub fn display(&self) -> String {
+        if self.kind == DieKind::Fate {
+            return format!("{}dF", self.count);
+        }
+        let mut out = format!("{}d{}", self.count, self.sides);
+        if let Some(reroll) = &self.reroll {
+            out.push_str(if reroll.once { "ro" } else { "r" });
+            out.push_str(&reroll.target.notation());
+        }
+        if let Some(success) = &self.success {
+            // Always written with an explicit comparison so it is not read as a count
+            match success {
+                ComparePoint::Exactly(n) => out.push_str(&format!("={}", n)),
+                other => out.push_str(&other.notation()),
+            }
+        }
+        if let Some(failure) = &self.failure {
+            out.push('f');
+            out.push_str(&failure.notation());
+        }
+        if let Some(explode) = &self.explode {
+            out.push('!');
+            // Plain "!" explodes on the highest face only
+            let (min_face, max_face) = self.face_range();
+            let max_only = (min_face..=max_face).all(|f| explode.matches(f) == (f == max_face));
+            if !max_only {
+                out.push_str(&explode.notation());
+            }
+        }
+        if let Some(keep) = &self.keep {
+            out.push_str(&keep.notation());
+        }
+        out
+    }
+
+    /// Lowest and highest value a single counted die can show
+    fn die_range(&self) -> (i64, i64) {
+        let (min_face, max_face) = self.face_range();
+        let (mut low, mut high) = (min_face as i64, max_face as i64);
+        if let Some(RerollRule { target, once: false }) = &self.reroll {
+            low = (low..=high).find(|f| !target.matches(*f as i32)).unwrap_or(low);
+            high = (low..=high).rev().find(|f| !target.matches(*f as i32)).unwrap_or(high);
+        }
+        if self.explode.is_some() {
+            high *= 1 + MAX_EXPLOSIONS_PER_DIE as i64;
+        }
+        (low, high)
+    }
+
+    fn range(&self) -> (i64, i64) {
+        let kept = self.keep.map_or(self.count, |k| k.kept_count(self.count)) as i64;
+        if self.success.is_some() {
+            // Exploding dice can add successes beyond the pool size
+            let rolled = if self.explode.is_some() {
+                kept * (1 + MAX_EXPLOSIONS_PER_DIE as i64)
+            } else {
+                kept
+            };
+            let low = if self.failure.is_some() { -rolled } else { 0 };
+            return (low, rolled);
+        }
+        let (low, high) = self.die_range();
+        (kept * low, kept * high)
+    }
+
+    fn validate(&self) -> Result<(), DiceParseError> {
+        if self.count == 0 {
             return Err(DiceParseError::InvalidDiceCount);
         }
-        if die_size < 2 {
+        if self.sides < 2 {
             return Err(DiceParseError::InvalidDieSize);
         }
-        Ok(Self {
-            dice_count,
-            die_size,
-            modifier,
-        })
+        if self.sides > MAX_DIE_SIZE {
+            return Err(DiceParseError::DieSizeTooLarge);
+        }
+        let (min_face, max_face) = self.face_range();
+        let all_faces = |target: &ComparePoint| (min_face..=max_face).all(|f| target.matches(f));
+        if let Some(explode) = &self.explode {
+            if all_faces(explode) {
+                return Err(DiceParseError::InvalidFormat(format!(
+                    "Exploding on every face of a d{} never stops",
+                    self.sides
+                )));
+            }
+        }
+        if let Some(reroll) = &self.reroll {
+            if !reroll.once && all_faces(&reroll.target) {
+                return Err(DiceParseError::InvalidFormat(format!(
+                    "Rerolling every face of a d{} never stops",
+                    self.sides
+                )));
+            }
+        }
+        if self.failure.is_some() && self.success.is_none() {
+            return Err(DiceParseError::InvalidFormat(
+                "Failures can only be counted together with a success target".to_string(),
+            ));
+        }
+        match self.keep {
+            Some(KeepRule::KeepHighest(n)) | Some(KeepRule::KeepLowest(n))
+                if n == 0 || n > self.count =>
+            {
+                Err(DiceParseError::InvalidFormat(format!(
+                    "Cannot keep {} of {} dice",
+                    n, self.count
+                )))
+            }
+            Some(KeepRule::DropHighest(n)) | Some(KeepRule::DropLowest(n)) if n >= self.count => {
+                Err(DiceParseError::InvalidFormat(format!(
+                    "Cannot drop {} of {} dice",
+                    n, self.count
+                )))
+            }
+            _ => Ok(()),
+        }
     }
+}
 
-    /// Parse a dice formula string like "1d20+5", "2d6-1", "1d100"
-    ///
-    /// Supported formats:
-    /// - "XdY" - Roll X dice of size Y
-    /// - "XdY+Z" - Roll X dice of size Y, add Z
-    /// - "XdY-Z" - Roll X dice of size Y, subtract Z
-    /// - "dY" - Roll 1 die of size Y (shorthand)
-    pub fn parse(input: &str) -> Result<Self, DiceParseError> {
-        let input = input.trim().to_lowercase();
-        if input.is_empty() {
-            return Err(DiceParseError::Empty);
+/// Binary arithmetic operators in a dice expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    /// Integer division, rounding down
+    Divide,
+}
+
+impl BinaryOp {
+    fn symbol(&self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Subtract => '-',
+            Self::Multiply => '*',
+            Self::Divide => '/',
         }
+    }
 
-        // Regex pattern: (\d*)d(\d+)([+-]\d+)?
-        // But we'll parse manually to avoid regex dependency in domain layer
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add | Self::Subtract => 1,
+            Self::Multiply | Self::Divide => 2,
+        }
+    }
 
-        // Find 'd' separator
-        let d_pos = input.find('d').ok_or_else(|| {
-            DiceParseError::InvalidFormat(format!("Missing 'd' separator in '{}'", input))
-        })?;
+    /// Apply the operator, saturating on overflow
+    pub fn apply(&self, left: i32, right: i32) -> i32 {
+        match self {
+            Self::Add => left.saturating_add(right),
+            Self::Subtract => left.saturating_sub(right),
+            Self::Multiply => left.saturating_mul(right),
+            Self::Divide => floor_div(left as i64, right as i64) as i32,
+        }
+    }
+}
 
-        // Parse dice count (before 'd')
-        let dice_count_str = &input[..d_pos];
-        let dice_count: u8 = if dice_count_str.is_empty() {
-            1 // "d20" means "1d20"
-        } else {
-            dice_count_str.parse().map_err(|_| {
-                DiceParseError::InvalidFormat(format!("Invalid dice count: '{}'", dice_count_str))
-            })?
+/// Integer division rounding towards negative infinity ("round down")
+fn floor_div(left: i64, right: i64) -> i64 {
+    let quotient = left / right;
+    if left % right != 0 && ((left < 0) != (right < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+/// A node in a dice expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiceExpr {
+    /// A flat number
+    Constant(i32),
+    /// A group of dice
+    Dice(DiceTerm),
+    /// Unary minus
+    Negate(Box<DiceExpr>),
+    /// Binary arithmetic
+    Binary {
+        op: BinaryOp,
+        left: Box<DiceExpr>,
+        right: Box<DiceExpr>,
+    },
+}
+
+impl DiceExpr {
+    fn binary(op: BinaryOp, left: DiceExpr, right: DiceExpr) -> Self {
+        Self::Binary {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Binary { op, .. } => op.precedence(),
+            Self::Negate(_) => 3,
+            Self::Constant(_) | Self::Dice(_) => 4,
+        }
+    }
+
+    /// Visit all dice terms in evaluation order
+    fn collect_terms<'a>(&'a self, terms: &mut Vec<&'a DiceTerm>) {
+        match self {
+            Self::Constant(_) => {}
+            Self::Dice(term) => terms.push(term),
+            Self::Negate(inner) => inner.collect_terms(terms),
+            Self::Binary { left, right, .. } => {
+                left.collect_terms(terms);
+                right.collect_terms(terms);
+            }
+        }
+    }
+
+    /// Lowest and highest possible value of this expression
+    fn range(&self) -> (i64, i64) {
+        match self {
+            Self::Constant(n) => (*n as i64, *n as i64),
+            Self::Dice(term) => term.range(),
+            Self::Negate(inner) => {
+                let (low, high) = inner.range();
+                (high.saturating_neg(), low.saturating_neg())
+            }
+            Self::Binary { op, left, right } => {
+                let (l_low, l_high) = left.range();
+                let (r_low, r_high) = right.range();
+                match op {
+                    BinaryOp::Add => (l_low.saturating_add(r_low), l_high.saturating_add(r_high)),
+                    BinaryOp::Subtract => (l_low.saturating_sub(r_high), l_high.saturating_sub(r_low)),
+                    BinaryOp::Multiply | BinaryOp::Divide => {
+                        let combine = |a: i64, b: i64| match op {
+                            BinaryOp::Multiply => a.saturating_mul(b),
+                            _ if b == 0 => 0,
+                            _ => floor_div(a, b),
+                        };
+                        let candidates = [
+                            combine(l_low, r_low),
+                            combine(l_low, r_high),
+                            combine(l_high, r_low),
+                            combine(l_high, r_high),
+                        ];
+                        (
+                            *candidates.iter().min().unwrap_or(&0),
+                            *candidates.iter().max().unwrap_or(&0),
+                        )
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), DiceParseError> {
+        match self {
+            Self::Constant(_) => Ok(()),
+            Self::Dice(term) => term.validate(),
+            Self::Negate(inner) => inner.validate(),
+            Self::Binary { op, left, right } => {
+                left.validate()?;
+                right.validate()?;
+                if *op == BinaryOp::Divide {
+                    let (low, high) = right.range();
+                    if low <= 0 && high >= 0 {
+                        return Err(DiceParseError::DivisionByZero);
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Format this expression, wrapping it in parentheses when needed
+    fn write_child(
+        &self,
+        parent_precedence: u8,
+        is_right_operand: bool,
+        out: &mut String,
+        term: &mut dyn FnMut(&DiceTerm, &mut String),
+        spaced: bool,
+    ) {
+        let needs_parens = self.precedence() < parent_precedence
+            || (is_right_operand && self.precedence() == parent_precedence);
+        if needs_parens {
+            out.push('(');
+        }
+        self.write(out, term, spaced);
+        if needs_parens {
+            out.push(')');
+        }
+    }
+
+    /// Format this expression, delegating dice terms to `term`
+    fn write(&self, out: &mut String, term: &mut dyn FnMut(&DiceTerm, &mut String), spaced: bool) {
+        match self {
+            Self::Constant(n) => out.push_str(&n.to_string()),
+            Self::Dice(dice) => term(dice, out),
+            Self::Negate(inner) => {
+                out.push('-');
+                inner.write_child(3, false, out, term, spaced);
+            }
+            Self::Binary { op, left, right } => {
+                left.write_child(op.precedence(), false, out, term, spaced);
+                if spaced {
+                    out.push(' ');
+                    out.push(op.symbol());
+                    out.push(' ');
+                } else {
+                    out.push(op.symbol());
+                }
+                right.write_child(op.precedence(), true, out, term, spaced);
+            }
+        }
+    }
+
+    /// Evaluate this expression, rolling dice through `next_die`
+    fn evaluate(
+        &self,
+        next_die: &mut dyn FnMut(u32) -> i32,
+        results: &mut Vec<DiceTermResult>,
+    ) -> i32 {
+        match self {
+            Self::Constant(n) => *n,
+            Self::Dice(term) => {
+                let result = roll_term(term, next_die);
+                let total = result.total;
+                results.push(result);
+                total
+            }
+            Self::Negate(inner) => inner.evaluate(next_die, results).saturating_neg(),
+            Self::Binary { op, left, right } => {
+                let left = left.evaluate(next_die, results);
+                let right = right.evaluate(next_die, results);
+                op.apply(left, right)
+            }
+        }
+    }
+}
+
+impl fmt::Display for DiceExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        self.write(&mut out, &mut |term, out| out.push_str(&term.display()), false);
+        write!(f, "{}", out)
+    }
+}
+
+/// Roll a single group of dice, applying rerolls, explosions and keep/drop
+fn roll_term(term: &DiceTerm, next_die: &mut dyn FnMut(u32) -> i32) -> DiceTermResult {
+    let mut rolls = Vec::with_capacity(term.count as usize);
+    // Fate dice are rolled as a d3 shifted to -1..=1
+    let (min_face, _) = term.face_range();
+    let mut next_face = |sides: u32| next_die(sides) + min_face - 1;
+
+    for _ in 0..term.count {
+        let mut value = next_face(term.sides);
+
+        if let Some(reroll) = &term.reroll {
+            let limit = if reroll.once { 1 } else { MAX_REROLLS_PER_DIE };
+            let mut rerolls = 0;
+            while rerolls < limit && reroll.target.matches(value) {
+                rolls.push(DieRoll {
+                    value,
+                    dropped: false,
+                    rerolled: true,
+                    exploded: false,
+                });
+                value = next_face(term.sides);
+                rerolls += 1;
+            }
+        }
+
+        let mut explosions = 0;
+        loop {
+            let explodes = explosions < MAX_EXPLOSIONS_PER_DIE
+                && term.explode.is_some_and(|e| e.matches(value));
+            rolls.push(DieRoll {
+                value,
+                dropped: false,
+                rerolled: false,
+                exploded: explodes,
+            });
+            if !explodes {
+                break;
+            }
+            value = next_face(term.sides);
+            explosions += 1;
+        }
+    }
+
+    if let Some(keep) = &term.keep {
+        // Rank the counted dice by value; ties are broken by roll order
+        let mut ranked: Vec<usize> = (0..rolls.len()).filter(|i| !rolls[*i].rerolled).collect();
+        ranked.sort_by_key(|i| rolls[*i].value);
+        let counted = ranked.len();
+        let drop: Vec<usize> = match keep {
+            KeepRule::KeepHighest(n) => ranked[..counted.saturating_sub(*n as usize)].to_vec(),
+            KeepRule::KeepLowest(n) => ranked[(*n as usize).min(counted)..].to_vec(),
+            KeepRule::DropHighest(n) => ranked[counted.saturating_sub(*n as usize)..].to_vec(),
+            KeepRule::DropLowest(n) => ranked[..(*n as usize).min(counted)].to_vec(),
         };
+        for index in drop {
+            rolls[index].dropped = true;
+        }
+    }
 
-        if dice_count == 0 {
-            return Err(DiceParseError::InvalidDiceCount);
+    let counted = || rolls.iter().filter(|r| r.counts());
+    let (successes, failures, total) = match &term.success {
+        Some(success) => {
+            let successes = counted().filter(|r| success.matches(r.value)).count() as u32;
+            let failures = term
+                .failure
+                .map_or(0, |failure| counted().filter(|r| failure.matches(r.value)).count() as u32);
+            (Some(successes), failures, successes as i32 - failures as i32)
         }
+        None => (
+            None,
+            0,
+            counted().fold(0i32, |acc, r| acc.saturating_add(r.value)),
+        ),
+    };
 
-        // Parse die size and modifier (after 'd')
-        let after_d = &input[d_pos + 1..];
-
-        // Find modifier separator (+ or -)
-        let (die_size_str, modifier) = if let Some(plus_pos) = after_d.find('+') {
-            let die_str = &after_d[..plus_pos];
-            let mod_str = &after_d[plus_pos + 1..];
-            let modifier: i32 = mod_str.parse().map_err(|_| {
-                DiceParseError::InvalidFormat(format!("Invalid modifier: '+{}'", mod_str))
-            })?;
-            (die_str, modifier)
-        } else if let Some(minus_pos) = after_d.rfind('-') {
-            // Use rfind to handle negative numbers correctly
-            if minus_pos == 0 {
-                // The '-' is at the start, which is invalid for die size
-                return Err(DiceParseError::InvalidFormat(format!(
-                    "Invalid die size: '{}'",
-                    after_d
-                )));
+    DiceTermResult {
+        term: term.clone(),
+        rolls,
+        total,
+        successes,
+        failures,
+    }
+}
+
+// =============================================================================
+// Parser
+// =============================================================================
+
+/// How deeply a formula may nest - parentheses, negations and chained
+/// operators each add a level - before parsing gives up
+const MAX_NESTING: usize = 64;
+
+struct Parser<'a> {
+    input: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            bytes: input.as_bytes(),
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    /// Go a level deeper into the formula
+    fn nest(&mut self) -> Result<(), DiceParseError> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err(DiceParseError::InvalidFormat(format!(
+                "Formula nested more than {} levels deep",
+                MAX_NESTING
+            )));
+        }
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected(&self) -> DiceParseError {
+        match self.input[self.pos..].chars().next() {
+            Some(c) => DiceParseError::InvalidFormat(format!(
+                "Unexpected '{}' at position {} in '{}'",
+                c,
+                self.pos + 1,
+                self.input
+            )),
+            None => DiceParseError::InvalidFormat(format!(
+                "Unexpected end of formula '{}'",
+                self.input
+            )),
+        }
+    }
+
+    fn digits(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| &self.input[start..self.pos])
+    }
+
+    fn expression(&mut self) -> Result<DiceExpr, DiceParseError> {
+        let depth = self.depth;
+        let mut left = self.product()?;
+        loop {
+            let op = if self.eat(b'+') {
+                BinaryOp::Add
+            } else if self.eat(b'-') {
+                BinaryOp::Subtract
+            } else {
+                self.depth = depth;
+                return Ok(left);
+            };
+            self.nest()?;
+            let right = self.product()?;
+            left = DiceExpr::binary(op, left, right);
+        }
+    }
+
+    fn product(&mut self) -> Result<DiceExpr, DiceParseError> {
+        let depth = self.depth;
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat(b'*') {
+                BinaryOp::Multiply
+            } else if self.eat(b'/') {
+                BinaryOp::Divide
+            } else {
+                self.depth = depth;
+                return Ok(left);
+            };
+            self.nest()?;
+            let right = self.unary()?;
+            left = DiceExpr::binary(op, left, right);
+        }
+    }
+
+    fn unary(&mut self) -> Result<DiceExpr, DiceParseError> {
+        self.nest()?;
+        let expr = if self.eat(b'-') {
+            DiceExpr::Negate(Box::new(self.unary()?))
+        } else {
+            self.primary()?
+        };
+        self.depth -= 1;
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<DiceExpr, DiceParseError> {
+        if self.eat(b'(') {
+            let inner = self.expression()?;
+            if !self.eat(b')') {
+                return Err(self.unexpected());
             }
-            let die_str = &after_d[..minus_pos];
-            let mod_str = &after_d[minus_pos + 1..];
-            let modifier: i32 = mod_str.parse::<i32>().map_err(|_| {
-                DiceParseError::InvalidFormat(format!("Invalid modifier: '-{}'", mod_str))
-            })?;
-            (die_str, -modifier)
+            return Ok(inner);
+        }
+
+        let number = self.digits();
+        if self.eat(b'd') {
+            let count = match number {
+                None => 1,
+                Some(digits) => digits.parse().map_err(|_| DiceParseError::TooManyDice)?,
+            };
+            return self.dice(count).map(DiceExpr::Dice);
+        }
+
+        match number {
+            Some(digits) => digits
+                .parse()
+                .map(DiceExpr::Constant)
+                .map_err(|_| DiceParseError::ModifierOverflow),
+            None => Err(self.unexpected()),
+        }
+    }
+
+    fn dice(&mut self, count: u32) -> Result<DiceTerm, DiceParseError> {
+        if self.eat(b'f') {
+            return Ok(DiceTerm::fate(count));
+        }
+        let sides = if self.eat(b'%') {
+            100
         } else {
-            (after_d, 0)
+            match self.digits() {
+                Some(digits) => digits.parse().map_err(|_| DiceParseError::DieSizeTooLarge)?,
+                None => {
+                    return Err(DiceParseError::InvalidFormat(format!(
+                        "Missing die size in '{}'",
+                        self.input
+                    )))
+                }
+            }
         };
+        let mut term = DiceTerm::new(count, sides);
 
-        let die_size: u8 = die_size_str.parse().map_err(|_| {
-            DiceParseError::InvalidFormat(format!("Invalid die size: '{}'", die_size_str))
-        })?;
+        loop {
+            match self.peek() {
+                Some(marker @ (b'k' | b'd')) if term.keep.is_none() => {
+                    self.pos += 1;
+                    let is_keep = marker == b'k';
+                    let rule = match (is_keep, self.peek()) {
+                        (true, Some(b'h')) | (true, Some(b'0'..=b'9')) => {
+                            self.eat(b'h');
+                            KeepRule::KeepHighest(self.count_argument()?)
+                        }
+                        (true, Some(b'l')) => {
+                            self.pos += 1;
+                            KeepRule::KeepLowest(self.count_argument()?)
+                        }
+                        (false, Some(b'h')) => {
+                            self.pos += 1;
+                            KeepRule::DropHighest(self.count_argument()?)
+                        }
+                        (false, Some(b'l')) => {
+                            self.pos += 1;
+                            KeepRule::DropLowest(self.count_argument()?)
+                        }
+                        _ => return Err(self.unexpected()),
+                    };
+                    term.keep = Some(rule);
+                }
+                Some(b'!') if term.explode.is_none() => {
+                    self.pos += 1;
+                    let target = match self.peek() {
+                        Some(b'>') | Some(b'<') | Some(b'=') | Some(b'0'..=b'9') => {
+                            self.compare_point()?
+                        }
+                        _ => ComparePoint::Exactly(sides as i32),
+                    };
+                    term.explode = Some(target);
+                }
+                Some(b'r') if term.reroll.is_none() => {
+                    self.pos += 1;
+                    let once = self.eat(b'o');
+                    let target = self.compare_point()?;
+                    term.reroll = Some(RerollRule { target, once });
+                }
+                Some(b'>' | b'<' | b'=') if term.success.is_none() => {
+                    term.success = Some(self.compare_point()?);
+                }
+                Some(b'f') if term.failure.is_none() => {
+                    self.pos += 1;
+                    term.failure = Some(self.compare_point()?);
+                }
+                _ => return Ok(term),
+            }
+        }
+    }
 
-        if die_size < 2 {
-            return Err(DiceParseError::InvalidDieSize);
+    fn count_argument(&mut self) -> Result<u32, DiceParseError> {
+        match self.digits() {
+            Some(digits) => digits.parse().map_err(|_| DiceParseError::TooManyDice),
+            None => Err(self.unexpected()),
         }
+    }
+
+    fn compare_point(&mut self) -> Result<ComparePoint, DiceParseError> {
+        let make: fn(i32) -> ComparePoint = if self.eat(b'>') {
+            ComparePoint::AtLeast
+        } else if self.eat(b'<') {
+            ComparePoint::AtMost
+        } else {
+            self.eat(b'=');
+            ComparePoint::Exactly
+        };
+        match self.digits() {
+            Some(digits) => digits
+                .parse()
+                .map(make)
+                .map_err(|_| DiceParseError::ModifierOverflow),
+            None => Err(self.unexpected()),
+        }
+    }
+}
+
+/// Split the outermost `+`/`-` chain into signed operands
+fn flatten_additive(expr: DiceExpr, negative: bool, out: &mut Vec<(bool, DiceExpr)>) {
+    match expr {
+        DiceExpr::Binary {
+            op: op @ (BinaryOp::Add | BinaryOp::Subtract),
+            left,
+            right,
+        } => {
+            flatten_additive(*left, negative, out);
+            flatten_additive(*right, negative ^ (op == BinaryOp::Subtract), out);
+        }
+        other => out.push((negative, other)),
+    }
+}
 
+// =============================================================================
+// Formula
+// =============================================================================
+
+/// A parsed dice formula like "2d6+3" or "4d6kh3+1d4"
+///
+/// Flat numbers in the outermost sum are folded into `modifier`, so that skill
+/// bonuses can be added without rebuilding the expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceFormula {
+    /// The dice part of the formula
+    pub expression: DiceExpr,
+    /// Flat modifier to add/subtract after rolling (+Z or -Z)
+    pub modifier: i32,
+}
+
+impl DiceFormula {
+    /// Create a simple "XdY+Z" dice formula
+    pub fn new(dice_count: u32, die_size: u32, modifier: i32) -> Result<Self, DiceParseError> {
+        Self::from_expression(DiceExpr::Dice(DiceTerm::new(dice_count, die_size)), modifier)
+    }
+
+    /// Create a formula from an expression, validating it
+    pub fn from_expression(expression: DiceExpr, modifier: i32) -> Result<Self, DiceParseError> {
+        let mut terms = Vec::new();
+        expression.collect_terms(&mut terms);
+        if terms.is_empty() {
+            return Err(DiceParseError::InvalidFormat(format!(
+                "'{}' does not roll any dice",
+                expression
+            )));
+        }
+        let total_dice: u64 = terms.iter().map(|t| t.count as u64).sum();
+        if total_dice > MAX_DICE_PER_FORMULA as u64 {
+            return Err(DiceParseError::TooManyDice);
+        }
+        expression.validate()?;
         Ok(Self {
-            dice_count,
-            die_size,
+            expression,
             modifier,
         })
     }
 
+    /// Parse a dice formula string like "1d20+5", "4d6kh3", "3d8+1d6+4" or "d%"
+    ///
+    /// See the module documentation for the full grammar.
+    pub fn parse(input: &str) -> Result<Self, DiceParseError> {
+        let normalized: String = input
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+        if normalized.is_empty() {
+            return Err(DiceParseError::Empty);
+        }
+
+        let mut parser = Parser::new(&normalized);
+        let expression = parser.expression()?;
+        if parser.pos < normalized.len() {
+            return Err(parser.unexpected());
+        }
+
+        // Fold flat numbers in the outermost sum into the modifier
+        let mut operands = Vec::new();
+        flatten_additive(expression, false, &mut operands);
+        let mut modifier: i32 = 0;
+        let mut dice: Option<DiceExpr> = None;
+        for (negative, operand) in operands {
+            if let DiceExpr::Constant(n) = operand {
+                let signed = if negative { -n } else { n };
+                modifier = modifier
+                    .checked_add(signed)
+                    .ok_or(DiceParseError::ModifierOverflow)?;
+                continue;
+            }
+            dice = Some(match (dice, negative) {
+                (None, false) => operand,
+                (None, true) => DiceExpr::Negate(Box::new(operand)),
+                (Some(left), false) => DiceExpr::binary(BinaryOp::Add, left, operand),
+                (Some(left), true) => DiceExpr::binary(BinaryOp::Subtract, left, operand),
+            });
+        }
+
+        match dice {
+            Some(expression) => Self::from_expression(expression, modifier),
+            None => Err(DiceParseError::InvalidFormat(format!(
+                "'{}' does not roll any dice",
+                normalized
+            ))),
+        }
+    }
+
     /// Get the default dice formula for a rule system
     pub fn default_for_system(dice_system: &DiceSystem) -> Self {
+        let simple = |count: u32, sides: u32, modifier: i32| Self {
+            expression: DiceExpr::Dice(DiceTerm::new(count, sides)),
+            modifier,
+        };
         match dice_system {
-            DiceSystem::D20 => Self {
-                dice_count: 1,
-                die_size: 20,
-                modifier: 0,
-            },
-            DiceSystem::D100 => Self {
-                dice_count: 1,
-                die_size: 100,
-                modifier: 0,
-            },
-            DiceSystem::DicePool { die_type, .. } => Self {
-                dice_count: 1,
-                die_size: *die_type,
-                modifier: 0,
-            },
+            DiceSystem::D20 => simple(1, 20, 0),
+            DiceSystem::D100 => simple(1, 100, 0),
+            DiceSystem::DicePool { .. } => {
+                // The pool size comes from the character; see `with_pool_bonus`
+                Self::dice_pool(1, dice_system).unwrap_or_else(|_| simple(1, 10, 0))
+            }
             DiceSystem::Fate => Self {
-                // FATE uses 4dF, but we represent as 4d3-8 (each F die is -1/0/+1)
-                // Actually, we'll use a special format: 4d3-8 gives range -4 to +4
-                dice_count: 4,
-                die_size: 3,
-                modifier: -8, // Adjusts 4-12 range to -4 to +4
+                expression: DiceExpr::Dice(DiceTerm::fate(4)),
+                modifier: 0,
             },
             DiceSystem::Custom(desc) => {
-                // Try to parse the custom description, fall back to d20
-                if desc.contains("2d6") {
-                    Self {
-                        dice_count: 2,
-                        die_size: 6,
-                        modifier: 0,
-                    }
+                // User-defined systems declare an expression; older worlds
+                // hold a free-text label, so fall back to guessing from it
+                if let Ok(formula) = Self::parse(desc) {
+                    formula
+                } else if desc.contains("2d6") {
+                    simple(2, 6, 0)
                 } else if desc.contains("d100") {
-                    Self {
-                        dice_count: 1,
-                        die_size: 100,
-                        modifier: 0,
-                    }
+                    simple(1, 100, 0)
                 } else {
-                    Self {
-                        dice_count: 1,
-                        die_size: 20,
-                        modifier: 0,
-                    }
+                    simple(1, 20, 0)
+                }
+            }
+        }
+    }
+
+    /// Build a success-counting pool of `pool_size` dice for a `DiceSystem::DicePool`
+    ///
+    /// Returns `InvalidFormat` for any other dice system.
+    pub fn dice_pool(pool_size: u32, dice_system: &DiceSystem) -> Result<Self, DiceParseError> {
+        let DiceSystem::DicePool {
+            die_type,
+            success_threshold,
+            ones_cancel,
+            again_threshold,
+        } = dice_system
+        else {
+            return Err(DiceParseError::InvalidFormat(
+                "Dice pools require a dice pool system".to_string(),
+            ));
+        };
+        let term = DiceTerm {
+            success: Some(ComparePoint::AtLeast(*success_threshold as i32)),
+            failure: ones_cancel.then_some(ComparePoint::Exactly(1)),
+            explode: again_threshold.map(|n| {
+                if n >= *die_type {
+                    ComparePoint::Exactly(*die_type as i32)
+                } else {
+                    ComparePoint::AtLeast(n as i32)
+                }
+            }),
+            ..DiceTerm::new(pool_size, *die_type as u32)
+        };
+        Self::from_expression(DiceExpr::Dice(term), 0)
+    }
+
+    /// Return a copy of this formula with an extra flat modifier
+    pub fn with_modifier(mut self, modifier: i32) -> Self {
+        self.modifier = self.modifier.saturating_add(modifier);
+        self
+    }
+
+    /// Whether the formula's dice are a single kept d20, so the dice total is
+    /// the d20's face
+    pub fn is_single_d20(&self) -> bool {
+        match &self.expression {
+            DiceExpr::Dice(term) => {
+                term.kind == DieKind::Numbered
+                    && term.sides == 20
+                    && term.explode.is_none()
+                    && term.success.is_none()
+                    && term.keep.map_or(term.count, |keep| keep.kept_count(term.count)) == 1
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether rolling this formula counts successes rather than summing faces
+    pub fn counts_successes(&self) -> bool {
+        self.dice_terms().iter().any(|t| t.success.is_some())
+    }
+
+    /// Return a copy with `bonus` dice added to the first success-counting pool
+    ///
+    /// Pools never shrink below a single die. Formulas without a pool get the
+    /// bonus as a flat modifier instead.
+    pub fn with_pool_bonus(mut self, bonus: i32) -> Self {
+        fn first_pool(expr: &mut DiceExpr) -> Option<&mut DiceTerm> {
+            match expr {
+                DiceExpr::Constant(_) => None,
+                DiceExpr::Dice(term) => term.success.is_some().then_some(term),
+                DiceExpr::Negate(inner) => first_pool(inner),
+                DiceExpr::Binary { left, right, .. } => {
+                    first_pool(left).or_else(|| first_pool(right))
                 }
             }
         }
+        match first_pool(&mut self.expression) {
+            Some(term) => {
+                let size = (term.count as i64 + bonus as i64).clamp(1, MAX_DICE_PER_FORMULA as i64);
+                term.count = size as u32;
+                self
+            }
+            None => self.with_modifier(bonus),
+        }
+    }
+
+    /// Return a copy with a roll mode applied to the primary die
+    ///
+    /// The primary die is the first lone, unmodified numbered die (the d20 in
+    /// "1d20+1d4+5"). It is rolled again and the best or worst result kept,
+    /// except for a d100, which rolls extra tens dice and keeps the lowest or
+    /// highest as Call of Cthulhu bonus and penalty dice do.
+    pub fn with_roll_mode(&self, mode: RollMode) -> Result<Self, DiceParseError> {
+        if mode.edge() == 0 {
+            return Ok(self.clone());
+        }
+        let mut expression = self.expression.clone();
+        let primary = primary_die(&mut expression).ok_or_else(|| {
+            DiceParseError::InvalidFormat(format!(
+                "'{}' has no single die to roll with {}",
+                self.display(),
+                mode
+            ))
+        })?;
+        if let DiceExpr::Dice(term) = primary {
+            *primary = roll_mode_expression(term.sides, mode.edge());
+        }
+        Self::from_expression(expression, self.modifier)
+    }
+
+    /// All dice groups in the formula, in evaluation order
+    pub fn dice_terms(&self) -> Vec<&DiceTerm> {
+        let mut terms = Vec::new();
+        self.expression.collect_terms(&mut terms);
+        terms
+    }
+
+    /// Total number of dice rolled (before explosions and rerolls)
+    pub fn dice_count(&self) -> u32 {
+        self.dice_terms().iter().map(|t| t.count).sum()
+    }
+
+    /// Die size shared by every dice group, if there is exactly one size
+    pub fn die_size(&self) -> Option<u32> {
+        let terms = self.dice_terms();
+        let first = terms.first()?.sides;
+        terms.iter().all(|t| t.sides == first).then_some(first)
     }
 
     /// Roll the dice and return the result
     pub fn roll(&self) -> DiceRollResult {
-        let mut rng = rand::thread_rng();
-        let mut individual_rolls = Vec::with_capacity(self.dice_count as usize);
+        self.roll_from(&mut RandomRollSource)
+    }
 
-        for _ in 0..self.dice_count {
-            let roll = rng.gen_range(1..=self.die_size as i32);
-            individual_rolls.push(roll);
-        }
+    /// Roll the dice, drawing every face from `source`
+    pub fn roll_from(&self, source: &mut impl RollSource) -> DiceRollResult {
+        self.roll_with(|sides| source.roll_die(sides))
+    }
 
-        let dice_total: i32 = individual_rolls.iter().sum();
-        let total = dice_total + self.modifier;
+    /// Roll the dice using `next_die` to produce each face (1..=sides)
+    pub fn roll_with(&self, mut next_die: impl FnMut(u32) -> i32) -> DiceRollResult {
+        let mut terms = Vec::new();
+        let dice_total = self.expression.evaluate(&mut next_die, &mut terms);
+        let individual_rolls = terms
+            .iter()
+            .flat_map(|t| t.rolls.iter().filter(|r| r.counts()).map(|r| r.value))
+            .collect();
+
+        let total = dice_total.saturating_add(self.modifier);
+        let successes = self.counts_successes().then_some(total);
+        let fate_ladder = terms
+            .iter()
+            .any(|t| t.term.kind == DieKind::Fate)
+            .then_some(FateLadder(total));
 
         DiceRollResult {
-            formula: self.clone(),
+            formula: Some(self.clone()),
+            terms,
             individual_rolls,
             dice_total,
             modifier_applied: self.modifier,
             total,
+            successes,
+            fate_ladder,
         }
     }
 
     /// Get the minimum possible roll
     pub fn min_roll(&self) -> i32 {
-        self.dice_count as i32 + self.modifier
+        let (low, _) = self.expression.range();
+        low.saturating_add(self.modifier as i64).clamp(i32::MIN as i64, i32::MAX as i64) as i32
     }
 
     /// Get the maximum possible roll
     pub fn max_roll(&self) -> i32 {
-        (self.dice_count as i32 * self.die_size as i32) + self.modifier
+        let (_, high) = self.expression.range();
+        high.saturating_add(self.modifier as i64).clamp(i32::MIN as i64, i32::MAX as i64) as i32
     }
 
     /// Format as a display string (e.g., "1d20+5")
     pub fn display(&self) -> String {
+        let dice = self.expression.to_string();
         if self.modifier == 0 {
-            format!("{}d{}", self.dice_count, self.die_size)
+            dice
         } else if self.modifier > 0 {
-            format!("{}d{}+{}", self.dice_count, self.die_size, self.modifier)
+            format!("{}+{}", dice, self.modifier)
         } else {
-            format!("{}d{}{}", self.dice_count, self.die_size, self.modifier)
+            format!("{}{}", dice, self.modifier)
         }
     }
 }
@@ -244,114 +1221,388 @@ impl fmt::Display for DiceFormula {
     }
 }
 
+/// Whether a dice group is a lone, unmodified numbered die
+fn is_primary_die(term: &DiceTerm) -> bool {
+    term.kind == DieKind::Numbered
+        && term.count == 1
+        && term.keep.is_none()
+        && term.explode.is_none()
+        && term.reroll.is_none()
+        && term.success.is_none()
+        && term.failure.is_none()
+}
+
+/// Find the first primary die in evaluation order
+fn primary_die(expr: &mut DiceExpr) -> Option<&mut DiceExpr> {
+    if matches!(&*expr, DiceExpr::Dice(term) if is_primary_die(term)) {
+        return Some(expr);
+    }
+    match expr {
+        DiceExpr::Negate(inner) => primary_die(inner),
+        DiceExpr::Binary { left, right, .. } => match primary_die(left) {
+            Some(found) => Some(found),
+            None => primary_die(right),
+        },
+        DiceExpr::Constant(_) | DiceExpr::Dice(_) => None,
+    }
+}
+
+/// Expression replacing a primary die rolled with `edge` extra dice
+///
+/// Positive edges keep the best result: the highest die, or the lowest tens
+/// die on a d100, which is built as "(Nd10kl1-1)*10+1d10".
+fn roll_mode_expression(sides: u32, edge: i32) -> DiceExpr {
+    let count = 1 + edge.unsigned_abs();
+    if sides == 100 {
+        let tens = DiceTerm {
+            keep: Some(if edge > 0 {
+                KeepRule::KeepLowest(1)
+            } else {
+                KeepRule::KeepHighest(1)
+            }),
+            ..DiceTerm::new(count, 10)
+        };
+        let tens = DiceExpr::binary(
+            BinaryOp::Multiply,
+            DiceExpr::binary(BinaryOp::Subtract, DiceExpr::Dice(tens), DiceExpr::Constant(1)),
+            DiceExpr::Constant(10),
+        );
+        return DiceExpr::binary(BinaryOp::Add, tens, DiceExpr::Dice(DiceTerm::new(1, 10)));
+    }
+    DiceExpr::Dice(DiceTerm {
+        keep: Some(if edge > 0 {
+            KeepRule::KeepHighest(1)
+        } else {
+            KeepRule::KeepLowest(1)
+        }),
+        ..DiceTerm::new(count, sides)
+    })
+}
+
+// =============================================================================
+// Roll results
+// =============================================================================
+
+/// A single die rolled as part of a dice group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DieRoll {
+    /// Face shown
+    pub value: i32,
+    /// Removed by a keep/drop rule
+    pub dropped: bool,
+    /// Replaced by a reroll
+    pub rerolled: bool,
+    /// Triggered an extra die
+    pub exploded: bool,
+}
+
+impl DieRoll {
+    /// Whether this die counts towards the total
+    pub fn counts(&self) -> bool {
+        !self.dropped && !self.rerolled
+    }
+}
+
+/// Result of rolling one dice group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceTermResult {
+    /// The dice group that was rolled
+    pub term: DiceTerm,
+    /// Every die rolled, including dropped and rerolled dice
+    pub rolls: Vec<DieRoll>,
+    /// Sum of the dice that count, or net successes for a success-counting group
+    pub total: i32,
+    /// Dice that met the success target (None unless the group counts successes)
+    pub successes: Option<u32>,
+    /// Dice that met the failure target and cancelled a success
+    pub failures: u32,
+}
+
+impl DiceTermResult {
+    /// Format a single face, using "+", "0" and "-" for Fate dice
+    fn face(&self, value: i32) -> String {
+        match (self.term.kind, value) {
+            (DieKind::Fate, 1) => "+".to_string(),
+            (DieKind::Fate, 0) => "0".to_string(),
+            (DieKind::Fate, _) => "-".to_string(),
+            (DieKind::Numbered, _) => value.to_string(),
+        }
+    }
+
+    /// Format as a breakdown (e.g., "1d20(14)", "4d6kh3[6, 5, 4, (2)]" or
+    /// "5d10>8f1[9*, 3, 1~, 10*!, 8*]")
+    ///
+    /// Dropped and rerolled dice are shown in parentheses, exploding dice with
+    /// "!", successes with "*" and cancelling failures with "~".
+    pub fn breakdown(&self) -> String {
+        let notation = self.term.display();
+        if let [only] = self.rolls.as_slice() {
+            if only.counts() && !only.exploded && self.successes.is_none() {
+                return format!("{}({})", notation, self.face(only.value));
+            }
+        }
+        let rolls: Vec<String> = self
+            .rolls
+            .iter()
+            .map(|r| {
+                let mut marker = String::new();
+                if r.counts() {
+                    if self.term.success.is_some_and(|t| t.matches(r.value)) {
+                        marker.push('*');
+                    }
+                    if self.term.failure.is_some_and(|t| t.matches(r.value)) {
+                        marker.push('~');
+                    }
+                }
+                if r.exploded {
+                    marker.push('!');
+                }
+                if r.counts() {
+                    format!("{}{}", self.face(r.value), marker)
+                } else {
+                    format!("({}{})", self.face(r.value), marker)
+                }
+            })
+            .collect();
+        format!("{}[{}]", notation, rolls.join(", "))
+    }
+}
+
+/// A rung on the Fate ladder (e.g., +3 Good)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FateLadder(pub i32);
+
+impl FateLadder {
+    /// Ladder name; values beyond the ladder take the nearest end
+    pub fn name(&self) -> &'static str {
+        match self.0 {
+            i32::MIN..=-2 => "Terrible",
+            -1 => "Poor",
+            0 => "Mediocre",
+            1 => "Average",
+            2 => "Fair",
+            3 => "Good",
+            4 => "Great",
+            5 => "Superb",
+            6 => "Fantastic",
+            7 => "Epic",
+            _ => "Legendary",
+        }
+    }
+
+    /// Format as "Good (+3)"
+    pub fn display(&self) -> String {
+        format!("{} ({:+})", self.name(), self.0)
+    }
+
+    /// Shifts gained over an opposition (negative when the roll fell short)
+    pub fn shifts_over(&self, opposition: FateLadder) -> i32 {
+        self.0.saturating_sub(opposition.0)
+    }
+}
+
 /// Result of rolling dice
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DiceRollResult {
-    /// The formula that was rolled
-    pub formula: DiceFormula,
-    /// Individual die results
+    /// The formula that was rolled (None for manual input)
+    pub formula: Option<DiceFormula>,
+    /// Per-group results in evaluation order
+    pub terms: Vec<DiceTermResult>,
+    /// Individual die results that count towards the total
     pub individual_rolls: Vec<i32>,
-    /// Sum of dice before modifier
+    /// Value of the dice expression before the modifier
     pub dice_total: i32,
     /// Modifier that was applied
     pub modifier_applied: i32,
     /// Final total (dice_total + modifier)
     pub total: i32,
+    /// Net successes when the formula counts successes (equal to `total`)
+    pub successes: Option<i32>,
+    /// Ladder rung reached when the formula rolls Fate dice
+    pub fate_ladder: Option<FateLadder>,
 }
 
 impl DiceRollResult {
     /// Create a result from a manual input (no actual dice rolled)
     pub fn from_manual(total: i32) -> Self {
         Self {
-            formula: DiceFormula {
-                dice_count: 0,
-                die_size: 0,
-                modifier: 0,
-            },
+            formula: None,
+            terms: vec![],
             individual_rolls: vec![],
             dice_total: total,
             modifier_applied: 0,
             total,
+            successes: None,
+            fate_ladder: None,
+        }
+    }
+
+    /// The same dice with a different flat modifier applied
+    ///
+    /// Returns None for success-counting pools, whose modifier is rolled as
+    /// extra dice rather than added.
+    pub fn with_modifier_applied(&self, modifier: i32) -> Option<Self> {
+        if self.successes.is_some() {
+            return None;
+        }
+        let mut result = self.clone();
+        if let Some(formula) = result.formula.as_mut() {
+            formula.modifier = modifier;
         }
+        result.modifier_applied = modifier;
+        result.total = result.dice_total.saturating_add(modifier);
+        if result.fate_ladder.is_some() {
+            result.fate_ladder = Some(FateLadder(result.total));
+        }
+        Some(result)
+    }
+
+    /// Check if a dice pool botched: no successes and at least one failure die
+    pub fn is_botch(&self) -> bool {
+        self.successes.is_some()
+            && self.terms.iter().all(|t| t.successes.unwrap_or(0) == 0)
+            && self.terms.iter().any(|t| t.failures > 0)
     }
 
     /// Check if this was a manual roll
     pub fn is_manual(&self) -> bool {
-        self.formula.dice_count == 0
+        self.formula.is_none()
     }
 
-    /// Format as a breakdown string (e.g., "1d20(14) + 5 = 19" or "Manual: 18")
+    /// Format as a breakdown string (e.g., "1d20(14) + 5 = 19",
+    /// "4d6kh3[6, 5, 4, (2)] = 15" or "Manual: 18")
     pub fn breakdown(&self) -> String {
-        if self.is_manual() {
-            format!("Manual: {}", self.total)
-        } else if self.individual_rolls.len() == 1 {
-            // Single die
-            let roll = self.individual_rolls[0];
-            if self.modifier_applied == 0 {
-                format!("{}({}) = {}", self.formula.display(), roll, self.total)
-            } else if self.modifier_applied > 0 {
-                format!(
-                    "{}d{}({}) + {} = {}",
-                    self.formula.dice_count,
-                    self.formula.die_size,
-                    roll,
-                    self.modifier_applied,
-                    self.total
-                )
-            } else {
-                format!(
-                    "{}d{}({}) - {} = {}",
-                    self.formula.dice_count,
-                    self.formula.die_size,
-                    roll,
-                    -self.modifier_applied,
-                    self.total
-                )
-            }
+        let Some(formula) = &self.formula else {
+            return format!("Manual: {}", self.total);
+        };
+
+        let mut results = self.terms.iter();
+        let mut out = String::new();
+        formula.expression.write(
+            &mut out,
+            &mut |term, out| match results.next() {
+                Some(result) => out.push_str(&result.breakdown()),
+                None => out.push_str(&term.display()),
+            },
+            true,
+        );
+
+        if self.modifier_applied > 0 {
+            out.push_str(&format!(" + {}", self.modifier_applied));
+        } else if self.modifier_applied < 0 {
+            out.push_str(&format!(" - {}", -(self.modifier_applied as i64)));
+        }
+        if let Some(ladder) = &self.fate_ladder {
+            format!("{} = {}", out, ladder.display())
+        } else if self.successes.is_some() {
+            let noun = if self.total == 1 { "success" } else { "successes" };
+            format!("{} = {} {}", out, self.total, noun)
         } else {
-            // Multiple dice
-            let rolls_str: Vec<String> = self.individual_rolls.iter().map(|r| r.to_string()).collect();
-            if self.modifier_applied == 0 {
-                format!(
-                    "{}[{}] = {}",
-                    self.formula.display(),
-                    rolls_str.join(", "),
-                    self.total
-                )
-            } else if self.modifier_applied > 0 {
-                format!(
-                    "{}d{}[{}] + {} = {}",
-                    self.formula.dice_count,
-                    self.formula.die_size,
-                    rolls_str.join(", "),
-                    self.modifier_applied,
-                    self.total
-                )
-            } else {
-                format!(
-                    "{}d{}[{}] - {} = {}",
-                    self.formula.dice_count,
-                    self.formula.die_size,
-                    rolls_str.join(", "),
-                    -self.modifier_applied,
-                    self.total
-                )
-            }
+            format!("{} = {}", out, self.total)
+        }
+    }
+
+    /// The face of the single kept d20, if the roll has exactly one
+    fn single_d20(&self) -> Option<i32> {
+        let mut d20s = self
+            .terms
+            .iter()
+            .filter(|result| result.term.kind == DieKind::Numbered && result.term.sides == 20);
+        let d20 = d20s.next()?;
+        if d20s.next().is_some() {
+            return None;
+        }
+        let mut kept = d20.rolls.iter().filter(|roll| roll.counts());
+        match (kept.next(), kept.next()) {
+            (Some(die), None) => Some(die.value),
+            _ => None,
         }
     }
 
     /// Check if this is a natural 20 (for d20 systems)
     pub fn is_natural_20(&self) -> bool {
-        self.formula.die_size == 20
-            && self.formula.dice_count == 1
-            && self.individual_rolls.first() == Some(&20)
+        self.single_d20() == Some(20)
     }
 
     /// Check if this is a natural 1 (for d20 systems)
     pub fn is_natural_1(&self) -> bool {
-        self.formula.die_size == 20
-            && self.formula.dice_count == 1
-            && self.individual_rolls.first() == Some(&1)
+        self.single_d20() == Some(1)
+    }
+
+    /// The rolled formula and its primary die, with the die's index among the rolled groups
+    fn primary_roll(
+        &self,
+        mode: RollMode,
+    ) -> Result<(&DiceFormula, usize, &DiceTermResult), DiceParseError> {
+        let Some(formula) = &self.formula else {
+            return Err(DiceParseError::InvalidFormat(
+                "Manual results have no dice to roll again".to_string(),
+            ));
+        };
+        self.terms
+            .iter()
+            .enumerate()
+            .find(|(_, result)| is_primary_die(&result.term))
+            .map(|(index, result)| (formula, index, result))
+            .ok_or_else(|| {
+                DiceParseError::InvalidFormat(format!(
+                    "'{}' has no single die to roll with {}",
+                    formula.display(),
+                    mode
+                ))
+            })
+    }
+
+    /// The extra dice needed to grant `mode` on this roll after the fact
+    ///
+    /// Roll these and pass the faces to `with_granted_mode`.
+    pub fn granted_dice(&self, mode: RollMode) -> Result<DiceFormula, DiceParseError> {
+        let (_, _, primary) = self.primary_roll(mode)?;
+        let sides = if primary.term.sides == 100 { 10 } else { primary.term.sides };
+        DiceFormula::new(mode.edge().unsigned_abs(), sides, 0)
+    }
+
+    /// Re-resolve this roll as if it had been made with `mode`
+    ///
+    /// Every die already rolled is kept; `extra_faces` are the faces rolled
+    /// for `granted_dice`. The result's breakdown shows every die with the
+    /// ones not kept in parentheses.
+    pub fn with_granted_mode(
+        &self,
+        mode: RollMode,
+        extra_faces: &[i32],
+    ) -> Result<Self, DiceParseError> {
+        let (formula, primary_index, primary) = self.primary_roll(mode)?;
+        if extra_faces.len() != mode.edge().unsigned_abs() as usize {
+            return Err(DiceParseError::InvalidFormat(format!(
+                "{} needs {} extra dice",
+                mode,
+                mode.edge().unsigned_abs()
+            )));
+        }
+        let formula = formula.with_roll_mode(mode)?;
+
+        // Replay the recorded dice, splicing the extra dice in at the primary die
+        let mut faces = Vec::new();
+        for (index, result) in self.terms.iter().enumerate() {
+            if index == primary_index {
+                let value = primary.rolls.first().map_or(1, |r| r.value);
+                if primary.term.sides == 100 {
+                    faces.push((value - 1) / 10 + 1);
+                    faces.extend_from_slice(extra_faces);
+                    faces.push((value - 1) % 10 + 1);
+                } else {
+                    faces.push(value);
+                    faces.extend_from_slice(extra_faces);
+                }
+                continue;
+            }
+            let (min_face, _) = result.term.face_range();
+            faces.extend(result.rolls.iter().map(|r| r.value - min_face + 1));
+        }
+
+        let mut faces = faces.into_iter();
+        Ok(formula.roll_with(|_| faces.next().unwrap_or(1)))
     }
 }
 
@@ -365,6 +1616,18 @@ pub enum DiceRollInput {
 }
 
 impl DiceRollInput {
+    /// Apply a roll mode to formula input; manual results are returned unchanged
+    /// since the player already rolled their dice.
+    pub fn with_roll_mode(self, mode: RollMode) -> Result<Self, DiceParseError> {
+        match self {
+            Self::Formula(formula_str) if mode.edge() != 0 => {
+                let formula = DiceFormula::parse(&formula_str)?.with_roll_mode(mode)?;
+                Ok(Self::Formula(formula.display()))
+            }
+            other => Ok(other),
+        }
+    }
+
     /// Resolve the input to a roll result
     pub fn resolve(&self) -> Result<DiceRollResult, DiceParseError> {
         match self {
@@ -377,12 +1640,28 @@ impl DiceRollInput {
     }
 
     /// Resolve with an additional modifier (from character skills)
+    ///
+    /// For success-counting dice pools the modifier adds dice to the pool
+    /// instead of a flat bonus.
     pub fn resolve_with_modifier(&self, skill_modifier: i32) -> Result<DiceRollResult, DiceParseError> {
+        self.resolve_with_modifier_from(skill_modifier, &mut RandomRollSource)
+    }
+
+    /// Resolve with an additional modifier, drawing dice faces from `source`
+    pub fn resolve_with_modifier_from(
+        &self,
+        skill_modifier: i32,
+        source: &mut impl RollSource,
+    ) -> Result<DiceRollResult, DiceParseError> {
         match self {
             Self::Formula(formula_str) => {
-                let mut formula = DiceFormula::parse(formula_str)?;
-                formula.modifier += skill_modifier;
-                Ok(formula.roll())
+                let formula = DiceFormula::parse(formula_str)?;
+                let formula = if formula.counts_successes() {
+                    formula.with_pool_bonus(skill_modifier)
+                } else {
+                    formula.with_modifier(skill_modifier)
+                };
+                Ok(formula.roll_from(source))
             }
             Self::ManualResult(total) => {
                 // For manual results, the player already factored in their modifier
@@ -396,67 +1675,73 @@ impl DiceRollInput {
 mod tests {
     use super::*;
 
+    /// Produce dice faces from a fixed sequence
+    fn faces(values: &[i32]) -> impl FnMut(u32) -> i32 + '_ {
+        let mut iter = values.iter();
+        move |_| *iter.next().expect("ran out of fixed dice faces")
+    }
+
     #[test]
     fn test_parse_simple_d20() {
         let formula = DiceFormula::parse("1d20").unwrap();
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 20);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(20));
         assert_eq!(formula.modifier, 0);
     }
 
     #[test]
     fn test_parse_shorthand_d20() {
         let formula = DiceFormula::parse("d20").unwrap();
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 20);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(20));
         assert_eq!(formula.modifier, 0);
     }
 
     #[test]
     fn test_parse_with_positive_modifier() {
         let formula = DiceFormula::parse("1d20+5").unwrap();
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 20);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(20));
         assert_eq!(formula.modifier, 5);
     }
 
     #[test]
     fn test_parse_with_negative_modifier() {
         let formula = DiceFormula::parse("1d20-3").unwrap();
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 20);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(20));
         assert_eq!(formula.modifier, -3);
     }
 
     #[test]
     fn test_parse_multiple_dice() {
         let formula = DiceFormula::parse("2d6+3").unwrap();
-        assert_eq!(formula.dice_count, 2);
-        assert_eq!(formula.die_size, 6);
+        assert_eq!(formula.dice_count(), 2);
+        assert_eq!(formula.die_size(), Some(6));
         assert_eq!(formula.modifier, 3);
     }
 
     #[test]
     fn test_parse_d100() {
         let formula = DiceFormula::parse("1d100").unwrap();
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 100);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(100));
         assert_eq!(formula.modifier, 0);
     }
 
     #[test]
     fn test_parse_case_insensitive() {
         let formula = DiceFormula::parse("1D20+5").unwrap();
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 20);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(20));
         assert_eq!(formula.modifier, 5);
     }
 
     #[test]
     fn test_parse_with_whitespace() {
-        let formula = DiceFormula::parse("  1d20+5  ").unwrap();
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 20);
+        let formula = DiceFormula::parse("  1d20 + 5  ").unwrap();
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(20));
         assert_eq!(formula.modifier, 5);
     }
 
@@ -489,6 +1774,119 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_percentile_and_multi_term() {
+        let formula = DiceFormula::parse("d%").unwrap();
+        assert_eq!(formula.die_size(), Some(100));
+
+        let formula = DiceFormula::parse("3d8+1d6+4").unwrap();
+        assert_eq!(formula.dice_terms().len(), 2);
+        assert_eq!(formula.dice_count(), 4);
+        assert_eq!(formula.die_size(), None);
+        assert_eq!(formula.modifier, 4);
+        assert_eq!(formula.display(), "3d8+1d6+4");
+        assert_eq!((formula.min_roll(), formula.max_roll()), (8, 34));
+    }
+
+    #[test]
+    fn test_parse_keep_drop_and_modifiers() {
+        let formula = DiceFormula::parse("4d6kh3").unwrap();
+        assert_eq!(formula.dice_terms()[0].keep, Some(KeepRule::KeepHighest(3)));
+        assert_eq!((formula.min_roll(), formula.max_roll()), (3, 18));
+
+        let formula = DiceFormula::parse("2d20kl1").unwrap();
+        assert_eq!(formula.dice_terms()[0].keep, Some(KeepRule::KeepLowest(1)));
+
+        let formula = DiceFormula::parse("4d6dl1").unwrap();
+        assert_eq!(formula.dice_terms()[0].keep, Some(KeepRule::DropLowest(1)));
+
+        let formula = DiceFormula::parse("1d6!").unwrap();
+        assert_eq!(formula.dice_terms()[0].explode, Some(ComparePoint::Exactly(6)));
+        assert_eq!(formula.display(), "1d6!");
+
+        let formula = DiceFormula::parse("2d10!>9ro<2").unwrap();
+        let term = formula.dice_terms()[0];
+        assert_eq!(term.explode, Some(ComparePoint::AtLeast(9)));
+        assert_eq!(
+            term.reroll,
+            Some(RerollRule {
+                target: ComparePoint::AtMost(2),
+                once: true
+            })
+        );
+        assert_eq!(formula.display(), "2d10ro<2!>9");
+    }
+
+    #[test]
+    fn test_parse_arithmetic() {
+        let formula = DiceFormula::parse("(2d6+3)*2").unwrap();
+        assert_eq!(formula.modifier, 0);
+        assert_eq!(formula.display(), "(2d6+3)*2");
+        assert_eq!((formula.min_roll(), formula.max_roll()), (10, 30));
+
+        let formula = DiceFormula::parse("1d20-1d4-2").unwrap();
+        assert_eq!(formula.display(), "1d20-1d4-2");
+        assert_eq!(formula.roll_with(faces(&[10, 3])).total, 5);
+
+        let formula = DiceFormula::parse("1d6/2").unwrap();
+        assert_eq!(formula.roll_with(faces(&[5])).total, 2);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_expressions() {
+        assert!(matches!(
+            DiceFormula::parse("1d20+"),
+            Err(DiceParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            DiceFormula::parse("(1d20"),
+            Err(DiceParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            DiceFormula::parse("3d6kh4"),
+            Err(DiceParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            DiceFormula::parse("1d6!>1"),
+            Err(DiceParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            DiceFormula::parse("1d6/(1d2-1)"),
+            Err(DiceParseError::DivisionByZero)
+        ));
+        assert!(matches!(
+            DiceFormula::parse("2000d6"),
+            Err(DiceParseError::TooManyDice)
+        ));
+        assert!(matches!(
+            DiceFormula::parse("1d20+99999999999"),
+            Err(DiceParseError::ModifierOverflow)
+        ));
+
+        // Deep nesting is refused rather than overflowing the stack
+        for deep in [
+            format!("{}1d20{}", "(".repeat(100_000), ")".repeat(100_000)),
+            format!("{}1d20", "-".repeat(100_000)),
+            vec!["1d4"; 10_000].join("+"),
+        ] {
+            assert!(matches!(
+                DiceFormula::parse(&deep),
+                Err(DiceParseError::InvalidFormat(_))
+            ));
+        }
+        assert!(DiceFormula::parse(&format!("{}1d20{}", "(".repeat(20), ")".repeat(20))).is_ok());
+    }
+
+    #[test]
+    fn test_huge_bounds_saturate() {
+        let huge = "(2147483647*2147483647*2147483647*2147483647)";
+        let formula = DiceFormula::parse(&format!("1d20/({huge}+{huge})")).unwrap();
+        assert_eq!((formula.min_roll(), formula.max_roll()), (0, 0));
+
+        let formula = DiceFormula::parse(&format!("1d20-{huge}-{huge}+5")).unwrap();
+        assert_eq!(formula.min_roll(), i32::MIN);
+    }
+
     #[test]
     fn test_roll_range() {
         let formula = DiceFormula::parse("1d20").unwrap();
@@ -508,27 +1906,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_roll_keep_highest() {
+        let formula = DiceFormula::parse("4d6kh3").unwrap();
+        let result = formula.roll_with(faces(&[2, 6, 4, 5]));
+        assert_eq!(result.total, 15);
+        assert_eq!(result.individual_rolls, vec![6, 4, 5]);
+        assert_eq!(result.breakdown(), "4d6kh3[(2), 6, 4, 5] = 15");
+    }
+
+    #[test]
+    fn test_roll_exploding_and_reroll() {
+        let formula = DiceFormula::parse("1d6!").unwrap();
+        let result = formula.roll_with(faces(&[6, 6, 2]));
+        assert_eq!(result.total, 14);
+        assert_eq!(result.breakdown(), "1d6![6!, 6!, 2] = 14");
+
+        let formula = DiceFormula::parse("2d6r1").unwrap();
+        let result = formula.roll_with(faces(&[1, 1, 3, 4]));
+        assert_eq!(result.total, 7);
+        assert_eq!(result.breakdown(), "2d6r1[(1), (1), 3, 4] = 7");
+    }
+
+    #[test]
+    fn test_breakdown_multi_term() {
+        let formula = DiceFormula::parse("3d8+1d6+4").unwrap();
+        let result = formula.roll_with(faces(&[1, 2, 3, 4]));
+        assert_eq!(result.breakdown(), "3d8[1, 2, 3] + 1d6(4) + 4 = 14");
+    }
+
     #[test]
     fn test_breakdown_single_die() {
-        let result = DiceRollResult {
-            formula: DiceFormula::new(1, 20, 5).unwrap(),
-            individual_rolls: vec![14],
-            dice_total: 14,
-            modifier_applied: 5,
-            total: 19,
-        };
+        let result = DiceFormula::new(1, 20, 5).unwrap().roll_with(faces(&[14]));
         assert_eq!(result.breakdown(), "1d20(14) + 5 = 19");
     }
 
     #[test]
     fn test_breakdown_multiple_dice() {
-        let result = DiceRollResult {
-            formula: DiceFormula::new(2, 6, 3).unwrap(),
-            individual_rolls: vec![4, 5],
-            dice_total: 9,
-            modifier_applied: 3,
-            total: 12,
-        };
+        let result = DiceFormula::new(2, 6, 3).unwrap().roll_with(faces(&[4, 5]));
         assert_eq!(result.breakdown(), "2d6[4, 5] + 3 = 12");
     }
 
@@ -541,30 +1956,106 @@ mod tests {
 
     #[test]
     fn test_natural_20() {
-        let result = DiceRollResult {
-            formula: DiceFormula::new(1, 20, 0).unwrap(),
-            individual_rolls: vec![20],
-            dice_total: 20,
-            modifier_applied: 0,
-            total: 20,
-        };
+        let result = DiceFormula::new(1, 20, 0).unwrap().roll_with(faces(&[20]));
         assert!(result.is_natural_20());
         assert!(!result.is_natural_1());
     }
 
     #[test]
     fn test_natural_1() {
-        let result = DiceRollResult {
-            formula: DiceFormula::new(1, 20, 0).unwrap(),
-            individual_rolls: vec![1],
-            dice_total: 1,
-            modifier_applied: 0,
-            total: 1,
-        };
+        let result = DiceFormula::new(1, 20, 0).unwrap().roll_with(faces(&[1]));
         assert!(result.is_natural_1());
         assert!(!result.is_natural_20());
     }
 
+    #[test]
+    fn test_natural_20_with_kept_die() {
+        let result = DiceFormula::parse("2d20kh1+3").unwrap().roll_with(faces(&[7, 20]));
+        assert!(result.is_natural_20());
+        assert_eq!(result.total, 23);
+    }
+
+    #[test]
+    fn test_naturals_read_the_d20_not_the_dice_total() {
+        let formula = DiceFormula::parse("1d20+1d4").unwrap();
+        let result = formula.roll_with(faces(&[17, 3]));
+        assert_eq!(result.dice_total, 20);
+        assert!(!result.is_natural_20());
+        assert!(formula.roll_with(faces(&[20, 1])).is_natural_20());
+        assert!(formula.roll_with(faces(&[1, 4])).is_natural_1());
+        assert!(!DiceFormula::parse("2d20").unwrap().roll_with(faces(&[19, 1])).is_natural_1());
+    }
+
+    #[test]
+    fn test_fate_dice() {
+        let formula = DiceFormula::parse("4dF+2").unwrap();
+        assert_eq!(formula.display(), "4dF+2");
+        assert_eq!((formula.min_roll(), formula.max_roll()), (-2, 6));
+
+        // d3 faces 1/2/3 map to -/0/+
+        let result = formula.roll_with(faces(&[3, 3, 2, 1]));
+        assert_eq!(result.total, 3);
+        assert_eq!(result.fate_ladder, Some(FateLadder(3)));
+        assert_eq!(result.breakdown(), "4dF[+, +, 0, -] + 2 = Good (+3)");
+        assert_eq!(FateLadder(3).shifts_over(FateLadder(2)), 1);
+    }
+
+    #[test]
+    fn test_with_modifier_applied_keeps_the_dice() {
+        let result = DiceFormula::parse("1d20+3").unwrap().roll_with(faces(&[14]));
+        let changed = result.with_modifier_applied(-1).unwrap();
+        assert_eq!(changed.total, 13);
+        assert_eq!(changed.breakdown(), "1d20(14) - 1 = 13");
+
+        let pool = DiceFormula::parse("3d10>8").unwrap().roll_with(faces(&[9, 2, 8]));
+        assert!(pool.with_modifier_applied(2).is_none());
+    }
+
+    #[test]
+    fn test_default_for_fate_system() {
+        let formula = DiceFormula::default_for_system(&DiceSystem::Fate);
+        assert_eq!(formula.display(), "4dF");
+        assert_eq!((formula.min_roll(), formula.max_roll()), (-4, 4));
+    }
+
+    #[test]
+    fn test_dice_pool_successes() {
+        let formula = DiceFormula::parse("5d10>8f1").unwrap();
+        assert!(formula.counts_successes());
+        assert_eq!((formula.min_roll(), formula.max_roll()), (-5, 5));
+
+        let result = formula.roll_with(faces(&[9, 3, 1, 10, 8]));
+        assert_eq!(result.successes, Some(2));
+        assert!(!result.is_botch());
+        assert_eq!(result.breakdown(), "5d10>8f1[9*, 3, 1~, 10*, 8*] = 2 successes");
+
+        let botch = formula.roll_with(faces(&[2, 3, 1, 4, 5]));
+        assert_eq!(botch.successes, Some(-1));
+        assert!(botch.is_botch());
+    }
+
+    #[test]
+    fn test_dice_pool_from_system() {
+        let system = DiceSystem::DicePool {
+            die_type: 10,
+            success_threshold: 8,
+            ones_cancel: false,
+            again_threshold: Some(10),
+        };
+        let formula = DiceFormula::dice_pool(3, &system).unwrap();
+        assert_eq!(formula.display(), "3d10>8!");
+        assert_eq!(DiceFormula::parse(&formula.display()).unwrap(), formula);
+
+        // 10-again adds a die that can succeed again
+        let result = formula.roll_with(faces(&[10, 8, 2, 4]));
+        assert_eq!(result.successes, Some(2));
+
+        let bigger = formula.with_pool_bonus(2);
+        assert_eq!(bigger.dice_count(), 5);
+        assert_eq!(bigger.clone().with_pool_bonus(-10).dice_count(), 1);
+        assert!(DiceFormula::dice_pool(3, &DiceSystem::D20).is_err());
+    }
+
     #[test]
     fn test_dice_roll_input_formula() {
         let input = DiceRollInput::Formula("1d20+5".to_string());
@@ -592,19 +2083,98 @@ mod tests {
     #[test]
     fn test_default_for_d20_system() {
         let formula = DiceFormula::default_for_system(&DiceSystem::D20);
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 20);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(20));
         assert_eq!(formula.modifier, 0);
     }
 
     #[test]
     fn test_default_for_d100_system() {
         let formula = DiceFormula::default_for_system(&DiceSystem::D100);
-        assert_eq!(formula.dice_count, 1);
-        assert_eq!(formula.die_size, 100);
+        assert_eq!(formula.dice_count(), 1);
+        assert_eq!(formula.die_size(), Some(100));
         assert_eq!(formula.modifier, 0);
     }
 
+    #[test]
+    fn test_default_for_custom_system_uses_its_expression() {
+        let formula = DiceFormula::default_for_system(&DiceSystem::Custom("3d8".to_string()));
+        assert_eq!(formula.dice_count(), 3);
+        assert_eq!(formula.die_size(), Some(8));
+
+        // Free-text labels from older worlds still fall back to a guess
+        let formula = DiceFormula::default_for_system(&DiceSystem::Custom("Variable die (d4-d20)".to_string()));
+        assert_eq!(formula.die_size(), Some(20));
+    }
+
+    #[test]
+    fn test_advantage_keeps_the_higher_d20() {
+        let formula = DiceFormula::parse("1d20+5")
+            .unwrap()
+            .with_roll_mode(RollMode::Advantage)
+            .unwrap();
+        assert_eq!(formula.display(), "2d20kh1+5");
+
+        let result = formula.roll_with(faces(&[4, 17]));
+        assert_eq!(result.total, 22);
+        assert_eq!(result.breakdown(), "2d20kh1[(4), 17] + 5 = 22");
+        assert!(!result.is_natural_1());
+    }
+
+    #[test]
+    fn test_roll_mode_applies_to_the_primary_die_only() {
+        let formula = DiceFormula::parse("1d20+1d4")
+            .unwrap()
+            .with_roll_mode(RollMode::Disadvantage)
+            .unwrap();
+        assert_eq!(formula.display(), "2d20kl1+1d4");
+        assert!(DiceFormula::parse("4d6")
+            .unwrap()
+            .with_roll_mode(RollMode::Advantage)
+            .is_err());
+    }
+
+    #[test]
+    fn test_percentile_bonus_dice_keep_the_lowest_tens() {
+        let formula = DiceFormula::parse("1d100")
+            .unwrap()
+            .with_roll_mode(RollMode::BonusDice(1))
+            .unwrap();
+        assert_eq!(formula.display(), "(2d10kl1-1)*10+1d10");
+        assert_eq!(DiceFormula::parse(&formula.display()).unwrap(), formula);
+
+        // Tens dice 7 and 3 (60 and 20), units 5: keeps 25
+        let result = formula.roll_with(faces(&[7, 3, 5]));
+        assert_eq!(result.total, 25);
+
+        let penalty = DiceFormula::parse("1d100")
+            .unwrap()
+            .with_roll_mode(RollMode::PenaltyDice(1))
+            .unwrap();
+        assert_eq!(penalty.roll_with(faces(&[7, 3, 5])).total, 65);
+    }
+
+    #[test]
+    fn test_granted_mode_keeps_the_original_dice() {
+        let result = DiceFormula::parse("1d20+1d4+2").unwrap().roll_with(faces(&[6, 3]));
+        let extra = result.granted_dice(RollMode::Advantage).unwrap();
+        assert_eq!(extra.display(), "1d20");
+
+        let granted = result.with_granted_mode(RollMode::Advantage, &[15]).unwrap();
+        assert_eq!(granted.total, 20);
+        assert_eq!(granted.breakdown(), "2d20kh1[(6), 15] + 1d4(3) + 2 = 20");
+
+        // Percentile: 47 gets a bonus tens die of 2 (10) -> 17
+        let result = DiceFormula::parse("1d100").unwrap().roll_with(faces(&[47]));
+        assert_eq!(result.granted_dice(RollMode::BonusDice(1)).unwrap().display(), "1d10");
+        let granted = result.with_granted_mode(RollMode::BonusDice(1), &[2]).unwrap();
+        assert_eq!(granted.total, 17);
+
+        assert!(DiceRollResult::from_manual(12)
+            .with_granted_mode(RollMode::Advantage, &[3])
+            .is_err());
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(DiceFormula::new(1, 20, 0).unwrap().display(), "1d20");
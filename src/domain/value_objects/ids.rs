@@ -84,3 +84,5 @@ define_id!(QueueItemId);
 define_id!(PlayerCharacterId);
 define_id!(RegionId);
 define_id!(GoalId);
+define_id!(RollId);
+define_id!(GridTokenId);
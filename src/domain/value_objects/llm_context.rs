@@ -66,6 +66,9 @@ pub struct CharacterContext {
     pub wants: Vec<String>,
     /// How this character relates to the player
     pub relationship_to_player: Option<String>,
+    /// Conditions currently affecting the character (e.g., "Poisoned: -2 to rolls")
+    #[serde(default)]
+    pub conditions: Vec<String>,
 }
 
 /// A single turn in a conversation
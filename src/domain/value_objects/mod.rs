@@ -3,6 +3,7 @@
 mod approval;
 mod archetype;
 mod comfyui_config;
+mod condition;
 mod context_budget;
 mod dice;
 mod directorial;
@@ -10,20 +11,29 @@ mod game_time;
 mod game_tools;
 mod ids;
 mod llm_context;
+mod progression;
 mod region;
+mod resource;
 mod relationship;
+mod roll_mode;
+mod roll_source;
 mod rule_system;
 mod settings;
+mod sheet_formula;
 
 pub use approval::{ApprovalDecision, ProposedToolInfo};
 pub use game_time::{GameTime, TimeOfDay};
 pub use archetype::{ArchetypeChange, CampbellArchetype};
 pub use comfyui_config::ComfyUIConfig;
+pub use condition::{ActiveCondition, ActiveConditions, ConditionDuration, SECONDS_PER_ROUND};
 pub use context_budget::{
     AssembledContext, CategoryContext, ContextBudgetConfig, ContextCategory,
     TokenCountMethod, TokenCounter, count_tokens, exceeds_token_budget,
 };
-pub use dice::DiceRollInput;
+pub use dice::{
+    DiceExpr, DiceFormula, DiceParseError, DiceRollInput, DiceRollResult, DiceTerm, DieKind,
+    FateLadder, KeepRule, MAX_EXPLOSIONS_PER_DIE, MAX_REROLLS_PER_DIE,
+};
 pub use directorial::{DirectorialNotes};
 pub use game_tools::{ChangeAmount, GameTool, InfoImportance, RelationshipChange};
 pub use ids::*;
@@ -31,12 +41,20 @@ pub use llm_context::{
     ActiveChallengeContext, ActiveNarrativeEventContext, CharacterContext, ConversationTurn,
     GamePromptRequest, PlayerActionContext, SceneContext,
 };
+pub use progression::{
+    Advancement, AdvancementAward, AdvancementMode, ProgressionLevel, ProgressionTable,
+};
 pub use region::{RegionFrequency, RegionRelationship, RegionRelationshipType, RegionShift};
+pub use resource::{RecoveryAmount, ResourceLevel, ResourceOperation, ResourceRecovery, RestType};
 pub use relationship::{FamilyRelation, Relationship, RelationshipEvent, RelationshipType};
+pub use roll_mode::RollMode;
+pub use roll_source::{RandomRollSource, RollSource, SeededRollSource};
 pub use rule_system::{
-    DiceSystem, RuleSystemConfig, RuleSystemType, RuleSystemVariant, StatDefinition, SuccessComparison,
+    ActionEffect, ActionPosition, DescriptorDifficulties, DescriptorTarget, DiagonalRule, DiceSystem, InitiativeRule, OpposedTieBreak,
+    ResolutionBands, RuleSystemConfig, RuleSystemType, RuleSystemVariant, StatDefinition, SuccessComparison,
 };
 pub use settings::{AppSettings, SettingsFieldMetadata, settings_metadata};
+pub use sheet_formula::SheetFormula;
 
 // NOTE: Want has been promoted to an entity (domain/entities/want.rs)
 // ActantTarget is no longer used - targets are now Neo4j edges
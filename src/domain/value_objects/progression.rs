@@ -0,0 +1,266 @@
+//! Progression - How characters advance (experience, milestones, levels)
+//!
+//! A progression table lists the levels a rule system offers, the experience
+//! or milestones each one needs, and the sheet fields and skills reaching it
+//! unlocks. Characters bank progress as it is awarded; gaining the levels it
+//! earns waits for the DM's approval.
+
+use std::fmt;
+
+/// What a progression table measures progress in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdvancementMode {
+    /// Experience points, awarded for challenges and story beats
+    Experience,
+    /// Milestones, awarded when the story reaches a turning point
+    Milestone,
+}
+
+/// Progress awarded to a character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdvancementAward {
+    Experience(u32),
+    Milestones(u32),
+}
+
+impl AdvancementAward {
+    /// An award of `amount` in what a mode measures
+    pub fn new(mode: AdvancementMode, amount: u32) -> Self {
+        match mode {
+            AdvancementMode::Experience => Self::Experience(amount),
+            AdvancementMode::Milestone => Self::Milestones(amount),
+        }
+    }
+
+    /// Read the reward of an `AddReward` effect or trigger
+    ///
+    /// Returns None for rewards that are not experience or milestones (gold,
+    /// renown) and for amounts below one.
+    pub fn from_reward(reward_type: &str, amount: i32) -> Option<Self> {
+        let amount = u32::try_from(amount).ok().filter(|amount| *amount > 0)?;
+        match reward_type.trim().to_lowercase().as_str() {
+            "xp" | "exp" | "experience" | "experience points" => Some(Self::Experience(amount)),
+            "milestone" | "milestones" => Some(Self::Milestones(amount)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AdvancementAward {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Experience(amount) => write!(f, "{} XP", amount),
+            Self::Milestones(1) => write!(f, "1 milestone"),
+            Self::Milestones(amount) => write!(f, "{} milestones", amount),
+        }
+    }
+}
+
+/// One level of a progression table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressionLevel {
+    pub level: u32,
+    /// Total experience or milestones needed to reach the level
+    pub threshold: u32,
+    /// Sheet fields that stay locked until the level is reached
+    pub unlocks_fields: Vec<String>,
+    /// Skills the character becomes proficient in on reaching the level
+    pub unlocks_skills: Vec<String>,
+}
+
+impl ProgressionLevel {
+    pub fn new(level: u32, threshold: u32) -> Self {
+        Self {
+            level,
+            threshold,
+            unlocks_fields: Vec::new(),
+            unlocks_skills: Vec::new(),
+        }
+    }
+}
+
+/// The levels characters advance through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressionTable {
+    pub mode: AdvancementMode,
+    /// Sheet field mirroring the character's level, if the sheet has one
+    pub level_field: Option<String>,
+    /// Levels in ascending order
+    pub levels: Vec<ProgressionLevel>,
+}
+
+impl ProgressionTable {
+    pub fn new(mode: AdvancementMode) -> Self {
+        Self {
+            mode,
+            level_field: None,
+            levels: Vec::new(),
+        }
+    }
+
+    /// A table with one level per threshold, starting at level 1
+    pub fn from_thresholds(mode: AdvancementMode, thresholds: &[u32]) -> Self {
+        let mut table = Self::new(mode);
+        table.levels = thresholds
+            .iter()
+            .zip(1..)
+            .map(|(threshold, level)| ProgressionLevel::new(level, *threshold))
+            .collect();
+        table
+    }
+
+    pub fn with_level_field(mut self, field_id: impl Into<String>) -> Self {
+        self.level_field = Some(field_id.into());
+        self
+    }
+
+    /// Add a level, replacing any with the same number
+    pub fn with_level(mut self, level: ProgressionLevel) -> Self {
+        self.levels.retain(|l| l.level != level.level);
+        self.levels.push(level);
+        self.levels.sort_by_key(|l| l.level);
+        self
+    }
+
+    /// The level new characters start at
+    pub fn starting_level(&self) -> u32 {
+        self.levels.first().map_or(1, |l| l.level)
+    }
+
+    /// The highest level a total of experience or milestones reaches
+    pub fn level_reached(&self, progress: u32) -> u32 {
+        self.levels
+            .iter()
+            .take_while(|l| l.threshold <= progress)
+            .last()
+            .map_or_else(|| self.starting_level(), |l| l.level)
+    }
+
+    /// The level after `level`, if there is one
+    pub fn next_level(&self, level: u32) -> Option<&ProgressionLevel> {
+        self.levels.iter().find(|l| l.level > level)
+    }
+
+    /// The levels above `from`, up to and including `to`
+    pub fn levels_gained(&self, from: u32, to: u32) -> impl Iterator<Item = &ProgressionLevel> {
+        self.levels.iter().filter(move |l| l.level > from && l.level <= to)
+    }
+
+    /// Fields still locked at `level`, with the level that unlocks each
+    pub fn locked_fields(&self, level: u32) -> Vec<(&str, u32)> {
+        self.levels
+            .iter()
+            .filter(|l| l.level > level)
+            .flat_map(|l| l.unlocks_fields.iter().map(move |field| (field.as_str(), l.level)))
+            .collect()
+    }
+
+    /// Problems that make the table unusable, empty if there are none
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.levels.is_empty() {
+            problems.push("progression needs at least one level".to_string());
+        }
+        for pair in self.levels.windows(2) {
+            if pair[0].level == pair[1].level {
+                problems.push(format!("level {} is defined twice", pair[0].level));
+            } else if pair[1].threshold < pair[0].threshold {
+                problems.push(format!(
+                    "level {} needs less progress than level {}",
+                    pair[1].level, pair[0].level
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// A character's progress and level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Advancement {
+    pub experience: u32,
+    pub milestones: u32,
+    /// The level the character has been approved for
+    pub level: u32,
+}
+
+impl Default for Advancement {
+    fn default() -> Self {
+        Self {
+            experience: 0,
+            milestones: 0,
+            level: 1,
+        }
+    }
+}
+
+impl Advancement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Progress in what a table measures
+    pub fn progress(&self, mode: AdvancementMode) -> u32 {
+        match mode {
+            AdvancementMode::Experience => self.experience,
+            AdvancementMode::Milestone => self.milestones,
+        }
+    }
+
+    /// Bank an award; levels it earns are gained separately
+    pub fn award(&mut self, award: AdvancementAward) {
+        match award {
+            AdvancementAward::Experience(amount) => self.experience = self.experience.saturating_add(amount),
+            AdvancementAward::Milestones(amount) => self.milestones = self.milestones.saturating_add(amount),
+        }
+    }
+
+    /// The level the character's progress has earned; above `level` while a
+    /// level-up waits for approval
+    pub fn earned_level(&self, table: &ProgressionTable) -> u32 {
+        table.level_reached(self.progress(table.mode)).max(self.level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_earns_levels() {
+        let table = ProgressionTable::from_thresholds(AdvancementMode::Experience, &[0, 300, 900, 2700])
+            .with_level(ProgressionLevel {
+                unlocks_fields: vec!["SPELL_SLOTS".to_string()],
+                unlocks_skills: vec!["Arcana".to_string()],
+                ..ProgressionLevel::new(3, 900)
+            });
+        assert_eq!(table.level_reached(0), 1);
+        assert_eq!(table.level_reached(899), 2);
+        assert_eq!(table.level_reached(100_000), 4);
+        assert_eq!(table.locked_fields(2), vec![("SPELL_SLOTS", 3)]);
+        assert!(table.locked_fields(3).is_empty());
+
+        let mut advancement = Advancement::new();
+        advancement.award(AdvancementAward::Experience(1000));
+        advancement.award(AdvancementAward::Milestones(1));
+        assert_eq!(advancement.earned_level(&table), 3);
+        let gained: Vec<u32> = table.levels_gained(advancement.level, 3).map(|l| l.level).collect();
+        assert_eq!(gained, vec![2, 3]);
+
+        advancement.level = 3;
+        assert_eq!(advancement.earned_level(&table), 3);
+        assert_eq!(table.next_level(3).map(|l| l.threshold), Some(2700));
+    }
+
+    #[test]
+    fn test_rewards_and_table_problems() {
+        assert_eq!(AdvancementAward::from_reward("XP", 250), Some(AdvancementAward::Experience(250)));
+        assert_eq!(AdvancementAward::from_reward("milestone", 1), Some(AdvancementAward::Milestones(1)));
+        assert_eq!(AdvancementAward::from_reward("gold", 50), None);
+        assert_eq!(AdvancementAward::from_reward("xp", -10), None);
+
+        let table = ProgressionTable::from_thresholds(AdvancementMode::Milestone, &[0, 2, 1]);
+        assert_eq!(table.problems(), vec!["level 3 needs less progress than level 2".to_string()]);
+        assert_eq!(ProgressionTable::new(AdvancementMode::Milestone).problems().len(), 1);
+    }
+}
@@ -0,0 +1,180 @@
+//! Sheet resources - Spending and recovering tracked values
+//!
+//! Resources are sheet values that run down in play and come back: hit
+//! points, spell slots, hit dice, luck, stress. They are operated on by ID,
+//! either one at a time or by a rest, which recovers several at once and takes
+//! game time.
+
+/// A change to one resource on a character sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceOperation {
+    /// Use up some of the resource; fails if there is not enough
+    Spend(u32),
+    /// Regain some of the resource, up to its maximum
+    Restore(u32),
+    /// Return the resource to its starting value (full for a resource)
+    Reset,
+}
+
+/// Where a resource stands and the bounds it moves within
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLevel {
+    pub current: i32,
+    pub floor: i32,
+    /// None for values without an upper bound
+    pub ceiling: Option<i32>,
+    /// The value a reset returns to
+    pub reset_to: i32,
+}
+
+impl ResourceLevel {
+    /// How much can still be spent; never negative
+    pub fn available(&self) -> i32 {
+        self.current.saturating_sub(self.floor).max(0)
+    }
+
+    /// The value after an operation, or None if a spend exceeds what is left
+    pub fn after(&self, operation: ResourceOperation) -> Option<i32> {
+        match operation {
+            ResourceOperation::Spend(amount) => {
+                let left = self.current.checked_sub_unsigned(amount)?;
+                (left >= self.floor).then_some(left)
+            }
+            ResourceOperation::Restore(amount) => {
+                let restored = self.current.saturating_add_unsigned(amount).max(self.floor);
+                Some(self.ceiling.map_or(restored, |ceiling| restored.min(ceiling.max(self.current))))
+            }
+            ResourceOperation::Reset => Some(self.reset_to),
+        }
+    }
+}
+
+/// A kind of rest a rule system offers (a short rest, a night's sleep, a
+/// month of therapy) and the resources it brings back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestType {
+    /// Stable identifier used by clients (e.g. "long_rest")
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Game time the rest takes
+    pub duration_minutes: u32,
+    pub recoveries: Vec<ResourceRecovery>,
+}
+
+impl RestType {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, duration_minutes: u32) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            description: String::new(),
+            duration_minutes,
+            recoveries: Vec::new(),
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Recover a sheet field by `amount` during this rest
+    pub fn recovers(mut self, field_id: impl Into<String>, amount: RecoveryAmount) -> Self {
+        self.recoveries.push(ResourceRecovery {
+            field_id: field_id.into(),
+            amount,
+        });
+        self
+    }
+}
+
+/// One resource a rest recovers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceRecovery {
+    pub field_id: String,
+    pub amount: RecoveryAmount,
+}
+
+/// How much of a resource a rest recovers
+///
+/// Fixed amounts stand in for recovery a system has players roll for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryAmount {
+    /// Back to the starting value
+    Full,
+    /// Regain up to this much
+    Restore(i32),
+    /// Half the maximum, at least 1 (e.g. D&D hit dice)
+    HalfOfMax,
+    /// Lower a value that counts up, such as harm or stress, by this much
+    Reduce(i32),
+    /// Lower a counting-up value all the way
+    Clear,
+}
+
+impl RecoveryAmount {
+    /// The new value of a resource after the recovery
+    pub fn recover(&self, level: &ResourceLevel) -> i32 {
+        let operation = match *self {
+            Self::Full => ResourceOperation::Reset,
+            Self::Restore(amount) => ResourceOperation::Restore(amount.max(0).unsigned_abs()),
+            Self::HalfOfMax => {
+                let max = level.ceiling.unwrap_or(level.reset_to);
+                ResourceOperation::Restore((max / 2).max(1).unsigned_abs())
+            }
+            Self::Reduce(amount) => {
+                return level.current.saturating_sub(amount).max(level.floor).min(level.current)
+            }
+            Self::Clear => return level.floor.min(level.current),
+        };
+        level.after(operation).unwrap_or(level.current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(current: i32, ceiling: Option<i32>) -> ResourceLevel {
+        ResourceLevel {
+            current,
+            floor: 0,
+            ceiling,
+            reset_to: ceiling.unwrap_or(0),
+        }
+    }
+
+    #[test]
+    fn test_operations_stay_in_bounds() {
+        let slots = level(2, Some(4));
+        assert_eq!(slots.after(ResourceOperation::Spend(2)), Some(0));
+        assert_eq!(slots.after(ResourceOperation::Spend(3)), None);
+        assert_eq!(slots.after(ResourceOperation::Restore(5)), Some(4));
+        assert_eq!(slots.after(ResourceOperation::Reset), Some(4));
+        assert_eq!(slots.after(ResourceOperation::Spend(u32::MAX)), None);
+        assert_eq!(slots.after(ResourceOperation::Restore(u32::MAX)), Some(4));
+
+        let unbounded = level(7, None);
+        assert_eq!(unbounded.after(ResourceOperation::Restore(5)), Some(12));
+
+        assert_eq!(slots.available(), 2);
+        let deep = ResourceLevel { floor: i32::MIN, ..level(i32::MAX, None) };
+        assert_eq!(deep.available(), i32::MAX);
+        let overdrawn = ResourceLevel { floor: 3, ..level(1, None) };
+        assert_eq!(overdrawn.available(), 0);
+    }
+
+    #[test]
+    fn test_recovery_amounts() {
+        let hit_dice = level(1, Some(9));
+        assert_eq!(RecoveryAmount::HalfOfMax.recover(&hit_dice), 5);
+        assert_eq!(RecoveryAmount::Full.recover(&hit_dice), 9);
+        assert_eq!(RecoveryAmount::Restore(2).recover(&hit_dice), 3);
+        assert_eq!(RecoveryAmount::Restore(-2).recover(&hit_dice), 1);
+
+        let stress = level(5, Some(9));
+        assert_eq!(RecoveryAmount::Reduce(2).recover(&stress), 3);
+        assert_eq!(RecoveryAmount::Reduce(9).recover(&stress), 0);
+        assert_eq!(RecoveryAmount::Clear.recover(&stress), 0);
+    }
+}
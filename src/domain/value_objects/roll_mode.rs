@@ -0,0 +1,74 @@
+//! Roll modes - rolling extra dice and keeping the best or worst
+//!
+//! Covers D&D 5e advantage/disadvantage (roll the d20 twice) and Call of
+//! Cthulhu bonus/penalty dice (roll extra tens dice on a d100). A roll mode is
+//! applied to the formula's single primary die; see
+//! `DiceFormula::with_roll_mode`.
+
+use serde::{Deserialize, Serialize};
+
+/// Most bonus or penalty dice a single roll can have
+pub const MAX_BONUS_DICE: u8 = 2;
+
+/// How many times the primary die is rolled and which result is kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollMode {
+    /// Roll once
+    #[default]
+    Normal,
+    /// Roll twice and keep the better result
+    Advantage,
+    /// Roll twice and keep the worse result
+    Disadvantage,
+    /// Roll this many extra dice and keep the best (capped at `MAX_BONUS_DICE`)
+    BonusDice(u8),
+    /// Roll this many extra dice and keep the worst (capped at `MAX_BONUS_DICE`)
+    PenaltyDice(u8),
+}
+
+impl RollMode {
+    /// Get a display name for the roll mode
+    pub fn display_name(&self) -> String {
+        match self {
+            RollMode::Normal => "Normal".to_string(),
+            RollMode::Advantage => "Advantage".to_string(),
+            RollMode::Disadvantage => "Disadvantage".to_string(),
+            RollMode::BonusDice(1) => "1 bonus die".to_string(),
+            RollMode::BonusDice(n) => format!("{} bonus dice", n),
+            RollMode::PenaltyDice(1) => "1 penalty die".to_string(),
+            RollMode::PenaltyDice(n) => format!("{} penalty dice", n),
+        }
+    }
+
+    /// Net extra dice: positive keeps the best, negative keeps the worst
+    pub fn edge(&self) -> i32 {
+        match self {
+            RollMode::Normal => 0,
+            RollMode::Advantage => 1,
+            RollMode::Disadvantage => -1,
+            RollMode::BonusDice(n) => (*n).min(MAX_BONUS_DICE) as i32,
+            RollMode::PenaltyDice(n) => -((*n).min(MAX_BONUS_DICE) as i32),
+        }
+    }
+}
+
+impl std::fmt::Display for RollMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_counts_extra_dice() {
+        assert_eq!(RollMode::Normal.edge(), 0);
+        assert_eq!(RollMode::Advantage.edge(), 1);
+        assert_eq!(RollMode::Disadvantage.edge(), -1);
+        assert_eq!(RollMode::BonusDice(2).edge(), 2);
+        assert_eq!(RollMode::PenaltyDice(5).edge(), -(MAX_BONUS_DICE as i32));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Supports multiple TTRPG systems through presets and customization.
 
+use super::{AdvancementMode, FateLadder, ProgressionTable, RecoveryAmount, RestType};
+
 /// The type of rule system (determines dice mechanics and success calculation)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuleSystemType {
@@ -103,6 +105,17 @@ pub struct RuleSystemConfig {
     pub success_comparison: SuccessComparison,
     /// Formula for skill checks (display only)
     pub skill_check_formula: String,
+    /// What descriptive difficulties (Easy, Hard, Risky...) mean in this system
+    pub descriptor_difficulties: DescriptorDifficulties,
+    /// Degrees of success declared by a user-defined system; presets grade
+    /// by their variant
+    pub custom_resolution: Option<ResolutionBands>,
+    /// Opposed tie rule declared by a user-defined system
+    pub custom_tie_break: Option<OpposedTieBreak>,
+    /// Rests declared by a user-defined system
+    pub custom_rest_types: Option<Vec<RestType>>,
+    /// Progression declared by a user-defined system
+    pub custom_progression: Option<ProgressionTable>,
 }
 
 impl Default for RuleSystemConfig {
@@ -146,6 +159,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::GreaterOrEqual,
             skill_check_formula: "1d20 + ability modifier + proficiency (if proficient)".to_string(),
             description: "Roll d20, add modifiers. Meet or beat the DC to succeed.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::d20(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -167,6 +185,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::GreaterOrEqual,
             skill_check_formula: "1d20 + modifier vs DC (4 degrees of success)".to_string(),
             description: "Roll d20 + modifier. Crit success on DC+10, crit fail on DC-10.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::pathfinder(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -188,6 +211,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::GreaterOrEqual,
             skill_check_formula: "1d20 + modifier vs DC".to_string(),
             description: "Roll d20, add modifiers. Meet or beat the DC to succeed.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::d20(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -212,6 +240,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::LessOrEqual,
             skill_check_formula: "Roll d100 ≤ skill value".to_string(),
             description: "Roll d100. Regular success ≤ skill, Hard ≤ half, Extreme ≤ fifth.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::percentile(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -234,6 +267,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::LessOrEqual,
             skill_check_formula: "Roll d100 ≤ skill value".to_string(),
             description: "Roll d100 under skill. Critical on 1/20th, special on 1/5th.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::percentile(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -255,6 +293,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::LessOrEqual,
             skill_check_formula: "Roll d100 ≤ skill value".to_string(),
             description: "Roll d100 and compare to skill value. Lower is better.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::percentile(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -276,6 +319,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::Narrative,
             skill_check_formula: "Roll stat die vs difficulty (1-6 scale)".to_string(),
             description: "Roll your stat die. Higher stat = bigger die. Narrative outcomes.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::stat_die(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -297,6 +345,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::Narrative,
             skill_check_formula: "4dF + approach vs difficulty ladder".to_string(),
             description: "Roll 4 Fate dice (+/-/blank) + approach. Compare to ladder.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::fate_ladder(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -317,6 +370,11 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::Narrative,
             skill_check_formula: "2d6 + stat: 10+ full success, 7-9 partial, 6- miss".to_string(),
             description: "Roll 2d6 + stat. 10+ success, 7-9 success with cost, 6- trouble.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::position_effect(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
         }
     }
 
@@ -331,6 +389,201 @@ impl RuleSystemConfig {
             success_comparison: SuccessComparison::Narrative,
             skill_check_formula: "Custom resolution".to_string(),
             description: "A custom rule system. Define your own stats and mechanics.".to_string(),
+            descriptor_difficulties: DescriptorDifficulties::d20(),
+            custom_resolution: None,
+            custom_tie_break: None,
+            custom_rest_types: None,
+            custom_progression: None,
+        }
+    }
+
+    /// How rolls are graded into degrees of success under this system
+    pub fn resolution_bands(&self) -> ResolutionBands {
+        if let Some(bands) = self.custom_resolution {
+            return bands;
+        }
+        match self.variant {
+            RuleSystemVariant::Pathfinder2e => ResolutionBands::Degrees { critical_margin: 10 },
+            RuleSystemVariant::CallOfCthulhu7e => ResolutionBands::RollUnder,
+            RuleSystemVariant::PoweredByApocalypse => ResolutionBands::Bands {
+                partial_at: 7,
+                success_at: 10,
+            },
+            RuleSystemVariant::FateCore => ResolutionBands::Shifts,
+            _ => ResolutionBands::Standard,
+        }
+    }
+
+    /// Who wins an opposed check when both sides score the same
+    pub fn opposed_tie_break(&self) -> OpposedTieBreak {
+        if let Some(tie_break) = self.custom_tie_break {
+            return tie_break;
+        }
+        match self.variant {
+            RuleSystemVariant::Pathfinder2e => OpposedTieBreak::Initiator,
+            RuleSystemVariant::CallOfCthulhu7e
+            | RuleSystemVariant::RuneQuest
+            | RuleSystemVariant::GenericD100 => OpposedTieBreak::HigherSkill,
+            RuleSystemVariant::FateCore => OpposedTieBreak::Partial,
+            _ => OpposedTieBreak::Defender,
+        }
+    }
+
+    /// The rests characters can take under this system
+    ///
+    /// Recoveries name fields of the variant's default sheet; fields a sheet
+    /// does not have are skipped.
+    pub fn rest_types(&self) -> Vec<RestType> {
+        if let Some(rests) = &self.custom_rest_types {
+            return rests.clone();
+        }
+        match self.variant {
+            RuleSystemVariant::Dnd5e => vec![
+                RestType::new("short_rest", "Short Rest", 60)
+                    .with_description("An hour of light activity; spend hit dice to heal")
+                    .recovers("PACT_SLOTS", RecoveryAmount::Full)
+                    .recovers("KI", RecoveryAmount::Full),
+                RestType::new("long_rest", "Long Rest", 8 * 60)
+                    .with_description("Eight hours of sleep and light activity")
+                    .recovers("HP", RecoveryAmount::Full)
+                    .recovers("HIT_DICE", RecoveryAmount::HalfOfMax)
+                    .recovers("SPELL_SLOTS", RecoveryAmount::Full)
+                    .recovers("PACT_SLOTS", RecoveryAmount::Full)
+                    .recovers("KI", RecoveryAmount::Full),
+            ],
+            RuleSystemVariant::Pathfinder2e => vec![
+                RestType::new("refocus", "Refocus", 10)
+                    .with_description("Ten minutes restoring your focus")
+                    .recovers("FOCUS_POINTS", RecoveryAmount::Restore(1)),
+                RestType::new("full_rest", "Full Night's Rest", 8 * 60)
+                    .with_description("Eight hours of rest and daily preparations")
+                    .recovers("HP", RecoveryAmount::Full)
+                    .recovers("SPELL_SLOTS", RecoveryAmount::Full)
+                    .recovers("FOCUS_POINTS", RecoveryAmount::Full),
+            ],
+            RuleSystemVariant::GenericD20 => vec![
+                RestType::new("short_rest", "Short Rest", 60)
+                    .recovers("HP", RecoveryAmount::HalfOfMax),
+                RestType::new("long_rest", "Long Rest", 8 * 60)
+                    .recovers("HP", RecoveryAmount::Full),
+            ],
+            RuleSystemVariant::CallOfCthulhu7e => vec![
+                RestType::new("night_rest", "Night's Rest", 8 * 60)
+                    .with_description("A night's sleep restores magic points")
+                    .recovers("MP", RecoveryAmount::Full),
+                RestType::new("recuperation", "Week of Recuperation", 7 * 24 * 60)
+                    .with_description("A week of rest heals wounds")
+                    .recovers("HP", RecoveryAmount::Restore(3)),
+                RestType::new("sanity_recovery", "Month of Therapy", 30 * 24 * 60)
+                    .with_description("A month of psychotherapy restores sanity")
+                    .recovers("SAN", RecoveryAmount::Restore(2)),
+            ],
+            RuleSystemVariant::RuneQuest | RuleSystemVariant::GenericD100 => vec![
+                RestType::new("night_rest", "Night's Rest", 8 * 60)
+                    .recovers("HP", RecoveryAmount::Restore(1))
+                    .recovers("MP", RecoveryAmount::Full),
+            ],
+            RuleSystemVariant::FateCore => vec![
+                RestType::new("recover", "Catch a Breath", 0)
+                    .with_description("Stress clears at the end of a scene")
+                    .recovers("PHYSICAL_STRESS", RecoveryAmount::Clear)
+                    .recovers("MENTAL_STRESS", RecoveryAmount::Clear),
+                RestType::new("refresh", "Refresh", 0)
+                    .with_description("Fate points return to refresh at a major milestone")
+                    .recovers("FATE_POINTS", RecoveryAmount::Full),
+            ],
+            RuleSystemVariant::PoweredByApocalypse => vec![
+                RestType::new("downtime", "Downtime", 24 * 60)
+                    .with_description("A day's rest heals a segment of harm")
+                    .recovers("HARM", RecoveryAmount::Reduce(1)),
+            ],
+            RuleSystemVariant::KidsOnBikes | RuleSystemVariant::Custom(_) => Vec::new(),
+        }
+    }
+
+    /// One of the system's rests by ID
+    pub fn rest_type(&self, id: &str) -> Option<RestType> {
+        self.rest_types().into_iter().find(|rest| rest.id == id)
+    }
+
+    /// How characters advance under this system
+    ///
+    /// None for systems without levels, where characters improve skill by
+    /// skill (Call of Cthulhu, RuneQuest) or between sessions (Kids on Bikes).
+    pub fn progression(&self) -> Option<ProgressionTable> {
+        if let Some(progression) = &self.custom_progression {
+            return Some(progression.clone());
+        }
+        match self.variant {
+            RuleSystemVariant::Dnd5e | RuleSystemVariant::GenericD20 => Some(
+                ProgressionTable::from_thresholds(
+                    AdvancementMode::Experience,
+                    &[
+                        0, 300, 900, 2_700, 6_500, 14_000, 23_000, 34_000, 48_000, 64_000, 85_000,
+                        100_000, 120_000, 140_000, 165_000, 195_000, 225_000, 265_000, 305_000,
+                        355_000,
+                    ],
+                )
+                .with_level_field("LEVEL"),
+            ),
+            RuleSystemVariant::Pathfinder2e => {
+                // 1,000 XP a level; tracked as a running total
+                let thresholds: Vec<u32> = (0..20).map(|level| level * 1_000).collect();
+                Some(
+                    ProgressionTable::from_thresholds(AdvancementMode::Experience, &thresholds)
+                        .with_level_field("LEVEL"),
+                )
+            }
+            RuleSystemVariant::FateCore => {
+                // Each major milestone raises the character's standing
+                let thresholds: Vec<u32> = (0..10).collect();
+                Some(ProgressionTable::from_thresholds(AdvancementMode::Milestone, &thresholds))
+            }
+            RuleSystemVariant::CallOfCthulhu7e
+            | RuleSystemVariant::RuneQuest
+            | RuleSystemVariant::GenericD100
+            | RuleSystemVariant::KidsOnBikes
+            | RuleSystemVariant::PoweredByApocalypse
+            | RuleSystemVariant::Custom(_) => None,
+        }
+    }
+
+    /// How turn order is decided in combat under this system
+    ///
+    /// Percentile systems act in order of Dexterity; the rest roll their
+    /// usual dice.
+    pub fn initiative(&self) -> InitiativeRule {
+        match &self.variant {
+            RuleSystemVariant::Dnd5e | RuleSystemVariant::GenericD20 => {
+                InitiativeRule::rolled(Some("DEX"), true)
+            }
+            // Perception, which rests on Wisdom
+            RuleSystemVariant::Pathfinder2e => InitiativeRule::rolled(Some("WIS"), true),
+            RuleSystemVariant::CallOfCthulhu7e
+            | RuleSystemVariant::RuneQuest
+            | RuleSystemVariant::GenericD100 => InitiativeRule::stat_order("DEX"),
+            RuleSystemVariant::FateCore => InitiativeRule::rolled(Some("QCK"), false),
+            RuleSystemVariant::KidsOnBikes | RuleSystemVariant::PoweredByApocalypse => {
+                InitiativeRule::rolled(None, false)
+            }
+            RuleSystemVariant::Custom(_) => {
+                let dex = self
+                    .stat_definitions
+                    .iter()
+                    .any(|stat| stat.abbreviation.eq_ignore_ascii_case("DEX"));
+                InitiativeRule::rolled(
+                    dex.then_some("DEX"),
+                    self.system_type == RuleSystemType::D20,
+                )
+            }
+        }
+    }
+
+    /// How diagonal steps on a grid map are counted under this system
+    pub fn diagonal_rule(&self) -> DiagonalRule {
+        match &self.variant {
+            RuleSystemVariant::Pathfinder2e => DiagonalRule::Alternating,
+            _ => DiagonalRule::Uniform,
         }
     }
 }
@@ -346,6 +599,267 @@ pub enum SuccessComparison {
     Narrative,
 }
 
+/// How a roll is graded into degrees of success
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResolutionBands {
+    /// Meet or beat a DC (or roll under a percentage); natural 20s and 1s
+    /// are criticals when the challenge defines them
+    #[default]
+    Standard,
+    /// Four degrees of success: beat the DC by `critical_margin` for a critical
+    /// success, miss it by as much for a critical failure. A natural 20 or 1
+    /// moves the result one degree (Pathfinder 2e)
+    Degrees { critical_margin: i32 },
+    /// Roll under a percentage: 01 is a critical, a fifth of the target an
+    /// extreme success, half a hard one. 96+ fumbles when the target is under
+    /// 50, otherwise only 100 does (Call of Cthulhu)
+    RollUnder,
+    /// Fixed bands on the total regardless of difficulty: `success_at` or more
+    /// succeeds, `partial_at` or more succeeds at a cost (PbtA 10+/7-9/6-)
+    Bands { partial_at: i32, success_at: i32 },
+    /// Shifts over the opposition on the Fate ladder
+    Shifts,
+}
+
+/// How a tied opposed check is settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpposedTieBreak {
+    /// The situation stays as it was: the defender wins (D&D 5e)
+    #[default]
+    Defender,
+    /// Meeting the opposition is enough: the acting side wins (Pathfinder 2e)
+    Initiator,
+    /// The higher skill wins, then the defender (Call of Cthulhu)
+    HigherSkill,
+    /// Neither side wins outright and the partial outcome applies (Fate)
+    Partial,
+}
+
+/// How combatants' places in the turn order are decided
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitiativeRule {
+    /// Whether combatants roll the system's dice; otherwise they act in
+    /// order of their stat
+    pub rolled: bool,
+    /// Stat added to the roll (or ordered by), by abbreviation
+    pub stat: Option<String>,
+    /// Whether the stat is an ability score that adds its d20 modifier
+    pub ability_modifier: bool,
+}
+
+impl InitiativeRule {
+    /// Name of the sheet field or stat that holds a ready-made initiative
+    /// bonus and is used ahead of `stat`
+    pub const INITIATIVE_FIELD: &'static str = "INITIATIVE";
+
+    fn rolled(stat: Option<&str>, ability_modifier: bool) -> Self {
+        Self {
+            rolled: true,
+            stat: stat.map(str::to_string),
+            ability_modifier,
+        }
+    }
+
+    fn stat_order(stat: &str) -> Self {
+        Self {
+            rolled: false,
+            stat: Some(stat.to_string()),
+            ability_modifier: false,
+        }
+    }
+
+    /// The bonus a combatant adds to initiative (or its place in stat
+    /// order), looking values up by field or stat name
+    pub fn modifier(&self, lookup: impl Fn(&str) -> Option<i32>) -> i32 {
+        if let Some(bonus) = lookup(Self::INITIATIVE_FIELD) {
+            return bonus;
+        }
+        match self.stat.as_deref().and_then(lookup) {
+            Some(score) if self.ability_modifier => (score - 10).div_euclid(2),
+            Some(value) => value,
+            None => 0,
+        }
+    }
+}
+
+/// How diagonal steps on a grid map are counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagonalRule {
+    /// No diagonal steps
+    Forbidden,
+    /// A diagonal step costs the same as a straight one (5-5-5, D&D 5e)
+    #[default]
+    Uniform,
+    /// Every second diagonal step costs double (5-10-5, Pathfinder 2e)
+    Alternating,
+}
+
+/// How exposed a character is when acting (Blades in the Dark style)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionPosition {
+    Controlled,
+    Risky,
+    Desperate,
+}
+
+impl ActionPosition {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Controlled => "controlled",
+            Self::Risky => "risky",
+            Self::Desperate => "desperate",
+        }
+    }
+}
+
+/// How much a successful action achieves (Blades in the Dark style)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionEffect {
+    Limited,
+    Standard,
+    Great,
+}
+
+impl ActionEffect {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Limited => "limited",
+            Self::Standard => "standard",
+            Self::Great => "great",
+        }
+    }
+}
+
+/// The concrete difficulty a descriptive difficulty stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptorTarget {
+    /// Meet or beat a DC (or the number on a stat die)
+    Dc(u32),
+    /// Roll at or under a percentage
+    Percentage(u32),
+    /// Beat an opposition on the Fate ladder
+    Ladder(i32),
+    /// The roll is graded by fixed bands; the descriptor only sets the stakes
+    PositionEffect { position: ActionPosition, effect: ActionEffect },
+}
+
+impl DescriptorTarget {
+    /// Get a human-readable description (e.g. "DC 15", "Good (+3)")
+    pub fn display(&self) -> String {
+        match self {
+            Self::Dc(dc) => format!("DC {}", dc),
+            Self::Percentage(p) => format!("{}%", p),
+            Self::Ladder(rung) => FateLadder(*rung).display(),
+            Self::PositionEffect { position, effect } => format!(
+                "{} position, {} effect",
+                position.display_name(),
+                effect.display_name()
+            ),
+        }
+    }
+}
+
+/// What each descriptive difficulty maps to under a rule system
+///
+/// Looked up through `DifficultyDescriptor::target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorDifficulties {
+    pub trivial: DescriptorTarget,
+    pub easy: DescriptorTarget,
+    pub routine: DescriptorTarget,
+    pub moderate: DescriptorTarget,
+    pub challenging: DescriptorTarget,
+    pub hard: DescriptorTarget,
+    pub very_hard: DescriptorTarget,
+    pub extreme: DescriptorTarget,
+    pub impossible: DescriptorTarget,
+    pub risky: DescriptorTarget,
+    pub desperate: DescriptorTarget,
+}
+
+impl Default for DescriptorDifficulties {
+    fn default() -> Self {
+        Self::d20()
+    }
+}
+
+impl DescriptorDifficulties {
+    /// The preset mapping for a rule system variant
+    pub fn for_variant(variant: &RuleSystemVariant) -> Self {
+        RuleSystemConfig::from_variant(variant.clone()).descriptor_difficulties
+    }
+
+    /// D&D-style DCs, from 5 (trivial) to 30 (nearly impossible)
+    pub fn d20() -> Self {
+        Self::dcs([5, 10, 12, 15, 17, 20, 25, 28, 30, 15, 20])
+    }
+
+    /// Pathfinder 2e DCs, which run higher to match proficiency bonuses
+    pub fn pathfinder() -> Self {
+        Self::dcs([10, 13, 15, 18, 20, 25, 30, 35, 40, 20, 25])
+    }
+
+    /// Target numbers for a single stat die (d4-d20)
+    pub fn stat_die() -> Self {
+        Self::dcs([2, 4, 5, 7, 9, 11, 13, 16, 20, 9, 13])
+    }
+
+    /// Percentile targets to roll under
+    pub fn percentile() -> Self {
+        Self::from_targets([95, 80, 65, 50, 40, 25, 15, 10, 1, 40, 20].map(DescriptorTarget::Percentage))
+    }
+
+    /// Opposition on the Fate ladder, from Mediocre (+0) to Legendary (+8)
+    pub fn fate_ladder() -> Self {
+        Self::from_targets([0, 1, 1, 2, 3, 4, 5, 6, 8, 3, 5].map(DescriptorTarget::Ladder))
+    }
+
+    /// ActionPosition and effect for games graded by fixed bands (PbtA)
+    pub fn position_effect() -> Self {
+        use ActionEffect::*;
+        use ActionPosition::*;
+        Self::from_targets(
+            [
+                (Controlled, Great),
+                (Controlled, Standard),
+                (Controlled, Standard),
+                (Risky, Standard),
+                (Risky, Standard),
+                (Risky, Limited),
+                (Desperate, Standard),
+                (Desperate, Limited),
+                (Desperate, Limited),
+                (Risky, Standard),
+                (Desperate, Standard),
+            ]
+            .map(|(position, effect)| DescriptorTarget::PositionEffect { position, effect }),
+        )
+    }
+
+    fn dcs(dcs: [u32; 11]) -> Self {
+        Self::from_targets(dcs.map(DescriptorTarget::Dc))
+    }
+
+    /// Build a mapping from targets listed in descriptor order, Trivial to Desperate
+    fn from_targets(targets: [DescriptorTarget; 11]) -> Self {
+        let [trivial, easy, routine, moderate, challenging, hard, very_hard, extreme, impossible, risky, desperate] =
+            targets;
+        Self {
+            trivial,
+            easy,
+            routine,
+            moderate,
+            challenging,
+            hard,
+            very_hard,
+            extreme,
+            impossible,
+            risky,
+            desperate,
+        }
+    }
+}
+
 /// Definition of a character stat
 #[derive(Debug, Clone)]
 pub struct StatDefinition {
@@ -381,10 +895,18 @@ pub enum DiceSystem {
     D20,
     /// Percentile system (Call of Cthulhu)
     D100,
-    /// Dice pool system (World of Darkness)
-    DicePool { die_type: u8, success_threshold: u8 },
+    /// Dice pool system (World of Darkness): each die meeting
+    /// `success_threshold` is a success
+    DicePool {
+        die_type: u8,
+        success_threshold: u8,
+        /// Each 1 rolled cancels a success; no successes plus a 1 is a botch
+        ones_cancel: bool,
+        /// "N-again": dice showing this value or higher are rolled again (10-again)
+        again_threshold: Option<u8>,
+    },
     /// FATE/Fudge dice
     Fate,
-    /// Custom dice expression
+    /// Dice expression such as "2d10+1d6" (older worlds may hold a free-text label)
     Custom(String),
 }
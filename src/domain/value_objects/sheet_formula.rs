@@ -0,0 +1,516 @@
+//! Formulas for derived character sheet fields
+//!
+//! A formula is plain arithmetic over other fields of the same sheet:
+//!
+//! - Field IDs such as `STR` or `DEX_MOD`; IDs that are not simple words go in
+//!   braces, e.g. `{custom_5f0c-…}`
+//! - Numbers, `+ - * / %` and parentheses
+//! - `floor`, `ceil`, `round`, `abs`, `min` and `max`
+//! - Table lookups: `lookup(LEVEL, 1: 2, 5: 3, 9: 4, 13: 5, 17: 6)` gives the
+//!   value of the highest key not above the input (the first value when the
+//!   input is below every key)
+//!
+//! Formulas are evaluated with decimals, so `floor((STR - 10) / 2)` works as
+//! written; the sheet stores the result rounded down.
+
+use std::fmt;
+
+/// Error when parsing or evaluating a sheet formula
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormulaError {
+    /// The formula string is empty
+    Empty,
+    /// Invalid format - unexpected character, unbalanced parentheses, etc.
+    InvalidFormat(String),
+    /// A function the formula language does not provide
+    UnknownFunction(String),
+    /// A function was called with the wrong number of arguments
+    WrongArgumentCount(String),
+    /// The formula divided by zero
+    DivisionByZero,
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty formula"),
+            Self::InvalidFormat(s) => write!(f, "Invalid formula: {}", s),
+            Self::UnknownFunction(name) => write!(f, "Unknown function '{}'", name),
+            Self::WrongArgumentCount(name) => {
+                write!(f, "Wrong number of arguments to '{}'", name)
+            }
+            Self::DivisionByZero => write!(f, "Division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for FormulaError {}
+
+/// A parsed derived-field formula
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetFormula {
+    source: String,
+    expr: FormulaExpr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FormulaExpr {
+    Number(f64),
+    Field(String),
+    Negate(Box<FormulaExpr>),
+    Binary {
+        op: FormulaOp,
+        left: Box<FormulaExpr>,
+        right: Box<FormulaExpr>,
+    },
+    Call {
+        function: Function,
+        args: Vec<FormulaExpr>,
+    },
+    /// Threshold table: (key, value) pairs sorted by key
+    Lookup {
+        input: Box<FormulaExpr>,
+        table: Vec<(f64, FormulaExpr)>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormulaOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Floor,
+    Ceil,
+    Round,
+    Abs,
+    Min,
+    Max,
+}
+
+impl Function {
+    fn named(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "floor" => Some(Self::Floor),
+            "ceil" => Some(Self::Ceil),
+            "round" => Some(Self::Round),
+            "abs" => Some(Self::Abs),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    /// Whether the function takes any number (at least one) of arguments
+    fn is_variadic(self) -> bool {
+        matches!(self, Self::Min | Self::Max)
+    }
+}
+
+impl SheetFormula {
+    /// Parse a formula
+    pub fn parse(source: &str) -> Result<Self, FormulaError> {
+        if source.trim().is_empty() {
+            return Err(FormulaError::Empty);
+        }
+        let mut parser = Parser::new(source);
+        let expr = parser.expression()?;
+        parser.skip_whitespace();
+        if parser.peek().is_some() {
+            return Err(parser.unexpected());
+        }
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    /// Field IDs the formula reads, in order of first use
+    pub fn references(&self) -> Vec<&str> {
+        let mut fields = Vec::new();
+        self.expr.collect_fields(&mut fields);
+        fields
+    }
+
+    /// Evaluate the formula, reading field values through `value_of`
+    pub fn evaluate(&self, value_of: impl Fn(&str) -> f64) -> Result<f64, FormulaError> {
+        self.expr.evaluate(&value_of)
+    }
+}
+
+impl fmt::Display for SheetFormula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl FormulaExpr {
+    fn binary(op: FormulaOp, left: FormulaExpr, right: FormulaExpr) -> Self {
+        Self::Binary {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match self {
+            Self::Number(_) => {}
+            Self::Field(id) => {
+                if !fields.contains(&id.as_str()) {
+                    fields.push(id);
+                }
+            }
+            Self::Negate(inner) => inner.collect_fields(fields),
+            Self::Binary { left, right, .. } => {
+                left.collect_fields(fields);
+                right.collect_fields(fields);
+            }
+            Self::Call { args, .. } => args.iter().for_each(|arg| arg.collect_fields(fields)),
+            Self::Lookup { input, table } => {
+                input.collect_fields(fields);
+                table.iter().for_each(|(_, value)| value.collect_fields(fields));
+            }
+        }
+    }
+
+    fn evaluate(&self, value_of: &dyn Fn(&str) -> f64) -> Result<f64, FormulaError> {
+        match self {
+            Self::Number(n) => Ok(*n),
+            Self::Field(id) => Ok(value_of(id)),
+            Self::Negate(inner) => Ok(-inner.evaluate(value_of)?),
+            Self::Binary { op, left, right } => {
+                let left = left.evaluate(value_of)?;
+                let right = right.evaluate(value_of)?;
+                match op {
+                    FormulaOp::Add => Ok(left + right),
+                    FormulaOp::Subtract => Ok(left - right),
+                    FormulaOp::Multiply => Ok(left * right),
+                    FormulaOp::Divide | FormulaOp::Remainder if right == 0.0 => {
+                        Err(FormulaError::DivisionByZero)
+                    }
+                    FormulaOp::Divide => Ok(left / right),
+                    FormulaOp::Remainder => Ok(left.rem_euclid(right)),
+                }
+            }
+            Self::Call { function, args } => {
+                let values = args
+                    .iter()
+                    .map(|arg| arg.evaluate(value_of))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let first = values[0];
+                Ok(match function {
+                    Function::Floor => first.floor(),
+                    Function::Ceil => first.ceil(),
+                    Function::Round => first.round(),
+                    Function::Abs => first.abs(),
+                    Function::Min => values.into_iter().fold(first, f64::min),
+                    Function::Max => values.into_iter().fold(first, f64::max),
+                })
+            }
+            Self::Lookup { input, table } => {
+                let input = input.evaluate(value_of)?;
+                let row = table
+                    .iter()
+                    .rev()
+                    .find(|(key, _)| *key <= input)
+                    .unwrap_or(&table[0]);
+                row.1.evaluate(value_of)
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Parser
+// =============================================================================
+
+/// How deeply a formula may nest - parentheses, calls, negations and chained
+/// operators each add a level - before parsing gives up
+const MAX_NESTING: usize = 64;
+
+struct Parser<'a> {
+    input: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            bytes: input.as_bytes(),
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    /// Go a level deeper into the formula
+    fn nest(&mut self) -> Result<(), FormulaError> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err(FormulaError::InvalidFormat(format!(
+                "Formula nested more than {} levels deep",
+                MAX_NESTING
+            )));
+        }
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.pos).is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), FormulaError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn unexpected(&self) -> FormulaError {
+        match self.input[self.pos..].chars().next() {
+            Some(c) => FormulaError::InvalidFormat(format!(
+                "Unexpected '{}' at position {} in '{}'",
+                c,
+                self.pos + 1,
+                self.input
+            )),
+            None => FormulaError::InvalidFormat(format!(
+                "Unexpected end of formula '{}'",
+                self.input
+            )),
+        }
+    }
+
+    fn expression(&mut self) -> Result<FormulaExpr, FormulaError> {
+        let depth = self.depth;
+        let mut left = self.product()?;
+        loop {
+            let op = if self.eat(b'+') {
+                FormulaOp::Add
+            } else if self.eat(b'-') {
+                FormulaOp::Subtract
+            } else {
+                self.depth = depth;
+                return Ok(left);
+            };
+            self.nest()?;
+            let right = self.product()?;
+            left = FormulaExpr::binary(op, left, right);
+        }
+    }
+
+    fn product(&mut self) -> Result<FormulaExpr, FormulaError> {
+        let depth = self.depth;
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat(b'*') {
+                FormulaOp::Multiply
+            } else if self.eat(b'/') {
+                FormulaOp::Divide
+            } else if self.eat(b'%') {
+                FormulaOp::Remainder
+            } else {
+                self.depth = depth;
+                return Ok(left);
+            };
+            self.nest()?;
+            let right = self.unary()?;
+            left = FormulaExpr::binary(op, left, right);
+        }
+    }
+
+    fn unary(&mut self) -> Result<FormulaExpr, FormulaError> {
+        self.nest()?;
+        let expr = if self.eat(b'-') {
+            FormulaExpr::Negate(Box::new(self.unary()?))
+        } else {
+            self.primary()?
+        };
+        self.depth -= 1;
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<FormulaExpr, FormulaError> {
+        if self.eat(b'(') {
+            let inner = self.expression()?;
+            self.expect(b')')?;
+            return Ok(inner);
+        }
+        if self.eat(b'{') {
+            let start = self.pos;
+            while self.peek().is_some_and(|b| b != b'}') {
+                self.pos += 1;
+            }
+            let id = self.input[start..self.pos].trim();
+            if id.is_empty() {
+                return Err(self.unexpected());
+            }
+            self.expect(b'}')?;
+            return Ok(FormulaExpr::Field(id.to_string()));
+        }
+
+        self.skip_whitespace();
+        if self.peek().is_some_and(|b| b.is_ascii_digit() || b == b'.') {
+            return self.number().map(FormulaExpr::Number);
+        }
+        let name = self.word().ok_or_else(|| self.unexpected())?;
+        if !self.eat(b'(') {
+            return Ok(FormulaExpr::Field(name.to_string()));
+        }
+        if name.eq_ignore_ascii_case("lookup") {
+            return self.lookup();
+        }
+
+        let function =
+            Function::named(name).ok_or_else(|| FormulaError::UnknownFunction(name.to_string()))?;
+        let mut args = vec![self.expression()?];
+        while self.eat(b',') {
+            args.push(self.expression()?);
+        }
+        self.expect(b')')?;
+        if args.len() > 1 && !function.is_variadic() {
+            return Err(FormulaError::WrongArgumentCount(name.to_string()));
+        }
+        Ok(FormulaExpr::Call { function, args })
+    }
+
+    /// The rest of `lookup(input, key: value, ...)` after the opening parenthesis
+    fn lookup(&mut self) -> Result<FormulaExpr, FormulaError> {
+        let input = self.expression()?;
+        let mut table = Vec::new();
+        while self.eat(b',') {
+            let negative = self.eat(b'-');
+            self.skip_whitespace();
+            let key = self.number()?;
+            self.expect(b':')?;
+            table.push((if negative { -key } else { key }, self.expression()?));
+        }
+        self.expect(b')')?;
+        if table.is_empty() {
+            return Err(FormulaError::WrongArgumentCount("lookup".to_string()));
+        }
+        table.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Ok(FormulaExpr::Lookup {
+            input: Box::new(input),
+            table,
+        })
+    }
+
+    fn number(&mut self) -> Result<f64, FormulaError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit() || b == b'.') {
+            self.pos += 1;
+        }
+        self.input[start..self.pos]
+            .parse()
+            .map_err(|_| FormulaError::InvalidFormat(format!(
+                "Invalid number '{}' in '{}'",
+                &self.input[start..self.pos],
+                self.input
+            )))
+    }
+
+    fn word(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| b.is_ascii_alphanumeric() || b == b'_')
+        {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| &self.input[start..self.pos])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(source: &str, fields: &[(&str, f64)]) -> Result<f64, FormulaError> {
+        SheetFormula::parse(source)?.evaluate(|id| {
+            fields
+                .iter()
+                .find(|(field, _)| *field == id)
+                .map_or(0.0, |(_, value)| *value)
+        })
+    }
+
+    #[test]
+    fn test_ability_modifier_and_functions() {
+        assert_eq!(evaluate("floor((STR - 10) / 2)", &[("STR", 15.0)]), Ok(2.0));
+        assert_eq!(evaluate("floor((STR - 10) / 2)", &[("STR", 7.0)]), Ok(-2.0));
+        assert_eq!(evaluate("ceil(POW / 5)", &[("POW", 51.0)]), Ok(11.0));
+        assert_eq!(evaluate("max(1, CON_MOD + 3, -2)", &[("CON_MOD", -1.0)]), Ok(2.0));
+        assert_eq!(evaluate("min(abs(-4), 3) * 2 % 4", &[]), Ok(2.0));
+        assert_eq!(evaluate("{custom_a1-b2} + 1", &[("custom_a1-b2", 4.0)]), Ok(5.0));
+    }
+
+    #[test]
+    fn test_lookup_table() {
+        let proficiency = "lookup(LEVEL, 1: 2, 5: 3, 9: 4, 13: 5, 17: 6)";
+        assert_eq!(evaluate(proficiency, &[("LEVEL", 1.0)]), Ok(2.0));
+        assert_eq!(evaluate(proficiency, &[("LEVEL", 8.0)]), Ok(3.0));
+        assert_eq!(evaluate(proficiency, &[("LEVEL", 20.0)]), Ok(6.0));
+        assert_eq!(evaluate(proficiency, &[("LEVEL", 0.0)]), Ok(2.0));
+        assert_eq!(evaluate("lookup(X, -1: 1, 0: X * 2)", &[("X", 3.0)]), Ok(6.0));
+    }
+
+    #[test]
+    fn test_references_and_errors() {
+        let formula = SheetFormula::parse("floor((CON + SIZ) / 10) + CON").unwrap();
+        assert_eq!(formula.references(), vec!["CON", "SIZ"]);
+
+        assert_eq!(SheetFormula::parse("  "), Err(FormulaError::Empty));
+        assert!(matches!(
+            SheetFormula::parse("floor(STR"),
+            Err(FormulaError::InvalidFormat(_))
+        ));
+        assert_eq!(
+            SheetFormula::parse("sqrt(STR)"),
+            Err(FormulaError::UnknownFunction("sqrt".to_string()))
+        );
+        assert_eq!(
+            SheetFormula::parse("floor(STR, 2)"),
+            Err(FormulaError::WrongArgumentCount("floor".to_string()))
+        );
+        assert_eq!(evaluate("STR / (DEX - 10)", &[("DEX", 10.0)]), Err(FormulaError::DivisionByZero));
+
+        // Deep nesting is refused rather than overflowing the stack
+        let deep = format!("{}STR{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(matches!(SheetFormula::parse(&deep), Err(FormulaError::InvalidFormat(_))));
+        assert!(matches!(
+            SheetFormula::parse(&format!("{}1", "-".repeat(100_000))),
+            Err(FormulaError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            SheetFormula::parse(&vec!["1"; 100_000].join(" + ")),
+            Err(FormulaError::InvalidFormat(_))
+        ));
+        assert!(SheetFormula::parse(&format!("{}STR{}", "(".repeat(20), ")".repeat(20))).is_ok());
+    }
+}
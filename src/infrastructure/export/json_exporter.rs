@@ -24,6 +24,12 @@ pub struct WorldSnapshot {
     pub characters: Vec<CharacterData>,
     /// All locations in the world
     pub locations: Vec<LocationData>,
+    /// The world's item catalogue
+    #[serde(default)]
+    pub items: Vec<ItemData>,
+    /// What each character holds (graph edges)
+    #[serde(default)]
+    pub inventories: Vec<InventoryData>,
     /// All relationships between characters
     pub relationships: Vec<RelationshipData>,
     /// Location connections (graph edges)
@@ -102,6 +108,27 @@ pub struct LocationData {
     // They can be reconstructed from separate queries if needed for export
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemData {
+    pub id: String,
+    pub world_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub item_type: Option<String>,
+    pub is_unique: bool,
+    pub properties: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryData {
+    pub character_id: String,
+    pub item_id: String,
+    pub quantity: u32,
+    pub equipped: bool,
+    pub acquired_at: String,
+    pub acquisition_method: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegionData {
     pub id: String,
@@ -181,6 +208,21 @@ impl JsonExporter {
         // Get all locations
         let locations = self.repository.locations().list_by_world(world_id).await?;
 
+        // Get the item catalogue and what each character holds
+        let items = self.repository.items().list_by_world(world_id).await?;
+        let mut inventories = Vec::new();
+        for character in &characters {
+            let inventory = self.repository.characters().get_inventory(character.id).await?;
+            inventories.extend(inventory.into_iter().map(|held| InventoryData {
+                character_id: character.id.to_string(),
+                item_id: held.item.id.to_string(),
+                quantity: held.quantity,
+                equipped: held.equipped,
+                acquired_at: held.acquired_at.to_rfc3339(),
+                acquisition_method: held.acquisition_method.map(|m| m.to_string()),
+            }));
+        }
+
         // Get all relationships (social network)
         let social_network = self
             .repository
@@ -299,6 +341,19 @@ impl JsonExporter {
                     atmosphere: l.atmosphere,
                 })
                 .collect(),
+            items: items
+                .into_iter()
+                .map(|i| ItemData {
+                    id: i.id.to_string(),
+                    world_id: i.world_id.to_string(),
+                    name: i.name,
+                    description: i.description,
+                    item_type: i.item_type,
+                    is_unique: i.is_unique,
+                    properties: i.properties,
+                })
+                .collect(),
+            inventories,
             relationships: social_network
                 .relationships
                 .into_iter()
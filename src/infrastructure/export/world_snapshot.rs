@@ -7,9 +7,10 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
+use crate::application::dto::GridMapDto;
 use crate::application::ports::outbound::{
-    CharacterData, ExportOptions, LocationData, PlayerWorldSnapshot, SceneData, WorldData,
-    WorldExporterPort,
+    CharacterData, ExportOptions, ItemData, LocationData, PlayerWorldSnapshot, SceneData,
+    WorldData, WorldExporterPort,
 };
 use crate::domain::value_objects::{SceneId, WorldId};
 use crate::infrastructure::persistence::Neo4jRepository;
@@ -36,6 +37,9 @@ pub async fn load_world_snapshot(
     // Load all characters
     let characters = repository.characters().list_by_world(world_id).await?;
 
+    // Load the item catalogue
+    let items = repository.items().list_by_world(world_id).await?;
+
     // Load all acts and their scenes
     let acts = repository.worlds().get_acts(world_id).await?;
     let mut scenes = Vec::new();
@@ -54,17 +58,23 @@ pub async fn load_world_snapshot(
         updated_at: world.updated_at.to_rfc3339(),
     };
 
-    let location_data: Vec<LocationData> = locations
-        .into_iter()
-        .map(|l| LocationData {
+    let mut location_data = Vec::with_capacity(locations.len());
+    for l in locations {
+        // Include the tactical map so clients can render its tiles
+        let grid_map = match repository.locations().get_grid_map_id(l.id).await? {
+            Some(grid_map_id) => repository.grid_maps().get(grid_map_id).await?,
+            None => None,
+        };
+        location_data.push(LocationData {
             id: l.id.to_string(),
             name: l.name,
             description: l.description,
             location_type: format!("{:?}", l.location_type),
             backdrop_asset: l.backdrop_asset,
             atmosphere: l.atmosphere,
-        })
-        .collect();
+            grid_map: grid_map.map(GridMapDto::from),
+        });
+    }
 
     let character_data: Vec<CharacterData> = characters
         .into_iter()
@@ -80,6 +90,18 @@ pub async fn load_world_snapshot(
         })
         .collect();
 
+    let item_data: Vec<ItemData> = items
+        .into_iter()
+        .map(|i| ItemData {
+            id: i.id.to_string(),
+            name: i.name,
+            description: i.description,
+            item_type: i.item_type,
+            is_unique: i.is_unique,
+            properties: i.properties,
+        })
+        .collect();
+
     let scene_data: Vec<SceneData> = scenes
         .iter()
         .map(|s| SceneData {
@@ -118,6 +140,7 @@ pub async fn load_world_snapshot(
         world: world_data,
         locations: location_data,
         characters: character_data,
+        items: item_data,
         scenes: scene_data,
         current_scene,
     })
@@ -267,6 +290,7 @@ mod tests {
                 location_type: "Exterior".to_string(),
                 backdrop_asset: Some("town_square.png".to_string()),
                 atmosphere: Some("Bustling marketplace atmosphere".to_string()),
+                grid_map: None,
             }],
             characters: vec![CharacterData {
                 id: "char-1".to_string(),
@@ -278,6 +302,14 @@ mod tests {
                 is_alive: true,
                 is_active: true,
             }],
+            items: vec![ItemData {
+                id: "item-1".to_string(),
+                name: "Staff of Power".to_string(),
+                description: None,
+                item_type: Some("Weapon".to_string()),
+                is_unique: true,
+                properties: None,
+            }],
             scenes: vec![],
             current_scene: None,
         };
@@ -286,11 +318,13 @@ mod tests {
         assert!(json.contains("Fantasy Realm"));
         assert!(json.contains("Town Square"));
         assert!(json.contains("Gandalf"));
+        assert!(json.contains("Staff of Power"));
 
         let deserialized: PlayerWorldSnapshot =
             serde_json::from_str(&json).expect("deserialization should succeed");
         assert_eq!(deserialized.world.name, "Fantasy Realm");
         assert_eq!(deserialized.locations.len(), 1);
         assert_eq!(deserialized.characters.len(), 1);
+        assert_eq!(deserialized.items.len(), 1);
     }
 }
@@ -0,0 +1,141 @@
+//! Advancement API routes
+//!
+//! Endpoints for awarding experience and milestones, checking where
+//! characters stand on their world's progression and approving level-ups.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    AdvancementDto, AwardExperienceRequestDto, LevelUpDto, ProgressionTableDto,
+};
+use crate::application::services::{AdvancementError, PlayerCharacterService};
+use crate::domain::value_objects::{PlayerCharacterId, WorldId};
+use crate::infrastructure::state::AppState;
+
+/// Map an advancement service error, reporting refused operations as such
+fn advancement_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<AdvancementError>() {
+        Some(AdvancementError::NotEligible(_)) => (StatusCode::CONFLICT, e.to_string()),
+        Some(refused) => (StatusCode::UNPROCESSABLE_ENTITY, refused.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+fn parse_pc_id(pc_id: &str) -> Result<PlayerCharacterId, (StatusCode, String)> {
+    Uuid::parse_str(pc_id)
+        .map(PlayerCharacterId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid player character ID".to_string()))
+}
+
+/// Get a player character's experience, milestones and level
+pub async fn get_advancement(
+    State(state): State<Arc<AppState>>,
+    Path(pc_id): Path<String>,
+) -> Result<Json<AdvancementDto>, (StatusCode, String)> {
+    let status = state
+        .player
+        .advancement_service
+        .status(parse_pc_id(&pc_id)?)
+        .await
+        .map_err(advancement_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Player character not found".to_string()))?;
+
+    Ok(Json(AdvancementDto::from(status)))
+}
+
+/// Award experience or milestones to a player character
+pub async fn award_experience(
+    State(state): State<Arc<AppState>>,
+    Path(pc_id): Path<String>,
+    Json(req): Json<AwardExperienceRequestDto>,
+) -> Result<Json<AdvancementDto>, (StatusCode, String)> {
+    if req.amount == 0 {
+        return Err((StatusCode::BAD_REQUEST, "Amount must be at least 1".to_string()));
+    }
+
+    let status = state
+        .player
+        .advancement_service
+        .award(parse_pc_id(&pc_id)?, req.award(), req.reason)
+        .await
+        .map_err(advancement_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Player character not found".to_string()))?;
+
+    Ok(Json(AdvancementDto::from(status)))
+}
+
+/// Approve the levels a player character has earned
+pub async fn level_up(
+    State(state): State<Arc<AppState>>,
+    Path(pc_id): Path<String>,
+) -> Result<Json<LevelUpDto>, (StatusCode, String)> {
+    let pc_id = parse_pc_id(&pc_id)?;
+    let pc = state
+        .player
+        .player_character_service
+        .get_pc(pc_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Player character not found".to_string()))?;
+
+    let game_time = {
+        let sessions = state.sessions.read().await;
+        sessions
+            .find_session_for_world(pc.world_id)
+            .and_then(|id| sessions.get_session(id))
+            .map(|session| session.display_game_time())
+    };
+
+    let outcome = state
+        .player
+        .advancement_service
+        .level_up(pc_id, game_time)
+        .await
+        .map_err(advancement_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Player character not found".to_string()))?;
+
+    Ok(Json(LevelUpDto::from(outcome)))
+}
+
+/// List the characters in a world with a level-up waiting for approval
+pub async fn list_pending_level_ups(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<Vec<AdvancementDto>>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let pending = state
+        .player
+        .advancement_service
+        .pending_level_ups(WorldId::from_uuid(uuid))
+        .await
+        .map_err(advancement_error)?;
+
+    Ok(Json(pending.into_iter().map(AdvancementDto::from).collect()))
+}
+
+/// Get the progression a world's characters follow
+pub async fn get_progression(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<ProgressionTableDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let table = state
+        .player
+        .advancement_service
+        .progression(WorldId::from_uuid(uuid))
+        .await
+        .map_err(|e| (StatusCode::NOT_FOUND, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "World has no progression".to_string()))?;
+
+    Ok(Json(ProgressionTableDto::from(table)))
+}
@@ -8,9 +8,10 @@
 //! - `REQUIRES_SKILL` -> Skill required for this challenge
 //! - `TIED_TO_SCENE` -> Scene this challenge appears in
 //! - `REQUIRES_COMPLETION_OF` -> Prerequisite challenges
+//! - `HAS_PROGRESS` -> Progress on a complex challenge
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -18,11 +19,12 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::dto::{
-    ChallengeResponseDto, CreateChallengeRequestDto, UpdateChallengeRequestDto,
+    ChallengeOddsDto, ChallengeOddsQueryDto, ChallengeResponseDto, ComplexChallengeProgressDto,
+    CreateChallengeRequestDto, UpdateChallengeRequestDto,
 };
-use crate::application::services::{ChallengeService, WorldService};
-use crate::domain::entities::{Challenge, ChallengePrerequisite};
-use crate::domain::value_objects::{ChallengeId, SceneId, SkillId, WorldId};
+use crate::application::services::{ChallengeService, PlayerCharacterService, WorldService};
+use crate::domain::entities::{Challenge, ChallengePrerequisite, ComplexChallengeProgress};
+use crate::domain::value_objects::{ChallengeId, PlayerCharacterId, SceneId, SkillId, WorldId};
 use crate::infrastructure::state::AppState;
 
 // ============================================================================
@@ -246,6 +248,10 @@ pub async fn create_challenge(
         challenge = challenge.with_tag(tag);
     }
 
+    if let Some(settings) = req.complex_settings {
+        challenge = challenge.with_complex_settings(settings.into());
+    }
+
     // Save the challenge first
     let challenge = state
         .game
@@ -342,6 +348,9 @@ pub async fn update_challenge(
     if let Some(tags) = req.tags {
         challenge.tags = tags;
     }
+    if let Some(complex_settings) = req.complex_settings {
+        challenge.complex_settings = Some(complex_settings.into());
+    }
 
     // Save node property updates
     let challenge = state
@@ -495,3 +504,93 @@ pub async fn set_active(
 
     Ok(StatusCode::OK)
 }
+
+/// Get the odds of each outcome for a challenge, optionally for a specific player character
+pub async fn get_challenge_odds(
+    State(state): State<Arc<AppState>>,
+    Path(challenge_id): Path<String>,
+    Query(query): Query<ChallengeOddsQueryDto>,
+) -> Result<Json<ChallengeOddsDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&challenge_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid challenge ID".to_string()))?;
+    let challenge_id = ChallengeId::from_uuid(uuid);
+
+    let pc_id = match query.pc_id {
+        Some(pc_id) => {
+            let uuid = Uuid::parse_str(&pc_id).map_err(|_| {
+                (StatusCode::BAD_REQUEST, "Invalid player character ID".to_string())
+            })?;
+            let pc_id = PlayerCharacterId::from_uuid(uuid);
+            state
+                .player
+                .player_character_service
+                .get_pc(pc_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .ok_or_else(|| (StatusCode::NOT_FOUND, "Player character not found".to_string()))?;
+            Some(pc_id)
+        }
+        None => None,
+    };
+
+    let odds = state
+        .game
+        .challenge_resolution_service
+        .get_challenge_odds(challenge_id, pc_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Challenge not found".to_string()))?;
+
+    Ok(Json(odds))
+}
+
+/// Get the progress made on a complex challenge
+pub async fn get_complex_progress(
+    State(state): State<Arc<AppState>>,
+    Path(challenge_id): Path<String>,
+) -> Result<Json<ComplexChallengeProgressDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&challenge_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid challenge ID".to_string()))?;
+    let challenge_id = ChallengeId::from_uuid(uuid);
+
+    let challenge = state
+        .game
+        .challenge_service
+        .get_challenge(challenge_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Challenge not found".to_string()))?;
+    let settings = challenge
+        .complex()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "Not a complex challenge".to_string()))?;
+
+    // A challenge nobody has rolled for yet has no progress stored
+    let progress = state
+        .game
+        .challenge_service
+        .get_complex_progress(challenge_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .unwrap_or_else(|| ComplexChallengeProgress::new(challenge_id));
+
+    Ok(Json(ComplexChallengeProgressDto::new(&challenge, &settings, progress)))
+}
+
+/// Clear a complex challenge's progress so it can be attempted again
+pub async fn reset_complex_progress(
+    State(state): State<Arc<AppState>>,
+    Path(challenge_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&challenge_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid challenge ID".to_string()))?;
+    let challenge_id = ChallengeId::from_uuid(uuid);
+
+    state
+        .game
+        .challenge_service
+        .clear_complex_progress(challenge_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
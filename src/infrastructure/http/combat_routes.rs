@@ -0,0 +1,187 @@
+//! Combat API routes
+//!
+//! Endpoints for starting a fight in a session, rolling initiative, moving
+//! through turns, tracking hit points and ending the fight. Players usually
+//! drive their own turns over the WebSocket; these are for the DM's tools.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    DamageCombatantRequestDto, EncounterDto, EndCombatRequestDto, InitiativeRequestDto,
+    StartCombatRequestDto,
+};
+use crate::application::services::{CombatError, CombatUpdate};
+use crate::domain::value_objects::SessionId;
+use crate::infrastructure::state::AppState;
+use crate::infrastructure::websocket_helpers::follow_combat_update;
+
+/// Map a combat service error, reporting refused operations as such
+fn combat_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<CombatError>() {
+        Some(CombatError::NotInCombat(_) | CombatError::SessionNotFound(_)) => {
+            (StatusCode::NOT_FOUND, e.to_string())
+        }
+        Some(CombatError::AlreadyInCombat(_) | CombatError::Encounter(_)) => {
+            (StatusCode::CONFLICT, e.to_string())
+        }
+        Some(refused) => (StatusCode::UNPROCESSABLE_ENTITY, refused.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+fn parse_session_id(session_id: &str) -> Result<SessionId, (StatusCode, String)> {
+    Uuid::parse_str(session_id)
+        .map(SessionId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid session ID".to_string()))
+}
+
+/// The session's displayed game time, for story events
+async fn game_time(state: &AppState, session_id: SessionId) -> Option<String> {
+    let sessions = state.sessions.read().await;
+    sessions
+        .get_session(session_id)
+        .map(|session| session.display_game_time())
+}
+
+/// Advance the clock and queue NPC turns as the fight moves on
+async fn followed(
+    state: &AppState,
+    session_id: SessionId,
+    update: CombatUpdate,
+) -> Json<EncounterDto> {
+    follow_combat_update(state, session_id, &update).await;
+    Json(EncounterDto::from(&update.encounter))
+}
+
+/// Get the fight running in a session
+pub async fn get_combat(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> Result<Json<EncounterDto>, (StatusCode, String)> {
+    let session_id = parse_session_id(&session_id)?;
+    let encounter = state
+        .game
+        .combat_service
+        .encounter(session_id)
+        .await
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Session is not in combat".to_string()))?;
+
+    Ok(Json(EncounterDto::from(&encounter)))
+}
+
+/// Start a fight in a session
+pub async fn start_combat(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Json(req): Json<StartCombatRequestDto>,
+) -> Result<(StatusCode, Json<EncounterDto>), (StatusCode, String)> {
+    let session_id = parse_session_id(&session_id)?;
+    let start = req
+        .into_start()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid ID format".to_string()))?;
+    let game_time = game_time(&state, session_id).await;
+
+    let update = state
+        .game
+        .combat_service
+        .start(session_id, start, game_time)
+        .await
+        .map_err(combat_error)?;
+
+    Ok((StatusCode::CREATED, followed(&state, session_id, update).await))
+}
+
+/// Roll initiative, or record a total rolled at the table
+pub async fn roll_initiative(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Json(req): Json<InitiativeRequestDto>,
+) -> Result<Json<EncounterDto>, (StatusCode, String)> {
+    let session_id = parse_session_id(&session_id)?;
+    let game_time = game_time(&state, session_id).await;
+    let combat = &state.game.combat_service;
+
+    let update = match (req.total, req.combatant_id) {
+        (Some(total), Some(combatant_id)) => {
+            combat
+                .set_initiative(session_id, &combatant_id, total, game_time)
+                .await
+        }
+        (Some(_), None) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "A rolled total needs a combatant".to_string(),
+            ));
+        }
+        (None, combatant_id) => {
+            combat
+                .roll_initiative(session_id, combatant_id.as_deref(), "DM".to_string(), game_time)
+                .await
+        }
+    }
+    .map_err(combat_error)?;
+
+    Ok(followed(&state, session_id, update).await)
+}
+
+/// End the current turn and pass to the next combatant
+pub async fn next_turn(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> Result<Json<EncounterDto>, (StatusCode, String)> {
+    let session_id = parse_session_id(&session_id)?;
+    let game_time = game_time(&state, session_id).await;
+
+    let update = state
+        .game
+        .combat_service
+        .next_turn(session_id, game_time)
+        .await
+        .map_err(combat_error)?;
+
+    Ok(followed(&state, session_id, update).await)
+}
+
+/// Damage, or with a negative amount heal, a combatant
+pub async fn damage_combatant(
+    State(state): State<Arc<AppState>>,
+    Path((session_id, combatant_id)): Path<(String, String)>,
+    Json(req): Json<DamageCombatantRequestDto>,
+) -> Result<Json<EncounterDto>, (StatusCode, String)> {
+    let session_id = parse_session_id(&session_id)?;
+    let game_time = game_time(&state, session_id).await;
+
+    let update = state
+        .game
+        .combat_service
+        .damage(session_id, &combatant_id, req.amount, req.reason, game_time)
+        .await
+        .map_err(combat_error)?;
+
+    Ok(followed(&state, session_id, update).await)
+}
+
+/// End the fight in a session
+pub async fn end_combat(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Json(req): Json<EndCombatRequestDto>,
+) -> Result<Json<EncounterDto>, (StatusCode, String)> {
+    let session_id = parse_session_id(&session_id)?;
+    let game_time = game_time(&state, session_id).await;
+
+    let update = state
+        .game
+        .combat_service
+        .end(session_id, req.outcome.map(Into::into), game_time)
+        .await
+        .map_err(combat_error)?;
+
+    Ok(Json(EncounterDto::from(&update.encounter)))
+}
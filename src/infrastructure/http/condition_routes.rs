@@ -0,0 +1,93 @@
+//! Condition API routes
+//!
+//! Endpoints for the conditions on NPCs and player characters. Both kinds of
+//! character share these handlers; the ID decides which one is meant.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+
+use crate::application::dto::{ActiveConditionDto, ApplyConditionRequestDto};
+use crate::infrastructure::state::AppState;
+
+fn not_found() -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, "Character not found".to_string())
+}
+
+/// List the conditions on a character
+pub async fn list_conditions(
+    State(state): State<Arc<AppState>>,
+    Path(character_id): Path<String>,
+) -> Result<Json<Vec<ActiveConditionDto>>, (StatusCode, String)> {
+    let conditions = state
+        .game
+        .condition_service
+        .conditions(&character_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(not_found)?;
+
+    Ok(Json(conditions.into_iter().map(ActiveConditionDto::from).collect()))
+}
+
+/// Apply a condition to a character
+///
+/// Timed conditions start at the game time of the world's running session,
+/// or at the current time if no session is running.
+pub async fn apply_condition(
+    State(state): State<Arc<AppState>>,
+    Path(character_id): Path<String>,
+    Json(req): Json<ApplyConditionRequestDto>,
+) -> Result<(StatusCode, Json<ActiveConditionDto>), (StatusCode, String)> {
+    if req.name.trim().is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Condition name must not be empty".to_string(),
+        ));
+    }
+
+    let service = &state.game.condition_service;
+    let world_id = service
+        .world_of(&character_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(not_found)?;
+    let applied_at = {
+        let sessions = state.sessions.read().await;
+        sessions
+            .find_session_for_world(world_id)
+            .and_then(|id| sessions.get_session(id))
+            .map(|session| session.game_time().current)
+            .unwrap_or_else(chrono::Utc::now)
+    };
+
+    let condition = req.into_condition(applied_at);
+    let applied = service
+        .apply_condition(&character_id, condition.clone())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !applied {
+        return Err(not_found());
+    }
+
+    Ok((StatusCode::CREATED, Json(ActiveConditionDto::from(condition))))
+}
+
+/// Remove a condition from a character by name
+pub async fn remove_condition(
+    State(state): State<Arc<AppState>>,
+    Path((character_id, name)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .game
+        .condition_service
+        .remove_condition(&character_id, &name)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Condition '{}' not found", name)))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,470 @@
+//! Grid map API routes
+//!
+//! Endpoints for creating, generating and editing tactical grid maps,
+//! importing them from Universal VTT and Tiled files and exporting them
+//! back to Tiled, patching individual tiles, giving a location its map and finding paths,
+//! movement ranges and cover across it.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    tiles_from_dto, CoverRequestDto, CoverResponseDto, CreateGridMapRequestDto,
+    GenerateGridMapRequestDto, GeneratedGridMapDto, GridMapDto, GridMapSummaryDto, GridPathDto,
+    ImportGridMapQueryDto, ImportedGridMapDto, MovementRangeRequestDto, PatchTilesRequestDto,
+    PathRequestDto, ReachableTileDto, SetGridMapRequestDto, UpdateGridMapRequestDto,
+};
+use crate::application::services::{
+    CreateGridMapRequest, GenerateGridMapRequest, GridMapService, ImportGridMapRequest,
+    LocationService, UpdateGridMapRequest,
+};
+use crate::domain::entities::{GridMap, GridMapError, ImportedMap, MapGenerator};
+use crate::domain::value_objects::{GridMapId, LocationId, WorldId};
+use crate::infrastructure::map_formats::{parse_tiled, parse_uvtt, to_tiled, MapFormatError, TiledMap};
+use crate::infrastructure::state::AppState;
+
+/// Map a grid map service error, reporting changes that do not fit the map
+/// as such
+fn grid_map_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<GridMapError>() {
+        Some(refused) => (StatusCode::UNPROCESSABLE_ENTITY, refused.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+fn parse_grid_map_id(grid_map_id: &str) -> Result<GridMapId, (StatusCode, String)> {
+    Uuid::parse_str(grid_map_id)
+        .map(GridMapId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid grid map ID".to_string()))
+}
+
+fn parse_location_id(location_id: &str) -> Result<LocationId, (StatusCode, String)> {
+    Uuid::parse_str(location_id)
+        .map(LocationId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid location ID".to_string()))
+}
+
+/// Check a location exists in the world, or 404/403
+async fn check_location(
+    state: &AppState,
+    world_id: WorldId,
+    location_id: LocationId,
+) -> Result<(), (StatusCode, String)> {
+    let location = state
+        .core
+        .location_service
+        .get_location(location_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Location not found".to_string()))?;
+    if location.world_id != world_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Location does not belong to the world".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Map a map file that cannot be read: malformed files are bad requests,
+/// well-formed ones the grid cannot hold are unprocessable
+fn map_format_error(e: MapFormatError) -> (StatusCode, String) {
+    match e {
+        MapFormatError::Json(_) => (StatusCode::BAD_REQUEST, e.to_string()),
+        _ => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+    }
+}
+
+/// Load a grid map, or 404
+async fn load_grid_map(state: &AppState, id: GridMapId) -> Result<GridMap, (StatusCode, String)> {
+    state
+        .core
+        .grid_map_service
+        .get_grid_map(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Grid map not found".to_string()))
+}
+
+/// List the grid maps in a world, without their tiles
+pub async fn list_grid_maps(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<Vec<GridMapSummaryDto>>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let grid_maps = state
+        .core
+        .grid_map_service
+        .list_grid_maps(WorldId::from_uuid(uuid))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(grid_maps.into_iter().map(GridMapSummaryDto::from).collect()))
+}
+
+/// Create a grid map in a world
+pub async fn create_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Json(req): Json<CreateGridMapRequestDto>,
+) -> Result<(StatusCode, Json<GridMapDto>), (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let service_request = CreateGridMapRequest {
+        name: req.name,
+        width: req.width,
+        height: req.height,
+        tilesheet_asset: req.tilesheet_asset,
+        tile_size: req.tile_size,
+        tiles: req.tiles.map(tiles_from_dto),
+    };
+
+    let grid_map = state
+        .core
+        .grid_map_service
+        .create_grid_map(WorldId::from_uuid(uuid), service_request)
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok((StatusCode::CREATED, Json(GridMapDto::from(grid_map))))
+}
+
+/// Generate a grid map in a world from a seed
+///
+/// Given a location, the map becomes its tactical map and, if asked, each
+/// room of a dungeon one of its regions. The seed is returned so the same
+/// map can be generated again.
+pub async fn generate_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Json(req): Json<GenerateGridMapRequestDto>,
+) -> Result<(StatusCode, Json<GeneratedGridMapDto>), (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+    let world_id = WorldId::from_uuid(uuid);
+    let seed = match req.seed {
+        Some(seed) => seed
+            .parse::<u64>()
+            .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid seed".to_string()))?,
+        None => rand::random(),
+    };
+    let location_id = req.location_id.as_deref().map(parse_location_id).transpose()?;
+
+    match location_id {
+        Some(location_id) => check_location(&state, world_id, location_id).await?,
+        None if req.rooms_as_regions => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Rooms can only become regions of a location".to_string(),
+            ));
+        }
+        None => {}
+    }
+
+    let service_request = GenerateGridMapRequest {
+        name: req.name,
+        tilesheet_asset: req.tilesheet_asset,
+        tile_size: req.tile_size,
+        generator: MapGenerator::new(req.width, req.height, seed, req.style.into())
+            .with_palette(req.palette.into()),
+        location_id,
+        rooms_as_regions: req.rooms_as_regions,
+    };
+
+    let generated = state
+        .core
+        .grid_map_service
+        .generate_grid_map(world_id, service_request)
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok((StatusCode::CREATED, Json(GeneratedGridMapDto::new(seed, generated))))
+}
+
+/// Import a map file read by `parse` into a world
+async fn import_grid_map(
+    state: &AppState,
+    world_id: &str,
+    query: ImportGridMapQueryDto,
+    body: &str,
+    parse: fn(&str) -> Result<ImportedMap, MapFormatError>,
+) -> Result<(StatusCode, Json<ImportedGridMapDto>), (StatusCode, String)> {
+    let uuid = Uuid::parse_str(world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+    let world_id = WorldId::from_uuid(uuid);
+    let location_id = query.location_id.as_deref().map(parse_location_id).transpose()?;
+    if let Some(location_id) = location_id {
+        check_location(state, world_id, location_id).await?;
+    }
+    let map = parse(body).map_err(map_format_error)?;
+
+    let service_request = ImportGridMapRequest {
+        name: query.name,
+        map,
+        tilesheet_asset: query.tilesheet_asset,
+        location_id,
+    };
+    let imported = state
+        .core
+        .map_import_service
+        .import(world_id, service_request)
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok((StatusCode::CREATED, Json(ImportedGridMapDto::from(imported))))
+}
+
+/// Import a Universal VTT file (`.dd2vtt`, `.uvtt`), sent as the body
+pub async fn import_uvtt_map(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Query(query): Query<ImportGridMapQueryDto>,
+    body: String,
+) -> Result<(StatusCode, Json<ImportedGridMapDto>), (StatusCode, String)> {
+    import_grid_map(&state, &world_id, query, &body, parse_uvtt).await
+}
+
+/// Import a Tiled JSON map, sent as the body
+pub async fn import_tiled_map(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Query(query): Query<ImportGridMapQueryDto>,
+    body: String,
+) -> Result<(StatusCode, Json<ImportedGridMapDto>), (StatusCode, String)> {
+    import_grid_map(&state, &world_id, query, &body, parse_tiled).await
+}
+
+/// Export a grid map as a Tiled JSON map
+pub async fn export_tiled_map(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+) -> Result<Json<TiledMap>, (StatusCode, String)> {
+    let grid_map = load_grid_map(&state, parse_grid_map_id(&grid_map_id)?).await?;
+    Ok(Json(to_tiled(&grid_map)))
+}
+
+/// Get a grid map with all its tiles
+pub async fn get_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+) -> Result<Json<GridMapDto>, (StatusCode, String)> {
+    let grid_map = load_grid_map(&state, parse_grid_map_id(&grid_map_id)?).await?;
+    Ok(Json(GridMapDto::from(grid_map)))
+}
+
+/// Update a grid map's name, size, tilesheet or tiles
+pub async fn update_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+    Json(req): Json<UpdateGridMapRequestDto>,
+) -> Result<Json<GridMapDto>, (StatusCode, String)> {
+    let grid_map_id = parse_grid_map_id(&grid_map_id)?;
+    load_grid_map(&state, grid_map_id).await?;
+
+    let service_request = UpdateGridMapRequest {
+        name: req.name,
+        width: req.width,
+        height: req.height,
+        tilesheet_asset: req.tilesheet_asset,
+        tile_size: req.tile_size,
+        tiles: req.tiles.map(tiles_from_dto),
+    };
+
+    let grid_map = state
+        .core
+        .grid_map_service
+        .update_grid_map(grid_map_id, service_request)
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok(Json(GridMapDto::from(grid_map)))
+}
+
+/// Replace some of a grid map's tiles
+///
+/// Either every tile is replaced or, if any lies off the map, none is.
+pub async fn patch_tiles(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+    Json(req): Json<PatchTilesRequestDto>,
+) -> Result<Json<GridMapDto>, (StatusCode, String)> {
+    let grid_map_id = parse_grid_map_id(&grid_map_id)?;
+    load_grid_map(&state, grid_map_id).await?;
+
+    let patches = req
+        .tiles
+        .into_iter()
+        .map(|patch| (patch.x, patch.y, patch.tile.into()))
+        .collect();
+
+    let grid_map = state
+        .core
+        .grid_map_service
+        .patch_tiles(grid_map_id, patches)
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok(Json(GridMapDto::from(grid_map)))
+}
+
+/// Delete a grid map
+pub async fn delete_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let grid_map_id = parse_grid_map_id(&grid_map_id)?;
+    load_grid_map(&state, grid_map_id).await?;
+
+    state
+        .core
+        .grid_map_service
+        .delete_grid_map(grid_map_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get a location's tactical map
+pub async fn get_location_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(location_id): Path<String>,
+) -> Result<Json<GridMapDto>, (StatusCode, String)> {
+    let grid_map = state
+        .core
+        .grid_map_service
+        .get_location_grid_map(parse_location_id(&location_id)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Location has no grid map".to_string()))?;
+
+    Ok(Json(GridMapDto::from(grid_map)))
+}
+
+/// Give a location a tactical map from its world
+pub async fn set_location_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(location_id): Path<String>,
+    Json(req): Json<SetGridMapRequestDto>,
+) -> Result<Json<GridMapDto>, (StatusCode, String)> {
+    let location_id = parse_location_id(&location_id)?;
+    let grid_map = load_grid_map(&state, parse_grid_map_id(&req.grid_map_id)?).await?;
+
+    let location = state
+        .core
+        .location_service
+        .get_location(location_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Location not found".to_string()))?;
+
+    if location.world_id != grid_map.world_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Grid map does not belong to the location's world".to_string(),
+        ));
+    }
+
+    state
+        .core
+        .location_service
+        .set_grid_map(location_id, grid_map.id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(GridMapDto::from(grid_map)))
+}
+
+/// Remove a location's tactical map; the map itself is kept
+pub async fn remove_location_grid_map(
+    State(state): State<Arc<AppState>>,
+    Path(location_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .core
+        .location_service
+        .remove_grid_map(parse_location_id(&location_id)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Find the cheapest route between two tiles; null if there is none
+pub async fn find_path(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+    Json(req): Json<PathRequestDto>,
+) -> Result<Json<Option<GridPathDto>>, (StatusCode, String)> {
+    let grid_map_id = parse_grid_map_id(&grid_map_id)?;
+    load_grid_map(&state, grid_map_id).await?;
+
+    let path = state
+        .core
+        .grid_map_service
+        .find_path(
+            grid_map_id,
+            req.from.into(),
+            req.to.into(),
+            req.diagonal_rule.map(Into::into),
+            req.occupied.into_iter().map(Into::into).collect(),
+            None,
+        )
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok(Json(path.map(GridPathDto::from)))
+}
+
+/// List the tiles reachable from a tile for a movement budget
+pub async fn movement_range(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+    Json(req): Json<MovementRangeRequestDto>,
+) -> Result<Json<Vec<ReachableTileDto>>, (StatusCode, String)> {
+    let grid_map_id = parse_grid_map_id(&grid_map_id)?;
+    load_grid_map(&state, grid_map_id).await?;
+
+    let tiles = state
+        .core
+        .grid_map_service
+        .movement_range(
+            grid_map_id,
+            req.from.into(),
+            req.movement,
+            req.diagonal_rule.map(Into::into),
+            req.occupied.into_iter().map(Into::into).collect(),
+            None,
+        )
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok(Json(tiles.into_iter().map(ReachableTileDto::from).collect()))
+}
+
+/// Whether an attacker can see a target, and the cover the target has
+pub async fn check_cover(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+    Json(req): Json<CoverRequestDto>,
+) -> Result<Json<CoverResponseDto>, (StatusCode, String)> {
+    let grid_map_id = parse_grid_map_id(&grid_map_id)?;
+    load_grid_map(&state, grid_map_id).await?;
+
+    let cover = state
+        .core
+        .grid_map_service
+        .cover(grid_map_id, req.attacker.into(), req.target.into(), None)
+        .await
+        .map_err(grid_map_error)?;
+
+    Ok(Json(CoverResponseDto::from(cover)))
+}
@@ -0,0 +1,142 @@
+//! Grid token API routes
+//!
+//! Endpoints for the DM to place, change, move and remove the tokens on a
+//! grid map. Players move their own tokens over the websocket.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    GridTokenDto, MoveTokenRequestDto, PlaceTokenRequestDto, TokenMovedDto, UpdateTokenRequestDto,
+};
+use crate::application::services::{GridMapService, TokenError, TokenMover};
+use crate::domain::entities::Facing;
+use crate::domain::value_objects::{GridMapId, GridTokenId};
+use crate::infrastructure::state::AppState;
+
+/// Map a grid token service error: unknown tokens are 404, moves and
+/// placements that do not fit the map 422
+fn token_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<TokenError>() {
+        Some(TokenError::NotFound(_)) => (StatusCode::NOT_FOUND, e.to_string()),
+        Some(TokenError::NotYours(_) | TokenError::NotYourTurn) => (StatusCode::FORBIDDEN, e.to_string()),
+        Some(TokenError::Placement(_)) => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+fn parse_token_id(token_id: &str) -> Result<GridTokenId, (StatusCode, String)> {
+    Uuid::parse_str(token_id)
+        .map(GridTokenId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid grid token ID".to_string()))
+}
+
+/// Parse a grid map ID and check the map exists, or 404
+async fn existing_grid_map(state: &AppState, grid_map_id: &str) -> Result<GridMapId, (StatusCode, String)> {
+    let id = Uuid::parse_str(grid_map_id)
+        .map(GridMapId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid grid map ID".to_string()))?;
+    state
+        .core
+        .grid_map_service
+        .get_grid_map(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Grid map not found".to_string()))?;
+    Ok(id)
+}
+
+/// List every token on a grid map, hidden ones included
+pub async fn list_tokens(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+) -> Result<Json<Vec<GridTokenDto>>, (StatusCode, String)> {
+    let grid_map_id = existing_grid_map(&state, &grid_map_id).await?;
+
+    let tokens = state
+        .game
+        .grid_token_service
+        .list(grid_map_id, true)
+        .await
+        .map_err(token_error)?;
+
+    Ok(Json(tokens.into_iter().map(GridTokenDto::from).collect()))
+}
+
+/// Put a token on a grid map
+pub async fn place_token(
+    State(state): State<Arc<AppState>>,
+    Path(grid_map_id): Path<String>,
+    Json(req): Json<PlaceTokenRequestDto>,
+) -> Result<(StatusCode, Json<GridTokenDto>), (StatusCode, String)> {
+    let grid_map_id = existing_grid_map(&state, &grid_map_id).await?;
+    let place = req
+        .into_place()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid token subject ID".to_string()))?;
+
+    let token = state
+        .game
+        .grid_token_service
+        .place(grid_map_id, place)
+        .await
+        .map_err(token_error)?;
+
+    Ok((StatusCode::CREATED, Json(GridTokenDto::from(token))))
+}
+
+/// Change a token's name, size, facing or visibility
+pub async fn update_token(
+    State(state): State<Arc<AppState>>,
+    Path(token_id): Path<String>,
+    Json(req): Json<UpdateTokenRequestDto>,
+) -> Result<Json<GridTokenDto>, (StatusCode, String)> {
+    let token = state
+        .game
+        .grid_token_service
+        .update(parse_token_id(&token_id)?, req.into())
+        .await
+        .map_err(token_error)?;
+
+    Ok(Json(GridTokenDto::from(token)))
+}
+
+/// Move a token along the cheapest route to a tile
+pub async fn move_token(
+    State(state): State<Arc<AppState>>,
+    Path(token_id): Path<String>,
+    Json(req): Json<MoveTokenRequestDto>,
+) -> Result<Json<TokenMovedDto>, (StatusCode, String)> {
+    let (token, path) = state
+        .game
+        .grid_token_service
+        .move_token(
+            parse_token_id(&token_id)?,
+            TokenMover::Dm,
+            (req.x, req.y),
+            req.facing.map(Facing::from),
+        )
+        .await
+        .map_err(token_error)?;
+
+    Ok(Json(TokenMovedDto::new(&token, path)))
+}
+
+/// Take a token off its map
+pub async fn remove_token(
+    State(state): State<Arc<AppState>>,
+    Path(token_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .game
+        .grid_token_service
+        .remove(parse_token_id(&token_id)?)
+        .await
+        .map_err(token_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,241 @@
+//! Item API routes
+//!
+//! Endpoints for a world's item catalogue, and for putting catalogue items
+//! in characters' inventories.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    parse_acquisition_method, AddInventoryItemRequestDto, CreateItemRequestDto, ItemResponseDto,
+    ListItemsQueryDto, UpdateInventoryItemRequestDto, UpdateItemRequestDto,
+};
+use crate::application::services::{
+    AddInventoryItemRequest, CreateItemRequest, ItemError, ItemService, UpdateItemRequest,
+};
+use crate::domain::entities::Item;
+use crate::domain::value_objects::{CharacterId, ItemId, WorldId};
+use crate::infrastructure::state::AppState;
+
+/// Map an item service error, reporting refused requests as such
+fn item_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<ItemError>() {
+        Some(ItemError::NotFound(_) | ItemError::CharacterNotFound(_)) => {
+            (StatusCode::NOT_FOUND, e.to_string())
+        }
+        Some(_) => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+fn parse_world_id(world_id: &str) -> Result<WorldId, (StatusCode, String)> {
+    Uuid::parse_str(world_id)
+        .map(WorldId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))
+}
+
+fn parse_item_id(item_id: &str) -> Result<ItemId, (StatusCode, String)> {
+    Uuid::parse_str(item_id)
+        .map(ItemId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid item ID".to_string()))
+}
+
+fn parse_character_id(character_id: &str) -> Result<CharacterId, (StatusCode, String)> {
+    Uuid::parse_str(character_id)
+        .map(CharacterId::from_uuid)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid character ID".to_string()))
+}
+
+/// Load an item of a world, or 404/403
+async fn load_world_item(
+    state: &AppState,
+    world_id: WorldId,
+    item_id: ItemId,
+) -> Result<Item, (StatusCode, String)> {
+    let item = state
+        .core
+        .item_service
+        .get_item(item_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Item not found".to_string()))?;
+    if item.world_id != world_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Item does not belong to this world".to_string(),
+        ));
+    }
+    Ok(item)
+}
+
+/// List the items in a world, optionally only those of one type
+pub async fn list_items(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Query(query): Query<ListItemsQueryDto>,
+) -> Result<Json<Vec<ItemResponseDto>>, (StatusCode, String)> {
+    let world_id = parse_world_id(&world_id)?;
+
+    let items = state
+        .core
+        .item_service
+        .list_items(world_id, query.item_type.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(items.into_iter().map(ItemResponseDto::from).collect()))
+}
+
+/// Add an item to a world's catalogue
+pub async fn create_item(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Json(req): Json<CreateItemRequestDto>,
+) -> Result<(StatusCode, Json<ItemResponseDto>), (StatusCode, String)> {
+    let world_id = parse_world_id(&world_id)?;
+
+    let service_request = CreateItemRequest {
+        name: req.name,
+        description: req.description,
+        item_type: req.item_type,
+        is_unique: req.is_unique,
+        properties: req.properties,
+    };
+
+    let item = state
+        .core
+        .item_service
+        .create_item(world_id, service_request)
+        .await
+        .map_err(item_error)?;
+
+    Ok((StatusCode::CREATED, Json(ItemResponseDto::from(item))))
+}
+
+/// Get an item of a world
+pub async fn get_item(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, item_id)): Path<(String, String)>,
+) -> Result<Json<ItemResponseDto>, (StatusCode, String)> {
+    let item = load_world_item(&state, parse_world_id(&world_id)?, parse_item_id(&item_id)?).await?;
+    Ok(Json(ItemResponseDto::from(item)))
+}
+
+/// Update an item of a world
+pub async fn update_item(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, item_id)): Path<(String, String)>,
+    Json(req): Json<UpdateItemRequestDto>,
+) -> Result<Json<ItemResponseDto>, (StatusCode, String)> {
+    let item_id = parse_item_id(&item_id)?;
+    load_world_item(&state, parse_world_id(&world_id)?, item_id).await?;
+
+    let service_request = UpdateItemRequest {
+        name: req.name,
+        description: req.description,
+        item_type: req.item_type,
+        is_unique: req.is_unique,
+        properties: req.properties,
+    };
+
+    let item = state
+        .core
+        .item_service
+        .update_item(item_id, service_request)
+        .await
+        .map_err(item_error)?;
+
+    Ok(Json(ItemResponseDto::from(item)))
+}
+
+/// Delete an item of a world, taking it out of every inventory
+pub async fn delete_item(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, item_id)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let item_id = parse_item_id(&item_id)?;
+    load_world_item(&state, parse_world_id(&world_id)?, item_id).await?;
+
+    state
+        .core
+        .item_service
+        .delete_item(item_id)
+        .await
+        .map_err(item_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Put a catalogue item in a character's inventory
+pub async fn add_inventory_item(
+    State(state): State<Arc<AppState>>,
+    Path(character_id): Path<String>,
+    Json(req): Json<AddInventoryItemRequestDto>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let character_id = parse_character_id(&character_id)?;
+    let acquisition_method = req
+        .acquisition_method
+        .as_deref()
+        .map(|method| {
+            parse_acquisition_method(method)
+                .ok_or_else(|| (StatusCode::BAD_REQUEST, format!("Invalid acquisition method: {}", method)))
+        })
+        .transpose()?;
+
+    let service_request = AddInventoryItemRequest {
+        item_id: parse_item_id(&req.item_id)?,
+        quantity: req.quantity,
+        equipped: req.equipped,
+        acquisition_method,
+    };
+
+    state
+        .core
+        .item_service
+        .add_inventory_item(character_id, service_request)
+        .await
+        .map_err(item_error)?;
+
+    Ok(StatusCode::CREATED)
+}
+
+/// Change how many of an item a character holds, or whether it is equipped
+pub async fn update_inventory_item(
+    State(state): State<Arc<AppState>>,
+    Path((character_id, item_id)): Path<(String, String)>,
+    Json(req): Json<UpdateInventoryItemRequestDto>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .core
+        .item_service
+        .update_inventory_item(
+            parse_character_id(&character_id)?,
+            parse_item_id(&item_id)?,
+            req.quantity,
+            req.equipped,
+        )
+        .await
+        .map_err(item_error)?;
+
+    Ok(StatusCode::OK)
+}
+
+/// Take an item out of a character's inventory
+pub async fn remove_inventory_item(
+    State(state): State<Arc<AppState>>,
+    Path((character_id, item_id)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .core
+        .item_service
+        .remove_inventory_item(parse_character_id(&character_id)?, parse_item_id(&item_id)?)
+        .await
+        .map_err(item_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,17 +1,24 @@
 //! HTTP REST API routes
 
+mod advancement_routes;
 mod asset_routes;
 mod challenge_routes;
 mod character_routes;
+mod combat_routes;
+mod condition_routes;
 mod config_routes;
 mod event_chain_routes;
 mod export_routes;
+mod grid_map_routes;
+mod grid_token_routes;
 mod interaction_routes;
+mod item_routes;
 mod location_routes;
 mod narrative_event_routes;
 mod observation_routes;
 mod player_character_routes;
 mod region_routes;
+mod resource_routes;
 mod session_routes;
 mod queue_routes;
 mod rule_system_routes;
@@ -25,6 +32,7 @@ mod workflow_routes;
 mod world_routes;
 
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{delete, get, patch, post, put},
     Router,
 };
@@ -32,6 +40,9 @@ use std::sync::Arc;
 
 use crate::infrastructure::state::AppState;
 
+/// Largest map file accepted for import; Universal VTT files carry the
+/// rendered map as an image, well past the default body limit
+const MAX_MAP_FILE_BYTES: usize = 64 * 1024 * 1024;
 
 /// Create all API routes
 pub fn create_routes() -> Router<Arc<AppState>> {
@@ -66,11 +77,23 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/characters/{id}/archetype",
             put(character_routes::change_archetype),
         )
-        // Character inventory route (Phase 23B)
+        // Character inventory routes (Phase 23B)
         .route(
             "/api/characters/{id}/inventory",
             get(character_routes::get_inventory),
         )
+        .route(
+            "/api/characters/{id}/inventory",
+            post(item_routes::add_inventory_item),
+        )
+        .route(
+            "/api/characters/{id}/inventory/{item_id}",
+            put(item_routes::update_inventory_item),
+        )
+        .route(
+            "/api/characters/{id}/inventory/{item_id}",
+            delete(item_routes::remove_inventory_item),
+        )
         // Character-Region relationship routes (Phase 23C)
         .route(
             "/api/characters/{id}/region-relationships",
@@ -111,6 +134,91 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/worlds/{world_id}/locations/available-for-starting",
             get(location_routes::list_available_starting_locations),
         )
+        // Grid map routes (tactical maps)
+        .route(
+            "/api/worlds/{world_id}/grid-maps",
+            get(grid_map_routes::list_grid_maps),
+        )
+        .route(
+            "/api/worlds/{world_id}/grid-maps",
+            post(grid_map_routes::create_grid_map),
+        )
+        .route(
+            "/api/worlds/{world_id}/grid-maps/generate",
+            post(grid_map_routes::generate_grid_map),
+        )
+        .route(
+            "/api/worlds/{world_id}/grid-maps/import/uvtt",
+            post(grid_map_routes::import_uvtt_map).layer(DefaultBodyLimit::max(MAX_MAP_FILE_BYTES)),
+        )
+        .route(
+            "/api/worlds/{world_id}/grid-maps/import/tiled",
+            post(grid_map_routes::import_tiled_map).layer(DefaultBodyLimit::max(MAX_MAP_FILE_BYTES)),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}",
+            get(grid_map_routes::get_grid_map),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}",
+            put(grid_map_routes::update_grid_map),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}",
+            delete(grid_map_routes::delete_grid_map),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}/export/tiled",
+            get(grid_map_routes::export_tiled_map),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}/tiles",
+            patch(grid_map_routes::patch_tiles),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}/path",
+            post(grid_map_routes::find_path),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}/movement-range",
+            post(grid_map_routes::movement_range),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}/cover",
+            post(grid_map_routes::check_cover),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}/tokens",
+            get(grid_token_routes::list_tokens),
+        )
+        .route(
+            "/api/grid-maps/{grid_map_id}/tokens",
+            post(grid_token_routes::place_token),
+        )
+        .route(
+            "/api/grid-tokens/{token_id}",
+            put(grid_token_routes::update_token),
+        )
+        .route(
+            "/api/grid-tokens/{token_id}",
+            delete(grid_token_routes::remove_token),
+        )
+        .route(
+            "/api/grid-tokens/{token_id}/move",
+            post(grid_token_routes::move_token),
+        )
+        .route(
+            "/api/locations/{location_id}/grid-map",
+            get(grid_map_routes::get_location_grid_map),
+        )
+        .route(
+            "/api/locations/{location_id}/grid-map",
+            put(grid_map_routes::set_location_grid_map),
+        )
+        .route(
+            "/api/locations/{location_id}/grid-map",
+            delete(grid_map_routes::remove_location_grid_map),
+        )
         // Region routes
         .route(
             "/api/locations/{location_id}/regions",
@@ -228,6 +336,12 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/sessions/{session_id}/game-time/advance",
             post(session_routes::advance_game_time),
         )
+        // Roll log routes
+        .route(
+            "/api/sessions/{session_id}/rolls",
+            get(session_routes::list_session_rolls),
+        )
+        .route("/api/rolls/{roll_id}", get(session_routes::get_roll))
         // Player Character routes
         .route(
             "/api/sessions/{session_id}/player-characters",
@@ -270,6 +384,89 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/player-characters/{pc_id}",
             delete(player_character_routes::delete_player_character),
         )
+        .route(
+            "/api/player-characters/{pc_id}/resources/{field_id}",
+            post(resource_routes::modify_resource),
+        )
+        .route(
+            "/api/worlds/{world_id}/rest-types",
+            get(resource_routes::list_rest_types),
+        )
+        .route(
+            "/api/worlds/{world_id}/rests",
+            post(resource_routes::take_rest),
+        )
+        // Advancement routes (experience, milestones and level-ups)
+        .route(
+            "/api/player-characters/{pc_id}/advancement",
+            get(advancement_routes::get_advancement),
+        )
+        .route(
+            "/api/player-characters/{pc_id}/experience",
+            post(advancement_routes::award_experience),
+        )
+        .route(
+            "/api/player-characters/{pc_id}/level-up",
+            post(advancement_routes::level_up),
+        )
+        .route(
+            "/api/worlds/{world_id}/level-ups",
+            get(advancement_routes::list_pending_level_ups),
+        )
+        .route(
+            "/api/worlds/{world_id}/progression",
+            get(advancement_routes::get_progression),
+        )
+        // Combat routes (initiative, turns and hit points)
+        .route(
+            "/api/sessions/{session_id}/combat",
+            get(combat_routes::get_combat),
+        )
+        .route(
+            "/api/sessions/{session_id}/combat",
+            post(combat_routes::start_combat),
+        )
+        .route(
+            "/api/sessions/{session_id}/combat/initiative",
+            post(combat_routes::roll_initiative),
+        )
+        .route(
+            "/api/sessions/{session_id}/combat/next-turn",
+            post(combat_routes::next_turn),
+        )
+        .route(
+            "/api/sessions/{session_id}/combat/combatants/{combatant_id}/damage",
+            post(combat_routes::damage_combatant),
+        )
+        .route(
+            "/api/sessions/{session_id}/combat/end",
+            post(combat_routes::end_combat),
+        )
+        // Condition routes (NPCs and player characters)
+        .route(
+            "/api/characters/{character_id}/conditions",
+            get(condition_routes::list_conditions),
+        )
+        .route(
+            "/api/characters/{character_id}/conditions",
+            post(condition_routes::apply_condition),
+        )
+        .route(
+            "/api/characters/{character_id}/conditions/{name}",
+            delete(condition_routes::remove_condition),
+        )
+        .route(
+            "/api/player-characters/{pc_id}/conditions",
+            get(condition_routes::list_conditions),
+        )
+        .route(
+            "/api/player-characters/{pc_id}/conditions",
+            post(condition_routes::apply_condition),
+        )
+        .route(
+            "/api/player-characters/{pc_id}/conditions/{name}",
+            delete(condition_routes::remove_condition),
+        )
         .route(
             "/api/player-characters/{pc_id}/location",
             put(player_character_routes::update_player_character_location),
@@ -354,6 +551,27 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/locations/{location_id}/gallery/{asset_id}",
             delete(asset_routes::delete_location_asset),
         )
+        // Item catalogue routes
+        .route(
+            "/api/worlds/{world_id}/items",
+            get(item_routes::list_items),
+        )
+        .route(
+            "/api/worlds/{world_id}/items",
+            post(item_routes::create_item),
+        )
+        .route(
+            "/api/worlds/{world_id}/items/{item_id}",
+            get(item_routes::get_item),
+        )
+        .route(
+            "/api/worlds/{world_id}/items/{item_id}",
+            put(item_routes::update_item),
+        )
+        .route(
+            "/api/worlds/{world_id}/items/{item_id}",
+            delete(item_routes::delete_item),
+        )
         // Asset Gallery routes - Items
         .route(
             "/api/items/{item_id}/gallery",
@@ -454,6 +672,22 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/rule-systems/{system_type}/presets/{variant}",
             get(rule_system_routes::get_preset),
         )
+        .route(
+            "/api/worlds/{world_id}/rule-system/definition",
+            get(rule_system_routes::get_rule_system_definition),
+        )
+        .route(
+            "/api/worlds/{world_id}/rule-system/definition",
+            put(rule_system_routes::upload_rule_system_definition),
+        )
+        .route(
+            "/api/worlds/{world_id}/rule-system/descriptors",
+            get(rule_system_routes::get_descriptor_difficulties),
+        )
+        .route(
+            "/api/worlds/{world_id}/rule-system/descriptors",
+            put(rule_system_routes::update_descriptor_difficulties),
+        )
         // Skill routes
         .route(
             "/api/worlds/{world_id}/skills",
@@ -492,6 +726,10 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/worlds/{world_id}/sheet-templates/{template_id}",
             delete(sheet_template_routes::delete_template),
         )
+        .route(
+            "/api/worlds/{world_id}/sheet-templates/{template_id}",
+            put(sheet_template_routes::update_template),
+        )
         .route(
             "/api/worlds/{world_id}/sheet-template/initialize",
             post(sheet_template_routes::initialize_template),
@@ -504,6 +742,22 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/worlds/{world_id}/sheet-templates/{template_id}/sections/{section_id}/fields",
             post(sheet_template_routes::add_field),
         )
+        .route(
+            "/api/worlds/{world_id}/sheet-templates/{template_id}/invalid-sheets",
+            get(sheet_template_routes::invalid_sheet_report),
+        )
+        .route(
+            "/api/worlds/{world_id}/sheet-templates/{template_id}/migration",
+            get(sheet_template_routes::preview_migration),
+        )
+        .route(
+            "/api/worlds/{world_id}/sheet-templates/{template_id}/migration/apply",
+            post(sheet_template_routes::apply_migration),
+        )
+        .route(
+            "/api/worlds/{world_id}/sheet-templates/{template_id}/migration/rollback",
+            post(sheet_template_routes::rollback_migration),
+        )
         // Challenge routes
         .route(
             "/api/worlds/{world_id}/challenges",
@@ -545,6 +799,18 @@ pub fn create_routes() -> Router<Arc<AppState>> {
             "/api/challenges/{challenge_id}/active",
             put(challenge_routes::set_active),
         )
+        .route(
+            "/api/challenges/{challenge_id}/odds",
+            get(challenge_routes::get_challenge_odds),
+        )
+        .route(
+            "/api/challenges/{challenge_id}/progress",
+            get(challenge_routes::get_complex_progress),
+        )
+        .route(
+            "/api/challenges/{challenge_id}/progress",
+            delete(challenge_routes::reset_complex_progress),
+        )
         // Story Event routes (Timeline)
         .route(
             "/api/worlds/{world_id}/story-events",
@@ -9,19 +9,33 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::application::ports::outbound::PlayerCharacterRepositoryPort;
+use crate::application::dto::SheetValidationErrorDto;
+use crate::application::ports::outbound::{PlayerCharacterRepositoryPort, SessionParticipantRole};
 use crate::application::services::{
     PlayerCharacterService,
     SceneResolutionService,
     CreatePlayerCharacterRequest, UpdatePlayerCharacterRequest,
 };
 use crate::domain::entities::PlayerCharacter;
-use crate::domain::entities::{CharacterSheetData, FieldValue};
+use crate::domain::entities::{CharacterSheetData, FieldValue, InvalidSheetData};
 use crate::domain::value_objects::{
     LocationId, PlayerCharacterId, RegionId, SessionId, WorldId,
 };
 use crate::infrastructure::state::AppState;
 
+/// Map a service error, reporting sheet validation failures per field as a
+/// JSON `SheetValidationErrorDto` body
+fn sheet_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<InvalidSheetData>() {
+        Some(invalid) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            serde_json::to_string(&SheetValidationErrorDto::from(invalid))
+                .unwrap_or_else(|_| invalid.to_string()),
+        ),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
 /// Extract user ID from X-User-Id header, falling back to a default if not provided
 fn extract_user_id(headers: &HeaderMap) -> String {
     headers
@@ -31,6 +45,21 @@ fn extract_user_id(headers: &HeaderMap) -> String {
         .unwrap_or_else(|| "anonymous".to_string())
 }
 
+/// The caller's role in a session: DM if they are its dungeon master, a
+/// player otherwise
+async fn caller_role(state: &AppState, user_id: &str, session_id: Option<SessionId>) -> SessionParticipantRole {
+    let sessions = state.sessions.read().await;
+    let is_dm = session_id
+        .and_then(|session_id| sessions.get_session(session_id))
+        .and_then(|session| session.get_dm())
+        .is_some_and(|dm| dm.user_id == user_id);
+    if is_dm {
+        SessionParticipantRole::DungeonMaster
+    } else {
+        SessionParticipantRole::Player
+    }
+}
+
 // =============================================================================
 // Request/Response DTOs
 // =============================================================================
@@ -61,6 +90,9 @@ pub struct UpdatePlayerCharacterRequestDto {
     pub sprite_asset: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub portrait_asset: Option<String>,
+    /// How many tiles far the character sees on a grid map
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vision_radius: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -155,6 +187,7 @@ pub struct PlayerCharacterResponseDto {
     pub sprite_asset: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub portrait_asset: Option<String>,
+    pub vision_radius: u32,
     pub created_at: String,
     pub last_active_at: String,
 }
@@ -174,6 +207,7 @@ impl From<PlayerCharacter> for PlayerCharacterResponseDto {
             starting_location_id: pc.starting_location_id.to_string(),
             sprite_asset: pc.sprite_asset,
             portrait_asset: pc.portrait_asset,
+            vision_radius: pc.vision_radius,
             created_at: pc.created_at.to_rfc3339(),
             last_active_at: pc.last_active_at.to_rfc3339(),
         }
@@ -222,6 +256,7 @@ pub async fn create_player_character(
     let location_id = LocationId::from_uuid(location_uuid);
 
     let sheet_data = req.sheet_data.map(|dto| dto.into());
+    let caller_role = caller_role(&state, &user_id, Some(session_id)).await;
 
     let service_request = CreatePlayerCharacterRequest {
         session_id: Some(session_id),
@@ -233,13 +268,14 @@ pub async fn create_player_character(
         sheet_data,
         sprite_asset: req.sprite_asset,
         portrait_asset: req.portrait_asset,
+        caller_role,
     };
 
     let pc = state
                 .player.player_character_service
         .create_pc(service_request)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(sheet_error)?;
 
     // Add PC to session
     {
@@ -326,12 +362,25 @@ pub async fn get_player_character(
 pub async fn update_player_character(
     State(state): State<Arc<AppState>>,
     Path(pc_id): Path<String>,
+    headers: HeaderMap,
     Json(req): Json<UpdatePlayerCharacterRequestDto>,
 ) -> Result<Json<PlayerCharacterResponseDto>, (StatusCode, String)> {
     let pc_uuid = Uuid::parse_str(&pc_id)
         .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid player character ID".to_string()))?;
     let pc_id = PlayerCharacterId::from_uuid(pc_uuid);
 
+    let pc = state
+                .player.player_character_service
+        .get_pc(pc_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Player character not found".to_string()))?;
+    let session_id = match pc.session_id {
+        Some(session_id) => Some(session_id),
+        None => state.sessions.read().await.find_session_for_world(pc.world_id),
+    };
+    let caller_role = caller_role(&state, &extract_user_id(&headers), session_id).await;
+
     let sheet_data = req.sheet_data.map(|dto| dto.into());
 
     let service_request = UpdatePlayerCharacterRequest {
@@ -340,13 +389,15 @@ pub async fn update_player_character(
         sheet_data,
         sprite_asset: req.sprite_asset,
         portrait_asset: req.portrait_asset,
+        vision_radius: req.vision_radius,
+        caller_role,
     };
 
     let pc = state
                 .player.player_character_service
         .update_pc(pc_id, service_request)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(sheet_error)?;
 
     Ok(Json(PlayerCharacterResponseDto::from(pc)))
 }
@@ -579,6 +630,11 @@ pub async fn select_pc(
     // Update last_active_at
     let mut updated_pc = pc.clone();
     updated_pc.last_active_at = chrono::Utc::now();
+    state
+                .player.player_character_service
+        .compute_derived_fields(std::slice::from_mut(&mut updated_pc))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     state.repository.player_characters()
         .update(&updated_pc)
         .await
@@ -643,7 +699,7 @@ pub async fn list_user_pcs(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     // If rule_system filter is specified, filter by world's rule system
-    let filtered_pcs = if let Some(rule_system) = query.rule_system {
+    let mut filtered_pcs = if let Some(rule_system) = query.rule_system {
         use crate::domain::value_objects::RuleSystemType;
         let target_type = match rule_system.to_uppercase().as_str() {
             "D20" => Some(RuleSystemType::D20),
@@ -672,6 +728,12 @@ pub async fn list_user_pcs(
         pcs
     };
 
+    state
+                .player.player_character_service
+        .compute_derived_fields(&mut filtered_pcs)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
     Ok(Json(filtered_pcs.into_iter().map(PlayerCharacterResponseDto::from).collect()))
 }
 
@@ -741,6 +803,13 @@ pub async fn import_pc(
         new_pc = new_pc.with_description(desc);
     }
     if let Some(sheet) = source_pc.sheet_data {
+        // The sheet must fit the target world's template; values the source
+        // world made read-only carry over unchanged
+        state
+                .player.player_character_service
+            .validate_sheet(target_world_id, &sheet, Some(&sheet), SessionParticipantRole::Player)
+            .await
+            .map_err(sheet_error)?;
         new_pc = new_pc.with_sheet_data(sheet);
     }
     if let Some(sprite) = source_pc.sprite_asset {
@@ -750,6 +819,13 @@ pub async fn import_pc(
         new_pc = new_pc.with_portrait(portrait);
     }
 
+    // Recompute derived fields with the target world's template
+    state
+                .player.player_character_service
+        .compute_derived_fields(std::slice::from_mut(&mut new_pc))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
     // Save the new PC
     state.repository.player_characters()
         .create(&new_pc)
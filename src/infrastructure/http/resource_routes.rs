@@ -0,0 +1,141 @@
+//! Sheet resource API routes
+//!
+//! Endpoints for spending and recovering character sheet resources and for
+//! taking the rests a world's rule system defines.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    ModifyResourceRequestDto, ResourceChangeDto, RestOutcomeDto, RestTypeDto, TakeRestRequestDto,
+};
+use crate::domain::entities::ResourceError;
+use crate::domain::value_objects::{PlayerCharacterId, WorldId};
+use crate::infrastructure::state::AppState;
+use crate::infrastructure::websocket::messages::ServerMessage;
+use crate::infrastructure::websocket_helpers::expire_conditions;
+
+/// Map a resource service error, reporting refused operations as a bad request
+fn resource_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<ResourceError>() {
+        Some(ResourceError::Insufficient { .. }) => (StatusCode::CONFLICT, e.to_string()),
+        Some(refused) => (StatusCode::UNPROCESSABLE_ENTITY, refused.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// List the rests a world's rule system offers
+pub async fn list_rest_types(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<Vec<RestTypeDto>>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let rests = state
+        .player
+        .sheet_resource_service
+        .rest_types(WorldId::from_uuid(uuid))
+        .await
+        .map_err(|e| (StatusCode::NOT_FOUND, e.to_string()))?;
+
+    Ok(Json(rests.into_iter().map(RestTypeDto::from).collect()))
+}
+
+/// Spend, restore or reset a resource on a player character's sheet
+pub async fn modify_resource(
+    State(state): State<Arc<AppState>>,
+    Path((pc_id, field_id)): Path<(String, String)>,
+    Json(req): Json<ModifyResourceRequestDto>,
+) -> Result<Json<ResourceChangeDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&pc_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid player character ID".to_string()))?;
+
+    let (_, change) = state
+        .player
+        .sheet_resource_service
+        .modify_resource(
+            PlayerCharacterId::from_uuid(uuid),
+            &field_id,
+            req.operation.into(),
+            req.reason,
+            None,
+        )
+        .await
+        .map_err(resource_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Player character not found".to_string()))?;
+
+    Ok(Json(ResourceChangeDto::from(change)))
+}
+
+/// Have characters in a world take a rest
+///
+/// If the world has a running session, its game time advances by the length
+/// of the rest, the new time is broadcast and conditions that ran out expire.
+pub async fn take_rest(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Json(req): Json<TakeRestRequestDto>,
+) -> Result<Json<RestOutcomeDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+    let world_id = WorldId::from_uuid(uuid);
+    let pc_ids = req
+        .pc_ids
+        .iter()
+        .map(|id| Uuid::parse_str(id).map(PlayerCharacterId::from_uuid))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid player character ID".to_string()))?;
+
+    let (session_id, started_at) = {
+        let sessions = state.sessions.read().await;
+        let session_id = sessions.find_session_for_world(world_id);
+        let started_at = session_id
+            .and_then(|id| sessions.get_session(id))
+            .map(|session| session.display_game_time());
+        (session_id, started_at)
+    };
+
+    let outcome = state
+        .player
+        .sheet_resource_service
+        .take_rest(world_id, &req.rest_type, &pc_ids, started_at)
+        .await
+        .map_err(resource_error)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Rest type not found".to_string()))?;
+    let duration_minutes = outcome.rest.duration_minutes;
+    let mut response = RestOutcomeDto::from(outcome);
+
+    if let Some(session_id) = session_id {
+        let game_time = {
+            let mut sessions = state.sessions.write().await;
+            sessions.get_session_mut(session_id).map(|session| {
+                session.advance_time_minutes(duration_minutes);
+                (
+                    session.display_game_time(),
+                    session.time_of_day().to_string(),
+                    session.is_time_paused(),
+                )
+            })
+        };
+        if let Some((display, time_of_day, is_paused)) = game_time {
+            response.game_time = Some(display.clone());
+            let time_updated = ServerMessage::GameTimeUpdated {
+                display,
+                time_of_day,
+                is_paused,
+            };
+            if let Ok(msg_json) = serde_json::to_value(&time_updated) {
+                let _ = state.async_session_port.broadcast_except(session_id, msg_json, "").await;
+            }
+        }
+        expire_conditions(&state, session_id).await;
+    }
+
+    Ok(Json(response))
+}
@@ -1,18 +1,59 @@
 //! Rule System API routes
 //!
-//! Provides endpoints for listing available rule systems and their presets.
+//! Provides endpoints for listing available rule systems and their presets,
+//! for uploading a world's own (homebrew) rule system, and for editing how a
+//! world's rule system maps difficulty descriptors.
+//!
+//! The listing endpoints take an optional `world_id` query parameter; a
+//! homebrew system uploaded to that world is listed alongside the presets.
+
+use std::sync::Arc;
 
-use axum::{extract::Path, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
 
 use crate::application::dto::{
-    parse_system_type, parse_variant, RuleSystemPresetDetailsDto, RuleSystemPresetSummaryDto,
-    RuleSystemSummaryDto, RuleSystemTypeDetailsDto,
+    parse_system_type, parse_variant, DescriptorDifficultiesDto, RuleSystemDefinitionDto,
+    RuleSystemPresetDetailsDto, RuleSystemPresetSummaryDto, RuleSystemQueryDto, RuleSystemSummaryDto,
+    RuleSystemTypeDetailsDto,
 };
-use crate::domain::value_objects::{RuleSystemConfig, RuleSystemType, RuleSystemVariant};
+use crate::application::services::{UpdateWorldRequest, WorldError, WorldService};
+use crate::domain::entities::{InvalidRuleSystem, RuleSystemDefinition};
+use crate::domain::value_objects::{RuleSystemConfig, RuleSystemType, RuleSystemVariant, WorldId};
+use crate::infrastructure::state::AppState;
+
+/// Load the homebrew rule system uploaded to the world named in the query
+async fn world_rule_system(
+    state: &AppState,
+    query: &RuleSystemQueryDto,
+) -> Result<Option<RuleSystemConfig>, (StatusCode, String)> {
+    let Some(world_id) = &query.world_id else {
+        return Ok(None);
+    };
+    let uuid = Uuid::parse_str(world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+    let world = state
+        .core.world_service
+        .get_world(WorldId::from_uuid(uuid))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "World not found".to_string()))?;
+    Ok(world.custom_rule_system().map(RuleSystemDefinition::to_config))
+}
 
 /// List all available rule system types
-pub async fn list_rule_systems() -> impl IntoResponse {
-    let systems = vec![
+pub async fn list_rule_systems(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<RuleSystemQueryDto>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let homebrew = world_rule_system(&state, &query).await?;
+
+    let mut systems = vec![
         RuleSystemSummaryDto {
             system_type: RuleSystemType::D20.into(),
             name: "D20 System".to_string(),
@@ -73,7 +114,20 @@ pub async fn list_rule_systems() -> impl IntoResponse {
         },
     ];
 
-    Json(systems)
+    if let Some(config) = homebrew {
+        if let Some(system) = systems
+            .iter_mut()
+            .find(|system| system.system_type == config.system_type.into())
+        {
+            system.presets.push(RuleSystemPresetSummaryDto {
+                variant: config.variant.into(),
+                name: config.name,
+                description: config.description,
+            });
+        }
+    }
+
+    Ok(Json(systems))
 }
 
 /// Get details about a specific rule system type
@@ -128,15 +182,22 @@ pub async fn get_rule_system(
 
 /// List presets for a rule system type
 pub async fn list_presets(
+    State(state): State<Arc<AppState>>,
     Path(system_type): Path<String>,
+    Query(query): Query<RuleSystemQueryDto>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     let system_type = parse_system_type(&system_type).map_err(|msg| (StatusCode::BAD_REQUEST, msg))?;
+    let homebrew = world_rule_system(&state, &query)
+        .await?
+        .filter(|config| config.system_type == system_type);
 
     let presets: Vec<RuleSystemPresetDetailsDto> = RuleSystemVariant::variants_for_type(system_type)
         .into_iter()
-        .map(|v| RuleSystemPresetDetailsDto {
-            variant: v.clone().into(),
-            config: RuleSystemConfig::from_variant(v).into(),
+        .map(RuleSystemConfig::from_variant)
+        .chain(homebrew)
+        .map(|config| RuleSystemPresetDetailsDto {
+            variant: config.variant.clone().into(),
+            config: config.into(),
         })
         .collect();
 
@@ -145,16 +206,152 @@ pub async fn list_presets(
 
 /// Get a specific preset configuration
 pub async fn get_preset(
+    State(state): State<Arc<AppState>>,
     Path((system_type, variant)): Path<(String, String)>,
+    Query(query): Query<RuleSystemQueryDto>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     let _system_type =
         parse_system_type(&system_type).map_err(|msg| (StatusCode::BAD_REQUEST, msg))?;
-    let variant = parse_variant(&variant).map_err(|msg| (StatusCode::BAD_REQUEST, msg))?;
 
-    let config = RuleSystemConfig::from_variant(variant.clone());
+    // A world's homebrew system is looked up by its name
+    let homebrew = world_rule_system(&state, &query)
+        .await?
+        .filter(|config| config.name.eq_ignore_ascii_case(&variant));
+    let (variant, config) = match homebrew {
+        Some(config) => (config.variant.clone(), config),
+        None => {
+            let variant = parse_variant(&variant).map_err(|msg| (StatusCode::BAD_REQUEST, msg))?;
+            (variant.clone(), RuleSystemConfig::from_variant(variant))
+        }
+    };
 
     Ok(Json(RuleSystemPresetDetailsDto {
         variant: variant.into(),
         config: config.into(),
     }))
 }
+
+/// Get how a world's rule system maps difficulty descriptors
+pub async fn get_descriptor_difficulties(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<DescriptorDifficultiesDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let world = state
+        .core.world_service
+        .get_world(WorldId::from_uuid(uuid))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "World not found".to_string()))?;
+
+    Ok(Json(world.rule_system.descriptor_difficulties.into()))
+}
+
+/// Replace how a world's rule system maps difficulty descriptors
+pub async fn update_descriptor_difficulties(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    Json(req): Json<DescriptorDifficultiesDto>,
+) -> Result<Json<DescriptorDifficultiesDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+    let world_id = WorldId::from_uuid(uuid);
+
+    let world = state
+        .core.world_service
+        .get_world(world_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "World not found".to_string()))?;
+
+    let mut rule_system = world.rule_system;
+    rule_system.descriptor_difficulties = req.into();
+    let world = state
+        .core.world_service
+        .update_world(
+            world_id,
+            UpdateWorldRequest {
+                name: None,
+                description: None,
+                rule_system: Some(rule_system),
+            },
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(world.rule_system.descriptor_difficulties.into()))
+}
+
+/// Get the homebrew rule system definition uploaded to a world
+pub async fn get_rule_system_definition(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+) -> Result<Json<RuleSystemDefinitionDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let world = state
+        .core.world_service
+        .get_world(WorldId::from_uuid(uuid))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "World not found".to_string()))?;
+
+    let definition = world.rule_system_definition.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            "World has no rule system definition".to_string(),
+        )
+    })?;
+
+    Ok(Json(definition.into()))
+}
+
+/// Upload a homebrew rule system definition and switch the world to it
+///
+/// The body is JSON, or YAML when sent with a YAML content type.
+pub async fn upload_rule_system_definition(
+    State(state): State<Arc<AppState>>,
+    Path(world_id): Path<String>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<RuleSystemDefinitionDto>, (StatusCode, String)> {
+    let uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+
+    let is_yaml = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("yaml"));
+    let dto: RuleSystemDefinitionDto = if is_yaml {
+        serde_yaml_ng::from_str(&body).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    } else {
+        serde_json::from_str(&body).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+    };
+
+    let world = state
+        .core.world_service
+        .set_rule_system_definition(WorldId::from_uuid(uuid), RuleSystemDefinition::from(dto))
+        .await
+        .map_err(|e| {
+            if let Some(invalid) = e.downcast_ref::<InvalidRuleSystem>() {
+                (StatusCode::UNPROCESSABLE_ENTITY, invalid.to_string())
+            } else if let Some(not_found) = e.downcast_ref::<WorldError>() {
+                (StatusCode::NOT_FOUND, not_found.to_string())
+            } else {
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }
+        })?;
+
+    world
+        .rule_system_definition
+        .map(|definition| Json(definition.into()))
+        .ok_or_else(|| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Rule system definition was not stored".to_string(),
+            )
+        })
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -11,8 +11,10 @@ use crate::{
     application::dto::{SessionInfo, WorldSnapshot},
     application::ports::outbound::PlayerWorldSnapshot,
     application::services::world_service::WorldService,
-    domain::value_objects::{SessionId, WorldId},
+    domain::entities::RollLogEntry,
+    domain::value_objects::{RollId, SessionId, WorldId},
     infrastructure::state::AppState,
+    infrastructure::websocket_helpers::expire_conditions,
 };
 
 /// List all active sessions.
@@ -199,6 +201,7 @@ fn convert_to_internal_snapshot(player_snapshot: &PlayerWorldSnapshot) -> WorldS
         name: player_snapshot.world.name.clone(),
         description: player_snapshot.world.description.clone(),
         rule_system: RuleSystemConfig::from(player_snapshot.world.rule_system.clone()),
+        rule_system_definition: None,
         created_at: now,
         updated_at: now,
     };
@@ -247,6 +250,7 @@ fn convert_to_internal_snapshot(player_snapshot: &PlayerWorldSnapshot) -> WorldS
                 current_archetype: CampbellArchetype::Ally,
                 archetype_history: Vec::new(),
                 stats: StatBlock::default(),
+                conditions: Default::default(),
                 is_alive: c.is_alive,
                 is_active: c.is_active,
             }
@@ -366,20 +370,25 @@ pub async fn advance_game_time(
         .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid session ID".to_string()))?;
     let session_id = SessionId::from_uuid(session_uuid);
 
-    let mut sessions = state.sessions.write().await;
-    let session = sessions
-        .get_session_mut(session_id)
-        .ok_or_else(|| (StatusCode::NOT_FOUND, "Session not found".to_string()))?;
+    let game_time = {
+        let mut sessions = state.sessions.write().await;
+        let session = sessions
+            .get_session_mut(session_id)
+            .ok_or_else(|| (StatusCode::NOT_FOUND, "Session not found".to_string()))?;
 
-    // Advance by hours and/or days
-    if req.hours > 0 {
-        session.advance_time_hours(req.hours);
-    }
-    if req.days > 0 {
-        session.advance_time_days(req.days);
-    }
+        // Advance by hours and/or days
+        if req.hours > 0 {
+            session.advance_time_hours(req.hours);
+        }
+        if req.days > 0 {
+            session.advance_time_days(req.days);
+        }
 
-    let game_time = session.game_time();
+        session.game_time().clone()
+    };
+
+    // Conditions that ran out in the time that passed end now
+    expire_conditions(&state, session_id).await;
 
     Ok(Json(GameTimeResponse {
         display: game_time.display_date(),
@@ -389,3 +398,123 @@ pub async fn advance_game_time(
         is_paused: game_time.is_paused(),
     }))
 }
+
+// =============================================================================
+// Roll Log Routes
+// =============================================================================
+
+/// Default number of rolls returned by the roll log listing
+const DEFAULT_ROLL_LOG_LIMIT: u32 = 100;
+
+/// Query parameters for listing a session's rolls
+#[derive(Debug, Clone, Deserialize)]
+pub struct RollLogQuery {
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+/// Response DTO for a logged roll
+#[derive(Debug, Clone, Serialize)]
+pub struct RollLogEntryResponse {
+    pub id: String,
+    pub session_id: String,
+    /// "challenge", "ad_hoc", "table" or "initiative"
+    pub purpose: String,
+    pub label: Option<String>,
+    pub roller: String,
+    pub formula: String,
+    /// Session seed, as a string since it does not fit in a JSON number
+    ///
+    /// Left out while the session is running, as it predicts every later roll.
+    pub seed: Option<String>,
+    pub sequence: u64,
+    /// Every die rolled in order, including dropped and rerolled dice
+    pub dice: Vec<i32>,
+    pub total: i32,
+    pub breakdown: String,
+    pub rolled_at: String,
+    /// Whether replaying the seed reproduces the recorded dice and total
+    pub verified: bool,
+}
+
+impl RollLogEntryResponse {
+    /// Build the response for a logged roll, with its seed if it is revealed
+    fn new(entry: RollLogEntry, seed_revealed: bool) -> Self {
+        let verified = entry.verify();
+        Self {
+            id: entry.id.to_string(),
+            session_id: entry.session_id.to_string(),
+            purpose: entry.purpose.as_str().to_string(),
+            label: entry.label,
+            roller: entry.roller,
+            formula: entry.formula,
+            seed: seed_revealed.then(|| entry.seed.to_string()),
+            sequence: entry.sequence,
+            dice: entry.dice,
+            total: entry.total,
+            breakdown: entry.breakdown,
+            rolled_at: entry.rolled_at.to_rfc3339(),
+            verified,
+        }
+    }
+}
+
+/// List the logged rolls for a session, most recent first
+///
+/// GET /api/sessions/{session_id}/rolls?limit=&offset=
+pub async fn list_session_rolls(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    Query(query): Query<RollLogQuery>,
+) -> Result<Json<Vec<RollLogEntryResponse>>, (StatusCode, String)> {
+    let session_uuid = uuid::Uuid::parse_str(&session_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid session ID".to_string()))?;
+    let session_id = SessionId::from_uuid(session_uuid);
+
+    let rolls = state
+        .game.dice_roll_service
+        .list_session_rolls(
+            session_id,
+            query.limit.unwrap_or(DEFAULT_ROLL_LOG_LIMIT),
+            query.offset.unwrap_or(0),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let seed_revealed = state.game.dice_roll_service.is_seed_revealed(session_id).await;
+
+    Ok(Json(
+        rolls
+            .into_iter()
+            .map(|entry| RollLogEntryResponse::new(entry, seed_revealed))
+            .collect(),
+    ))
+}
+
+/// Get a single logged roll, replaying it to check it against the record
+///
+/// GET /api/rolls/{roll_id}
+pub async fn get_roll(
+    State(state): State<Arc<AppState>>,
+    Path(roll_id): Path<String>,
+) -> Result<Json<RollLogEntryResponse>, (StatusCode, String)> {
+    let roll_uuid = uuid::Uuid::parse_str(&roll_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid roll ID".to_string()))?;
+
+    let entry = state
+        .game.dice_roll_service
+        .get_roll(RollId::from_uuid(roll_uuid))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Roll not found".to_string()))?;
+
+    let seed_revealed = state
+        .game
+        .dice_roll_service
+        .is_seed_revealed(entry.session_id)
+        .await;
+
+    Ok(Json(RollLogEntryResponse::new(entry, seed_revealed)))
+}
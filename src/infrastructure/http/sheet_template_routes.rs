@@ -7,20 +7,39 @@ use axum::{
     http::StatusCode,
     Json,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::application::services::WorldService;
+use crate::application::services::{PlayerCharacterService, SheetMigrationError, WorldService};
 use crate::application::dto::{
-    CreateFieldRequestDto, CreateSectionRequestDto, SheetTemplateResponseDto, SheetTemplateSummaryDto,
+    CharacterMigrationDto, CreateFieldRequestDto, CreateSectionRequestDto, InvalidSheetReportDto,
+    MigrationPreviewDto, SheetTemplateResponseDto, SheetTemplateSummaryDto,
+    UpdateSheetTemplateRequestDto,
 };
 use crate::domain::entities::{
-    CharacterSheetTemplate, SheetField, SheetSection, SheetTemplateId,
+    CharacterSheetTemplate, InvalidDerivedFields, SheetField, SheetSection, SheetTemplateId,
 };
-use crate::domain::value_objects::WorldId;
+use crate::domain::value_objects::{ProgressionTable, WorldId};
 use crate::infrastructure::state::AppState;
 // NOTE: sheet template request/response DTOs live in `application/dto/sheet_template.rs`.
 
+/// Map a template save error, reporting broken derived fields as a bad request
+fn save_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<InvalidDerivedFields>() {
+        Some(invalid) => (StatusCode::UNPROCESSABLE_ENTITY, invalid.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// Map a migration error, reporting refused migrations as a conflict
+fn migration_error(e: anyhow::Error) -> (StatusCode, String) {
+    match e.downcast_ref::<SheetMigrationError>() {
+        Some(refused) => (StatusCode::CONFLICT, refused.to_string()),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
 /// Get the sheet template for a world
 ///
 /// Returns the default template if one exists, or generates one based on the rule system.
@@ -50,8 +69,7 @@ pub async fn get_template(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or_else(|| (StatusCode::NOT_FOUND, "World not found".to_string()))?;
 
-    let template =
-        CharacterSheetTemplate::default_for_variant(world_id, &world.rule_system.variant);
+    let template = CharacterSheetTemplate::default_for_world(&world);
 
     Ok(Json(SheetTemplateResponseDto::from(template)))
 }
@@ -82,8 +100,7 @@ pub async fn list_templates(
 
     // If no templates exist, return the default template as a summary
     if templates.is_empty() {
-        let default =
-            CharacterSheetTemplate::default_for_variant(world_id, &world.rule_system.variant);
+        let default = CharacterSheetTemplate::default_for_world(&world);
         return Ok(Json(vec![SheetTemplateSummaryDto::from(default)]));
     }
 
@@ -157,15 +174,14 @@ pub async fn initialize_template(
     }
 
     // Create the default template
-    let template =
-        CharacterSheetTemplate::default_for_variant(world_id, &world.rule_system.variant);
+    let template = CharacterSheetTemplate::default_for_world(&world);
 
     // Save it
     state
                 .player.sheet_template_service
         .create(&template)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(save_error)?;
 
     Ok(Json(SheetTemplateResponseDto::from(template)))
 }
@@ -182,7 +198,7 @@ pub async fn add_section(
     let template_id = SheetTemplateId::from_string(template_id);
 
     // Get existing template
-    let mut template = state
+    let template = state
                 .player.sheet_template_service
         .get(&template_id)
         .await
@@ -218,14 +234,15 @@ pub async fn add_section(
     let max_order = template.sections.iter().map(|s| s.order).max().unwrap_or(0);
     section = section.with_order(max_order + 1);
 
-    template.sections.push(section);
+    let mut sections = template.sections.clone();
+    sections.push(section);
 
-    // Save updates
-    state
+    // Save as the next version
+    let template = state
                 .player.sheet_template_service
-        .update(&template)
+        .revise(&template, sections, &HashMap::new())
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(save_error)?;
 
     Ok(Json(SheetTemplateResponseDto::from(template)))
 }
@@ -242,7 +259,7 @@ pub async fn add_field(
     let template_id = SheetTemplateId::from_string(template_id);
 
     // Get existing template
-    let mut template = state
+    let template = state
                 .player.sheet_template_service
         .get(&template_id)
         .await
@@ -258,8 +275,8 @@ pub async fn add_field(
     }
 
     // Find the section
-    let section = template
-        .sections
+    let mut sections = template.sections.clone();
+    let section = sections
         .iter_mut()
         .find(|s| s.id == section_id)
         .ok_or_else(|| (StatusCode::NOT_FOUND, "Section not found".to_string()))?;
@@ -284,16 +301,186 @@ pub async fn add_field(
 
     section.fields.push(field);
 
-    // Save updates
-    state
+    // Save as the next version; the new field's default reaches existing
+    // sheets when the migration is applied
+    let template = state
                 .player.sheet_template_service
-        .update(&template)
+        .revise(&template, sections, &HashMap::new())
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(save_error)?;
 
     Ok(Json(SheetTemplateResponseDto::from(template)))
 }
 
+/// Get a template of a world, checking it belongs to the world
+async fn world_template(
+    state: &AppState,
+    world_id: &str,
+    template_id: String,
+) -> Result<CharacterSheetTemplate, (StatusCode, String)> {
+    let world_uuid = Uuid::parse_str(world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+    let world_id = WorldId::from_uuid(world_uuid);
+    let template_id = SheetTemplateId::from_string(template_id);
+
+    let template = state
+        .player
+        .sheet_template_service
+        .get(&template_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+
+    if template.world_id != world_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Template does not belong to this world".to_string(),
+        ));
+    }
+    Ok(template)
+}
+
+/// Replace a template's sections, saving it as the next version
+///
+/// The edit is queued as a migration; character sheets are left alone until
+/// it is applied.
+pub async fn update_template(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, template_id)): Path<(String, String)>,
+    Json(req): Json<UpdateSheetTemplateRequestDto>,
+) -> Result<Json<SheetTemplateResponseDto>, (StatusCode, String)> {
+    let mut template = world_template(&state, &world_id, template_id).await?;
+    if let Some(name) = req.name {
+        template.name = name;
+    }
+    if let Some(description) = req.description {
+        template.description = description;
+    }
+    if let Some(progression) = req.progression {
+        let progression: ProgressionTable = progression.into();
+        let problems = progression.problems();
+        if !problems.is_empty() {
+            return Err((StatusCode::UNPROCESSABLE_ENTITY, problems.join("; ")));
+        }
+        template.progression = Some(progression);
+    }
+
+    let sections = req.sections.into_iter().map(Into::into).collect();
+    let template = state
+        .player
+        .sheet_template_service
+        .revise(&template, sections, &req.renamed_fields)
+        .await
+        .map_err(save_error)?;
+
+    Ok(Json(SheetTemplateResponseDto::from(template)))
+}
+
+/// Preview what the template's pending migrations would change on each
+/// character in the world
+pub async fn preview_migration(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, template_id)): Path<(String, String)>,
+) -> Result<Json<MigrationPreviewDto>, (StatusCode, String)> {
+    let template = world_template(&state, &world_id, template_id).await?;
+
+    let characters = state
+        .player
+        .sheet_template_service
+        .preview_migration(&template)
+        .await
+        .map_err(migration_error)?;
+
+    Ok(Json(MigrationPreviewDto {
+        template_id: template.id.0,
+        version: template.version,
+        migrations: template.pending_migrations.into_iter().map(Into::into).collect(),
+        characters: characters.into_iter().map(CharacterMigrationDto::from).collect(),
+    }))
+}
+
+/// Apply the template's pending migrations to every character in the world
+pub async fn apply_migration(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, template_id)): Path<(String, String)>,
+) -> Result<Json<SheetTemplateResponseDto>, (StatusCode, String)> {
+    let template = world_template(&state, &world_id, template_id).await?;
+    if template.pending_migrations.is_empty() {
+        return Err((
+            StatusCode::CONFLICT,
+            "Template has no pending migrations".to_string(),
+        ));
+    }
+
+    let template = state
+        .player
+        .sheet_template_service
+        .apply_migration(&template)
+        .await
+        .map_err(migration_error)?;
+
+    Ok(Json(SheetTemplateResponseDto::from(template)))
+}
+
+/// Restore the character sheets from before the last applied migration
+pub async fn rollback_migration(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, template_id)): Path<(String, String)>,
+) -> Result<Json<SheetTemplateResponseDto>, (StatusCode, String)> {
+    let template = world_template(&state, &world_id, template_id).await?;
+    if template.applied_migrations.is_empty() {
+        return Err((
+            StatusCode::CONFLICT,
+            "Template has no applied migration to roll back".to_string(),
+        ));
+    }
+
+    let template = state
+        .player
+        .sheet_template_service
+        .rollback_migration(&template)
+        .await
+        .map_err(migration_error)?;
+
+    Ok(Json(SheetTemplateResponseDto::from(template)))
+}
+
+/// List the world's player characters whose sheets no longer fit a template
+///
+/// Template authors check this after editing a template.
+pub async fn invalid_sheet_report(
+    State(state): State<Arc<AppState>>,
+    Path((world_id, template_id)): Path<(String, String)>,
+) -> Result<Json<Vec<InvalidSheetReportDto>>, (StatusCode, String)> {
+    let world_uuid = Uuid::parse_str(&world_id)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid world ID".to_string()))?;
+    let world_id = WorldId::from_uuid(world_uuid);
+    let template_id = SheetTemplateId::from_string(template_id);
+
+    let template = state
+                .player.sheet_template_service
+        .get(&template_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Template not found".to_string()))?;
+
+    // Verify template belongs to world
+    if template.world_id != world_id {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Template does not belong to this world".to_string(),
+        ));
+    }
+
+    let invalid = state
+                .player.player_character_service
+        .invalid_sheets(&template)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(invalid.into_iter().map(InvalidSheetReportDto::from).collect()))
+}
+
 /// Delete a template
 pub async fn delete_template(
     State(state): State<Arc<AppState>>,
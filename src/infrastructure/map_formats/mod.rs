@@ -0,0 +1,28 @@
+//! Map file formats
+//!
+//! Reads maps drawn in other tools onto the grid, and writes grid maps back
+//! out for them:
+//! - [`parse_uvtt`]: Universal VTT files (`.dd2vtt`, `.uvtt`), as exported
+//!   by Dungeondraft, with walls, doors, lights and the rendered image
+//! - [`parse_tiled`] / [`to_tiled`]: Tiled JSON maps, read and written
+
+mod tiled;
+mod uvtt;
+
+pub use tiled::{parse_tiled, to_tiled, TiledMap};
+pub use uvtt::parse_uvtt;
+
+use crate::domain::entities::GridMapError;
+
+/// A map file that cannot be read onto the grid
+#[derive(Debug, thiserror::Error)]
+pub enum MapFormatError {
+    #[error("Invalid map file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid embedded data: {0}")]
+    Encoding(#[from] base64::DecodeError),
+    #[error("Unsupported map: {0}")]
+    Unsupported(String),
+    #[error("{0}")]
+    Size(#[from] GridMapError),
+}
@@ -0,0 +1,605 @@
+//! Tiled JSON reader and writer
+//!
+//! Tiled maps are read as follows:
+//! - The first tile layer drawn on a cell gives its tilesheet index
+//! - Terrain, cover, elevation and passability come from `terrain`,
+//!   `cover`, `elevation` and `passable` properties, set on a tile layer
+//!   for all its tiles or on single tiles of a tileset; later layers win
+//! - Objects whose class (or type) is `wall` turn the tiles they cover to
+//!   walls, `cover` gives them cover (its `cover` property, light if not
+//!   set) and `door` puts a door at their centre, locked if its `locked`
+//!   property is true
+//!
+//! Grid maps are written with a "Tiles" layer drawn from the map's
+//! tilesheet and a hidden "Terrain" layer whose tiles carry the properties
+//! above, so a written map reads back the same.
+
+use std::collections::{BTreeMap, HashMap};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::MapFormatError;
+use crate::domain::entities::{GridMap, ImportedMap, TerrainType, Tile};
+
+/// Bits of a tile GID that flag flipped and rotated tiles
+const GID_FLAGS: u32 = 0xF000_0000;
+
+/// A Tiled JSON map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TiledMap {
+    #[serde(rename = "type", default = "map_type")]
+    pub map_type: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default = "orthogonal")]
+    pub orientation: String,
+    #[serde(default = "right_down")]
+    pub renderorder: String,
+    #[serde(default)]
+    pub infinite: bool,
+    pub width: u32,
+    pub height: u32,
+    pub tilewidth: u32,
+    pub tileheight: u32,
+    #[serde(default)]
+    pub nextlayerid: u32,
+    #[serde(default)]
+    pub nextobjectid: u32,
+    #[serde(default)]
+    pub layers: Vec<TiledLayer>,
+    #[serde(default)]
+    pub tilesets: Vec<TiledTileset>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<TiledProperty>,
+}
+
+fn map_type() -> String {
+    "map".to_string()
+}
+
+fn orthogonal() -> String {
+    "orthogonal".to_string()
+}
+
+fn right_down() -> String {
+    "right-down".to_string()
+}
+
+fn visible() -> bool {
+    true
+}
+
+fn opaque() -> f64 {
+    1.0
+}
+
+/// A layer of a Tiled map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TiledLayer {
+    Tilelayer {
+        #[serde(default)]
+        id: u32,
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        width: u32,
+        #[serde(default)]
+        height: u32,
+        #[serde(default)]
+        data: Option<TiledData>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encoding: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        compression: Option<String>,
+        #[serde(default = "visible")]
+        visible: bool,
+        #[serde(default = "opaque")]
+        opacity: f64,
+        #[serde(default)]
+        x: i32,
+        #[serde(default)]
+        y: i32,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        properties: Vec<TiledProperty>,
+    },
+    Objectgroup {
+        #[serde(default)]
+        id: u32,
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        objects: Vec<TiledObject>,
+        #[serde(default = "visible")]
+        visible: bool,
+        #[serde(default = "opaque")]
+        opacity: f64,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        properties: Vec<TiledProperty>,
+    },
+    Group {
+        #[serde(default)]
+        id: u32,
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        layers: Vec<TiledLayer>,
+    },
+    /// Image layers point at files the engine cannot reach, so are skipped
+    Imagelayer {
+        #[serde(default)]
+        id: u32,
+        #[serde(default)]
+        name: String,
+    },
+}
+
+/// A tile layer's cells: an array of GIDs, or them encoded as a string
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TiledData {
+    Gids(Vec<u32>),
+    Encoded(String),
+}
+
+/// An object on a Tiled object layer, in pixels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TiledObject {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub object_type: String,
+    #[serde(default)]
+    pub class: String,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub width: f64,
+    #[serde(default)]
+    pub height: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<TiledProperty>,
+}
+
+/// A tileset of a Tiled map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TiledTileset {
+    pub firstgid: u32,
+    #[serde(default)]
+    pub name: String,
+    /// Path of an external tileset file, which cannot be read here
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub tilecount: u32,
+    #[serde(default)]
+    pub tilewidth: u32,
+    #[serde(default)]
+    pub tileheight: u32,
+    #[serde(default)]
+    pub columns: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiles: Vec<TiledTile>,
+}
+
+/// Properties of one tile of a tileset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TiledTile {
+    pub id: u32,
+    #[serde(default)]
+    pub properties: Vec<TiledProperty>,
+}
+
+/// A custom property
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TiledProperty {
+    pub name: String,
+    #[serde(rename = "type", default = "string_type")]
+    pub property_type: String,
+    pub value: Value,
+}
+
+fn string_type() -> String {
+    "string".to_string()
+}
+
+impl TiledProperty {
+    fn new(name: &str, value: impl Into<Value>) -> Self {
+        let value = value.into();
+        let property_type = match &value {
+            Value::Bool(_) => "bool",
+            Value::Number(_) => "int",
+            _ => "string",
+        };
+        Self {
+            name: name.to_string(),
+            property_type: property_type.to_string(),
+            value,
+        }
+    }
+}
+
+/// What the `terrain`, `cover`, `elevation` and `passable` properties say
+/// about a tile
+#[derive(Debug, Clone, Copy, Default)]
+struct TileTraits {
+    terrain: Option<TerrainType>,
+    cover: Option<u8>,
+    elevation: Option<i32>,
+    passable: Option<bool>,
+}
+
+impl TileTraits {
+    fn from_properties(properties: &[TiledProperty]) -> Self {
+        let mut traits = Self::default();
+        for property in properties {
+            match (property.name.as_str(), &property.value) {
+                ("terrain", Value::String(terrain)) => traits.terrain = terrain_from_name(terrain),
+                ("cover", value) => traits.cover = value.as_u64().map(|cover| cover.min(2) as u8),
+                ("elevation", value) => traits.elevation = value.as_i64().map(|elevation| elevation as i32),
+                ("passable", Value::Bool(passable)) => traits.passable = Some(*passable),
+                _ => {}
+            }
+        }
+        traits
+    }
+
+    fn is_empty(&self) -> bool {
+        self.terrain.is_none() && self.cover.is_none() && self.elevation.is_none() && self.passable.is_none()
+    }
+
+    fn apply(&self, tile: &mut Tile) {
+        if let Some(terrain) = self.terrain {
+            *tile = Tile::new(terrain, tile.tile_index).with_elevation(tile.elevation);
+        }
+        if let Some(cover) = self.cover {
+            tile.cover_value = cover;
+        }
+        if let Some(elevation) = self.elevation {
+            tile.elevation = elevation;
+        }
+        if let Some(passable) = self.passable {
+            tile.passable = passable;
+        }
+    }
+}
+
+fn terrain_from_name(name: &str) -> Option<TerrainType> {
+    match name.to_lowercase().as_str() {
+        "ground" => Some(TerrainType::Ground),
+        "water" => Some(TerrainType::Water),
+        "wall" => Some(TerrainType::Wall),
+        "difficult" => Some(TerrainType::Difficult),
+        "hazard" => Some(TerrainType::Hazard),
+        "pit" => Some(TerrainType::Pit),
+        _ => None,
+    }
+}
+
+fn terrain_name(terrain: TerrainType) -> &'static str {
+    match terrain {
+        TerrainType::Ground => "ground",
+        TerrainType::Water => "water",
+        TerrainType::Wall => "wall",
+        TerrainType::Difficult => "difficult",
+        TerrainType::Hazard => "hazard",
+        TerrainType::Pit => "pit",
+    }
+}
+
+/// Every tile layer and object, groups flattened, in drawing order
+fn flatten(layers: &[TiledLayer]) -> Vec<&TiledLayer> {
+    layers
+        .iter()
+        .flat_map(|layer| match layer {
+            TiledLayer::Group { layers, .. } => flatten(layers),
+            layer => vec![layer],
+        })
+        .collect()
+}
+
+fn decode_gids(
+    data: &TiledData,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+) -> Result<Vec<u32>, MapFormatError> {
+    match (data, encoding, compression) {
+        (TiledData::Gids(gids), _, _) => Ok(gids.clone()),
+        (_, _, Some(compression)) if !compression.is_empty() => Err(MapFormatError::Unsupported(
+            format!("{} compressed layers; save the map with CSV layer data", compression),
+        )),
+        (TiledData::Encoded(text), Some("base64"), _) => {
+            let bytes = base64::engine::general_purpose::STANDARD.decode(text.trim())?;
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|gid| u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]]))
+                .collect())
+        }
+        (TiledData::Encoded(text), _, _) => Ok(text
+            .split(',')
+            .filter_map(|gid| gid.trim().parse().ok())
+            .collect()),
+    }
+}
+
+/// The tiles of a rectangle given in pixels, clamped to the map
+fn tiles_under(map: &ImportedMap, object: &TiledObject) -> Vec<(usize, usize)> {
+    let (tile_width, tile_height) = (f64::from(map.tile_size.max(1)), f64::from(map.tile_size.max(1)));
+    let first_x = (object.x / tile_width).floor().max(0.0) as u32;
+    let first_y = (object.y / tile_height).floor().max(0.0) as u32;
+    let last_x = (((object.x + object.width) / tile_width).ceil() as u32)
+        .max(first_x.saturating_add(1))
+        .min(map.width);
+    let last_y = (((object.y + object.height) / tile_height).ceil() as u32)
+        .max(first_y.saturating_add(1))
+        .min(map.height);
+    (first_y..last_y)
+        .flat_map(|y| (first_x..last_x).map(move |x| (x as usize, y as usize)))
+        .collect()
+}
+
+/// Read a Tiled JSON map onto the grid
+pub fn parse_tiled(text: &str) -> Result<ImportedMap, MapFormatError> {
+    let tiled: TiledMap = serde_json::from_str(text)?;
+    if tiled.orientation != "orthogonal" {
+        return Err(MapFormatError::Unsupported(format!("{} maps", tiled.orientation)));
+    }
+    if tiled.infinite {
+        return Err(MapFormatError::Unsupported("infinite maps".to_string()));
+    }
+    let mut map = ImportedMap::new(tiled.width, tiled.height, tiled.tilewidth)?;
+
+    let mut tilesets: Vec<&TiledTileset> = tiled.tilesets.iter().collect();
+    tilesets.sort_by_key(|tileset| std::cmp::Reverse(tileset.firstgid));
+    let tile_traits: HashMap<u32, TileTraits> = tiled
+        .tilesets
+        .iter()
+        .flat_map(|tileset| {
+            tileset.tiles.iter().map(move |tile| {
+                let gid = tileset.firstgid.checked_add(tile.id).ok_or_else(|| {
+                    MapFormatError::Unsupported(format!(
+                        "tile {} of the tileset at GID {} is past the last GID",
+                        tile.id, tileset.firstgid
+                    ))
+                })?;
+                Ok((gid, TileTraits::from_properties(&tile.properties)))
+            })
+        })
+        .collect::<Result<_, MapFormatError>>()?;
+
+    let cells = (map.width * map.height) as usize;
+    let mut drawn = vec![false; cells];
+    let layers = flatten(&tiled.layers);
+    for layer in &layers {
+        let TiledLayer::Tilelayer {
+            data: Some(data),
+            encoding,
+            compression,
+            properties,
+            ..
+        } = layer
+        else {
+            continue;
+        };
+        let layer_traits = TileTraits::from_properties(properties);
+        let gids = decode_gids(data, encoding.as_deref(), compression.as_deref())?;
+        for (cell, gid) in gids.into_iter().take(cells).enumerate() {
+            let gid = gid & !GID_FLAGS;
+            if gid == 0 {
+                continue;
+            }
+            let tile = &mut map.tiles[cell / map.width as usize][cell % map.width as usize];
+            if !drawn[cell] {
+                let firstgid = tilesets
+                    .iter()
+                    .find(|tileset| tileset.firstgid <= gid)
+                    .map_or(1, |tileset| tileset.firstgid);
+                tile.tile_index = gid - firstgid;
+                drawn[cell] = true;
+            }
+            layer_traits.apply(tile);
+            if let Some(traits) = tile_traits.get(&gid).filter(|traits| !traits.is_empty()) {
+                traits.apply(tile);
+            }
+        }
+    }
+
+    for layer in &layers {
+        let TiledLayer::Objectgroup { objects, .. } = layer else {
+            continue;
+        };
+        for object in objects {
+            let class = if object.class.is_empty() { &object.object_type } else { &object.class };
+            let traits = TileTraits::from_properties(&object.properties);
+            match class.to_lowercase().as_str() {
+                "wall" => {
+                    for (x, y) in tiles_under(&map, object) {
+                        let tile = &mut map.tiles[y][x];
+                        *tile = Tile::new(TerrainType::Wall, tile.tile_index).with_elevation(tile.elevation);
+                    }
+                }
+                "cover" => {
+                    for (x, y) in tiles_under(&map, object) {
+                        map.tiles[y][x].cover_value = traits.cover.unwrap_or(1);
+                    }
+                }
+                "door" => {
+                    let locked = object
+                        .properties
+                        .iter()
+                        .any(|property| property.name == "locked" && property.value == Value::Bool(true));
+                    map.add_door(
+                        (object.x + object.width / 2.0) / f64::from(tiled.tilewidth.max(1)),
+                        (object.y + object.height / 2.0) / f64::from(tiled.tileheight.max(1)),
+                        locked,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+/// Write a grid map as a Tiled JSON map
+pub fn to_tiled(grid_map: &GridMap) -> TiledMap {
+    let tiles: Vec<&Tile> = grid_map.tiles.iter().flatten().collect();
+    let tile_count = tiles.iter().map(|tile| tile.tile_index + 1).max().unwrap_or(1);
+
+    // One terrain tile for each mix of terrain, cover, elevation and passability
+    let mut kinds: BTreeMap<(&'static str, u8, i32, bool), u32> = BTreeMap::new();
+    for tile in &tiles {
+        let next = kinds.len() as u32;
+        kinds
+            .entry((terrain_name(tile.terrain_type), tile.cover_value, tile.elevation, tile.passable))
+            .or_insert(next);
+    }
+    let terrain_firstgid = tile_count + 1;
+
+    let tile_layer = |id: u32, name: &str, data: Vec<u32>, visible: bool| TiledLayer::Tilelayer {
+        id,
+        name: name.to_string(),
+        width: grid_map.width,
+        height: grid_map.height,
+        data: Some(TiledData::Gids(data)),
+        encoding: None,
+        compression: None,
+        visible,
+        opacity: 1.0,
+        x: 0,
+        y: 0,
+        properties: Vec::new(),
+    };
+    let tilesheet = tile_layer(1, "Tiles", tiles.iter().map(|tile| tile.tile_index + 1).collect(), true);
+    let terrain = tile_layer(
+        2,
+        "Terrain",
+        tiles
+            .iter()
+            .map(|tile| {
+                terrain_firstgid
+                    + kinds[&(terrain_name(tile.terrain_type), tile.cover_value, tile.elevation, tile.passable)]
+            })
+            .collect(),
+        false,
+    );
+
+    let mut terrain_tiles: Vec<TiledTile> = kinds
+        .iter()
+        .map(|(&(terrain, cover, elevation, passable), &id)| TiledTile {
+            id,
+            properties: vec![
+                TiledProperty::new("terrain", terrain),
+                TiledProperty::new("cover", cover),
+                TiledProperty::new("elevation", elevation),
+                TiledProperty::new("passable", passable),
+            ],
+        })
+        .collect();
+    terrain_tiles.sort_by_key(|tile| tile.id);
+
+    TiledMap {
+        map_type: map_type(),
+        version: "1.10".to_string(),
+        orientation: orthogonal(),
+        renderorder: right_down(),
+        infinite: false,
+        width: grid_map.width,
+        height: grid_map.height,
+        tilewidth: grid_map.tile_size,
+        tileheight: grid_map.tile_size,
+        nextlayerid: 3,
+        nextobjectid: 1,
+        layers: vec![tilesheet, terrain],
+        tilesets: vec![
+            TiledTileset {
+                firstgid: 1,
+                name: "Tilesheet".to_string(),
+                source: None,
+                image: Some(grid_map.tilesheet_asset.clone()),
+                tilecount: tile_count,
+                tilewidth: grid_map.tile_size,
+                tileheight: grid_map.tile_size,
+                columns: 0,
+                tiles: Vec::new(),
+            },
+            TiledTileset {
+                firstgid: terrain_firstgid,
+                name: "Terrain".to_string(),
+                source: None,
+                image: None,
+                tilecount: kinds.len() as u32,
+                tilewidth: grid_map.tile_size,
+                tileheight: grid_map.tile_size,
+                columns: 0,
+                tiles: terrain_tiles,
+            },
+        ],
+        properties: vec![TiledProperty::new("name", grid_map.name.clone())],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::WorldId;
+
+    #[test]
+    fn test_written_maps_read_back_the_same() {
+        let mut grid_map = GridMap::new(WorldId::new(), "Ford", 5, 3, "ford.png");
+        grid_map.set_tile(1, 0, Tile::new(TerrainType::Water, 7).with_elevation(-1));
+        grid_map.set_tile(3, 2, Tile::new(TerrainType::Wall, 2));
+        grid_map.set_tile(4, 1, Tile::new(TerrainType::Ground, 0).with_cover(1));
+
+        let text = serde_json::to_string(&to_tiled(&grid_map)).unwrap();
+        let map = parse_tiled(&text).unwrap();
+        assert_eq!((map.width, map.height, map.tile_size), (5, 3, 32));
+        assert_eq!(map.tiles, grid_map.tiles);
+    }
+
+    #[test]
+    fn test_objects_make_walls_cover_and_doors() {
+        let text = r#"{
+            "width": 4, "height": 2, "tilewidth": 16, "tileheight": 16,
+            "orientation": "orthogonal", "infinite": false,
+            "tilesets": [{"firstgid": 1, "tiles": [
+                {"id": 2, "properties": [{"name": "terrain", "type": "string", "value": "difficult"}]}
+            ]}],
+            "layers": [
+                {"type": "tilelayer", "name": "Floor", "width": 4, "height": 2,
+                 "data": [1, 1, 3, 1, 1, 1, 1, 1]},
+                {"type": "objectgroup", "name": "Blocking", "objects": [
+                    {"id": 1, "type": "wall", "x": 16, "y": 0, "width": 16, "height": 32},
+                    {"id": 2, "class": "cover", "x": 48, "y": 16, "width": 16, "height": 16,
+                     "properties": [{"name": "cover", "type": "int", "value": 2}]},
+                    {"id": 3, "type": "door", "x": 16, "y": 16, "width": 16, "height": 16,
+                     "properties": [{"name": "locked", "type": "bool", "value": true}]},
+                    {"id": 4, "type": "wall", "x": 1e12, "y": 1e12, "width": 16, "height": 16}
+                ]}
+            ]
+        }"#;
+
+        let map = parse_tiled(text).unwrap();
+        assert_eq!(map.tiles[0][2].terrain_type, TerrainType::Difficult);
+        assert_eq!(map.tiles[0][2].tile_index, 2);
+        assert!(!map.tiles[0][1].passable);
+        assert!(map.tiles[1][1].passable);
+        assert_eq!(map.tiles[1][3].cover_value, 2);
+        assert_eq!(map.doors.len(), 1);
+        assert!(map.doors[0].locked);
+        assert_eq!(map.doors[0].position, (1, 1));
+
+        let infinite = text.replace("\"infinite\": false", "\"infinite\": true");
+        assert!(parse_tiled(&infinite).is_err());
+
+        let overflowing = text.replace("\"firstgid\": 1", "\"firstgid\": 4294967295");
+        assert!(matches!(parse_tiled(&overflowing), Err(MapFormatError::Unsupported(_))));
+    }
+}
@@ -0,0 +1,144 @@
+//! Universal VTT reader
+//!
+//! Universal VTT is JSON: the map's size in grid squares, walls and object
+//! outlines as lines in grid units, doors ("portals"), lights and the
+//! rendered map as a base64 image. Doors in the format have no locks, so
+//! every door is read as unlocked.
+
+use base64::Engine;
+use serde::Deserialize;
+
+use super::MapFormatError;
+use crate::domain::entities::{ImportedMap, MapImage, MapLight};
+
+#[derive(Debug, Deserialize)]
+struct UvttFile {
+    resolution: UvttResolution,
+    #[serde(default)]
+    line_of_sight: Vec<Vec<UvttPoint>>,
+    #[serde(default)]
+    objects_line_of_sight: Vec<Vec<UvttPoint>>,
+    #[serde(default)]
+    portals: Vec<UvttPortal>,
+    #[serde(default)]
+    lights: Vec<UvttLight>,
+    #[serde(default)]
+    image: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UvttResolution {
+    #[serde(default)]
+    map_origin: UvttPoint,
+    map_size: UvttPoint,
+    pixels_per_grid: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct UvttPoint {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct UvttPortal {
+    position: UvttPoint,
+}
+
+#[derive(Debug, Deserialize)]
+struct UvttLight {
+    position: UvttPoint,
+    range: f64,
+    #[serde(default)]
+    color: String,
+}
+
+/// Read a Universal VTT file onto the grid
+pub fn parse_uvtt(text: &str) -> Result<ImportedMap, MapFormatError> {
+    let file: UvttFile = serde_json::from_str(text)?;
+    let resolution = &file.resolution;
+    if resolution.map_size.x <= 0.0 || resolution.map_size.y <= 0.0 {
+        return Err(MapFormatError::Unsupported("map has no size".to_string()));
+    }
+
+    let mut map = ImportedMap::new(
+        resolution.map_size.x.ceil() as u32,
+        resolution.map_size.y.ceil() as u32,
+        resolution.pixels_per_grid,
+    )?;
+    let origin = resolution.map_origin;
+    let local = |point: &UvttPoint| (point.x - origin.x, point.y - origin.y);
+
+    for outline in file.line_of_sight.iter().chain(&file.objects_line_of_sight) {
+        for pair in outline.windows(2) {
+            map.add_wall(local(&pair[0]), local(&pair[1]), 0);
+        }
+    }
+    for portal in &file.portals {
+        let (x, y) = local(&portal.position);
+        map.add_door(x, y, false);
+    }
+    map.lights = file
+        .lights
+        .iter()
+        .map(|light| {
+            let (x, y) = local(&light.position);
+            MapLight {
+                x,
+                y,
+                range: light.range,
+                color: light.color.clone(),
+            }
+        })
+        .collect();
+    if let Some(image) = file.image.filter(|image| !image.is_empty()) {
+        // Some exporters give a data URL rather than bare base64
+        let encoded = image.rsplit(',').next().unwrap_or_default();
+        map.image = Some(MapImage::new(
+            base64::engine::general_purpose::STANDARD.decode(encoded.trim())?,
+        ));
+    }
+
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::TerrainType;
+
+    #[test]
+    fn test_parse_uvtt() {
+        let text = r#"{
+            "format": 0.3,
+            "resolution": {
+                "map_origin": {"x": 0, "y": 0},
+                "map_size": {"x": 8, "y": 4},
+                "pixels_per_grid": 70
+            },
+            "line_of_sight": [[{"x": 4.5, "y": 0}, {"x": 4.5, "y": 3.9}]],
+            "portals": [{
+                "position": {"x": 4.5, "y": 2.5},
+                "bounds": [{"x": 4.5, "y": 2}, {"x": 4.5, "y": 3}],
+                "rotation": 0, "closed": true, "freestanding": false
+            }],
+            "lights": [{"position": {"x": 1, "y": 1}, "range": 3, "intensity": 1, "color": "ffeccd8b", "shadows": true}],
+            "image": "iVBORw0KGgo="
+        }"#;
+
+        let map = parse_uvtt(text).unwrap();
+        assert_eq!((map.width, map.height, map.tile_size), (8, 4, 70));
+        assert_eq!(map.tiles[0][4].terrain_type, TerrainType::Wall);
+        assert!(map.tiles[2][4].passable);
+        assert_eq!(map.doors.len(), 1);
+        assert_eq!(map.doors[0].position, (4, 2));
+        assert_eq!(map.lights[0].range, 3.0);
+        assert_eq!(map.image.as_ref().map(|image| image.extension), Some("png"));
+
+        let (areas, doorways) = map.divide();
+        assert_eq!(areas.len(), 2);
+        assert_eq!(doorways.len(), 1);
+
+        assert!(parse_uvtt("{}").is_err());
+    }
+}
@@ -11,12 +11,14 @@
 //! - Session: Game session management
 //! - Event Bus: Event publishing and subscription infrastructure
 //! - Repositories: Additional persistence implementations
+//! - Map Formats: Universal VTT and Tiled map import and export
 
 pub mod comfyui;
 pub mod config;
 pub mod event_bus;
 pub mod export;
 pub mod http;
+pub mod map_formats;
 pub mod ollama;
 pub mod persistence;
 pub mod queue_workers;
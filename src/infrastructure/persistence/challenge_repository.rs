@@ -8,16 +8,21 @@
 //! - `(Challenge)-[:REQUIRES_COMPLETION_OF {success_required}]->(Challenge)` - Prerequisites
 //! - `(Challenge)-[:AVAILABLE_AT {always_available, time_restriction}]->(Location)` - Location availability
 //! - `(Challenge)-[:ON_SUCCESS_UNLOCKS]->(Location)` - Location unlocked on success
+//! - `(Challenge)-[:HAS_PROGRESS]->(ComplexChallengeProgress)` - Progress on a complex challenge
 
 use anyhow::Result;
 use async_trait::async_trait;
 use neo4rs::{query, Row};
 
 use super::connection::Neo4jConnection;
-use crate::application::dto::{DifficultyRequestDto, OutcomesRequestDto, TriggerConditionRequestDto};
+use crate::application::dto::{
+    ComplexChallengeRollDto, ComplexChallengeSettingsDto, DifficultyRequestDto, OutcomesRequestDto,
+    TriggerConditionRequestDto,
+};
 use crate::application::ports::outbound::ChallengeRepositoryPort;
 use crate::domain::entities::{
     Challenge, ChallengeLocationAvailability, ChallengePrerequisite, ChallengeType,
+    ComplexChallengeProgress, ComplexChallengeStatus,
 };
 use crate::domain::value_objects::{ChallengeId, LocationId, SceneId, SkillId, WorldId};
 
@@ -55,6 +60,7 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
                 .collect::<Vec<_>>(),
         )?;
         let tags_json = serde_json::to_string(&challenge.tags)?;
+        let complex_settings_json = complex_settings_json(challenge)?;
 
         let q = query(
             "MATCH (w:World {id: $world_id})
@@ -70,7 +76,8 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
                 active: $active,
                 challenge_order: $challenge_order,
                 is_favorite: $is_favorite,
-                tags_json: $tags_json
+                tags_json: $tags_json,
+                complex_settings_json: $complex_settings_json
             })
             CREATE (w)-[:CONTAINS_CHALLENGE]->(c)
             RETURN c.id as id",
@@ -89,7 +96,8 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
         .param("active", challenge.active)
         .param("challenge_order", challenge.order as i64)
         .param("is_favorite", challenge.is_favorite)
-        .param("tags_json", tags_json);
+        .param("tags_json", tags_json)
+        .param("complex_settings_json", complex_settings_json);
 
         self.connection.graph().run(q).await?;
         tracing::debug!("Created challenge: {}", challenge.name);
@@ -216,6 +224,7 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
                 .collect::<Vec<_>>(),
         )?;
         let tags_json = serde_json::to_string(&challenge.tags)?;
+        let complex_settings_json = complex_settings_json(challenge)?;
 
         let q = query(
             "MATCH (c:Challenge {id: $id})
@@ -228,7 +237,8 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
                 c.active = $active,
                 c.challenge_order = $challenge_order,
                 c.is_favorite = $is_favorite,
-                c.tags_json = $tags_json
+                c.tags_json = $tags_json,
+                c.complex_settings_json = $complex_settings_json
             RETURN c.id as id",
         )
         .param("id", challenge.id.to_string())
@@ -244,7 +254,8 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
         .param("active", challenge.active)
         .param("challenge_order", challenge.order as i64)
         .param("is_favorite", challenge.is_favorite)
-        .param("tags_json", tags_json);
+        .param("tags_json", tags_json)
+        .param("complex_settings_json", complex_settings_json);
 
         self.connection.graph().run(q).await?;
         tracing::debug!("Updated challenge: {}", challenge.name);
@@ -254,7 +265,8 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
     async fn delete(&self, id: ChallengeId) -> Result<()> {
         let q = query(
             "MATCH (c:Challenge {id: $id})
-            DETACH DELETE c",
+            OPTIONAL MATCH (c)-[:HAS_PROGRESS]->(p:ComplexChallengeProgress)
+            DETACH DELETE p, c",
         )
         .param("id", id.to_string());
 
@@ -659,6 +671,79 @@ impl ChallengeRepositoryPort for Neo4jChallengeRepository {
         );
         Ok(())
     }
+
+    // -------------------------------------------------------------------------
+    // Complex Challenge Progress (HAS_PROGRESS)
+    // -------------------------------------------------------------------------
+
+    async fn get_complex_progress(
+        &self,
+        challenge_id: ChallengeId,
+    ) -> Result<Option<ComplexChallengeProgress>> {
+        let q = query(
+            "MATCH (c:Challenge {id: $challenge_id})-[:HAS_PROGRESS]->(p:ComplexChallengeProgress)
+            RETURN p",
+        )
+        .param("challenge_id", challenge_id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+
+        if let Some(row) = result.next().await? {
+            Ok(Some(row_to_complex_progress(row, challenge_id)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn save_complex_progress(&self, progress: &ComplexChallengeProgress) -> Result<()> {
+        let rolls_json = serde_json::to_string(
+            &progress
+                .rolls
+                .iter()
+                .cloned()
+                .map(ComplexChallengeRollDto::from)
+                .collect::<Vec<_>>(),
+        )?;
+
+        let q = query(
+            "MATCH (c:Challenge {id: $challenge_id})
+            MERGE (c)-[:HAS_PROGRESS]->(p:ComplexChallengeProgress)
+            SET p.successes = $successes,
+                p.failures = $failures,
+                p.status = $status,
+                p.rolls_json = $rolls_json,
+                p.started_at = $started_at,
+                p.updated_at = $updated_at",
+        )
+        .param("challenge_id", progress.challenge_id.to_string())
+        .param("successes", progress.successes as i64)
+        .param("failures", progress.failures as i64)
+        .param("status", format!("{:?}", progress.status))
+        .param("rolls_json", rolls_json)
+        .param("started_at", progress.started_at.to_rfc3339())
+        .param("updated_at", progress.updated_at.to_rfc3339());
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!(
+            "Saved progress for complex challenge {}: {} successes, {} failures",
+            progress.challenge_id,
+            progress.successes,
+            progress.failures
+        );
+        Ok(())
+    }
+
+    async fn clear_complex_progress(&self, challenge_id: ChallengeId) -> Result<()> {
+        let q = query(
+            "MATCH (c:Challenge {id: $challenge_id})-[:HAS_PROGRESS]->(p:ComplexChallengeProgress)
+            DETACH DELETE p",
+        )
+        .param("challenge_id", challenge_id.to_string());
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Cleared progress for complex challenge {}", challenge_id);
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -681,6 +766,7 @@ fn row_to_challenge(row: Row) -> Result<Challenge> {
     let order: i64 = node.get("challenge_order").unwrap_or(0);
     let is_favorite: bool = node.get("is_favorite").unwrap_or(false);
     let tags_json: String = node.get("tags_json").unwrap_or_else(|_| "[]".to_string());
+    let complex_settings_json: String = node.get("complex_settings_json").unwrap_or_default();
 
     Ok(Challenge {
         id: ChallengeId::from_uuid(uuid::Uuid::parse_str(&id_str)?),
@@ -698,9 +784,58 @@ fn row_to_challenge(row: Row) -> Result<Challenge> {
         order: order as u32,
         is_favorite,
         tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+        complex_settings: if complex_settings_json.is_empty() {
+            None
+        } else {
+            Some(serde_json::from_str::<ComplexChallengeSettingsDto>(&complex_settings_json)?.into())
+        },
     })
 }
 
+/// Serialize a challenge's complex settings (empty when it has none)
+fn complex_settings_json(challenge: &Challenge) -> Result<String> {
+    match &challenge.complex_settings {
+        Some(settings) => Ok(serde_json::to_string(&ComplexChallengeSettingsDto::from(
+            settings.clone(),
+        ))?),
+        None => Ok(String::new()),
+    }
+}
+
+/// Convert a Neo4j row to a ComplexChallengeProgress
+fn row_to_complex_progress(row: Row, challenge_id: ChallengeId) -> Result<ComplexChallengeProgress> {
+    let node: neo4rs::Node = row.get("p")?;
+
+    let successes: i64 = node.get("successes").unwrap_or(0);
+    let failures: i64 = node.get("failures").unwrap_or(0);
+    let status_str: String = node.get("status")?;
+    let rolls_json: String = node.get("rolls_json").unwrap_or_else(|_| "[]".to_string());
+    let started_at_str: String = node.get("started_at")?;
+    let updated_at_str: String = node.get("updated_at")?;
+
+    Ok(ComplexChallengeProgress {
+        challenge_id,
+        successes: successes as u32,
+        failures: failures as u32,
+        status: parse_complex_status(&status_str),
+        rolls: serde_json::from_str::<Vec<ComplexChallengeRollDto>>(&rolls_json)?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        started_at: chrono::DateTime::parse_from_rfc3339(&started_at_str)?.with_timezone(&chrono::Utc),
+        updated_at: chrono::DateTime::parse_from_rfc3339(&updated_at_str)?.with_timezone(&chrono::Utc),
+    })
+}
+
+/// Parse ComplexChallengeStatus from string
+fn parse_complex_status(s: &str) -> ComplexChallengeStatus {
+    match s {
+        "Succeeded" => ComplexChallengeStatus::Succeeded,
+        "Failed" => ComplexChallengeStatus::Failed,
+        _ => ComplexChallengeStatus::InProgress,
+    }
+}
+
 /// Parse ChallengeType from string
 fn parse_challenge_type(s: &str) -> ChallengeType {
     match s {
@@ -22,7 +22,8 @@ use crate::domain::entities::{
     InventoryItem, Item, StatBlock, Want,
 };
 use crate::domain::value_objects::{
-    ArchetypeChange, CampbellArchetype, CharacterId, ItemId, LocationId, RegionFrequency, RegionId,
+    ActiveCondition, ActiveConditions, ArchetypeChange, CampbellArchetype, CharacterId,
+    ConditionDuration, ItemId, LocationId, RegionFrequency, RegionId,
     RegionRelationship, RegionRelationshipType, RegionShift, SceneId, WantId, WorldId,
 };
 
@@ -175,6 +176,7 @@ impl Neo4jCharacterRepository {
                 c.current_archetype = $current_archetype,
                 c.archetype_history = $archetype_history,
                 c.stats = $stats,
+                c.conditions = $conditions,
                 c.is_alive = $is_alive,
                 c.is_active = $is_active
             RETURN c.id as id",
@@ -197,6 +199,7 @@ impl Neo4jCharacterRepository {
         )
         .param("archetype_history", archetype_history_json)
         .param("stats", stats_json)
+        .param("conditions", conditions_to_json(&character.conditions)?)
         .param("is_alive", character.is_alive)
         .param("is_active", character.is_active);
 
@@ -513,7 +516,8 @@ impl Neo4jCharacterRepository {
     // Inventory
     // =========================================================================
 
-    /// Add an item to character's inventory
+    /// Add an item from the catalogue of the character's world to their
+    /// inventory, adding to the quantity if they already hold some
     pub async fn add_inventory_item(
         &self,
         character_id: CharacterId,
@@ -527,13 +531,15 @@ impl Neo4jCharacterRepository {
             .unwrap_or_default();
 
         let q = query(
-            "MATCH (c:Character {id: $character_id}), (i:Item {id: $item_id})
-            CREATE (c)-[:POSSESSES {
-                quantity: $quantity,
-                equipped: $equipped,
-                acquired_at: $acquired_at,
-                acquisition_method: $acquisition_method
-            }]->(i)
+            "MATCH (c:Character {id: $character_id})
+            MATCH (w:World {id: c.world_id})-[:CONTAINS_ITEM]->(i:Item {id: $item_id})
+            MERGE (c)-[r:POSSESSES]->(i)
+            ON CREATE SET r.quantity = $quantity,
+                r.equipped = $equipped,
+                r.acquired_at = $acquired_at,
+                r.acquisition_method = $acquisition_method
+            ON MATCH SET r.quantity = r.quantity + $quantity,
+                r.equipped = r.equipped OR $equipped
             RETURN i.id as id",
         )
         .param("character_id", character_id.to_string())
@@ -543,7 +549,14 @@ impl Neo4jCharacterRepository {
         .param("acquired_at", Utc::now().to_rfc3339())
         .param("acquisition_method", method_str);
 
-        self.connection.graph().run(q).await?;
+        let mut result = self.connection.graph().execute(q).await?;
+        if result.next().await?.is_none() {
+            anyhow::bail!(
+                "Item {} is not in the catalogue of character {}'s world",
+                item_id,
+                character_id
+            );
+        }
         Ok(())
     }
 
@@ -1100,6 +1113,8 @@ fn row_to_character(row: Row) -> Result<Character> {
     let current_archetype_str: String = node.get("current_archetype")?;
     let archetype_history_json: String = node.get("archetype_history")?;
     let stats_json: String = node.get("stats")?;
+    // Characters saved before conditions existed have no property
+    let conditions_json: String = node.get("conditions").unwrap_or_default();
     let is_alive: bool = node.get("is_alive")?;
     let is_active: bool = node.get("is_active")?;
 
@@ -1113,6 +1128,7 @@ fn row_to_character(row: Row) -> Result<Character> {
             .map(Into::into)
             .collect();
     let stats: StatBlock = serde_json::from_str::<StatBlockStored>(&stats_json)?.into();
+    let conditions = conditions_from_json(&conditions_json)?;
 
     Ok(Character {
         id: CharacterId::from_uuid(id),
@@ -1133,6 +1149,7 @@ fn row_to_character(row: Row) -> Result<Character> {
         current_archetype,
         archetype_history,
         stats,
+        conditions,
         is_alive,
         is_active,
     })
@@ -1161,7 +1178,7 @@ fn row_to_want(row: &Row) -> Result<Want> {
     })
 }
 
-fn row_to_item(row: &Row) -> Result<Item> {
+pub(super) fn row_to_item(row: &Row) -> Result<Item> {
     let node: neo4rs::Node = row.get("i")?;
 
     let id_str: String = node.get("id")?;
@@ -1262,6 +1279,81 @@ impl From<ArchetypeChangeStored> for ArchetypeChange {
     }
 }
 
+/// Condition as stored on Character and PlayerCharacter nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct ActiveConditionStored {
+    pub name: String,
+    pub description: String,
+    pub duration: ConditionDurationStored,
+    pub roll_modifier: i32,
+    pub applied_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(super) enum ConditionDurationStored {
+    Rounds { rounds: u32 },
+    Minutes { minutes: u32 },
+    UntilRest,
+    Permanent,
+}
+
+impl From<ActiveCondition> for ActiveConditionStored {
+    fn from(value: ActiveCondition) -> Self {
+        let duration = match value.duration {
+            ConditionDuration::Rounds(rounds) => ConditionDurationStored::Rounds { rounds },
+            ConditionDuration::Minutes(minutes) => ConditionDurationStored::Minutes { minutes },
+            ConditionDuration::UntilRest => ConditionDurationStored::UntilRest,
+            ConditionDuration::Permanent => ConditionDurationStored::Permanent,
+        };
+        Self {
+            name: value.name,
+            description: value.description,
+            duration,
+            roll_modifier: value.roll_modifier,
+            applied_at: value.applied_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<ActiveConditionStored> for ActiveCondition {
+    fn from(value: ActiveConditionStored) -> Self {
+        let applied_at = chrono::DateTime::parse_from_rfc3339(&value.applied_at)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .unwrap_or_else(|_| chrono::Utc::now());
+        let duration = match value.duration {
+            ConditionDurationStored::Rounds { rounds } => ConditionDuration::Rounds(rounds),
+            ConditionDurationStored::Minutes { minutes } => ConditionDuration::Minutes(minutes),
+            ConditionDurationStored::UntilRest => ConditionDuration::UntilRest,
+            ConditionDurationStored::Permanent => ConditionDuration::Permanent,
+        };
+        Self {
+            name: value.name,
+            description: value.description,
+            duration,
+            roll_modifier: value.roll_modifier,
+            applied_at,
+        }
+    }
+}
+
+pub(super) fn conditions_to_json(conditions: &ActiveConditions) -> Result<String> {
+    let stored: Vec<ActiveConditionStored> = conditions.iter().cloned().map(Into::into).collect();
+    Ok(serde_json::to_string(&stored)?)
+}
+
+pub(super) fn conditions_from_json(json: &str) -> Result<ActiveConditions> {
+    if json.is_empty() {
+        return Ok(ActiveConditions::new());
+    }
+    let stored: Vec<ActiveConditionStored> = serde_json::from_str(json)?;
+    Ok(stored
+        .into_iter()
+        .map(ActiveCondition::from)
+        .collect::<Vec<_>>()
+        .into())
+}
+
 // =============================================================================
 // CharacterRepositoryPort Implementation
 // =============================================================================
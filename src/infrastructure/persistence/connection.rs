@@ -40,6 +40,7 @@ impl Neo4jConnection {
             "CREATE CONSTRAINT act_id IF NOT EXISTS FOR (a:Act) REQUIRE a.id IS UNIQUE",
             "CREATE CONSTRAINT item_id IF NOT EXISTS FOR (i:Item) REQUIRE i.id IS UNIQUE",
             "CREATE CONSTRAINT grid_map_id IF NOT EXISTS FOR (g:GridMap) REQUIRE g.id IS UNIQUE",
+            "CREATE CONSTRAINT grid_token_id IF NOT EXISTS FOR (t:GridToken) REQUIRE t.id IS UNIQUE",
         ];
 
         for constraint in constraints {
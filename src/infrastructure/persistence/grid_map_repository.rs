@@ -0,0 +1,258 @@
+//! Grid map repository implementation for Neo4j
+//!
+//! Tiles are stored on the map's node as a run-length encoded JSON string,
+//! read row by row. Most maps are large areas of the same tile, so this
+//! keeps even the biggest maps to a few kilobytes.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use neo4rs::{query, Row};
+use serde::{Deserialize, Serialize};
+
+use super::connection::Neo4jConnection;
+use crate::application::ports::outbound::GridMapRepositoryPort;
+use crate::domain::entities::{GridMap, TerrainType, Tile};
+use crate::domain::value_objects::{GridMapId, WorldId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum StoredTerrainType {
+    Ground,
+    Water,
+    Wall,
+    Difficult,
+    Hazard,
+    Pit,
+}
+
+impl From<TerrainType> for StoredTerrainType {
+    fn from(value: TerrainType) -> Self {
+        match value {
+            TerrainType::Ground => Self::Ground,
+            TerrainType::Water => Self::Water,
+            TerrainType::Wall => Self::Wall,
+            TerrainType::Difficult => Self::Difficult,
+            TerrainType::Hazard => Self::Hazard,
+            TerrainType::Pit => Self::Pit,
+        }
+    }
+}
+
+impl From<StoredTerrainType> for TerrainType {
+    fn from(value: StoredTerrainType) -> Self {
+        match value {
+            StoredTerrainType::Ground => Self::Ground,
+            StoredTerrainType::Water => Self::Water,
+            StoredTerrainType::Wall => Self::Wall,
+            StoredTerrainType::Difficult => Self::Difficult,
+            StoredTerrainType::Hazard => Self::Hazard,
+            StoredTerrainType::Pit => Self::Pit,
+        }
+    }
+}
+
+/// A run of identical tiles, stored as
+/// `[count, terrain, elevation, tile_index, passable, cover]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredTileRun(u32, StoredTerrainType, i32, u32, bool, u8);
+
+impl StoredTileRun {
+    fn new(tile: &Tile) -> Self {
+        Self(
+            1,
+            tile.terrain_type.into(),
+            tile.elevation,
+            tile.tile_index,
+            tile.passable,
+            tile.cover_value,
+        )
+    }
+
+    fn same_tile(&self, other: &Self) -> bool {
+        (self.1, self.2, self.3, self.4, self.5) == (other.1, other.2, other.3, other.4, other.5)
+    }
+
+    fn tile(&self) -> Tile {
+        Tile {
+            terrain_type: self.1.into(),
+            elevation: self.2,
+            tile_index: self.3,
+            passable: self.4,
+            cover_value: self.5,
+        }
+    }
+}
+
+/// Encode a map's tiles, row by row, as runs of identical tiles
+fn tiles_to_json(tiles: &[Vec<Tile>]) -> Result<String> {
+    let mut runs: Vec<StoredTileRun> = Vec::new();
+    for tile in tiles.iter().flatten() {
+        let run = StoredTileRun::new(tile);
+        match runs.last_mut() {
+            Some(last) if last.same_tile(&run) => last.0 += 1,
+            _ => runs.push(run),
+        }
+    }
+    Ok(serde_json::to_string(&runs)?)
+}
+
+/// Decode a map's tiles; runs that fall short of the map are filled with
+/// the default tile
+fn tiles_from_json(json: &str, width: u32, height: u32) -> Result<Vec<Vec<Tile>>> {
+    let runs: Vec<StoredTileRun> = serde_json::from_str(json)?;
+    let mut tiles = runs
+        .iter()
+        .flat_map(|run| std::iter::repeat_n(run.tile(), run.0 as usize));
+
+    Ok((0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| tiles.next().unwrap_or_default())
+                .collect()
+        })
+        .collect())
+}
+
+/// Repository for GridMap operations
+pub struct Neo4jGridMapRepository {
+    connection: Neo4jConnection,
+}
+
+impl Neo4jGridMapRepository {
+    pub fn new(connection: Neo4jConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create or replace a grid map
+    pub async fn save(&self, grid_map: &GridMap) -> Result<()> {
+        let tiles_json = tiles_to_json(&grid_map.tiles)?;
+
+        let q = query(
+            "MATCH (w:World {id: $world_id})
+            MERGE (g:GridMap {id: $id})
+            SET g.world_id = $world_id,
+                g.name = $name,
+                g.width = $width,
+                g.height = $height,
+                g.tilesheet_asset = $tilesheet_asset,
+                g.tile_size = $tile_size,
+                g.tiles = $tiles
+            MERGE (w)-[:HAS_GRID_MAP]->(g)
+            RETURN g.id as id",
+        )
+        .param("id", grid_map.id.to_string())
+        .param("world_id", grid_map.world_id.to_string())
+        .param("name", grid_map.name.clone())
+        .param("width", grid_map.width as i64)
+        .param("height", grid_map.height as i64)
+        .param("tilesheet_asset", grid_map.tilesheet_asset.clone())
+        .param("tile_size", grid_map.tile_size as i64)
+        .param("tiles", tiles_json);
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Saved grid map: {}", grid_map.name);
+        Ok(())
+    }
+
+    /// Get a grid map by ID
+    pub async fn get(&self, id: GridMapId) -> Result<Option<GridMap>> {
+        let q = query(
+            "MATCH (g:GridMap {id: $id})
+            RETURN g",
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+
+        if let Some(row) = result.next().await? {
+            Ok(Some(row_to_grid_map(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List all grid maps in a world
+    pub async fn list_by_world(&self, world_id: WorldId) -> Result<Vec<GridMap>> {
+        let q = query(
+            "MATCH (w:World {id: $world_id})-[:HAS_GRID_MAP]->(g:GridMap)
+            RETURN g
+            ORDER BY g.name",
+        )
+        .param("world_id", world_id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+        let mut grid_maps = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            grid_maps.push(row_to_grid_map(row)?);
+        }
+
+        Ok(grid_maps)
+    }
+
+    /// Delete a grid map and its tokens, unlinking it from any location
+    pub async fn delete(&self, id: GridMapId) -> Result<()> {
+        let q = query(
+            "MATCH (g:GridMap {id: $id})
+            OPTIONAL MATCH (g)-[:HAS_TOKEN]->(t:GridToken)
+            DETACH DELETE t, g",
+        )
+        .param("id", id.to_string());
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Deleted grid map: {}", id);
+        Ok(())
+    }
+}
+
+/// Convert a Neo4j row to a GridMap
+fn row_to_grid_map(row: Row) -> Result<GridMap> {
+    let node: neo4rs::Node = row.get("g")?;
+
+    let id_str: String = node.get("id")?;
+    let world_id_str: String = node.get("world_id")?;
+    let name: String = node.get("name")?;
+    let width: i64 = node.get("width")?;
+    let height: i64 = node.get("height")?;
+    let tilesheet_asset: String = node.get("tilesheet_asset").unwrap_or_default();
+    let tile_size: i64 = node.get("tile_size").unwrap_or(32);
+    let tiles_json: String = node.get("tiles").unwrap_or_else(|_| "[]".to_string());
+
+    let width = width as u32;
+    let height = height as u32;
+    let tiles = tiles_from_json(&tiles_json, width, height)
+        .with_context(|| format!("Invalid tiles on grid map {}", id_str))?;
+
+    Ok(GridMap {
+        id: GridMapId::from_uuid(uuid::Uuid::parse_str(&id_str)?),
+        world_id: WorldId::from_uuid(uuid::Uuid::parse_str(&world_id_str)?),
+        name,
+        width,
+        height,
+        tilesheet_asset,
+        tile_size: tile_size as u32,
+        tiles,
+    })
+}
+
+// =============================================================================
+// GridMapRepositoryPort Implementation
+// =============================================================================
+
+#[async_trait]
+impl GridMapRepositoryPort for Neo4jGridMapRepository {
+    async fn save(&self, grid_map: &GridMap) -> Result<()> {
+        Neo4jGridMapRepository::save(self, grid_map).await
+    }
+
+    async fn get(&self, id: GridMapId) -> Result<Option<GridMap>> {
+        Neo4jGridMapRepository::get(self, id).await
+    }
+
+    async fn list(&self, world_id: WorldId) -> Result<Vec<GridMap>> {
+        Neo4jGridMapRepository::list_by_world(self, world_id).await
+    }
+
+    async fn delete(&self, id: GridMapId) -> Result<()> {
+        Neo4jGridMapRepository::delete(self, id).await
+    }
+}
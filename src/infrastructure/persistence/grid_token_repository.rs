@@ -0,0 +1,206 @@
+//! Grid token repository implementation for Neo4j
+//!
+//! Tokens hang off their map as `(GridMap)-[:HAS_TOKEN]->(GridToken)`. The
+//! character a token stands for is kept as a kind and an ID on the token
+//! rather than as an edge, so deleting a character leaves its token behind
+//! for the DM to clear.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use neo4rs::{query, Row};
+
+use super::connection::Neo4jConnection;
+use crate::application::ports::outbound::GridTokenRepositoryPort;
+use crate::domain::entities::{Facing, GridToken, TokenSubject};
+use crate::domain::value_objects::{CharacterId, GridMapId, GridTokenId, PlayerCharacterId};
+
+/// Repository for GridToken operations
+pub struct Neo4jGridTokenRepository {
+    connection: Neo4jConnection,
+}
+
+impl Neo4jGridTokenRepository {
+    pub fn new(connection: Neo4jConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create or replace a grid token
+    pub async fn save(&self, token: &GridToken) -> Result<()> {
+        let (subject_kind, subject_id) = subject_to_parts(&token.subject);
+
+        let q = query(
+            "MATCH (g:GridMap {id: $grid_map_id})
+            MERGE (t:GridToken {id: $id})
+            SET t.grid_map_id = $grid_map_id,
+                t.subject_kind = $subject_kind,
+                t.subject_id = $subject_id,
+                t.name = $name,
+                t.x = $x,
+                t.y = $y,
+                t.size = $size,
+                t.facing = $facing,
+                t.hidden = $hidden
+            MERGE (g)-[:HAS_TOKEN]->(t)
+            RETURN t.id as id",
+        )
+        .param("id", token.id.to_string())
+        .param("grid_map_id", token.grid_map_id.to_string())
+        .param("subject_kind", subject_kind)
+        .param("subject_id", subject_id)
+        .param("name", token.name.clone())
+        .param("x", token.x as i64)
+        .param("y", token.y as i64)
+        .param("size", token.size as i64)
+        .param("facing", facing_to_str(token.facing))
+        .param("hidden", token.hidden);
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Saved grid token: {}", token.name);
+        Ok(())
+    }
+
+    /// Get a grid token by ID
+    pub async fn get(&self, id: GridTokenId) -> Result<Option<GridToken>> {
+        let q = query(
+            "MATCH (t:GridToken {id: $id})
+            RETURN t",
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+
+        if let Some(row) = result.next().await? {
+            Ok(Some(row_to_grid_token(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List all tokens on a grid map
+    pub async fn list_by_grid_map(&self, grid_map_id: GridMapId) -> Result<Vec<GridToken>> {
+        let q = query(
+            "MATCH (g:GridMap {id: $grid_map_id})-[:HAS_TOKEN]->(t:GridToken)
+            RETURN t
+            ORDER BY t.name",
+        )
+        .param("grid_map_id", grid_map_id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+        let mut tokens = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            tokens.push(row_to_grid_token(row)?);
+        }
+
+        Ok(tokens)
+    }
+
+    /// Delete a grid token
+    pub async fn delete(&self, id: GridTokenId) -> Result<()> {
+        let q = query(
+            "MATCH (t:GridToken {id: $id})
+            DETACH DELETE t",
+        )
+        .param("id", id.to_string());
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Deleted grid token: {}", id);
+        Ok(())
+    }
+}
+
+fn subject_to_parts(subject: &TokenSubject) -> (&'static str, String) {
+    match subject {
+        TokenSubject::Pc(id) => ("pc", id.to_string()),
+        TokenSubject::Npc(id) => ("npc", id.to_string()),
+        TokenSubject::Prop => ("prop", String::new()),
+    }
+}
+
+fn subject_from_parts(kind: &str, id: &str) -> Result<TokenSubject> {
+    Ok(match kind {
+        "pc" => TokenSubject::Pc(PlayerCharacterId::from_uuid(uuid::Uuid::parse_str(id)?)),
+        "npc" => TokenSubject::Npc(CharacterId::from_uuid(uuid::Uuid::parse_str(id)?)),
+        _ => TokenSubject::Prop,
+    })
+}
+
+fn facing_to_str(facing: Facing) -> &'static str {
+    match facing {
+        Facing::North => "N",
+        Facing::NorthEast => "NE",
+        Facing::East => "E",
+        Facing::SouthEast => "SE",
+        Facing::South => "S",
+        Facing::SouthWest => "SW",
+        Facing::West => "W",
+        Facing::NorthWest => "NW",
+    }
+}
+
+fn facing_from_str(facing: &str) -> Facing {
+    match facing {
+        "N" => Facing::North,
+        "NE" => Facing::NorthEast,
+        "E" => Facing::East,
+        "SE" => Facing::SouthEast,
+        "SW" => Facing::SouthWest,
+        "W" => Facing::West,
+        "NW" => Facing::NorthWest,
+        _ => Facing::South,
+    }
+}
+
+/// Convert a Neo4j row to a GridToken
+fn row_to_grid_token(row: Row) -> Result<GridToken> {
+    let node: neo4rs::Node = row.get("t")?;
+
+    let id_str: String = node.get("id")?;
+    let grid_map_id_str: String = node.get("grid_map_id")?;
+    let subject_kind: String = node.get("subject_kind").unwrap_or_default();
+    let subject_id: String = node.get("subject_id").unwrap_or_default();
+    let name: String = node.get("name")?;
+    let x: i64 = node.get("x")?;
+    let y: i64 = node.get("y")?;
+    let size: i64 = node.get("size").unwrap_or(1);
+    let facing: String = node.get("facing").unwrap_or_default();
+    let hidden: bool = node.get("hidden").unwrap_or(false);
+
+    let subject = subject_from_parts(&subject_kind, &subject_id)
+        .with_context(|| format!("Invalid subject on grid token {}", id_str))?;
+
+    Ok(GridToken {
+        id: GridTokenId::from_uuid(uuid::Uuid::parse_str(&id_str)?),
+        grid_map_id: GridMapId::from_uuid(uuid::Uuid::parse_str(&grid_map_id_str)?),
+        subject,
+        name,
+        x: x as u32,
+        y: y as u32,
+        size: size as u32,
+        facing: facing_from_str(&facing),
+        hidden,
+    })
+}
+
+// =============================================================================
+// GridTokenRepositoryPort Implementation
+// =============================================================================
+
+#[async_trait]
+impl GridTokenRepositoryPort for Neo4jGridTokenRepository {
+    async fn save(&self, token: &GridToken) -> Result<()> {
+        Neo4jGridTokenRepository::save(self, token).await
+    }
+
+    async fn get(&self, id: GridTokenId) -> Result<Option<GridToken>> {
+        Neo4jGridTokenRepository::get(self, id).await
+    }
+
+    async fn list(&self, grid_map_id: GridMapId) -> Result<Vec<GridToken>> {
+        Neo4jGridTokenRepository::list_by_grid_map(self, grid_map_id).await
+    }
+
+    async fn delete(&self, id: GridTokenId) -> Result<()> {
+        Neo4jGridTokenRepository::delete(self, id).await
+    }
+}
@@ -0,0 +1,200 @@
+//! Item repository implementation for Neo4j
+//!
+//! Items are a world's catalogue of objects, held by the world through
+//! `(World)-[:CONTAINS_ITEM]->(Item)`. Characters possess catalogue items
+//! through `POSSESSES` edges, which go with an item when it is deleted.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use neo4rs::query;
+
+use super::character_repository::row_to_item;
+use super::connection::Neo4jConnection;
+use crate::application::ports::outbound::ItemRepositoryPort;
+use crate::domain::entities::Item;
+use crate::domain::value_objects::{ItemId, WorldId};
+
+/// Repository for Item operations
+pub struct Neo4jItemRepository {
+    connection: Neo4jConnection,
+}
+
+impl Neo4jItemRepository {
+    pub fn new(connection: Neo4jConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create a new item in its world's catalogue
+    pub async fn create(&self, item: &Item) -> Result<()> {
+        let q = query(
+            "MATCH (w:World {id: $world_id})
+            CREATE (i:Item {
+                id: $id,
+                world_id: $world_id,
+                name: $name,
+                description: $description,
+                item_type: $item_type,
+                is_unique: $is_unique,
+                properties: $properties
+            })
+            CREATE (w)-[:CONTAINS_ITEM]->(i)
+            RETURN i.id as id",
+        )
+        .param("id", item.id.to_string())
+        .param("world_id", item.world_id.to_string())
+        .param("name", item.name.clone())
+        .param("description", item.description.clone().unwrap_or_default())
+        .param("item_type", item.item_type.clone().unwrap_or_default())
+        .param("is_unique", item.is_unique)
+        .param("properties", item.properties.clone().unwrap_or_default());
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Created item: {}", item.name);
+        Ok(())
+    }
+
+    /// Get an item by ID
+    pub async fn get(&self, id: ItemId) -> Result<Option<Item>> {
+        let q = query(
+            "MATCH (i:Item {id: $id})
+            RETURN i",
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+
+        if let Some(row) = result.next().await? {
+            Ok(Some(row_to_item(&row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List all items in a world
+    pub async fn list_by_world(&self, world_id: WorldId) -> Result<Vec<Item>> {
+        let q = query(
+            "MATCH (w:World {id: $world_id})-[:CONTAINS_ITEM]->(i:Item)
+            RETURN i
+            ORDER BY i.name",
+        )
+        .param("world_id", world_id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+        let mut items = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            items.push(row_to_item(&row)?);
+        }
+
+        Ok(items)
+    }
+
+    /// List the items of a type in a world, ignoring case
+    pub async fn list_by_type(&self, world_id: WorldId, item_type: &str) -> Result<Vec<Item>> {
+        let q = query(
+            "MATCH (w:World {id: $world_id})-[:CONTAINS_ITEM]->(i:Item)
+            WHERE toLower(i.item_type) = toLower($item_type)
+            RETURN i
+            ORDER BY i.name",
+        )
+        .param("world_id", world_id.to_string())
+        .param("item_type", item_type);
+
+        let mut result = self.connection.graph().execute(q).await?;
+        let mut items = Vec::new();
+
+        while let Some(row) = result.next().await? {
+            items.push(row_to_item(&row)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Update an item
+    pub async fn update(&self, item: &Item) -> Result<()> {
+        let q = query(
+            "MATCH (i:Item {id: $id})
+            SET i.name = $name,
+                i.description = $description,
+                i.item_type = $item_type,
+                i.is_unique = $is_unique,
+                i.properties = $properties
+            RETURN i.id as id",
+        )
+        .param("id", item.id.to_string())
+        .param("name", item.name.clone())
+        .param("description", item.description.clone().unwrap_or_default())
+        .param("item_type", item.item_type.clone().unwrap_or_default())
+        .param("is_unique", item.is_unique)
+        .param("properties", item.properties.clone().unwrap_or_default());
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Updated item: {}", item.name);
+        Ok(())
+    }
+
+    /// How many of an item are held across every inventory
+    pub async fn held_count(&self, id: ItemId) -> Result<u32> {
+        let q = query(
+            "OPTIONAL MATCH (:Character)-[r:POSSESSES]->(i:Item {id: $id})
+            RETURN coalesce(sum(r.quantity), 0) as held",
+        )
+        .param("id", id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+        if let Some(row) = result.next().await? {
+            let held: i64 = row.get("held")?;
+            Ok(held.clamp(0, i64::from(u32::MAX)) as u32)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Delete an item, taking it out of every inventory
+    pub async fn delete(&self, id: ItemId) -> Result<()> {
+        let q = query(
+            "MATCH (i:Item {id: $id})
+            DETACH DELETE i",
+        )
+        .param("id", id.to_string());
+
+        self.connection.graph().run(q).await?;
+        tracing::debug!("Deleted item: {}", id);
+        Ok(())
+    }
+}
+
+// =============================================================================
+// ItemRepositoryPort Implementation
+// =============================================================================
+
+#[async_trait]
+impl ItemRepositoryPort for Neo4jItemRepository {
+    async fn create(&self, item: &Item) -> Result<()> {
+        Neo4jItemRepository::create(self, item).await
+    }
+
+    async fn get(&self, id: ItemId) -> Result<Option<Item>> {
+        Neo4jItemRepository::get(self, id).await
+    }
+
+    async fn list(&self, world_id: WorldId) -> Result<Vec<Item>> {
+        Neo4jItemRepository::list_by_world(self, world_id).await
+    }
+
+    async fn update(&self, item: &Item) -> Result<()> {
+        Neo4jItemRepository::update(self, item).await
+    }
+
+    async fn delete(&self, id: ItemId) -> Result<()> {
+        Neo4jItemRepository::delete(self, id).await
+    }
+
+    async fn get_by_type(&self, world_id: WorldId, item_type: &str) -> Result<Vec<Item>> {
+        Neo4jItemRepository::list_by_type(self, world_id, item_type).await
+    }
+
+    async fn held_count(&self, id: ItemId) -> Result<u32> {
+        Neo4jItemRepository::held_count(self, id).await
+    }
+}
@@ -8,7 +8,10 @@ mod challenge_repository;
 mod character_repository;
 mod connection;
 mod event_chain_repository;
+mod grid_map_repository;
+mod grid_token_repository;
 mod interaction_repository;
+mod item_repository;
 mod location_repository;
 mod narrative_event_repository;
 mod observation_repository;
@@ -32,7 +35,10 @@ pub use crate::domain::value_objects::{
 };
 pub use connection::Neo4jConnection;
 pub use event_chain_repository::Neo4jEventChainRepository;
+pub use grid_map_repository::Neo4jGridMapRepository;
+pub use grid_token_repository::Neo4jGridTokenRepository;
 pub use interaction_repository::Neo4jInteractionRepository;
+pub use item_repository::Neo4jItemRepository;
 pub use location_repository::Neo4jLocationRepository;
 pub use narrative_event_repository::Neo4jNarrativeEventRepository;
 pub use observation_repository::Neo4jObservationRepository;
@@ -74,6 +80,14 @@ impl Neo4jRepository {
         Neo4jLocationRepository::new(self.connection.clone())
     }
 
+    pub fn grid_maps(&self) -> Neo4jGridMapRepository {
+        Neo4jGridMapRepository::new(self.connection.clone())
+    }
+
+    pub fn grid_tokens(&self) -> Neo4jGridTokenRepository {
+        Neo4jGridTokenRepository::new(self.connection.clone())
+    }
+
     pub fn scenes(&self) -> Neo4jSceneRepository {
         Neo4jSceneRepository::new(self.connection.clone())
     }
@@ -86,6 +100,10 @@ impl Neo4jRepository {
         Neo4jInteractionRepository::new(self.connection.clone())
     }
 
+    pub fn items(&self) -> Neo4jItemRepository {
+        Neo4jItemRepository::new(self.connection.clone())
+    }
+
     pub fn assets(&self) -> Neo4jAssetRepository {
         Neo4jAssetRepository::new(self.connection.clone())
     }
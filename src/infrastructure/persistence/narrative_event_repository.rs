@@ -10,11 +10,12 @@ use uuid::Uuid;
 use super::connection::Neo4jConnection;
 use crate::application::ports::outbound::NarrativeEventRepositoryPort;
 use crate::domain::entities::{
-    ChainedEvent, EventChainMembership, EventEffect, EventOutcome, FeaturedNpc, NarrativeEvent,
+    AreaTemplate, ChainedEvent, EventChainMembership, EventEffect, EventOutcome, FeaturedNpc, NarrativeEvent,
     NarrativeTrigger, NarrativeTriggerType, OutcomeCondition, TriggerLogic,
 };
 use crate::domain::value_objects::{
-    ActId, ChallengeId, CharacterId, EventChainId, LocationId, NarrativeEventId, SceneId, WorldId,
+    ActId, ChallengeId, CharacterId, EventChainId, GridMapId, LocationId, NarrativeEventId, SceneId,
+    WorldId,
 };
 
 // ============================================================================
@@ -201,6 +202,12 @@ enum StoredEventEffect {
         stat_name: String,
         modifier: i32,
     },
+    AreaEffect {
+        grid_map_id: String,
+        template: StoredAreaTemplate,
+        description: String,
+        saving_throw: Option<String>,
+    },
     AddReward {
         reward_type: String,
         amount: i32,
@@ -212,6 +219,52 @@ enum StoredEventEffect {
     },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "shape")]
+enum StoredAreaTemplate {
+    Sphere {
+        center: (u32, u32),
+        radius: u32,
+    },
+    Cube {
+        origin: (u32, u32),
+        size: u32,
+    },
+    Cone {
+        origin: (u32, u32),
+        toward: (u32, u32),
+        length: u32,
+    },
+    Line {
+        origin: (u32, u32),
+        toward: (u32, u32),
+        length: u32,
+        width: u32,
+    },
+}
+
+impl From<AreaTemplate> for StoredAreaTemplate {
+    fn from(value: AreaTemplate) -> Self {
+        match value {
+            AreaTemplate::Sphere { center, radius } => Self::Sphere { center, radius },
+            AreaTemplate::Cube { origin, size } => Self::Cube { origin, size },
+            AreaTemplate::Cone { origin, toward, length } => Self::Cone { origin, toward, length },
+            AreaTemplate::Line { origin, toward, length, width } => Self::Line { origin, toward, length, width },
+        }
+    }
+}
+
+impl From<StoredAreaTemplate> for AreaTemplate {
+    fn from(value: StoredAreaTemplate) -> Self {
+        match value {
+            StoredAreaTemplate::Sphere { center, radius } => Self::Sphere { center, radius },
+            StoredAreaTemplate::Cube { origin, size } => Self::Cube { origin, size },
+            StoredAreaTemplate::Cone { origin, toward, length } => Self::Cone { origin, toward, length },
+            StoredAreaTemplate::Line { origin, toward, length, width } => Self::Line { origin, toward, length, width },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct StoredChainedEvent {
     event_id: String,
@@ -477,6 +530,14 @@ impl From<&EventEffect> for StoredEventEffect {
                     modifier: *modifier,
                 }
             }
+            EventEffect::AreaEffect { grid_map_id, template, description, saving_throw } => {
+                StoredEventEffect::AreaEffect {
+                    grid_map_id: grid_map_id.to_string(),
+                    template: (*template).into(),
+                    description: description.clone(),
+                    saving_throw: saving_throw.map(|id| id.to_string()),
+                }
+            }
             EventEffect::AddReward { reward_type, amount, description } => {
                 StoredEventEffect::AddReward {
                     reward_type: reward_type.clone(),
@@ -725,6 +786,16 @@ impl From<StoredEventEffect> for EventEffect {
                     modifier,
                 }
             }
+            StoredEventEffect::AreaEffect { grid_map_id, template, description, saving_throw } => {
+                EventEffect::AreaEffect {
+                    grid_map_id: GridMapId::from(Uuid::parse_str(&grid_map_id).unwrap_or_default()),
+                    template: template.into(),
+                    description,
+                    saving_throw: saving_throw
+                        .and_then(|id| Uuid::parse_str(&id).ok())
+                        .map(ChallengeId::from),
+                }
+            }
             StoredEventEffect::AddReward { reward_type, amount, description } => {
                 EventEffect::AddReward { reward_type, amount, description }
             }
@@ -3,17 +3,47 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use neo4rs::{query, Row};
+use serde::{Deserialize, Serialize};
 use serde_json;
 
+use super::character_repository::{conditions_from_json, conditions_to_json};
 use super::connection::Neo4jConnection;
 use crate::application::ports::outbound::PlayerCharacterRepositoryPort;
 use neo4rs::Node;
-use crate::domain::entities::PlayerCharacter;
+use crate::domain::entities::{PlayerCharacter, DEFAULT_VISION_RADIUS};
 use crate::domain::entities::CharacterSheetData;
 use crate::domain::value_objects::{
-    LocationId, PlayerCharacterId, RegionId, SessionId,
+    Advancement, LocationId, PlayerCharacterId, RegionId, SessionId,
 };
 
+/// A character's advancement as stored on its node
+#[derive(Debug, Serialize, Deserialize)]
+struct AdvancementStored {
+    experience: u32,
+    milestones: u32,
+    level: u32,
+}
+
+impl From<Advancement> for AdvancementStored {
+    fn from(value: Advancement) -> Self {
+        Self {
+            experience: value.experience,
+            milestones: value.milestones,
+            level: value.level,
+        }
+    }
+}
+
+impl From<AdvancementStored> for Advancement {
+    fn from(value: AdvancementStored) -> Self {
+        Self {
+            experience: value.experience,
+            milestones: value.milestones,
+            level: value.level,
+        }
+    }
+}
+
 /// Repository for PlayerCharacter operations
 pub struct Neo4jPlayerCharacterRepository {
     connection: Neo4jConnection,
@@ -56,6 +86,7 @@ impl PlayerCharacterRepositoryPort for Neo4jPlayerCharacterRepository {
                     starting_location_id: $starting_location_id,
                     sprite_asset: $sprite_asset,
                     portrait_asset: $portrait_asset,
+                    vision_radius: $vision_radius,
                     created_at: $created_at,
                     last_active_at: $last_active_at
                 })
@@ -77,6 +108,7 @@ impl PlayerCharacterRepositoryPort for Neo4jPlayerCharacterRepository {
             .param("starting_location_id", pc.starting_location_id.to_string())
             .param("sprite_asset", pc.sprite_asset.clone().unwrap_or_default())
             .param("portrait_asset", pc.portrait_asset.clone().unwrap_or_default())
+            .param("vision_radius", pc.vision_radius as i64)
             .param("created_at", pc.created_at.to_rfc3339())
             .param("last_active_at", pc.last_active_at.to_rfc3339());
 
@@ -99,6 +131,7 @@ impl PlayerCharacterRepositoryPort for Neo4jPlayerCharacterRepository {
                     starting_location_id: $starting_location_id,
                     sprite_asset: $sprite_asset,
                     portrait_asset: $portrait_asset,
+                    vision_radius: $vision_radius,
                     created_at: $created_at,
                     last_active_at: $last_active_at
                 })
@@ -118,6 +151,7 @@ impl PlayerCharacterRepositoryPort for Neo4jPlayerCharacterRepository {
             .param("starting_location_id", pc.starting_location_id.to_string())
             .param("sprite_asset", pc.sprite_asset.clone().unwrap_or_default())
             .param("portrait_asset", pc.portrait_asset.clone().unwrap_or_default())
+            .param("vision_radius", pc.vision_radius as i64)
             .param("created_at", pc.created_at.to_rfc3339())
             .param("last_active_at", pc.last_active_at.to_rfc3339());
 
@@ -201,22 +235,35 @@ impl PlayerCharacterRepositoryPort for Neo4jPlayerCharacterRepository {
         } else {
             "{}".to_string()
         };
+        // Only set by template migrations, so new characters never have one
+        let sheet_backup_json = match &pc.sheet_backup {
+            Some(backup) => serde_json::to_string(backup)?,
+            None => String::new(),
+        };
 
         let q = query(
             "MATCH (pc:PlayerCharacter {id: $id})
             SET pc.name = $name,
                 pc.description = $description,
                 pc.sheet_data = $sheet_data,
+                pc.sheet_backup = $sheet_backup,
+                pc.conditions = $conditions,
+                pc.advancement = $advancement,
                 pc.sprite_asset = $sprite_asset,
                 pc.portrait_asset = $portrait_asset,
+                pc.vision_radius = $vision_radius,
                 pc.last_active_at = $last_active_at",
         )
         .param("id", pc.id.to_string())
         .param("name", pc.name.clone())
         .param("description", pc.description.clone().unwrap_or_default())
         .param("sheet_data", sheet_data_json)
+        .param("sheet_backup", sheet_backup_json)
+        .param("conditions", conditions_to_json(&pc.conditions)?)
+        .param("advancement", serde_json::to_string(&AdvancementStored::from(pc.advancement))?)
         .param("sprite_asset", pc.sprite_asset.clone().unwrap_or_default())
         .param("portrait_asset", pc.portrait_asset.clone().unwrap_or_default())
+        .param("vision_radius", pc.vision_radius as i64)
         .param("last_active_at", pc.last_active_at.to_rfc3339());
 
         self.connection.graph().run(q).await?;
@@ -376,6 +423,25 @@ impl PlayerCharacterRepositoryPort for Neo4jPlayerCharacterRepository {
         Ok(pcs)
     }
 
+    async fn get_by_world(
+        &self,
+        world_id: crate::domain::value_objects::WorldId,
+    ) -> Result<Vec<PlayerCharacter>> {
+        let q = query(
+            "MATCH (pc:PlayerCharacter)-[:IN_WORLD]->(w:World {id: $world_id})
+            RETURN pc
+            ORDER BY pc.name",
+        )
+        .param("world_id", world_id.to_string());
+
+        let mut result = self.connection.graph().execute(q).await?;
+        let mut pcs = Vec::new();
+        while let Some(row) = result.next().await? {
+            pcs.push(parse_player_character_row(row)?);
+        }
+        Ok(pcs)
+    }
+
     async fn get_unbound_by_user(&self, user_id: &str) -> Result<Vec<PlayerCharacter>> {
         let q = query(
             "MATCH (pc:PlayerCharacter {user_id: $user_id})
@@ -458,6 +524,30 @@ fn parse_player_character_row(row: Row) -> Result<PlayerCharacter> {
         )
     };
 
+    let sheet_backup_str: String = node.get("sheet_backup").unwrap_or_default();
+    let sheet_backup = if sheet_backup_str.is_empty() {
+        None
+    } else {
+        Some(
+            serde_json::from_str::<CharacterSheetData>(&sheet_backup_str)
+                .context("Failed to parse sheet_backup")?,
+        )
+    };
+
+    // Set on update, so characters never updated have no conditions stored
+    let conditions_str: String = node.get("conditions").unwrap_or_default();
+    let conditions = conditions_from_json(&conditions_str).context("Failed to parse conditions")?;
+
+    // Likewise set on update; characters never advanced start at level 1
+    let advancement_str: String = node.get("advancement").unwrap_or_default();
+    let advancement = if advancement_str.is_empty() {
+        Advancement::new()
+    } else {
+        serde_json::from_str::<AdvancementStored>(&advancement_str)
+            .context("Failed to parse advancement")?
+            .into()
+    };
+
     let current_location_id_str: String = node.get("current_location_id").context("Missing current_location_id")?;
     let current_location_id = LocationId::from_uuid(
         uuid::Uuid::parse_str(&current_location_id_str)
@@ -495,6 +585,10 @@ fn parse_player_character_row(row: Row) -> Result<PlayerCharacter> {
         portrait_asset
     };
 
+    let vision_radius: i64 = node
+        .get("vision_radius")
+        .unwrap_or(DEFAULT_VISION_RADIUS as i64);
+
     let created_at_str: String = node.get("created_at").context("Missing created_at")?;
     let created_at = DateTime::parse_from_rfc3339(&created_at_str)
         .context("Invalid created_at timestamp")?
@@ -513,6 +607,10 @@ fn parse_player_character_row(row: Row) -> Result<PlayerCharacter> {
         name,
         description,
         sheet_data,
+        sheet_backup,
+        conditions,
+        advancement,
+        vision_radius: vision_radius as u32,
         current_location_id,
         current_region_id,
         starting_location_id,
@@ -472,6 +472,10 @@ impl RegionRepositoryPort for Neo4jRegionRepository {
         Neo4jRegionRepository::list_spawn_points(self, world_id).await
     }
 
+    async fn create_connection(&self, connection: &RegionConnection) -> Result<()> {
+        Neo4jRegionRepository::create_connection(self, connection).await
+    }
+
     async fn get_npcs_related_to_region(
         &self,
         region_id: RegionId,
@@ -559,6 +563,7 @@ fn row_to_character_for_presence(row: Row) -> Result<Character> {
         current_archetype,
         archetype_history: vec![],
         stats: StatBlock::default(),
+        conditions: Default::default(),
         is_alive,
         is_active,
     })
@@ -129,6 +129,12 @@ enum StoredStoryEventType {
         new_value: i32,
         reason: String,
     },
+    LevelGained {
+        character_id: String,
+        character_name: String,
+        level: u32,
+        unlocked: Vec<String>,
+    },
     FlagChanged {
         flag_name: String,
         new_value: bool,
@@ -427,6 +433,17 @@ impl From<&StoryEventType> for StoredStoryEventType {
                 new_value: *new_value,
                 reason: reason.clone(),
             },
+            StoryEventType::LevelGained {
+                character_id,
+                character_name,
+                level,
+                unlocked,
+            } => StoredStoryEventType::LevelGained {
+                character_id: character_id.to_string(),
+                character_name: character_name.clone(),
+                level: *level,
+                unlocked: unlocked.clone(),
+            },
             StoryEventType::FlagChanged {
                 flag_name,
                 new_value,
@@ -789,6 +806,17 @@ impl From<StoredStoryEventType> for StoryEventType {
                 new_value,
                 reason,
             },
+            StoredStoryEventType::LevelGained {
+                character_id,
+                character_name,
+                level,
+                unlocked,
+            } => StoryEventType::LevelGained {
+                character_id: CharacterId::from(Uuid::parse_str(&character_id).unwrap_or_default()),
+                character_name,
+                level,
+                unlocked,
+            },
             StoredStoryEventType::FlagChanged {
                 flag_name,
                 new_value,
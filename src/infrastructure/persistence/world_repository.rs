@@ -5,7 +5,7 @@ use async_trait::async_trait;
 use neo4rs::{query, Row};
 
 use super::connection::Neo4jConnection;
-use crate::application::dto::RuleSystemConfigDto;
+use crate::application::dto::{RuleSystemConfigDto, RuleSystemDefinitionDto};
 use crate::application::ports::outbound::WorldRepositoryPort;
 use crate::domain::entities::{Act, MonomythStage, World};
 use crate::domain::value_objects::{ActId, RuleSystemConfig, WorldId};
@@ -24,6 +24,7 @@ impl Neo4jWorldRepository {
     pub async fn create(&self, world: &World) -> Result<()> {
         let rule_system_json =
             serde_json::to_string(&RuleSystemConfigDto::from(world.rule_system.clone()))?;
+        let rule_system_definition_json = rule_system_definition_json(world)?;
 
         let q = query(
             "CREATE (w:World {
@@ -31,6 +32,7 @@ impl Neo4jWorldRepository {
                 name: $name,
                 description: $description,
                 rule_system: $rule_system,
+                rule_system_definition: $rule_system_definition,
                 created_at: $created_at,
                 updated_at: $updated_at
             })
@@ -40,6 +42,7 @@ impl Neo4jWorldRepository {
         .param("name", world.name.clone())
         .param("description", world.description.clone())
         .param("rule_system", rule_system_json)
+        .param("rule_system_definition", rule_system_definition_json)
         .param("created_at", world.created_at.to_rfc3339())
         .param("updated_at", world.updated_at.to_rfc3339());
 
@@ -53,7 +56,8 @@ impl Neo4jWorldRepository {
         let q = query(
             "MATCH (w:World {id: $id})
             RETURN w.id as id, w.name as name, w.description as description,
-                   w.rule_system as rule_system, w.created_at as created_at,
+                   w.rule_system as rule_system,
+                   w.rule_system_definition as rule_system_definition, w.created_at as created_at,
                    w.updated_at as updated_at",
         )
         .param("id", id.to_string());
@@ -72,7 +76,8 @@ impl Neo4jWorldRepository {
         let q = query(
             "MATCH (w:World)
             RETURN w.id as id, w.name as name, w.description as description,
-                   w.rule_system as rule_system, w.created_at as created_at,
+                   w.rule_system as rule_system,
+                   w.rule_system_definition as rule_system_definition, w.created_at as created_at,
                    w.updated_at as updated_at
             ORDER BY w.name",
         );
@@ -91,12 +96,14 @@ impl Neo4jWorldRepository {
     pub async fn update(&self, world: &World) -> Result<()> {
         let rule_system_json =
             serde_json::to_string(&RuleSystemConfigDto::from(world.rule_system.clone()))?;
+        let rule_system_definition_json = rule_system_definition_json(world)?;
 
         let q = query(
             "MATCH (w:World {id: $id})
             SET w.name = $name,
                 w.description = $description,
                 w.rule_system = $rule_system,
+                w.rule_system_definition = $rule_system_definition,
                 w.updated_at = $updated_at
             RETURN w.id as id",
         )
@@ -104,6 +111,7 @@ impl Neo4jWorldRepository {
         .param("name", world.name.clone())
         .param("description", world.description.clone())
         .param("rule_system", rule_system_json)
+        .param("rule_system_definition", rule_system_definition_json)
         .param("updated_at", world.updated_at.to_rfc3339());
 
         self.connection.graph().run(q).await?;
@@ -179,12 +187,19 @@ fn row_to_world(row: Row) -> Result<World> {
     let name: String = row.get("name")?;
     let description: String = row.get("description")?;
     let rule_system_json: String = row.get("rule_system")?;
+    // Worlds saved before homebrew systems have no definition property
+    let rule_system_definition_json: String = row.get("rule_system_definition").unwrap_or_default();
     let created_at_str: String = row.get("created_at")?;
     let updated_at_str: String = row.get("updated_at")?;
 
     let id = uuid::Uuid::parse_str(&id_str)?;
     let rule_system: RuleSystemConfig =
         serde_json::from_str::<RuleSystemConfigDto>(&rule_system_json)?.into();
+    let rule_system_definition = if rule_system_definition_json.is_empty() {
+        None
+    } else {
+        Some(serde_json::from_str::<RuleSystemDefinitionDto>(&rule_system_definition_json)?.into())
+    };
     let created_at =
         chrono::DateTime::parse_from_rfc3339(&created_at_str)?.with_timezone(&chrono::Utc);
     let updated_at =
@@ -195,11 +210,21 @@ fn row_to_world(row: Row) -> Result<World> {
         name,
         description,
         rule_system,
+        rule_system_definition,
         created_at,
         updated_at,
     })
 }
 
+fn rule_system_definition_json(world: &World) -> Result<String> {
+    match &world.rule_system_definition {
+        Some(definition) => Ok(serde_json::to_string(&RuleSystemDefinitionDto::from(
+            definition.clone(),
+        ))?),
+        None => Ok(String::new()),
+    }
+}
+
 fn row_to_act(row: Row) -> Result<Act> {
     let id_str: String = row.get("id")?;
     let world_id_str: String = row.get("world_id")?;
@@ -2,7 +2,9 @@
 
 pub mod sqlite_app_event_repository;
 pub mod sqlite_generation_read_state_repository;
+pub mod sqlite_roll_log_repository;
 
 pub use sqlite_app_event_repository::SqliteAppEventRepository;
 pub use sqlite_generation_read_state_repository::SqliteGenerationReadStateRepository;
+pub use sqlite_roll_log_repository::SqliteRollLogRepository;
 
@@ -0,0 +1,188 @@
+//! SQLite Roll Log Repository - Persistent audit log of dice rolls
+//!
+//! Shares the event database with the app event repository. Seeds are stored
+//! as text because SQLite integers are signed, and a granted roll mode as JSON.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+
+use crate::application::ports::outbound::{RollLogError, RollLogPort};
+use crate::domain::entities::{RollLogEntry, RollPurpose};
+use crate::domain::value_objects::{RollId, SessionId};
+
+/// SQLite implementation of RollLogPort
+pub struct SqliteRollLogRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRollLogRepository {
+    /// Create a new repository and ensure the table exists
+    pub async fn new(pool: SqlitePool) -> Result<Self, RollLogError> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS roll_log (
+                id TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                purpose TEXT NOT NULL,
+                label TEXT,
+                roller TEXT NOT NULL,
+                formula TEXT NOT NULL,
+                seed TEXT NOT NULL,
+                sequence INTEGER NOT NULL,
+                dice TEXT NOT NULL,
+                total INTEGER NOT NULL,
+                breakdown TEXT NOT NULL,
+                rolled_at TEXT NOT NULL,
+                granted TEXT
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| RollLogError::StorageError(e.to_string()))?;
+
+        // Logs created before roll modes could be granted lack the column
+        let has_granted: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('roll_log') WHERE name = 'granted'",
+        )
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| RollLogError::StorageError(e.to_string()))?;
+        if has_granted == 0 {
+            sqlx::query("ALTER TABLE roll_log ADD COLUMN granted TEXT")
+                .execute(&pool)
+                .await
+                .map_err(|e| RollLogError::StorageError(e.to_string()))?;
+        }
+
+        sqlx::query(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_roll_log_session_rolled
+            ON roll_log(session_id, rolled_at)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .map_err(|e| RollLogError::StorageError(e.to_string()))?;
+
+        Ok(Self { pool })
+    }
+}
+
+/// Convert a roll_log row back into an entry
+fn row_to_entry(row: &SqliteRow) -> Result<RollLogEntry, RollLogError> {
+    let parse_uuid = |column: &str| {
+        let value: String = row.get(column);
+        uuid::Uuid::parse_str(&value)
+            .map_err(|e| RollLogError::SerializationError(format!("Invalid {}: {}", column, e)))
+    };
+
+    let purpose: String = row.get("purpose");
+    let seed: String = row.get("seed");
+    let sequence: i64 = row.get("sequence");
+    let dice: String = row.get("dice");
+    let rolled_at: String = row.get("rolled_at");
+    let granted: Option<String> = row.get("granted");
+
+    Ok(RollLogEntry {
+        id: RollId::from_uuid(parse_uuid("id")?),
+        session_id: SessionId::from_uuid(parse_uuid("session_id")?),
+        purpose: purpose
+            .parse::<RollPurpose>()
+            .map_err(|e| RollLogError::SerializationError(e.to_string()))?,
+        label: row.get("label"),
+        roller: row.get("roller"),
+        formula: row.get("formula"),
+        seed: seed
+            .parse()
+            .map_err(|e| RollLogError::SerializationError(format!("Invalid seed: {}", e)))?,
+        sequence: sequence as u64,
+        dice: serde_json::from_str(&dice)
+            .map_err(|e| RollLogError::SerializationError(e.to_string()))?,
+        total: row.get::<i64, _>("total") as i32,
+        breakdown: row.get("breakdown"),
+        rolled_at: DateTime::parse_from_rfc3339(&rolled_at)
+            .map_err(|e| RollLogError::StorageError(format!("Invalid timestamp: {}", e)))?
+            .with_timezone(&Utc),
+        granted: granted
+            .map(|granted| serde_json::from_str(&granted))
+            .transpose()
+            .map_err(|e| RollLogError::SerializationError(e.to_string()))?,
+    })
+}
+
+#[async_trait]
+impl RollLogPort for SqliteRollLogRepository {
+    async fn record(&self, entry: &RollLogEntry) -> Result<(), RollLogError> {
+        let dice = serde_json::to_string(&entry.dice)
+            .map_err(|e| RollLogError::SerializationError(e.to_string()))?;
+        let granted = entry
+            .granted
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| RollLogError::SerializationError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO roll_log
+                (id, session_id, purpose, label, roller, formula, seed, sequence, dice, total, breakdown, rolled_at, granted)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(entry.id.to_string())
+        .bind(entry.session_id.to_string())
+        .bind(entry.purpose.as_str())
+        .bind(&entry.label)
+        .bind(&entry.roller)
+        .bind(&entry.formula)
+        .bind(entry.seed.to_string())
+        .bind(entry.sequence as i64)
+        .bind(&dice)
+        .bind(entry.total as i64)
+        .bind(&entry.breakdown)
+        .bind(entry.rolled_at.to_rfc3339())
+        .bind(&granted)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RollLogError::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, id: RollId) -> Result<Option<RollLogEntry>, RollLogError> {
+        let row = sqlx::query("SELECT * FROM roll_log WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| RollLogError::StorageError(e.to_string()))?;
+
+        row.as_ref().map(row_to_entry).transpose()
+    }
+
+    async fn list_for_session(
+        &self,
+        session_id: SessionId,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<RollLogEntry>, RollLogError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT *
+            FROM roll_log
+            WHERE session_id = ?
+            ORDER BY rolled_at DESC, sequence DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(session_id.to_string())
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RollLogError::StorageError(e.to_string()))?;
+
+        rows.iter().map(row_to_entry).collect()
+    }
+}
@@ -7,7 +7,9 @@ use chrono::{DateTime, Utc};
 use tokio::sync::mpsc;
 
 use crate::application::dto::WorldSnapshot;
-use crate::domain::value_objects::{GameTime, ProposedToolInfo, SessionId, TimeOfDay, WorldId};
+use crate::domain::value_objects::{
+    GameTime, ProposedToolInfo, SeededRollSource, SessionId, TimeOfDay, WorldId, SECONDS_PER_ROUND,
+};
 use crate::infrastructure::websocket::{ParticipantRole, ServerMessage};
 
 use super::conversation::ConversationTurn;
@@ -88,6 +90,10 @@ pub struct GameSession {
     pub player_characters: HashMap<String, crate::domain::entities::PlayerCharacter>,
     /// In-game time tracking (Phase 23C)
     game_time: GameTime,
+    /// Seed for all engine dice rolls in this session
+    roll_seed: u64,
+    /// Sequence index of the next engine roll
+    next_roll_sequence: u64,
 }
 
 impl GameSession {
@@ -116,6 +122,8 @@ impl GameSession {
             pending_approvals: HashMap::new(),
             player_characters: HashMap::new(),
             game_time: GameTime::new(),
+            roll_seed: rand::random(),
+            next_roll_sequence: 0,
         }
     }
 
@@ -432,6 +440,17 @@ impl GameSession {
         self.game_time.advance_days(days);
     }
 
+    /// Advance game time by minutes (convenience method)
+    pub fn advance_time_minutes(&mut self, minutes: u32) {
+        self.game_time.advance(chrono::Duration::minutes(minutes as i64));
+    }
+
+    /// Advance game time by combat rounds
+    pub fn advance_time_rounds(&mut self, rounds: u32) {
+        self.game_time
+            .advance(chrono::Duration::seconds(rounds as i64 * SECONDS_PER_ROUND));
+    }
+
     /// Get the current time of day
     pub fn time_of_day(&self) -> TimeOfDay {
         self.game_time.time_of_day()
@@ -446,11 +465,23 @@ impl GameSession {
     pub fn is_time_paused(&self) -> bool {
         self.game_time.is_paused()
     }
+
+    // =========================================================================
+    // Dice Rolling
+    // =========================================================================
+
+    /// Take the roll source for the next engine roll, advancing the sequence
+    pub fn next_roll_source(&mut self) -> SeededRollSource {
+        let source = SeededRollSource::new(self.roll_seed, self.next_roll_sequence);
+        self.next_roll_sequence += 1;
+        source
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::entities::World;
     use crate::domain::value_objects::RuleSystemConfig;
 
     fn create_test_world() -> World {
@@ -459,6 +490,7 @@ mod tests {
             name: "Test World".to_string(),
             description: "A test world".to_string(),
             rule_system: RuleSystemConfig::default(),
+            rule_system_definition: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
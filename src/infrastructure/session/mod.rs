@@ -545,6 +545,12 @@ impl SessionManagementPort for SessionManager {
             directorial_notes: current_scene.directorial_notes.clone(),
         })
     }
+
+    fn get_game_time(&self, session_id: SessionId) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.sessions
+            .get(&session_id)
+            .map(|s| s.game_time().current)
+    }
 }
 
 #[cfg(test)]
@@ -559,6 +565,7 @@ mod tests {
             name: "Test World".to_string(),
             description: "A test world".to_string(),
             rule_system: RuleSystemConfig::default(),
+            rule_system_definition: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -13,7 +13,7 @@ use crate::application::ports::outbound::{
     AsyncSessionError, AsyncSessionPort, SessionJoinInfo, SessionParticipantInfo,
     SessionParticipantRole, SessionWorldData,
 };
-use crate::domain::value_objects::{ProposedToolInfo, SessionId, WorldId};
+use crate::domain::value_objects::{ProposedToolInfo, SeededRollSource, SessionId, WorldId};
 use crate::infrastructure::session::{ClientId, PendingApproval, SessionError, SessionManager};
 use crate::infrastructure::websocket::messages::{ParticipantRole, ServerMessage};
 
@@ -386,4 +386,13 @@ impl AsyncSessionPort for SessionManagerAdapter {
             Err(AsyncSessionError::SessionNotFound(session_id.to_string()))
         }
     }
+
+    // === Dice rolling ===
+
+    async fn next_roll_source(&self, session_id: SessionId) -> Option<SeededRollSource> {
+        let mut sessions = self.inner.write().await;
+        sessions
+            .get_session_mut(session_id)
+            .map(|session| session.next_roll_source())
+    }
 }
@@ -1,19 +1,23 @@
 //! Core domain services for world building
 
 use crate::application::services::{
-    CharacterServiceImpl, InteractionServiceImpl, LocationServiceImpl,
-    RelationshipServiceImpl, SceneServiceImpl, SkillServiceImpl, WorldServiceImpl,
+    CharacterServiceImpl, GridMapServiceImpl, InteractionServiceImpl, ItemServiceImpl,
+    LocationServiceImpl, MapImportService, RelationshipServiceImpl, SceneServiceImpl, SkillServiceImpl, WorldServiceImpl,
 };
 
 /// Core services for fundamental world-building entities
 ///
 /// This struct groups the primary domain services that handle the core
 /// entities of the world-building system: worlds, characters, locations,
-/// scenes, skills, interactions, and relationships.
+/// scenes, tactical maps and their imports, items, skills, interactions,
+/// and relationships.
 pub struct CoreServices {
     pub world_service: WorldServiceImpl,
     pub character_service: CharacterServiceImpl,
     pub location_service: LocationServiceImpl,
+    pub grid_map_service: GridMapServiceImpl,
+    pub map_import_service: MapImportService,
+    pub item_service: ItemServiceImpl,
     pub scene_service: SceneServiceImpl,
     pub skill_service: SkillServiceImpl,
     pub interaction_service: InteractionServiceImpl,
@@ -22,10 +26,14 @@ pub struct CoreServices {
 
 impl CoreServices {
     /// Creates a new CoreServices instance with all the core domain services
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         world_service: WorldServiceImpl,
         character_service: CharacterServiceImpl,
         location_service: LocationServiceImpl,
+        grid_map_service: GridMapServiceImpl,
+        map_import_service: MapImportService,
+        item_service: ItemServiceImpl,
         scene_service: SceneServiceImpl,
         skill_service: SkillServiceImpl,
         interaction_service: InteractionServiceImpl,
@@ -35,6 +43,9 @@ impl CoreServices {
             world_service,
             character_service,
             location_service,
+            grid_map_service,
+            map_import_service,
+            item_service,
             scene_service,
             skill_service,
             interaction_service,
@@ -5,16 +5,17 @@ use std::sync::Arc;
 use crate::application::dto::ApprovalItem;
 use crate::application::ports::outbound::LlmPort;
 use crate::application::services::{
-    challenge_resolution_service::ChallengeResolutionService, ChallengeOutcomeApprovalService,
-    ChallengeServiceImpl, EventChainServiceImpl, EventEffectExecutor, NarrativeEventApprovalService,
+    challenge_resolution_service::ChallengeResolutionService, AreaEffectService, ChallengeOutcomeApprovalService,
+    ChallengeServiceImpl, CombatService, ConditionService, GridTokenService, DiceRollService, EventChainServiceImpl, EventEffectExecutor, NarrativeEventApprovalService,
     NarrativeEventServiceImpl, PlayerCharacterServiceImpl, SkillServiceImpl, StoryEventService,
-    TriggerEvaluationService,
+    TriggerEvaluationService, VisionService,
 };
 
 /// Services for game mechanics, challenges, and narrative events
 ///
 /// This struct groups services related to the gameplay and storytelling
-/// aspects: story events, challenges, narrative events, and their approval workflows.
+/// aspects: story events, challenges, conditions, narrative events, and their
+/// approval workflows.
 ///
 /// Generic over `L: LlmPort` for LLM-powered suggestion generation.
 pub struct GameServices<L: LlmPort> {
@@ -30,6 +31,8 @@ pub struct GameServices<L: LlmPort> {
         >,
     >,
     pub challenge_outcome_approval_service: Arc<ChallengeOutcomeApprovalService<L>>,
+    /// Service for seeded dice rolls and the session roll log
+    pub dice_roll_service: Arc<DiceRollService>,
     pub narrative_event_service: NarrativeEventServiceImpl,
     pub narrative_event_approval_service: Arc<NarrativeEventApprovalService<NarrativeEventServiceImpl>>,
     pub event_chain_service: EventChainServiceImpl,
@@ -37,6 +40,16 @@ pub struct GameServices<L: LlmPort> {
     pub trigger_evaluation_service: Arc<TriggerEvaluationService>,
     /// Service for executing narrative event outcome effects (Phase 2)
     pub event_effect_executor: Arc<EventEffectExecutor>,
+    /// Service for timed conditions on NPCs and player characters
+    pub condition_service: ConditionService,
+    /// Service for combat encounters, initiative and turn order
+    pub combat_service: CombatService,
+    /// Service for line of sight and fog of war on grid maps
+    pub vision_service: VisionService,
+    /// Service for token placement and movement on grid maps
+    pub grid_token_service: GridTokenService,
+    /// Service for areas of effect and their saving throws on grid maps
+    pub area_effect_service: AreaEffectService,
 }
 
 impl<L: LlmPort + 'static> GameServices<L> {
@@ -55,22 +68,34 @@ impl<L: LlmPort + 'static> GameServices<L> {
             >,
         >,
         challenge_outcome_approval_service: Arc<ChallengeOutcomeApprovalService<L>>,
+        dice_roll_service: Arc<DiceRollService>,
         narrative_event_service: NarrativeEventServiceImpl,
         narrative_event_approval_service: Arc<NarrativeEventApprovalService<NarrativeEventServiceImpl>>,
         event_chain_service: EventChainServiceImpl,
         trigger_evaluation_service: Arc<TriggerEvaluationService>,
         event_effect_executor: Arc<EventEffectExecutor>,
+        condition_service: ConditionService,
+        combat_service: CombatService,
+        vision_service: VisionService,
+        grid_token_service: GridTokenService,
+        area_effect_service: AreaEffectService,
     ) -> Self {
         Self {
             story_event_service,
             challenge_service,
             challenge_resolution_service,
             challenge_outcome_approval_service,
+            dice_roll_service,
             narrative_event_service,
             narrative_event_approval_service,
             event_chain_service,
             trigger_evaluation_service,
             event_effect_executor,
+            condition_service,
+            combat_service,
+            vision_service,
+            grid_token_service,
+            area_effect_service,
         }
     }
 }
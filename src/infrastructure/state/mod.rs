@@ -24,20 +24,20 @@ use tokio::sync::RwLock;
 
 use crate::application::ports::outbound::AsyncSessionPort;
 use crate::application::services::{
-    AssetGenerationQueueService, AssetServiceImpl,
+    AdvancementService, AssetGenerationQueueService, AssetServiceImpl,
     challenge_resolution_service::ChallengeResolutionService, ChallengeOutcomeApprovalService,
-    ChallengeServiceImpl, CharacterServiceImpl, DMActionQueueService, DMApprovalQueueService,
-    EventChainServiceImpl, InteractionServiceImpl, LLMQueueService, LocationServiceImpl,
+    ChallengeServiceImpl, CharacterServiceImpl, CombatService, ConditionService, DiceRollService, DMActionQueueService, DMApprovalQueueService,
+    EventChainServiceImpl, GridMapServiceImpl, InteractionServiceImpl, ItemServiceImpl, LLMQueueService, LocationServiceImpl, MapImportService,
     NarrativeEventApprovalService, NarrativeEventServiceImpl, PlayerActionQueueService,
     PlayerCharacterServiceImpl, SceneResolutionServiceImpl, SceneServiceImpl, SettingsService,
-    SheetTemplateService, SkillServiceImpl, StoryEventService, RelationshipServiceImpl,
+    SheetResourceService, SheetTemplateService, SkillServiceImpl, StoryEventService, RelationshipServiceImpl,
     WorkflowConfigService, WorldServiceImpl, GenerationQueueProjectionService, SessionJoinService,
-    OutcomeTriggerService, TriggerEvaluationService, EventEffectExecutor,
+    OutcomeTriggerService, TriggerEvaluationService, EventEffectExecutor, GridTokenService, VisionService, AreaEffectService,
 };
 use crate::application::services::generation_service::{GenerationService, GenerationEvent};
 use crate::application::dto::AppEvent;
 use crate::application::ports::outbound::{
-    AppEventRepositoryPort, EventBusPort, GenerationReadStatePort,
+    AppEventRepositoryPort, EventBusPort, GenerationReadStatePort, RollLogPort,
 };
 use crate::infrastructure::comfyui::ComfyUIClient;
 use crate::infrastructure::config::AppConfig;
@@ -47,7 +47,7 @@ use crate::infrastructure::ollama::OllamaClient;
 use crate::infrastructure::persistence::{Neo4jRepository, SqliteSettingsRepository};
 use crate::infrastructure::queues::QueueFactory;
 use crate::infrastructure::repositories::{
-    SqliteAppEventRepository, SqliteGenerationReadStateRepository,
+    SqliteAppEventRepository, SqliteGenerationReadStateRepository, SqliteRollLogRepository,
 };
 use crate::infrastructure::session::SessionManager;
 use crate::infrastructure::session_adapter::SessionManagerAdapter;
@@ -122,6 +122,12 @@ impl AppState {
             Arc::new(repository.characters());
         let location_repo: Arc<dyn crate::application::ports::outbound::LocationRepositoryPort> =
             Arc::new(repository.locations());
+        let grid_map_repo: Arc<dyn crate::application::ports::outbound::GridMapRepositoryPort> =
+            Arc::new(repository.grid_maps());
+        let region_repo: Arc<dyn crate::application::ports::outbound::RegionRepositoryPort> =
+            Arc::new(repository.regions());
+        let grid_token_repo: Arc<dyn crate::application::ports::outbound::GridTokenRepositoryPort> =
+            Arc::new(repository.grid_tokens());
         let scene_repo: Arc<dyn crate::application::ports::outbound::SceneRepositoryPort> =
             Arc::new(repository.scenes());
         let relationship_repo: Arc<dyn crate::application::ports::outbound::RelationshipRepositoryPort> =
@@ -134,6 +140,8 @@ impl AppState {
             Arc::new(repository.story_events());
         let challenge_repo: Arc<dyn crate::application::ports::outbound::ChallengeRepositoryPort> =
             Arc::new(repository.challenges());
+        let item_repo: Arc<dyn crate::application::ports::outbound::ItemRepositoryPort> =
+            Arc::new(repository.items());
         let asset_repo: Arc<dyn crate::application::ports::outbound::AssetRepositoryPort> =
             Arc::new(repository.assets());
         let workflow_repo: Arc<dyn crate::application::ports::outbound::WorkflowRepositoryPort> =
@@ -160,10 +168,14 @@ impl AppState {
             settings_service.clone(),
         );
         let location_service = LocationServiceImpl::new(world_repo.clone(), location_repo.clone());
+        let grid_map_service =
+            GridMapServiceImpl::new(grid_map_repo, world_repo.clone(), location_repo.clone());
+        let item_service = ItemServiceImpl::new(item_repo, world_repo.clone(), character_repo.clone());
         let relationship_repo_for_effects = relationship_repo.clone();
         let relationship_service = RelationshipServiceImpl::new(relationship_repo);
         let scene_repo_for_resolution = scene_repo.clone();
         let character_repo_for_triggers = character_repo.clone();
+        let condition_service = ConditionService::new(character_repo.clone(), player_character_repo.clone());
         let scene_service = SceneServiceImpl::new(scene_repo.clone(), location_repo.clone(), character_repo);
         let skill_service = SkillServiceImpl::new(skill_repo.clone(), world_repo.clone());
         let interaction_service = InteractionServiceImpl::new(interaction_repo);
@@ -182,24 +194,34 @@ impl AppState {
         let event_chain_service = EventChainServiceImpl::new(event_chain_repo);
         let asset_repo_for_service = asset_repo.clone();
         let asset_service = AssetServiceImpl::new(asset_repo_for_service);
+
+        // Create map import service (Universal VTT and Tiled maps)
+        let map_import_service = MapImportService::new(
+            Arc::new(grid_map_service.clone()),
+            location_repo.clone(),
+            region_repo,
+            Arc::new(asset_service.clone()),
+            std::path::PathBuf::from("./data/assets"),
+        );
         let workflow_config_service = WorkflowConfigService::new(workflow_repo);
-        let sheet_template_service = SheetTemplateService::new(sheet_template_repo);
+        let sheet_template_service = SheetTemplateService::new(
+            sheet_template_repo.clone(),
+            player_character_repo.clone(),
+        );
         let player_character_repo_for_triggers = player_character_repo.clone();
+        let player_character_repo_for_resources = player_character_repo.clone();
+        let player_character_repo_for_advancement = player_character_repo.clone();
         let player_character_service = PlayerCharacterServiceImpl::new(
             player_character_repo.clone(),
             location_repo.clone(),
             world_repo.clone(),
+            sheet_template_repo,
         );
         let scene_resolution_service = SceneResolutionServiceImpl::new(
             player_character_repo,
             scene_repo_for_resolution,
         );
 
-        // Create outcome trigger service for challenge resolution (Phase 22D)
-        let outcome_trigger_service = Arc::new(OutcomeTriggerService::new(
-            challenge_repo.clone(),
-        ));
-
         // Initialize queue infrastructure using factory
         let queue_factory = QueueFactory::new(config.queue.clone()).await?;
         tracing::info!("Queue backend: {}", queue_factory.config().backend);
@@ -233,6 +255,12 @@ impl AppState {
         generation_read_state_repository.init_schema().await?;
         let generation_read_state_repository: Arc<dyn GenerationReadStatePort> =
             Arc::new(generation_read_state_repository);
+        // The roll log lives alongside app events so rolls can be audited per session
+        let roll_log_repository: Arc<dyn RollLogPort> = Arc::new(
+            SqliteRollLogRepository::new(app_event_repository_impl.pool().clone())
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to initialize roll log repository: {}", e))?,
+        );
 
         let app_event_repository: Arc<dyn AppEventRepositoryPort> =
             Arc::new(app_event_repository_impl);
@@ -292,7 +320,10 @@ impl AppState {
             queue_factory.asset_generation_notifier(),
         ));
 
-        let dm_approval_queue_service = Arc::new(DMApprovalQueueService::new(approval_queue.clone()));
+        let dm_approval_queue_service = Arc::new(
+            DMApprovalQueueService::new(approval_queue.clone())
+                .with_condition_service(condition_service.clone()),
+        );
 
         // Create generation service (generation_event_tx already created above)
         let generation_service = Arc::new(GenerationService::new(
@@ -303,6 +334,28 @@ impl AppState {
             generation_event_tx,
         ));
 
+        // Create dice roll service (seeded session rolls, recorded in the roll log)
+        let dice_roll_service = Arc::new(DiceRollService::new(
+            async_session_port.clone(),
+            roll_log_repository,
+        ));
+
+        // Create advancement service (experience, milestones and level-ups)
+        let advancement_service = AdvancementService::new(
+            Arc::new(player_character_service.clone()),
+            player_character_repo_for_advancement,
+            world_repo.clone(),
+            skill_repo.clone(),
+            story_event_service.clone(),
+            async_session_port.clone(),
+        );
+
+        // Create outcome trigger service for challenge resolution (Phase 22D)
+        let outcome_trigger_service = Arc::new(
+            OutcomeTriggerService::new(challenge_repo.clone())
+                .with_advancement_service(advancement_service.clone()),
+        );
+
         // Create challenge outcome approval service (P3.3) - must be created before resolution service
         // Wire LLM port for suggestion generation and settings service for branch count
         let llm_for_suggestions = Arc::new(llm_client.clone());
@@ -322,9 +375,12 @@ impl AppState {
                 Arc::new(challenge_service.clone()),
                 Arc::new(skill_service.clone()),
                 Arc::new(player_character_service.clone()),
+                world_repo.clone(),
+                character_repo_for_triggers.clone(),
                 event_bus.clone(),
                 dm_approval_queue_service.clone(),
                 outcome_trigger_service,
+                dice_roll_service.clone(),
             )
             .with_outcome_approval_service(challenge_outcome_approval_service.clone()),
         );
@@ -336,6 +392,49 @@ impl AppState {
             Arc::new(story_event_service.clone()),
         ));
 
+        // Create sheet resource service
+        let sheet_resource_service = SheetResourceService::new(
+            Arc::new(player_character_service.clone()),
+            player_character_repo_for_resources,
+            world_repo.clone(),
+            story_event_service.clone(),
+        );
+
+        // Create combat service (encounters, initiative and turn order)
+        let combat_service = CombatService::new(
+            Arc::new(player_character_service.clone()),
+            character_repo_for_triggers.clone(),
+            world_repo.clone(),
+            dice_roll_service.clone(),
+            sheet_resource_service.clone(),
+            story_event_service.clone(),
+            async_session_port.clone(),
+        );
+
+        // Create vision service (line of sight and fog of war)
+        let vision_service = VisionService::new(
+            Arc::new(grid_map_service.clone()),
+            Arc::new(player_character_service.clone()),
+            async_session_port.clone(),
+        );
+
+        // Create grid token service (token placement and movement)
+        let grid_token_service = GridTokenService::new(
+            grid_token_repo,
+            Arc::new(grid_map_service.clone()),
+            combat_service.clone(),
+            vision_service.clone(),
+            async_session_port.clone(),
+        );
+
+        // Create area effect service (spell and hazard templates)
+        let area_effect_service = AreaEffectService::new(
+            Arc::new(grid_map_service.clone()),
+            grid_token_service.clone(),
+            async_session_port.clone(),
+        )
+        .with_saving_throws(challenge_resolution_service.clone());
+
         // Create trigger evaluation service (Phase 2)
         let trigger_evaluation_service = Arc::new(TriggerEvaluationService::new(
             narrative_event_repo_for_triggers,
@@ -346,12 +445,17 @@ impl AppState {
         ));
 
         // Create event effect executor (Phase 2)
-        let event_effect_executor = Arc::new(EventEffectExecutor::new(
-            async_session_port.clone(),
-            challenge_repo_for_effects,
-            narrative_event_repo_for_effects,
-            relationship_repo_for_effects,
-        ));
+        let event_effect_executor = Arc::new(
+            EventEffectExecutor::new(
+                async_session_port.clone(),
+                challenge_repo_for_effects,
+                narrative_event_repo_for_effects,
+                relationship_repo_for_effects,
+            )
+            .with_advancement_service(advancement_service.clone())
+            .with_combat_service(combat_service.clone())
+            .with_area_effect_service(area_effect_service.clone()),
+        );
 
         // Create session join service
         let session_join_service = Arc::new(SessionJoinService::new(
@@ -371,6 +475,9 @@ impl AppState {
             world_service,
             character_service,
             location_service,
+            grid_map_service,
+            map_import_service,
+            item_service,
             scene_service,
             skill_service,
             interaction_service,
@@ -382,11 +489,17 @@ impl AppState {
             challenge_service,
             challenge_resolution_service,
             challenge_outcome_approval_service,
+            dice_roll_service,
             narrative_event_service,
             narrative_event_approval_service,
             event_chain_service,
             trigger_evaluation_service,
             event_effect_executor,
+            condition_service,
+            combat_service,
+            vision_service,
+            grid_token_service,
+            area_effect_service,
         );
 
         let queues = QueueServices::new(
@@ -406,6 +519,8 @@ impl AppState {
 
         let player = PlayerServices::new(
             sheet_template_service,
+            sheet_resource_service,
+            advancement_service,
             player_character_service,
             scene_resolution_service,
             session_join_service,
@@ -2,6 +2,7 @@
 //!
 //! Message types are aligned between Engine and Player for seamless communication.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use axum::{
@@ -14,7 +15,10 @@ use axum::{
 use futures_util::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 
-use crate::application::dto::{AdHocOutcomesDto, ChallengeOutcomeDecision, DMAction};
+use crate::application::dto::{
+    AdHocOutcomesDto, AreaEffectDto, ChallengeOutcomeDecision, DMAction, GridMapDto, GridPathDto,
+    GridTokenDto, ReachableTileDto, TokenMovedDto,
+};
 use crate::application::services::scene_service::SceneService;
 use crate::application::services::scene_resolution_service::SceneResolutionService;
 use crate::application::services::player_character_service::PlayerCharacterService;
@@ -22,8 +26,15 @@ use crate::application::services::location_service::LocationService;
 use crate::application::services::interaction_service::InteractionService;
 use crate::application::services::session_join_service as sjs;
 use crate::application::services::challenge_resolution_service as crs;
+use crate::application::services::{
+    CombatError, GridMapService, SavingThrowError, TokenError, TokenMover, VisionError,
+};
 use crate::application::ports::outbound::{PlayerCharacterRepositoryPort, SessionParticipantRole};
-use crate::domain::value_objects::{TimeOfDay, ActionId, RegionRelationshipType};
+use crate::domain::entities::{CombatantRef, Facing, GridMapError, GridPosition};
+use crate::domain::value_objects::{
+    ActionId, ChallengeId, GridMapId, GridTokenId, PlayerCharacterId, RegionRelationshipType, SessionId,
+    TimeOfDay,
+};
 use crate::infrastructure::session::ClientId;
 use crate::infrastructure::state::AppState;
 
@@ -89,6 +100,12 @@ fn to_challenge_outcome_decision(decision: messages::ChallengeOutcomeDecisionDat
         messages::ChallengeOutcomeDecisionData::Suggest { guidance } => {
             ChallengeOutcomeDecision::Suggest { guidance }
         }
+        messages::ChallengeOutcomeDecisionData::GrantRollMode { roll_mode } => {
+            ChallengeOutcomeDecision::GrantRollMode { roll_mode }
+        }
+        messages::ChallengeOutcomeDecisionData::SetOpponentModifier { modifier } => {
+            ChallengeOutcomeDecision::SetOpponentModifier { modifier }
+        }
     }
 }
 
@@ -171,6 +188,10 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
             participant.user_id,
             session_id
         );
+        // The session ends once its last client leaves
+        if state.async_session_port.get_session_world_id(session_id).await.is_none() {
+            state.game.challenge_resolution_service.end_session(session_id).await;
+        }
     }
 
     // Cancel the send task
@@ -797,7 +818,12 @@ async fn handle_message(
             }
         }
 
-        ClientMessage::ChallengeRoll { challenge_id, roll } => {
+        ClientMessage::ChallengeRoll {
+            challenge_id,
+            roll,
+            roll_mode,
+            skill_id,
+        } => {
             tracing::debug!(
                 "Received challenge roll: {} for challenge {}",
                 roll,
@@ -805,7 +831,7 @@ async fn handle_message(
             );
             state
                 .game.challenge_resolution_service
-                .handle_roll(client_id.to_string(), challenge_id, roll)
+                .handle_roll(client_id.to_string(), challenge_id, roll, roll_mode, skill_id)
                 .await
                 .and_then(value_to_server_message)
         }
@@ -813,6 +839,8 @@ async fn handle_message(
         ClientMessage::ChallengeRollInput {
             challenge_id,
             input_type,
+            roll_mode,
+            skill_id,
         } => {
             tracing::debug!(
                 "Received challenge roll input: {:?} for challenge {}",
@@ -821,7 +849,26 @@ async fn handle_message(
             );
             state
                 .game.challenge_resolution_service
-                .handle_roll_input(client_id.to_string(), challenge_id, to_service_dice_input(input_type))
+                .handle_roll_input(
+                    client_id.to_string(),
+                    challenge_id,
+                    to_service_dice_input(input_type),
+                    roll_mode,
+                    skill_id,
+                )
+                .await
+                .and_then(value_to_server_message)
+        }
+
+        ClientMessage::RollDice {
+            formula,
+            label,
+            is_table_roll,
+        } => {
+            tracing::debug!("Received dice roll: {} ({:?})", formula, label);
+            state
+                .game.dice_roll_service
+                .handle_free_roll(client_id.to_string(), formula, label, is_table_roll)
                 .await
                 .and_then(value_to_server_message)
         }
@@ -829,10 +876,11 @@ async fn handle_message(
         ClientMessage::TriggerChallenge {
             challenge_id,
             target_character_id,
+            opponent,
         } => {
             state
                 .game.challenge_resolution_service
-                .handle_trigger(client_id.to_string(), challenge_id, target_character_id)
+                .handle_trigger(client_id.to_string(), challenge_id, target_character_id, opponent)
                 .await
                 .and_then(value_to_server_message)
         }
@@ -995,11 +1043,26 @@ async fn handle_message(
             // Convert wire decision to service decision
             let svc_decision = to_challenge_outcome_decision(decision);
 
-            // Process the decision via the approval service
-            match state.game.challenge_outcome_approval_service
-                .process_decision(session_id, &resolution_id, svc_decision)
-                .await
-            {
+            // Granted roll modes and opponent modifiers re-evaluate the roll through
+            // challenge resolution; everything else is processed by the approval service
+            let result = match svc_decision {
+                ChallengeOutcomeDecision::GrantRollMode { roll_mode } => {
+                    state.game.challenge_resolution_service
+                        .grant_roll_mode(session_id, &resolution_id, roll_mode)
+                        .await
+                }
+                ChallengeOutcomeDecision::SetOpponentModifier { modifier } => {
+                    state.game.challenge_resolution_service
+                        .set_opponent_modifier(session_id, &resolution_id, modifier)
+                        .await
+                }
+                svc_decision => {
+                    state.game.challenge_outcome_approval_service
+                        .process_decision(session_id, &resolution_id, svc_decision)
+                        .await
+                }
+            };
+            match result {
                 Ok(()) => {
                     // Success - resolution broadcast is handled by the service
                     None
@@ -1519,16 +1582,23 @@ async fn handle_message(
                 let _ = state.async_session_port.broadcast_except(session_id, msg_json, "").await;
             }
 
+            crate::infrastructure::websocket_helpers::expire_conditions(state, session_id).await;
+
             tracing::info!("Game time advanced by {} hours", hours);
             None
         }
 
         // =========================================================================
-        // Phase 23C: Navigation
+        // Sheet Resources
         // =========================================================================
 
-        ClientMessage::SelectPlayerCharacter { pc_id } => {
-            tracing::info!("Player selecting PC {}", pc_id);
+        ClientMessage::ModifyResource {
+            pc_id,
+            field_id,
+            operation,
+            reason,
+        } => {
+            tracing::info!("Modifying resource {} on PC {}", field_id, pc_id);
 
             let client_id_str = client_id.to_string();
             let session_id = match state.async_session_port.get_client_session(&client_id_str).await {
@@ -1541,7 +1611,6 @@ async fn handle_message(
                 }
             };
 
-            // Parse PC ID
             let pc_uuid = match uuid::Uuid::parse_str(&pc_id) {
                 Ok(uuid) => crate::domain::value_objects::PlayerCharacterId::from_uuid(uuid),
                 Err(_) => {
@@ -1552,9 +1621,38 @@ async fn handle_message(
                 }
             };
 
-            // Get PC details
-            let pc = match state.repository.player_characters().get(pc_uuid).await {
-                Ok(Some(pc)) => pc,
+            // Players may only change their own character's resources
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                let user_id = state.async_session_port.get_client_user_id(&client_id_str).await;
+                let owner = match state.repository.player_characters().get(pc_uuid).await {
+                    Ok(pc) => pc.map(|pc| pc.user_id),
+                    Err(e) => {
+                        return Some(ServerMessage::Error {
+                            code: "DATABASE_ERROR".to_string(),
+                            message: format!("Failed to fetch PC: {}", e),
+                        });
+                    }
+                };
+                if owner.is_none() || owner != user_id {
+                    return Some(ServerMessage::Error {
+                        code: "NOT_AUTHORIZED".to_string(),
+                        message: "You can only change your own character's resources".to_string(),
+                    });
+                }
+            }
+
+            let game_time = {
+                let sessions = state.sessions.read().await;
+                sessions.get_session(session_id).map(|session| session.display_game_time())
+            };
+
+            let change = match state
+                .player
+                .sheet_resource_service
+                .modify_resource(pc_uuid, &field_id, operation.into(), reason.clone(), game_time)
+                .await
+            {
+                Ok(Some((_, change))) => change,
                 Ok(None) => {
                     return Some(ServerMessage::Error {
                         code: "PC_NOT_FOUND".to_string(),
@@ -1562,26 +1660,40 @@ async fn handle_message(
                     });
                 }
                 Err(e) => {
+                    let code = if e.downcast_ref::<crate::domain::entities::ResourceError>().is_some() {
+                        "RESOURCE_REFUSED"
+                    } else {
+                        "RESOURCE_ERROR"
+                    };
                     return Some(ServerMessage::Error {
-                        code: "DATABASE_ERROR".to_string(),
-                        message: format!("Failed to fetch PC: {}", e),
+                        code: code.to_string(),
+                        message: e.to_string(),
                     });
                 }
             };
 
-            // Send PcSelected response
-            Some(ServerMessage::PcSelected {
-                pc_id: pc_id.clone(),
-                pc_name: pc.name.clone(),
-                location_id: pc.current_location_id.to_string(),
-                region_id: pc.current_region_id.map(|r| r.to_string()),
-            })
+            let resource_changed = ServerMessage::ResourceChanged {
+                pc_id,
+                change: change.into(),
+                reason,
+            };
+            if let Ok(msg_json) = serde_json::to_value(&resource_changed) {
+                let _ = state.async_session_port.broadcast_except(session_id, msg_json, "").await;
+            }
+            None
         }
 
-        ClientMessage::MoveToRegion { pc_id, region_id } => {
-            tracing::info!("PC {} moving to region {}", pc_id, region_id);
+        ClientMessage::TakeRest { rest_type, pc_ids } => {
+            tracing::info!("DM calling for a {} rest", rest_type);
 
             let client_id_str = client_id.to_string();
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can call for a rest".to_string(),
+                });
+            }
+
             let session_id = match state.async_session_port.get_client_session(&client_id_str).await {
                 Some(sid) => sid,
                 None => {
@@ -1592,9 +1704,12 @@ async fn handle_message(
                 }
             };
 
-            // Parse IDs
-            let pc_uuid = match uuid::Uuid::parse_str(&pc_id) {
-                Ok(uuid) => crate::domain::value_objects::PlayerCharacterId::from_uuid(uuid),
+            let pc_ids = match pc_ids
+                .iter()
+                .map(|id| uuid::Uuid::parse_str(id).map(crate::domain::value_objects::PlayerCharacterId::from_uuid))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(ids) => ids,
                 Err(_) => {
                     return Some(ServerMessage::Error {
                         code: "INVALID_PC_ID".to_string(),
@@ -1602,172 +1717,94 @@ async fn handle_message(
                     });
                 }
             };
-            let region_uuid = match uuid::Uuid::parse_str(&region_id) {
-                Ok(uuid) => crate::domain::value_objects::RegionId::from_uuid(uuid),
-                Err(_) => {
-                    return Some(ServerMessage::Error {
-                        code: "INVALID_REGION_ID".to_string(),
-                        message: "Invalid region ID format".to_string(),
-                    });
-                }
-            };
-
-            // Get current PC to check if movement is valid
-            let pc = match state.repository.player_characters().get(pc_uuid).await {
-                Ok(Some(pc)) => pc,
-                Ok(None) => {
-                    return Some(ServerMessage::Error {
-                        code: "PC_NOT_FOUND".to_string(),
-                        message: "Player character not found".to_string(),
-                    });
-                }
-                Err(e) => {
-                    return Some(ServerMessage::Error {
-                        code: "DATABASE_ERROR".to_string(),
-                        message: format!("Failed to fetch PC: {}", e),
-                    });
-                }
-            };
 
-            // Check if movement is allowed (check for locked connections)
-            if let Some(current_region_id) = pc.current_region_id {
-                let connections = match state.repository.regions().get_connections(current_region_id).await {
-                    Ok(conns) => conns,
-                    Err(e) => {
+            let (world_id, started_at) = {
+                let sessions = state.sessions.read().await;
+                match sessions.get_session(session_id) {
+                    Some(session) => (session.world_id, session.display_game_time()),
+                    None => {
                         return Some(ServerMessage::Error {
-                            code: "DATABASE_ERROR".to_string(),
-                            message: format!("Failed to fetch connections: {}", e),
-                        });
-                    }
-                };
-
-                // Find the connection to target region
-                if let Some(conn) = connections.iter().find(|c| c.to_region == region_uuid) {
-                    if conn.is_locked {
-                        return Some(ServerMessage::MovementBlocked {
-                            pc_id: pc_id.clone(),
-                            reason: conn.lock_description.clone().unwrap_or_else(|| "The path is locked.".to_string()),
+                            code: "SESSION_NOT_FOUND".to_string(),
+                            message: "Session not found".to_string(),
                         });
                     }
                 }
-            }
+            };
 
-            // Get target region
-            let target_region = match state.repository.regions().get(region_uuid).await {
-                Ok(Some(region)) => region,
+            let outcome = match state
+                .player
+                .sheet_resource_service
+                .take_rest(world_id, &rest_type, &pc_ids, Some(started_at))
+                .await
+            {
+                Ok(Some(outcome)) => outcome,
                 Ok(None) => {
                     return Some(ServerMessage::Error {
-                        code: "REGION_NOT_FOUND".to_string(),
-                        message: "Target region not found".to_string(),
+                        code: "REST_TYPE_NOT_FOUND".to_string(),
+                        message: format!("Unknown rest type: {}", rest_type),
                     });
                 }
                 Err(e) => {
                     return Some(ServerMessage::Error {
-                        code: "DATABASE_ERROR".to_string(),
-                        message: format!("Failed to fetch region: {}", e),
+                        code: "RESOURCE_ERROR".to_string(),
+                        message: e.to_string(),
                     });
                 }
             };
 
-            // Update PC position
-            if let Err(e) = state.repository.player_characters()
-                .update_position(pc_uuid, target_region.location_id, Some(region_uuid))
-                .await
-            {
-                return Some(ServerMessage::Error {
-                    code: "UPDATE_ERROR".to_string(),
-                    message: format!("Failed to update position: {}", e),
-                });
-            }
-
-            // Get location for name
-            let location = state.repository.locations().get(target_region.location_id).await
-                .ok().flatten();
-            let location_name = location.as_ref().map(|l| l.name.clone()).unwrap_or_default();
-            let backdrop = target_region.backdrop_asset.clone()
-                .or_else(|| location.as_ref().and_then(|l| l.backdrop_asset.clone()));
-
-            // Get NPCs present (simple rule-based)
-            let game_time = {
-                let sessions = state.sessions.read().await;
-                sessions.get_session(session_id)
-                    .map(|s| s.game_time().clone())
-                    .unwrap_or_default()
-            };
-
-            let npc_relationships = state.repository.characters()
-                .get_npcs_related_to_region(region_uuid)
-                .await
-                .unwrap_or_default();
-
-            let time_of_day = game_time.time_of_day();
-            let npcs_present: Vec<messages::NpcPresenceData> = npc_relationships
-                .into_iter()
-                .filter_map(|(character, rel_type)| {
-                    let is_present = rel_type.is_npc_present(time_of_day);
-                    if is_present {
-                        Some(messages::NpcPresenceData {
-                            character_id: character.id.to_string(),
-                            name: character.name,
-                            sprite_asset: character.sprite_asset,
-                            portrait_asset: character.portrait_asset,
-                        })
-                    } else {
-                        None
-                    }
+            // The rest takes game time
+            let game_time_info = {
+                let mut sessions = state.sessions.write().await;
+                sessions.get_session_mut(session_id).map(|session| {
+                    session.advance_time_minutes(outcome.rest.duration_minutes);
+                    (
+                        session.display_game_time(),
+                        session.time_of_day().to_string(),
+                        session.is_time_paused(),
+                    )
                 })
-                .collect();
-
-            // Get navigation options
-            let connections = state.repository.regions().get_connections(region_uuid).await.unwrap_or_default();
-            let exits = state.repository.regions().get_exits(region_uuid).await.unwrap_or_default();
+            };
 
-            let mut connected_regions = Vec::new();
-            for conn in connections {
-                if let Ok(Some(target)) = state.repository.regions().get(conn.to_region).await {
-                    connected_regions.push(messages::NavigationTarget {
-                        region_id: conn.to_region.to_string(),
-                        name: target.name,
-                        is_locked: conn.is_locked,
-                        lock_description: conn.lock_description,
-                    });
-                }
+            let mut outcome = crate::application::dto::RestOutcomeDto::from(outcome);
+            outcome.game_time = game_time_info.as_ref().map(|info| info.0.clone());
+            if let Ok(msg_json) = serde_json::to_value(&ServerMessage::RestTaken { outcome }) {
+                let _ = state.async_session_port.broadcast_except(session_id, msg_json, "").await;
             }
 
-            let mut exit_targets = Vec::new();
-            for exit in exits {
-                if let Ok(Some(target_loc)) = state.repository.locations().get(exit.to_location).await {
-                    exit_targets.push(messages::NavigationExit {
-                        location_id: exit.to_location.to_string(),
-                        location_name: target_loc.name,
-                        arrival_region_id: exit.arrival_region_id.to_string(),
-                        description: exit.description,
-                    });
+            if let Some((display, time_of_day, is_paused)) = game_time_info {
+                let time_updated = ServerMessage::GameTimeUpdated {
+                    display,
+                    time_of_day,
+                    is_paused,
+                };
+                if let Ok(msg_json) = serde_json::to_value(&time_updated) {
+                    let _ = state.async_session_port.broadcast_except(session_id, msg_json, "").await;
                 }
             }
-
-            Some(ServerMessage::SceneChanged {
-                pc_id,
-                region: messages::RegionData {
-                    id: region_uuid.to_string(),
-                    name: target_region.name,
-                    location_id: target_region.location_id.to_string(),
-                    location_name,
-                    backdrop_asset: backdrop,
-                    atmosphere: target_region.atmosphere,
-                },
-                npcs_present,
-                navigation: messages::NavigationData {
-                    connected_regions,
-                    exits: exit_targets,
-                },
-            })
+            crate::infrastructure::websocket_helpers::expire_conditions(state, session_id).await;
+            None
         }
 
-        ClientMessage::ExitToLocation { pc_id, location_id, arrival_region_id } => {
-            tracing::info!("PC {} exiting to location {}", pc_id, location_id);
+        // =========================================================================
+        // Advancement
+        // =========================================================================
+
+        ClientMessage::AwardExperience {
+            pc_ids,
+            kind,
+            amount,
+            reason,
+        } => {
+            tracing::info!("DM awarding {} {:?}", amount, kind);
 
             let client_id_str = client_id.to_string();
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can award experience".to_string(),
+                });
+            }
+
             let session_id = match state.async_session_port.get_client_session(&client_id_str).await {
                 Some(sid) => sid,
                 None => {
@@ -1778,9 +1815,12 @@ async fn handle_message(
                 }
             };
 
-            // Parse IDs
-            let pc_uuid = match uuid::Uuid::parse_str(&pc_id) {
-                Ok(uuid) => crate::domain::value_objects::PlayerCharacterId::from_uuid(uuid),
+            let pc_ids = match pc_ids
+                .iter()
+                .map(|id| uuid::Uuid::parse_str(id).map(crate::domain::value_objects::PlayerCharacterId::from_uuid))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(ids) => ids,
                 Err(_) => {
                     return Some(ServerMessage::Error {
                         code: "INVALID_PC_ID".to_string(),
@@ -1788,8 +1828,935 @@ async fn handle_message(
                     });
                 }
             };
-            let location_uuid = match uuid::Uuid::parse_str(&location_id) {
-                Ok(uuid) => crate::domain::value_objects::LocationId::from_uuid(uuid),
+            if amount == 0 {
+                return Some(ServerMessage::Error {
+                    code: "INVALID_AMOUNT".to_string(),
+                    message: "Amount must be at least 1".to_string(),
+                });
+            }
+
+            // The service announces each award to the session
+            let award = crate::domain::value_objects::AdvancementAward::new(kind.into(), amount);
+            let advancement = &state.player.advancement_service;
+            let result = if pc_ids.is_empty() {
+                advancement.award_session(session_id, award, reason).await.map(|_| ())
+            } else {
+                let mut result = Ok(());
+                for pc_id in pc_ids {
+                    if let Err(e) = advancement.award(pc_id, award, reason.clone()).await {
+                        result = Err(e);
+                        break;
+                    }
+                }
+                result
+            };
+            result.err().map(|e| ServerMessage::Error {
+                code: "ADVANCEMENT_ERROR".to_string(),
+                message: e.to_string(),
+            })
+        }
+
+        ClientMessage::ApproveLevelUp { pc_id } => {
+            tracing::info!("DM approving level-up for PC {}", pc_id);
+
+            let client_id_str = client_id.to_string();
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can approve a level-up".to_string(),
+                });
+            }
+
+            let session_id = match state.async_session_port.get_client_session(&client_id_str).await {
+                Some(sid) => sid,
+                None => {
+                    return Some(ServerMessage::Error {
+                        code: "NO_SESSION".to_string(),
+                        message: "Client is not in a session".to_string(),
+                    });
+                }
+            };
+
+            let pc_uuid = match uuid::Uuid::parse_str(&pc_id) {
+                Ok(uuid) => crate::domain::value_objects::PlayerCharacterId::from_uuid(uuid),
+                Err(_) => {
+                    return Some(ServerMessage::Error {
+                        code: "INVALID_PC_ID".to_string(),
+                        message: "Invalid PC ID format".to_string(),
+                    });
+                }
+            };
+
+            let game_time = {
+                let sessions = state.sessions.read().await;
+                sessions.get_session(session_id).map(|session| session.display_game_time())
+            };
+
+            // The service announces the levels gained to the session
+            match state.player.advancement_service.level_up(pc_uuid, game_time).await {
+                Ok(Some(_)) => None,
+                Ok(None) => Some(ServerMessage::Error {
+                    code: "PC_NOT_FOUND".to_string(),
+                    message: "Player character not found".to_string(),
+                }),
+                Err(e) => {
+                    let code = if e.downcast_ref::<crate::application::services::AdvancementError>().is_some() {
+                        "LEVEL_UP_REFUSED"
+                    } else {
+                        "ADVANCEMENT_ERROR"
+                    };
+                    Some(ServerMessage::Error {
+                        code: code.to_string(),
+                        message: e.to_string(),
+                    })
+                }
+            }
+        }
+
+        // =========================================================================
+        // Combat
+        // =========================================================================
+
+        ClientMessage::StartCombat {
+            description,
+            location_id,
+            pc_ids,
+            npcs,
+        } => {
+            tracing::info!("DM starting combat: {}", description);
+
+            let client_id_str = client_id.to_string();
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can start combat".to_string(),
+                });
+            }
+            let (session_id, _, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+
+            let request = crate::application::dto::StartCombatRequestDto {
+                description,
+                location_id,
+                pc_ids,
+                npcs,
+            };
+            let start = match request.into_start() {
+                Ok(start) => start,
+                Err(_) => {
+                    return Some(ServerMessage::Error {
+                        code: "INVALID_ID".to_string(),
+                        message: "Invalid ID format".to_string(),
+                    });
+                }
+            };
+
+            // The service announces the fight to the session
+            match state.game.combat_service.start(session_id, start, game_time).await {
+                Ok(update) => {
+                    crate::infrastructure::websocket_helpers::follow_combat_update(state, session_id, &update).await;
+                    None
+                }
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::RollInitiative { combatant_id, total } => {
+            let (session_id, player_pc, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+
+            // Players roll for their own character only
+            let combatant_id = match (player_pc, combatant_id) {
+                (None, combatant_id) => combatant_id,
+                (Some(pc_id), None) => Some(pc_id.to_string()),
+                (Some(pc_id), Some(id)) if id == pc_id.to_string() => Some(id),
+                (Some(_), Some(_)) => {
+                    return Some(ServerMessage::Error {
+                        code: "NOT_AUTHORIZED".to_string(),
+                        message: "You can only roll initiative for your own character".to_string(),
+                    });
+                }
+            };
+
+            let combat = &state.game.combat_service;
+            let result = match (total, combatant_id) {
+                (Some(total), Some(id)) => combat.set_initiative(session_id, &id, total, game_time).await,
+                (Some(_), None) => {
+                    return Some(ServerMessage::Error {
+                        code: "MISSING_COMBATANT".to_string(),
+                        message: "A rolled total needs a combatant".to_string(),
+                    });
+                }
+                (None, id) => {
+                    let roller = state
+                        .async_session_port
+                        .get_client_user_id(&client_id.to_string())
+                        .await
+                        .unwrap_or_default();
+                    combat.roll_initiative(session_id, id.as_deref(), roller, game_time).await
+                }
+            };
+            match result {
+                Ok(update) => {
+                    crate::infrastructure::websocket_helpers::follow_combat_update(state, session_id, &update).await;
+                    None
+                }
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::EndTurn => {
+            let (session_id, player_pc, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            if let Err(e) = check_acting_pc(state, session_id, player_pc).await {
+                return Some(e);
+            }
+            match state.game.combat_service.next_turn(session_id, game_time).await {
+                Ok(update) => {
+                    crate::infrastructure::websocket_helpers::follow_combat_update(state, session_id, &update).await;
+                    None
+                }
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::DelayTurn => {
+            let (session_id, player_pc, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            if let Err(e) = check_acting_pc(state, session_id, player_pc).await {
+                return Some(e);
+            }
+            match state.game.combat_service.delay(session_id, game_time).await {
+                Ok(update) => {
+                    crate::infrastructure::websocket_helpers::follow_combat_update(state, session_id, &update).await;
+                    None
+                }
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::ReadyAction { trigger } => {
+            let (session_id, player_pc, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            if let Err(e) = check_acting_pc(state, session_id, player_pc).await {
+                return Some(e);
+            }
+            match state.game.combat_service.ready(session_id, trigger, game_time).await {
+                Ok(update) => {
+                    crate::infrastructure::websocket_helpers::follow_combat_update(state, session_id, &update).await;
+                    None
+                }
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::CombatantAct { combatant_id } => {
+            let (session_id, player_pc, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            if player_pc.is_some_and(|pc_id| pc_id.to_string() != combatant_id) {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "You can only act for your own character".to_string(),
+                });
+            }
+            match state.game.combat_service.act(session_id, &combatant_id, game_time).await {
+                Ok(update) => {
+                    crate::infrastructure::websocket_helpers::follow_combat_update(state, session_id, &update).await;
+                    None
+                }
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::DamageCombatant {
+            combatant_id,
+            amount,
+            reason,
+        } => {
+            let client_id_str = client_id.to_string();
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can change hit points in combat".to_string(),
+                });
+            }
+            let (session_id, _, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            match state
+                .game
+                .combat_service
+                .damage(session_id, &combatant_id, amount, reason, game_time)
+                .await
+            {
+                Ok(_) => None,
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::RemoveCombatant { combatant_id, fled } => {
+            let client_id_str = client_id.to_string();
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can remove combatants".to_string(),
+                });
+            }
+            let (session_id, _, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            match state
+                .game
+                .combat_service
+                .remove(session_id, &combatant_id, fled, game_time)
+                .await
+            {
+                Ok(_) => None,
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        ClientMessage::EndCombat { outcome } => {
+            let client_id_str = client_id.to_string();
+            if !state.async_session_port.is_client_dm(&client_id_str).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can end combat".to_string(),
+                });
+            }
+            let (session_id, _, game_time) = match combat_context(state, client_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            match state
+                .game
+                .combat_service
+                .end(session_id, outcome.map(Into::into), game_time)
+                .await
+            {
+                Ok(_) => None,
+                Err(e) => Some(combat_error(e)),
+            }
+        }
+
+        // =========================================================================
+        // Grid Maps
+        // =========================================================================
+
+        ClientMessage::FindPath { grid_map_id, request } => {
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let explored = match explored_tiles(state, client_id, id).await {
+                Ok(explored) => explored,
+                Err(e) => return Some(e),
+            };
+            match state
+                .core
+                .grid_map_service
+                .find_path(
+                    id,
+                    request.from.into(),
+                    request.to.into(),
+                    request.diagonal_rule.map(Into::into),
+                    request.occupied.into_iter().map(Into::into).collect(),
+                    explored,
+                )
+                .await
+            {
+                Ok(path) => Some(ServerMessage::PathFound {
+                    grid_map_id,
+                    path: path.map(GridPathDto::from),
+                }),
+                Err(e) => Some(grid_map_error(e)),
+            }
+        }
+
+        ClientMessage::GetMovementRange { grid_map_id, request } => {
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let explored = match explored_tiles(state, client_id, id).await {
+                Ok(explored) => explored,
+                Err(e) => return Some(e),
+            };
+            match state
+                .core
+                .grid_map_service
+                .movement_range(
+                    id,
+                    request.from.into(),
+                    request.movement,
+                    request.diagonal_rule.map(Into::into),
+                    request.occupied.into_iter().map(Into::into).collect(),
+                    explored,
+                )
+                .await
+            {
+                Ok(tiles) => Some(ServerMessage::MovementRange {
+                    grid_map_id,
+                    tiles: tiles.into_iter().map(ReachableTileDto::from).collect(),
+                }),
+                Err(e) => Some(grid_map_error(e)),
+            }
+        }
+
+        ClientMessage::CheckCover { grid_map_id, request } => {
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let explored = match explored_tiles(state, client_id, id).await {
+                Ok(explored) => explored,
+                Err(e) => return Some(e),
+            };
+            match state
+                .core
+                .grid_map_service
+                .cover(id, request.attacker.into(), request.target.into(), explored)
+                .await
+            {
+                Ok(cover) => Some(ServerMessage::CoverChecked {
+                    grid_map_id,
+                    attacker: request.attacker,
+                    target: request.target,
+                    result: cover.into(),
+                }),
+                Err(e) => Some(grid_map_error(e)),
+            }
+        }
+
+        ClientMessage::LookAround { grid_map_id, pc_id, x, y } => {
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let (session_id, is_dm, pc_id) = match vision_context(state, client_id, pc_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            let Some(pc_id) = pc_id else {
+                return Some(ServerMessage::Error {
+                    code: "MISSING_PC_ID".to_string(),
+                    message: "Say which PC looks around".to_string(),
+                });
+            };
+            let from = match x.zip(y).filter(|_| is_dm) {
+                Some(tile) => tile,
+                None => match state.game.grid_token_service.pc_token(id, pc_id).await {
+                    Ok(Some(token)) => token.position(),
+                    Ok(None) => {
+                        return Some(ServerMessage::Error {
+                            code: "NO_TOKEN".to_string(),
+                            message: "The PC has no token on this map".to_string(),
+                        });
+                    }
+                    Err(e) => return Some(token_error(e)),
+                },
+            };
+            match state
+                .game
+                .vision_service
+                .look(session_id, id, pc_id, from)
+                .await
+            {
+                // The PC's player has already been sent the view
+                Ok(view) if is_dm => Some(ServerMessage::MapView { view }),
+                Ok(_) => None,
+                Err(e) => Some(vision_error(e)),
+            }
+        }
+
+        ClientMessage::GetMapView { grid_map_id, pc_id } => {
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let (session_id, _, pc_id) = match vision_context(state, client_id, pc_id).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            match pc_id {
+                Some(pc_id) => match state.game.vision_service.view(session_id, id, pc_id).await {
+                    Ok(view) => Some(ServerMessage::MapView { view }),
+                    Err(e) => Some(vision_error(e)),
+                },
+                None => match state.core.grid_map_service.get_grid_map(id).await {
+                    Ok(Some(grid_map)) => Some(ServerMessage::FullMap {
+                        grid_map: GridMapDto::from(grid_map),
+                    }),
+                    Ok(None) => Some(ServerMessage::Error {
+                        code: "GRID_MAP_NOT_FOUND".to_string(),
+                        message: "Grid map not found".to_string(),
+                    }),
+                    Err(e) => Some(grid_map_error(e)),
+                },
+            }
+        }
+
+        ClientMessage::GetTokens { grid_map_id } => {
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let is_dm = state.async_session_port.is_client_dm(&client_id.to_string()).await;
+            match state.game.grid_token_service.list(id, is_dm).await {
+                Ok(tokens) => Some(ServerMessage::Tokens {
+                    grid_map_id,
+                    tokens: tokens.into_iter().map(GridTokenDto::from).collect(),
+                }),
+                Err(e) => Some(token_error(e)),
+            }
+        }
+
+        ClientMessage::PlaceToken { grid_map_id, request } => {
+            if !state.async_session_port.is_client_dm(&client_id.to_string()).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can place tokens".to_string(),
+                });
+            }
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let place = match request.into_place() {
+                Ok(place) => place,
+                Err(_) => {
+                    return Some(ServerMessage::Error {
+                        code: "INVALID_TOKEN_SUBJECT".to_string(),
+                        message: "Invalid token subject ID format".to_string(),
+                    })
+                }
+            };
+            // Everyone in the session is told of the new token
+            match state.game.grid_token_service.place(id, place).await {
+                Ok(_) => None,
+                Err(e) => Some(token_error(e)),
+            }
+        }
+
+        ClientMessage::MoveToken { token_id, request } => {
+            let token_id = match grid_token_context(state, client_id, &token_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let (_, is_dm, pc_id) = match vision_context(state, client_id, None).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            let mover = match pc_id {
+                Some(pc_id) if !is_dm => TokenMover::Player(pc_id),
+                _ => TokenMover::Dm,
+            };
+            match state
+                .game
+                .grid_token_service
+                .move_token(
+                    token_id,
+                    mover,
+                    (request.x, request.y),
+                    request.facing.map(Facing::from),
+                )
+                .await
+            {
+                // The session has been told of the move, unless the token is
+                // hidden and the mover a player
+                Ok((token, path)) if token.hidden && !is_dm => Some(ServerMessage::TokenMoved {
+                    moved: TokenMovedDto::new(&token, path),
+                }),
+                Ok(_) => None,
+                Err(e) => Some(token_error(e)),
+            }
+        }
+
+        ClientMessage::UpdateToken { token_id, request } => {
+            if !state.async_session_port.is_client_dm(&client_id.to_string()).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can change tokens".to_string(),
+                });
+            }
+            let token_id = match grid_token_context(state, client_id, &token_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            match state.game.grid_token_service.update(token_id, request.into()).await {
+                Ok(_) => None,
+                Err(e) => Some(token_error(e)),
+            }
+        }
+
+        ClientMessage::RemoveToken { token_id } => {
+            if !state.async_session_port.is_client_dm(&client_id.to_string()).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can remove tokens".to_string(),
+                });
+            }
+            let token_id = match grid_token_context(state, client_id, &token_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            match state.game.grid_token_service.remove(token_id).await {
+                Ok(_) => None,
+                Err(e) => Some(token_error(e)),
+            }
+        }
+
+        ClientMessage::LockMovement { grid_map_id, locked } => {
+            if !state.async_session_port.is_client_dm(&client_id.to_string()).await {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can lock movement".to_string(),
+                });
+            }
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            match state.game.grid_token_service.set_locked(id, locked).await {
+                Ok(()) => None,
+                Err(e) => Some(token_error(e)),
+            }
+        }
+
+        ClientMessage::PreviewAreaEffect { grid_map_id, template } => {
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let is_dm = state.async_session_port.is_client_dm(&client_id.to_string()).await;
+            match state.game.area_effect_service.resolve(id, &template.into()).await {
+                Ok(area) => Some(ServerMessage::AreaPreview {
+                    effect: AreaEffectDto::new(&area, is_dm),
+                }),
+                Err(e) => Some(area_effect_error(e)),
+            }
+        }
+
+        ClientMessage::ApplyAreaEffect { grid_map_id, request } => {
+            let (session_id, is_dm, _) = match vision_context(state, client_id, None).await {
+                Ok(context) => context,
+                Err(e) => return Some(e),
+            };
+            if !is_dm {
+                return Some(ServerMessage::Error {
+                    code: "NOT_AUTHORIZED".to_string(),
+                    message: "Only the DM can set off area effects".to_string(),
+                });
+            }
+            let id = match grid_map_query_context(state, client_id, &grid_map_id).await {
+                Ok(id) => id,
+                Err(e) => return Some(e),
+            };
+            let saving_throw = match request.saving_throw_challenge_id.as_deref() {
+                Some(challenge_id) => match uuid::Uuid::parse_str(challenge_id) {
+                    Ok(uuid) => Some(ChallengeId::from_uuid(uuid)),
+                    Err(_) => {
+                        return Some(ServerMessage::Error {
+                            code: "INVALID_CHALLENGE_ID".to_string(),
+                            message: "Invalid challenge ID format".to_string(),
+                        })
+                    }
+                },
+                None => None,
+            };
+            // The service shows the effect to the session itself
+            match state
+                .game
+                .area_effect_service
+                .apply(session_id, id, &request.template.into(), &request.description, saving_throw)
+                .await
+            {
+                Ok(_) => None,
+                Err(e) => Some(area_effect_error(e)),
+            }
+        }
+
+        // =========================================================================
+        // Phase 23C: Navigation
+        // =========================================================================
+
+        ClientMessage::SelectPlayerCharacter { pc_id } => {
+            tracing::info!("Player selecting PC {}", pc_id);
+
+            let client_id_str = client_id.to_string();
+            let session_id = match state.async_session_port.get_client_session(&client_id_str).await {
+                Some(sid) => sid,
+                None => {
+                    return Some(ServerMessage::Error {
+                        code: "NO_SESSION".to_string(),
+                        message: "Client is not in a session".to_string(),
+                    });
+                }
+            };
+
+            // Parse PC ID
+            let pc_uuid = match uuid::Uuid::parse_str(&pc_id) {
+                Ok(uuid) => crate::domain::value_objects::PlayerCharacterId::from_uuid(uuid),
+                Err(_) => {
+                    return Some(ServerMessage::Error {
+                        code: "INVALID_PC_ID".to_string(),
+                        message: "Invalid PC ID format".to_string(),
+                    });
+                }
+            };
+
+            // Get PC details
+            let pc = match state.repository.player_characters().get(pc_uuid).await {
+                Ok(Some(pc)) => pc,
+                Ok(None) => {
+                    return Some(ServerMessage::Error {
+                        code: "PC_NOT_FOUND".to_string(),
+                        message: "Player character not found".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Some(ServerMessage::Error {
+                        code: "DATABASE_ERROR".to_string(),
+                        message: format!("Failed to fetch PC: {}", e),
+                    });
+                }
+            };
+
+            // Send PcSelected response
+            Some(ServerMessage::PcSelected {
+                pc_id: pc_id.clone(),
+                pc_name: pc.name.clone(),
+                location_id: pc.current_location_id.to_string(),
+                region_id: pc.current_region_id.map(|r| r.to_string()),
+            })
+        }
+
+        ClientMessage::MoveToRegion { pc_id, region_id } => {
+            tracing::info!("PC {} moving to region {}", pc_id, region_id);
+
+            let client_id_str = client_id.to_string();
+            let session_id = match state.async_session_port.get_client_session(&client_id_str).await {
+                Some(sid) => sid,
+                None => {
+                    return Some(ServerMessage::Error {
+                        code: "NO_SESSION".to_string(),
+                        message: "Client is not in a session".to_string(),
+                    });
+                }
+            };
+
+            // Parse IDs
+            let pc_uuid = match uuid::Uuid::parse_str(&pc_id) {
+                Ok(uuid) => crate::domain::value_objects::PlayerCharacterId::from_uuid(uuid),
+                Err(_) => {
+                    return Some(ServerMessage::Error {
+                        code: "INVALID_PC_ID".to_string(),
+                        message: "Invalid PC ID format".to_string(),
+                    });
+                }
+            };
+            let region_uuid = match uuid::Uuid::parse_str(&region_id) {
+                Ok(uuid) => crate::domain::value_objects::RegionId::from_uuid(uuid),
+                Err(_) => {
+                    return Some(ServerMessage::Error {
+                        code: "INVALID_REGION_ID".to_string(),
+                        message: "Invalid region ID format".to_string(),
+                    });
+                }
+            };
+
+            // Get current PC to check if movement is valid
+            let pc = match state.repository.player_characters().get(pc_uuid).await {
+                Ok(Some(pc)) => pc,
+                Ok(None) => {
+                    return Some(ServerMessage::Error {
+                        code: "PC_NOT_FOUND".to_string(),
+                        message: "Player character not found".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Some(ServerMessage::Error {
+                        code: "DATABASE_ERROR".to_string(),
+                        message: format!("Failed to fetch PC: {}", e),
+                    });
+                }
+            };
+
+            // Check if movement is allowed (check for locked connections)
+            if let Some(current_region_id) = pc.current_region_id {
+                let connections = match state.repository.regions().get_connections(current_region_id).await {
+                    Ok(conns) => conns,
+                    Err(e) => {
+                        return Some(ServerMessage::Error {
+                            code: "DATABASE_ERROR".to_string(),
+                            message: format!("Failed to fetch connections: {}", e),
+                        });
+                    }
+                };
+
+                // Find the connection to target region
+                if let Some(conn) = connections.iter().find(|c| c.to_region == region_uuid) {
+                    if conn.is_locked {
+                        return Some(ServerMessage::MovementBlocked {
+                            pc_id: pc_id.clone(),
+                            reason: conn.lock_description.clone().unwrap_or_else(|| "The path is locked.".to_string()),
+                        });
+                    }
+                }
+            }
+
+            // Get target region
+            let target_region = match state.repository.regions().get(region_uuid).await {
+                Ok(Some(region)) => region,
+                Ok(None) => {
+                    return Some(ServerMessage::Error {
+                        code: "REGION_NOT_FOUND".to_string(),
+                        message: "Target region not found".to_string(),
+                    });
+                }
+                Err(e) => {
+                    return Some(ServerMessage::Error {
+                        code: "DATABASE_ERROR".to_string(),
+                        message: format!("Failed to fetch region: {}", e),
+                    });
+                }
+            };
+
+            // Update PC position
+            if let Err(e) = state.repository.player_characters()
+                .update_position(pc_uuid, target_region.location_id, Some(region_uuid))
+                .await
+            {
+                return Some(ServerMessage::Error {
+                    code: "UPDATE_ERROR".to_string(),
+                    message: format!("Failed to update position: {}", e),
+                });
+            }
+
+            // Get location for name
+            let location = state.repository.locations().get(target_region.location_id).await
+                .ok().flatten();
+            let location_name = location.as_ref().map(|l| l.name.clone()).unwrap_or_default();
+            let backdrop = target_region.backdrop_asset.clone()
+                .or_else(|| location.as_ref().and_then(|l| l.backdrop_asset.clone()));
+
+            // Get NPCs present (simple rule-based)
+            let game_time = {
+                let sessions = state.sessions.read().await;
+                sessions.get_session(session_id)
+                    .map(|s| s.game_time().clone())
+                    .unwrap_or_default()
+            };
+
+            let npc_relationships = state.repository.characters()
+                .get_npcs_related_to_region(region_uuid)
+                .await
+                .unwrap_or_default();
+
+            let time_of_day = game_time.time_of_day();
+            let npcs_present: Vec<messages::NpcPresenceData> = npc_relationships
+                .into_iter()
+                .filter_map(|(character, rel_type)| {
+                    let is_present = rel_type.is_npc_present(time_of_day);
+                    if is_present {
+                        Some(messages::NpcPresenceData {
+                            character_id: character.id.to_string(),
+                            name: character.name,
+                            sprite_asset: character.sprite_asset,
+                            portrait_asset: character.portrait_asset,
+                        })
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            // Get navigation options
+            let connections = state.repository.regions().get_connections(region_uuid).await.unwrap_or_default();
+            let exits = state.repository.regions().get_exits(region_uuid).await.unwrap_or_default();
+
+            let mut connected_regions = Vec::new();
+            for conn in connections {
+                if let Ok(Some(target)) = state.repository.regions().get(conn.to_region).await {
+                    connected_regions.push(messages::NavigationTarget {
+                        region_id: conn.to_region.to_string(),
+                        name: target.name,
+                        is_locked: conn.is_locked,
+                        lock_description: conn.lock_description,
+                    });
+                }
+            }
+
+            let mut exit_targets = Vec::new();
+            for exit in exits {
+                if let Ok(Some(target_loc)) = state.repository.locations().get(exit.to_location).await {
+                    exit_targets.push(messages::NavigationExit {
+                        location_id: exit.to_location.to_string(),
+                        location_name: target_loc.name,
+                        arrival_region_id: exit.arrival_region_id.to_string(),
+                        description: exit.description,
+                    });
+                }
+            }
+
+            Some(ServerMessage::SceneChanged {
+                pc_id,
+                region: messages::RegionData {
+                    id: region_uuid.to_string(),
+                    name: target_region.name,
+                    location_id: target_region.location_id.to_string(),
+                    location_name,
+                    backdrop_asset: backdrop,
+                    atmosphere: target_region.atmosphere,
+                },
+                npcs_present,
+                navigation: messages::NavigationData {
+                    connected_regions,
+                    exits: exit_targets,
+                },
+            })
+        }
+
+        ClientMessage::ExitToLocation { pc_id, location_id, arrival_region_id } => {
+            tracing::info!("PC {} exiting to location {}", pc_id, location_id);
+
+            let client_id_str = client_id.to_string();
+            let session_id = match state.async_session_port.get_client_session(&client_id_str).await {
+                Some(sid) => sid,
+                None => {
+                    return Some(ServerMessage::Error {
+                        code: "NO_SESSION".to_string(),
+                        message: "Client is not in a session".to_string(),
+                    });
+                }
+            };
+
+            // Parse IDs
+            let pc_uuid = match uuid::Uuid::parse_str(&pc_id) {
+                Ok(uuid) => crate::domain::value_objects::PlayerCharacterId::from_uuid(uuid),
+                Err(_) => {
+                    return Some(ServerMessage::Error {
+                        code: "INVALID_PC_ID".to_string(),
+                        message: "Invalid PC ID format".to_string(),
+                    });
+                }
+            };
+            let location_uuid = match uuid::Uuid::parse_str(&location_id) {
+                Ok(uuid) => crate::domain::value_objects::LocationId::from_uuid(uuid),
                 Err(_) => {
                     return Some(ServerMessage::Error {
                         code: "INVALID_LOCATION_ID".to_string(),
@@ -1953,6 +2920,280 @@ async fn handle_message(
     }
 }
 
+/// The session a client fights in, the PC it plays (None for the DM) and
+/// the session's game time
+async fn combat_context(
+    state: &AppState,
+    client_id: ClientId,
+) -> Result<(SessionId, Option<PlayerCharacterId>, Option<String>), ServerMessage> {
+    let client_id_str = client_id.to_string();
+    let session_id = state
+        .async_session_port
+        .get_client_session(&client_id_str)
+        .await
+        .ok_or_else(|| ServerMessage::Error {
+            code: "NO_SESSION".to_string(),
+            message: "Client is not in a session".to_string(),
+        })?;
+
+    let player_pc = if state.async_session_port.is_client_dm(&client_id_str).await {
+        None
+    } else {
+        let user_id = state
+            .async_session_port
+            .get_client_user_id(&client_id_str)
+            .await
+            .unwrap_or_default();
+        let pc = state
+            .player
+            .player_character_service
+            .get_pc_by_user_and_session(&user_id, session_id)
+            .await
+            .map_err(|e| ServerMessage::Error {
+                code: "DATABASE_ERROR".to_string(),
+                message: format!("Failed to fetch PC: {}", e),
+            })?
+            .ok_or_else(|| ServerMessage::Error {
+                code: "NOT_AUTHORIZED".to_string(),
+                message: "You have no character in this fight".to_string(),
+            })?;
+        Some(pc.id)
+    };
+
+    let game_time = {
+        let sessions = state.sessions.read().await;
+        sessions.get_session(session_id).map(|session| session.display_game_time())
+    };
+    Ok((session_id, player_pc, game_time))
+}
+
+/// Check that a player's character is the one whose turn it is; the DM may
+/// act on any turn
+async fn check_acting_pc(
+    state: &AppState,
+    session_id: SessionId,
+    player_pc: Option<PlayerCharacterId>,
+) -> Result<(), ServerMessage> {
+    let Some(pc_id) = player_pc else {
+        return Ok(());
+    };
+    let acting = state
+        .game
+        .combat_service
+        .encounter(session_id)
+        .await
+        .and_then(|encounter| encounter.current().map(|current| current.who));
+    if acting == Some(CombatantRef::Pc(pc_id)) {
+        Ok(())
+    } else {
+        Err(ServerMessage::Error {
+            code: "NOT_YOUR_TURN".to_string(),
+            message: "It is not your character's turn".to_string(),
+        })
+    }
+}
+
+/// Map a combat service error, reporting refused operations as such
+fn combat_error(e: anyhow::Error) -> ServerMessage {
+    let code = if e.downcast_ref::<CombatError>().is_some() {
+        "COMBAT_REFUSED"
+    } else {
+        "COMBAT_ERROR"
+    };
+    ServerMessage::Error {
+        code: code.to_string(),
+        message: e.to_string(),
+    }
+}
+
+/// Check that a client is in a session and parse the grid map it asks
+/// about, which must belong to the session's world
+async fn grid_map_query_context(
+    state: &AppState,
+    client_id: ClientId,
+    grid_map_id: &str,
+) -> Result<GridMapId, ServerMessage> {
+    let client_id_str = client_id.to_string();
+    let Some(session_id) = state.async_session_port.get_client_session(&client_id_str).await else {
+        return Err(ServerMessage::Error {
+            code: "NO_SESSION".to_string(),
+            message: "Client is not in a session".to_string(),
+        });
+    };
+    let id = uuid::Uuid::parse_str(grid_map_id)
+        .map(GridMapId::from_uuid)
+        .map_err(|_| ServerMessage::Error {
+            code: "INVALID_GRID_MAP_ID".to_string(),
+            message: "Invalid grid map ID format".to_string(),
+        })?;
+
+    let grid_map = state
+        .core
+        .grid_map_service
+        .get_grid_map(id)
+        .await
+        .map_err(grid_map_error)?;
+    let world_id = state.async_session_port.get_session_world_id(session_id).await;
+    match grid_map {
+        Some(grid_map) if Some(grid_map.world_id) == world_id => Ok(id),
+        _ => Err(ServerMessage::Error {
+            code: "GRID_MAP_NOT_FOUND".to_string(),
+            message: "Grid map not found".to_string(),
+        }),
+    }
+}
+
+/// The tiles of a grid map a client's queries may run over: everything for
+/// the DM, only what their own character has explored for a player
+async fn explored_tiles(
+    state: &AppState,
+    client_id: ClientId,
+    grid_map_id: GridMapId,
+) -> Result<Option<HashSet<GridPosition>>, ServerMessage> {
+    match vision_context(state, client_id, None).await? {
+        (_, true, _) => Ok(None),
+        (session_id, false, Some(pc_id)) => Ok(Some(
+            state.game.vision_service.explored(session_id, grid_map_id, pc_id).await,
+        )),
+        (_, false, None) => Ok(Some(HashSet::new())),
+    }
+}
+
+/// Check that a client is in a session and parse the grid token it asks about
+async fn grid_token_context(
+    state: &AppState,
+    client_id: ClientId,
+    token_id: &str,
+) -> Result<GridTokenId, ServerMessage> {
+    if state
+        .async_session_port
+        .get_client_session(&client_id.to_string())
+        .await
+        .is_none()
+    {
+        return Err(ServerMessage::Error {
+            code: "NO_SESSION".to_string(),
+            message: "Client is not in a session".to_string(),
+        });
+    }
+    uuid::Uuid::parse_str(token_id)
+        .map(GridTokenId::from_uuid)
+        .map_err(|_| ServerMessage::Error {
+            code: "INVALID_TOKEN_ID".to_string(),
+            message: "Invalid grid token ID format".to_string(),
+        })
+}
+
+/// The session a client looks at grid maps in, whether it is the DM's, and
+/// the PC whose view it wants: a player's own, or the one the DM names
+async fn vision_context(
+    state: &AppState,
+    client_id: ClientId,
+    pc_id: Option<String>,
+) -> Result<(SessionId, bool, Option<PlayerCharacterId>), ServerMessage> {
+    let client_id_str = client_id.to_string();
+    let session_id = state
+        .async_session_port
+        .get_client_session(&client_id_str)
+        .await
+        .ok_or_else(|| ServerMessage::Error {
+            code: "NO_SESSION".to_string(),
+            message: "Client is not in a session".to_string(),
+        })?;
+    let requested = match pc_id {
+        Some(pc_id) => Some(
+            uuid::Uuid::parse_str(&pc_id)
+                .map(PlayerCharacterId::from_uuid)
+                .map_err(|_| ServerMessage::Error {
+                    code: "INVALID_PC_ID".to_string(),
+                    message: "Invalid PC ID format".to_string(),
+                })?,
+        ),
+        None => None,
+    };
+
+    if state.async_session_port.is_client_dm(&client_id_str).await {
+        return Ok((session_id, true, requested));
+    }
+
+    let user_id = state
+        .async_session_port
+        .get_client_user_id(&client_id_str)
+        .await
+        .unwrap_or_default();
+    let pc = state
+        .player
+        .player_character_service
+        .get_pc_by_user_and_session(&user_id, session_id)
+        .await
+        .map_err(|e| ServerMessage::Error {
+            code: "DATABASE_ERROR".to_string(),
+            message: format!("Failed to fetch PC: {}", e),
+        })?
+        .ok_or_else(|| ServerMessage::Error {
+            code: "NO_PC".to_string(),
+            message: "You have no character in this session".to_string(),
+        })?;
+    if requested.is_some_and(|requested| requested != pc.id) {
+        return Err(ServerMessage::Error {
+            code: "NOT_AUTHORIZED".to_string(),
+            message: "Players can only see through their own character's eyes".to_string(),
+        });
+    }
+    Ok((session_id, false, Some(pc.id)))
+}
+
+/// Map a vision service error, reporting characters outside the session as
+/// such
+fn vision_error(e: anyhow::Error) -> ServerMessage {
+    if e.downcast_ref::<VisionError>().is_some() {
+        return ServerMessage::Error {
+            code: "VISION_REFUSED".to_string(),
+            message: e.to_string(),
+        };
+    }
+    grid_map_error(e)
+}
+
+/// Map a grid token service error, reporting refused placements and moves
+/// as such
+fn token_error(e: anyhow::Error) -> ServerMessage {
+    let code = match e.downcast_ref::<TokenError>() {
+        Some(TokenError::NotFound(_)) => "TOKEN_NOT_FOUND",
+        Some(TokenError::NotYours(_) | TokenError::NotYourTurn) => "NOT_AUTHORIZED",
+        Some(TokenError::Placement(_)) => "TOKEN_REFUSED",
+        None => return grid_map_error(e),
+    };
+    ServerMessage::Error {
+        code: code.to_string(),
+        message: e.to_string(),
+    }
+}
+
+/// Map an area effect error, reporting saving throws that cannot be called
+fn area_effect_error(e: anyhow::Error) -> ServerMessage {
+    match e.downcast_ref::<SavingThrowError>() {
+        Some(_) => ServerMessage::Error {
+            code: "SAVING_THROW_REFUSED".to_string(),
+            message: e.to_string(),
+        },
+        None => token_error(e),
+    }
+}
+
+/// Map a grid map service error, reporting positions off the map as such
+fn grid_map_error(e: anyhow::Error) -> ServerMessage {
+    let code = if e.downcast_ref::<GridMapError>().is_some() {
+        "GRID_MAP_REFUSED"
+    } else {
+        "GRID_MAP_ERROR"
+    };
+    ServerMessage::Error {
+        code: code.to_string(),
+        message: e.to_string(),
+    }
+}
+
 // Re-export message types from the dedicated messages module
 pub mod messages;
 pub use messages::{
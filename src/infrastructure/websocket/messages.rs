@@ -7,8 +7,16 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::application::dto::{ChallengeSuggestionInfo, NarrativeEventSuggestionInfo};
-use crate::domain::value_objects::{ApprovalDecision, ProposedToolInfo};
+use crate::application::dto::{
+    AdvancementModeDto, AreaEffectDto, AreaEffectRequestDto, AreaTemplateDto, ChallengeOddsDto, CombatNpcRequestDto, CombatOutcomeDto, EncounterDto, ChallengeSuggestionInfo, ComplexChallengeProgressDto, EndedConditionDto,
+    CoverRequestDto, CoverResponseDto, GridMapDto, GridPathDto, GridPositionDto, GridTokenDto,
+    MapViewDto, MoveTokenRequestDto, MovementRangeRequestDto, PlaceTokenRequestDto, TokenMovedDto,
+    UpdateTokenRequestDto, NarrativeEventSuggestionInfo, OpponentDto,
+    OpposedRollDto, PathRequestDto, ReachableTileDto, ResourceChangeDto,
+    ResourceOperationDto, RestOutcomeDto,
+};
+use crate::domain::entities::OutcomeType;
+use crate::domain::value_objects::{ApprovalDecision, ProposedToolInfo, RollMode};
 
 /// Messages from client (Player) to server (Engine)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,17 +49,43 @@ pub enum ClientMessage {
     ChallengeRoll {
         challenge_id: String,
         roll: i32,
+        /// Advantage, disadvantage or bonus/penalty dice (recorded with the result)
+        #[serde(default)]
+        roll_mode: RollMode,
+        /// Skill chosen for this round of a complex challenge
+        #[serde(default)]
+        skill_id: Option<String>,
     },
     /// Player submits a challenge roll with dice input (formula or manual)
     ChallengeRollInput {
         challenge_id: String,
         /// Dice input - either "formula" with dice string, or "manual" with result
         input_type: DiceInputType,
+        /// Advantage, disadvantage or bonus/penalty dice applied to the formula
+        #[serde(default)]
+        roll_mode: RollMode,
+        /// Skill chosen for this round of a complex challenge
+        #[serde(default)]
+        skill_id: Option<String>,
+    },
+    /// Roll dice outside of a challenge (ad-hoc or random table roll)
+    RollDice {
+        /// Dice formula (e.g., "2d6+1", "1d100")
+        formula: String,
+        /// What the roll is for (e.g., "Damage", "Wandering monsters")
+        #[serde(default)]
+        label: Option<String>,
+        /// Whether this is a roll on a random table
+        #[serde(default)]
+        is_table_roll: bool,
     },
     /// DM triggers a challenge manually
     TriggerChallenge {
         challenge_id: String,
         target_character_id: String,
+        /// Who the character rolls against, required for opposed challenges
+        #[serde(default)]
+        opponent: Option<OpponentDto>,
     },
     /// DM approves/rejects/modifies a suggested challenge
     ChallengeSuggestionDecision {
@@ -218,6 +252,212 @@ pub enum ClientMessage {
         /// Number of hours to advance
         hours: u32,
     },
+
+    // =========================================================================
+    // Sheet Resources
+    // =========================================================================
+
+    /// Spend, restore or reset a resource on a PC's sheet (DM or owning player)
+    ModifyResource {
+        pc_id: String,
+        /// Sheet field ID (e.g. "HP", "SPELL_SLOTS")
+        field_id: String,
+        operation: ResourceOperationDto,
+        /// Why it changed, for the story timeline
+        #[serde(default)]
+        reason: Option<String>,
+    },
+
+    /// DM has characters take one of the rule system's rests
+    TakeRest {
+        /// Rest type ID (e.g. "long_rest")
+        rest_type: String,
+        /// Characters who rest; everyone in the world if empty
+        #[serde(default)]
+        pc_ids: Vec<String>,
+    },
+
+    // =========================================================================
+    // Advancement
+    // =========================================================================
+
+    /// DM awards experience or milestones to characters
+    AwardExperience {
+        /// Characters awarded; everyone in the world if empty
+        #[serde(default)]
+        pc_ids: Vec<String>,
+        kind: AdvancementModeDto,
+        amount: u32,
+        /// Why it was awarded, shown to the players
+        #[serde(default)]
+        reason: Option<String>,
+    },
+
+    /// DM approves the levels a PC has earned
+    ApproveLevelUp { pc_id: String },
+
+    // =========================================================================
+    // Combat
+    // =========================================================================
+
+    /// DM starts a fight in the session
+    StartCombat {
+        #[serde(default)]
+        description: String,
+        /// Where the fight happens; the first PC's location if not given
+        #[serde(default)]
+        location_id: Option<String>,
+        /// PCs who fight; everyone in the session if empty
+        #[serde(default)]
+        pc_ids: Vec<String>,
+        #[serde(default)]
+        npcs: Vec<CombatNpcRequestDto>,
+    },
+
+    /// Roll initiative (DM for anyone, a player for their own PC)
+    RollInitiative {
+        /// Combatant to roll for; the DM rolls for everyone still waiting if
+        /// not given, a player for their own PC
+        #[serde(default)]
+        combatant_id: Option<String>,
+        /// A total rolled at the table, modifier included
+        #[serde(default)]
+        total: Option<i32>,
+    },
+
+    /// End the current turn (DM, or the player whose PC is acting)
+    EndTurn,
+
+    /// The acting combatant delays its turn (DM, or the acting PC's player)
+    DelayTurn,
+
+    /// The acting combatant readies an action and ends its turn (DM, or
+    /// the acting PC's player)
+    ReadyAction {
+        /// What the action waits for
+        trigger: String,
+    },
+
+    /// A delaying combatant steps back in, or a readied action is triggered
+    /// (DM, or the combatant's player)
+    CombatantAct { combatant_id: String },
+
+    /// DM damages a combatant; a negative amount heals
+    DamageCombatant {
+        combatant_id: String,
+        amount: i32,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+
+    /// DM takes a combatant out of the fight
+    RemoveCombatant {
+        combatant_id: String,
+        /// Whether it fled rather than fell
+        #[serde(default)]
+        fled: bool,
+    },
+
+    /// DM ends the fight
+    EndCombat {
+        /// How it ended; worked out from who is left standing if not given
+        #[serde(default)]
+        outcome: Option<CombatOutcomeDto>,
+    },
+
+    // =========================================================================
+    // Grid Maps
+    // =========================================================================
+
+    /// Find the cheapest route between two tiles of a grid map
+    FindPath {
+        grid_map_id: String,
+        #[serde(flatten)]
+        request: PathRequestDto,
+    },
+
+    /// List the tiles reachable from a tile of a grid map
+    GetMovementRange {
+        grid_map_id: String,
+        #[serde(flatten)]
+        request: MovementRangeRequestDto,
+    },
+
+    /// Check whether an attacker can see a target, and the target's cover
+    CheckCover {
+        grid_map_id: String,
+        #[serde(flatten)]
+        request: CoverRequestDto,
+    },
+
+    /// A PC looks around from its token on a grid map, lifting the fog there
+    /// (a player for their own PC, the DM for any)
+    LookAround {
+        grid_map_id: String,
+        /// Required from the DM; a player always looks with their own PC
+        #[serde(default)]
+        pc_id: Option<String>,
+        /// Tile to look from instead of the PC's token; only the DM may
+        /// choose one, and players' are ignored
+        #[serde(default)]
+        x: Option<u32>,
+        #[serde(default)]
+        y: Option<u32>,
+    },
+
+    /// Get a grid map as the client may see it: a player gets what their PC
+    /// has seen, the DM the full map or, given a PC, that PC's view
+    GetMapView {
+        grid_map_id: String,
+        #[serde(default)]
+        pc_id: Option<String>,
+    },
+
+    /// List the tokens on a grid map (hidden ones for the DM only)
+    GetTokens { grid_map_id: String },
+
+    /// DM puts a token on a grid map
+    PlaceToken {
+        grid_map_id: String,
+        #[serde(flatten)]
+        request: PlaceTokenRequestDto,
+    },
+
+    /// Move a token along the cheapest route to a tile (a player their own
+    /// PC's, the DM any)
+    MoveToken {
+        token_id: String,
+        #[serde(flatten)]
+        request: MoveTokenRequestDto,
+    },
+
+    /// DM changes a token's name, size, facing or visibility
+    UpdateToken {
+        token_id: String,
+        #[serde(flatten)]
+        request: UpdateTokenRequestDto,
+    },
+
+    /// DM takes a token off its map
+    RemoveToken { token_id: String },
+
+    /// DM locks player movement on a grid map to each PC's turn, or lifts
+    /// the lock
+    LockMovement { grid_map_id: String, locked: bool },
+
+    /// Preview the tiles and tokens an area of effect would catch
+    PreviewAreaEffect {
+        grid_map_id: String,
+        template: AreaTemplateDto,
+    },
+
+    /// DM sets off an area of effect, calling for saving throws from the
+    /// player characters caught
+    ApplyAreaEffect {
+        grid_map_id: String,
+        #[serde(flatten)]
+        request: AreaEffectRequestDto,
+    },
 }
 
 /// Messages from server (Engine) to client (Player)
@@ -303,6 +543,9 @@ pub enum ServerMessage {
         /// Human-readable hint about the rule system (e.g., "Roll d20, add your Persuasion modifier")
         #[serde(default)]
         rule_system_hint: Option<String>,
+        /// Outcome odds (only sent to the DM)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        odds: Option<ChallengeOddsDto>,
     },
     /// Challenge result broadcast to all
     ChallengeResolved {
@@ -313,6 +556,12 @@ pub enum ServerMessage {
         modifier: i32,
         total: i32,
         outcome: String,
+        /// Outcome tier reached under the world's rule system
+        #[serde(default)]
+        outcome_type: Option<OutcomeType>,
+        /// How far the roll beat (positive) or missed (negative) the success line
+        #[serde(default)]
+        margin: Option<i32>,
         outcome_description: String,
         /// Roll breakdown string (e.g., "1d20(14) + 3 = 17" or "Manual: 18")
         #[serde(default)]
@@ -320,6 +569,33 @@ pub enum ServerMessage {
         /// Individual dice results if rolled with formula
         #[serde(default)]
         individual_rolls: Option<Vec<i32>>,
+        /// Roll log entry for the roll (absent for manual results)
+        #[serde(default)]
+        roll_id: Option<String>,
+        /// The opponent's roll for opposed checks
+        #[serde(default)]
+        opposed: Option<OpposedRollDto>,
+    },
+    /// Progress on a complex challenge, broadcast to all after each roll
+    ComplexChallengeProgress {
+        progress: ComplexChallengeProgressDto,
+    },
+    /// Result of an ad-hoc or table roll, broadcast to all
+    DiceRolled {
+        /// Roll log entry for the roll
+        #[serde(default)]
+        roll_id: Option<String>,
+        /// User ID of whoever rolled
+        roller: String,
+        /// "ad_hoc" or "table"
+        purpose: String,
+        #[serde(default)]
+        label: Option<String>,
+        formula: String,
+        total: i32,
+        /// Roll breakdown string (e.g., "2d6(3, 5) + 1 = 9")
+        roll_breakdown: String,
+        individual_rolls: Vec<i32>,
     },
     /// Narrative event has been triggered
     NarrativeEventTriggered {
@@ -421,6 +697,9 @@ pub enum ServerMessage {
         outcome_type: String,
         /// Status: "awaiting_dm_approval"
         status: String,
+        /// Roll log entry the DM can use to verify the roll
+        #[serde(default)]
+        roll_id: Option<String>,
     },
 
     /// Pending challenge outcome for DM approval queue
@@ -443,6 +722,12 @@ pub enum ServerMessage {
         /// Roll breakdown string
         #[serde(default)]
         roll_breakdown: Option<String>,
+        /// Roll log entry the DM can use to verify the roll
+        #[serde(default)]
+        roll_id: Option<String>,
+        /// The opponent's roll for opposed checks
+        #[serde(default)]
+        opposed: Option<OpposedRollDto>,
     },
 
     /// LLM-generated outcome suggestions are ready (sent to DM)
@@ -548,6 +833,156 @@ pub enum ServerMessage {
         /// Whether time is paused
         is_paused: bool,
     },
+
+    // =========================================================================
+    // Sheet Resources
+    // =========================================================================
+
+    /// A resource on a PC's sheet changed (broadcast to all)
+    ResourceChanged {
+        pc_id: String,
+        change: ResourceChangeDto,
+        reason: Option<String>,
+    },
+
+    /// Characters took a rest (broadcast to all)
+    RestTaken { outcome: RestOutcomeDto },
+
+    // =========================================================================
+    // Conditions
+    // =========================================================================
+
+    /// Timed conditions ran out as game time advanced (broadcast to all)
+    ConditionsExpired { conditions: Vec<EndedConditionDto> },
+
+    // =========================================================================
+    // Advancement
+    // =========================================================================
+
+    /// A PC was awarded experience or milestones (broadcast to all)
+    ExperienceAwarded {
+        pc_id: String,
+        character_name: String,
+        /// The award as shown to players, e.g. "250 XP"
+        award: String,
+        #[serde(default)]
+        reason: Option<String>,
+        experience: u32,
+        milestones: u32,
+    },
+
+    /// A PC has earned a level waiting for approval (DM only)
+    LevelUpAvailable {
+        pc_id: String,
+        character_name: String,
+        level: u32,
+        earned_level: u32,
+    },
+
+    /// A PC gained one or more levels (broadcast to all)
+    LevelGained {
+        pc_id: String,
+        character_name: String,
+        level: u32,
+        unlocked_fields: Vec<String>,
+        unlocked_skills: Vec<String>,
+    },
+
+    // =========================================================================
+    // Combat
+    // =========================================================================
+
+    /// A fight started (broadcast to all)
+    CombatStarted { encounter: EncounterDto },
+
+    /// A fight's roster, initiative or hit points changed (broadcast to all)
+    CombatUpdated { encounter: EncounterDto },
+
+    /// The turn passed to another combatant (broadcast to all)
+    CombatTurnChanged {
+        round: u32,
+        combatant_id: String,
+        combatant_name: String,
+        /// Whether this turn began a new round
+        new_round: bool,
+        encounter: EncounterDto,
+    },
+
+    /// A combatant fell or fled (broadcast to all)
+    CombatantDefeated {
+        combatant_id: String,
+        combatant_name: String,
+        fled: bool,
+    },
+
+    /// A fight ended (broadcast to all)
+    CombatEnded {
+        #[serde(default)]
+        outcome: Option<CombatOutcomeDto>,
+        rounds: u32,
+    },
+
+    // =========================================================================
+    // Grid Maps
+    // =========================================================================
+
+    /// The route asked for, or none if the tiles are not connected
+    PathFound {
+        grid_map_id: String,
+        path: Option<GridPathDto>,
+    },
+
+    /// The tiles within reach, with what each costs to reach
+    MovementRange {
+        grid_map_id: String,
+        tiles: Vec<ReachableTileDto>,
+    },
+
+    /// Whether an attacker can see a target, and the target's cover
+    CoverChecked {
+        grid_map_id: String,
+        attacker: GridPositionDto,
+        target: GridPositionDto,
+        #[serde(flatten)]
+        result: CoverResponseDto,
+    },
+
+    /// What a PC has seen of a grid map (sent to its player, or to the DM
+    /// on request)
+    MapView { view: MapViewDto },
+
+    /// A whole grid map, fog lifted (DM only)
+    FullMap { grid_map: GridMapDto },
+
+    /// The tokens on a grid map the client may see
+    Tokens {
+        grid_map_id: String,
+        tokens: Vec<GridTokenDto>,
+    },
+
+    /// A token was placed or changed
+    TokenUpdated { token: GridTokenDto },
+
+    /// A token moved, and the route it took
+    TokenMoved {
+        #[serde(flatten)]
+        moved: TokenMovedDto,
+    },
+
+    /// A token left the map, or was hidden from the players
+    TokenRemoved {
+        grid_map_id: String,
+        token_id: String,
+    },
+
+    /// The DM locked or unlocked player movement on a grid map
+    MovementLocked { grid_map_id: String, locked: bool },
+
+    /// What an area of effect would catch
+    AreaPreview {
+        #[serde(flatten)]
+        effect: AreaEffectDto,
+    },
 }
 
 /// Information about a session participant
@@ -697,6 +1132,14 @@ pub enum ChallengeOutcomeDecisionData {
         #[serde(default)]
         guidance: Option<String>,
     },
+    /// Grant a roll mode after the roll and re-evaluate the outcome
+    GrantRollMode {
+        roll_mode: RollMode,
+    },
+    /// Override an NPC opponent's modifier and re-evaluate the opposed check
+    SetOpponentModifier {
+        modifier: i32,
+    },
 }
 
 /// Outcome branch data for DM selection
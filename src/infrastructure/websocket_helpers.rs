@@ -3,20 +3,25 @@
 //! These functions assist with building prompts and processing queue items
 //! in the WebSocket handler and background workers.
 
-use crate::application::dto::PlayerActionItem;
+use crate::application::dto::{EndedConditionDto, PlayerActionItem};
 use crate::application::ports::outbound::{CharacterRepositoryPort, QueueError};
 use crate::application::services::{
-    ChallengeService, ChallengeServiceImpl, NarrativeEventService, NarrativeEventServiceImpl,
+    ChallengeService, ChallengeServiceImpl, CombatUpdate, NarrativeEventService, NarrativeEventServiceImpl,
     SettingsService, SkillService, SkillServiceImpl,
 };
 use crate::domain::value_objects::{
     ActiveChallengeContext, ActiveNarrativeEventContext, CharacterContext, ConversationTurn,
-    GamePromptRequest, PlayerActionContext, SceneContext,
+    GamePromptRequest, PlayerActionContext, SceneContext, SessionId,
 };
 use crate::infrastructure::session::SessionManager;
+use crate::infrastructure::state::AppState;
+use crate::infrastructure::websocket::messages::ServerMessage;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Who NPC combat turns are queued for
+const COMBAT_ACTOR: &str = "combat";
+
 /// Build a GamePromptRequest from a PlayerActionItem using session context
 pub async fn build_prompt_from_action(
     sessions: &Arc<RwLock<SessionManager>>,
@@ -128,12 +133,27 @@ pub async fn build_prompt_from_action(
         }
     };
 
+    // Conditions change during play, so read them fresh rather than from the snapshot
+    let character_conditions = match character_repo.get(responding_character.id).await {
+        Ok(Some(character)) => character.conditions.iter().map(|c| c.summary()).collect(),
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to fetch conditions for character {}: {}",
+                responding_character.id,
+                e
+            );
+            Vec::new()
+        }
+    };
+
     let character_context = CharacterContext {
         name: responding_character.name.clone(),
         archetype: format!("{:?}", responding_character.current_archetype),
         current_mood: None, // Character mood tracking not yet implemented
         wants: character_wants,
         relationship_to_player: None, // Relationship tracking not yet implemented
+        conditions: character_conditions,
     };
 
     // Get directorial notes
@@ -195,7 +215,9 @@ pub async fn build_prompt_from_action(
                     id: c.id.to_string(),
                     name: c.name,
                     skill_name,
-                    difficulty_display: c.difficulty.display(),
+                    difficulty_display: c
+                        .difficulty
+                        .describe(&world_snapshot.world.rule_system.descriptor_difficulties),
                     description: c.description,
                     trigger_hints: c
                         .trigger_conditions
@@ -259,3 +281,107 @@ pub async fn build_prompt_from_action(
         active_narrative_events,
     })
 }
+
+/// Expire the conditions in a session's world that have run out at its
+/// current game time, and tell everyone in the session which ended
+pub async fn expire_conditions(state: &AppState, session_id: SessionId) {
+    let (world_id, now) = {
+        let sessions = state.sessions.read().await;
+        match sessions.get_session(session_id) {
+            Some(session) => (session.world_id, session.game_time().current),
+            None => return,
+        }
+    };
+
+    let ended = match state.game.condition_service.expire_conditions(world_id, now).await {
+        Ok(ended) => ended,
+        Err(e) => {
+            tracing::warn!("Failed to expire conditions in world {}: {}", world_id, e);
+            return;
+        }
+    };
+    if ended.is_empty() {
+        return;
+    }
+
+    let expired = ServerMessage::ConditionsExpired {
+        conditions: ended.into_iter().map(EndedConditionDto::from).collect(),
+    };
+    if let Ok(msg_json) = serde_json::to_value(&expired) {
+        let _ = state.async_session_port.broadcast_except(session_id, msg_json, "").await;
+    }
+}
+
+/// Follow up a change to a session's fight
+///
+/// A completed round moves the game clock on by a round, and an
+/// LLM-controlled NPC whose turn it is has its action proposed through the
+/// player action queue, so the DM approves it like any other response.
+pub async fn follow_combat_update(state: &AppState, session_id: SessionId, update: &CombatUpdate) {
+    if update.completed_round.is_some() {
+        let game_time = {
+            let mut sessions = state.sessions.write().await;
+            sessions.get_session_mut(session_id).map(|session| {
+                session.advance_time_rounds(1);
+                (
+                    session.display_game_time(),
+                    session.time_of_day().to_string(),
+                    session.is_time_paused(),
+                )
+            })
+        };
+        if let Some((display, time_of_day, is_paused)) = game_time {
+            let time_updated = ServerMessage::GameTimeUpdated {
+                display,
+                time_of_day,
+                is_paused,
+            };
+            if let Ok(msg_json) = serde_json::to_value(&time_updated) {
+                let _ = state.async_session_port.broadcast_except(session_id, msg_json, "").await;
+            }
+        }
+        expire_conditions(state, session_id).await;
+    }
+
+    let Some(turn) = &update.npc_turn else {
+        return;
+    };
+    match state
+        .queues
+        .player_action_queue_service
+        .enqueue_action(
+            session_id,
+            COMBAT_ACTOR.to_string(),
+            None,
+            "combat_turn".to_string(),
+            Some(turn.name.clone()),
+            Some(turn.prompt.clone()),
+        )
+        .await
+    {
+        Ok(action_id) => {
+            tracing::info!(
+                "Proposing {}'s ({}) combat turn in session {}",
+                turn.name,
+                turn.character_id,
+                session_id
+            );
+            let depth = state
+                .queues
+                .player_action_queue_service
+                .depth()
+                .await
+                .unwrap_or(0);
+            let dm_msg = ServerMessage::ActionQueued {
+                action_id: action_id.to_string(),
+                player_name: turn.name.clone(),
+                action_type: "combat_turn".to_string(),
+                queue_depth: depth,
+            };
+            if let Ok(dm_json) = serde_json::to_value(&dm_msg) {
+                let _ = state.async_session_port.send_to_dm(session_id, dm_json).await;
+            }
+        }
+        Err(e) => tracing::error!("Failed to queue {}'s combat turn: {}", turn.name, e),
+    }
+}